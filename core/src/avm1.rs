@@ -11,6 +11,7 @@ mod activation;
 mod callable_value;
 mod clamp;
 mod debug;
+mod debugger;
 mod error;
 mod flv;
 mod fscommand;
@@ -1,4 +1,5 @@
 use crate::avm1::callable_value::CallableValue;
+use crate::avm1::debugger;
 use crate::avm1::error::Error;
 use crate::avm1::function::{Avm1Function, ExecutionReason, FunctionObject};
 use crate::avm1::object::{Object, TObject};
@@ -172,6 +173,14 @@ impl<'a> ActivationIdentifier<'a> {
     pub fn depth(&self) -> u16 {
         self.depth
     }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn parent(&self) -> Option<&'a ActivationIdentifier<'a>> {
+        self.parent
+    }
 }
 
 /// Represents a single activation of a given AVM1 function or keyframe.
@@ -432,14 +441,19 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     pub fn run_actions(&mut self, code: SwfSlice) -> Result<ReturnType<'gc>, Error<'gc>> {
         let mut read = Reader::new(&code.movie.data()[code.start..], self.swf_version());
 
-        loop {
+        let result = loop {
             let result = self.do_action(&code, &mut read);
             match result {
                 Ok(FrameControl::Return(return_type)) => break Ok(return_type),
                 Ok(FrameControl::Continue) => {}
                 Err(e) => break Err(e),
             }
+        };
+
+        if self.context.debugger.is_attached() {
+            debugger::actions_finished(self);
         }
+        result
     }
 
     /// Run a single action from a given action reader.
@@ -460,6 +474,12 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             //Executing beyond the end of a function constitutes an implicit return.
             Ok(FrameControl::Return(ReturnType::Implicit))
         } else {
+            if self.context.debugger.is_attached() {
+                let offset =
+                    reader.get_ref().as_ptr() as usize - data.movie.data().as_ptr() as usize;
+                debugger::action_started(self, data, offset);
+            }
+
             let action = reader.read_action()?;
             avm_debug!(
                 self.context.avm1,
@@ -1198,6 +1218,12 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     fn action_get_url(&mut self, action: GetUrl) -> Result<FrameControl<'gc>, Error<'gc>> {
         let target = action.target.decode(self.encoding());
         let url = action.url.decode(self.encoding());
+        if let Some((print_as_bitmap, bounding_box)) = globals::print_job::parse_print_url(&url) {
+            let target = AvmString::new(self.gc(), target.into_owned());
+            self.print(print_as_bitmap, bounding_box, target.into())?;
+            return Ok(FrameControl::Continue);
+        }
+
         // TODO: Use `StageObject::get_level_by_path`.
         if target.starts_with(WStr::from_units(b"_level")) && target.len() > 6 {
             match target[6..].parse::<i32>() {
@@ -1243,6 +1269,22 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 
+    /// Prints a clip for the `print:` URLs that `print()` and `printAsBitmap()`
+    /// compile to.
+    fn print(
+        &mut self,
+        print_as_bitmap: bool,
+        bounding_box: &WStr,
+        target: Value<'gc>,
+    ) -> Result<(), Error<'gc>> {
+        let start = self.target_clip_or_root();
+        match self.resolve_target_display_object(start, target, true)? {
+            Some(clip) => globals::print_job::print_clip(self, clip, print_as_bitmap, bounding_box),
+            None => avm_warn!(self, "print: Target not found: {:?}", target),
+        }
+        Ok(())
+    }
+
     fn action_get_url_2(&mut self, action: GetUrl2) -> Result<FrameControl<'gc>, Error<'gc>> {
         // TODO: Support `LoadVariablesFlag`, `LoadTargetFlag`
         // TODO: What happens if there's only one string?
@@ -1257,6 +1299,11 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             return Ok(FrameControl::Continue);
         }
 
+        if let Some((print_as_bitmap, bounding_box)) = globals::print_job::parse_print_url(&url) {
+            self.print(print_as_bitmap, bounding_box, target_val)?;
+            return Ok(FrameControl::Continue);
+        }
+
         // TODO: Use `StageObject::get_level_by_path`.
         let level_target = if target.starts_with(WStr::from_units(b"_level")) && target.len() >= 6 {
             match target[6..].parse::<f64>() {
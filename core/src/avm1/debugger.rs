@@ -0,0 +1,216 @@
+//! Pausing AVM1 code for the debugger.
+
+use crate::avm1::activation::{Activation, ActivationIdentifier};
+use crate::avm1::scope::Scope;
+use crate::avm1::{Object, TObject, Value, VariableDumper};
+use crate::backend::debugger::{SourceLocation, StackFrame, Variable};
+use crate::debugger::{self, DebugTarget, Debugger};
+use crate::display_object::TDisplayObject;
+use crate::string::AvmString;
+use crate::tag_utils::SwfSlice;
+use gc_arena::{Collect, Gc};
+use web_time::Instant;
+
+/// A call on the stack, as it was when its current line started running.
+///
+/// The debugger inspects callers through their scope, as the activations
+/// themselves are busy running their calls.
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct DebugFrame<'gc> {
+    depth: u16,
+    #[collect(require_static)]
+    location: SourceLocation,
+    this: Value<'gc>,
+    scope: Gc<'gc, Scope<'gc>>,
+}
+
+/// Called before each action runs, with the offset of the action in the movie data.
+pub fn action_started(activation: &mut Activation<'_, '_>, code: &SwfSlice, offset: usize) {
+    // SWD files count offsets from the start of the SWF file, header included.
+    let header_len = code.movie.uncompressed_len() as usize - code.movie.data().len();
+    let Some(location) = activation
+        .context
+        .debugger
+        .avm1_line(code.movie.url(), (header_len + offset) as u32)
+        .cloned()
+    else {
+        return;
+    };
+
+    let depth = activation.id.depth();
+    let frame = DebugFrame {
+        depth,
+        location: location.clone(),
+        this: activation.this_cell(),
+        scope: activation.scope(),
+    };
+    let frames = &mut activation.context.avm1.debug_frames;
+    frames.retain(|frame| frame.depth < depth);
+    frames.push(frame);
+
+    let depth = usize::from(depth);
+    if let Some(reason) = activation.context.debugger.pause_reason(&location, depth) {
+        let mut target = PausedActivation { activation };
+        debugger::pause(&mut target, reason, location, depth);
+        // The time spent paused doesn't count towards the script timeout.
+        target.activation.context.update_start = Instant::now();
+    }
+}
+
+/// Called when an activation finishes running its actions.
+pub fn actions_finished(activation: &mut Activation<'_, '_>) {
+    let depth = activation.id.depth();
+    activation
+        .context
+        .avm1
+        .debug_frames
+        .retain(|frame| frame.depth < depth);
+}
+
+/// Shows a value to the user, without running any code.
+fn describe_value<'gc>(activation: &mut Activation<'_, 'gc>, value: Value<'gc>) -> String {
+    match value {
+        Value::Object(object) if object.as_executable().is_some() => "[function]".to_string(),
+        Value::Object(_) => "[object]".to_string(),
+        Value::MovieClip(_) => value
+            .coerce_to_object(activation)
+            .as_display_object()
+            .map(|clip| clip.path().to_string())
+            .unwrap_or_else(|| "[movieclip]".to_string()),
+        value => {
+            let mut dumper = VariableDumper::new("");
+            dumper.print_value(&value, activation);
+            dumper.output().to_string()
+        }
+    }
+}
+
+/// Reads a property of an object or of its prototypes without calling any
+/// getter, or returns `None` if there's no such property.
+fn stored_property<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    name: AvmString<'gc>,
+) -> Option<Value<'gc>> {
+    let mut proto = Value::Object(object);
+    // Prototype chains may be cyclic.
+    for _ in 0..256 {
+        let Value::Object(object) = proto else {
+            break;
+        };
+        if let Some(value) = object.get_local_stored(name, activation, false) {
+            return Some(value);
+        }
+        proto = object.proto(activation);
+    }
+    None
+}
+
+struct PausedActivation<'a, 'b, 'gc> {
+    activation: &'a mut Activation<'b, 'gc>,
+}
+
+impl<'gc> PausedActivation<'_, '_, 'gc> {
+    /// The calls on the stack, innermost first.
+    fn calls(&self) -> impl Iterator<Item = &ActivationIdentifier<'_>> {
+        std::iter::successors(Some(&self.activation.id), |id| id.parent())
+    }
+
+    /// The `this` and scope of the given frame, counted from the innermost call.
+    fn frame(&self, frame: u32) -> Result<(Value<'gc>, Gc<'gc, Scope<'gc>>), String> {
+        if frame == 0 {
+            return Ok((self.activation.this_cell(), self.activation.scope()));
+        }
+        let depth = self
+            .calls()
+            .nth(frame as usize)
+            .ok_or_else(|| format!("There's no frame {frame}"))?
+            .depth();
+        self.activation
+            .context
+            .avm1
+            .debug_frames
+            .iter()
+            .find(|debug_frame| debug_frame.depth == depth)
+            .map(|debug_frame| (debug_frame.this, debug_frame.scope))
+            .ok_or_else(|| format!("Frame {frame} has no debugging information"))
+    }
+}
+
+impl DebugTarget for PausedActivation<'_, '_, '_> {
+    fn debugger(&mut self) -> &mut Debugger {
+        self.activation.context.debugger
+    }
+
+    fn stack(&mut self) -> Vec<StackFrame> {
+        let debug_frames = &self.activation.context.avm1.debug_frames;
+        self.calls()
+            .map(|id| StackFrame {
+                name: id.name().to_string(),
+                location: debug_frames
+                    .iter()
+                    .find(|frame| frame.depth == id.depth())
+                    .map(|frame| frame.location.clone()),
+            })
+            .collect()
+    }
+
+    fn locals(&mut self, frame: u32) -> Result<Vec<Variable>, String> {
+        let (this, scope) = self.frame(frame)?;
+
+        let activation = &mut *self.activation;
+        let mut variables = vec![Variable {
+            name: "this".to_string(),
+            value: describe_value(activation, this),
+        }];
+
+        let locals = scope.locals_cell();
+        for name in locals.get_keys(activation, false) {
+            let value = locals
+                .get_local_stored(name, activation, false)
+                .unwrap_or(Value::Undefined);
+            variables.push(Variable {
+                name: name.to_string(),
+                value: describe_value(activation, value),
+            });
+        }
+        Ok(variables)
+    }
+
+    /// Evaluates a variable path made of names separated by dots, such as `_root.clip._x`.
+    fn evaluate(&mut self, frame: u32, expression: &str) -> Result<String, String> {
+        let (this, scope) = self.frame(frame)?;
+
+        let mut names = expression.split('.').map(str::trim);
+        if names.clone().any(str::is_empty) {
+            return Err(format!("Unsupported expression: {expression}"));
+        }
+
+        let activation = &mut *self.activation;
+        let first = names.next().unwrap_or_default();
+        let mut value = if first == "this" {
+            this
+        } else {
+            let first = AvmString::new_utf8(activation.gc(), first);
+            std::iter::successors(Some(scope), |scope| scope.parent())
+                .find_map(|scope| stored_property(activation, scope.locals_cell(), first))
+                .unwrap_or(Value::Undefined)
+        };
+
+        for name in names {
+            let object = match value {
+                Value::Object(_) | Value::MovieClip(_) => value.coerce_to_object(activation),
+                _ => {
+                    return Err(format!(
+                        "Can't read {name} of {}",
+                        describe_value(activation, value)
+                    ))
+                }
+            };
+            let name = AvmString::new_utf8(activation.gc(), name);
+            value = stored_property(activation, object, name).unwrap_or(Value::Undefined);
+        }
+        Ok(describe_value(activation, value))
+    }
+}
@@ -46,6 +46,7 @@ pub(crate) mod netstream;
 pub(crate) mod number;
 mod object;
 mod point;
+pub(crate) mod print_job;
 mod rectangle;
 mod selection;
 pub(crate) mod shared_object;
@@ -585,6 +586,7 @@ pub fn create_globals<'gc>(
     let netstream_proto = netstream::create_proto(context, object_proto, function_proto);
     let netconnection_proto = netconnection::create_proto(context, object_proto, function_proto);
     let xml_socket_proto = xml_socket::create_proto(context, object_proto, function_proto);
+    let print_job_proto = print_job::create_proto(context, object_proto, function_proto);
 
     //TODO: These need to be constructors and should also set `.prototype` on each one
     let object = object::create_object_object(context, object_proto, function_proto);
@@ -687,6 +689,7 @@ pub fn create_globals<'gc>(
     let netstream = netstream::create_class(context, netstream_proto, function_proto);
     let netconnection = netconnection::create_class(context, netconnection_proto, function_proto);
     let xml_socket = xml_socket::create_class(context, xml_socket_proto, function_proto);
+    let print_job = print_job::create_class(context, print_job_proto, function_proto);
 
     let flash = ScriptObject::new(gc_context, Some(object_proto));
 
@@ -1105,6 +1108,12 @@ pub fn create_globals<'gc>(
         xml_socket.into(),
         Attribute::DONT_ENUM,
     );
+    globals.define_value(
+        gc_context,
+        "PrintJob",
+        print_job.into(),
+        Attribute::DONT_ENUM,
+    );
 
     define_properties_on(GLOBAL_DECLS, context, globals, function_proto);
 
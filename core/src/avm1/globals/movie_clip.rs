@@ -600,7 +600,9 @@ fn begin_bitmap_fill<'gc>(
                 width: bitmap_data.width() as u16,
                 height: bitmap_data.height() as u16,
             };
-            let id = movie_clip.drawing_mut(activation.gc()).add_bitmap(bitmap);
+            let id = movie_clip
+                .drawing_mut(activation.gc())
+                .add_bitmap(bitmap_data, bitmap);
 
             let mut matrix = avm1::globals::matrix::object_to_matrix_or_default(
                 args.get(1)
@@ -134,6 +134,41 @@ impl<'gc> NetConnection<'gc> {
         )?;
         Ok(())
     }
+
+    /// Call a method on a `NetConnection` on behalf of the server it's
+    /// connected to.
+    pub fn call_client_method(
+        context: &mut UpdateContext<'gc>,
+        net_connection: Object<'gc>,
+        name: &str,
+        arguments: &[Rc<flash_lso::types::Value>],
+    ) -> Result<(), Error<'gc>> {
+        let Some(root_clip) = context.stage.root_clip() else {
+            tracing::warn!("Ignored NetConnection call as there's no root movie");
+            return Ok(());
+        };
+        let mut activation = Activation::from_nothing(
+            context,
+            ActivationIdentifier::root("[NetConnection call]"),
+            root_clip,
+        );
+        let reader = flash_lso::read::Reader::default();
+        let mut reference_cache = BTreeMap::default();
+        let arguments: Vec<_> = arguments
+            .iter()
+            .map(|argument| {
+                deserialize_value(
+                    &mut activation,
+                    argument,
+                    &reader.amf0_decoder,
+                    &mut reference_cache,
+                )
+            })
+            .collect();
+        let name = AvmString::new_utf8(activation.gc(), name);
+        net_connection.call_method(name, &arguments, &mut activation, ExecutionReason::Special)?;
+        Ok(())
+    }
 }
 
 pub fn constructor<'gc>(
@@ -322,6 +357,12 @@ fn connect<'gc>(
     {
         // HTTP(S) is for Flash Remoting, which is just POST requests to the URL.
         NetConnections::connect_to_flash_remoting(activation.context, this, url.to_string());
+    } else if url.starts_with(WStr::from_units(b"rtmp")) {
+        let arguments = args[1..]
+            .iter()
+            .map(|arg| serialize(activation, *arg))
+            .collect();
+        NetConnections::connect_to_rtmp(activation.context, this, url.to_string(), arguments);
     } else {
         avm1_stub!(
             activation,
@@ -1,4 +1,5 @@
 use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::globals::netconnection::NetConnection;
 use crate::avm1::object::{NativeObject, Object, TObject};
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Activation, Error, ScriptObject, Value};
@@ -9,9 +10,14 @@ use crate::string::StringContext;
 pub fn constructor<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let netstream = NetStream::new(activation.context.gc_context, Some(this.into()));
+    let connection = args
+        .first()
+        .and_then(|connection| NetConnection::cast(*connection))
+        .and_then(|connection| connection.handle());
+    netstream.set_net_connection(activation.gc(), connection);
     this.set_native(
         activation.context.gc_context,
         NativeObject::NetStream(netstream),
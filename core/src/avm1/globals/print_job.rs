@@ -0,0 +1,235 @@
+//! PrintJob object, and the `print` family of global functions
+
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::object::NativeObject;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{Activation, Error, Object, ScriptObject, TObject, Value};
+use crate::avm1_stub;
+use crate::backend::print::PrintOrientation;
+use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::print_job::{PageScaling, PrintJob, PrintJobId};
+use crate::string::{StringContext, WStr};
+use gc_arena::Gc;
+use ruffle_render::matrix::Matrix;
+use std::cell::Cell;
+use swf::{Rectangle, Twips};
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "start" => method(start; DONT_ENUM | DONT_DELETE);
+    "addPage" => method(add_page; DONT_ENUM | DONT_DELETE);
+    "send" => method(send; DONT_ENUM | DONT_DELETE);
+};
+
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    this.set_native(
+        activation.gc(),
+        NativeObject::PrintJob(Gc::new(activation.gc(), Cell::new(None))),
+    );
+    Ok(this.into())
+}
+
+fn job_cell<'gc>(this: Object<'gc>) -> Option<Gc<'gc, Cell<Option<PrintJobId>>>> {
+    match this.native() {
+        NativeObject::PrintJob(job) => Some(job),
+        _ => None,
+    }
+}
+
+pub fn start<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(job) = job_cell(this) else {
+        return Ok(false.into());
+    };
+    if job.get().is_some() {
+        return Ok(false.into());
+    }
+
+    let Some((id, settings)) = PrintJob::start(activation.context) else {
+        return Ok(false.into());
+    };
+    job.set(Some(id));
+
+    let orientation = match settings.orientation {
+        PrintOrientation::Portrait => "portrait",
+        PrintOrientation::Landscape => "landscape",
+    };
+    this.set("paperWidth", settings.paper_width.into(), activation)?;
+    this.set("paperHeight", settings.paper_height.into(), activation)?;
+    this.set("pageWidth", settings.page_width.into(), activation)?;
+    this.set("pageHeight", settings.page_height.into(), activation)?;
+    this.set("orientation", orientation.into(), activation)?;
+
+    Ok(true.into())
+}
+
+pub fn add_page<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(id) = job_cell(this).and_then(|job| job.get()) else {
+        return Ok(false.into());
+    };
+
+    let target = args.get(0).copied().unwrap_or(Value::Undefined);
+    let target = match target {
+        // A number is a level, as in `_level0`.
+        Value::Number(level) => activation.get_level(level as i32),
+        _ => {
+            let start = activation.target_clip_or_root();
+            activation.resolve_target_display_object(start, target, false)?
+        }
+    };
+    let Some(target) = target else {
+        return Ok(false.into());
+    };
+
+    let print_area = match args.get(1) {
+        Some(Value::Object(print_area)) => {
+            let x_min = print_area
+                .get("xMin", activation)?
+                .coerce_to_f64(activation)?;
+            let x_max = print_area
+                .get("xMax", activation)?
+                .coerce_to_f64(activation)?;
+            let y_min = print_area
+                .get("yMin", activation)?
+                .coerce_to_f64(activation)?;
+            let y_max = print_area
+                .get("yMax", activation)?
+                .coerce_to_f64(activation)?;
+            Some(Rectangle {
+                x_min: Twips::from_pixels(x_min),
+                x_max: Twips::from_pixels(x_max),
+                y_min: Twips::from_pixels(y_min),
+                y_max: Twips::from_pixels(y_max),
+            })
+        }
+        _ => None,
+    };
+
+    let print_as_bitmap = match args.get(2) {
+        Some(Value::Object(options)) => options
+            .get("printAsBitmap", activation)?
+            .as_bool(activation.swf_version()),
+        _ => false,
+    };
+
+    // Frames are numbered from 1, and any other value prints the current frame.
+    let frame = match args.get(3) {
+        Some(Value::Undefined) | None => None,
+        Some(frame) => {
+            let frame = frame.coerce_to_i32(activation)?;
+            u16::try_from(frame).ok().filter(|frame| *frame > 0)
+        }
+    };
+
+    Ok(PrintJob::add_page(
+        activation.context,
+        id,
+        target,
+        print_area,
+        frame,
+        PageScaling::None,
+        print_as_bitmap,
+    )
+    .into())
+}
+
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(job) = job_cell(this) {
+        if let Some(id) = job.take() {
+            PrintJob::send(activation.context, id);
+        }
+    }
+    Ok(Value::Undefined)
+}
+
+/// Parse the URL used by the `print` family of functions, which compile to
+/// `getURL` calls such as `getURL("print:#bframe", "_root.clip")`.
+///
+/// Returns whether to print as a bitmap, and the bounding box option.
+pub fn parse_print_url(url: &WStr) -> Option<(bool, &WStr)> {
+    for (prefix, print_as_bitmap) in [
+        (WStr::from_units(b"print:"), false),
+        (WStr::from_units(b"printasbitmap:"), true),
+    ] {
+        if url.len() >= prefix.len() && url[..prefix.len()].eq_ignore_case(prefix) {
+            return Some((print_as_bitmap, &url[prefix.len()..]));
+        }
+    }
+    None
+}
+
+/// Prints a clip as a single page, as done by `print()` and `printAsBitmap()`.
+pub fn print_clip<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    target: DisplayObject<'gc>,
+    print_as_bitmap: bool,
+    bounding_box: &WStr,
+) {
+    avm1_stub!(activation, "print", "only the current frame is printed");
+
+    // Without a bounding box, a whole level is printed at the size of the movie.
+    let is_level = target.avm1_parent().is_none();
+    let print_area = if bounding_box.is_empty() && is_level {
+        let (width, height) = activation.context.stage.movie_size();
+        Rectangle {
+            x_min: Twips::ZERO,
+            y_min: Twips::ZERO,
+            x_max: Twips::from_pixels(width.into()),
+            y_max: Twips::from_pixels(height.into()),
+        }
+    } else {
+        target.bounds_with_transform(&Matrix::IDENTITY)
+    };
+
+    let Some((id, _)) = PrintJob::start(activation.context) else {
+        return;
+    };
+    PrintJob::add_page(
+        activation.context,
+        id,
+        target,
+        Some(print_area),
+        None,
+        PageScaling::Fit,
+        print_as_bitmap,
+    );
+    PrintJob::send(activation.context, id);
+}
+
+pub fn create_proto<'gc>(
+    context: &mut StringContext<'gc>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let print_job_proto = ScriptObject::new(context.gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, context, print_job_proto, fn_proto);
+    print_job_proto.into()
+}
+
+pub fn create_class<'gc>(
+    context: &mut StringContext<'gc>,
+    print_job_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    FunctionObject::constructor(
+        context.gc_context,
+        Executable::Native(constructor),
+        constructor_to_fn!(constructor),
+        fn_proto,
+        print_job_proto,
+    )
+}
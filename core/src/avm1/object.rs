@@ -26,6 +26,7 @@ use crate::bitmap::bitmap_data::BitmapDataWrapper;
 use crate::display_object::DisplayObject;
 use crate::display_object::TDisplayObject;
 use crate::html::TextFormat;
+use crate::print_job::PrintJobId;
 use crate::streams::NetStream;
 use crate::string::AvmString;
 use crate::xml::XmlNode;
@@ -70,6 +71,8 @@ pub enum NativeObject<'gc> {
     NetConnection(NetConnection<'gc>),
     LocalConnection(LocalConnection<'gc>),
     Sound(Sound<'gc>),
+    /// The print job started by a `PrintJob` object, if any.
+    PrintJob(Gc<'gc, Cell<Option<PrintJobId>>>),
 }
 
 /// Represents an object that can be directly interacted with by the AVM
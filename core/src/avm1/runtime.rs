@@ -1,3 +1,4 @@
+use crate::avm1::debugger::DebugFrame;
 use crate::avm1::function::{ExecutionReason, FunctionObject};
 use crate::avm1::globals::as_broadcaster::BroadcasterFunctions;
 use crate::avm1::globals::{as_broadcaster, create_globals};
@@ -85,6 +86,9 @@ pub struct Avm1<'gc> {
     /// More examples of this are in the movieclip_invalid_get_bounds_X tests.
     use_new_invalid_bounds_value: bool,
 
+    /// The calls on the stack that the debugger can inspect, outermost first.
+    pub(super) debug_frames: Vec<DebugFrame<'gc>>,
+
     #[cfg(feature = "avm_debug")]
     pub debug_output: bool,
 }
@@ -114,6 +118,7 @@ impl<'gc> Avm1<'gc> {
             clip_exec_list: None,
             constructor_registry_case_insensitive: PropertyMap::new(),
             constructor_registry_case_sensitive: PropertyMap::new(),
+            debug_frames: Vec::new(),
 
             #[cfg(feature = "avm_debug")]
             debug_output: false,
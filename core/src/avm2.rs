@@ -33,12 +33,13 @@ macro_rules! avm_debug {
 }
 
 pub mod activation;
-mod amf;
+pub mod amf;
 pub mod api_version;
 mod array;
 pub mod bytearray;
 mod call_stack;
 mod class;
+mod debugger;
 mod domain;
 mod dynamic_map;
 mod e4x;
@@ -60,6 +61,7 @@ pub mod property;
 mod property_map;
 mod qname;
 mod regexp;
+pub mod sampler;
 mod scope;
 pub mod script;
 #[cfg(feature = "known_stubs")]
@@ -85,16 +87,24 @@ pub use crate::avm2::multiname::{CommonMultinames, Multiname};
 pub use crate::avm2::namespace::{CommonNamespaces, Namespace};
 pub use crate::avm2::object::{
     ArrayObject, BitmapDataObject, ClassObject, EventObject, Object, SoundChannelObject,
-    StageObject, TObject,
+    SoundObject, StageObject, TObject,
 };
 pub use crate::avm2::qname::QName;
+pub use crate::avm2::sampler::Sampler;
 pub use crate::avm2::value::Value;
 
 use self::api_version::ApiVersion;
 use self::object::WeakObject;
 use self::scope::Scope;
 
-const BROADCAST_WHITELIST: [&str; 4] = ["enterFrame", "exitFrame", "frameConstructed", "render"];
+const BROADCAST_WHITELIST: [&str; 6] = [
+    "enterFrame",
+    "exitFrame",
+    "frameConstructed",
+    "render",
+    "deviceAdded",
+    "deviceRemoved",
+];
 
 const PREALLOCATED_STACK_SIZE: usize = 120000;
 
@@ -118,6 +128,12 @@ pub struct Avm2<'gc> {
     /// The current call stack of the player.
     call_stack: GcRefLock<'gc, CallStack<'gc>>,
 
+    /// The state of `flash.sampler`.
+    pub sampler: Sampler<'gc>,
+
+    /// The calls on the stack that the debugger can inspect, outermost first.
+    debug_frames: Vec<debugger::DebugFrame<'gc>>,
+
     /// This domain is used exclusively for classes from playerglobals
     playerglobals_domain: Domain<'gc>,
 
@@ -209,6 +225,8 @@ impl<'gc> Avm2<'gc> {
             stack: Vec::with_capacity(PREALLOCATED_STACK_SIZE),
             scope_stack: Vec::new(),
             call_stack: GcRefLock::new(mc, CallStack::new().into()),
+            sampler: Sampler::default(),
+            debug_frames: Vec::new(),
             playerglobals_domain,
             stage_domain,
             system_classes: None,
@@ -656,7 +674,13 @@ impl<'gc> Avm2<'gc> {
     }
 
     /// Pushes an executable on the call stack
-    pub fn push_call(&self, mc: &Mutation<'gc>, method: Method<'gc>, class: Option<Class<'gc>>) {
+    pub fn push_call(
+        &mut self,
+        mc: &Mutation<'gc>,
+        method: Method<'gc>,
+        class: Option<Class<'gc>>,
+    ) {
+        self.sampler.record_invocation(method);
         self.call_stack.borrow_mut(mc).push(method, class)
     }
 
@@ -666,8 +690,11 @@ impl<'gc> Avm2<'gc> {
     }
 
     /// Pops an executable off the call stack
-    pub fn pop_call(&self, mc: &Mutation<'gc>) -> Option<CallNode<'gc>> {
-        self.call_stack.borrow_mut(mc).pop()
+    pub fn pop_call(&mut self, mc: &Mutation<'gc>) -> Option<CallNode<'gc>> {
+        let mut call_stack = self.call_stack.borrow_mut(mc);
+        let call = call_stack.pop();
+        debugger::calls_returned(&mut self.debug_frames, call_stack.frames().len());
+        call
     }
 
     pub fn call_stack(&self) -> GcRefLock<'gc, CallStack<'gc>> {
@@ -2,6 +2,7 @@
 
 use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::Class;
+use crate::avm2::debugger;
 use crate::avm2::domain::Domain;
 use crate::avm2::e4x::{escape_attribute_value, escape_element_value};
 use crate::avm2::error::{
@@ -14,6 +15,7 @@ use crate::avm2::object::{
 };
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::op::Op;
+use crate::avm2::sampler;
 use crate::avm2::scope::{search_scope_stack, Scope, ScopeChain};
 use crate::avm2::script::Script;
 use crate::avm2::value::Value;
@@ -561,6 +563,11 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         self.local_registers.get_unchecked(id)
     }
 
+    /// The number of local registers, including `this`.
+    pub fn num_local_registers(&self) -> u32 {
+        self.local_registers.0.len() as u32
+    }
+
     /// Set a local register.
     pub fn set_local_register(&mut self, id: u32, value: impl Into<Value<'gc>>) {
         // Verification guarantees that this is valid
@@ -977,9 +984,9 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                     register,
                 } => self.op_debug(*is_local_register, *register_name, *register),
                 Op::DebugFile { file_name } => self.op_debug_file(*file_name),
-                Op::DebugLine { line_num } => self.op_debug_line(*line_num),
-                Op::Bkpt => self.op_bkpt(),
-                Op::BkptLine { line_num } => self.op_bkpt_line(*line_num),
+                Op::DebugLine { line_num } => self.op_debug_line(method, opcodes, *line_num),
+                Op::Bkpt => self.op_bkpt(opcodes),
+                Op::BkptLine { line_num } => self.op_bkpt_line(opcodes, *line_num),
                 Op::Timestamp => self.op_timestamp(),
                 Op::TypeOf => self.op_type_of(),
                 Op::EscXAttr => self.op_esc_xattr(),
@@ -1888,6 +1895,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         let args = self.pop_stack_args(num_args);
         let array = ArrayStorage::from_args(&args[..]);
         let array_obj = ArrayObject::from_storage(self, array)?;
+        let array_class = self.avm2().classes().array;
+        sampler::sample_new_object(self, array_obj, array_class);
 
         self.push_stack(array_obj);
 
@@ -3104,19 +3113,41 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_debug_line(&mut self, line_num: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
+    fn op_debug_line(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        opcodes: &[Op<'gc>],
+        line_num: u32,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         avm_debug!(self.avm2(), "Line: {line_num}");
 
+        if self.context.debugger.is_attached() {
+            let ip = self.ip as usize;
+            debugger::line_started(self, method, opcodes, ip, line_num);
+        }
+
         Ok(FrameControl::Continue)
     }
 
-    fn op_bkpt(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
+    fn op_bkpt(&mut self, opcodes: &[Op<'gc>]) -> Result<FrameControl<'gc>, Error<'gc>> {
         // while a debugger is not attached, this is a no-op
+        if self.context.debugger.is_attached() {
+            let ip = self.ip as usize;
+            debugger::breakpoint(self, opcodes, ip, None);
+        }
         Ok(FrameControl::Continue)
     }
 
-    fn op_bkpt_line(&mut self, _line_num: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
+    fn op_bkpt_line(
+        &mut self,
+        opcodes: &[Op<'gc>],
+        line_num: u32,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         // while a debugger is not attached, this is a no-op
+        if self.context.debugger.is_attached() {
+            let ip = self.ip as usize;
+            debugger::breakpoint(self, opcodes, ip, Some(line_num));
+        }
         Ok(FrameControl::Continue)
     }
 
@@ -354,6 +354,13 @@ impl ByteArrayStorage {
         &mut self.bytes
     }
 
+    /// Exchanges the bytes of this ByteArray with other bytes, keeping the
+    /// position, endianness and object encoding.
+    #[inline]
+    pub fn swap_bytes(&mut self, bytes: &mut Vec<u8>) {
+        std::mem::swap(&mut self.bytes, bytes);
+    }
+
     #[inline]
     pub fn len(&self) -> usize {
         self.bytes.len()
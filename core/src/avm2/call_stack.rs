@@ -16,6 +16,30 @@ pub enum CallNode<'gc> {
     },
 }
 
+impl CallNode<'_> {
+    pub fn display(&self, output: &mut WString) {
+        match self {
+            CallNode::GlobalInit(script) => {
+                let name = if let Some(tuint) = script.translation_unit() {
+                    if let Some(name) = tuint.name() {
+                        name.to_utf8_lossy().to_string()
+                    } else {
+                        "<No name>".to_string()
+                    }
+                } else {
+                    "<No translation unit>".to_string()
+                };
+
+                // NOTE: We intentionally diverge from Flash Player's output
+                // here - everything with the [] brackets is extra information
+                // added by Ruffle
+                output.push_utf8(&format!("global$init() [TU={}]", name));
+            }
+            CallNode::Method { method, class } => display_function(output, method, *class),
+        }
+    }
+}
+
 #[derive(Collect, Clone)]
 #[collect(no_drop)]
 pub struct CallStack<'gc> {
@@ -42,31 +66,18 @@ impl<'gc> CallStack<'gc> {
     pub fn display(&self, output: &mut WString) {
         for call in self.stack.iter().rev() {
             output.push_utf8("\n\tat ");
-            match call {
-                CallNode::GlobalInit(script) => {
-                    let name = if let Some(tuint) = script.translation_unit() {
-                        if let Some(name) = tuint.name() {
-                            name.to_utf8_lossy().to_string()
-                        } else {
-                            "<No name>".to_string()
-                        }
-                    } else {
-                        "<No translation unit>".to_string()
-                    };
-
-                    // NOTE: We intentionally diverge from Flash Player's output
-                    // here - everything with the [] brackets is extra information
-                    // added by Ruffle
-                    output.push_utf8(&format!("global$init() [TU={}]", name));
-                }
-                CallNode::Method { method, class } => display_function(output, method, *class),
-            }
+            call.display(output);
         }
     }
 
     pub fn is_empty(&self) -> bool {
         self.stack.is_empty()
     }
+
+    /// The calls on the stack, outermost first.
+    pub fn frames(&self) -> &[CallNode<'gc>] {
+        &self.stack
+    }
 }
 
 impl Default for CallStack<'_> {
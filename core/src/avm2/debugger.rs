@@ -0,0 +1,366 @@
+//! Pausing AVM2 code for the debugger.
+
+use crate::avm2::method::BytecodeMethod;
+use crate::avm2::object::TObject;
+use crate::avm2::op::Op;
+use crate::avm2::property::Property;
+use crate::avm2::{Activation, Error, Multiname, Namespace, Object, Value};
+use crate::backend::debugger::{PauseReason, SourceLocation, StackFrame, Variable};
+use crate::debugger::{self, DebugTarget, Debugger};
+use crate::string::{AvmString, WString};
+use gc_arena::{Collect, Gc};
+use std::collections::HashMap;
+use web_time::Instant;
+
+/// A call on the stack, as it was when its current line started running.
+///
+/// Only the innermost call of a paused script can be inspected as it is, so
+/// its callers are shown as they were when they made their calls.
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct DebugFrame<'gc> {
+    /// The number of calls on the stack, this one included.
+    depth: usize,
+    method: Gc<'gc, BytecodeMethod<'gc>>,
+    ip: usize,
+    line: u32,
+    registers: Vec<Value<'gc>>,
+}
+
+impl<'gc> DebugFrame<'gc> {
+    fn with_code<R>(&self, f: impl FnOnce(&[Op<'gc>]) -> R) -> R {
+        let verified_info = self.method.verified_info.borrow();
+        f(verified_info
+            .as_ref()
+            .map_or(&[], |info| info.parsed_code.as_slice()))
+    }
+
+    fn location(&self) -> SourceLocation {
+        self.with_code(|code| location(code, self.ip, self.line))
+    }
+}
+
+/// Forgets the calls that returned, leaving `depth` calls on the stack.
+pub fn calls_returned(frames: &mut Vec<DebugFrame<'_>>, depth: usize) {
+    while frames.last().is_some_and(|frame| frame.depth > depth) {
+        frames.pop();
+    }
+}
+
+/// The location of the code at `ip`, as given by the last `debugfile` before it.
+fn location(code: &[Op<'_>], ip: usize, line: u32) -> SourceLocation {
+    let file = code[..ip.min(code.len())]
+        .iter()
+        .rev()
+        .find_map(|op| match op {
+            Op::DebugFile { file_name } => Some(file_name.to_string()),
+            _ => None,
+        })
+        .unwrap_or_default();
+    SourceLocation { file, line }
+}
+
+/// The names of the local registers, as given by `debug` instructions.
+fn register_names<'gc>(code: &[Op<'gc>]) -> HashMap<u32, AvmString<'gc>> {
+    code.iter()
+        .filter_map(|op| match op {
+            Op::Debug {
+                is_local_register: true,
+                register_name,
+                register,
+            } => Some((u32::from(*register), (*register_name).into())),
+            _ => None,
+        })
+        .collect()
+}
+
+fn call_depth(activation: &mut Activation<'_, '_>) -> usize {
+    activation.avm2().call_stack().borrow().frames().len()
+}
+
+fn registers<'gc>(activation: &Activation<'_, 'gc>) -> Vec<Value<'gc>> {
+    (0..activation.num_local_registers())
+        .map(|register| activation.local_register(register))
+        .collect()
+}
+
+/// Called by `debugline` instructions while a debugger is attached, when a new line starts running.
+pub fn line_started<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    method: Gc<'gc, BytecodeMethod<'gc>>,
+    code: &[Op<'gc>],
+    ip: usize,
+    line: u32,
+) {
+    let depth = call_depth(activation);
+    let registers = registers(activation);
+    let frames = &mut activation.avm2().debug_frames;
+    calls_returned(frames, depth - 1);
+    frames.push(DebugFrame {
+        depth,
+        method,
+        ip,
+        line,
+        registers,
+    });
+
+    if !activation.context.debugger.may_pause_at_line(line) {
+        return;
+    }
+    let location = location(code, ip, line);
+    if let Some(reason) = activation.context.debugger.pause_reason(&location, depth) {
+        pause(activation, code, location, reason, depth);
+    }
+}
+
+/// Called by `bkpt` and `bkptline` instructions.
+pub fn breakpoint<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    code: &[Op<'gc>],
+    ip: usize,
+    line: Option<u32>,
+) {
+    let line = line.unwrap_or_else(|| {
+        code[..ip.min(code.len())]
+            .iter()
+            .rev()
+            .find_map(|op| match op {
+                Op::DebugLine { line_num } => Some(*line_num),
+                _ => None,
+            })
+            .unwrap_or(0)
+    });
+    let location = location(code, ip, line);
+    let depth = call_depth(activation);
+    pause(activation, code, location, PauseReason::Breakpoint, depth);
+}
+
+fn pause<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    code: &[Op<'gc>],
+    location: SourceLocation,
+    reason: PauseReason,
+    depth: usize,
+) {
+    let mut target = PausedActivation {
+        activation,
+        code,
+        location: location.clone(),
+    };
+    debugger::pause(&mut target, reason, location, depth);
+    // The time spent paused doesn't count towards the script timeout.
+    target.activation.context.update_start = Instant::now();
+}
+
+/// Shows a value to the user, without running any code.
+fn describe_value<'gc>(activation: &mut Activation<'_, 'gc>, value: Value<'gc>) -> String {
+    match value {
+        Value::Object(object) => format!(
+            "[object {}]",
+            object.instance_of_class_name(activation.gc())
+        ),
+        Value::String(string) => format!("{:?}", string.to_string()),
+        value => value
+            .coerce_to_string(activation)
+            .map(|string| string.to_string())
+            .unwrap_or_default(),
+    }
+}
+
+fn describe_error(error: Error<'_>) -> String {
+    format!("{error:?}")
+}
+
+/// Reads a property of an object without calling any getter, or returns
+/// `None` if the object has no such property.
+fn read_property<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    name: AvmString<'gc>,
+) -> Option<Result<Value<'gc>, String>> {
+    // Private properties are worth seeing too.
+    let multiname = Multiname::new(Namespace::any(), name);
+    let value = match object.vtable().get_trait(&multiname) {
+        Some(Property::Slot { slot_id } | Property::ConstSlot { slot_id }) => {
+            Ok(object.get_slot(slot_id))
+        }
+        Some(Property::Method { disp_id }) => object
+            .vtable()
+            .make_bound_method(activation, object, disp_id)
+            .map(Value::from)
+            .ok_or_else(|| format!("Method {name} not found")),
+        Some(Property::Virtual { .. }) => Err(format!(
+            "{name} is a getter, which isn't called while paused"
+        )),
+        None if matches!(object, Object::ProxyObject(_)) => Err(format!(
+            "{name} would be read by a Proxy, which isn't called while paused"
+        )),
+        None => {
+            let multiname = Multiname::new(activation.avm2().find_public_namespace(), name);
+            if !object.has_property(&multiname) {
+                return None;
+            }
+            object
+                .get_property_local(&multiname, activation)
+                .map_err(describe_error)
+        }
+    };
+    Some(value)
+}
+
+struct PausedActivation<'a, 'b, 'gc> {
+    activation: &'a mut Activation<'b, 'gc>,
+    code: &'a [Op<'gc>],
+    location: SourceLocation,
+}
+
+/// The local registers of a call, with their names.
+type Registers<'gc> = (Vec<Value<'gc>>, HashMap<u32, AvmString<'gc>>);
+
+impl<'gc> PausedActivation<'_, '_, 'gc> {
+    /// The registers of the given frame, counted from the innermost call.
+    fn registers(&mut self, frame: u32) -> Result<Registers<'gc>, String> {
+        if frame == 0 {
+            return Ok((registers(self.activation), register_names(self.code)));
+        }
+
+        let depth = call_depth(self.activation)
+            .checked_sub(frame as usize)
+            .filter(|depth| *depth > 0)
+            .ok_or_else(|| format!("There's no frame {frame}"))?;
+        self.activation
+            .avm2()
+            .debug_frames
+            .iter()
+            .find(|debug_frame| debug_frame.depth == depth)
+            .map(|debug_frame| {
+                (
+                    debug_frame.registers.clone(),
+                    debug_frame.with_code(register_names),
+                )
+            })
+            .ok_or_else(|| format!("Frame {frame} has no debugging information"))
+    }
+
+    /// Looks up a name as a local, as a property of `this` or of the global
+    /// object, or as a class.
+    fn lookup(
+        &mut self,
+        (registers, names): &Registers<'gc>,
+        name: &str,
+    ) -> Result<Value<'gc>, String> {
+        if name == "this" {
+            return Ok(registers.first().copied().unwrap_or(Value::Undefined));
+        }
+        if let Some((register, _)) = names
+            .iter()
+            .find(|(_, register_name)| register_name.to_string() == name)
+        {
+            return Ok(registers
+                .get(*register as usize)
+                .copied()
+                .unwrap_or(Value::Undefined));
+        }
+
+        let activation = &mut *self.activation;
+        let name = AvmString::new_utf8(activation.gc(), name);
+        let this = registers.first().and_then(|this| this.as_object());
+        for object in [this, activation.global_scope()].into_iter().flatten() {
+            if let Some(value) = read_property(activation, object, name) {
+                return value;
+            }
+        }
+
+        let multiname = Multiname::new(Namespace::any(), name);
+        activation
+            .domain()
+            .get_class(activation.context, &multiname)
+            .and_then(|class| class.class_object())
+            .map(Value::from)
+            .ok_or_else(|| format!("{name} is not defined"))
+    }
+}
+
+impl DebugTarget for PausedActivation<'_, '_, '_> {
+    fn debugger(&mut self) -> &mut Debugger {
+        self.activation.context.debugger
+    }
+
+    fn stack(&mut self) -> Vec<StackFrame> {
+        let avm2 = self.activation.avm2();
+        let call_stack = avm2.call_stack();
+        let call_stack = call_stack.borrow();
+        let depth = call_stack.frames().len();
+        call_stack
+            .frames()
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, call)| {
+                let mut name = WString::new();
+                call.display(&mut name);
+                let location = if i == 0 {
+                    Some(self.location.clone())
+                } else {
+                    avm2.debug_frames
+                        .iter()
+                        .find(|frame| frame.depth == depth - i)
+                        .map(DebugFrame::location)
+                };
+                StackFrame {
+                    name: name.to_string(),
+                    location,
+                }
+            })
+            .collect()
+    }
+
+    fn locals(&mut self, frame: u32) -> Result<Vec<Variable>, String> {
+        let (registers, names) = self.registers(frame)?;
+        let activation = &mut *self.activation;
+        let variables = registers
+            .into_iter()
+            .enumerate()
+            .map(|(register, value)| {
+                let register = register as u32;
+                let name = match register {
+                    0 => "this".to_string(),
+                    _ => names
+                        .get(&register)
+                        .map(|name| name.to_string())
+                        .unwrap_or_else(|| format!("_local{register}")),
+                };
+                Variable {
+                    name,
+                    value: describe_value(activation, value),
+                }
+            })
+            .collect();
+        Ok(variables)
+    }
+
+    /// Evaluates an expression made of names separated by dots, such as `this.x`.
+    fn evaluate(&mut self, frame: u32, expression: &str) -> Result<String, String> {
+        let registers = self.registers(frame)?;
+
+        let mut names = expression.split('.').map(str::trim);
+        if names.clone().any(str::is_empty) {
+            return Err(format!("Unsupported expression: {expression}"));
+        }
+
+        let first = names.next().unwrap_or_default();
+        let mut value = self.lookup(&registers, first)?;
+        let activation = &mut *self.activation;
+        for name in names {
+            let Some(object) = value.as_object() else {
+                return Err(format!(
+                    "Can't read {name} of {}",
+                    describe_value(activation, value)
+                ));
+            };
+            let name = AvmString::new_utf8(activation.gc(), name);
+            value = read_property(activation, object, name).unwrap_or(Ok(Value::Undefined))?;
+        }
+        Ok(describe_value(activation, value))
+    }
+}
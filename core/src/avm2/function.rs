@@ -83,6 +83,16 @@ impl<'gc> BoundMethod<'gc> {
         )
     }
 
+    /// The receiver this function is always called with, if it's bound.
+    pub fn bound_receiver(&self) -> Option<Object<'gc>> {
+        self.bound_receiver
+    }
+
+    /// The scope this function was defined in.
+    pub fn scope(&self) -> ScopeChain<'gc> {
+        self.scope
+    }
+
     pub fn bound_class(&self) -> Option<Class<'gc>> {
         self.bound_class
     }
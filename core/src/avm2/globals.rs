@@ -85,6 +85,12 @@ pub struct SystemClasses<'gc> {
     pub textfield: ClassObject<'gc>,
     pub textformat: ClassObject<'gc>,
     pub graphics: ClassObject<'gc>,
+    pub graphicsbitmapfill: ClassObject<'gc>,
+    pub graphicsendfill: ClassObject<'gc>,
+    pub graphicsgradientfill: ClassObject<'gc>,
+    pub graphicspath: ClassObject<'gc>,
+    pub graphicssolidfill: ClassObject<'gc>,
+    pub graphicsstroke: ClassObject<'gc>,
     pub loader: ClassObject<'gc>,
     pub loaderinfo: ClassObject<'gc>,
     pub bytearray: ClassObject<'gc>,
@@ -103,6 +109,10 @@ pub struct SystemClasses<'gc> {
     pub date: ClassObject<'gc>,
     pub qname: ClassObject<'gc>,
     pub mouseevent: ClassObject<'gc>,
+    pub touchevent: ClassObject<'gc>,
+    pub transformgestureevent: ClassObject<'gc>,
+    pub gestureevent: ClassObject<'gc>,
+    pub pressandtapgestureevent: ClassObject<'gc>,
     pub progressevent: ClassObject<'gc>,
     pub httpstatusevent: ClassObject<'gc>,
     pub textevent: ClassObject<'gc>,
@@ -158,10 +168,15 @@ pub struct SystemClasses<'gc> {
     pub netstatusevent: ClassObject<'gc>,
     pub shaderfilter: ClassObject<'gc>,
     pub statusevent: ClassObject<'gc>,
+    pub syncevent: ClassObject<'gc>,
+    pub stackframe: ClassObject<'gc>,
+    pub newobjectsample: ClassObject<'gc>,
+    pub deleteobjectsample: ClassObject<'gc>,
     pub asyncerrorevent: ClassObject<'gc>,
     pub contextmenuevent: ClassObject<'gc>,
     pub filereference: ClassObject<'gc>,
     pub filefilter: ClassObject<'gc>,
+    pub file: ClassObject<'gc>,
     pub font: ClassObject<'gc>,
     pub textline: ClassObject<'gc>,
     pub sampledataevent: ClassObject<'gc>,
@@ -171,6 +186,19 @@ pub struct SystemClasses<'gc> {
     pub id3info: ClassObject<'gc>,
     pub textrun: ClassObject<'gc>,
     pub sharedobject: ClassObject<'gc>,
+    pub numberparseresult: ClassObject<'gc>,
+    pub currencyparseresult: ClassObject<'gc>,
+    pub worker: ClassObject<'gc>,
+    pub workerdomain: ClassObject<'gc>,
+    pub messagechannel: ClassObject<'gc>,
+    pub mutex: ClassObject<'gc>,
+    pub condition: ClassObject<'gc>,
+    pub gameinput: ClassObject<'gc>,
+    pub gameinputevent: ClassObject<'gc>,
+    pub gameinputdevice: ClassObject<'gc>,
+    pub gameinputcontrol: ClassObject<'gc>,
+    pub sqlresult: ClassObject<'gc>,
+    pub sqlerror: ClassObject<'gc>,
 }
 
 #[derive(Clone, Collect)]
@@ -204,6 +232,12 @@ pub struct SystemClassDefs<'gc> {
     pub graphicssolidfill: Class<'gc>,
     pub graphicsshaderfill: Class<'gc>,
     pub graphicsstroke: Class<'gc>,
+    pub pngencoderoptions: Class<'gc>,
+    pub jpegencoderoptions: Class<'gc>,
+    pub jpegxrencoderoptions: Class<'gc>,
+    pub groupelement: Class<'gc>,
+    pub graphicelement: Class<'gc>,
+    pub eastasianjustifier: Class<'gc>,
 
     pub cubetexture: Class<'gc>,
     pub rectangletexture: Class<'gc>,
@@ -245,6 +279,12 @@ impl<'gc> SystemClasses<'gc> {
             textfield: object,
             textformat: object,
             graphics: object,
+            graphicsbitmapfill: object,
+            graphicsendfill: object,
+            graphicsgradientfill: object,
+            graphicspath: object,
+            graphicssolidfill: object,
+            graphicsstroke: object,
             loader: object,
             loaderinfo: object,
             bytearray: object,
@@ -261,6 +301,10 @@ impl<'gc> SystemClasses<'gc> {
             date: object,
             qname: object,
             mouseevent: object,
+            touchevent: object,
+            transformgestureevent: object,
+            gestureevent: object,
+            pressandtapgestureevent: object,
             progressevent: object,
             httpstatusevent: object,
             textevent: object,
@@ -316,10 +360,15 @@ impl<'gc> SystemClasses<'gc> {
             netstatusevent: object,
             shaderfilter: object,
             statusevent: object,
+            syncevent: object,
+            stackframe: object,
+            newobjectsample: object,
+            deleteobjectsample: object,
             asyncerrorevent: object,
             contextmenuevent: object,
             filereference: object,
             filefilter: object,
+            file: object,
             font: object,
             textline: object,
             sampledataevent: object,
@@ -329,6 +378,19 @@ impl<'gc> SystemClasses<'gc> {
             id3info: object,
             textrun: object,
             sharedobject: object,
+            numberparseresult: object,
+            currencyparseresult: object,
+            worker: object,
+            workerdomain: object,
+            messagechannel: object,
+            mutex: object,
+            condition: object,
+            gameinput: object,
+            gameinputevent: object,
+            gameinputdevice: object,
+            gameinputcontrol: object,
+            sqlresult: object,
+            sqlerror: object,
         }
     }
 }
@@ -371,6 +433,12 @@ impl<'gc> SystemClassDefs<'gc> {
             graphicssolidfill: object,
             graphicsshaderfill: object,
             graphicsstroke: object,
+            pngencoderoptions: object,
+            jpegencoderoptions: object,
+            jpegxrencoderoptions: object,
+            groupelement: object,
+            graphicelement: object,
+            eastasianjustifier: object,
 
             cubetexture: object,
             rectangletexture: object,
@@ -873,6 +941,16 @@ pub fn init_native_system_classes(activation: &mut Activation<'_, '_>) {
             ("flash.display", "Scene", scene),
             ("flash.display", "FrameLabel", framelabel),
             ("flash.display", "Graphics", graphics),
+            ("flash.display", "GraphicsBitmapFill", graphicsbitmapfill),
+            ("flash.display", "GraphicsEndFill", graphicsendfill),
+            (
+                "flash.display",
+                "GraphicsGradientFill",
+                graphicsgradientfill
+            ),
+            ("flash.display", "GraphicsPath", graphicspath),
+            ("flash.display", "GraphicsSolidFill", graphicssolidfill),
+            ("flash.display", "GraphicsStroke", graphicsstroke),
             ("flash.display", "Loader", loader),
             ("flash.display", "LoaderInfo", loaderinfo),
             ("flash.display", "MorphShape", morphshape),
@@ -912,10 +990,26 @@ pub fn init_native_system_classes(activation: &mut Activation<'_, '_>) {
             ("flash.events", "SecurityErrorEvent", securityerrorevent),
             ("flash.events", "IOErrorEvent", ioerrorevent),
             ("flash.events", "MouseEvent", mouseevent),
+            ("flash.events", "TouchEvent", touchevent),
+            (
+                "flash.events",
+                "TransformGestureEvent",
+                transformgestureevent
+            ),
+            ("flash.events", "GestureEvent", gestureevent),
+            (
+                "flash.events",
+                "PressAndTapGestureEvent",
+                pressandtapgestureevent
+            ),
             ("flash.events", "FullScreenEvent", fullscreenevent),
             ("flash.events", "UncaughtErrorEvents", uncaughterrorevents),
             ("flash.events", "NetStatusEvent", netstatusevent),
             ("flash.events", "StatusEvent", statusevent),
+            ("flash.events", "SyncEvent", syncevent),
+            ("flash.sampler", "StackFrame", stackframe),
+            ("flash.sampler", "NewObjectSample", newobjectsample),
+            ("flash.sampler", "DeleteObjectSample", deleteobjectsample),
             ("flash.events", "AsyncErrorEvent", asyncerrorevent),
             ("flash.events", "ContextMenuEvent", contextmenuevent),
             ("flash.events", "FocusEvent", focusevent),
@@ -931,10 +1025,32 @@ pub fn init_native_system_classes(activation: &mut Activation<'_, '_>) {
             ("flash.net", "URLVariables", urlvariables),
             ("flash.net", "FileReference", filereference),
             ("flash.net", "FileFilter", filefilter),
+            ("flash.filesystem", "File", file),
+            ("flash.data", "SQLResult", sqlresult),
+            ("flash.errors", "SQLError", sqlerror),
             ("flash.net", "SharedObject", sharedobject),
+            (
+                "flash.globalization",
+                "NumberParseResult",
+                numberparseresult
+            ),
+            (
+                "flash.globalization",
+                "CurrencyParseResult",
+                currencyparseresult
+            ),
             ("flash.utils", "ByteArray", bytearray),
             ("flash.utils", "Dictionary", dictionary),
             ("flash.system", "ApplicationDomain", application_domain),
+            ("flash.system", "Worker", worker),
+            ("flash.system", "WorkerDomain", workerdomain),
+            ("flash.system", "MessageChannel", messagechannel),
+            ("flash.concurrent", "Mutex", mutex),
+            ("flash.concurrent", "Condition", condition),
+            ("flash.events", "GameInputEvent", gameinputevent),
+            ("flash.ui", "GameInput", gameinput),
+            ("flash.ui", "GameInputDevice", gameinputdevice),
+            ("flash.ui", "GameInputControl", gameinputcontrol),
             ("flash.text", "Font", font),
             ("flash.text", "StaticText", statictext),
             ("flash.text", "TextFormat", textformat),
@@ -982,6 +1098,20 @@ pub fn init_native_system_classes(activation: &mut Activation<'_, '_>) {
             ),
             ("flash.display", "GraphicsSolidFill", graphicssolidfill),
             ("flash.display", "GraphicsStroke", graphicsstroke),
+            ("flash.display", "PNGEncoderOptions", pngencoderoptions),
+            ("flash.display", "JPEGEncoderOptions", jpegencoderoptions),
+            (
+                "flash.display",
+                "JPEGXREncoderOptions",
+                jpegxrencoderoptions
+            ),
+            ("flash.text.engine", "GroupElement", groupelement),
+            ("flash.text.engine", "GraphicElement", graphicelement),
+            (
+                "flash.text.engine",
+                "EastAsianJustifier",
+                eastasianjustifier
+            ),
             ("flash.display3D.textures", "CubeTexture", cubetexture),
             (
                 "flash.display3D.textures",
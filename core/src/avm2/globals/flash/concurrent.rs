@@ -0,0 +1,4 @@
+//! `flash.concurrent` namespace
+
+pub mod condition;
+pub mod mutex;
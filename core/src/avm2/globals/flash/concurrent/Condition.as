@@ -1,8 +1,24 @@
 package flash.concurrent {
     [API("684")]
     public final class Condition {
-        public static const isSupported: Boolean = false;
+        public static const isSupported: Boolean = true;
 
-        public function Condition(mutex: Mutex) {}
+        // The ID of the condition in the worker registry.
+        internal var _id:int;
+
+        public function Condition(mutex: Mutex) {
+            if (mutex == null) {
+                throw new ArgumentError("Error #2007: Parameter mutex must be non-null.", 2007);
+            }
+            this.init(mutex);
+        }
+
+        private native function init(mutex:Mutex):void;
+
+        public native function get mutex():Mutex;
+
+        public native function notify():void;
+        public native function notifyAll():void;
+        public native function wait(timeout:Number = -1):Boolean;
     }
-}
\ No newline at end of file
+}
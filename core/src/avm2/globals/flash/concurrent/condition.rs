@@ -0,0 +1,146 @@
+//! `flash.concurrent.Condition` native methods
+
+use crate::avm2::error::illegal_operation_error;
+use crate::avm2::globals::flash::concurrent::mutex::mutex_id;
+use crate::avm2::globals::flash::system::worker::{object_for_handle, shared_id};
+use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Multiname, Object, Value};
+use crate::worker::{SharedHandle, Wait};
+
+fn condition_id<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<usize, Error<'gc>> {
+    let namespace = activation.avm2().namespaces.flash_concurrent_internal;
+    Ok(shared_id(activation, this, namespace)? as usize)
+}
+
+/// Throws unless the current worker owns the mutex of a condition.
+fn check_owned<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    message: &str,
+    code: u32,
+) -> Result<(), Error<'gc>> {
+    let id = condition_id(activation, this)?;
+    let current = activation.context.workers.current();
+    let registry = activation.context.workers.registry();
+    let owner = registry.mutex_owner(registry.condition_mutex(id));
+    drop(registry);
+
+    if owner != Some(current) {
+        return Err(Error::AvmError(illegal_operation_error(
+            activation, message, code,
+        )?));
+    }
+    Ok(())
+}
+
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mutex = args.get_object(activation, 0, "mutex")?;
+    let mutex = mutex_id(activation, mutex)?;
+
+    let id = activation
+        .context
+        .workers
+        .registry()
+        .create_condition(mutex);
+    let name = Multiname::new(
+        activation.avm2().namespaces.flash_concurrent_internal,
+        "_id",
+    );
+    this.set_property(&name, (id as u32).into(), activation)?;
+    activation
+        .context
+        .workers
+        .insert_object(SharedHandle::Condition(id), this);
+    Ok(Value::Undefined)
+}
+
+pub fn get_mutex<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = condition_id(activation, this)?;
+    let mutex = activation.context.workers.registry().condition_mutex(id);
+    Ok(object_for_handle(activation, SharedHandle::Mutex(mutex))?.into())
+}
+
+pub fn notify<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    check_owned(
+        activation,
+        this,
+        "Error #1516: The condition cannot notify because the mutex is not owned by the current worker.",
+        1516,
+    )?;
+
+    let id = condition_id(activation, this)?;
+    activation
+        .context
+        .workers
+        .registry()
+        .notify_condition(id, false);
+    Ok(Value::Undefined)
+}
+
+pub fn notify_all<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    check_owned(
+        activation,
+        this,
+        "Error #1517: The condition cannot notifyAll because the mutex is not owned by the current worker.",
+        1517,
+    )?;
+
+    let id = condition_id(activation, this)?;
+    activation
+        .context
+        .workers
+        .registry()
+        .notify_condition(id, true);
+    Ok(Value::Undefined)
+}
+
+/// Implements `Condition.wait`.
+///
+/// This releases the mutex and parks the worker, which stops the current
+/// script. The worker owns the mutex again once it's resumed, after it has
+/// been notified or the timeout has passed.
+pub fn wait<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    check_owned(
+        activation,
+        this,
+        "Error #1518: The condition cannot wait because the mutex is not owned by the current worker.",
+        1518,
+    )?;
+
+    // A negative timeout waits until the condition is notified.
+    let timeout = args.get_f64(activation, 0)?;
+    let timeout = (timeout >= 0.0).then_some(timeout);
+
+    let id = condition_id(activation, this)?;
+    let workers = &activation.context.workers;
+    let depth = workers.registry().start_waiting(id, workers.current());
+    Err(workers.park(Wait::Notify {
+        condition: id,
+        depth,
+        timeout,
+    }))
+}
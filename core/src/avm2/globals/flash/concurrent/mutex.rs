@@ -0,0 +1,90 @@
+//! `flash.concurrent.Mutex` native methods
+
+use crate::avm2::error::illegal_operation_error;
+use crate::avm2::globals::flash::system::worker::shared_id;
+use crate::avm2::object::TObject;
+use crate::avm2::{Activation, Error, Multiname, Object, Value};
+use crate::worker::{SharedHandle, Wait};
+
+pub(super) fn mutex_id<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<usize, Error<'gc>> {
+    let namespace = activation.avm2().namespaces.flash_concurrent_internal;
+    Ok(shared_id(activation, this, namespace)? as usize)
+}
+
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = activation.context.workers.registry().create_mutex();
+    let name = Multiname::new(
+        activation.avm2().namespaces.flash_concurrent_internal,
+        "_id",
+    );
+    this.set_property(&name, (id as u32).into(), activation)?;
+    activation
+        .context
+        .workers
+        .insert_object(SharedHandle::Mutex(id), this);
+    Ok(Value::Undefined)
+}
+
+/// Implements `Mutex.lock`.
+///
+/// If another worker owns the mutex, this worker is parked until it can own
+/// the mutex, which stops the current script.
+pub fn lock<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = mutex_id(activation, this)?;
+    let workers = &activation.context.workers;
+    if !workers.registry().lock_mutex(id, workers.current()) {
+        return Err(workers.park(Wait::Lock {
+            mutex: id,
+            depth: 1,
+        }));
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn try_lock<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = mutex_id(activation, this)?;
+    let current = activation.context.workers.current();
+    Ok(activation
+        .context
+        .workers
+        .registry()
+        .lock_mutex(id, current)
+        .into())
+}
+
+pub fn unlock<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = mutex_id(activation, this)?;
+    let current = activation.context.workers.current();
+    if !activation
+        .context
+        .workers
+        .registry()
+        .unlock_mutex(id, current)
+    {
+        return Err(Error::AvmError(illegal_operation_error(
+            activation,
+            "Error #1522: Mutex cannot be unlocked because it is not owned by the current worker.",
+            1522,
+        )?));
+    }
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,4 @@
+//! `flash.data` namespace
+
+pub mod encrypted_local_store;
+pub mod sql_connection;
@@ -0,0 +1,18 @@
+package flash.data {
+    import flash.utils.ByteArray;
+
+    [API("661")]
+    public class EncryptedLocalStore {
+        public static function get isSupported():Boolean {
+            return true;
+        }
+
+        public static native function getItem(name:String):ByteArray;
+
+        public static native function setItem(name:String, data:ByteArray, stronglyBound:Boolean = false):void;
+
+        public static native function removeItem(name:String):void;
+
+        public static native function reset():void;
+    }
+}
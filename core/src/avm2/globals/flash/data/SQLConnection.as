@@ -0,0 +1,198 @@
+package flash.data {
+    import flash.errors.IllegalOperationError;
+    import flash.errors.SQLError;
+    import flash.errors.SQLErrorOperation;
+    import flash.events.EventDispatcher;
+    import flash.events.SQLErrorEvent;
+    import flash.events.SQLEvent;
+    import flash.filesystem.File;
+    import flash.net.Responder;
+    import flash.utils.ByteArray;
+    import flash.utils.setTimeout;
+
+    [API("661")]
+    public class SQLConnection extends EventDispatcher {
+        // The handle of the open database, or -1 if the connection is closed.
+        internal var _handle:int = -1;
+
+        // Whether the connection was opened with `openAsync`.
+        internal var _async:Boolean = false;
+
+        internal var _lastInsertRowID:Number = 0;
+
+        private var _autoCompact:Boolean = false;
+        private var _pageSize:uint = 1024;
+        private var _cacheSize:uint = 2000;
+
+        public function SQLConnection() {
+        }
+
+        public static native function get isSupported():Boolean;
+
+        // The database is closed when this connection is garbage collected.
+        private native function openDatabase(file:File, openMode:String):int;
+
+        private static native function closeDatabase(handle:int):void;
+
+        private static native function isInTransaction(handle:int):Boolean;
+
+        private static native function getTotalChanges(handle:int):Number;
+
+        internal static native function executeStatement(handle:int, operation:String, text:String, names:Array, values:Array, itemClass:Class):SQLResult;
+
+        internal static native function callResponder(responder:Responder, success:Boolean, value:*):void;
+
+        public function get connected():Boolean {
+            return this._handle != -1;
+        }
+
+        public function get inTransaction():Boolean {
+            return this.connected && isInTransaction(this._handle);
+        }
+
+        public function get lastInsertRowID():Number {
+            return this._lastInsertRowID;
+        }
+
+        public function get totalChanges():Number {
+            return this.connected ? getTotalChanges(this._handle) : 0;
+        }
+
+        public function get autoCompact():Boolean {
+            return this._autoCompact;
+        }
+
+        public function get pageSize():uint {
+            return this._pageSize;
+        }
+
+        public function get cacheSize():uint {
+            return this._cacheSize;
+        }
+
+        public function set cacheSize(value:uint):void {
+            this._cacheSize = value;
+        }
+
+        public function open(reference:Object = null, openMode:String = "create", autoCompact:Boolean = false, pageSize:int = 1024, encryptionKey:ByteArray = null):void {
+            this.openConnection(reference, openMode, autoCompact, pageSize, encryptionKey);
+            this._async = false;
+        }
+
+        public function openAsync(reference:Object = null, openMode:String = "create", responder:Responder = null, autoCompact:Boolean = false, pageSize:int = 1024, encryptionKey:ByteArray = null):void {
+            try {
+                this.openConnection(reference, openMode, autoCompact, pageSize, encryptionKey);
+            } catch (error:SQLError) {
+                this.dispatchFailure(error, responder);
+                return;
+            }
+            this._async = true;
+            this.dispatchSuccess(SQLEvent.OPEN, responder);
+        }
+
+        private function openConnection(reference:Object, openMode:String, autoCompact:Boolean, pageSize:int, encryptionKey:ByteArray):void {
+            if (this.connected) {
+                throw new IllegalOperationError("Error #3101: Database connection is already open.", 3101);
+            }
+            if (reference != null && !(reference is File)) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            if (openMode != SQLMode.CREATE && openMode != SQLMode.READ && openMode != SQLMode.UPDATE) {
+                throw new ArgumentError("Error #2008: Parameter openMode must be one of the accepted values.", 2008);
+            }
+            if (encryptionKey != null) {
+                throw new SQLError(SQLErrorOperation.OPEN, "Encrypted databases are not supported.", "Error #3138: Encrypted databases are not supported.", 3138);
+            }
+
+            this._handle = this.openDatabase(reference as File, openMode);
+            this._autoCompact = autoCompact;
+            this._pageSize = pageSize;
+            this._lastInsertRowID = 0;
+        }
+
+        public function close(responder:Responder = null):void {
+            if (this.connected) {
+                // The database is closed even if its changes can't be saved.
+                var handle:int = this._handle;
+                this._handle = -1;
+                if (!this._async) {
+                    closeDatabase(handle);
+                    return;
+                }
+                try {
+                    closeDatabase(handle);
+                } catch (error:SQLError) {
+                    this.dispatchFailure(error, responder);
+                    return;
+                }
+            }
+            if (this._async) {
+                this.dispatchSuccess(SQLEvent.CLOSE, responder);
+            }
+        }
+
+        public function begin(option:String = null, responder:Responder = null):void {
+            if (option == null) {
+                option = SQLTransactionLockType.DEFERRED;
+            }
+            if (option != SQLTransactionLockType.DEFERRED && option != SQLTransactionLockType.IMMEDIATE && option != SQLTransactionLockType.EXCLUSIVE) {
+                throw new ArgumentError("Error #2008: Parameter option must be one of the accepted values.", 2008);
+            }
+            this.runTransactionStatement(SQLErrorOperation.BEGIN, "BEGIN " + option.toUpperCase(), SQLEvent.BEGIN, responder);
+        }
+
+        public function commit(responder:Responder = null):void {
+            this.runTransactionStatement(SQLErrorOperation.COMMIT, "COMMIT", SQLEvent.COMMIT, responder);
+        }
+
+        public function rollback(responder:Responder = null):void {
+            this.runTransactionStatement(SQLErrorOperation.ROLLBACK, "ROLLBACK", SQLEvent.ROLLBACK, responder);
+        }
+
+        private function runTransactionStatement(operation:String, text:String, eventType:String, responder:Responder):void {
+            this.checkConnected();
+            if (!this._async) {
+                executeStatement(this._handle, operation, text, [], [], null);
+                return;
+            }
+
+            try {
+                executeStatement(this._handle, operation, text, [], [], null);
+            } catch (error:SQLError) {
+                this.dispatchFailure(error, responder);
+                return;
+            }
+            this.dispatchSuccess(eventType, responder);
+        }
+
+        internal function checkConnected():void {
+            if (!this.connected) {
+                throw new IllegalOperationError("Error #3104: A SQLConnection must be open to perform this operation.", 3104);
+            }
+        }
+
+        // Asynchronous operations run right away, but are only reported
+        // on the next frame, as if they had run in the background.
+        private function dispatchSuccess(eventType:String, responder:Responder):void {
+            var connection:SQLConnection = this;
+            setTimeout(function():void {
+                if (responder != null) {
+                    callResponder(responder, true, new SQLEvent(eventType));
+                } else {
+                    connection.dispatchEvent(new SQLEvent(eventType));
+                }
+            }, 0);
+        }
+
+        private function dispatchFailure(error:SQLError, responder:Responder):void {
+            var connection:SQLConnection = this;
+            setTimeout(function():void {
+                if (responder != null) {
+                    callResponder(responder, false, error);
+                } else {
+                    connection.dispatchEvent(new SQLErrorEvent(SQLErrorEvent.ERROR, false, false, error));
+                }
+            }, 0);
+        }
+    }
+}
@@ -0,0 +1,32 @@
+package flash.data {
+    [API("661")]
+    public class SQLResult {
+        private var _data:Array;
+        private var _complete:Boolean;
+        private var _rowsAffected:Number;
+        private var _lastInsertRowID:Number;
+
+        public function SQLResult(data:Array = null, complete:Boolean = true, rowsAffected:Number = 0, lastInsertRowID:Number = 0) {
+            this._data = data;
+            this._complete = complete;
+            this._rowsAffected = rowsAffected;
+            this._lastInsertRowID = lastInsertRowID;
+        }
+
+        public function get data():Array {
+            return this._data;
+        }
+
+        public function get complete():Boolean {
+            return this._complete;
+        }
+
+        public function get rowsAffected():Number {
+            return this._rowsAffected;
+        }
+
+        public function get lastInsertRowID():Number {
+            return this._lastInsertRowID;
+        }
+    }
+}
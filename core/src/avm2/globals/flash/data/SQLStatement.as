@@ -0,0 +1,139 @@
+package flash.data {
+    import flash.errors.IllegalOperationError;
+    import flash.errors.SQLError;
+    import flash.errors.SQLErrorOperation;
+    import flash.events.EventDispatcher;
+    import flash.events.SQLErrorEvent;
+    import flash.events.SQLEvent;
+    import flash.net.Responder;
+    import flash.utils.setTimeout;
+
+    [API("661")]
+    public class SQLStatement extends EventDispatcher {
+        private var _sqlConnection:SQLConnection = null;
+        private var _text:String = null;
+        private var _parameters:Object = {};
+        private var _itemClass:Class = null;
+        private var _executing:Boolean = false;
+
+        // Results that haven't been taken with `getResult` yet.
+        private var _results:Array = [];
+
+        public function SQLStatement() {
+        }
+
+        public function get sqlConnection():SQLConnection {
+            return this._sqlConnection;
+        }
+
+        public function set sqlConnection(value:SQLConnection):void {
+            this.checkNotExecuting();
+            this._sqlConnection = value;
+        }
+
+        public function get text():String {
+            return this._text;
+        }
+
+        public function set text(value:String):void {
+            this.checkNotExecuting();
+            this._text = value;
+        }
+
+        public function get itemClass():Class {
+            return this._itemClass;
+        }
+
+        public function set itemClass(value:Class):void {
+            this.checkNotExecuting();
+            this._itemClass = value;
+        }
+
+        public function get parameters():Object {
+            return this._parameters;
+        }
+
+        public function get executing():Boolean {
+            return this._executing;
+        }
+
+        public function clearParameters():void {
+            this._parameters = {};
+        }
+
+        public function execute(prefetch:int = -1, responder:Responder = null):void {
+            this.checkNotExecuting();
+            if (this._sqlConnection == null || this._text == null) {
+                throw new IllegalOperationError("Error #3107: Statement text and connection must be set before executing.", 3107);
+            }
+            this._sqlConnection.checkConnected();
+
+            // Every row is always fetched at once, so `prefetch` is ignored.
+            var names:Array = [];
+            var values:Array = [];
+            for (var name:String in this._parameters) {
+                names.push(name);
+                values.push(this._parameters[name]);
+            }
+
+            var connection:SQLConnection = this._sqlConnection;
+            if (!connection._async) {
+                this.storeResult(SQLConnection.executeStatement(connection._handle, SQLErrorOperation.EXECUTE, this._text, names, values, this._itemClass));
+                return;
+            }
+
+            var result:SQLResult = null;
+            var error:SQLError = null;
+            try {
+                result = this.storeResult(SQLConnection.executeStatement(connection._handle, SQLErrorOperation.EXECUTE, this._text, names, values, this._itemClass));
+            } catch (e:SQLError) {
+                error = e;
+            }
+
+            // Asynchronous statements are only reported on the next frame.
+            var statement:SQLStatement = this;
+            this._executing = true;
+            setTimeout(function():void {
+                statement._executing = false;
+                if (error != null) {
+                    if (responder != null) {
+                        SQLConnection.callResponder(responder, false, error);
+                    } else {
+                        statement.dispatchEvent(new SQLErrorEvent(SQLErrorEvent.ERROR, false, false, error));
+                    }
+                } else if (responder != null) {
+                    statement._results.shift();
+                    SQLConnection.callResponder(responder, true, result);
+                } else {
+                    statement.dispatchEvent(new SQLEvent(SQLEvent.RESULT));
+                }
+            }, 0);
+        }
+
+        private function storeResult(result:SQLResult):SQLResult {
+            if (result.lastInsertRowID != 0) {
+                this._sqlConnection._lastInsertRowID = result.lastInsertRowID;
+            }
+            this._results.push(result);
+            return result;
+        }
+
+        public function getResult():SQLResult {
+            return this._results.length > 0 ? this._results.shift() : null;
+        }
+
+        public function next(prefetch:int = -1, responder:Responder = null):void {
+            // Results are never split, so there's never anything left to fetch.
+            throw new IllegalOperationError("Error #3108: Operation is not permitted when the statement has no pending results.", 3108);
+        }
+
+        public function cancel():void {
+        }
+
+        private function checkNotExecuting():void {
+            if (this._executing) {
+                throw new IllegalOperationError("Error #3105: Operation is not permitted while a statement is executing.", 3105);
+            }
+        }
+    }
+}
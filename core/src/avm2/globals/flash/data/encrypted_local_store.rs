@@ -0,0 +1,154 @@
+//! `flash.data.EncryptedLocalStore` native methods
+//!
+//! All items are kept together in a single entry of the storage backend.
+//! Ruffle doesn't encrypt them; they're only as private as shared objects are.
+
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::object::{ByteArrayObject, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::context::UpdateContext;
+
+/// The key of the storage backend entry holding the store of this movie.
+///
+/// Like shared objects, stores are kept separately for each host and movie path.
+fn storage_key(context: &UpdateContext<'_>) -> Option<String> {
+    let url = url::Url::parse(context.swf.url()).ok()?;
+    let mut movie_path = url.path();
+    movie_path = movie_path.strip_prefix('/').unwrap_or(movie_path);
+    movie_path = movie_path.strip_suffix('/').unwrap_or(movie_path);
+
+    let host = if url.scheme() == "file" {
+        if let [_, b':', b'/', ..] = movie_path.as_bytes() {
+            movie_path = &movie_path[3..];
+        }
+        "localhost"
+    } else {
+        url.host_str().unwrap_or_default()
+    };
+
+    let key = format!("{host}/{movie_path}/#EncryptedLocalStore");
+    if key.split('/').any(|s| s.starts_with('.')) {
+        return None;
+    }
+    Some(key)
+}
+
+/// Reads all items of the store.
+///
+/// Each item is stored as its name and then its data, both prefixed with their length.
+fn load_items(context: &UpdateContext<'_>) -> Vec<(String, Vec<u8>)> {
+    let Some(data) = storage_key(context).and_then(|key| context.storage.get(&key)) else {
+        return Vec::new();
+    };
+
+    fn read_chunk<'a>(data: &mut &'a [u8]) -> Option<&'a [u8]> {
+        let (len, rest) = data.split_first_chunk::<4>()?;
+        let len = u32::from_le_bytes(*len) as usize;
+        if rest.len() < len {
+            return None;
+        }
+        let (chunk, rest) = rest.split_at(len);
+        *data = rest;
+        Some(chunk)
+    }
+
+    let mut items = Vec::new();
+    let mut data = &data[..];
+    while !data.is_empty() {
+        let (Some(name), Some(value)) = (read_chunk(&mut data), read_chunk(&mut data)) else {
+            tracing::warn!("EncryptedLocalStore: Ignoring corrupted store");
+            break;
+        };
+        items.push((String::from_utf8_lossy(name).into_owned(), value.to_vec()));
+    }
+    items
+}
+
+fn save_items(context: &mut UpdateContext<'_>, items: &[(String, Vec<u8>)]) {
+    let Some(key) = storage_key(context) else {
+        tracing::error!("EncryptedLocalStore: Unable to determine where to store items");
+        return;
+    };
+
+    let mut data = Vec::new();
+    for (name, value) in items {
+        data.extend_from_slice(&(name.len() as u32).to_le_bytes());
+        data.extend_from_slice(name.as_bytes());
+        data.extend_from_slice(&(value.len() as u32).to_le_bytes());
+        data.extend_from_slice(value);
+    }
+
+    if !context.storage.put(&key, &data) {
+        tracing::error!("EncryptedLocalStore: Unable to save items");
+    }
+}
+
+pub fn get_item<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string_non_null(activation, 0, "name")?.to_string();
+
+    let items = load_items(activation.context);
+    match items.into_iter().find(|(item, _)| *item == name) {
+        Some((_, data)) => {
+            let storage = ByteArrayStorage::from_vec(data);
+            Ok(ByteArrayObject::from_storage(activation, storage)?.into())
+        }
+        None => Ok(Value::Null),
+    }
+}
+
+pub fn set_item<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string_non_null(activation, 0, "name")?.to_string();
+    let data = args.get_object(activation, 1, "data")?;
+    let data = data
+        .as_bytearray()
+        .map(|bytes| bytes.bytes().to_vec())
+        .unwrap_or_default();
+    // `stronglyBound` only matters for signed applications, so it's ignored.
+
+    let mut items = load_items(activation.context);
+    match items.iter_mut().find(|(item, _)| *item == name) {
+        Some((_, value)) => *value = data,
+        None => items.push((name, data)),
+    }
+    save_items(activation.context, &items);
+
+    Ok(Value::Undefined)
+}
+
+pub fn remove_item<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string_non_null(activation, 0, "name")?.to_string();
+
+    let mut items = load_items(activation.context);
+    let len = items.len();
+    items.retain(|(item, _)| *item != name);
+    if items.len() != len {
+        save_items(activation.context, &items);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn reset<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(key) = storage_key(activation.context) {
+        activation.context.storage.remove_key(&key);
+    }
+
+    Ok(Value::Undefined)
+}
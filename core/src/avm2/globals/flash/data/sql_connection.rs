@@ -0,0 +1,288 @@
+//! `flash.data.SQLConnection` native methods
+
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::globals::flash::filesystem::file::file_path;
+use crate::avm2::object::{ArrayObject, ByteArrayObject, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, ArrayStorage, Error, Object, Value};
+use crate::backend::filesystem::FilesystemError;
+use crate::sql::{self, SqlConnectionHandle, SqlError, SqlMode, SqlParameter, SqlValue};
+use crate::string::AvmString;
+
+/// Creates a `SQLError` describing a failed operation.
+fn sql_error<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    operation: AvmString<'gc>,
+    error: SqlError,
+) -> Error<'gc> {
+    let (message, code, details) = match error {
+        SqlError::Unsupported | SqlError::Filesystem(FilesystemError::NotFound) => (
+            "Error #3125: Unable to open the database file.",
+            3125,
+            error.to_string(),
+        ),
+        SqlError::Filesystem(FilesystemError::AccessDenied) => (
+            "Error #3122: Attempted to write to a read-only database.",
+            3122,
+            error.to_string(),
+        ),
+        SqlError::NotConnected => (
+            "Error #3104: A SQLConnection must be open to perform this operation.",
+            3104,
+            error.to_string(),
+        ),
+        SqlError::Filesystem(FilesystemError::Other(details)) => {
+            tracing::warn!("SQL database I/O error: {details}");
+            ("Error #3126: Disk I/O error occurred.", 3126, details)
+        }
+        SqlError::Filesystem(_) => (
+            "Error #3126: Disk I/O error occurred.",
+            3126,
+            error.to_string(),
+        ),
+        SqlError::Database(details) => ("Error #3115: SQL Error.", 3115, details),
+    };
+
+    let details = AvmString::new_utf8(activation.gc(), details);
+    let message = AvmString::new_utf8(activation.gc(), message);
+    let class = activation.avm2().classes().sqlerror;
+    match class.construct(
+        activation,
+        &[
+            operation.into(),
+            details.into(),
+            message.into(),
+            code.into(),
+        ],
+    ) {
+        Ok(error) => Error::AvmError(error.into()),
+        Err(err) => err,
+    }
+}
+
+fn handle<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+) -> Result<SqlConnectionHandle, Error<'gc>> {
+    Ok(SqlConnectionHandle::from_raw(args.get_u32(activation, 0)?))
+}
+
+/// Turns a statement parameter into a value that can be bound to it.
+fn sql_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<SqlValue, Error<'gc>> {
+    Ok(match value {
+        Value::Undefined | Value::Null => SqlValue::Null,
+        Value::Bool(value) => SqlValue::Integer(value.into()),
+        Value::Integer(value) => SqlValue::Integer(value.into()),
+        Value::Number(value) => SqlValue::Real(value),
+        Value::String(value) => SqlValue::Text(value.to_string()),
+        Value::Object(object) => match object.as_bytearray() {
+            Some(bytes) => SqlValue::Blob(bytes.bytes().to_vec()),
+            None => SqlValue::Text(value.coerce_to_string(activation)?.to_string()),
+        },
+    })
+}
+
+/// Turns a value read from a database into an ActionScript value.
+fn avm_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    value: SqlValue,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(match value {
+        SqlValue::Null => Value::Null,
+        SqlValue::Integer(value) => (value as f64).into(),
+        SqlValue::Real(value) => value.into(),
+        SqlValue::Text(value) => AvmString::new_utf8(activation.gc(), value).into(),
+        SqlValue::Blob(value) => {
+            ByteArrayObject::from_storage(activation, ByteArrayStorage::from_vec(value))?.into()
+        }
+    })
+}
+
+pub fn get_is_supported<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(sql::is_supported().into())
+}
+
+pub fn open_database<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let file = args.try_get_object(activation, 0).and_then(file_path);
+    let mode = match &*args.get_string(activation, 1)?.to_utf8_lossy() {
+        "read" => SqlMode::Read,
+        "update" => SqlMode::Update,
+        _ => SqlMode::Create,
+    };
+
+    let context = &mut *activation.context;
+    match context
+        .sql_connections
+        .open(context.filesystem, file, mode, Some(this))
+    {
+        Ok(handle) => Ok(handle.raw().into()),
+        Err(e) => {
+            let operation = AvmString::new_utf8(activation.gc(), "open");
+            Err(sql_error(activation, operation, e))
+        }
+    }
+}
+
+pub fn close_database<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let handle = handle(activation, args)?;
+    let context = &mut *activation.context;
+    match context.sql_connections.close(context.filesystem, handle) {
+        Ok(()) => Ok(Value::Undefined),
+        Err(e) => {
+            let operation = AvmString::new_utf8(activation.gc(), "close");
+            Err(sql_error(activation, operation, e))
+        }
+    }
+}
+
+pub fn is_in_transaction<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let handle = handle(activation, args)?;
+    Ok(activation
+        .context
+        .sql_connections
+        .in_transaction(handle)
+        .into())
+}
+
+pub fn get_total_changes<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let handle = handle(activation, args)?;
+    let total_changes = activation.context.sql_connections.total_changes(handle);
+    Ok((total_changes as f64).into())
+}
+
+/// Executes a statement, returning its `SQLResult`.
+///
+/// Parameters are given as two arrays of names and values. Names that are
+/// numbers are the indices of unnamed parameters.
+pub fn execute_statement<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let handle = handle(activation, args)?;
+    let operation = args.get_string(activation, 1)?;
+    let text = args.get_string(activation, 2)?.to_string();
+    let names = args.get_object(activation, 3, "names")?;
+    let values = args.get_object(activation, 4, "values")?;
+    let item_class = args
+        .try_get_object(activation, 5)
+        .and_then(|class| class.as_class_object());
+
+    let names: Vec<_> = names
+        .as_array_storage()
+        .map(|names| names.iter().collect())
+        .unwrap_or_default();
+    let values: Vec<_> = values
+        .as_array_storage()
+        .map(|values| values.iter().collect())
+        .unwrap_or_default();
+
+    let mut parameters = Vec::with_capacity(names.len());
+    for (name, value) in names.into_iter().zip(values) {
+        let name = name
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?
+            .to_string();
+        let parameter = match name.parse() {
+            Ok(index) => SqlParameter::Index(index),
+            Err(_) => SqlParameter::Name(name),
+        };
+        let value = sql_value(activation, value.unwrap_or(Value::Undefined))?;
+        parameters.push((parameter, value));
+    }
+
+    let result = match activation
+        .context
+        .sql_connections
+        .execute(handle, &text, &parameters)
+    {
+        Ok(result) => result,
+        Err(e) => return Err(sql_error(activation, operation, e)),
+    };
+
+    let data = if result.rows.is_empty() {
+        Value::Null
+    } else {
+        let columns: Vec<_> = result
+            .columns
+            .iter()
+            .map(|column| AvmString::new_utf8(activation.gc(), column))
+            .collect();
+        let mut rows = Vec::with_capacity(result.rows.len());
+        for row in result.rows {
+            let item = match item_class {
+                Some(class) => class.construct(activation, &[])?,
+                None => activation
+                    .avm2()
+                    .classes()
+                    .object
+                    .construct(activation, &[])?,
+            };
+            for (column, value) in columns.iter().zip(row) {
+                let value = avm_value(activation, value)?;
+                item.set_public_property(*column, value, activation)?;
+            }
+            rows.push(item.into());
+        }
+        ArrayObject::from_storage(activation, ArrayStorage::from_args(&rows))?.into()
+    };
+
+    let class = activation.avm2().classes().sqlresult;
+    let result = class.construct(
+        activation,
+        &[
+            data,
+            true.into(),
+            (result.rows_affected as f64).into(),
+            (result.last_insert_row_id as f64).into(),
+        ],
+    )?;
+    Ok(result.into())
+}
+
+/// Calls the result or status function of a `Responder`.
+pub fn call_responder<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let responder = args.get_object(activation, 0, "responder")?;
+    let success = args.get_bool(1);
+    let value = args.get_value(2);
+
+    if let Some(responder_object) = responder.as_responder() {
+        let function = if success {
+            responder_object.result()
+        } else {
+            responder_object.status()
+        };
+        if let Some(function) = function {
+            function.call(responder.into(), &[value], activation)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
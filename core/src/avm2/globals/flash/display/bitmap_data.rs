@@ -351,6 +351,153 @@ pub fn copy_pixels_to_byte_array<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `BitmapData.encode`.
+pub fn encode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(bitmap_data) = this.as_bitmap_data() else {
+        return Ok(Value::Null);
+    };
+    bitmap_data.check_valid(activation)?;
+
+    let rectangle = args.get_object(activation, 0, "rect")?;
+    let compressor = args.get_object(activation, 1, "compressor")?;
+    let bytearray = match args.try_get_object(activation, 2) {
+        Some(bytearray) => bytearray,
+        None => ByteArrayObject::from_storage(activation, ByteArrayStorage::new())?.into(),
+    };
+
+    let (x, y, width, height) = get_rectangle_x_y_width_height(activation, rectangle)?;
+    let (width, height, pixels) = operations::get_pixels_as_rgba(
+        activation.context.renderer,
+        bitmap_data,
+        x,
+        y,
+        width,
+        height,
+    );
+
+    let class_defs = activation.avm2().class_defs();
+    let (png_options, jpeg_options, jpeg_xr_options) = (
+        class_defs.pngencoderoptions,
+        class_defs.jpegencoderoptions,
+        class_defs.jpegxrencoderoptions,
+    );
+    let compressor_class = compressor.instance_class();
+    let encoded = if compressor_class == png_options {
+        let fast_compression = compressor
+            .get_public_property("fastCompression", activation)?
+            .coerce_to_boolean();
+        if width == 0 || height == 0 {
+            return Ok(bytearray.into());
+        }
+        ruffle_render::utils::encode_png(
+            width,
+            height,
+            &pixels,
+            bitmap_data.transparency(),
+            fast_compression,
+        )?
+    } else if compressor_class == jpeg_options {
+        let quality = compressor
+            .get_public_property("quality", activation)?
+            .coerce_to_u32(activation)?
+            .clamp(1, 100);
+        if width == 0 || height == 0 {
+            return Ok(bytearray.into());
+        }
+        ruffle_render::utils::encode_jpeg(width, height, &pixels, quality as u8)?
+    } else if compressor_class == jpeg_xr_options {
+        let Some(encoded) = encode_jpeg_xr(
+            activation,
+            compressor,
+            width,
+            height,
+            &pixels,
+            bitmap_data.transparency(),
+        )?
+        else {
+            return Ok(bytearray.into());
+        };
+        encoded
+    } else {
+        return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+    };
+
+    bytearray
+        .as_bytearray_mut()
+        .unwrap()
+        .write_bytes(&encoded)
+        .map_err(|e| e.to_avm(activation))?;
+
+    Ok(bytearray.into())
+}
+
+/// Encodes pixels with `JPEGXREncoderOptions`, returning `None` if there's nothing to encode.
+#[cfg(feature = "jpegxr")]
+fn encode_jpeg_xr<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    compressor: Object<'gc>,
+    width: u32,
+    height: u32,
+    pixels: &[u8],
+    transparency: bool,
+) -> Result<Option<Vec<u8>>, Error<'gc>> {
+    let quantization = compressor
+        .get_public_property("quantization", activation)?
+        .coerce_to_u32(activation)?
+        .min(100);
+    let color_space = compressor
+        .get_public_property("colorSpace", activation)?
+        .coerce_to_string(activation)?;
+    let trim_flex_bits = compressor
+        .get_public_property("trimFlexBits", activation)?
+        .coerce_to_u32(activation)?
+        .min(15);
+    if &color_space != b"auto" && &color_space != b"4:4:4" {
+        // Chroma is always encoded at full resolution.
+        avm2_stub_method!(
+            activation,
+            "flash.display.BitmapData",
+            "encode",
+            "with JPEGXREncoderOptions.colorSpace other than 4:4:4"
+        );
+    }
+    if width == 0 || height == 0 {
+        return Ok(None);
+    }
+    Ok(Some(ruffle_render::jpegxr::encode_jpeg_xr(
+        width,
+        height,
+        pixels,
+        transparency,
+        ruffle_render::jpegxr::JpegXrOptions {
+            quantization: quantization as u8,
+            trim_flex_bits: trim_flex_bits as u8,
+        },
+    )))
+}
+
+#[cfg(not(feature = "jpegxr"))]
+fn encode_jpeg_xr<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _compressor: Object<'gc>,
+    _width: u32,
+    _height: u32,
+    _pixels: &[u8],
+    _transparency: bool,
+) -> Result<Option<Vec<u8>>, Error<'gc>> {
+    avm2_stub_method!(
+        activation,
+        "flash.display.BitmapData",
+        "encode",
+        "with JPEGXREncoderOptions"
+    );
+    Ok(None)
+}
+
 pub fn get_vector<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -5,18 +5,23 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::error::{make_error_2004, make_error_2007, make_error_2008, Error2004Type};
-use crate::avm2::globals::flash::geom::transform::object_to_matrix;
-use crate::avm2::object::{Object, TObject, VectorObject};
+use crate::avm2::globals::flash::display::bitmap_data::fill_bitmap_data_from_symbol;
+use crate::avm2::globals::flash::geom::transform::{matrix_to_object, object_to_matrix};
+use crate::avm2::object::{ArrayObject, BitmapDataObject, Object, TObject, VectorObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::vector::VectorStorage;
 use crate::avm2::{ArrayStorage, Error};
-use crate::avm2_stub_method;
-use crate::display_object::TDisplayObject;
-use crate::drawing::Drawing;
+use crate::character::Character;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+use crate::drawing::{Drawing, DrawingData};
 use crate::string::{AvmString, WStr};
+use crate::tag_utils::SwfMovie;
+use ruffle_render::bitmap::BitmapSource;
+use ruffle_render::matrix::Matrix as RenderMatrix;
 use ruffle_render::shape_utils::{DrawCommand, FillRule, GradientType};
 use std::f64::consts::FRAC_1_SQRT_2;
+use std::sync::Arc;
 use swf::{
     Color, FillStyle, Fixed16, Fixed8, Gradient, GradientInterpolation, GradientRecord,
     GradientSpread, LineCapStyle, LineJoinStyle, LineStyle, Matrix, Point, Twips,
@@ -53,7 +58,7 @@ pub fn begin_bitmap_fill<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this.as_display_object() {
-        let bitmap = args
+        let bitmap_data = args
             .get_object(activation, 0, "bitmap")?
             .as_bitmap_data()
             .expect("Bitmap argument is ensured to be a BitmapData from actionscript");
@@ -67,12 +72,12 @@ pub fn begin_bitmap_fill<'gc>(
         let is_smoothed = args.get_bool(3);
 
         let handle =
-            bitmap.bitmap_handle(activation.context.gc_context, activation.context.renderer);
+            bitmap_data.bitmap_handle(activation.context.gc_context, activation.context.renderer);
 
         let bitmap = ruffle_render::bitmap::BitmapInfo {
             handle,
-            width: bitmap.width() as u16,
-            height: bitmap.height() as u16,
+            width: bitmap_data.width() as u16,
+            height: bitmap_data.height() as u16,
         };
         let scale_matrix = Matrix::scale(
             (Twips::TWIPS_PER_PIXEL as i16).into(),
@@ -80,7 +85,7 @@ pub fn begin_bitmap_fill<'gc>(
         );
 
         if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
-            let id = draw.add_bitmap(bitmap);
+            let id = draw.add_bitmap(bitmap_data, bitmap);
             draw.set_fill_style(Some(FillStyle::Bitmap {
                 id,
                 matrix: matrix * scale_matrix,
@@ -299,6 +304,14 @@ fn caps_to_cap_style(caps: Option<AvmString>) -> LineCapStyle {
     }
 }
 
+fn cap_style_to_caps(cap_style: LineCapStyle) -> &'static str {
+    match cap_style {
+        LineCapStyle::None => "none",
+        LineCapStyle::Round => "round",
+        LineCapStyle::Square => "square",
+    }
+}
+
 fn joints_to_join_style(joints: Option<AvmString>, miter_limit: f64) -> LineJoinStyle {
     if let Some(joints) = joints {
         if &joints == b"miter" {
@@ -515,7 +528,7 @@ const UNIT_CIRCLE_POINTS: [(f64, f64); 5] = [
 /// Draw a roundrect.
 #[allow(clippy::too_many_arguments)]
 fn draw_round_rect_internal(
-    draw: &mut Drawing,
+    draw: &mut Drawing<'_>,
     x: f64,
     y: f64,
     width: f64,
@@ -971,15 +984,6 @@ pub fn draw_path<'gc>(
     let data = args.get_object(activation, 1, "data")?;
     let winding = args.get_string(activation, 2)?;
 
-    // FIXME - implement fill behavior described in the Flash docs
-    // (which is different from just running each command sequentially on `Graphics`)
-    avm2_stub_method!(
-        activation,
-        "flash.display.Graphics",
-        "drawPath",
-        "fill behavior"
-    );
-
     let commands = commands
         .as_vector_storage()
         .expect("commands is not a Vector");
@@ -1044,56 +1048,48 @@ impl TriangleCulling {
         }
     }
 
-    fn cull(self, (a, b, c): Triangle) -> bool {
-        fn triangle_orientation((a, b, c): Triangle) -> i64 {
-            let ax = a.x.get() as i64;
-            let ay = a.y.get() as i64;
-            let bx = b.x.get() as i64;
-            let by = b.y.get() as i64;
-            let cx = c.x.get() as i64;
-            let cy = c.y.get() as i64;
-            (bx - ax) * (cy - ay) - (by - ay) * (cx - ax)
-        }
-
+    fn cull(self, triangle: Triangle) -> bool {
         match self {
             Self::None => false,
-            Self::Positive => triangle_orientation((a, b, c)) >= 0,
-            Self::Negative => triangle_orientation((a, b, c)) <= 0,
+            Self::Positive => triangle_orientation(triangle) >= 0,
+            Self::Negative => triangle_orientation(triangle) <= 0,
         }
     }
 }
 
 type Triangle = (Point<Twips>, Point<Twips>, Point<Twips>);
 
+/// The indices of the vertices of a triangle.
+type TriangleIndices = (usize, usize, usize);
+
+fn triangle_orientation((a, b, c): Triangle) -> i64 {
+    let ax = a.x.get() as i64;
+    let ay = a.y.get() as i64;
+    let bx = b.x.get() as i64;
+    let by = b.y.get() as i64;
+    let cx = c.x.get() as i64;
+    let cy = c.y.get() as i64;
+    (bx - ax) * (cy - ay) - (by - ay) * (cx - ax)
+}
+
 fn draw_triangles_internal<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    drawing: &mut Drawing,
+    drawing: &mut Drawing<'gc>,
     vertices: &Object<'gc>,
     indices: Option<&Object<'gc>>,
     uvt_data: Option<&Object<'gc>>,
     culling: TriangleCulling,
 ) -> Result<(), Error<'gc>> {
-    // FIXME Triangles should be drawn using non-zero winding rule.
-    //   When fixed, update output.expected.png of avm2/graphics_draw_triangles.
-    avm2_stub_method!(
-        activation,
-        "flash.display.Graphics",
-        "drawTriangles",
-        "winding behavior"
-    );
-
-    if uvt_data.is_some() {
-        avm2_stub_method!(
-            activation,
-            "flash.display.Graphics",
-            "drawTriangles",
-            "with uvt data"
-        );
-    }
-
     let vertices = vertices
         .as_vector_storage()
         .expect("vertices is not a Vector");
+    let uvt_data = uvt_data.map(|uvt_data| {
+        uvt_data
+            .as_vector_storage()
+            .expect("uvtData is not a Vector")
+    });
+
+    let mut triangles = Vec::new();
 
     if let Some(indices) = indices {
         if vertices.length() % 2 != 0 {
@@ -1129,7 +1125,7 @@ fn draw_triangles_internal<'gc>(
             vertices: &VectorStorage<'gc>,
             indices: &mut impl Iterator<Item = Value<'gc>>,
             activation: &mut Activation<'_, 'gc>,
-        ) -> Option<Triangle> {
+        ) -> Option<(Triangle, TriangleIndices)> {
             match (indices.next(), indices.next(), indices.next()) {
                 (Some(i0), Some(i1), Some(i2)) => {
                     let i0 = i0.coerce_to_u32(activation).ok()? as usize;
@@ -1140,7 +1136,7 @@ fn draw_triangles_internal<'gc>(
                     let p1 = read_point(vertices, i1, activation).ok()?;
                     let p2 = read_point(vertices, i2, activation).ok()?;
 
-                    Some((p0, p1, p2))
+                    Some(((p0, p1, p2), (i0, i1, i2)))
                 }
                 _ => None,
             }
@@ -1149,7 +1145,7 @@ fn draw_triangles_internal<'gc>(
         let indices = &mut indices.iter();
 
         while let Some(triangle) = next_triangle(&vertices, indices, activation) {
-            draw_triangle_internal(triangle, drawing, culling);
+            triangles.push(triangle);
         }
     } else {
         if vertices.length() % 6 != 0 {
@@ -1196,20 +1192,71 @@ fn draw_triangles_internal<'gc>(
             }
         }
 
+        let mut index = 0;
         while let Some(triangle) = next_triangle(&mut vertices, activation)? {
-            draw_triangle_internal(triangle, drawing, culling);
+            triangles.push((triangle, (index, index + 1, index + 2)));
+            index += 3;
         }
     }
 
+    let triangles = triangles
+        .into_iter()
+        .filter(|(triangle, _)| !culling.cull(*triangle));
+
+    let bitmap_fill = match (drawing.fill_style(), &uvt_data) {
+        (
+            Some(&FillStyle::Bitmap {
+                id,
+                is_smoothed,
+                is_repeating,
+                ..
+            }),
+            Some(uvt_data),
+        ) => drawing.bitmap_size(id).map(|size| {
+            let vertex_count = vertices.length() / 2;
+            TriangleBitmapFill {
+                id,
+                width: size.width.into(),
+                height: size.height.into(),
+                is_smoothed,
+                is_repeating,
+                has_t: vertex_count > 0 && uvt_data.length() == vertex_count * 3,
+            }
+        }),
+        _ => None,
+    };
+
+    if let (Some(bitmap_fill), Some(uvt_data)) = (bitmap_fill, &uvt_data) {
+        let triangles = triangles
+            .filter_map(|(triangle, indices)| {
+                bitmap_fill.read_vertices(activation, uvt_data, triangle, indices)
+            })
+            .collect::<Vec<_>>();
+        draw_textured_triangles(drawing, &bitmap_fill, &triangles);
+    } else {
+        // Flash fills triangles with the non-zero winding rule. Make sure they
+        // all wind the same way, so that overlapping triangles don't cancel out.
+        let commands = triangles.flat_map(|((a, b, c), _)| {
+            let (b, c) = if triangle_orientation((a, b, c)) < 0 {
+                (c, b)
+            } else {
+                (b, c)
+            };
+            [
+                DrawCommand::MoveTo(a),
+                DrawCommand::LineTo(b),
+                DrawCommand::LineTo(c),
+                DrawCommand::LineTo(a),
+            ]
+        });
+        drawing.draw_path(FillRule::NonZero, commands);
+    }
+
     Ok(())
 }
 
 #[inline]
-fn draw_triangle_internal((a, b, c): Triangle, drawing: &mut Drawing, culling: TriangleCulling) {
-    if culling.cull((a, b, c)) {
-        return;
-    }
-
+fn draw_triangle_internal((a, b, c): Triangle, drawing: &mut Drawing<'_>) {
     drawing.draw_command(DrawCommand::MoveTo(a));
 
     drawing.draw_command(DrawCommand::LineTo(b));
@@ -1217,6 +1264,197 @@ fn draw_triangle_internal((a, b, c): Triangle, drawing: &mut Drawing, culling: T
     drawing.draw_command(DrawCommand::LineTo(a));
 }
 
+/// The most a textured triangle is split up along each edge to approximate perspective.
+const MAX_PERSPECTIVE_SUBDIVISIONS: usize = 8;
+
+/// The current bitmap fill, when drawing triangles with UV coordinates.
+struct TriangleBitmapFill {
+    id: u16,
+    width: f64,
+    height: f64,
+    is_smoothed: bool,
+    is_repeating: bool,
+
+    /// Whether `uvtData` has a T coordinate for each vertex.
+    has_t: bool,
+}
+
+/// A vertex of a textured triangle, in twips and bitmap pixels.
+#[derive(Clone, Copy)]
+struct TexturedVertex {
+    x: f64,
+    y: f64,
+    u: f64,
+    v: f64,
+    t: f64,
+}
+
+impl TexturedVertex {
+    fn point(self) -> Point<Twips> {
+        Point::new(
+            Twips::new(self.x.round() as i32),
+            Twips::new(self.y.round() as i32),
+        )
+    }
+}
+
+impl TriangleBitmapFill {
+    fn read_vertices<'gc>(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        uvt_data: &VectorStorage<'gc>,
+        (a, b, c): Triangle,
+        (i0, i1, i2): TriangleIndices,
+    ) -> Option<[TexturedVertex; 3]> {
+        let mut read_vertex = |point: Point<Twips>, index: usize| {
+            let stride = if self.has_t { 3 } else { 2 };
+            let mut read = |offset| {
+                uvt_data
+                    .get(index * stride + offset, activation)
+                    .ok()
+                    .map(|value| value.as_f64())
+            };
+            Some(TexturedVertex {
+                x: point.x.get() as f64,
+                y: point.y.get() as f64,
+                u: read(0)? * self.width,
+                v: read(1)? * self.height,
+                t: if self.has_t { read(2)? } else { 1.0 },
+            })
+        };
+
+        let mut vertices = [
+            read_vertex(a, i0)?,
+            read_vertex(b, i1)?,
+            read_vertex(c, i2)?,
+        ];
+
+        // T only makes sense as a positive scale; anything else is drawn without perspective.
+        if vertices.iter().any(|v| v.t <= 0.0 || !v.t.is_finite()) {
+            for vertex in &mut vertices {
+                vertex.t = 1.0;
+            }
+        }
+
+        Some(vertices)
+    }
+
+    /// The bitmap matrix that maps the bitmap onto a triangle, or `None` if the triangle
+    /// doesn't cover any area of the bitmap.
+    fn matrix(&self, [p0, p1, p2]: [TexturedVertex; 3]) -> Option<Matrix> {
+        let (du1, dv1) = (p1.u - p0.u, p1.v - p0.v);
+        let (du2, dv2) = (p2.u - p0.u, p2.v - p0.v);
+        let (dx1, dy1) = (p1.x - p0.x, p1.y - p0.y);
+        let (dx2, dy2) = (p2.x - p0.x, p2.y - p0.y);
+
+        let det = du1 * dv2 - du2 * dv1;
+        if det.abs() < f64::EPSILON {
+            return None;
+        }
+
+        let a = (dx1 * dv2 - dx2 * dv1) / det;
+        let b = (dy1 * dv2 - dy2 * dv1) / det;
+        let c = (dx2 * du1 - dx1 * du2) / det;
+        let d = (dy2 * du1 - dy1 * du2) / det;
+        let tx = p0.x - (a * p0.u + c * p0.v);
+        let ty = p0.y - (b * p0.u + d * p0.v);
+
+        let limit = i16::MAX as f64;
+        if [a, b, c, d]
+            .iter()
+            .any(|n| !n.is_finite() || n.abs() >= limit)
+        {
+            return None;
+        }
+
+        Some(Matrix {
+            a: Fixed16::from_f64(a),
+            b: Fixed16::from_f64(b),
+            c: Fixed16::from_f64(c),
+            d: Fixed16::from_f64(d),
+            tx: Twips::new(tx.round() as i32),
+            ty: Twips::new(ty.round() as i32),
+        })
+    }
+}
+
+/// Draw triangles with a bitmap mapped onto each one by its UV coordinates.
+///
+/// Each triangle is filled separately with its own bitmap matrix. Triangles with
+/// differing T coordinates are split up so that the bitmap is mapped with perspective.
+fn draw_textured_triangles(
+    drawing: &mut Drawing<'_>,
+    bitmap_fill: &TriangleBitmapFill,
+    triangles: &[[TexturedVertex; 3]],
+) {
+    let fill_style = drawing.fill_style().cloned();
+    let fill_rule = drawing.fill_rule();
+
+    // Finish the current fill, so that the triangles are drawn on top of it.
+    drawing.new_fill(None, None);
+    drawing.add_triangle_fills(triangles.iter().flat_map(|&triangle| {
+        subdivide_triangle(triangle).into_iter().filter_map(|part| {
+            let matrix = bitmap_fill.matrix(part)?;
+            let style = FillStyle::Bitmap {
+                id: bitmap_fill.id,
+                matrix,
+                is_smoothed: bitmap_fill.is_smoothed,
+                is_repeating: bitmap_fill.is_repeating,
+            };
+            Some((style, part.map(TexturedVertex::point)))
+        })
+    }));
+
+    // Strokes follow the triangle edges, not the edges of the split up triangles.
+    if drawing.line_style().is_some() {
+        for triangle in triangles {
+            let [a, b, c] = triangle.map(TexturedVertex::point);
+            draw_triangle_internal((a, b, c), drawing);
+        }
+    }
+
+    drawing.new_fill(fill_style, fill_rule);
+}
+
+/// Split a triangle into smaller ones, with UV coordinates interpolated with
+/// perspective correction, according to how much its T coordinates differ.
+fn subdivide_triangle(vertices: [TexturedVertex; 3]) -> Vec<[TexturedVertex; 3]> {
+    let [p0, p1, p2] = vertices;
+    let min_t = p0.t.min(p1.t).min(p2.t);
+    let max_t = p0.t.max(p1.t).max(p2.t);
+    let n = (((max_t / min_t) - 1.0) * 4.0)
+        .ceil()
+        .clamp(1.0, MAX_PERSPECTIVE_SUBDIVISIONS as f64) as usize;
+    if n == 1 {
+        return vec![vertices];
+    }
+
+    let at = |i: usize, j: usize| {
+        let l1 = i as f64 / n as f64;
+        let l2 = j as f64 / n as f64;
+        let l0 = 1.0 - l1 - l2;
+        let t = l0 * p0.t + l1 * p1.t + l2 * p2.t;
+        TexturedVertex {
+            x: l0 * p0.x + l1 * p1.x + l2 * p2.x,
+            y: l0 * p0.y + l1 * p1.y + l2 * p2.y,
+            u: (l0 * p0.u * p0.t + l1 * p1.u * p1.t + l2 * p2.u * p2.t) / t,
+            v: (l0 * p0.v * p0.t + l1 * p1.v * p1.t + l2 * p2.v * p2.t) / t,
+            t,
+        }
+    };
+
+    let mut parts = Vec::with_capacity(n * n);
+    for i in 0..n {
+        for j in 0..n - i {
+            parts.push([at(i, j), at(i + 1, j), at(i, j + 1)]);
+            if i + j + 1 < n {
+                parts.push([at(i + 1, j), at(i + 1, j + 1), at(i, j + 1)]);
+            }
+        }
+    }
+    parts
+}
+
 /// Implements `Graphics.drawGraphicsData`
 pub fn draw_graphics_data<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -1248,7 +1486,7 @@ pub fn line_bitmap_style<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this.as_display_object() {
-        let bitmap = args
+        let bitmap_data = args
             .get_object(activation, 0, "bitmap")?
             .as_bitmap_data()
             .expect("Bitmap argument is ensured to be a BitmapData from actionscript");
@@ -1262,12 +1500,12 @@ pub fn line_bitmap_style<'gc>(
         let is_smoothed = args.get_bool(3);
 
         let handle =
-            bitmap.bitmap_handle(activation.context.gc_context, activation.context.renderer);
+            bitmap_data.bitmap_handle(activation.context.gc_context, activation.context.renderer);
 
         let bitmap = ruffle_render::bitmap::BitmapInfo {
             handle,
-            width: bitmap.width() as u16,
-            height: bitmap.height() as u16,
+            width: bitmap_data.width() as u16,
+            height: bitmap_data.height() as u16,
         };
         let scale_matrix = Matrix::scale(
             Fixed16::from_f64(bitmap.width as f64),
@@ -1275,7 +1513,7 @@ pub fn line_bitmap_style<'gc>(
         );
 
         if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
-            let id = draw.add_bitmap(bitmap);
+            let id = draw.add_bitmap(bitmap_data, bitmap);
             draw.set_line_fill_style(FillStyle::Bitmap {
                 id,
                 matrix: matrix * scale_matrix,
@@ -1291,15 +1529,329 @@ pub fn line_bitmap_style<'gc>(
 /// Implements `Graphics.readGraphicsData`
 pub fn read_graphics_data<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.display.Graphics", "readGraphicsData");
+    let recurse = args.get_bool(0);
+
+    let mut graphics_data = Vec::new();
+    if let Some(dobj) = this.as_display_object() {
+        read_display_object_graphics_data(
+            activation,
+            dobj,
+            RenderMatrix::IDENTITY,
+            recurse,
+            &mut graphics_data,
+        )?;
+    }
+
     let value_type = activation.avm2().class_defs().igraphicsdata;
-    let new_storage = VectorStorage::new(0, false, Some(value_type), activation);
+    let new_storage = VectorStorage::from_values(graphics_data, false, Some(value_type));
     Ok(VectorObject::from_vector(new_storage, activation)?.into())
 }
 
+/// Where to find the `BitmapData` behind a bitmap fill.
+enum BitmapDataSource<'a, 'gc> {
+    /// The bitmap was drawn with the drawing API, which remembers where it
+    /// came from.
+    Drawing(&'a Drawing<'gc>),
+
+    /// The bitmap is a character in the library of this movie.
+    Library(Arc<SwfMovie>),
+}
+
+impl<'gc> BitmapDataSource<'_, 'gc> {
+    fn bitmap_data(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        id: u16,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let bitmap_data = match self {
+            BitmapDataSource::Drawing(drawing) => match drawing.bitmap_data(id) {
+                Some(bitmap_data) if bitmap_data.object2().as_object().is_some() => {
+                    return Ok(bitmap_data.object2());
+                }
+                Some(bitmap_data) => bitmap_data,
+                None => return Ok(Value::Null),
+            },
+            BitmapDataSource::Library(movie) => {
+                let compressed = activation
+                    .context
+                    .library
+                    .library_for_movie(movie.clone())
+                    .and_then(|library| match library.character_by_id(id) {
+                        Some(Character::Bitmap { compressed, .. }) => Some(compressed.clone()),
+                        _ => None,
+                    });
+
+                let Some(compressed) = compressed else {
+                    return Ok(Value::Null);
+                };
+                fill_bitmap_data_from_symbol(activation, &compressed)
+            }
+        };
+
+        let class = activation.avm2().classes().bitmapdata;
+        Ok(BitmapDataObject::from_bitmap_data_internal(activation, bitmap_data, class)?.into())
+    }
+}
+
+/// Describe the vector content of a display object as `IGraphicsData`
+/// objects, transformed by `matrix`.
+fn read_display_object_graphics_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    dobj: DisplayObject<'gc>,
+    matrix: RenderMatrix,
+    recurse: bool,
+    output: &mut Vec<Value<'gc>>,
+) -> Result<(), Error<'gc>> {
+    let content = match dobj {
+        DisplayObject::Graphic(graphic) => match graphic.drawing().map(|d| d.clone()) {
+            Some(drawing) => Some((drawing, None)),
+            // Shapes that haven't been drawn on still have their SWF shape.
+            None => Some((
+                Drawing::from_swf_shape(&graphic.swf_shape()),
+                Some(graphic.movie()),
+            )),
+        },
+        DisplayObject::MovieClip(movie_clip) => movie_clip.drawing().map(|d| (d.clone(), None)),
+        _ => None,
+    };
+
+    if let Some((drawing, library)) = content {
+        let bitmaps = match library {
+            Some(movie) => BitmapDataSource::Library(movie),
+            None => BitmapDataSource::Drawing(&drawing),
+        };
+        for data in drawing.graphics_data() {
+            let object = graphics_data_to_object(activation, data, &bitmaps, matrix)?;
+            output.push(object.into());
+        }
+    }
+
+    if recurse {
+        if let Some(container) = dobj.as_container() {
+            let children: Vec<_> = container.iter_render_list().collect();
+            for child in children {
+                let child_matrix = matrix * *child.base().matrix();
+                read_display_object_graphics_data(
+                    activation,
+                    child,
+                    child_matrix,
+                    recurse,
+                    output,
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn graphics_data_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    data: DrawingData<'_>,
+    bitmaps: &BitmapDataSource<'_, 'gc>,
+    matrix: RenderMatrix,
+) -> Result<Object<'gc>, Error<'gc>> {
+    match data {
+        DrawingData::Fill(style) => fill_style_to_object(activation, style, bitmaps, matrix),
+        DrawingData::EndFill => activation
+            .avm2()
+            .classes()
+            .graphicsendfill
+            .construct(activation, &[]),
+        DrawingData::Stroke(None) => activation
+            .avm2()
+            .classes()
+            .graphicsstroke
+            .construct(activation, &[]),
+        DrawingData::Stroke(Some(style)) => {
+            let scale_mode = match (style.allow_scale_x(), style.allow_scale_y()) {
+                (true, true) => "normal",
+                (true, false) => "horizontal",
+                (false, true) => "vertical",
+                (false, false) => "none",
+            };
+            let (joints, miter_limit) = match style.join_style() {
+                LineJoinStyle::Round => ("round", 3.0),
+                LineJoinStyle::Bevel => ("bevel", 3.0),
+                LineJoinStyle::Miter(limit) => ("miter", limit.to_f64()),
+            };
+            let fill = fill_style_to_object(activation, style.fill_style(), bitmaps, matrix)?;
+            let args = [
+                style.width().to_pixels().into(),
+                style.is_pixel_hinted().into(),
+                scale_mode.into(),
+                cap_style_to_caps(style.start_cap()).into(),
+                joints.into(),
+                miter_limit.into(),
+                fill.into(),
+            ];
+            activation
+                .avm2()
+                .classes()
+                .graphicsstroke
+                .construct(activation, &args)
+        }
+        DrawingData::Path {
+            commands,
+            winding_rule,
+        } => {
+            let mut path_commands = Vec::with_capacity(commands.len());
+            let mut path_data = Vec::new();
+            for (i, command) in commands.iter().enumerate() {
+                // A move that is immediately followed by another one doesn't draw anything.
+                if matches!(command, DrawCommand::MoveTo(_))
+                    && matches!(commands.get(i + 1), Some(DrawCommand::MoveTo(_)))
+                {
+                    continue;
+                }
+                let (kind, points) = match *command {
+                    DrawCommand::MoveTo(point) => (1, vec![point]),
+                    DrawCommand::LineTo(point) => (2, vec![point]),
+                    DrawCommand::QuadraticCurveTo { control, anchor } => (3, vec![control, anchor]),
+                    DrawCommand::CubicCurveTo {
+                        control_a,
+                        control_b,
+                        anchor,
+                    } => (6, vec![control_a, control_b, anchor]),
+                };
+                path_commands.push(Value::Integer(kind));
+                for point in points {
+                    let point = matrix * point;
+                    path_data.push(point.x.to_pixels().into());
+                    path_data.push(point.y.to_pixels().into());
+                }
+            }
+
+            let class_defs = activation.avm2().class_defs();
+            let path_commands =
+                VectorStorage::from_values(path_commands, false, Some(class_defs.int));
+            let path_data = VectorStorage::from_values(path_data, false, Some(class_defs.number));
+            let winding = match winding_rule {
+                FillRule::EvenOdd => "evenOdd",
+                FillRule::NonZero => "nonZero",
+            };
+            let args = [
+                VectorObject::from_vector(path_commands, activation)?.into(),
+                VectorObject::from_vector(path_data, activation)?.into(),
+                winding.into(),
+            ];
+            activation
+                .avm2()
+                .classes()
+                .graphicspath
+                .construct(activation, &args)
+        }
+    }
+}
+
+fn fill_style_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style: &FillStyle,
+    bitmaps: &BitmapDataSource<'_, 'gc>,
+    matrix: RenderMatrix,
+) -> Result<Object<'gc>, Error<'gc>> {
+    match style {
+        FillStyle::Color(color) => {
+            let args = [color.to_rgb().into(), (color.a as f64 / 255.0).into()];
+            activation
+                .avm2()
+                .classes()
+                .graphicssolidfill
+                .construct(activation, &args)
+        }
+        FillStyle::LinearGradient(gradient) => {
+            gradient_to_object(activation, "linear", gradient, 0.0, matrix)
+        }
+        FillStyle::RadialGradient(gradient) => {
+            gradient_to_object(activation, "radial", gradient, 0.0, matrix)
+        }
+        FillStyle::FocalGradient {
+            gradient,
+            focal_point,
+        } => gradient_to_object(activation, "radial", gradient, focal_point.to_f64(), matrix),
+        FillStyle::Bitmap {
+            id,
+            matrix: bitmap_matrix,
+            is_smoothed,
+            is_repeating,
+        } => {
+            // Bitmap fill matrices map texels to twips, rather than to pixels.
+            let twips_to_pixels = RenderMatrix::scale(
+                1.0 / Twips::TWIPS_PER_PIXEL as f32,
+                1.0 / Twips::TWIPS_PER_PIXEL as f32,
+            );
+            let bitmap_matrix = matrix * RenderMatrix::from(*bitmap_matrix) * twips_to_pixels;
+            let args = [
+                bitmaps.bitmap_data(activation, *id)?,
+                matrix_to_object(bitmap_matrix, activation)?,
+                (*is_repeating).into(),
+                (*is_smoothed).into(),
+            ];
+            activation
+                .avm2()
+                .classes()
+                .graphicsbitmapfill
+                .construct(activation, &args)
+        }
+    }
+}
+
+fn gradient_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    gradient_type: &'static str,
+    gradient: &Gradient,
+    focal_point: f64,
+    matrix: RenderMatrix,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let colors = gradient
+        .records
+        .iter()
+        .map(|record| Some(record.color.to_rgb().into()));
+    let alphas = gradient
+        .records
+        .iter()
+        .map(|record| Some((record.color.a as f64 / 255.0).into()));
+    let ratios = gradient
+        .records
+        .iter()
+        .map(|record| Some(record.ratio.into()));
+    let colors =
+        ArrayObject::from_storage(activation, ArrayStorage::from_storage(colors.collect()))?;
+    let alphas =
+        ArrayObject::from_storage(activation, ArrayStorage::from_storage(alphas.collect()))?;
+    let ratios =
+        ArrayObject::from_storage(activation, ArrayStorage::from_storage(ratios.collect()))?;
+
+    let spread_method = match gradient.spread {
+        GradientSpread::Pad => "pad",
+        GradientSpread::Reflect => "reflect",
+        GradientSpread::Repeat => "repeat",
+    };
+    let interpolation_method = match gradient.interpolation {
+        GradientInterpolation::Rgb => "rgb",
+        GradientInterpolation::LinearRgb => "linearRGB",
+    };
+
+    let args = [
+        gradient_type.into(),
+        colors.into(),
+        alphas.into(),
+        ratios.into(),
+        matrix_to_object(matrix * RenderMatrix::from(gradient.matrix), activation)?,
+        spread_method.into(),
+        interpolation_method.into(),
+        focal_point.into(),
+    ];
+    activation
+        .avm2()
+        .classes()
+        .graphicsgradientfill
+        .construct(activation, &args)
+}
+
 fn read_point<'gc>(
     activation: &mut Activation<'_, 'gc>,
     data: &VectorStorage<'gc>,
@@ -1319,7 +1871,7 @@ fn read_point<'gc>(
 
 fn process_commands<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    drawing: &mut Drawing,
+    drawing: &mut Drawing<'gc>,
     commands: &VectorStorage<'gc>,
     data: &VectorStorage<'gc>,
     winding: AvmString,
@@ -1344,11 +1896,9 @@ fn process_commands<'gc>(
         return Err(make_error_2008(activation, "winding"));
     };
 
-    drawing.set_fill_rule(Some(rule));
-
     fn process_command<'gc>(
         activation: &mut Activation<'_, 'gc>,
-        drawing: &mut Drawing,
+        path: &mut Vec<DrawCommand>,
         data: &VectorStorage<'gc>,
         command: i32,
         data_index: &mut usize,
@@ -1360,37 +1910,37 @@ fn process_commands<'gc>(
             // MOVE_TO
             1 => {
                 let point = read_point(activation, data, data_index)?;
-                drawing.draw_command(DrawCommand::MoveTo(point));
+                path.push(DrawCommand::MoveTo(point));
             }
             // LINE_TO
             2 => {
                 let point = read_point(activation, data, data_index)?;
-                drawing.draw_command(DrawCommand::LineTo(point));
+                path.push(DrawCommand::LineTo(point));
             }
             // CURVE_TO
             3 => {
                 let control = read_point(activation, data, data_index)?;
                 let anchor = read_point(activation, data, data_index)?;
-                drawing.draw_command(DrawCommand::QuadraticCurveTo { control, anchor });
+                path.push(DrawCommand::QuadraticCurveTo { control, anchor });
             }
             // WIDE_MOVE_TO
             4 => {
                 *data_index += 2;
                 let point = read_point(activation, data, data_index)?;
-                drawing.draw_command(DrawCommand::MoveTo(point));
+                path.push(DrawCommand::MoveTo(point));
             }
             // WIDE_LINE_TO
             5 => {
                 *data_index += 2;
                 let point = read_point(activation, data, data_index)?;
-                drawing.draw_command(DrawCommand::LineTo(point));
+                path.push(DrawCommand::LineTo(point));
             }
             // CUBIC_CURVE_TO
             6 => {
                 let control_a = read_point(activation, data, data_index)?;
                 let control_b = read_point(activation, data, data_index)?;
                 let anchor = read_point(activation, data, data_index)?;
-                drawing.draw_command(DrawCommand::CubicCurveTo {
+                path.push(DrawCommand::CubicCurveTo {
                     control_a,
                     control_b,
                     anchor,
@@ -1404,6 +1954,7 @@ fn process_commands<'gc>(
         Some(())
     }
 
+    let mut path = Vec::new();
     let mut data_index = 0;
     for i in 0..commands.length() {
         let command = commands
@@ -1411,20 +1962,21 @@ fn process_commands<'gc>(
             .expect("missing command")
             .as_i32();
 
-        if process_command(activation, drawing, data, command, &mut data_index).is_none() {
+        if process_command(activation, &mut path, data, command, &mut data_index).is_none() {
             break;
         }
     }
 
-    // Reset winding rule after drawing commands
-    drawing.set_fill_rule(None);
+    // The winding rule only applies to this path, not to anything else drawn
+    // with the same fill.
+    drawing.draw_path(rule, path);
 
     Ok(())
 }
 
 fn handle_igraphics_data<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    drawing: &mut Drawing,
+    drawing: &mut Drawing<'gc>,
     obj: &Object<'gc>,
 ) -> Result<(), Error<'gc>> {
     let class = obj.instance_class();
@@ -1530,7 +2082,7 @@ fn handle_igraphics_data<'gc>(
 
 fn handle_graphics_triangle_path<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    drawing: &mut Drawing,
+    drawing: &mut Drawing<'gc>,
     obj: &Object<'gc>,
 ) -> Result<(), Error<'gc>> {
     let culling = {
@@ -1562,7 +2114,7 @@ fn handle_graphics_triangle_path<'gc>(
 
 fn handle_igraphics_fill<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    drawing: &mut Drawing,
+    drawing: &mut Drawing<'gc>,
     obj: &Object<'gc>,
 ) -> Result<Option<FillStyle>, Error<'gc>> {
     let class = obj.instance_class();
@@ -1693,7 +2245,7 @@ fn handle_gradient_fill<'gc>(
 
 fn handle_bitmap_fill<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    drawing: &mut Drawing,
+    drawing: &mut Drawing<'gc>,
     obj: &Object<'gc>,
 ) -> Result<FillStyle, Error<'gc>> {
     let bitmap_data = obj
@@ -1735,7 +2287,7 @@ fn handle_bitmap_fill<'gc>(
         Fixed16::from_f64(bitmap.height as f64),
     );
 
-    let id = drawing.add_bitmap(bitmap);
+    let id = drawing.add_bitmap(bitmap_data, bitmap);
 
     let style = FillStyle::Bitmap {
         id,
@@ -188,6 +188,7 @@ pub fn get_content_type<'gc>(
             ContentType::Jpeg => Ok("image/jpeg".into()),
             ContentType::Png => Ok("image/png".into()),
             ContentType::Gif => Ok("image/gif".into()),
+            ContentType::JpegXr => Ok("image/vnd.ms-photo".into()),
             ContentType::Unknown => Ok(Value::Null),
         };
     }
@@ -0,0 +1,39 @@
+package flash.errors {
+    [API("661")]
+    public class SQLError extends Error {
+        prototype.name = "SQLError";
+
+        private var _operation:String;
+        private var _details:String;
+        private var _detailID:int;
+        private var _detailArguments:Array;
+
+        public function SQLError(operation:String, details:String = "", message:String = "", id:int = 0, detailID:int = -1, detailArgs:Array = null) {
+            super(message, id);
+            this._operation = operation;
+            this._details = details;
+            this._detailID = detailID;
+            this._detailArguments = detailArgs;
+        }
+
+        public function get operation():String {
+            return this._operation;
+        }
+
+        public function get details():String {
+            return this._details;
+        }
+
+        public function get detailID():int {
+            return this._detailID;
+        }
+
+        public function get detailArguments():Array {
+            return this._detailArguments;
+        }
+
+        public function toString():String {
+            return "SQLError: '" + this.message + "', details:'" + this._details + "', operation:'" + this._operation + "', detailID:'" + this._detailID + "'";
+        }
+    }
+}
@@ -9,7 +9,7 @@ pub fn get_tap_stage_x<'gc>(
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    mouse_event::local_to_stage_x(activation, this, "localTapX", "localTapY")
+    mouse_event::local_to_stage_x(activation, this, "tapLocalX", "tapLocalY")
 }
 
 pub fn get_tap_stage_y<'gc>(
@@ -17,5 +17,5 @@ pub fn get_tap_stage_y<'gc>(
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    mouse_event::local_to_stage_y(activation, this, "localTapX", "localTapY")
+    mouse_event::local_to_stage_y(activation, this, "tapLocalX", "tapLocalY")
 }
@@ -1,36 +1,133 @@
 package flash.filesystem {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
-
+    import flash.events.Event;
+    import flash.events.FileListEvent;
+    import flash.events.IOErrorEvent;
     import flash.net.FileReference;
+    import flash.utils.setTimeout;
 
     [API("661")]
     public class File extends FileReference {
-        private static var _applicationDirectory:File = null;
-
         public function File(path:String = null) {
-            stub_constructor("flash.filesystem.File");
+            if (path != null) {
+                this.init(path);
+            }
         }
 
-        public static function get applicationDirectory():File {
-            if (_applicationDirectory === null) {
-                _applicationDirectory = new File();
-            }
+        private native function init(path:String):void;
+
+        public static native function get applicationDirectory():File;
+
+        public static native function get applicationStorageDirectory():File;
 
-            return _applicationDirectory;
+        public static native function get separator():String;
+
+        public static native function get lineEnding():String;
+
+        public static function get systemCharset():String {
+            return "utf-8";
         }
 
-        public function resolvePath(path:String):File {
-            stub_method("flash.filesystem.File", "resolvePath");
+        public native function get exists():Boolean;
+
+        public native function get isDirectory():Boolean;
 
-            return new File();
+        public function get isHidden():Boolean {
+            return false;
         }
 
-        public function get exists():Boolean {
-            stub_getter("flash.filesystem.File", "exists");
+        public function get isPackage():Boolean {
+            return false;
+        }
 
+        public function get isSymbolicLink():Boolean {
             return false;
         }
+
+        public native function get nativePath():String;
+        public native function set nativePath(value:String):void;
+
+        public native function get url():String;
+        public native function set url(value:String):void;
+
+        public native function get parent():File;
+
+        public native function resolvePath(path:String):File;
+
+        public native function getRelativePath(ref:FileReference, useDotDot:Boolean = false):String;
+
+        public function canonicalize():void {
+            // Paths are always kept in their canonical form.
+        }
+
+        public function clone():File {
+            var file:File = new File();
+            if (this.url != null) {
+                file.url = this.url;
+            }
+            return file;
+        }
+
+        public native function getDirectoryListing():Array;
+
+        public function getDirectoryListingAsync():void {
+            var self:File = this;
+            this.runAsync(function():Event {
+                return new FileListEvent(FileListEvent.DIRECTORY_LISTING, false, false, self.getDirectoryListing());
+            });
+        }
+
+        public native function copyTo(newLocation:FileReference, overwrite:Boolean = false):void;
+
+        public function copyToAsync(newLocation:FileReference, overwrite:Boolean = false):void {
+            var self:File = this;
+            this.runAsync(function():Event {
+                self.copyTo(newLocation, overwrite);
+                return new Event(Event.COMPLETE);
+            });
+        }
+
+        public native function moveTo(newLocation:FileReference, overwrite:Boolean = false):void;
+
+        public function moveToAsync(newLocation:FileReference, overwrite:Boolean = false):void {
+            var self:File = this;
+            this.runAsync(function():Event {
+                self.moveTo(newLocation, overwrite);
+                return new Event(Event.COMPLETE);
+            });
+        }
+
+        public native function createDirectory():void;
+
+        public native function deleteFile():void;
+
+        public function deleteFileAsync():void {
+            var self:File = this;
+            this.runAsync(function():Event {
+                self.deleteFile();
+                return new Event(Event.COMPLETE);
+            });
+        }
+
+        public native function deleteDirectory(deleteDirectoryContents:Boolean = false):void;
+
+        public function deleteDirectoryAsync(deleteDirectoryContents:Boolean = false):void {
+            var self:File = this;
+            this.runAsync(function():Event {
+                self.deleteDirectory(deleteDirectoryContents);
+                return new Event(Event.COMPLETE);
+            });
+        }
+
+        // AIR runs asynchronous operations in the background. We run them
+        // immediately, and only dispatch their result later.
+        private function runAsync(operation:Function):void {
+            var event:Event;
+            try {
+                event = operation();
+            } catch (error:Error) {
+                event = new IOErrorEvent(IOErrorEvent.IO_ERROR, false, false, error.message, error.errorID);
+            }
+            setTimeout(this.dispatchEvent, 0, event);
+        }
     }
 }
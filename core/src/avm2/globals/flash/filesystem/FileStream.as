@@ -0,0 +1,266 @@
+package flash.filesystem {
+    import flash.errors.IOError;
+    import flash.events.Event;
+    import flash.events.EventDispatcher;
+    import flash.events.IOErrorEvent;
+    import flash.events.ProgressEvent;
+    import flash.utils.ByteArray;
+    import flash.utils.IDataInput;
+    import flash.utils.IDataOutput;
+    import flash.utils.setTimeout;
+
+    [API("661")]
+    public class FileStream extends EventDispatcher implements IDataInput, IDataOutput {
+        // The whole file is kept in memory while the stream is open. Every
+        // write goes straight through to the file as well, so nothing is lost
+        // if the stream is never closed.
+        private var _buffer:ByteArray = new ByteArray();
+        private var _file:File = null;
+        private var _mode:String = null;
+        private var _async:Boolean = false;
+        // Where the write currently in progress started.
+        private var _writeStart:uint = 0;
+
+        public var readAhead:Number = Infinity;
+
+        public function FileStream() {
+        }
+
+        private static native function readFile(file:File):ByteArray;
+        private static native function writeFile(file:File, data:ByteArray):void;
+        private static native function writeFileRange(file:File, data:ByteArray, start:uint, end:uint):void;
+        private static native function setFileLength(file:File, length:uint):void;
+
+        public function open(file:File, fileMode:String):void {
+            this.openFile(file, fileMode, false);
+        }
+
+        public function openAsync(file:File, fileMode:String):void {
+            try {
+                this.openFile(file, fileMode, true);
+            } catch (error:IOError) {
+                setTimeout(this.dispatchEvent, 0, new IOErrorEvent(IOErrorEvent.IO_ERROR, false, false, error.message, error.errorID));
+                return;
+            }
+
+            if (fileMode == FileMode.READ || fileMode == FileMode.UPDATE) {
+                setTimeout(this.dispatchReadEvents, 0);
+            }
+        }
+
+        private function openFile(file:File, fileMode:String, async:Boolean):void {
+            if (file == null) {
+                throw new TypeError("Error #2007: Parameter file must be non-null.", 2007);
+            }
+            if (this._file != null) {
+                this.close();
+            }
+
+            var buffer:ByteArray;
+            switch (fileMode) {
+                case FileMode.READ:
+                    buffer = readFile(file);
+                    break;
+                case FileMode.WRITE:
+                    buffer = new ByteArray();
+                    writeFile(file, buffer);
+                    break;
+                case FileMode.APPEND:
+                case FileMode.UPDATE:
+                    if (file.exists) {
+                        buffer = readFile(file);
+                    } else {
+                        buffer = new ByteArray();
+                        writeFile(file, buffer);
+                    }
+                    if (fileMode == FileMode.APPEND) {
+                        buffer.position = buffer.length;
+                    }
+                    break;
+                default:
+                    throw new ArgumentError("Error #2008: Parameter fileMode must be one of the accepted values.", 2008);
+            }
+
+            buffer.endian = this._buffer.endian;
+            buffer.objectEncoding = this._buffer.objectEncoding;
+            this._buffer = buffer;
+            this._file = file;
+            this._mode = fileMode;
+            this._async = async;
+        }
+
+        private function dispatchReadEvents():void {
+            if (this._file == null) {
+                return;
+            }
+            var length:uint = this._buffer.length;
+            this.dispatchEvent(new ProgressEvent(ProgressEvent.PROGRESS, false, false, length, length));
+            this.dispatchEvent(new Event(Event.COMPLETE));
+        }
+
+        public function close():void {
+            if (this._file == null) {
+                return;
+            }
+            var buffer:ByteArray = new ByteArray();
+            buffer.endian = this._buffer.endian;
+            buffer.objectEncoding = this._buffer.objectEncoding;
+            this._buffer = buffer;
+            this._file = null;
+            this._mode = null;
+
+            if (this._async) {
+                setTimeout(this.dispatchEvent, 0, new Event(Event.CLOSE));
+            }
+        }
+
+        private function checkReadable():ByteArray {
+            if (this._file == null || this._mode == FileMode.WRITE || this._mode == FileMode.APPEND) {
+                throw new IOError("Error #2029: This URLStream object does not have a stream opened.", 2029);
+            }
+            return this._buffer;
+        }
+
+        private function checkWritable():ByteArray {
+            if (this._file == null || this._mode == FileMode.READ) {
+                throw new IOError("Error #2029: This URLStream object does not have a stream opened.", 2029);
+            }
+            if (this._mode == FileMode.APPEND) {
+                this._buffer.position = this._buffer.length;
+            }
+            this._writeStart = this._buffer.position;
+            return this._buffer;
+        }
+
+        // Copies what was written since `checkWritable` to the file.
+        private function commitWrite():void {
+            writeFileRange(this._file, this._buffer, this._writeStart, this._buffer.position);
+        }
+
+        public function get bytesAvailable():uint {
+            return this._buffer.bytesAvailable;
+        }
+
+        public function get position():Number {
+            return this._buffer.position;
+        }
+
+        public function set position(value:Number):void {
+            this._buffer.position = value;
+        }
+
+        public function get endian():String {
+            return this._buffer.endian;
+        }
+
+        public function set endian(value:String):void {
+            this._buffer.endian = value;
+        }
+
+        public function get objectEncoding():uint {
+            return this._buffer.objectEncoding;
+        }
+
+        public function set objectEncoding(value:uint):void {
+            this._buffer.objectEncoding = value;
+        }
+
+        public function truncate():void {
+            var buffer:ByteArray = this.checkWritable();
+            buffer.length = buffer.position;
+            setFileLength(this._file, buffer.length);
+        }
+
+        public function readBoolean():Boolean {
+            return this.checkReadable().readBoolean();
+        }
+        public function readByte():int {
+            return this.checkReadable().readByte();
+        }
+        public function readBytes(bytes:ByteArray, offset:uint = 0, length:uint = 0):void {
+            this.checkReadable().readBytes(bytes, offset, length);
+        }
+        public function readDouble():Number {
+            return this.checkReadable().readDouble();
+        }
+        public function readFloat():Number {
+            return this.checkReadable().readFloat();
+        }
+        public function readInt():int {
+            return this.checkReadable().readInt();
+        }
+        public function readMultiByte(length:uint, charSet:String):String {
+            return this.checkReadable().readMultiByte(length, charSet);
+        }
+        public function readObject():* {
+            return this.checkReadable().readObject();
+        }
+        public function readShort():int {
+            return this.checkReadable().readShort();
+        }
+        public function readUnsignedByte():uint {
+            return this.checkReadable().readUnsignedByte();
+        }
+        public function readUnsignedInt():uint {
+            return this.checkReadable().readUnsignedInt();
+        }
+        public function readUnsignedShort():uint {
+            return this.checkReadable().readUnsignedShort();
+        }
+        public function readUTF():String {
+            return this.checkReadable().readUTF();
+        }
+        public function readUTFBytes(length:uint):String {
+            return this.checkReadable().readUTFBytes(length);
+        }
+
+        public function writeBoolean(value:Boolean):void {
+            this.checkWritable().writeBoolean(value);
+            this.commitWrite();
+        }
+        public function writeByte(value:int):void {
+            this.checkWritable().writeByte(value);
+            this.commitWrite();
+        }
+        public function writeBytes(bytes:ByteArray, offset:uint = 0, length:uint = 0):void {
+            this.checkWritable().writeBytes(bytes, offset, length);
+            this.commitWrite();
+        }
+        public function writeDouble(value:Number):void {
+            this.checkWritable().writeDouble(value);
+            this.commitWrite();
+        }
+        public function writeFloat(value:Number):void {
+            this.checkWritable().writeFloat(value);
+            this.commitWrite();
+        }
+        public function writeInt(value:int):void {
+            this.checkWritable().writeInt(value);
+            this.commitWrite();
+        }
+        public function writeMultiByte(value:String, charSet:String):void {
+            this.checkWritable().writeMultiByte(value, charSet);
+            this.commitWrite();
+        }
+        public function writeObject(object:*):void {
+            this.checkWritable().writeObject(object);
+            this.commitWrite();
+        }
+        public function writeShort(value:int):void {
+            this.checkWritable().writeShort(value);
+            this.commitWrite();
+        }
+        public function writeUnsignedInt(value:uint):void {
+            this.checkWritable().writeUnsignedInt(value);
+            this.commitWrite();
+        }
+        public function writeUTF(value:String):void {
+            this.checkWritable().writeUTF(value);
+            this.commitWrite();
+        }
+        public function writeUTFBytes(value:String):void {
+            this.checkWritable().writeUTFBytes(value);
+            this.commitWrite();
+        }
+    }
+}
@@ -0,0 +1,407 @@
+//! `flash.filesystem.File` native methods
+
+use crate::avm2::error::{io_error, make_error_2004, make_error_2037, Error2004Type};
+use crate::avm2::object::{ArrayObject, FileReference, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, ArrayStorage, Error, Object, Value};
+use crate::backend::filesystem::{
+    copy_recursive, delete_recursive, FileMetadata, FilePath, FileRoot, FilesystemError,
+};
+use crate::string::AvmString;
+
+/// Turns an error from the filesystem backend into an `IOError`.
+pub fn filesystem_error<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    error: FilesystemError,
+) -> Error<'gc> {
+    let (message, code) = match error {
+        FilesystemError::NotFound => ("Error #3003: File or directory does not exist.", 3003),
+        FilesystemError::AlreadyExists => ("Error #3002: File or directory exists.", 3002),
+        FilesystemError::AccessDenied => ("Error #3001: File or directory access denied.", 3001),
+        FilesystemError::DirectoryNotEmpty => ("Error #3010: Directory is not empty.", 3010),
+        FilesystemError::Other(error) => {
+            tracing::warn!("File I/O error: {error}");
+            ("Error #2038: File I/O Error.", 2038)
+        }
+    };
+    match io_error(activation, message, code) {
+        Ok(err) => Error::AvmError(err),
+        Err(err) => err,
+    }
+}
+
+/// Gets the path that a `File` points to, if any.
+pub fn file_path(this: Object<'_>) -> Option<FilePath> {
+    let this = this.as_file_reference()?;
+    let reference = this.file_reference();
+    match &*reference {
+        FileReference::Path(path) => Some(path.clone()),
+        _ => None,
+    }
+}
+
+fn required_path<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<FilePath, Error<'gc>> {
+    file_path(this).ok_or_else(|| make_error_2037(activation))
+}
+
+/// Gets the path that a `File` points to, if it may be changed.
+pub fn writable_path<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<FilePath, Error<'gc>> {
+    let path = required_path(activation, this)?;
+    if !path.file_root().is_writable() {
+        return Err(filesystem_error(activation, FilesystemError::AccessDenied));
+    }
+    Ok(path)
+}
+
+/// Gets the metadata of a file, failing if it doesn't exist.
+pub fn metadata<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    path: &FilePath,
+) -> Result<FileMetadata, Error<'gc>> {
+    match activation.context.filesystem.metadata(path) {
+        Some(metadata) => Ok(metadata),
+        None => Err(filesystem_error(activation, FilesystemError::NotFound)),
+    }
+}
+
+/// Creates a new `File` pointing to a path.
+///
+/// Without a path, the `File` doesn't point anywhere.
+pub fn file_for_path<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    path: Option<FilePath>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let file = activation
+        .avm2()
+        .classes()
+        .file
+        .construct(activation, &[])?;
+    if let Some(path) = path {
+        file.as_file_reference()
+            .expect("File is a FileReference")
+            .init_from_path(path);
+    }
+    Ok(file)
+}
+
+pub fn get_application_directory<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = FilePath::root(FileRoot::Application);
+    Ok(file_for_path(activation, Some(path))?.into())
+}
+
+pub fn get_application_storage_directory<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = FilePath::root(FileRoot::ApplicationStorage);
+
+    // AIR creates the storage directory the first time it's needed.
+    if let Err(e) = activation.context.filesystem.create_directory(&path) {
+        return Err(filesystem_error(activation, e));
+    }
+
+    Ok(file_for_path(activation, Some(path))?.into())
+}
+
+pub fn get_separator<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(if cfg!(windows) { "\\" } else { "/" }.into())
+}
+
+pub fn get_line_ending<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(if cfg!(windows) { "\r\n" } else { "\n" }.into())
+}
+
+/// Implements the `File` constructor, which accepts either a URL or a native
+/// path.
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = args.get_string(activation, 0)?.to_string();
+    let path =
+        FilePath::from_url(&path).or_else(|| activation.context.filesystem.path_from_native(&path));
+    set_path(activation, this, path)
+}
+
+fn set_path<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    path: Option<FilePath>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(path) = path else {
+        return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+    };
+    this.as_file_reference().unwrap().init_from_path(path);
+    Ok(Value::Undefined)
+}
+
+pub fn get_exists<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let exists = file_path(this)
+        .and_then(|path| activation.context.filesystem.metadata(&path))
+        .is_some();
+    Ok(exists.into())
+}
+
+pub fn get_is_directory<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let is_directory = file_path(this)
+        .and_then(|path| activation.context.filesystem.metadata(&path))
+        .is_some_and(|metadata| metadata.is_directory);
+    Ok(is_directory.into())
+}
+
+pub fn get_native_path<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let native_path = file_path(this)
+        .map(|path| activation.context.filesystem.native_path(&path))
+        .unwrap_or_default();
+    Ok(AvmString::new_utf8(activation.gc(), native_path).into())
+}
+
+pub fn set_native_path<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let native_path = args.get_string(activation, 0)?.to_string();
+    let path = activation.context.filesystem.path_from_native(&native_path);
+    set_path(activation, this, path)
+}
+
+pub fn get_url<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(match file_path(this) {
+        Some(path) => AvmString::new_utf8(activation.gc(), path.to_url()).into(),
+        None => Value::Null,
+    })
+}
+
+pub fn set_url<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = args.get_string(activation, 0)?.to_string();
+    set_path(activation, this, FilePath::from_url(&url))
+}
+
+pub fn get_parent<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    match file_path(this).and_then(|path| path.parent()) {
+        Some(parent) => Ok(file_for_path(activation, Some(parent))?.into()),
+        None => Ok(Value::Null),
+    }
+}
+
+pub fn resolve_path<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = required_path(activation, this)?;
+    let relative = args.get_string(activation, 0)?.to_string();
+
+    // Paths outside of the roots can't be accessed, so they don't point anywhere.
+    let resolved = path.resolve(&relative);
+    Ok(file_for_path(activation, resolved)?.into())
+}
+
+pub fn get_relative_path<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = required_path(activation, this)?;
+    let reference = args.get_object(activation, 0, "ref")?;
+    let use_dot_dot = args.get_bool(1);
+    let Some(reference) = file_path(reference) else {
+        return Ok(Value::Null);
+    };
+    if path.file_root() != reference.file_root() {
+        return Ok(Value::Null);
+    }
+
+    let common = path
+        .components()
+        .iter()
+        .zip(reference.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let up = path.components().len() - common;
+    if up > 0 && !use_dot_dot {
+        return Ok(Value::Null);
+    }
+
+    let components: Vec<&str> = std::iter::repeat("..")
+        .take(up)
+        .chain(reference.components()[common..].iter().map(String::as_str))
+        .collect();
+    Ok(AvmString::new_utf8(activation.gc(), components.join("/")).into())
+}
+
+pub fn get_directory_listing<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = required_path(activation, this)?;
+    let names = match activation.context.filesystem.list_directory(&path) {
+        Ok(names) => names,
+        Err(e) => return Err(filesystem_error(activation, e)),
+    };
+
+    let mut files = Vec::with_capacity(names.len());
+    for name in names {
+        files.push(file_for_path(activation, Some(path.join(&name)))?.into());
+    }
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&files))?.into())
+}
+
+/// Implements `copyTo` and `moveTo`.
+fn copy_or_move<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+    is_move: bool,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let from = if is_move {
+        writable_path(activation, this)?
+    } else {
+        required_path(activation, this)?
+    };
+    let new_location = args.get_object(activation, 0, "newLocation")?;
+    let to = writable_path(activation, new_location)?;
+    let overwrite = args.get_bool(1);
+
+    let filesystem = &mut *activation.context.filesystem;
+    let result = (|| {
+        if filesystem.metadata(&from).is_none() {
+            return Err(FilesystemError::NotFound);
+        }
+        if from == to {
+            return Ok(());
+        }
+        if filesystem.metadata(&to).is_some() {
+            if to.contains(&from) {
+                // Overwriting a directory containing the source would delete it.
+                return Err(FilesystemError::AccessDenied);
+            }
+            if !overwrite {
+                return Err(FilesystemError::AlreadyExists);
+            }
+            delete_recursive(filesystem, &to)?;
+        }
+        copy_recursive(filesystem, &from, &to)?;
+        if is_move {
+            delete_recursive(filesystem, &from)?;
+        }
+        Ok(())
+    })();
+
+    match result {
+        Ok(()) => Ok(Value::Undefined),
+        Err(e) => Err(filesystem_error(activation, e)),
+    }
+}
+
+pub fn copy_to<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    copy_or_move(activation, this, args, false)
+}
+
+pub fn move_to<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    copy_or_move(activation, this, args, true)
+}
+
+pub fn create_directory<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = writable_path(activation, this)?;
+    match activation.context.filesystem.create_directory(&path) {
+        Ok(()) => Ok(Value::Undefined),
+        Err(e) => Err(filesystem_error(activation, e)),
+    }
+}
+
+pub fn delete_file<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = writable_path(activation, this)?;
+    if metadata(activation, &path)?.is_directory {
+        return Err(filesystem_error(activation, FilesystemError::AccessDenied));
+    }
+    match activation.context.filesystem.delete(&path) {
+        Ok(()) => Ok(Value::Undefined),
+        Err(e) => Err(filesystem_error(activation, e)),
+    }
+}
+
+pub fn delete_directory<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = writable_path(activation, this)?;
+    let delete_directory_contents = args.get_bool(0);
+    if !metadata(activation, &path)?.is_directory {
+        return Err(filesystem_error(activation, FilesystemError::AccessDenied));
+    }
+
+    let filesystem = &mut *activation.context.filesystem;
+    let result = if delete_directory_contents {
+        delete_recursive(filesystem, &path)
+    } else {
+        filesystem.delete(&path)
+    };
+    match result {
+        Ok(()) => Ok(Value::Undefined),
+        Err(e) => Err(filesystem_error(activation, e)),
+    }
+}
@@ -0,0 +1,94 @@
+//! `flash.filesystem.FileStream` native methods
+
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::error::make_error_2037;
+use crate::avm2::globals::flash::filesystem::file::{file_path, filesystem_error, writable_path};
+use crate::avm2::object::{ByteArrayObject, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, Value};
+
+/// Reads the whole contents of a file into a new `ByteArray`.
+pub fn read_file<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let file = args.get_object(activation, 0, "file")?;
+    let Some(path) = file_path(file) else {
+        return Err(make_error_2037(activation));
+    };
+
+    let data = match activation.context.filesystem.read(&path) {
+        Ok(data) => data,
+        Err(e) => return Err(filesystem_error(activation, e)),
+    };
+    let storage = ByteArrayStorage::from_vec(data);
+    Ok(ByteArrayObject::from_storage(activation, storage)?.into())
+}
+
+/// Replaces the contents of a file with a `ByteArray`.
+pub fn write_file<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let file = args.get_object(activation, 0, "file")?;
+    let path = writable_path(activation, file)?;
+    let data = args.get_object(activation, 1, "data")?;
+    let data = data
+        .as_bytearray()
+        .map(|bytes| bytes.bytes().to_vec())
+        .unwrap_or_default();
+
+    match activation.context.filesystem.write(&path, &data) {
+        Ok(()) => Ok(Value::Undefined),
+        Err(e) => Err(filesystem_error(activation, e)),
+    }
+}
+
+/// Writes the bytes of a `ByteArray` between `start` and `end` to the same
+/// place in a file.
+pub fn write_file_range<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let file = args.get_object(activation, 0, "file")?;
+    let path = writable_path(activation, file)?;
+    let data = args.get_object(activation, 1, "data")?;
+    let start = args.get_u32(activation, 2)? as usize;
+    let end = args.get_u32(activation, 3)? as usize;
+    let data = data
+        .as_bytearray()
+        .and_then(|bytes| bytes.bytes().get(start..end).map(<[u8]>::to_vec))
+        .unwrap_or_default();
+
+    match activation
+        .context
+        .filesystem
+        .write_at(&path, start as u64, &data)
+    {
+        Ok(()) => Ok(Value::Undefined),
+        Err(e) => Err(filesystem_error(activation, e)),
+    }
+}
+
+/// Cuts a file off at, or pads it with zeros up to, the given length.
+pub fn set_file_length<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let file = args.get_object(activation, 0, "file")?;
+    let path = writable_path(activation, file)?;
+    let length = args.get_u32(activation, 1)?;
+
+    match activation
+        .context
+        .filesystem
+        .set_length(&path, length.into())
+    {
+        Ok(()) => Ok(Value::Undefined),
+        Err(e) => Err(filesystem_error(activation, e)),
+    }
+}
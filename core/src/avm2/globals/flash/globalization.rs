@@ -0,0 +1,425 @@
+//! `flash.globalization` namespace
+
+use crate::avm2::object::{TObject, VectorObject};
+use crate::avm2::vector::VectorStorage;
+use crate::avm2::{Activation, Error, Multiname, Object, Value};
+use crate::string::{AvmString, WStr, WString};
+
+pub mod currency_formatter;
+pub mod date_time_formatter;
+mod locale_data;
+pub mod number_formatter;
+
+use locale_data::LocaleData;
+
+const NO_ERROR: &str = "noError";
+const PARSE_ERROR: &str = "parseError";
+const USING_DEFAULT_WARNING: &str = "usingDefaultWarning";
+const USING_FALLBACK_WARNING: &str = "usingFallbackWarning";
+
+/// Pick the locale to use for a requested locale ID.
+///
+/// Returns the locale along with the `LastOperationStatus` that describes how
+/// it was picked. Locales that aren't known fall back to another region with
+/// the same language, then to the user's language, and finally to `en-US`.
+fn resolve_locale(
+    activation: &mut Activation<'_, '_>,
+    requested: &WStr,
+) -> (&'static LocaleData, &'static str) {
+    let requested = requested.to_utf8_lossy().replace('_', "-");
+    // Keywords, such as "@collation=phonebook", don't affect formatting.
+    let requested = requested.split('@').next().unwrap_or_default();
+
+    let ui_language = activation.context.ui.language();
+    let ui_locale = locale_data::find(&ui_language.to_string())
+        .or_else(|| locale_data::find_language(ui_language.language.as_str()));
+
+    if requested.eq_ignore_ascii_case("i-default") {
+        return match ui_locale {
+            Some(locale) => (locale, NO_ERROR),
+            None => (locale_data::default_locale(), USING_DEFAULT_WARNING),
+        };
+    }
+
+    if let Some(locale) = locale_data::find(requested) {
+        return (locale, NO_ERROR);
+    }
+
+    let language = requested.split('-').next().unwrap_or_default();
+    if let Some(locale) = locale_data::find_language(language) {
+        return (locale, USING_FALLBACK_WARNING);
+    }
+
+    (
+        ui_locale.unwrap_or_else(locale_data::default_locale),
+        USING_DEFAULT_WARNING,
+    )
+}
+
+/// Look up the locale that a formatter picked when it was constructed.
+fn actual_locale<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<&'static LocaleData, Error<'gc>> {
+    let name = get_internal(activation, this, "_actualLocaleIDName")?
+        .coerce_to_string(activation)?
+        .to_utf8_lossy()
+        .into_owned();
+
+    Ok(locale_data::find(&name).unwrap_or_else(locale_data::default_locale))
+}
+
+/// Implements `getAvailableLocaleIDNames` for all formatters.
+fn available_locale_id_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let names = locale_data::LOCALES
+        .iter()
+        .map(|locale| AvmString::new_utf8(activation.gc(), locale.name).into())
+        .collect();
+
+    let value_type = activation.avm2().class_defs().string;
+    let storage = VectorStorage::from_values(names, false, Some(value_type));
+    Ok(VectorObject::from_vector(storage, activation)?.into())
+}
+
+fn get_internal<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = Multiname::new(
+        activation.avm2().namespaces.flash_globalization_internal,
+        name,
+    );
+    this.get_property(&name, activation)
+}
+
+fn set_internal<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    name: &'static str,
+    value: impl Into<Value<'gc>>,
+) -> Result<(), Error<'gc>> {
+    let name = Multiname::new(
+        activation.avm2().namespaces.flash_globalization_internal,
+        name,
+    );
+    this.set_property(&name, value.into(), activation)
+}
+
+fn set_last_operation_status<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    status: &'static str,
+) -> Result<(), Error<'gc>> {
+    set_internal(activation, this, "_lastOperationStatus", status)
+}
+
+/// The settings shared by `NumberFormatter` and `CurrencyFormatter`, read
+/// from the public properties of either.
+struct NumberFormat<'gc> {
+    decimal_separator: AvmString<'gc>,
+    grouping_separator: AvmString<'gc>,
+    grouping_pattern: GroupingPattern,
+    fractional_digits: usize,
+    leading_zero: bool,
+    trailing_zeros: bool,
+    use_grouping: bool,
+    negative_symbol: AvmString<'gc>,
+
+    /// The code point of the zero digit to use.
+    digits_type: u32,
+}
+
+/// A parsed `groupingPattern`, such as `"3;2;*"`.
+struct GroupingPattern {
+    /// The sizes of digit groups, starting from the decimal separator.
+    sizes: Vec<usize>,
+
+    /// Whether the last size is repeated for the rest of the number.
+    repeat_last: bool,
+}
+
+impl GroupingPattern {
+    fn parse(pattern: &WStr) -> Self {
+        let pattern = pattern.to_utf8_lossy();
+        let mut sizes = Vec::new();
+        let mut repeat_last = false;
+        for part in pattern.split(';') {
+            if part.trim() == "*" {
+                repeat_last = true;
+                break;
+            }
+            match part.trim().parse::<usize>() {
+                Ok(size) if size > 0 => sizes.push(size),
+                _ => break,
+            }
+        }
+
+        Self { sizes, repeat_last }
+    }
+
+    /// Split the integer digits of a number into groups, from left to right.
+    fn group<'a>(&self, digits: &'a str) -> Vec<&'a str> {
+        let mut groups = Vec::new();
+        let mut end = digits.len();
+        let mut sizes = self.sizes.iter();
+        let mut size = sizes.next().copied();
+        while let Some(group_size) = size {
+            if end <= group_size {
+                break;
+            }
+            groups.push(&digits[end - group_size..end]);
+            end -= group_size;
+            size = sizes
+                .next()
+                .copied()
+                .or(self.repeat_last.then_some(group_size));
+        }
+        groups.push(&digits[..end]);
+        groups.reverse();
+        groups
+    }
+}
+
+/// A number found by [`NumberFormat::parse`].
+struct ParsedNumber {
+    value: f64,
+
+    /// The index of the first character of the number, including its sign.
+    start: usize,
+
+    /// The index after the last character of the number, including its sign.
+    end: usize,
+}
+
+impl<'gc> NumberFormat<'gc> {
+    fn read(activation: &mut Activation<'_, 'gc>, this: Object<'gc>) -> Result<Self, Error<'gc>> {
+        let decimal_separator = this
+            .get_public_property("decimalSeparator", activation)?
+            .coerce_to_string(activation)?;
+        let grouping_separator = this
+            .get_public_property("groupingSeparator", activation)?
+            .coerce_to_string(activation)?;
+        let grouping_pattern = this
+            .get_public_property("groupingPattern", activation)?
+            .coerce_to_string(activation)?;
+        let fractional_digits = this
+            .get_public_property("fractionalDigits", activation)?
+            .coerce_to_i32(activation)?;
+        let leading_zero = this
+            .get_public_property("leadingZero", activation)?
+            .coerce_to_boolean();
+        let trailing_zeros = this
+            .get_public_property("trailingZeros", activation)?
+            .coerce_to_boolean();
+        let use_grouping = this
+            .get_public_property("useGrouping", activation)?
+            .coerce_to_boolean();
+        let negative_symbol = this
+            .get_public_property("negativeSymbol", activation)?
+            .coerce_to_string(activation)?;
+        let digits_type = this
+            .get_public_property("digitsType", activation)?
+            .coerce_to_u32(activation)?;
+
+        Ok(Self {
+            decimal_separator,
+            grouping_separator,
+            grouping_pattern: GroupingPattern::parse(&grouping_pattern),
+            fractional_digits: fractional_digits.clamp(0, 20) as usize,
+            leading_zero,
+            trailing_zeros,
+            use_grouping,
+            negative_symbol,
+            digits_type,
+        })
+    }
+
+    /// Format the absolute value of a number, without any sign.
+    ///
+    /// Returns the formatted number, and whether it's negative.
+    fn format_unsigned(&self, value: f64, fractional_digits: usize) -> (WString, bool) {
+        let mut output = WString::new();
+        if value.is_nan() {
+            output.push_utf8("NaN");
+            return (output, false);
+        }
+        if value.is_infinite() {
+            output.push_char('∞');
+            return (output, value < 0.0);
+        }
+
+        let formatted = format!("{:.*}", fractional_digits, value.abs());
+        let (integer, fraction) = formatted
+            .split_once('.')
+            .unwrap_or((formatted.as_str(), ""));
+        let fraction = if self.trailing_zeros {
+            fraction
+        } else {
+            fraction.trim_end_matches('0')
+        };
+        let is_negative = value < 0.0 && formatted.chars().any(|c| c.is_ascii_digit() && c != '0');
+
+        if integer != "0" || self.leading_zero || fraction.is_empty() {
+            if self.use_grouping {
+                for (i, group) in self.grouping_pattern.group(integer).iter().enumerate() {
+                    if i > 0 {
+                        output.push_str(&self.grouping_separator);
+                    }
+                    self.push_digits(&mut output, group);
+                }
+            } else {
+                self.push_digits(&mut output, integer);
+            }
+        }
+        if !fraction.is_empty() {
+            output.push_str(&self.decimal_separator);
+            self.push_digits(&mut output, fraction);
+        }
+
+        (output, is_negative)
+    }
+
+    /// Format a number, using `NumberFormatter.negativeNumberFormat`.
+    fn format_number(&self, value: f64, fractional_digits: usize, negative_format: u32) -> WString {
+        let (number, is_negative) = self.format_unsigned(value, fractional_digits);
+        if !is_negative {
+            return number;
+        }
+
+        let mut output = WString::new();
+        match negative_format {
+            0 => {
+                output.push_char('(');
+                output.push_str(&number);
+                output.push_char(')');
+            }
+            2 => {
+                output.push_str(&self.negative_symbol);
+                output.push_char(' ');
+                output.push_str(&number);
+            }
+            3 => {
+                output.push_str(&number);
+                output.push_str(&self.negative_symbol);
+            }
+            4 => {
+                output.push_str(&number);
+                output.push_char(' ');
+                output.push_str(&self.negative_symbol);
+            }
+            _ => {
+                output.push_str(&self.negative_symbol);
+                output.push_str(&number);
+            }
+        }
+        output
+    }
+
+    fn push_digits(&self, output: &mut WString, digits: &str) {
+        for digit in digits.chars() {
+            let digit = digit.to_digit(10).unwrap_or_default();
+            let c = char::from_u32(self.digits_type + digit).unwrap_or('?');
+            output.push_char(c);
+        }
+    }
+
+    fn digit_value(&self, c: u16) -> Option<u32> {
+        let c = u32::from(c);
+        if (u32::from(b'0')..=u32::from(b'9')).contains(&c) {
+            Some(c - u32::from(b'0'))
+        } else if (self.digits_type..self.digits_type + 10).contains(&c) {
+            Some(c - self.digits_type)
+        } else {
+            None
+        }
+    }
+
+    /// Find a number at the start of some text, after any whitespace.
+    ///
+    /// The number may be negative in any of the `negativeNumberFormat` styles.
+    fn parse(&self, text: &WStr) -> Option<ParsedNumber> {
+        let is_space = |c: u16| char::from_u32(u32::from(c)).is_some_and(char::is_whitespace);
+        let mut index = text.iter().position(|c| !is_space(c))?;
+        let start = index;
+
+        let mut is_negative = false;
+        let mut has_parenthesis = false;
+        if text.at(index) == u16::from(b'(') {
+            has_parenthesis = true;
+            is_negative = true;
+            index += 1;
+        } else if !self.negative_symbol.is_empty()
+            && text[index..].starts_with(&self.negative_symbol)
+        {
+            is_negative = true;
+            index += self.negative_symbol.len();
+        }
+        while index < text.len() && is_space(text.at(index)) {
+            index += 1;
+        }
+
+        let mut digits = String::new();
+        let mut has_digits = false;
+        let mut has_decimal = false;
+        while index < text.len() {
+            let rest = &text[index..];
+            if let Some(digit) = self.digit_value(text.at(index)) {
+                digits.push(char::from_digit(digit, 10).unwrap_or('0'));
+                has_digits = true;
+                index += 1;
+            } else if !has_decimal
+                && !self.decimal_separator.is_empty()
+                && rest.starts_with(&self.decimal_separator)
+            {
+                digits.push('.');
+                has_decimal = true;
+                index += self.decimal_separator.len();
+            } else if !has_decimal
+                && has_digits
+                && !self.grouping_separator.is_empty()
+                && rest.starts_with(&self.grouping_separator)
+                && rest
+                    .get(self.grouping_separator.len())
+                    .and_then(|c| self.digit_value(c))
+                    .is_some()
+            {
+                index += self.grouping_separator.len();
+            } else {
+                break;
+            }
+        }
+        if !has_digits {
+            return None;
+        }
+
+        if has_parenthesis {
+            while index < text.len() && is_space(text.at(index)) {
+                index += 1;
+            }
+            if index >= text.len() || text.at(index) != u16::from(b')') {
+                return None;
+            }
+            index += 1;
+        } else if !is_negative && !self.negative_symbol.is_empty() {
+            // The negative symbol may also come after the number.
+            let mut suffix = index;
+            while suffix < text.len() && is_space(text.at(suffix)) {
+                suffix += 1;
+            }
+            if text[suffix..].starts_with(&self.negative_symbol) {
+                is_negative = true;
+                index = suffix + self.negative_symbol.len();
+            }
+        }
+
+        let value = digits.parse::<f64>().ok()?;
+        Some(ParsedNumber {
+            value: if is_negative { -value } else { value },
+            start,
+            end: index,
+        })
+    }
+}
@@ -1,101 +1,98 @@
 package flash.globalization {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
+    import flash.globalization.DateTimeStyle;
     import flash.globalization.LastOperationStatus;
 
     public final class DateTimeFormatter {
         private var _dateStyle:String;
         private var _dateTimePattern:String;
-        private var _localeIDName:String;
+        private var _requestedLocaleIDName:String;
         private var _timeStyle:String;
 
+        internal var _actualLocaleIDName:String;
+        internal var _lastOperationStatus:String = LastOperationStatus.NO_ERROR;
+
         private static function throwNonNull(name: String) {
             throw new TypeError("Error #2007: Parameter " + name + " must be non-null.", 2007);
         }
 
+        private static function checkStyle(style:String, name:String):void {
+            if (style == null) throwNonNull(name);
+            if (style != DateTimeStyle.LONG && style != DateTimeStyle.MEDIUM &&
+                style != DateTimeStyle.SHORT && style != DateTimeStyle.NONE) {
+                throw new ArgumentError("Error #2008: Parameter " + name + " must be one of the accepted values.", 2008);
+            }
+        }
+
         public function DateTimeFormatter(requestedLocaleIDName:String, dateStyle:String = "long", timeStyle:String = "long") {
-            stub_constructor("flash.globalization.DateTimeFormatter");
             if (requestedLocaleIDName == null) throwNonNull("requestedLocaleIDName");
-            this._localeIDName = requestedLocaleIDName;
-            this._dateTimePattern = "EEEE, MMMM d, yyyy h:mm:ss a";
+            this._requestedLocaleIDName = requestedLocaleIDName;
+            this.init(requestedLocaleIDName);
+
+            // Keep the status from picking the locale.
+            var status:String = this._lastOperationStatus;
             this.setDateTimeStyles(dateStyle, timeStyle);
+            this._lastOperationStatus = status;
         }
 
+        private native function init(requestedLocaleIDName:String):void;
+
+        private native function stylePattern(dateStyle:String, timeStyle:String):String;
+
         public function get actualLocaleIDName():String {
-            stub_getter("flash.globalization.DateTimeFormatter", "actualLocaleIDName");
-            return this._localeIDName;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this._actualLocaleIDName;
         }
 
         public function get lastOperationStatus():String {
-            stub_getter("flash.globalization.DateTimeFormatter", "lastOperationStatus");
-            return LastOperationStatus.NO_ERROR;
+            return this._lastOperationStatus;
         }
 
         public function get requestedLocaleIDName():String {
-            return this._localeIDName;
+            return this._requestedLocaleIDName;
         }
 
-        public function format(dateTime:Date):String {
-            stub_method("flash.globalization.DateTimeFormatter", "format");
-            if (dateTime == null) throwNonNull("dateTime");
-            return dateTime.toString();
-        }
+        public native function format(dateTime:Date):String;
 
-        public function formatUTC(dateTime:Date):String {
-            stub_method("flash.globalization.DateTimeFormatter", "formatUTC");
-            if (dateTime == null) throwNonNull("dateTime");
-            return dateTime.toUTCString();
-        }
+        public native function formatUTC(dateTime:Date):String;
 
-        public static function getAvailableLocaleIDNames():Vector.<String> {
-            stub_method("flash.globalization.DateTimeFormatter", "getAvailableLocaleIDNames");
-            return new <String>["en-US"];
-        }
+        public static native function getAvailableLocaleIDNames():Vector.<String>;
 
         public function getDateStyle():String {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             return this._dateStyle;
         }
 
         public function getDateTimePattern():String {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             return this._dateTimePattern;
         }
 
-        public function getFirstWeekday():int {
-            stub_method("flash.globalization.DateTimeFormatter", "getFirstWeekday");
-            return 0;
-        }
+        public native function getFirstWeekday():int;
 
-        public function getMonthNames(nameStyle:String = "full", context:String = "standalone"):Vector.<String> {
-            stub_method("flash.globalization.DateTimeFormatter", "getMonthNames");
-            if (nameStyle == null) throwNonNull("nameStyle");
-            if (context == null) throwNonNull("context");
-            return new <String>["January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November", "December"];
-        }
+        public native function getMonthNames(nameStyle:String = "full", context:String = "standalone"):Vector.<String>;
 
         public function getTimeStyle():String {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             return this._timeStyle;
         }
 
-        public function getWeekdayNames(nameStyle:String = "full", context:String = "standalone"):Vector.<String> {
-            stub_method("flash.globalization.DateTimeFormatter", "getWeekdayNames");
-            if (nameStyle == null) throwNonNull("nameStyle");
-            if (context == null) throwNonNull("context");
-            return new <String>["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"];
-        }
+        public native function getWeekdayNames(nameStyle:String = "full", context:String = "standalone"):Vector.<String>;
 
         public function setDateTimePattern(pattern:String):void {
-            stub_method("flash.globalization.DateTimeFormatter", "setDateTimePattern");
             if (pattern == null) throwNonNull("pattern");
             this._dateTimePattern = pattern;
+            this._dateStyle = DateTimeStyle.CUSTOM;
+            this._timeStyle = DateTimeStyle.CUSTOM;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function setDateTimeStyles(dateStyle:String, timeStyle:String):void {
-            stub_method("flash.globalization.DateTimeFormatter", "setDateTimeStyles");
-            if (dateStyle == null) throwNonNull("dateStyle");
-            if (timeStyle == null) throwNonNull("timeStyle");
+            checkStyle(dateStyle, "dateStyle");
+            checkStyle(timeStyle, "timeStyle");
             this._dateStyle = dateStyle;
             this._timeStyle = timeStyle;
+            this._dateTimePattern = this.stylePattern(dateStyle, timeStyle);
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
     }
-}
\ No newline at end of file
+}
@@ -1,22 +1,52 @@
 package flash.globalization {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
     import flash.globalization.LastOperationStatus;
 
     public final class LocaleID {
         public static const DEFAULT:String = "i-default";
 
         private var _name:String;
+        private var _language:String = "";
+        private var _script:String = "";
+        private var _region:String = "";
+        private var _variant:String = "";
+        private var _keywords:Object = {};
+        private var _lastOperationStatus:String = LastOperationStatus.NO_ERROR;
 
         public function LocaleID(name:String) {
-            stub_constructor("flash.globalization.LocaleID");
+            if (name == null) {
+                throw new TypeError("Error #2007: Parameter name must be non-null.", 2007);
+            }
             this._name = name;
+
+            // Locale IDs look like "language-Script-REGION-variant@key=value;key=value",
+            // where everything but the language is optional.
+            var tags:String = name;
+            var keywordsIndex:int = name.indexOf("@");
+            if (keywordsIndex >= 0) {
+                tags = name.substring(0, keywordsIndex);
+                for each (var pair:String in name.substring(keywordsIndex + 1).split(";")) {
+                    var equalsIndex:int = pair.indexOf("=");
+                    if (equalsIndex > 0) {
+                        this._keywords[pair.substring(0, equalsIndex)] = pair.substring(equalsIndex + 1);
+                    }
+                }
+            }
+
+            var subtags:Array = tags.split("_").join("-").split("-");
+            this._language = subtags.shift();
+            if (subtags.length > 0 && /^[A-Za-z]{4}$/.test(subtags[0])) {
+                this._script = subtags.shift();
+            }
+            if (subtags.length > 0 && /^([A-Za-z]{2}|[0-9]{3})$/.test(subtags[0])) {
+                this._region = subtags.shift();
+            }
+            if (subtags.length > 0) {
+                this._variant = subtags.join("-");
+            }
         }
 
         public function get lastOperationStatus():String {
-            stub_getter("flash.globalization.LocaleID", "lastOperationStatus");
-            return LastOperationStatus.NO_ERROR;
+            return this._lastOperationStatus;
         }
 
         public function get name():String {
@@ -24,38 +54,82 @@ package flash.globalization {
         }
 
         public static function determinePreferredLocales(want:Vector.<String>, have:Vector.<String>, keyword:String = "userinterface"):Vector.<String> {
-            stub_method("flash.globalization.LocaleID", "determinePreferredLocales");
-            return null;
+            if (want == null) {
+                throw new TypeError("Error #2007: Parameter want must be non-null.", 2007);
+            }
+            if (have == null) {
+                throw new TypeError("Error #2007: Parameter have must be non-null.", 2007);
+            }
+
+            var result:Vector.<String> = new Vector.<String>();
+            var add = function(locale:String):void {
+                if (result.indexOf(locale) < 0) {
+                    result.push(locale);
+                }
+            };
+
+            for each (var wanted:String in want) {
+                var wantedID:LocaleID = new LocaleID(wanted);
+                var candidate:String;
+
+                // An exact match is best, followed by any locale of the same language.
+                for each (candidate in have) {
+                    if (candidate.toLowerCase().split("_").join("-") == wanted.toLowerCase().split("_").join("-")) {
+                        add(candidate);
+                    }
+                }
+                for each (candidate in have) {
+                    if (new LocaleID(candidate).getLanguage().toLowerCase() == wantedID.getLanguage().toLowerCase()) {
+                        add(candidate);
+                    }
+                }
+            }
+
+            return result;
         }
 
         public function getKeysAndValues():Object {
-            stub_method("flash.globalization.LocaleID", "getKeysAndValues");
-            return new Object();
+            var result:Object = {};
+            for (var key:String in this._keywords) {
+                result[key] = this._keywords[key];
+            }
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return result;
         }
 
         public function getLanguage():String {
-            stub_method("flash.globalization.LocaleID", "getLanguage");
-            return this._name;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this._language;
         }
 
         public function getRegion():String {
-            stub_method("flash.globalization.LocaleID", "getRegion");
-            return "";
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this._region;
         }
 
         public function getScript():String {
-            stub_method("flash.globalization.LocaleID", "getScript");
-            return "";
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this._script;
         }
 
         public function getVariant():String {
-            stub_method("flash.globalization.LocaleID", "getVariant");
-            return "";
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this._variant;
         }
 
         public function isRightToLeft():Boolean {
-            stub_method("flash.globalization.LocaleID", "isRightToLeft");
-            return false;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+
+            var script:String = this._script.toLowerCase();
+            if (script != "") {
+                return script == "arab" || script == "hebr" || script == "thaa" ||
+                    script == "syrc" || script == "nkoo";
+            }
+
+            var language:String = this._language.toLowerCase();
+            return language == "ar" || language == "he" || language == "fa" ||
+                language == "ur" || language == "yi" || language == "ps" ||
+                language == "dv" || language == "ug";
         }
     }
 }
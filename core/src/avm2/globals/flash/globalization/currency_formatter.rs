@@ -0,0 +1,190 @@
+//! `flash.globalization.CurrencyFormatter` native methods
+
+use crate::avm2::globals::flash::globalization::{
+    actual_locale, available_locale_id_names, get_internal, resolve_locale, set_internal,
+    set_last_operation_status, NumberFormat, NO_ERROR, PARSE_ERROR,
+};
+use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::string::{AvmString, WString};
+
+/// The formats documented on `CurrencyFormatter.positiveCurrencyFormat`,
+/// where `¤` is the currency string and `n` is the number.
+const POSITIVE_FORMATS: [&str; 4] = ["¤n", "n¤", "¤ n", "n ¤"];
+
+/// The formats documented on `CurrencyFormatter.negativeCurrencyFormat`,
+/// where `¤` is the currency string, `n` is the number, and `-` is the
+/// negative symbol.
+const NEGATIVE_FORMATS: [&str; 16] = [
+    "(¤n)", "-¤n", "¤-n", "¤n-", "(n¤)", "-n¤", "n-¤", "n¤-", "-n ¤", "-¤ n", "n ¤-", "¤ n-",
+    "¤ -n", "n- ¤", "(¤ n)", "(n ¤)",
+];
+
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string(activation, 0)?;
+    let (locale, status) = resolve_locale(activation, &requested);
+
+    this.set_public_property(
+        "decimalSeparator",
+        locale.decimal_separator.into(),
+        activation,
+    )?;
+    this.set_public_property(
+        "groupingSeparator",
+        locale.grouping_separator.into(),
+        activation,
+    )?;
+    this.set_public_property(
+        "groupingPattern",
+        locale.grouping_pattern.into(),
+        activation,
+    )?;
+    this.set_public_property(
+        "fractionalDigits",
+        locale.currency_fractional_digits.into(),
+        activation,
+    )?;
+    this.set_public_property(
+        "positiveCurrencyFormat",
+        locale.positive_currency_format.into(),
+        activation,
+    )?;
+    this.set_public_property(
+        "negativeCurrencyFormat",
+        locale.negative_currency_format.into(),
+        activation,
+    )?;
+
+    set_internal(
+        activation,
+        this,
+        "_currencyISOCode",
+        locale.currency_iso_code,
+    )?;
+    set_internal(activation, this, "_currencySymbol", locale.currency_symbol)?;
+    set_internal(activation, this, "_actualLocaleIDName", locale.name)?;
+    set_last_operation_status(activation, this, status)?;
+
+    Ok(Value::Undefined)
+}
+
+pub fn format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get_f64(activation, 0)?;
+    let with_currency_symbol = args.get_bool(1);
+
+    let format = NumberFormat::read(activation, this)?;
+    let currency = if with_currency_symbol {
+        get_internal(activation, this, "_currencySymbol")?
+    } else {
+        get_internal(activation, this, "_currencyISOCode")?
+    }
+    .coerce_to_string(activation)?;
+
+    let (number, is_negative) = format.format_unsigned(value, format.fractional_digits);
+    let template = if is_negative {
+        let index = this
+            .get_public_property("negativeCurrencyFormat", activation)?
+            .coerce_to_u32(activation)?;
+        NEGATIVE_FORMATS
+            .get(index as usize)
+            .unwrap_or(&NEGATIVE_FORMATS[1])
+    } else {
+        let index = this
+            .get_public_property("positiveCurrencyFormat", activation)?
+            .coerce_to_u32(activation)?;
+        POSITIVE_FORMATS
+            .get(index as usize)
+            .unwrap_or(&POSITIVE_FORMATS[0])
+    };
+
+    let mut output = WString::new();
+    for c in template.chars() {
+        match c {
+            '¤' => output.push_str(&currency),
+            'n' => output.push_str(&number),
+            '-' => output.push_str(&format.negative_symbol),
+            _ => output.push_char(c),
+        }
+    }
+
+    set_last_operation_status(activation, this, NO_ERROR)?;
+    Ok(AvmString::new(activation.gc(), output).into())
+}
+
+pub fn formatting_with_currency_symbol_is_safe<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string_non_null(activation, 0, "requestedISOCode")?;
+    let locale = actual_locale(activation, this)?;
+
+    set_last_operation_status(activation, this, NO_ERROR)?;
+    Ok((requested.to_utf8_lossy() == locale.currency_iso_code).into())
+}
+
+pub fn parse<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let text = args.get_string_non_null(activation, 0, "inputString")?;
+    let format = NumberFormat::read(activation, this)?;
+    let iso_code =
+        get_internal(activation, this, "_currencyISOCode")?.coerce_to_string(activation)?;
+    let symbol = get_internal(activation, this, "_currencySymbol")?.coerce_to_string(activation)?;
+
+    // The currency string may be on either side of the number, so take it
+    // out and parse what's left.
+    let mut currency_string = AvmString::default();
+    let mut number = WString::from(&*text);
+    for currency in [iso_code, symbol] {
+        if currency.is_empty() {
+            continue;
+        }
+        if let Some(index) = text.find(&*currency) {
+            currency_string = currency;
+            number = WString::new();
+            number.push_str(&text[..index]);
+            number.push_str(&text[index + currency.len()..]);
+            break;
+        }
+    }
+
+    let value = format.parse(&number).filter(|parsed| {
+        number[parsed.end..]
+            .iter()
+            .all(|c| char::from_u32(u32::from(c)).is_some_and(char::is_whitespace))
+    });
+
+    let result_class = activation.avm2().classes().currencyparseresult;
+    match value {
+        Some(parsed) => {
+            set_last_operation_status(activation, this, NO_ERROR)?;
+            Ok(result_class
+                .construct(activation, &[parsed.value.into(), currency_string.into()])?
+                .into())
+        }
+        None => {
+            set_last_operation_status(activation, this, PARSE_ERROR)?;
+            Ok(result_class.construct(activation, &[])?.into())
+        }
+    }
+}
+
+pub fn get_available_locale_id_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    available_locale_id_names(activation)
+}
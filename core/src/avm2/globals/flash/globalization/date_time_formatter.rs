@@ -0,0 +1,317 @@
+//! `flash.globalization.DateTimeFormatter` native methods
+
+use crate::avm2::error::make_error_2008;
+use crate::avm2::globals::flash::globalization::locale_data::LocaleData;
+use crate::avm2::globals::flash::globalization::{
+    actual_locale, available_locale_id_names, resolve_locale, set_internal,
+    set_last_operation_status, NO_ERROR,
+};
+use crate::avm2::object::{TObject, VectorObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::vector::VectorStorage;
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::locale::get_timezone;
+use crate::string::{AvmString, WStr, WString};
+use chrono::{DateTime, Datelike, FixedOffset, Offset, Timelike, Utc};
+
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string(activation, 0)?;
+    let (locale, status) = resolve_locale(activation, &requested);
+
+    set_internal(activation, this, "_actualLocaleIDName", locale.name)?;
+    set_last_operation_status(activation, this, status)?;
+
+    Ok(Value::Undefined)
+}
+
+/// Returns the pattern for a combination of date and time styles, which
+/// have already been checked by the caller.
+pub fn style_pattern<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let date_style = args.get_string(activation, 0)?;
+    let time_style = args.get_string(activation, 1)?;
+    let locale = actual_locale(activation, this)?;
+
+    let style_index = |style: &WStr| match style.to_utf8_lossy().as_ref() {
+        "long" => Some(0),
+        "medium" => Some(1),
+        "short" => Some(2),
+        _ => None,
+    };
+
+    let date_pattern = style_index(&date_style).map(|i| locale.date_patterns[i]);
+    let time_pattern = style_index(&time_style).map(|i| locale.time_patterns[i]);
+    let pattern = match (date_pattern, time_pattern) {
+        (Some(date), Some(time)) => format!("{date} {time}"),
+        (Some(pattern), None) | (None, Some(pattern)) => pattern.to_string(),
+        (None, None) => String::new(),
+    };
+
+    Ok(AvmString::new_utf8(activation.gc(), pattern).into())
+}
+
+fn format_date<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+    offset: FixedOffset,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let date = args.get_object(activation, 0, "dateTime")?;
+    let locale = actual_locale(activation, this)?;
+    let pattern = this
+        .call_public_property("getDateTimePattern", &[], activation)?
+        .coerce_to_string(activation)?;
+
+    let date_time = date.as_date_object().and_then(|date| date.date_time());
+    let output = match date_time {
+        Some(date_time) => format_pattern(locale, &pattern, date_time.with_timezone(&offset)),
+        None => WString::new(),
+    };
+
+    set_last_operation_status(activation, this, NO_ERROR)?;
+    Ok(AvmString::new(activation.gc(), output).into())
+}
+
+pub fn format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    format_date(activation, this, args, get_timezone())
+}
+
+pub fn format_utc<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    format_date(activation, this, args, Utc.fix())
+}
+
+/// Format a date with a Unicode TR35 date pattern, as used by
+/// `DateTimeFormatter.setDateTimePattern`.
+fn format_pattern(locale: &LocaleData, pattern: &WStr, date: DateTime<FixedOffset>) -> WString {
+    let pattern: Vec<char> = pattern.to_utf8_lossy().chars().collect();
+    let mut output = String::new();
+
+    let pad = |output: &mut String, value: u32, width: usize| {
+        output.push_str(&format!("{value:0width$}"));
+    };
+
+    let mut i = 0;
+    while i < pattern.len() {
+        let c = pattern[i];
+
+        if c == '\'' {
+            // Quoted text is copied as-is, and `''` is a literal quote.
+            if pattern.get(i + 1) == Some(&'\'') {
+                output.push('\'');
+                i += 2;
+                continue;
+            }
+            i += 1;
+            while i < pattern.len() {
+                if pattern[i] == '\'' {
+                    if pattern.get(i + 1) == Some(&'\'') {
+                        output.push('\'');
+                        i += 2;
+                        continue;
+                    }
+                    break;
+                }
+                output.push(pattern[i]);
+                i += 1;
+            }
+            i += 1;
+            continue;
+        }
+
+        if !c.is_ascii_alphabetic() {
+            output.push(c);
+            i += 1;
+            continue;
+        }
+
+        let count = pattern[i..].iter().take_while(|&&p| p == c).count();
+        i += count;
+
+        let month = date.month0() as usize;
+        let weekday = date.weekday().num_days_from_sunday() as usize;
+        match c {
+            'G' => output.push_str(if date.year() > 0 { "AD" } else { "BC" }),
+            'y' if count == 2 => pad(&mut output, date.year().rem_euclid(100) as u32, 2),
+            'y' => pad(&mut output, date.year().unsigned_abs(), count),
+            'M' | 'L' if count >= 4 => {
+                let names = match (c, locale.month_names_format) {
+                    ('M', Some(names)) => names,
+                    _ => locale.month_names,
+                };
+                output.push_str(names[month]);
+            }
+            'M' | 'L' if count == 3 => output.push_str(locale.month_names_abbreviated[month]),
+            'M' | 'L' => pad(&mut output, date.month(), count),
+            'd' => pad(&mut output, date.day(), count),
+            'D' => pad(&mut output, date.ordinal(), count),
+            'E' if count >= 4 => output.push_str(locale.weekday_names[weekday]),
+            'E' => output.push_str(locale.weekday_names_abbreviated[weekday]),
+            'Q' if count >= 3 => output.push_str(&format!("Q{}", month / 3 + 1)),
+            'Q' => pad(&mut output, month as u32 / 3 + 1, count),
+            'w' => pad(&mut output, date.iso_week().week(), count),
+            'W' => {
+                let first_of_month = date.with_day(1).unwrap_or(date);
+                let first_weekday = (first_of_month.weekday().num_days_from_sunday() + 7
+                    - locale.first_weekday as u32)
+                    % 7;
+                pad(&mut output, (date.day0() + first_weekday) / 7 + 1, count);
+            }
+            'F' => pad(&mut output, date.day0() / 7 + 1, count),
+            'a' => output.push_str(locale.am_pm[(date.hour() >= 12) as usize]),
+            'h' => pad(&mut output, (date.hour() + 11) % 12 + 1, count),
+            'H' => pad(&mut output, date.hour(), count),
+            'K' => pad(&mut output, date.hour() % 12, count),
+            'k' => pad(&mut output, (date.hour() + 23) % 24 + 1, count),
+            'm' => pad(&mut output, date.minute(), count),
+            's' => pad(&mut output, date.second(), count),
+            'S' => {
+                let millis = format!("{:03}", date.timestamp_subsec_millis());
+                let digits: String = millis
+                    .chars()
+                    .chain(std::iter::repeat('0'))
+                    .take(count)
+                    .collect();
+                output.push_str(&digits);
+            }
+            'z' | 'Z' | 'v' => {
+                let seconds = date.offset().local_minus_utc();
+                let sign = if seconds < 0 { '-' } else { '+' };
+                let minutes = seconds.unsigned_abs() / 60;
+                if c == 'Z' && count < 4 {
+                    output.push_str(&format!("{sign}{:02}{:02}", minutes / 60, minutes % 60));
+                } else {
+                    output.push_str(&format!("GMT{sign}{:02}:{:02}", minutes / 60, minutes % 60));
+                }
+            }
+            _ => (0..count).for_each(|_| output.push(c)),
+        }
+    }
+
+    WString::from_utf8_owned(output)
+}
+
+fn get_name_style<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+) -> Result<AvmString<'gc>, Error<'gc>> {
+    let name_style = args.get_string_non_null(activation, 0, "nameStyle")?;
+    if &name_style != b"full"
+        && &name_style != b"longAbbreviation"
+        && &name_style != b"shortAbbreviation"
+    {
+        return Err(make_error_2008(activation, "nameStyle"));
+    }
+    Ok(name_style)
+}
+
+fn get_name_context<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+) -> Result<AvmString<'gc>, Error<'gc>> {
+    let context = args.get_string_non_null(activation, 1, "context")?;
+    if &context != b"format" && &context != b"standalone" {
+        return Err(make_error_2008(activation, "context"));
+    }
+    Ok(context)
+}
+
+/// Build a `Vector.<String>` of names, as returned by `getMonthNames` and
+/// `getWeekdayNames`.
+fn names_vector<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    names: &[&'static str],
+    abbreviated: &[&'static str],
+    narrow: &[&'static str],
+    name_style: &WStr,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let names = match name_style.to_utf8_lossy().as_ref() {
+        "longAbbreviation" => abbreviated,
+        "shortAbbreviation" => narrow,
+        _ => names,
+    };
+    let names = names
+        .iter()
+        .map(|name| AvmString::new_utf8(activation.gc(), *name).into())
+        .collect();
+
+    let value_type = activation.avm2().class_defs().string;
+    let storage = VectorStorage::from_values(names, false, Some(value_type));
+    Ok(VectorObject::from_vector(storage, activation)?.into())
+}
+
+pub fn get_month_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name_style = get_name_style(activation, args)?;
+    let context = get_name_context(activation, args)?;
+    let locale = actual_locale(activation, this)?;
+
+    let names = match locale.month_names_format {
+        Some(names) if &context == b"format" => names,
+        _ => locale.month_names,
+    };
+
+    set_last_operation_status(activation, this, NO_ERROR)?;
+    names_vector(
+        activation,
+        &names,
+        &locale.month_names_abbreviated,
+        &locale.month_names_narrow,
+        &name_style,
+    )
+}
+
+pub fn get_weekday_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name_style = get_name_style(activation, args)?;
+    get_name_context(activation, args)?;
+    let locale = actual_locale(activation, this)?;
+
+    set_last_operation_status(activation, this, NO_ERROR)?;
+    names_vector(
+        activation,
+        &locale.weekday_names,
+        &locale.weekday_names_abbreviated,
+        &locale.weekday_names_narrow,
+        &name_style,
+    )
+}
+
+pub fn get_first_weekday<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let locale = actual_locale(activation, this)?;
+
+    set_last_operation_status(activation, this, NO_ERROR)?;
+    Ok(locale.first_weekday.into())
+}
+
+pub fn get_available_locale_id_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    available_locale_id_names(activation)
+}
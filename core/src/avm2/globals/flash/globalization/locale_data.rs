@@ -0,0 +1,1045 @@
+//! Locale data used by the `flash.globalization` formatters.
+//!
+//! The values in `LOCALES` are taken from the Unicode CLDR (version 45),
+//! converted to the format conventions of the Flash Player API. For example,
+//! currency formats are indices into the list of formats documented on
+//! `CurrencyFormatter`, Flash's long date style is CLDR's full style, and
+//! years are always spelled out with four digits. The first locale is the
+//! default.
+
+/// Everything the formatters need to know about a single locale.
+pub struct LocaleData {
+    /// The locale ID, as reported by `actualLocaleIDName`.
+    pub name: &'static str,
+
+    pub decimal_separator: &'static str,
+    pub grouping_separator: &'static str,
+
+    /// The sizes of digit groups, from the decimal separator outwards, as used
+    /// by `NumberFormatter.groupingPattern`.
+    pub grouping_pattern: &'static str,
+
+    /// An index into the list of formats documented on
+    /// `NumberFormatter.negativeNumberFormat`.
+    pub negative_number_format: u32,
+
+    pub currency_iso_code: &'static str,
+    pub currency_symbol: &'static str,
+    pub currency_fractional_digits: i32,
+
+    /// An index into the list of formats documented on
+    /// `CurrencyFormatter.positiveCurrencyFormat`.
+    pub positive_currency_format: u32,
+
+    /// An index into the list of formats documented on
+    /// `CurrencyFormatter.negativeCurrencyFormat`.
+    pub negative_currency_format: u32,
+
+    /// Month names on their own, as in a calendar header.
+    pub month_names: [&'static str; 12],
+
+    /// Month names as used in a date, if they are different from
+    /// `month_names`.
+    pub month_names_format: Option<[&'static str; 12]>,
+
+    pub month_names_abbreviated: [&'static str; 12],
+
+    /// Month names as used by `DateTimeNameStyle.SHORT_ABBREVIATION`, which
+    /// are usually a single letter.
+    pub month_names_narrow: [&'static str; 12],
+
+    /// Weekday names, starting with Sunday.
+    pub weekday_names: [&'static str; 7],
+
+    pub weekday_names_abbreviated: [&'static str; 7],
+
+    pub weekday_names_narrow: [&'static str; 7],
+
+    pub am_pm: [&'static str; 2],
+
+    /// The first day of the week, where Sunday is 0.
+    pub first_weekday: i32,
+
+    /// Date patterns for the long, medium and short date styles.
+    pub date_patterns: [&'static str; 3],
+
+    /// Time patterns for the long, medium and short time styles.
+    pub time_patterns: [&'static str; 3],
+}
+
+pub fn default_locale() -> &'static LocaleData {
+    &LOCALES[0]
+}
+
+/// Find the locale with the given ID, ignoring case.
+pub fn find(name: &str) -> Option<&'static LocaleData> {
+    LOCALES
+        .iter()
+        .find(|locale| locale.name.eq_ignore_ascii_case(name))
+}
+
+/// Find the first locale for the given language, ignoring case.
+pub fn find_language(language: &str) -> Option<&'static LocaleData> {
+    LOCALES.iter().find(|locale| {
+        locale
+            .name
+            .split('-')
+            .next()
+            .is_some_and(|l| l.eq_ignore_ascii_case(language))
+    })
+}
+
+/// All supported locales, as listed by `getAvailableLocaleIDNames`. The currency of each locale and its number of fractional
+/// digits are from the CLDR's supplemental currency data.
+pub static LOCALES: [LocaleData; 19] = [
+    LocaleData {
+        name: "en-US",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "USD",
+        currency_symbol: "$",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        month_names: [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ],
+        month_names_format: None,
+        month_names_abbreviated: [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ],
+        month_names_narrow: ["J", "F", "M", "A", "M", "J", "J", "A", "S", "O", "N", "D"],
+        weekday_names: [
+            "Sunday",
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+        ],
+        weekday_names_abbreviated: ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"],
+        weekday_names_narrow: ["S", "M", "T", "W", "T", "F", "S"],
+        am_pm: ["AM", "PM"],
+        first_weekday: 0,
+        date_patterns: ["EEEE, MMMM d, yyyy", "MMM d, yyyy", "M/d/yy"],
+        time_patterns: ["h:mm:ss\u{202f}a", "h:mm:ss\u{202f}a", "h:mm\u{202f}a"],
+    },
+    LocaleData {
+        name: "en-GB",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "GBP",
+        currency_symbol: "£",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        month_names: [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ],
+        month_names_format: None,
+        month_names_abbreviated: [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sept", "Oct", "Nov", "Dec",
+        ],
+        month_names_narrow: ["J", "F", "M", "A", "M", "J", "J", "A", "S", "O", "N", "D"],
+        weekday_names: [
+            "Sunday",
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+        ],
+        weekday_names_abbreviated: ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"],
+        weekday_names_narrow: ["S", "M", "T", "W", "T", "F", "S"],
+        am_pm: ["am", "pm"],
+        first_weekday: 1,
+        date_patterns: ["EEEE, d MMMM yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "fr-FR",
+        decimal_separator: ",",
+        grouping_separator: "\u{202f}",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ],
+        month_names_format: None,
+        month_names_abbreviated: [
+            "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+            "nov.", "déc.",
+        ],
+        month_names_narrow: ["J", "F", "M", "A", "M", "J", "J", "A", "S", "O", "N", "D"],
+        weekday_names: [
+            "dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi",
+        ],
+        weekday_names_abbreviated: ["dim.", "lun.", "mar.", "mer.", "jeu.", "ven.", "sam."],
+        weekday_names_narrow: ["D", "L", "M", "M", "J", "V", "S"],
+        am_pm: ["AM", "PM"],
+        first_weekday: 1,
+        date_patterns: ["EEEE d MMMM yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "de-DE",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ],
+        month_names_format: None,
+        month_names_abbreviated: [
+            "Jan.", "Feb.", "März", "Apr.", "Mai", "Juni", "Juli", "Aug.", "Sept.", "Okt.", "Nov.",
+            "Dez.",
+        ],
+        month_names_narrow: ["J", "F", "M", "A", "M", "J", "J", "A", "S", "O", "N", "D"],
+        weekday_names: [
+            "Sonntag",
+            "Montag",
+            "Dienstag",
+            "Mittwoch",
+            "Donnerstag",
+            "Freitag",
+            "Samstag",
+        ],
+        weekday_names_abbreviated: ["So.", "Mo.", "Di.", "Mi.", "Do.", "Fr.", "Sa."],
+        weekday_names_narrow: ["S", "M", "D", "M", "D", "F", "S"],
+        am_pm: ["AM", "PM"],
+        first_weekday: 1,
+        date_patterns: ["EEEE, d. MMMM yyyy", "dd.MM.yyyy", "dd.MM.yy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "es-ES",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "enero",
+            "febrero",
+            "marzo",
+            "abril",
+            "mayo",
+            "junio",
+            "julio",
+            "agosto",
+            "septiembre",
+            "octubre",
+            "noviembre",
+            "diciembre",
+        ],
+        month_names_format: None,
+        month_names_abbreviated: [
+            "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+        ],
+        month_names_narrow: ["E", "F", "M", "A", "M", "J", "J", "A", "S", "O", "N", "D"],
+        weekday_names: [
+            "domingo",
+            "lunes",
+            "martes",
+            "miércoles",
+            "jueves",
+            "viernes",
+            "sábado",
+        ],
+        weekday_names_abbreviated: ["dom", "lun", "mar", "mié", "jue", "vie", "sáb"],
+        weekday_names_narrow: ["D", "L", "M", "X", "J", "V", "S"],
+        am_pm: ["a.\u{a0}m.", "p.\u{a0}m."],
+        first_weekday: 1,
+        date_patterns: ["EEEE, d 'de' MMMM 'de' yyyy", "d MMM yyyy", "d/M/yy"],
+        time_patterns: ["H:mm:ss", "H:mm:ss", "H:mm"],
+    },
+    LocaleData {
+        name: "it-IT",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "gennaio",
+            "febbraio",
+            "marzo",
+            "aprile",
+            "maggio",
+            "giugno",
+            "luglio",
+            "agosto",
+            "settembre",
+            "ottobre",
+            "novembre",
+            "dicembre",
+        ],
+        month_names_format: None,
+        month_names_abbreviated: [
+            "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+        ],
+        month_names_narrow: ["G", "F", "M", "A", "M", "G", "L", "A", "S", "O", "N", "D"],
+        weekday_names: [
+            "domenica",
+            "lunedì",
+            "martedì",
+            "mercoledì",
+            "giovedì",
+            "venerdì",
+            "sabato",
+        ],
+        weekday_names_abbreviated: ["dom", "lun", "mar", "mer", "gio", "ven", "sab"],
+        weekday_names_narrow: ["D", "L", "M", "M", "G", "V", "S"],
+        am_pm: ["AM", "PM"],
+        first_weekday: 1,
+        date_patterns: ["EEEE d MMMM yyyy", "d MMM yyyy", "dd/MM/yy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "pt-BR",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "BRL",
+        currency_symbol: "R$",
+        currency_fractional_digits: 2,
+        positive_currency_format: 2,
+        negative_currency_format: 9,
+        month_names: [
+            "janeiro",
+            "fevereiro",
+            "março",
+            "abril",
+            "maio",
+            "junho",
+            "julho",
+            "agosto",
+            "setembro",
+            "outubro",
+            "novembro",
+            "dezembro",
+        ],
+        month_names_format: None,
+        month_names_abbreviated: [
+            "jan.", "fev.", "mar.", "abr.", "mai.", "jun.", "jul.", "ago.", "set.", "out.", "nov.",
+            "dez.",
+        ],
+        month_names_narrow: ["J", "F", "M", "A", "M", "J", "J", "A", "S", "O", "N", "D"],
+        weekday_names: [
+            "domingo",
+            "segunda-feira",
+            "terça-feira",
+            "quarta-feira",
+            "quinta-feira",
+            "sexta-feira",
+            "sábado",
+        ],
+        weekday_names_abbreviated: ["dom.", "seg.", "ter.", "qua.", "qui.", "sex.", "sáb."],
+        weekday_names_narrow: ["D", "S", "T", "Q", "Q", "S", "S"],
+        am_pm: ["AM", "PM"],
+        first_weekday: 0,
+        date_patterns: [
+            "EEEE, d 'de' MMMM 'de' yyyy",
+            "d 'de' MMM 'de' yyyy",
+            "dd/MM/yyyy",
+        ],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "nl-NL",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 2,
+        negative_currency_format: 9,
+        month_names: [
+            "januari",
+            "februari",
+            "maart",
+            "april",
+            "mei",
+            "juni",
+            "juli",
+            "augustus",
+            "september",
+            "oktober",
+            "november",
+            "december",
+        ],
+        month_names_format: None,
+        month_names_abbreviated: [
+            "jan", "feb", "mrt", "apr", "mei", "jun", "jul", "aug", "sep", "okt", "nov", "dec",
+        ],
+        month_names_narrow: ["J", "F", "M", "A", "M", "J", "J", "A", "S", "O", "N", "D"],
+        weekday_names: [
+            "zondag",
+            "maandag",
+            "dinsdag",
+            "woensdag",
+            "donderdag",
+            "vrijdag",
+            "zaterdag",
+        ],
+        weekday_names_abbreviated: ["zo", "ma", "di", "wo", "do", "vr", "za"],
+        weekday_names_narrow: ["Z", "M", "D", "W", "D", "V", "Z"],
+        am_pm: ["a.m.", "p.m."],
+        first_weekday: 1,
+        date_patterns: ["EEEE d MMMM yyyy", "d MMM yyyy", "dd-MM-yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "ru-RU",
+        decimal_separator: ",",
+        grouping_separator: "\u{a0}",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "RUB",
+        currency_symbol: "₽",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "январь",
+            "февраль",
+            "март",
+            "апрель",
+            "май",
+            "июнь",
+            "июль",
+            "август",
+            "сентябрь",
+            "октябрь",
+            "ноябрь",
+            "декабрь",
+        ],
+        month_names_format: Some([
+            "января",
+            "февраля",
+            "марта",
+            "апреля",
+            "мая",
+            "июня",
+            "июля",
+            "августа",
+            "сентября",
+            "октября",
+            "ноября",
+            "декабря",
+        ]),
+        month_names_abbreviated: [
+            "янв.",
+            "февр.",
+            "мар.",
+            "апр.",
+            "мая",
+            "июн.",
+            "июл.",
+            "авг.",
+            "сент.",
+            "окт.",
+            "нояб.",
+            "дек.",
+        ],
+        month_names_narrow: ["Я", "Ф", "М", "А", "М", "И", "И", "А", "С", "О", "Н", "Д"],
+        weekday_names: [
+            "воскресенье",
+            "понедельник",
+            "вторник",
+            "среда",
+            "четверг",
+            "пятница",
+            "суббота",
+        ],
+        weekday_names_abbreviated: ["вс", "пн", "вт", "ср", "чт", "пт", "сб"],
+        weekday_names_narrow: ["В", "П", "В", "С", "Ч", "П", "С"],
+        am_pm: ["AM", "PM"],
+        first_weekday: 1,
+        date_patterns: [
+            "EEEE, d MMMM yyyy\u{202f}г.",
+            "d MMM yyyy\u{202f}г.",
+            "dd.MM.yyyy",
+        ],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "pl-PL",
+        decimal_separator: ",",
+        grouping_separator: "\u{a0}",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "PLN",
+        currency_symbol: "zł",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "styczeń",
+            "luty",
+            "marzec",
+            "kwiecień",
+            "maj",
+            "czerwiec",
+            "lipiec",
+            "sierpień",
+            "wrzesień",
+            "październik",
+            "listopad",
+            "grudzień",
+        ],
+        month_names_format: Some([
+            "stycznia",
+            "lutego",
+            "marca",
+            "kwietnia",
+            "maja",
+            "czerwca",
+            "lipca",
+            "sierpnia",
+            "września",
+            "października",
+            "listopada",
+            "grudnia",
+        ]),
+        month_names_abbreviated: [
+            "sty", "lut", "mar", "kwi", "maj", "cze", "lip", "sie", "wrz", "paź", "lis", "gru",
+        ],
+        month_names_narrow: ["s", "l", "m", "k", "m", "c", "l", "s", "w", "p", "l", "g"],
+        weekday_names: [
+            "niedziela",
+            "poniedziałek",
+            "wtorek",
+            "środa",
+            "czwartek",
+            "piątek",
+            "sobota",
+        ],
+        weekday_names_abbreviated: ["niedz.", "pon.", "wt.", "śr.", "czw.", "pt.", "sob."],
+        weekday_names_narrow: ["n", "p", "w", "ś", "c", "p", "s"],
+        am_pm: ["AM", "PM"],
+        first_weekday: 1,
+        date_patterns: ["EEEE, d MMMM yyyy", "d MMM yyyy", "d.MM.yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "sv-SE",
+        decimal_separator: ",",
+        grouping_separator: "\u{a0}",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "SEK",
+        currency_symbol: "kr",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "januari",
+            "februari",
+            "mars",
+            "april",
+            "maj",
+            "juni",
+            "juli",
+            "augusti",
+            "september",
+            "oktober",
+            "november",
+            "december",
+        ],
+        month_names_format: None,
+        month_names_abbreviated: [
+            "jan.", "feb.", "mars", "apr.", "maj", "juni", "juli", "aug.", "sep.", "okt.", "nov.",
+            "dec.",
+        ],
+        month_names_narrow: ["J", "F", "M", "A", "M", "J", "J", "A", "S", "O", "N", "D"],
+        weekday_names: [
+            "söndag", "måndag", "tisdag", "onsdag", "torsdag", "fredag", "lördag",
+        ],
+        weekday_names_abbreviated: ["sön", "mån", "tis", "ons", "tors", "fre", "lör"],
+        weekday_names_narrow: ["S", "M", "T", "O", "T", "F", "L"],
+        am_pm: ["fm", "em"],
+        first_weekday: 1,
+        date_patterns: ["EEEE d MMMM yyyy", "d MMM yyyy", "yyyy-MM-dd"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "tr-TR",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "TRY",
+        currency_symbol: "₺",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        month_names: [
+            "Ocak", "Şubat", "Mart", "Nisan", "Mayıs", "Haziran", "Temmuz", "Ağustos", "Eylül",
+            "Ekim", "Kasım", "Aralık",
+        ],
+        month_names_format: None,
+        month_names_abbreviated: [
+            "Oca", "Şub", "Mar", "Nis", "May", "Haz", "Tem", "Ağu", "Eyl", "Eki", "Kas", "Ara",
+        ],
+        month_names_narrow: ["O", "Ş", "M", "N", "M", "H", "T", "A", "E", "E", "K", "A"],
+        weekday_names: [
+            "Pazar",
+            "Pazartesi",
+            "Salı",
+            "Çarşamba",
+            "Perşembe",
+            "Cuma",
+            "Cumartesi",
+        ],
+        weekday_names_abbreviated: ["Paz", "Pzt", "Sal", "Çar", "Per", "Cum", "Cmt"],
+        weekday_names_narrow: ["P", "P", "S", "Ç", "P", "C", "C"],
+        am_pm: ["ÖÖ", "ÖS"],
+        first_weekday: 1,
+        date_patterns: ["d MMMM yyyy EEEE", "d MMM yyyy", "d.MM.yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "ja-JP",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "JPY",
+        currency_symbol: "￥",
+        currency_fractional_digits: 0,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        month_names: [
+            "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+        ],
+        month_names_format: None,
+        month_names_abbreviated: [
+            "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+        ],
+        month_names_narrow: [
+            "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12",
+        ],
+        weekday_names: [
+            "日曜日",
+            "月曜日",
+            "火曜日",
+            "水曜日",
+            "木曜日",
+            "金曜日",
+            "土曜日",
+        ],
+        weekday_names_abbreviated: ["日", "月", "火", "水", "木", "金", "土"],
+        weekday_names_narrow: ["日", "月", "火", "水", "木", "金", "土"],
+        am_pm: ["午前", "午後"],
+        first_weekday: 0,
+        date_patterns: ["yyyy年M月d日EEEE", "yyyy/MM/dd", "yyyy/MM/dd"],
+        time_patterns: ["H:mm:ss", "H:mm:ss", "H:mm"],
+    },
+    LocaleData {
+        name: "zh-CN",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "CNY",
+        currency_symbol: "¥",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        month_names: [
+            "一月",
+            "二月",
+            "三月",
+            "四月",
+            "五月",
+            "六月",
+            "七月",
+            "八月",
+            "九月",
+            "十月",
+            "十一月",
+            "十二月",
+        ],
+        month_names_format: None,
+        month_names_abbreviated: [
+            "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+        ],
+        month_names_narrow: [
+            "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12",
+        ],
+        weekday_names: [
+            "星期日",
+            "星期一",
+            "星期二",
+            "星期三",
+            "星期四",
+            "星期五",
+            "星期六",
+        ],
+        weekday_names_abbreviated: ["周日", "周一", "周二", "周三", "周四", "周五", "周六"],
+        weekday_names_narrow: ["日", "一", "二", "三", "四", "五", "六"],
+        am_pm: ["上午", "下午"],
+        first_weekday: 1,
+        date_patterns: ["yyyy年M月d日EEEE", "yyyy年M月d日", "yyyy/M/d"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "zh-TW",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "TWD",
+        currency_symbol: "$",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        month_names: [
+            "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+        ],
+        month_names_format: None,
+        month_names_abbreviated: [
+            "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+        ],
+        month_names_narrow: [
+            "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12",
+        ],
+        weekday_names: [
+            "星期日",
+            "星期一",
+            "星期二",
+            "星期三",
+            "星期四",
+            "星期五",
+            "星期六",
+        ],
+        weekday_names_abbreviated: ["週日", "週一", "週二", "週三", "週四", "週五", "週六"],
+        weekday_names_narrow: ["日", "一", "二", "三", "四", "五", "六"],
+        am_pm: ["上午", "下午"],
+        first_weekday: 0,
+        date_patterns: ["yyyy年M月d日 EEEE", "yyyy年M月d日", "yyyy/M/d"],
+        time_patterns: ["ah:mm:ss", "ah:mm:ss", "ah:mm"],
+    },
+    LocaleData {
+        name: "ko-KR",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "KRW",
+        currency_symbol: "₩",
+        currency_fractional_digits: 0,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        month_names: [
+            "1월", "2월", "3월", "4월", "5월", "6월", "7월", "8월", "9월", "10월", "11월", "12월",
+        ],
+        month_names_format: None,
+        month_names_abbreviated: [
+            "1월", "2월", "3월", "4월", "5월", "6월", "7월", "8월", "9월", "10월", "11월", "12월",
+        ],
+        month_names_narrow: [
+            "1월", "2월", "3월", "4월", "5월", "6월", "7월", "8월", "9월", "10월", "11월", "12월",
+        ],
+        weekday_names: [
+            "일요일",
+            "월요일",
+            "화요일",
+            "수요일",
+            "목요일",
+            "금요일",
+            "토요일",
+        ],
+        weekday_names_abbreviated: ["일", "월", "화", "수", "목", "금", "토"],
+        weekday_names_narrow: ["일", "월", "화", "수", "목", "금", "토"],
+        am_pm: ["오전", "오후"],
+        first_weekday: 0,
+        date_patterns: ["yyyy년 MMMM d일 EEEE", "yyyy. M. d.", "yy. M. d."],
+        time_patterns: ["a h:mm:ss", "a h:mm:ss", "a h:mm"],
+    },
+    LocaleData {
+        name: "he-IL",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "ILS",
+        currency_symbol: "₪",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "ינואר",
+            "פברואר",
+            "מרץ",
+            "אפריל",
+            "מאי",
+            "יוני",
+            "יולי",
+            "אוגוסט",
+            "ספטמבר",
+            "אוקטובר",
+            "נובמבר",
+            "דצמבר",
+        ],
+        month_names_format: None,
+        month_names_abbreviated: [
+            "ינו׳", "פבר׳", "מרץ", "אפר׳", "מאי", "יוני", "יולי", "אוג׳", "ספט׳", "אוק׳", "נוב׳",
+            "דצמ׳",
+        ],
+        month_names_narrow: [
+            "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12",
+        ],
+        weekday_names: [
+            "יום ראשון",
+            "יום שני",
+            "יום שלישי",
+            "יום רביעי",
+            "יום חמישי",
+            "יום שישי",
+            "יום שבת",
+        ],
+        weekday_names_abbreviated: [
+            "יום א׳",
+            "יום ב׳",
+            "יום ג׳",
+            "יום ד׳",
+            "יום ה׳",
+            "יום ו׳",
+            "שבת",
+        ],
+        weekday_names_narrow: ["א׳", "ב׳", "ג׳", "ד׳", "ה׳", "ו׳", "ש׳"],
+        am_pm: ["AM", "PM"],
+        first_weekday: 0,
+        date_patterns: ["EEEE, d בMMMM yyyy", "d בMMM yyyy", "d.M.yyyy"],
+        time_patterns: ["H:mm:ss", "H:mm:ss", "H:mm"],
+    },
+    LocaleData {
+        name: "hi-IN",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;2;*",
+        negative_number_format: 1,
+        currency_iso_code: "INR",
+        currency_symbol: "₹",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        month_names: [
+            "जनवरी",
+            "फ\u{93c}रवरी",
+            "मार\u{94d}च",
+            "अप\u{94d}र\u{948}ल",
+            "मई",
+            "ज\u{942}न",
+            "ज\u{941}लाई",
+            "अगस\u{94d}त",
+            "सित\u{902}बर",
+            "अक\u{94d}त\u{942}बर",
+            "नव\u{902}बर",
+            "दिस\u{902}बर",
+        ],
+        month_names_format: None,
+        month_names_abbreviated: [
+            "जन॰",
+            "फ\u{93c}र॰",
+            "मार\u{94d}च",
+            "अप\u{94d}र\u{948}ल",
+            "मई",
+            "ज\u{942}न",
+            "ज\u{941}ल॰",
+            "अग॰",
+            "सित॰",
+            "अक\u{94d}त\u{942}॰",
+            "नव॰",
+            "दिस॰",
+        ],
+        month_names_narrow: [
+            "ज",
+            "फ\u{93c}",
+            "मा",
+            "अ",
+            "म",
+            "ज\u{942}",
+            "ज\u{941}",
+            "अ",
+            "सि",
+            "अ",
+            "न",
+            "दि",
+        ],
+        weekday_names: [
+            "रविवार",
+            "सोमवार",
+            "म\u{902}गलवार",
+            "ब\u{941}धवार",
+            "ग\u{941}र\u{941}वार",
+            "श\u{941}क\u{94d}रवार",
+            "शनिवार",
+        ],
+        weekday_names_abbreviated: [
+            "रवि",
+            "सोम",
+            "म\u{902}गल",
+            "ब\u{941}ध",
+            "ग\u{941}र\u{941}",
+            "श\u{941}क\u{94d}र",
+            "शनि",
+        ],
+        weekday_names_narrow: [
+            "र",
+            "सो",
+            "म\u{902}",
+            "ब\u{941}",
+            "ग\u{941}",
+            "श\u{941}",
+            "श",
+        ],
+        am_pm: ["am", "pm"],
+        first_weekday: 0,
+        date_patterns: ["EEEE, d MMMM yyyy", "d MMM yyyy", "d/M/yy"],
+        time_patterns: ["h:mm:ss a", "h:mm:ss a", "h:mm a"],
+    },
+    LocaleData {
+        name: "ar-EG",
+        decimal_separator: "٫",
+        grouping_separator: "٬",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "EGP",
+        currency_symbol: "ج.م.\u{200f}",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "يناير",
+            "فبراير",
+            "مارس",
+            "أبريل",
+            "مايو",
+            "يونيو",
+            "يوليو",
+            "أغسطس",
+            "سبتمبر",
+            "أكتوبر",
+            "نوفمبر",
+            "ديسمبر",
+        ],
+        month_names_format: None,
+        month_names_abbreviated: [
+            "يناير",
+            "فبراير",
+            "مارس",
+            "أبريل",
+            "مايو",
+            "يونيو",
+            "يوليو",
+            "أغسطس",
+            "سبتمبر",
+            "أكتوبر",
+            "نوفمبر",
+            "ديسمبر",
+        ],
+        month_names_narrow: ["ي", "ف", "م", "أ", "و", "ن", "ل", "غ", "س", "ك", "ب", "د"],
+        weekday_names: [
+            "الأحد",
+            "الاثنين",
+            "الثلاثاء",
+            "الأربعاء",
+            "الخميس",
+            "الجمعة",
+            "السبت",
+        ],
+        weekday_names_abbreviated: [
+            "الأحد",
+            "الاثنين",
+            "الثلاثاء",
+            "الأربعاء",
+            "الخميس",
+            "الجمعة",
+            "السبت",
+        ],
+        weekday_names_narrow: ["ح", "ن", "ث", "ر", "خ", "ج", "س"],
+        am_pm: ["ص", "م"],
+        first_weekday: 6,
+        date_patterns: [
+            "EEEE، d MMMM yyyy",
+            "dd\u{200f}/MM\u{200f}/yyyy",
+            "d\u{200f}/M\u{200f}/yyyy",
+        ],
+        time_patterns: ["h:mm:ss a", "h:mm:ss a", "h:mm a"],
+    },
+];
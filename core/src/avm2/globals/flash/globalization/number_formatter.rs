@@ -0,0 +1,148 @@
+//! `flash.globalization.NumberFormatter` native methods
+
+use crate::avm2::globals::flash::globalization::{
+    available_locale_id_names, resolve_locale, set_internal, set_last_operation_status,
+    NumberFormat, NO_ERROR, PARSE_ERROR,
+};
+use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::string::AvmString;
+
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string(activation, 0)?;
+    let (locale, status) = resolve_locale(activation, &requested);
+
+    this.set_public_property(
+        "decimalSeparator",
+        locale.decimal_separator.into(),
+        activation,
+    )?;
+    this.set_public_property(
+        "groupingSeparator",
+        locale.grouping_separator.into(),
+        activation,
+    )?;
+    this.set_public_property(
+        "groupingPattern",
+        locale.grouping_pattern.into(),
+        activation,
+    )?;
+    this.set_public_property(
+        "negativeNumberFormat",
+        locale.negative_number_format.into(),
+        activation,
+    )?;
+
+    set_internal(activation, this, "_actualLocaleIDName", locale.name)?;
+    set_last_operation_status(activation, this, status)?;
+
+    Ok(Value::Undefined)
+}
+
+fn format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    value: f64,
+    fractional_digits: Option<usize>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let format = NumberFormat::read(activation, this)?;
+    let negative_format = this
+        .get_public_property("negativeNumberFormat", activation)?
+        .coerce_to_u32(activation)?;
+
+    let fractional_digits = fractional_digits.unwrap_or(format.fractional_digits);
+    let output = format.format_number(value, fractional_digits, negative_format);
+
+    set_last_operation_status(activation, this, NO_ERROR)?;
+    Ok(AvmString::new(activation.gc(), output).into())
+}
+
+pub fn format_int<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get_i32(activation, 0)?;
+    format(activation, this, value.into(), Some(0))
+}
+
+pub fn format_uint<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get_u32(activation, 0)?;
+    format(activation, this, value.into(), Some(0))
+}
+
+pub fn format_number<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get_f64(activation, 0)?;
+    format(activation, this, value, None)
+}
+
+pub fn parse<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let text = args.get_string_non_null(activation, 0, "parseString")?;
+    let format = NumberFormat::read(activation, this)?;
+
+    let result_class = activation.avm2().classes().numberparseresult;
+    let Some(number) = format.parse(&text) else {
+        set_last_operation_status(activation, this, PARSE_ERROR)?;
+        return Ok(result_class.construct(activation, &[])?.into());
+    };
+
+    set_last_operation_status(activation, this, NO_ERROR)?;
+    Ok(result_class
+        .construct(
+            activation,
+            &[number.value.into(), number.start.into(), number.end.into()],
+        )?
+        .into())
+}
+
+pub fn parse_number<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let text = args.get_string_non_null(activation, 0, "parseString")?;
+    let format = NumberFormat::read(activation, this)?;
+
+    // Unlike `parse`, the whole string has to be a number.
+    let value = format.parse(&text).filter(|number| {
+        text[number.end..]
+            .iter()
+            .all(|c| char::from_u32(u32::from(c)).is_some_and(char::is_whitespace))
+    });
+
+    match value {
+        Some(number) => {
+            set_last_operation_status(activation, this, NO_ERROR)?;
+            Ok(number.value.into())
+        }
+        None => {
+            set_last_operation_status(activation, this, PARSE_ERROR)?;
+            Ok(f64::NAN.into())
+        }
+    }
+}
+
+pub fn get_available_locale_id_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    available_locale_id_names(activation)
+}
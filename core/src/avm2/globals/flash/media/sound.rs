@@ -194,23 +194,47 @@ pub fn play<'gc>(
 /// `Sound.extract`
 pub fn extract<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.media.Sound", "extract");
-
-    let bytearray = args.try_get_object(activation, 0);
+    let target = args.get_object(activation, 0, "target")?;
     let length = args.get_f64(activation, 1)?;
+    let start_position = args.get_f64(activation, 2)?;
+
+    let Some(sound_object) = this.as_sound_object() else {
+        return Ok(0.into());
+    };
+    let Some(sound_handle) = sound_object.sound_handle() else {
+        return Ok(0.into());
+    };
 
-    if let Some(bytearray) = bytearray {
-        if let Some(mut bytearray) = bytearray.as_bytearray_mut() {
-            bytearray
-                .write_bytes(vec![0u8; length.ceil() as usize].as_slice())
+    // A negative start position continues from where the last extraction ended.
+    let start_position = if start_position >= 0.0 {
+        start_position as u32
+    } else {
+        sound_object.extract_position()
+    };
+    let length = if length > 0.0 { length as u32 } else { 0 };
+
+    let samples = activation
+        .context
+        .audio
+        .extract_sound_data(sound_handle, start_position, length)
+        .unwrap_or_default();
+
+    if let Some(mut target) = target.as_bytearray_mut() {
+        for [left, right] in &samples {
+            target
+                .write_float(*left)
+                .map_err(|e| e.to_avm(activation))?;
+            target
+                .write_float(*right)
                 .map_err(|e| e.to_avm(activation))?;
         }
     }
+    sound_object.set_extract_position(start_position + samples.len() as u32);
 
-    Ok(Value::Undefined)
+    Ok(samples.len().into())
 }
 
 /// `Sound.close`
@@ -18,16 +18,14 @@ package flash.net {
         public static const DIRECT_CONNECTIONS: String = "directConnections";
 
         public function NetStream(connection:NetConnection, peer:String = CONNECT_TO_FMS) {
-
+            this.init(connection);
         }
 
-        public function appendBytes(bytes:ByteArray) {
-            stub_method("flash.net.NetStream", "appendBytes");
-        }
+        private native function init(connection:NetConnection):void;
 
-        public function appendBytesAction(action:String) {
-            stub_method("flash.net.NetStream", "appendBytesAction");
-        }
+        public native function appendBytes(bytes:ByteArray):void;
+
+        public native function appendBytesAction(action:String):void;
 
         public function attach(connection:NetConnection) {
             stub_method("flash.net.NetStream", "attach");
@@ -17,6 +17,8 @@ package flash.net {
 
         public static native function getLocal(name:String, localPath:String = null, secure:Boolean = false): SharedObject;
 
+        public static native function getRemote(name:String, remotePath:String = null, persistence:Object = false, secure:Boolean = false): SharedObject;
+
         public native function get size() : uint;
         public native function get objectEncoding() : uint;
         public native function set objectEncoding(value:uint) : void;
@@ -25,10 +27,13 @@ package flash.net {
         public native function close() : void;
         public native function clear() : void;
 
+        public native function connect(myConnection:NetConnection, params:String = null):void;
+        public native function send(... arguments):void;
+        public native function setDirty(propertyName:String):void;
+
         public function setProperty(propertyName:String, value:Object = null):void {
             this.data[propertyName] = value;
-            // This should also mark remote SharedObjects as dirty,
-            // but we don't support them yet
+            this.setDirty(propertyName);
         }
 
         public native function get data():Object;
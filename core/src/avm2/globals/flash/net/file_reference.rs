@@ -1,5 +1,6 @@
 use crate::avm2::bytearray::ByteArrayStorage;
 use crate::avm2::error::{argument_error, error, make_error_2037, make_error_2097};
+use crate::avm2::globals::flash::filesystem::file::{filesystem_error, metadata};
 pub use crate::avm2::object::file_reference_allocator;
 use crate::avm2::object::{ByteArrayObject, DateObject, FileReference};
 use crate::avm2::{Activation, Avm2, Error, EventObject, Object, TObject, Value};
@@ -22,6 +23,10 @@ pub fn get_creation_date<'gc>(
                 Value::Null
             }
         }
+        FileReference::Path(ref path) => match metadata(activation, path)?.creation_time {
+            Some(time) => DateObject::from_date_time(activation, time)?.into(),
+            None => Value::Null,
+        },
     };
 
     Ok(creation_date)
@@ -40,6 +45,13 @@ pub fn get_data<'gc>(
             let storage = ByteArrayStorage::from_vec(bytes.to_vec());
             ByteArrayObject::from_storage(activation, storage)?
         }
+        FileReference::Path(ref path) if this.loaded() => {
+            let bytes = match activation.context.filesystem.read(path) {
+                Ok(bytes) => bytes,
+                Err(e) => return Err(filesystem_error(activation, e)),
+            };
+            ByteArrayObject::from_storage(activation, ByteArrayStorage::from_vec(bytes))?
+        }
         // Contrary to other getters `data` will return null instead of throwing.
         _ => return Ok(Value::Null),
     };
@@ -63,6 +75,10 @@ pub fn get_modification_date<'gc>(
                 Value::Null
             }
         }
+        FileReference::Path(ref path) => match metadata(activation, path)?.modification_time {
+            Some(time) => DateObject::from_date_time(activation, time)?.into(),
+            None => Value::Null,
+        },
     };
 
     Ok(modification_date)
@@ -81,6 +97,10 @@ pub fn get_name<'gc>(
             let name = dialog_result.file_name().unwrap_or_default();
             AvmString::new_utf8(activation.context.gc_context, name).into()
         }
+        FileReference::Path(ref path) => {
+            let name = path.name().unwrap_or_default();
+            AvmString::new_utf8(activation.context.gc_context, name).into()
+        }
     };
 
     Ok(name)
@@ -96,6 +116,7 @@ pub fn get_size<'gc>(
     let size = match *this.file_reference() {
         FileReference::None => return Err(make_error_2037(activation)),
         FileReference::FileDialogResult(ref dialog_result) => dialog_result.size().unwrap_or(0),
+        FileReference::Path(ref path) => metadata(activation, path)?.size,
     };
 
     Ok(Value::Number(size as f64))
@@ -114,6 +135,12 @@ pub fn get_type<'gc>(
             let type_ = dialog_result.file_type().unwrap_or_default();
             AvmString::new_utf8(activation.context.gc_context, type_).into()
         }
+        FileReference::Path(ref path) => match path.name().and_then(|name| name.rsplit_once('.')) {
+            Some((_, extension)) => {
+                AvmString::new_utf8(activation.context.gc_context, format!(".{extension}")).into()
+            }
+            None => Value::Null,
+        },
     };
 
     Ok(type_)
@@ -202,6 +229,7 @@ pub fn load<'gc>(
     let size = match *this.file_reference() {
         FileReference::None => return Err(make_error_2037(activation)),
         FileReference::FileDialogResult(ref dialog_result) => dialog_result.size().unwrap_or(0),
+        FileReference::Path(ref path) => metadata(activation, path)?.size,
     };
 
     let open_evt = EventObject::bare_default_event(activation.context, "open");
@@ -37,6 +37,17 @@ pub fn connect<'gc>(
     {
         // HTTP(S) is for Flash Remoting, which is just POST requests to the URL.
         NetConnections::connect_to_flash_remoting(activation.context, connection, url.to_string());
+    } else if url.starts_with(WStr::from_units(b"rtmp")) {
+        let mut arguments = Vec::new();
+        let mut object_table = FnvHashMap::default();
+        for arg in &args[1..] {
+            if let Some(value) =
+                serialize_value(activation, *arg, AMFVersion::AMF0, &mut object_table)
+            {
+                arguments.push(value);
+            }
+        }
+        NetConnections::connect_to_rtmp(activation.context, connection, url.to_string(), arguments);
     } else {
         avm2_stub_method!(
             activation,
@@ -1,9 +1,26 @@
-use crate::avm2::error::{make_error_2004, Error2004Type};
+use crate::avm2::error::{make_error_2004, make_error_2008, Error2004Type};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::{Activation, Error, Object, TObject, Value};
+use crate::streams::AppendBytesAction;
 
 pub use crate::avm2::object::netstream_allocator as net_stream_allocator;
 
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        let connection = args
+            .try_get_object(activation, 0)
+            .and_then(|connection| connection.as_net_connection())
+            .and_then(|connection| connection.handle());
+        ns.set_net_connection(activation.gc(), connection);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_bytes_loaded<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -28,6 +45,48 @@ pub fn get_bytes_total<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn append_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        let bytearray = args.get_object(activation, 0, "bytes")?;
+        let mut data = bytearray
+            .as_bytearray()
+            .expect("Parameter must be a bytearray!")
+            .bytes()
+            .to_vec();
+
+        ns.append_bytes(activation.context, &mut data);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn append_bytes_action<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        let action = args.get_string_non_null(activation, 0, "action")?;
+        let action = if &action == b"endSequence" {
+            AppendBytesAction::EndSequence
+        } else if &action == b"resetBegin" {
+            AppendBytesAction::ResetBegin
+        } else if &action == b"resetSeek" {
+            AppendBytesAction::ResetSeek
+        } else {
+            return Err(make_error_2008(activation, "action"));
+        };
+
+        ns.append_bytes_action(activation.context, action);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn play<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
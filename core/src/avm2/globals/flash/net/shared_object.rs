@@ -1,12 +1,17 @@
 //! `flash.net.SharedObject` builtin/prototype
 
+use crate::avm2::amf::serialize_value;
 use crate::avm2::error::error;
 use crate::avm2::object::TObject;
 pub use crate::avm2::object::{shared_object_allocator, SharedObjectObject};
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::{Activation, Error, Object, Value};
+use crate::net_connection::{NetConnections, SharedObjectEvent};
 use crate::{avm2_stub_getter, avm2_stub_method, avm2_stub_setter};
 use flash_lso::types::{AMFVersion, Lso};
+use fnv::FnvHashMap;
 use std::borrow::Cow;
+use std::rc::Rc;
 
 fn new_lso<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -177,6 +182,127 @@ pub fn get_local<'gc>(
     Ok(created_shared_object.into())
 }
 
+pub fn get_remote<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string(activation, 0)?.to_string();
+    if !matches!(args.get(2), None | Some(Value::Bool(false))) {
+        avm2_stub_method!(
+            activation,
+            "flash.net.SharedObject",
+            "getRemote",
+            "with persistence"
+        );
+    }
+
+    // The data lives on the server, and only shows up once we connect.
+    let data = activation
+        .avm2()
+        .classes()
+        .object
+        .construct(activation, &[])?;
+
+    Ok(SharedObjectObject::from_data_and_name(activation, data, name).into())
+}
+
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let shared_object = this.as_shared_object().unwrap();
+
+    let connection = args
+        .get_object(activation, 0, "myConnection")?
+        .as_net_connection()
+        .and_then(|connection| connection.handle());
+    let connected = connection.is_some_and(|connection| {
+        NetConnections::connect_shared_object(activation.context, connection, shared_object)
+    });
+
+    if !connected {
+        return Err(Error::AvmError(error(
+            activation,
+            "Error #2139: SharedObject could not connect.",
+            2139,
+        )?));
+    }
+
+    shared_object.set_remote_connection(connection);
+
+    Ok(Value::Undefined)
+}
+
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let shared_object = this.as_shared_object().unwrap();
+    let Some(connection) = shared_object.remote_connection() else {
+        return Ok(Value::Undefined);
+    };
+
+    let mut values = Vec::new();
+    let mut object_table = FnvHashMap::default();
+    for arg in args {
+        if let Some(value) = serialize_value(activation, *arg, AMFVersion::AMF0, &mut object_table)
+        {
+            values.push(Rc::new(value));
+        }
+    }
+
+    NetConnections::send_shared_object_events(
+        activation.context,
+        connection,
+        shared_object.name(),
+        vec![SharedObjectEvent::SendMessage { values }],
+    );
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_dirty<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let shared_object = this.as_shared_object().unwrap();
+    let Some(connection) = shared_object.remote_connection() else {
+        return Ok(Value::Undefined);
+    };
+
+    let name = args.get_string(activation, 0)?;
+    let value = shared_object.data().get_public_property(name, activation)?;
+    let event = match serialize_value(
+        activation,
+        value,
+        AMFVersion::AMF0,
+        &mut FnvHashMap::default(),
+    ) {
+        Some(value) if !matches!(value, flash_lso::types::Value::Undefined) => {
+            SharedObjectEvent::RequestChange {
+                name: name.to_string(),
+                value: Rc::new(value),
+            }
+        }
+        _ => SharedObjectEvent::RequestRemove {
+            name: name.to_string(),
+        },
+    };
+
+    NetConnections::send_shared_object_events(
+        activation.context,
+        connection,
+        shared_object.name(),
+        vec![event],
+    );
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_data<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -238,9 +364,17 @@ pub fn get_size<'gc>(
 
 pub fn close<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    let shared_object = this.as_shared_object().unwrap();
+
+    if let Some(connection) = shared_object.remote_connection() {
+        shared_object.set_remote_connection(None);
+        NetConnections::close_shared_object(activation.context, connection, shared_object.name());
+        return Ok(Value::Undefined);
+    }
+
     avm2_stub_method!(activation, "flash.net.SharedObject", "close");
     Ok(Value::Undefined)
 }
@@ -1,6 +1,52 @@
 package flash.printing {
+    import flash.display.Sprite;
     import flash.events.EventDispatcher;
+    import flash.geom.Rectangle;
 
     public class PrintJob extends EventDispatcher {
+        // The ID of the print job started by this object, or 0.
+        internal var _job:uint = 0;
+        internal var _orientation:String = PrintJobOrientation.PORTRAIT;
+        internal var _pageHeight:int = 0;
+        internal var _pageWidth:int = 0;
+        internal var _paperHeight:int = 0;
+        internal var _paperWidth:int = 0;
+        internal var _maxPixelsPerInch:Number = 0;
+
+        public function PrintJob() {
+        }
+
+        public static native function get isSupported():Boolean;
+
+        public function get orientation():String {
+            return this._orientation;
+        }
+
+        public function get pageHeight():int {
+            return this._pageHeight;
+        }
+
+        public function get pageWidth():int {
+            return this._pageWidth;
+        }
+
+        public function get paperHeight():int {
+            return this._paperHeight;
+        }
+
+        public function get paperWidth():int {
+            return this._paperWidth;
+        }
+
+        [API("667")]
+        public function get maxPixelsPerInch():Number {
+            return this._maxPixelsPerInch;
+        }
+
+        public native function start():Boolean;
+
+        public native function addPage(sprite:Sprite, printArea:Rectangle = null, options:PrintJobOptions = null, frameNum:int = 0):void;
+
+        public native function send():void;
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,132 @@
+//! `flash.printing.PrintJob` native methods
+
+use crate::avm2::error::error;
+use crate::avm2::globals::flash::display::display_object::object_to_rectangle;
+use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Multiname, Object, Value};
+use crate::backend::print::PrintOrientation;
+use crate::print_job::{PageScaling, PrintJob, PrintJobId};
+
+fn get_internal<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = Multiname::new(activation.avm2().namespaces.flash_printing_internal, name);
+    this.get_property(&name, activation)
+}
+
+fn set_internal<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    name: &'static str,
+    value: impl Into<Value<'gc>>,
+) -> Result<(), Error<'gc>> {
+    let name = Multiname::new(activation.avm2().namespaces.flash_printing_internal, name);
+    this.set_property(&name, value.into(), activation)
+}
+
+fn get_job<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<Option<PrintJobId>, Error<'gc>> {
+    let id = get_internal(activation, this, "_job")?.coerce_to_u32(activation)?;
+    Ok(PrintJobId::from_raw(id))
+}
+
+pub fn get_is_supported<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.print.is_supported().into())
+}
+
+pub fn start<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if get_job(activation, this)?.is_some() {
+        return Ok(false.into());
+    }
+
+    let Some((id, settings)) = PrintJob::start(activation.context) else {
+        return Ok(false.into());
+    };
+
+    let orientation = match settings.orientation {
+        PrintOrientation::Portrait => "portrait",
+        PrintOrientation::Landscape => "landscape",
+    };
+    set_internal(activation, this, "_job", id.get())?;
+    set_internal(activation, this, "_orientation", orientation)?;
+    set_internal(activation, this, "_pageWidth", settings.page_width)?;
+    set_internal(activation, this, "_pageHeight", settings.page_height)?;
+    set_internal(activation, this, "_paperWidth", settings.paper_width)?;
+    set_internal(activation, this, "_paperHeight", settings.paper_height)?;
+    set_internal(activation, this, "_maxPixelsPerInch", settings.dpi)?;
+
+    Ok(true.into())
+}
+
+pub fn add_page<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let sprite = args.get_object(activation, 0, "sprite")?;
+    let print_area = match args.try_get_object(activation, 1) {
+        Some(print_area) => Some(object_to_rectangle(activation, print_area)?),
+        None => None,
+    };
+    let print_as_bitmap = match args.try_get_object(activation, 2) {
+        Some(options) => options
+            .get_public_property("printAsBitmap", activation)?
+            .coerce_to_boolean(),
+        None => false,
+    };
+    // Frames are numbered from 1, and 0 prints the current frame.
+    let frame = u16::try_from(args.get_i32(activation, 3)?)
+        .ok()
+        .filter(|frame| *frame > 0);
+
+    let job = get_job(activation, this)?;
+    let added = job
+        .zip(sprite.as_display_object())
+        .is_some_and(|(job, sprite)| {
+            PrintJob::add_page(
+                activation.context,
+                job,
+                sprite,
+                print_area,
+                frame,
+                PageScaling::None,
+                print_as_bitmap,
+            )
+        });
+
+    if !added {
+        return Err(Error::AvmError(error(
+            activation,
+            "Error #2057: The page could not be added to the print job.",
+            2057,
+        )?));
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(job) = get_job(activation, this)? {
+        PrintJob::send(activation.context, job);
+        set_internal(activation, this, "_job", 0)?;
+    }
+
+    Ok(Value::Undefined)
+}
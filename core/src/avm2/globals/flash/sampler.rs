@@ -0,0 +1,375 @@
+//! `flash.sampler` namespace
+
+use crate::avm2::function::display_function;
+use crate::avm2::object::{ArrayObject, QNameObject, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::sampler::{self, find_method, Accessor, Sample};
+use crate::avm2::CallNode;
+use crate::avm2::{Activation, ArrayStorage, Error, Multiname, Object, Value};
+use crate::string::{AvmString, WStr, WString};
+
+pub mod new_object_sample;
+
+/// Implements `flash.sampler.startSampling`
+pub fn start_sampling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.avm2.sampler.start();
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.stopSampling`
+pub fn stop_sampling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.avm2.sampler.stop();
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.pauseSampling`
+pub fn pause_sampling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.avm2.sampler.pause();
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.clearSamples`
+pub fn clear_samples<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.avm2.sampler.clear_samples();
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.sampleInternalAllocs`
+pub fn sample_internal_allocs<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let everything = args.get_bool(0);
+    activation
+        .context
+        .avm2
+        .sampler
+        .set_sample_internal_allocs(everything);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.setSamplerCallback`
+pub fn set_sampler_callback<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let callback = args.try_get_object(activation, 0);
+    activation.context.avm2.sampler.set_callback(callback);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.getSampleCount`
+pub fn get_sample_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.avm2().sampler.samples().len().into())
+}
+
+/// Implements `flash.sampler.getSamples`
+pub fn get_samples<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Don't sample the samples.
+    let was_sampling = activation.avm2().sampler.is_sampling();
+    activation.context.avm2.sampler.pause();
+
+    let samples = activation.avm2().sampler.samples().to_vec();
+    let result = samples
+        .into_iter()
+        .map(|sample| sample_to_object(activation, sample).map(Value::from))
+        .collect::<Result<Vec<_>, _>>();
+
+    if was_sampling {
+        activation.context.avm2.sampler.start();
+    }
+
+    let samples = result?;
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&samples))?.into())
+}
+
+fn sample_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    sample: Sample<'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    match sample {
+        Sample::NewObject {
+            id,
+            time,
+            stack,
+            class,
+            size,
+        } => {
+            let stack = stack_to_array(activation, &stack)?;
+            let object = activation
+                .avm2()
+                .classes()
+                .newobjectsample
+                .construct(activation, &[])?;
+            init_public_property(activation, object, "id", (id as f64).into())?;
+            init_public_property(activation, object, "time", time.into())?;
+            init_public_property(activation, object, "stack", stack)?;
+            init_public_property(activation, object, "type", class.into())?;
+
+            // The object may be collected before the size is asked for, so
+            // keep it on the sample.
+            let name = Multiname::new(activation.avm2().namespaces.flash_sampler_internal, "_size");
+            object.set_property(&name, size.into(), activation)?;
+            Ok(object)
+        }
+        Sample::DeleteObject { id, time, size } => {
+            let object = activation
+                .avm2()
+                .classes()
+                .deleteobjectsample
+                .construct(activation, &[])?;
+            init_public_property(activation, object, "id", (id as f64).into())?;
+            init_public_property(activation, object, "time", time.into())?;
+            init_public_property(activation, object, "size", size.into())?;
+            Ok(object)
+        }
+    }
+}
+
+/// Build an array of `StackFrame`s, innermost call first.
+fn stack_to_array<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    stack: &[CallNode<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut frames = Vec::with_capacity(stack.len());
+    for call in stack.iter().rev() {
+        let mut name = WString::new();
+        match call {
+            CallNode::GlobalInit(_) => name.push_utf8("global$init"),
+            CallNode::Method { method, class } => display_function(&mut name, method, *class),
+        }
+        // `StackFrame.toString` adds the parentheses itself.
+        let name = name.strip_suffix(WStr::from_units(b"()")).unwrap_or(&name);
+        let name = AvmString::new(activation.gc(), name);
+
+        // We don't keep track of debug line information, so leave the
+        // location empty.
+        let frame = activation
+            .avm2()
+            .classes()
+            .stackframe
+            .construct(activation, &[])?;
+        init_public_property(activation, frame, "name", name.into())?;
+        frames.push(frame.into());
+    }
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&frames))?.into())
+}
+
+fn init_public_property<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    name: &'static str,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let name = Multiname::new(activation.avm2().find_public_namespace(), name);
+    object.init_property(&name, value, activation)
+}
+
+/// Implements `flash.sampler.getSize`
+pub fn get_size<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(sampler::estimate_size(args.get_value(0)).into())
+}
+
+/// Get the method referred to by the `obj` and `name` parameters of the
+/// invocation count functions.
+fn get_method<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+    accessor: Accessor,
+) -> Result<Option<crate::avm2::method::Method<'gc>>, Error<'gc>> {
+    let Some(object) = args.try_get_object(activation, 0) else {
+        return Ok(None);
+    };
+    let name = args
+        .try_get_object(activation, 1)
+        .and_then(|name| name.as_qname_object())
+        .map(|name| name.name().clone());
+
+    find_method(activation, object, name.as_ref(), accessor)
+}
+
+fn get_invocation_count_for<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+    accessor: Accessor,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(match get_method(activation, args, accessor)? {
+        Some(method) => activation.avm2().sampler.invocation_count(method).into(),
+        None => (-1).into(),
+    })
+}
+
+/// Implements `flash.sampler.getInvocationCount`
+pub fn get_invocation_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    get_invocation_count_for(activation, args, Accessor::Method)
+}
+
+/// Implements `flash.sampler.getGetterInvocationCount`
+pub fn get_getter_invocation_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    get_invocation_count_for(activation, args, Accessor::Getter)
+}
+
+/// Implements `flash.sampler.getSetterInvocationCount`
+pub fn get_setter_invocation_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    get_invocation_count_for(activation, args, Accessor::Setter)
+}
+
+/// Implements `flash.sampler.isGetterSetter`
+pub fn is_getter_setter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let is_getter = get_method(activation, args, Accessor::Getter)?.is_some();
+    let is_setter = get_method(activation, args, Accessor::Setter)?.is_some();
+
+    Ok((is_getter || is_setter).into())
+}
+
+/// Implements `flash.sampler.getLexicalScopes`
+pub fn get_lexical_scopes<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(function) = args
+        .try_get_object(activation, 0)
+        .and_then(|function| function.as_function_object())
+    else {
+        return Ok(Value::Null);
+    };
+
+    let scope = function.executable().scope();
+    let mut scopes = Vec::new();
+    let mut index = 0;
+    while let Some(scope) = scope.get(index) {
+        scopes.push(scope.values().into());
+        index += 1;
+    }
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&scopes))?.into())
+}
+
+/// Implements `flash.sampler.getSavedThis`
+pub fn get_saved_this<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let receiver = args
+        .try_get_object(activation, 0)
+        .and_then(|function| function.as_function_object())
+        .and_then(|function| function.executable().bound_receiver());
+
+    Ok(receiver.map_or(Value::Null, Value::from))
+}
+
+/// Implements `flash.sampler.getMasterString`
+pub fn get_master_string<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(string) = args.try_get_string(activation, 0)? else {
+        return Ok(Value::Null);
+    };
+
+    // Substrings and some concatenations share their characters with the
+    // string they were made from.
+    let master = string
+        .as_managed()
+        .and_then(|repr| repr.owner())
+        .map(AvmString::from);
+
+    Ok(master.map_or(Value::Null, Value::from))
+}
+
+/// Implements `flash.sampler.getMemberNames`
+pub fn get_member_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(object) = args.try_get_object(activation, 0) else {
+        return Ok(Value::Null);
+    };
+    let instance_names = args.get_bool(1);
+
+    let vtable = match object.as_class_object() {
+        Some(class) if instance_names => class.instance_vtable(),
+        _ => object.vtable(),
+    };
+
+    let mut names = Vec::new();
+    let traits: Vec<_> = vtable
+        .resolved_traits()
+        .iter()
+        .map(|(name, namespace, _)| Multiname::new(namespace, name))
+        .collect();
+    for name in traits {
+        names.push(QNameObject::from_name(activation, name)?.into());
+    }
+
+    if !instance_names {
+        let public = activation.avm2().find_public_namespace();
+        let mut index = object.get_next_enumerant(0, activation)?;
+        while let Some(i) = index {
+            let name = object
+                .get_enumerant_name(i, activation)?
+                .coerce_to_string(activation)?;
+            names.push(QNameObject::from_name(activation, Multiname::new(public, name))?.into());
+            index = object.get_next_enumerant(i, activation)?;
+        }
+    }
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&names))?.into())
+}
@@ -1,20 +1,16 @@
 package flash.sampler {
-    import __ruffle__.stub_getter;
-
     public final class NewObjectSample extends Sample {
         public const id:Number;
      
         public const type:Class;
 
-        public function get object():* {
-            stub_getter("flash.sampler.NewObjectSample", "object");
-            return {};
-        }
+        public native function get object():*;
+
+        // The estimated size of the object when it was created.
+        internal var _size:Number;
 
         public function get size():Number {
-            stub_getter("flash.sampler.NewObjectSample", "size");
-            return 0;
+            return this._size;
         }
     }
 }
-
@@ -0,0 +1,26 @@
+//! `flash.sampler.NewObjectSample` builtin/prototype
+
+use crate::avm2::object::TObject;
+use crate::avm2::{Activation, Error, Object, Value};
+
+fn sample_id<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<u64, Error<'gc>> {
+    Ok(this
+        .get_public_property("id", activation)?
+        .coerce_to_number(activation)? as u64)
+}
+
+/// Implements `NewObjectSample.object`
+pub fn get_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = sample_id(activation, this)?;
+    let mc = activation.gc();
+    let object = activation.avm2().sampler.object(id, mc);
+
+    Ok(object.map_or(Value::Undefined, Value::from))
+}
@@ -3,8 +3,18 @@ package flash.system {
 
     [API("682")]
     public final class MessageChannel extends EventDispatcher {
+        // The ID of the channel in the worker registry.
+        internal var _id:int;
+
         public function MessageChannel() {
-            super();
+            throw new ArgumentError("Error #2012: MessageChannel$ class cannot be instantiated.", 2012);
         }
+
+        public native function get messageAvailable():Boolean;
+        public native function get state():String;
+
+        public native function close():void;
+        public native function receive(blockUntilReceived:Boolean = false):*;
+        public native function send(arg:*, queueLimit:int = -1):void;
     }
 }
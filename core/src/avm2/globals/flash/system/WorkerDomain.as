@@ -1,10 +1,26 @@
 package flash.system {
+    import flash.utils.ByteArray;
+
     [API("680")] // the docs say 682, that's wrong
     public final class WorkerDomain {
-        public static const isSupported: Boolean = false;
+        public static const isSupported: Boolean = true;
+
+        private static var _current: WorkerDomain;
 
         public function WorkerDomain() {
             throw new ArgumentError("Error #2012: WorkerDomain$ class cannot be instantiated.", 2012)
         }
+
+        public static function get current():WorkerDomain {
+            if (_current == null) {
+                _current = createCurrent();
+            }
+            return _current;
+        }
+
+        private static native function createCurrent():WorkerDomain;
+
+        public native function createWorker(swf:ByteArray, giveAppPrivileges:Boolean = false):Worker;
+        public native function listWorkers():Vector.<Worker>;
     }
-}
\ No newline at end of file
+}
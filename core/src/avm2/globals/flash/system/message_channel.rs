@@ -0,0 +1,100 @@
+//! `flash.system.MessageChannel` native methods
+
+use crate::avm2::globals::flash::system::worker::{from_shared_value, shared_id, to_shared_value};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::string::AvmString;
+use crate::worker::{ChannelStatus, Wait};
+
+fn channel_id<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<usize, Error<'gc>> {
+    let namespace = activation.avm2().namespaces.flash_system_internal;
+    Ok(shared_id(activation, this, namespace)? as usize)
+}
+
+pub fn get_message_available<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = channel_id(activation, this)?;
+    Ok(activation
+        .context
+        .workers
+        .registry()
+        .message_available(id)
+        .into())
+}
+
+pub fn get_state<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = channel_id(activation, this)?;
+    let status = activation.context.workers.registry().channel_status(id);
+    Ok(AvmString::new_utf8(activation.gc(), status.as_str()).into())
+}
+
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = channel_id(activation, this)?;
+    activation.context.workers.registry().close_channel(id);
+    Ok(Value::Undefined)
+}
+
+/// Implements `MessageChannel.receive`.
+///
+/// When blocking, this parks the worker until a message is available, which
+/// stops the current script. The message stays queued until it's received.
+pub fn receive<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = channel_id(activation, this)?;
+    let block_until_received = args.get_bool(0);
+
+    let workers = &activation.context.workers;
+    let mut registry = workers.registry();
+    if block_until_received
+        && !registry.message_available(id)
+        && registry.channel_status(id) != ChannelStatus::Closed
+    {
+        drop(registry);
+        return Err(workers.park(Wait::Receive { channel: id }));
+    }
+
+    let message = registry.receive_message(id);
+    drop(registry);
+    match message {
+        Some(message) => from_shared_value(activation, &message),
+        None => Ok(Value::Null),
+    }
+}
+
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = channel_id(activation, this)?;
+    let queue_limit = args.get_i32(activation, 1)?;
+
+    if activation.context.workers.registry().channel_status(id) != ChannelStatus::Open {
+        return Ok(Value::Undefined);
+    }
+
+    let message = to_shared_value(activation, args.get_value(0))?;
+    activation
+        .context
+        .workers
+        .registry()
+        .send_message(id, message, queue_limit);
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,265 @@
+//! `flash.system.Worker` native methods
+
+use crate::avm2::amf::{deserialize_value, serialize_value, ObjectTable};
+use crate::avm2::object::{ByteArrayObject, ScriptObject, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Multiname, Namespace, Object, Value};
+use crate::string::AvmString;
+use crate::worker::{SharedHandle, SharedValue, WorkerId};
+use flash_lso::types::{AMFVersion, Value as AmfValue};
+
+/// Reads the registry ID of a worker, message channel, mutex or condition.
+pub fn shared_id<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    namespace: Namespace<'gc>,
+) -> Result<u32, Error<'gc>> {
+    let name = Multiname::new(namespace, "_id");
+    this.get_property(&name, activation)?
+        .coerce_to_u32(activation)
+}
+
+fn worker_id<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<WorkerId, Error<'gc>> {
+    let namespace = activation.avm2().namespaces.flash_system_internal;
+    Ok(WorkerId::from_raw(shared_id(activation, this, namespace)?))
+}
+
+/// Gets the object that represents a shared object in the current worker,
+/// creating it if this worker hasn't seen the object before.
+pub fn object_for_handle<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    handle: SharedHandle,
+) -> Result<Object<'gc>, Error<'gc>> {
+    if let Some(object) = activation.context.workers.object(handle, activation.gc()) {
+        return Ok(object);
+    }
+
+    let object = if let SharedHandle::ByteArray(id) = handle {
+        let bytes = activation.context.workers.registry().byte_array(id);
+        let object = ByteArrayObject::from_shared(activation, bytes)?;
+        let name = Multiname::new(
+            activation.avm2().namespaces.flash_utils_internal,
+            "_shareable",
+        );
+        object.set_property(&name, true.into(), activation)?;
+        object
+    } else {
+        let avm2 = activation.avm2();
+        let classes = avm2.classes();
+        let namespaces = &avm2.namespaces;
+        let (class, namespace, id) = match handle {
+            SharedHandle::Worker(id) => {
+                (classes.worker, namespaces.flash_system_internal, id.raw())
+            }
+            SharedHandle::MessageChannel(id) => (
+                classes.messagechannel,
+                namespaces.flash_system_internal,
+                id as u32,
+            ),
+            SharedHandle::Mutex(id) => (
+                classes.mutex,
+                namespaces.flash_concurrent_internal,
+                id as u32,
+            ),
+            SharedHandle::Condition(id) => (
+                classes.condition,
+                namespaces.flash_concurrent_internal,
+                id as u32,
+            ),
+            SharedHandle::ByteArray(_) => unreachable!(),
+        };
+
+        // These objects can't be constructed from ActionScript, or would
+        // create a new shared object if they were, so skip their constructors.
+        let object = ScriptObject::custom_object(
+            activation.gc(),
+            class.inner_class_definition(),
+            Some(class.prototype()),
+            class.instance_vtable(),
+        );
+        if let Some(superclass) = class.superclass_object() {
+            superclass.call_super_init(object.into(), &[], activation)?;
+        }
+        object.set_property(&Multiname::new(namespace, "_id"), id.into(), activation)?;
+        object
+    };
+
+    activation.context.workers.insert_object(handle, object);
+    Ok(object)
+}
+
+/// Finds the shared object that an object represents, if any.
+///
+/// A shareable `ByteArray` becomes a shared object the first time it's passed
+/// to another worker.
+pub fn handle_for_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Option<SharedHandle>, Error<'gc>> {
+    if let Some(handle) = activation.context.workers.handle(object, activation.gc()) {
+        return Ok(Some(handle));
+    }
+
+    let Some(bytearray) = object.as_bytearray_object() else {
+        return Ok(None);
+    };
+    let name = Multiname::new(
+        activation.avm2().namespaces.flash_utils_internal,
+        "_shareable",
+    );
+    if !object.get_property(&name, activation)?.coerce_to_boolean() {
+        return Ok(None);
+    }
+
+    let id = activation
+        .context
+        .workers
+        .registry()
+        .create_byte_array(bytearray.share());
+    let handle = SharedHandle::ByteArray(id);
+    activation.context.workers.insert_object(handle, object);
+    Ok(Some(handle))
+}
+
+/// Prepares a value to be passed to another worker.
+///
+/// Shared objects are passed by reference, and everything else is copied
+/// with AMF3. Shared objects nested inside a copied value are copied too.
+pub fn to_shared_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<SharedValue, Error<'gc>> {
+    if let Value::Object(object) = value {
+        if let Some(handle) = handle_for_object(activation, object)? {
+            return Ok(SharedValue::Shared(handle));
+        }
+    }
+
+    let amf = serialize_value(
+        activation,
+        value,
+        AMFVersion::AMF3,
+        &mut ObjectTable::default(),
+    )
+    .unwrap_or(AmfValue::Undefined);
+    Ok(SharedValue::Copied(amf))
+}
+
+/// Turns a value passed from another worker into a value in this worker.
+pub fn from_shared_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    value: &SharedValue,
+) -> Result<Value<'gc>, Error<'gc>> {
+    match value {
+        SharedValue::Copied(amf) => deserialize_value(activation, amf),
+        SharedValue::Shared(handle) => Ok(object_for_handle(activation, *handle)?.into()),
+    }
+}
+
+pub fn get_current<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let current = activation.context.workers.current();
+    Ok(object_for_handle(activation, SharedHandle::Worker(current))?.into())
+}
+
+pub fn get_is_primordial<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(worker_id(activation, this)?.is_primordial().into())
+}
+
+pub fn get_state<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = worker_id(activation, this)?;
+    let status = activation.context.workers.registry().worker_status(id);
+    Ok(AvmString::new_utf8(activation.gc(), status.as_str()).into())
+}
+
+pub fn create_message_channel<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let receiver = args.get_object(activation, 0, "receiver")?;
+    let sender = worker_id(activation, this)?;
+    let receiver = worker_id(activation, receiver)?;
+
+    let id = activation
+        .context
+        .workers
+        .registry()
+        .create_channel(sender, receiver);
+    Ok(object_for_handle(activation, SharedHandle::MessageChannel(id))?.into())
+}
+
+pub fn get_shared_property<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = worker_id(activation, this)?;
+    let key = args.get_string(activation, 0)?.to_string();
+
+    let value = activation
+        .context
+        .workers
+        .registry()
+        .shared_property(id, &key)
+        .cloned();
+    match value {
+        Some(value) => from_shared_value(activation, &value),
+        None => Ok(Value::Undefined),
+    }
+}
+
+pub fn set_shared_property<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = worker_id(activation, this)?;
+    let key = args.get_string(activation, 0)?.to_utf8_lossy().into_owned();
+    let value = to_shared_value(activation, args.get_value(1))?;
+
+    activation
+        .context
+        .workers
+        .registry()
+        .set_shared_property(id, key, value);
+    Ok(Value::Undefined)
+}
+
+pub fn start<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = worker_id(activation, this)?;
+    activation.context.workers.registry().start_worker(id);
+    Ok(Value::Undefined)
+}
+
+pub fn terminate<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = worker_id(activation, this)?;
+    Ok(activation
+        .context
+        .workers
+        .registry()
+        .terminate_worker(id)
+        .into())
+}
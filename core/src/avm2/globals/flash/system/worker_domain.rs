@@ -0,0 +1,65 @@
+//! `flash.system.WorkerDomain` native methods
+
+use crate::avm2::globals::flash::system::worker::object_for_handle;
+use crate::avm2::object::{ScriptObject, TObject, VectorObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::vector::VectorStorage;
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::tag_utils::SwfMovie;
+use crate::worker::SharedHandle;
+
+pub fn create_current<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // There's only one worker domain, so it has no state of its own. Its
+    // constructor always throws, so skip it.
+    let class = activation.avm2().classes().workerdomain;
+    let object = ScriptObject::custom_object(
+        activation.gc(),
+        class.inner_class_definition(),
+        Some(class.prototype()),
+        class.instance_vtable(),
+    );
+    if let Some(superclass) = class.superclass_object() {
+        superclass.call_super_init(object.into(), &[], activation)?;
+    }
+    Ok(object.into())
+}
+
+pub fn create_worker<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let swf = args.get_object(activation, 0, "swf")?;
+    let url = activation.context.swf.url().to_string();
+
+    // A worker with an invalid movie can still be created, but terminates as
+    // soon as it's started.
+    let movie = swf.as_bytearray().and_then(|bytes| {
+        SwfMovie::from_data(bytes.bytes(), url, None)
+            .inspect_err(|e| tracing::warn!("Couldn't load worker SWF: {e}"))
+            .ok()
+    });
+
+    let id = activation.context.workers.registry().create_worker(movie);
+    Ok(object_for_handle(activation, SharedHandle::Worker(id))?.into())
+}
+
+pub fn list_workers<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let ids = activation.context.workers.registry().live_workers();
+    let workers = ids
+        .into_iter()
+        .map(|id| Ok(object_for_handle(activation, SharedHandle::Worker(id))?.into()))
+        .collect::<Result<Vec<_>, Error<'gc>>>()?;
+
+    let value_type = activation.avm2().classes().worker.inner_class_definition();
+    let storage = VectorStorage::from_values(workers, false, Some(value_type));
+    Ok(VectorObject::from_vector(storage, activation)?.into())
+}
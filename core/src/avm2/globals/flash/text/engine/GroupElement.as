@@ -75,7 +75,23 @@ package flash.text.engine {
             var resultingText:String = "";
 
             for (var i = 0; i < this._elements.length; i ++) {
-                resultingText += this._elements[i].text;
+                var elementText:String = this._elements[i].text;
+                if (elementText != null) {
+                    resultingText += elementText;
+                }
+            }
+
+            return resultingText;
+        }
+
+        override public function get rawText():String {
+            var resultingText:String = "";
+
+            for (var i = 0; i < this._elements.length; i ++) {
+                var elementText:String = this._elements[i].rawText;
+                if (elementText != null) {
+                    resultingText += elementText;
+                }
             }
 
             return resultingText;
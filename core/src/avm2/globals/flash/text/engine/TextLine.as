@@ -1,8 +1,7 @@
 package flash.text.engine {
-    import __ruffle__.stub_getter;
     import __ruffle__.stub_setter;
-    import __ruffle__.stub_method;
 
+    import flash.display.DisplayObject;
     import flash.display.DisplayObjectContainer;
     import flash.errors.IllegalOperationError;
     import flash.geom.Rectangle;
@@ -18,6 +17,17 @@ package flash.text.engine {
         internal var _textBlock:TextBlock = null;
         internal var _rawTextLength:int = 0;
         internal var _validity:String = "valid";
+        internal var _textBlockBeginIndex:int = 0;
+        internal var _ascent:Number = 0.0;
+        internal var _descent:Number = 0.0;
+        internal var _unjustifiedTextWidth:Number = 0.0;
+        internal var _hasGraphicElement:Boolean = false;
+        internal var _hasTabs:Boolean = false;
+        internal var _nextLine:TextLine = null;
+        internal var _previousLine:TextLine = null;
+
+        // The `DisplayObject` of each atom that is a `GraphicElement`, by atom index.
+        internal var _atomGraphics:Array = null;
 
         public static const MAX_LINE_WIDTH:int = 1000000;
 
@@ -32,8 +42,7 @@ package flash.text.engine {
         }
 
         public function get textBlockBeginIndex():int {
-            stub_getter("flash.text.engine.TextLine", "textBlockBeginIndex");
-            return 0;
+            return this._textBlockBeginIndex;
         }
 
         public function get specifiedWidth():Number {
@@ -45,74 +54,131 @@ package flash.text.engine {
         }
 
         public function get ascent():Number {
-            stub_getter("flash.text.engine.TextLine", "ascent");
-            return 12.0;
+            return this._ascent;
         }
 
         public function get descent():Number {
-            stub_getter("flash.text.engine.TextLine", "descent");
-            return 3.0;
+            return this._descent;
         }
 
         public function get unjustifiedTextWidth():Number {
-            stub_getter("flash.text.engine.TextLine", "unjustifiedTextWidth");
-            return this._specifiedWidth;
+            return this._unjustifiedTextWidth;
         }
 
         public native function get textWidth():Number;
-        public native function get textHeight():Number;
+
+        public function get textHeight():Number {
+            return this._ascent + this._descent;
+        }
 
         public function get validity():String {
-            stub_getter("flash.text.engine.TextLine", "validity");
             return this._validity;
         }
 
         public function set validity(value:String):void {
-            stub_setter("flash.text.engine.TextLine", "validity");
+            if (value == null) {
+                throw new TypeError("Error #2007: Parameter validity must be non-null.", 2007);
+            }
+            if (value == TextLineValidity.VALID && this._validity != TextLineValidity.VALID) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            if (value == TextLineValidity.STATIC) {
+                stub_setter("flash.text.engine.TextLine", "validity", "with static");
+            }
             this._validity = value;
         }
 
         public function get hasGraphicElement():Boolean {
-            stub_getter("flash.text.engine.TextLine", "hasGraphicElement");
-            return false;
+            return this._hasGraphicElement;
         }
 
+        // Every character is its own atom.
         public function get atomCount():int {
-            stub_getter("flash.text.engine.TextLine", "atomCount");
             return this._rawTextLength;
         }
 
         public function get nextLine():TextLine {
-            return null;
+            return this._nextLine;
         }
 
         public function get previousLine():TextLine {
-            return null;
+            return this._previousLine;
         }
 
         public function getBaselinePosition(baseline:String):Number {
-            stub_method("flash.text.engine.TextLine", "getBaselinePosition");
-            return 0.0;
+            switch (baseline) {
+                case TextBaseline.ROMAN:
+                    return 0.0;
+                case TextBaseline.ASCENT:
+                case TextBaseline.IDEOGRAPHIC_TOP:
+                    return -this._ascent;
+                case TextBaseline.DESCENT:
+                case TextBaseline.IDEOGRAPHIC_BOTTOM:
+                    return this._descent;
+                case TextBaseline.IDEOGRAPHIC_CENTER:
+                    return (this._descent - this._ascent) / 2;
+                default:
+                    throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
         }
 
         public function get hasTabs():Boolean {
-            stub_getter("flash.text.engine.TextLine", "hasTabs");
-            return false;
+            return this._hasTabs;
         }
 
-        public function getAtomIndexAtPoint(stageX:Number, stageY:Number):int {
-            stub_method("flash.text.engine.TextLine", "getAtomIndexAtPoint");
-            return -1;
-        }
+        public native function getAtomIndexAtPoint(stageX:Number, stageY:Number):int;
 
         public function getAtomIndexAtCharIndex(charIndex:int):int {
-            stub_method("flash.text.engine.TextLine", "getAtomIndexAtCharIndex");
-            return -1;
+            var index:int = charIndex - this._textBlockBeginIndex;
+            if (index < 0 || index >= this._rawTextLength) {
+                return -1;
+            }
+            return index;
+        }
+
+        public native function getAtomBounds(index:int):Rectangle;
+
+        public function getAtomCenter(index:int):Number {
+            var bounds:Rectangle = this.getAtomBounds(index);
+            return bounds.x + bounds.width / 2;
+        }
+
+        public function getAtomTextBlockBeginIndex(index:int):int {
+            this.checkAtomIndex(index);
+            return this._textBlockBeginIndex + index;
+        }
+
+        public function getAtomTextBlockEndIndex(index:int):int {
+            this.checkAtomIndex(index);
+            return this._textBlockBeginIndex + index + 1;
+        }
+
+        public function getAtomGraphic(index:int):DisplayObject {
+            this.checkAtomIndex(index);
+            if (this._atomGraphics == null) {
+                return null;
+            }
+            return this._atomGraphics[index];
+        }
+
+        public function getAtomWordBoundaryOnLeft(index:int):Boolean {
+            this.checkAtomIndex(index);
+            if (index == 0 || this._textBlock == null) {
+                return true;
+            }
+            var text:String = this._textBlock.content.rawText;
+            var blockIndex:int = this._textBlockBeginIndex + index;
+            return isWhitespace(text.charAt(blockIndex - 1)) != isWhitespace(text.charAt(blockIndex));
+        }
+
+        private static function isWhitespace(char:String):Boolean {
+            return char == " " || char == "\t" || char == "\n" || char == "\r";
         }
 
-        public function getAtomBounds(index:int):Rectangle {
-            stub_method("flash.text.engine.TextLine", "getAtomBounds");
-            return new Rectangle(0, 0, 0, 0);
+        private function checkAtomIndex(index:int):void {
+            if (index < 0 || index >= this._rawTextLength) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
         }
 
         // This function does nothing in Flash Player 32
@@ -1,192 +1,1010 @@
 use crate::avm2::activation::Activation;
-use crate::avm2::error::Error;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::error::{make_error_2004, Error, Error2004Type};
 use crate::avm2::globals::flash::display::display_object::initialize_for_allocator;
-use crate::avm2::object::{Object, TObject};
+use crate::avm2::globals::flash::text::engine::text_line::{atom_edges, get_internal};
+use crate::avm2::object::{ArrayObject, Object, TObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Multiname;
-use crate::avm2_stub_method;
+use crate::context::UpdateContext;
 use crate::display_object::{EditText, TDisplayObject};
 use crate::html::TextFormat;
-use crate::string::WStr;
+use crate::string::{utils as string_utils, WString};
+use std::ops::Range;
+use swf::{Rectangle, Twips};
 
+/// The character that stands in for a `GraphicElement` in the text of a block.
+const GRAPHIC_ELEMENT_CHAR: u16 = 0xFDEF;
+
+/// Where lines may be broken in some text, from `ElementFormat.breakOpportunity`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BreakOpportunity {
+    /// Break at word boundaries and around ideographs.
+    Auto,
+
+    /// Break between any two characters.
+    Any,
+
+    /// Never break.
+    None,
+
+    /// Put every character on a line of its own.
+    All,
+}
+
+/// A part of a text block's content that shares a single `ElementFormat`.
+struct ContentRun<'gc> {
+    /// The characters of the text block covered by this run.
+    range: Range<usize>,
+
+    text_format: TextFormat,
+
+    is_device_font: bool,
+
+    break_opportunity: BreakOpportunity,
+
+    /// The `GraphicElement` this run stands in for, if any.
+    graphic: Option<GraphicRun<'gc>>,
+}
+
+struct GraphicRun<'gc> {
+    graphic: Value<'gc>,
+    width: f64,
+    height: f64,
+}
+
+/// Which lines of a text block are justified, from `TextJustifier.lineJustification`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Justification {
+    Unjustified,
+    AllButLast,
+    AllButMandatoryBreak,
+    AllIncludingLast,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TabAlignment {
+    Start,
+    Center,
+    End,
+    Decimal,
+}
+
+struct TabStop {
+    alignment: TabAlignment,
+    position: f64,
+    decimal_alignment_token: WString,
+}
+
+/// Everything about a text block that affects how it's broken into lines.
+struct BlockContent<'gc> {
+    text: WString,
+
+    runs: Vec<ContentRun<'gc>>,
+
+    /// The block's tab stops, sorted by position.
+    tab_stops: Vec<TabStop>,
+
+    justification: Justification,
+
+    /// Whether justification spreads out every character, rather than only
+    /// spaces.
+    justify_letters: bool,
+}
+
+impl<'gc> BlockContent<'gc> {
+    fn run_at(&self, index: usize) -> Option<&ContentRun<'gc>> {
+        self.runs.iter().find(|run| run.range.contains(&index))
+    }
+
+    fn break_opportunity_at(&self, index: usize) -> BreakOpportunity {
+        self.run_at(index)
+            .map_or(BreakOpportunity::Auto, |run| run.break_opportunity)
+    }
+}
+
+/// The result of laying out one line of a text block.
+struct LineLayout {
+    /// The number of characters on the line.
+    length: usize,
+
+    /// Whether the line had to be broken somewhere that isn't a break
+    /// opportunity.
+    emergency: bool,
+
+    ascent: f64,
+
+    descent: f64,
+
+    unjustified_width: f64,
+
+    has_tabs: bool,
+}
+
+/// Implements `TextBlock.createTextLine`.
 pub fn create_text_line<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let namespaces = activation.avm2().namespaces;
-    avm2_stub_method!(activation, "flash.text.TextBlock", "createTextLine");
-
-    let previous_text_line = args.try_get_object(activation, 0);
+    let previous_line = args.try_get_object(activation, 0);
     let width = args.get_f64(activation, 1)?;
+    let fit_something = args.get_bool(3);
 
-    let content = this.get_public_property("content", activation)?;
+    break_line(activation, this, None, previous_line, width, fit_something)
+}
 
-    let content = if matches!(content, Value::Null) {
+/// Implements `TextBlock.recreateTextLine`.
+pub fn recreate_text_line<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(text_line) = args.try_get_object(activation, 0) else {
+        return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+    };
+    let previous_line = args.try_get_object(activation, 1);
+    let width = args.get_f64(activation, 2)?;
+    let fit_something = args.get_bool(4);
+
+    // The line to reuse has to have been released, or belong to this block.
+    let text_block = get_internal(activation, text_line, "_textBlock")?;
+    let is_other_block = text_block
+        .as_object()
+        .is_some_and(|text_block| !Object::ptr_eq(text_block, this));
+    let is_previous_line =
+        previous_line.is_some_and(|previous_line| Object::ptr_eq(previous_line, text_line));
+    if is_other_block || is_previous_line {
+        return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+    }
+
+    break_line(
+        activation,
+        this,
+        Some(text_line),
+        previous_line,
+        width,
+        fit_something,
+    )
+}
+
+/// Break the next line of a text block, after `previous_line`.
+///
+/// If `reuse` is given, that `TextLine` is laid out again rather than
+/// creating a new one.
+fn break_line<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    block: Object<'gc>,
+    reuse: Option<Object<'gc>>,
+    previous_line: Option<Object<'gc>>,
+    width: f64,
+    fit_something: bool,
+) -> Result<Value<'gc>, Error<'gc>> {
+    if !(0.0..=1000000.0).contains(&width) {
+        return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+    }
+
+    let content = block.get_public_property("content", activation)?;
+    let Some(content) = content.as_object() else {
         return Ok(Value::Null);
-    } else {
-        content.as_object().unwrap()
-    };
-
-    let text = match previous_text_line {
-        Some(_) => {
-            // Some SWFs rely on eventually getting `null` from createLineText.
-            // TODO: Support multiple lines
-            this.set_property(
-                &Multiname::new(
-                    namespaces.flash_text_engine_internal,
-                    "_textLineCreationResult",
-                ),
-                "complete".into(),
-                activation,
-            )?;
-            return Ok(Value::Null);
-        }
-        // Get the content element's text property.
-        // TODO: GraphicElement?
-        None => {
-            let txt = content
-                .get_public_property("text", activation)
-                .unwrap_or_else(|_| activation.strings().empty().into());
-
-            if matches!(txt, Value::Null) {
-                // FP returns a null TextLine when `o` is null- note that
-                // `o` is already coerced to a String because of the AS bindings.
-                return Ok(Value::Null);
-            } else {
-                txt.coerce_to_string(activation)
-                    .expect("Guaranteed by AS bindings")
-            }
-        }
     };
 
-    let class = activation.avm2().classes().textline;
-    let movie = activation.caller_movie_or_root();
+    // FP returns a null TextLine when the content has no text.
+    if matches!(
+        content.get_public_property("rawText", activation)?,
+        Value::Null
+    ) {
+        return Ok(Value::Null);
+    }
 
-    // FIXME: TextLine should be its own DisplayObject
-    let display_object: EditText =
-        EditText::new_tlf(activation.context, movie, 0.0, 0.0, width, 15.0);
+    let begin = if let Some(previous_line) = previous_line {
+        let previous_block = get_internal(activation, previous_line, "_textBlock")?;
+        let validity =
+            get_internal(activation, previous_line, "_validity")?.coerce_to_string(activation)?;
+        let is_ours = previous_block
+            .as_object()
+            .is_some_and(|previous_block| Object::ptr_eq(previous_block, block));
+        if !is_ours || &validity != b"valid" {
+            return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+        }
 
-    display_object.set_text(text.as_wstr(), activation.context);
+        let begin = get_internal(activation, previous_line, "_textBlockBeginIndex")?
+            .coerce_to_i32(activation)?;
+        let length =
+            get_internal(activation, previous_line, "_rawTextLength")?.coerce_to_i32(activation)?;
+        (begin + length).max(0) as usize
+    } else {
+        0
+    };
 
-    // FIXME: This needs to use `intrinsic_bounds` to measure the width
-    // of the provided text, and set the width of the EditText to that.
-    // Some games depend on this (e.g. Realm Grinder).
+    let block_content = read_block_content(activation, block, content)?;
+    if previous_line.is_some() && begin >= block_content.text.len() {
+        set_internal(activation, block, "_textLineCreationResult", "complete")?;
+        return Ok(Value::Null);
+    }
 
-    let element_format = content
-        .get_public_property("elementFormat", activation)?
-        .as_object();
+    let edit_text = reuse
+        .and_then(|line| line.as_display_object())
+        .and_then(|display_object| display_object.as_edit_text());
+    let edit_text = match edit_text {
+        Some(edit_text) => edit_text,
+        None => {
+            let movie = activation.caller_movie_or_root();
+            EditText::new_tlf(activation.context, movie, 0.0, 0.0, width, 15.0)
+        }
+    };
 
-    apply_format(activation, display_object, text.as_wstr(), element_format)?;
+    let Some(layout) = layout_line(
+        activation.context,
+        edit_text,
+        &block_content,
+        begin,
+        width,
+        fit_something,
+    ) else {
+        set_internal(
+            activation,
+            block,
+            "_textLineCreationResult",
+            "insufficientWidth",
+        )?;
+        return Ok(Value::Null);
+    };
 
-    let instance = initialize_for_allocator(activation, display_object.into(), class)?;
-    class.call_super_init(instance.into(), &[], activation)?;
+    let instance = match reuse {
+        Some(instance) => instance,
+        None => {
+            let class = activation.avm2().classes().textline;
+            let instance = initialize_for_allocator(activation, edit_text.into(), class)?;
+            class.call_super_init(instance.into(), &[], activation)?;
+            instance
+        }
+    };
 
-    instance.set_property(
-        &Multiname::new(namespaces.flash_text_engine_internal, "_textBlock"),
-        this.into(),
-        activation,
-    )?;
+    let end = begin + layout.length;
+    let edges = atom_edges(edit_text, layout.length);
+    let mut atom_graphics = ArrayStorage::new(0);
+    let mut line_graphics = Vec::new();
+    let mut has_graphic_element = false;
+    for run in &block_content.runs {
+        if let Some(graphic) = &run.graphic {
+            if (begin..end).contains(&run.range.start) {
+                let index = run.range.start - begin;
+                atom_graphics.set(index, graphic.graphic);
+                has_graphic_element = true;
 
-    instance.set_property(
-        &Multiname::new(namespaces.flash_text_engine_internal, "_specifiedWidth"),
-        args.get_value(1),
-        activation,
-    )?;
+                // Graphics sit on the baseline, at the start of their atom.
+                let display_object = graphic
+                    .graphic
+                    .as_object()
+                    .and_then(|graphic| graphic.as_display_object());
+                if let Some(display_object) = display_object {
+                    let gc_context = activation.context.gc_context;
+                    display_object.set_x(gc_context, Twips::from_pixels(edges[index].0));
+                    display_object.set_y(gc_context, Twips::from_pixels(-graphic.height));
+                    line_graphics.push(display_object);
+                }
+            }
+        }
+    }
+    edit_text.set_tlf_graphics(activation.context.gc_context, line_graphics);
+    let atom_graphics = if has_graphic_element {
+        ArrayObject::from_storage(activation, atom_graphics)?.into()
+    } else {
+        Value::Null
+    };
 
-    instance.set_property(
-        &Multiname::new(namespaces.flash_text_engine_internal, "_rawTextLength"),
-        text.len().into(),
-        activation,
-    )?;
+    link_line(activation, block, instance, previous_line)?;
 
-    this.set_property(
-        &Multiname::new(
-            namespaces.flash_text_engine_internal,
-            "_textLineCreationResult",
-        ),
-        "success".into(),
+    set_internal(activation, instance, "_textBlock", block)?;
+    set_internal(activation, instance, "_validity", "valid")?;
+    set_internal(activation, instance, "_specifiedWidth", width)?;
+    set_internal(activation, instance, "_textBlockBeginIndex", begin)?;
+    set_internal(activation, instance, "_rawTextLength", layout.length)?;
+    set_internal(activation, instance, "_ascent", layout.ascent)?;
+    set_internal(activation, instance, "_descent", layout.descent)?;
+    set_internal(
         activation,
+        instance,
+        "_unjustifiedTextWidth",
+        layout.unjustified_width,
     )?;
-
-    this.set_property(
-        &Multiname::new(namespaces.flash_text_engine_internal, "_firstLine"),
-        instance.into(),
+    set_internal(activation, instance, "_hasTabs", layout.has_tabs)?;
+    set_internal(
         activation,
+        instance,
+        "_hasGraphicElement",
+        has_graphic_element,
     )?;
+    set_internal(activation, instance, "_atomGraphics", atom_graphics)?;
+
+    let result = if layout.emergency {
+        "emergency"
+    } else {
+        "success"
+    };
+    set_internal(activation, block, "_textLineCreationResult", result)?;
 
     Ok(instance.into())
 }
 
-fn apply_format<'gc>(
+/// Put `line` after `previous_line` in a block's list of lines.
+///
+/// Any lines that used to follow `previous_line` become invalid.
+fn link_line<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    display_object: EditText<'gc>,
-    text: &WStr,
-    element_format: Option<Object<'gc>>,
+    block: Object<'gc>,
+    line: Object<'gc>,
+    previous_line: Option<Object<'gc>>,
 ) -> Result<(), Error<'gc>> {
-    if let Some(element_format) = element_format {
-        // TODO: Support more ElementFormat properties
-        let color = element_format
-            .get_public_property("color", activation)?
-            .coerce_to_u32(activation)?;
-        let size = element_format
-            .get_public_property("fontSize", activation)?
-            .coerce_to_number(activation)?;
+    let mut stale_line = match previous_line {
+        Some(previous_line) => get_internal(activation, previous_line, "_nextLine")?,
+        None => get_internal(activation, block, "_firstLine")?,
+    }
+    .as_object();
 
-        let (font, bold, italic, is_device_font) = if let Value::Object(font_description) =
-            element_format.get_public_property("fontDescription", activation)?
-        {
-            (
-                Some(
-                    font_description
-                        .get_public_property("fontName", activation)?
-                        .coerce_to_string(activation)?
-                        .as_wstr()
-                        .into(),
-                ),
-                Some(
-                    &font_description
-                        .get_public_property("fontWeight", activation)?
-                        .coerce_to_string(activation)?
-                        == b"bold",
-                ),
-                Some(
-                    &font_description
-                        .get_public_property("fontPosture", activation)?
-                        .coerce_to_string(activation)?
-                        == b"italic",
-                ),
-                &font_description
-                    .get_public_property("fontLookup", activation)?
-                    .coerce_to_string(activation)?
-                    == b"device",
-            )
+    while let Some(stale) = stale_line {
+        stale_line = get_internal(activation, stale, "_nextLine")?.as_object();
+        set_internal(activation, stale, "_validity", "invalid")?;
+        set_internal(activation, stale, "_textBlock", Value::Null)?;
+        set_internal(activation, stale, "_previousLine", Value::Null)?;
+        set_internal(activation, stale, "_nextLine", Value::Null)?;
+    }
+
+    match previous_line {
+        Some(previous_line) => {
+            set_internal(activation, previous_line, "_nextLine", line)?;
+            set_internal(activation, line, "_previousLine", previous_line)?;
+        }
+        None => {
+            set_internal(activation, block, "_firstLine", line)?;
+            set_internal(activation, line, "_previousLine", Value::Null)?;
+        }
+    }
+    set_internal(activation, line, "_nextLine", Value::Null)?;
+    set_internal(activation, block, "_lastLine", line)?;
+
+    Ok(())
+}
+
+/// Lay out the line of `content` that starts at `begin` into `edit_text`.
+///
+/// Returns `None` if nothing fits on the line.
+fn layout_line<'gc>(
+    context: &mut UpdateContext<'gc>,
+    edit_text: EditText<'gc>,
+    content: &BlockContent<'gc>,
+    begin: usize,
+    width: f64,
+    fit_something: bool,
+) -> Option<LineLayout> {
+    let text = &content.text;
+    let tab_positions: Vec<f64> = content.tab_stops.iter().map(|stop| stop.position).collect();
+
+    // Lay out everything up to the next mandatory break, to see how much of
+    // it fits in the line.
+    let limit = mandatory_break_after(content, begin);
+    let mut spacing = vec![0.0; limit - begin];
+    set_line_text(
+        context,
+        edit_text,
+        content,
+        begin..limit,
+        &spacing,
+        &tab_positions,
+    );
+
+    // Graphics are stood in for by spaces, which are then stretched to the
+    // size of the graphic.
+    let edges = atom_edges(edit_text, limit - begin);
+    let mut has_graphics = false;
+    for run in &content.runs {
+        if let Some(graphic) = &run.graphic {
+            if (begin..limit).contains(&run.range.start) {
+                let index = run.range.start - begin;
+                let (x_min, x_max) = edges[index];
+                spacing[index] = graphic.width - (x_max - x_min);
+                has_graphics = true;
+            }
+        }
+    }
+    let edges = if has_graphics {
+        set_line_text(
+            context,
+            edit_text,
+            content,
+            begin..limit,
+            &spacing,
+            &tab_positions,
+        );
+        atom_edges(edit_text, limit - begin)
+    } else {
+        edges
+    };
+
+    let (length, emergency) = find_line_end(content, begin, &edges, width, fit_something)?;
+    let end = begin + length;
+    spacing.truncate(length);
+    set_line_text(
+        context,
+        edit_text,
+        content,
+        begin..end,
+        &spacing,
+        &tab_positions,
+    );
+
+    let is_tab = |index: usize| text.at(index) == u16::from(b'\t');
+    let has_tabs = (begin..end).any(is_tab);
+    if has_tabs {
+        let edges = atom_edges(edit_text, length);
+        if let Some(aligned_positions) = align_tabs(content, begin, &edges) {
+            set_line_text(
+                context,
+                edit_text,
+                content,
+                begin..end,
+                &spacing,
+                &aligned_positions,
+            );
+        }
+    }
+
+    let unjustified_width = edit_text.measure_text(context).0.to_pixels();
+
+    let is_last_line = end >= text.len();
+    let ends_with_mandatory_break = length > 0 && is_mandatory_break(text.at(end - 1));
+    let justify = match content.justification {
+        Justification::Unjustified => false,
+        Justification::AllButLast => !is_last_line,
+        Justification::AllButMandatoryBreak => !is_last_line && !ends_with_mandatory_break,
+        Justification::AllIncludingLast => true,
+    };
+    if justify {
+        let edges = atom_edges(edit_text, length);
+        if justify_line(content, begin, &edges, width, &mut spacing) {
+            set_line_text(
+                context,
+                edit_text,
+                content,
+                begin..end,
+                &spacing,
+                &tab_positions,
+            );
+        }
+    }
+
+    let (text_ascent, descent) = edit_text
+        .line_metrics(0)
+        .map(|metrics| (metrics.ascent, metrics.descent))
+        .unwrap_or_default();
+    let text_width = edit_text.measure_text(context).0;
+
+    // Graphics sit on the baseline.
+    let mut ascent = text_ascent.to_pixels();
+    for run in &content.runs {
+        if let Some(graphic) = &run.graphic {
+            if (begin..end).contains(&run.range.start) {
+                ascent = ascent.max(graphic.height);
+            }
+        }
+    }
+
+    // Put the origin of the line on its baseline.
+    edit_text.set_tlf_bounds(
+        context,
+        Rectangle {
+            x_min: -EditText::GUTTER,
+            x_max: text_width + EditText::GUTTER,
+            y_min: -(text_ascent + EditText::GUTTER),
+            y_max: descent + EditText::GUTTER,
+        },
+    );
+
+    Some(LineLayout {
+        length,
+        emergency,
+        ascent,
+        descent: descent.to_pixels(),
+        unjustified_width,
+        has_tabs,
+    })
+}
+
+/// Replace the text of the `EditText` behind a line with part of a block's
+/// content.
+///
+/// `spacing` is extra space to add after each character of the line.
+fn set_line_text<'gc>(
+    context: &mut UpdateContext<'gc>,
+    edit_text: EditText<'gc>,
+    content: &BlockContent<'gc>,
+    range: Range<usize>,
+    spacing: &[f64],
+    tab_positions: &[f64],
+) {
+    let mut text = WString::new();
+    for c in content.text[range.clone()].iter() {
+        if is_mandatory_break(c) || c == GRAPHIC_ELEMENT_CHAR {
+            text.push(u16::from(b' '));
         } else {
-            (None, None, None, true)
+            text.push(c);
+        }
+    }
+
+    let mut formats = Vec::new();
+    for run in &content.runs {
+        let from = run.range.start.max(range.start);
+        let to = run.range.end.min(range.end);
+        if from >= to {
+            continue;
+        }
+
+        let mut format = run.text_format.clone();
+        if !tab_positions.is_empty() {
+            format.tab_stops = Some(tab_positions.to_vec());
+        }
+        let letter_spacing = format.letter_spacing.unwrap_or_default();
+
+        // Characters with extra space after them need a format of their own.
+        let mut start = from;
+        for index in from..to {
+            let extra = spacing[index - range.start];
+            if extra != 0.0 {
+                if start < index {
+                    formats.push((start - range.start, index - range.start, format.clone()));
+                }
+                let spaced_format = TextFormat {
+                    letter_spacing: Some(letter_spacing + extra),
+                    ..format.clone()
+                };
+                formats.push((index - range.start, index + 1 - range.start, spaced_format));
+                start = index + 1;
+            }
+        }
+        if start < to {
+            formats.push((start - range.start, to - range.start, format));
+        }
+    }
+
+    if let Some(run) = content.run_at(range.start) {
+        edit_text.set_new_text_format(run.text_format.clone(), context);
+        edit_text.set_is_device_font(context, run.is_device_font);
+    } else {
+        edit_text.set_is_device_font(context, true);
+    }
+    edit_text.set_tlf_text(&text, &formats, context);
+}
+
+/// Whether a character always ends a line.
+fn is_mandatory_break(c: u16) -> bool {
+    string_utils::swf_is_newline(c) || c == 0x2028 || c == 0x2029
+}
+
+/// Whether a character is from a script that can be broken between any two
+/// characters.
+fn is_ideographic(c: u16) -> bool {
+    matches!(c, 0x2E80..=0x9FFF | 0xAC00..=0xD7AF | 0xF900..=0xFAFF | 0xFF00..=0xFFEF)
+}
+
+/// The index after the last character that can be on a line starting at
+/// `begin`, before any mandatory break.
+fn mandatory_break_after(content: &BlockContent<'_>, begin: usize) -> usize {
+    let text = &content.text;
+    for index in begin..text.len() {
+        if content.break_opportunity_at(index) == BreakOpportunity::All {
+            return if index == begin { index + 1 } else { index };
+        }
+        if is_mandatory_break(text.at(index)) {
+            return index + 1;
+        }
+    }
+    text.len()
+}
+
+/// Whether a line can be broken before the character at `index`.
+fn can_break_before(content: &BlockContent<'_>, index: usize) -> bool {
+    let before = content.break_opportunity_at(index - 1);
+    let after = content.break_opportunity_at(index);
+    if before == BreakOpportunity::Any || after == BreakOpportunity::Any {
+        return true;
+    }
+    if before == BreakOpportunity::None || after == BreakOpportunity::None {
+        return false;
+    }
+
+    let before = content.text.at(index - 1);
+    let after = content.text.at(index);
+    if string_utils::swf_is_whitespace(after) {
+        return false;
+    }
+    string_utils::swf_is_whitespace(before)
+        || before == u16::from(b'-')
+        || before == GRAPHIC_ELEMENT_CHAR
+        || after == GRAPHIC_ELEMENT_CHAR
+        || is_ideographic(before)
+        || is_ideographic(after)
+}
+
+/// Find how many characters of a line fit in `width`.
+///
+/// Returns the length of the line, and whether it had to be broken somewhere
+/// that isn't a break opportunity.
+fn find_line_end(
+    content: &BlockContent<'_>,
+    begin: usize,
+    edges: &[(f64, f64)],
+    width: f64,
+    fit_something: bool,
+) -> Option<(usize, bool)> {
+    // Whitespace is allowed to hang off the end of a line.
+    let overflow = edges.iter().enumerate().position(|(index, (_, x_max))| {
+        *x_max > width && !string_utils::swf_is_whitespace(content.text.at(begin + index))
+    });
+    let Some(overflow) = overflow else {
+        return Some((edges.len(), false));
+    };
+
+    if let Some(length) = (1..=overflow)
+        .rev()
+        .find(|length| can_break_before(content, begin + length))
+    {
+        Some((length, false))
+    } else if overflow > 0 {
+        Some((overflow, true))
+    } else if fit_something {
+        Some((1, true))
+    } else {
+        None
+    }
+}
+
+/// Work out where tab stops need to be for text after each tab to line up
+/// with its stop, given a layout where every stop is start-aligned.
+///
+/// Returns `None` if no tab stops need to move.
+fn align_tabs(content: &BlockContent<'_>, begin: usize, edges: &[(f64, f64)]) -> Option<Vec<f64>> {
+    let text = &content.text;
+    let is_tab = |index: usize| text.at(begin + index) == u16::from(b'\t');
+
+    let mut positions: Vec<f64> = content.tab_stops.iter().map(|stop| stop.position).collect();
+    let mut changed = false;
+    for tab in (0..edges.len()).filter(|index| is_tab(*index)) {
+        // The tab goes to the first stop after wherever it starts.
+        let cursor = edges[tab].0;
+        let Some(stop_index) = content
+            .tab_stops
+            .iter()
+            .position(|stop| stop.position > cursor)
+        else {
+            continue;
         };
+        let stop = &content.tab_stops[stop_index];
+        if stop.alignment == TabAlignment::Start {
+            continue;
+        }
+
+        let segment_start = tab + 1;
+        let segment_end = (segment_start..edges.len())
+            .find(|index| is_tab(*index))
+            .unwrap_or(edges.len());
+        let segment_end = (segment_start..segment_end)
+            .rev()
+            .find(|index| !string_utils::swf_is_whitespace(text.at(begin + index)))
+            .map_or(segment_start, |index| index + 1);
+        if segment_start >= segment_end {
+            continue;
+        }
 
-        let format = TextFormat {
-            color: Some(swf::Color::from_rgb(color, 0xFF)),
-            size: Some(size),
-            font,
-            bold,
-            italic,
-            ..TextFormat::default()
+        let left = edges[segment_start].0;
+        let segment_width = edges[segment_end - 1].1 - left;
+        let offset = match stop.alignment {
+            TabAlignment::Start => unreachable!(),
+            TabAlignment::Center => segment_width / 2.0,
+            TabAlignment::End => segment_width,
+            TabAlignment::Decimal => {
+                let segment = &text[begin + segment_start..begin + segment_end];
+                let token = stop.decimal_alignment_token.as_wstr();
+                match segment.find(token).filter(|_| !token.is_empty()) {
+                    Some(index) => edges[segment_start + index].0 - left,
+                    None => segment_width,
+                }
+            }
         };
 
-        display_object.set_is_device_font(activation.context, is_device_font);
-        display_object.set_text_format(0, text.len(), format.clone(), activation.context);
-        display_object.set_new_text_format(format, activation.context);
-    } else {
-        display_object.set_is_device_font(activation.context, true);
+        // The stop still has to be after the cursor to be used.
+        positions[stop_index] = (stop.position - offset).max(cursor + Twips::ONE.to_pixels());
+        changed = true;
     }
 
-    display_object.set_word_wrap(true, activation.context);
+    changed.then_some(positions)
+}
 
-    let measured_text = display_object.measure_text(activation.context);
+/// Add extra space to `spacing` so that a line fills `width`.
+///
+/// Returns `false` if there's nowhere to add space.
+fn justify_line(
+    content: &BlockContent<'_>,
+    begin: usize,
+    edges: &[(f64, f64)],
+    width: f64,
+    spacing: &mut [f64],
+) -> bool {
+    let text = &content.text;
 
-    display_object.set_height(activation.context, measured_text.1.to_pixels());
+    // Trailing whitespace isn't justified, and neither is anything before a tab.
+    let Some(last) = (0..edges.len())
+        .rev()
+        .find(|index| !string_utils::swf_is_whitespace(text.at(begin + index)))
+    else {
+        return false;
+    };
+    let first = (0..last)
+        .rev()
+        .find(|index| text.at(begin + index) == u16::from(b'\t'))
+        .map_or(0, |tab| tab + 1);
+
+    let gaps: Vec<usize> = (first..last)
+        .filter(|index| content.justify_letters || text.at(begin + index) == u16::from(b' '))
+        .collect();
+    let extra = (width - edges[last].1).floor();
+    if gaps.is_empty() || extra <= 0.0 {
+        return false;
+    }
+
+    // Letter spacing is rounded to whole pixels, so spread the remainder out
+    // over the first gaps.
+    let per_gap = (extra / gaps.len() as f64).floor();
+    let remainder = extra as usize - per_gap as usize * gaps.len();
+    for (gap_index, index) in gaps.into_iter().enumerate() {
+        spacing[index] += per_gap + if gap_index < remainder { 1.0 } else { 0.0 };
+    }
+    true
+}
+
+fn read_block_content<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    block: Object<'gc>,
+    content: Object<'gc>,
+) -> Result<BlockContent<'gc>, Error<'gc>> {
+    let mut text = WString::new();
+    let mut runs = Vec::new();
+    collect_runs(activation, content, None, &mut text, &mut runs)?;
+
+    let mut tab_stops = Vec::new();
+    let tab_stop_vector = block.get_public_property("tabStops", activation)?;
+    let tab_stop_values: Vec<Value<'gc>> = tab_stop_vector
+        .as_object()
+        .and_then(|vector| vector.as_vector_storage().map(|v| v.iter().collect()))
+        .unwrap_or_default();
+    for tab_stop in tab_stop_values {
+        let Some(tab_stop) = tab_stop.as_object() else {
+            continue;
+        };
+        let alignment = tab_stop
+            .get_public_property("alignment", activation)?
+            .coerce_to_string(activation)?;
+        let alignment = if &alignment == b"center" {
+            TabAlignment::Center
+        } else if &alignment == b"end" {
+            TabAlignment::End
+        } else if &alignment == b"decimal" {
+            TabAlignment::Decimal
+        } else {
+            TabAlignment::Start
+        };
+        let position = tab_stop
+            .get_public_property("position", activation)?
+            .coerce_to_number(activation)?;
+        let decimal_alignment_token = tab_stop
+            .get_public_property("decimalAlignmentToken", activation)?
+            .coerce_to_string(activation)?;
+        tab_stops.push(TabStop {
+            alignment,
+            position,
+            decimal_alignment_token: decimal_alignment_token.as_wstr().into(),
+        });
+    }
+    tab_stops.sort_by(|a, b| a.position.total_cmp(&b.position));
+
+    let (justification, justify_letters) =
+        match block.get_public_property("textJustifier", activation)? {
+            Value::Object(justifier) => {
+                let line_justification = justifier
+                    .get_public_property("lineJustification", activation)?
+                    .coerce_to_string(activation)?;
+                let justification = if &line_justification == b"allButLast" {
+                    Justification::AllButLast
+                } else if &line_justification == b"allButMandatoryBreak" {
+                    Justification::AllButMandatoryBreak
+                } else if &line_justification == b"allIncludingLast" {
+                    Justification::AllIncludingLast
+                } else {
+                    Justification::Unjustified
+                };
+
+                let east_asian_justifier = activation.avm2().class_defs().eastasianjustifier;
+                let justify_letters = justifier.instance_class() == east_asian_justifier
+                    || justifier
+                        .get_public_property("letterSpacing", activation)?
+                        .coerce_to_boolean();
+                (justification, justify_letters)
+            }
+            _ => (Justification::Unjustified, false),
+        };
+
+    Ok(BlockContent {
+        text,
+        runs,
+        tab_stops,
+        justification,
+        justify_letters,
+    })
+}
+
+/// Flatten a tree of content elements into runs of text.
+///
+/// Elements without an `ElementFormat` of their own use the format of the
+/// group they're in.
+fn collect_runs<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    element: Object<'gc>,
+    group_format: Option<Object<'gc>>,
+    text: &mut WString,
+    runs: &mut Vec<ContentRun<'gc>>,
+) -> Result<(), Error<'gc>> {
+    let element_format = element
+        .get_public_property("elementFormat", activation)?
+        .as_object()
+        .or(group_format);
+
+    let class_defs = activation.avm2().class_defs();
+    let (group_element, graphic_element) = (class_defs.groupelement, class_defs.graphicelement);
+
+    if element.instance_class() == group_element {
+        let elements = get_internal(activation, element, "_elements")?;
+        let elements: Vec<Value<'gc>> = elements
+            .as_object()
+            .and_then(|vector| vector.as_vector_storage().map(|v| v.iter().collect()))
+            .unwrap_or_default();
+        for child in elements {
+            if let Some(child) = child.as_object() {
+                collect_runs(activation, child, element_format, text, runs)?;
+            }
+        }
+        return Ok(());
+    }
+
+    let graphic = if element.instance_class() == graphic_element {
+        Some(GraphicRun {
+            graphic: element.get_public_property("graphic", activation)?,
+            width: element
+                .get_public_property("elementWidth", activation)?
+                .coerce_to_number(activation)?,
+            height: element
+                .get_public_property("elementHeight", activation)?
+                .coerce_to_number(activation)?,
+        })
+    } else {
+        None
+    };
+
+    let element_text = match element.get_public_property("rawText", activation)? {
+        Value::Null | Value::Undefined => return Ok(()),
+        element_text => element_text.coerce_to_string(activation)?,
+    };
+    if element_text.is_empty() {
+        return Ok(());
+    }
+
+    let start = text.len();
+    text.push_str(&element_text);
+    let (text_format, is_device_font, break_opportunity) =
+        read_element_format(activation, element_format)?;
+    runs.push(ContentRun {
+        range: start..text.len(),
+        text_format,
+        is_device_font,
+        break_opportunity,
+        graphic,
+    });
 
     Ok(())
 }
+
+fn read_element_format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    element_format: Option<Object<'gc>>,
+) -> Result<(TextFormat, bool, BreakOpportunity), Error<'gc>> {
+    let Some(element_format) = element_format else {
+        return Ok((TextFormat::default(), true, BreakOpportunity::Auto));
+    };
+
+    // TODO: Support more ElementFormat properties
+    let color = element_format
+        .get_public_property("color", activation)?
+        .coerce_to_u32(activation)?;
+    let size = element_format
+        .get_public_property("fontSize", activation)?
+        .coerce_to_number(activation)?;
+    let kerning = element_format
+        .get_public_property("kerning", activation)?
+        .coerce_to_string(activation)?;
+    let tracking_left = element_format
+        .get_public_property("trackingLeft", activation)?
+        .coerce_to_number(activation)?;
+    let tracking_right = element_format
+        .get_public_property("trackingRight", activation)?
+        .coerce_to_number(activation)?;
+    let break_opportunity = element_format
+        .get_public_property("breakOpportunity", activation)?
+        .coerce_to_string(activation)?;
+    let break_opportunity = if &break_opportunity == b"any" {
+        BreakOpportunity::Any
+    } else if &break_opportunity == b"none" {
+        BreakOpportunity::None
+    } else if &break_opportunity == b"all" {
+        BreakOpportunity::All
+    } else {
+        BreakOpportunity::Auto
+    };
+
+    let (font, bold, italic, is_device_font) = if let Value::Object(font_description) =
+        element_format.get_public_property("fontDescription", activation)?
+    {
+        (
+            Some(
+                font_description
+                    .get_public_property("fontName", activation)?
+                    .coerce_to_string(activation)?
+                    .as_wstr()
+                    .into(),
+            ),
+            Some(
+                &font_description
+                    .get_public_property("fontWeight", activation)?
+                    .coerce_to_string(activation)?
+                    == b"bold",
+            ),
+            Some(
+                &font_description
+                    .get_public_property("fontPosture", activation)?
+                    .coerce_to_string(activation)?
+                    == b"italic",
+            ),
+            &font_description
+                .get_public_property("fontLookup", activation)?
+                .coerce_to_string(activation)?
+                == b"device",
+        )
+    } else {
+        (None, None, None, true)
+    };
+
+    let format = TextFormat {
+        color: Some(swf::Color::from_rgb(color, 0xFF)),
+        size: Some(size),
+        font,
+        bold,
+        italic,
+        kerning: Some(&kerning != b"off"),
+        letter_spacing: Some(tracking_left + tracking_right),
+        ..TextFormat::default()
+    };
+
+    Ok((format, is_device_font, break_opportunity))
+}
+
+fn set_internal<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    name: &'static str,
+    value: impl Into<Value<'gc>>,
+) -> Result<(), Error<'gc>> {
+    let name = Multiname::new(
+        activation.avm2().namespaces.flash_text_engine_internal,
+        name,
+    );
+    object.set_property(&name, value.into(), activation)
+}
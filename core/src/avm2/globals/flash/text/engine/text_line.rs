@@ -1,8 +1,11 @@
 use crate::avm2::activation::Activation;
-use crate::avm2::error::Error;
+use crate::avm2::error::{make_error_2006, Error};
 use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
-use crate::display_object::TDisplayObject;
+use crate::avm2::Multiname;
+use crate::display_object::{EditText, TDisplayObject};
+use crate::prelude::*;
 
 pub fn super_init<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -25,14 +28,122 @@ pub fn get_text_width<'gc>(
     Ok(measured_text.0.to_pixels().into())
 }
 
-pub fn get_text_height<'gc>(
+pub fn get_atom_bounds<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    let index = args.get_i32(activation, 0)?;
+    let atom_count = get_internal(activation, this, "_rawTextLength")?.coerce_to_i32(activation)?;
+    if index < 0 || index >= atom_count {
+        return Err(make_error_2006(activation));
+    }
+
+    let ascent = get_internal(activation, this, "_ascent")?.coerce_to_number(activation)?;
+    let descent = get_internal(activation, this, "_descent")?.coerce_to_number(activation)?;
+
+    let edit_text = this
+        .as_display_object()
+        .and_then(|display_object| display_object.as_edit_text())
+        .unwrap();
+    let (x_min, x_max) = atom_edges(edit_text, atom_count as usize)[index as usize];
+
+    Ok(activation
+        .avm2()
+        .classes()
+        .rectangle
+        .construct(
+            activation,
+            &[
+                x_min.into(),
+                (-ascent).into(),
+                (x_max - x_min).into(),
+                (ascent + descent).into(),
+            ],
+        )?
+        .into())
+}
+
+pub fn get_atom_index_at_point<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let stage_x = args.get_f64(activation, 0)?;
+    let stage_y = args.get_f64(activation, 1)?;
+
+    let atom_count = get_internal(activation, this, "_rawTextLength")?.coerce_to_i32(activation)?;
+    let ascent = get_internal(activation, this, "_ascent")?.coerce_to_number(activation)?;
+    let descent = get_internal(activation, this, "_descent")?.coerce_to_number(activation)?;
+
     let display_object = this.as_display_object().unwrap();
     let edit_text = display_object.as_edit_text().unwrap();
 
-    let measured_text = edit_text.measure_text(activation.context);
-    Ok(measured_text.1.to_pixels().into())
+    let global = Point::from_pixels(stage_x, stage_y);
+    let Some(local) = display_object.global_to_local(global) else {
+        return Ok((-1).into());
+    };
+    let (x, y) = (local.x.to_pixels(), local.y.to_pixels());
+    if y < -ascent || y > descent {
+        return Ok((-1).into());
+    }
+
+    let index = atom_edges(edit_text, atom_count.max(0) as usize)
+        .into_iter()
+        .position(|(x_min, x_max)| x >= x_min && x < x_max);
+    Ok(index.map_or(-1, |index| index as i32).into())
+}
+
+/// The left and right edges of each of the first `count` atoms of a line, in
+/// pixels from the origin of the line.
+///
+/// Characters without a box of their own, like tabs, cover the gap up to the
+/// next character.
+pub fn atom_edges(edit_text: EditText<'_>, count: usize) -> Vec<(f64, f64)> {
+    let mut edges: Vec<Option<(f64, f64)>> = (0..count)
+        .map(|index| {
+            edit_text.char_bounds(index).map(|bounds| {
+                (
+                    (bounds.x_min - EditText::GUTTER).to_pixels(),
+                    (bounds.x_max - EditText::GUTTER).to_pixels(),
+                )
+            })
+        })
+        .collect();
+
+    let mut next_x = edges
+        .iter()
+        .rev()
+        .flatten()
+        .next()
+        .map_or(0.0, |(_, x_max)| *x_max);
+    for index in (0..count).rev() {
+        match edges[index] {
+            Some((x_min, _)) => next_x = x_min,
+            None => {
+                let previous_x = edges[..index]
+                    .iter()
+                    .rev()
+                    .flatten()
+                    .next()
+                    .map_or(0.0, |(_, x_max)| *x_max);
+                edges[index] = Some((previous_x, next_x.max(previous_x)));
+                next_x = previous_x;
+            }
+        }
+    }
+
+    edges.into_iter().flatten().collect()
+}
+
+pub(super) fn get_internal<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = Multiname::new(
+        activation.avm2().namespaces.flash_text_engine_internal,
+        name,
+    );
+    object.get_property(&name, activation)
 }
@@ -1,18 +1,65 @@
 package flash.ui {
     import flash.events.EventDispatcher;
 
+    // Device events are broadcast by the player to every GameInput that has a
+    // listener for them.
     [API("688")]
     public final class GameInput extends EventDispatcher {
-        public static function get isSupported():Boolean {
-            return false;
+        // The connected devices, in the order they were connected. This is
+        // created on first use, as gamepads may be connected before any movie
+        // has loaded.
+        private static var _devices:Array = null;
+
+        public function GameInput() {
         }
 
+        public static native function get isSupported():Boolean;
+
         public static function get numDevices():int {
-            return 0;
+            return devices.length;
         }
 
         public static function getDeviceAt(index:int):GameInputDevice {
-            throw new RangeError("Error #1506: The specified range is invalid.", 1506);
+            if (index < 0 || index >= devices.length) {
+                throw new RangeError("Error #1506: The specified range is invalid.", 1506);
+            }
+            return devices[index];
+        }
+
+        private static native function createDevices():Array;
+
+        internal static function get devices():Array {
+            if (_devices == null) {
+                _devices = createDevices();
+            }
+            return _devices;
+        }
+
+        // Called by the player when a gamepad is connected.
+        internal static function addDevice(device:GameInputDevice):void {
+            devices.push(device);
+        }
+
+        // Called by the player when a gamepad is disconnected.
+        internal static function removeDevice(gamepad:int):GameInputDevice {
+            for (var i:int = 0; i < devices.length; i++) {
+                var device:GameInputDevice = devices[i];
+                if (device._gamepad == gamepad) {
+                    devices.splice(i, 1);
+                    return device;
+                }
+            }
+            return null;
+        }
+
+        // Called by the player when a control of a gamepad changes.
+        internal static function setControlValue(gamepad:int, index:int, value:Number):void {
+            for each (var device:GameInputDevice in devices) {
+                if (device._gamepad == gamepad) {
+                    device.setControlValue(index, value);
+                    return;
+                }
+            }
         }
     }
-}
\ No newline at end of file
+}
@@ -1,10 +1,46 @@
 package flash.ui {
+    import flash.events.Event;
     import flash.events.EventDispatcher;
 
     [API("688")]
     public dynamic class GameInputControl extends EventDispatcher {
+        internal var _device:GameInputDevice;
+        internal var _id:String;
+        internal var _minValue:Number;
+        internal var _maxValue:Number;
+        internal var _value:Number = 0;
+
         public function GameInputControl() {
             throw new ArgumentError("Error #2012: GameInputControl$ class cannot be instantiated.", 2012)
         }
+
+        public function get device():GameInputDevice {
+            return this._device;
+        }
+
+        public function get id():String {
+            return this._id;
+        }
+
+        public function get minValue():Number {
+            return this._minValue;
+        }
+
+        public function get maxValue():Number {
+            return this._maxValue;
+        }
+
+        public function get value():Number {
+            return this._value;
+        }
+
+        internal function setValue(value:Number, dispatch:Boolean):void {
+            if (this._value != value) {
+                this._value = value;
+                if (dispatch) {
+                    this.dispatchEvent(new Event(Event.CHANGE));
+                }
+            }
+        }
     }
-}
\ No newline at end of file
+}
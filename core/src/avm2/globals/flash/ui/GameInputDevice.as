@@ -1,4 +1,7 @@
 package flash.ui {
+    import flash.utils.ByteArray;
+    import __ruffle__.stub_method;
+
     // The AS3 docs say this is only available in AIR 3.7.
     // That was determined to be a lie.
     [API("688")]
@@ -7,5 +10,57 @@ package flash.ui {
         // control values. If `startCachingSamples` returns samples that
         // require more memory than you specify, it throws a memory error.
         public static const MAX_BUFFER_SIZE:int = 32000;
+
+        // The ID that the player uses for this gamepad.
+        internal var _gamepad:int;
+
+        internal var _id:String;
+        internal var _name:String;
+        internal var _controls:Vector.<GameInputControl>;
+
+        // Controls only dispatch change events while the device is enabled.
+        public var enabled:Boolean = false;
+
+        public var sampleInterval:int = 0;
+
+        public function GameInputDevice() {
+            throw new ArgumentError("Error #2012: GameInputDevice$ class cannot be instantiated.", 2012);
+        }
+
+        public function get id():String {
+            return this._id;
+        }
+
+        public function get name():String {
+            return this._name;
+        }
+
+        public function get numControls():int {
+            return this._controls.length;
+        }
+
+        public function getControlAt(i:int):GameInputControl {
+            if (i < 0 || i >= this._controls.length) {
+                throw new RangeError("Error #1506: The specified range is invalid.", 1506);
+            }
+            return this._controls[i];
+        }
+
+        public function startCachingSamples(numSamples:int, controls:Vector.<String>):void {
+            stub_method("flash.ui.GameInputDevice", "startCachingSamples");
+        }
+
+        public function stopCachingSamples():void {
+            stub_method("flash.ui.GameInputDevice", "stopCachingSamples");
+        }
+
+        public function getCachedSamples(data:ByteArray, append:Boolean = false):int {
+            stub_method("flash.ui.GameInputDevice", "getCachedSamples");
+            return 0;
+        }
+
+        internal function setControlValue(index:int, value:Number):void {
+            this._controls[index].setValue(value, this.enabled);
+        }
     }
 }
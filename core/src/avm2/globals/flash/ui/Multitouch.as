@@ -1,6 +1,32 @@
 package flash.ui {
+    import flash.events.GestureEvent;
+    import flash.events.PressAndTapGestureEvent;
+    import flash.events.TransformGestureEvent;
+
     public final class Multitouch {
-        // FIXME - implement a setter and gett
-        public static var inputMode:String = MultitouchInputMode.GESTURE;
+        public static native function get supportsTouchEvents():Boolean;
+
+        public static function get supportsGestureEvents():Boolean {
+            // Gestures are recognized by the frontend from touch input.
+            return supportsTouchEvents;
+        }
+
+        public static function get supportedGestures():Vector.<String> {
+            if (!supportsGestureEvents) {
+                return null;
+            }
+
+            return Vector.<String>([
+                TransformGestureEvent.GESTURE_PAN,
+                TransformGestureEvent.GESTURE_ROTATE,
+                TransformGestureEvent.GESTURE_SWIPE,
+                TransformGestureEvent.GESTURE_ZOOM,
+                GestureEvent.GESTURE_TWO_FINGER_TAP,
+                PressAndTapGestureEvent.GESTURE_PRESS_AND_TAP
+            ]);
+        }
+
+        public static native function get inputMode():String;
+        public static native function set inputMode(value:String):void;
     }
-}
\ No newline at end of file
+}
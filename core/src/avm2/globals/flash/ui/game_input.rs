@@ -0,0 +1,253 @@
+//! `flash.ui.GameInput` support
+
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::object::{ArrayObject, ClassObject, ScriptObject, TObject, VectorObject};
+use crate::avm2::vector::VectorStorage;
+use crate::avm2::{Activation, Avm2, Error, Multiname, Object, Value};
+use crate::context::UpdateContext;
+use crate::events::{GamepadAxis, GamepadButton, PlayerEvent};
+use crate::string::AvmString;
+
+/// The analog sticks, in the order they appear as controls of a device.
+const STICKS: [GamepadAxis; 4] = [
+    GamepadAxis::LeftStickX,
+    GamepadAxis::LeftStickY,
+    GamepadAxis::RightStickX,
+    GamepadAxis::RightStickY,
+];
+
+/// The buttons, in the order they appear as controls of a device, after the
+/// analog sticks.
+const BUTTONS: [GamepadButton; 14] = [
+    GamepadButton::South,
+    GamepadButton::East,
+    GamepadButton::North,
+    GamepadButton::West,
+    GamepadButton::LeftTrigger,
+    GamepadButton::LeftTrigger2,
+    GamepadButton::RightTrigger,
+    GamepadButton::RightTrigger2,
+    GamepadButton::Select,
+    GamepadButton::Start,
+    GamepadButton::DPadUp,
+    GamepadButton::DPadDown,
+    GamepadButton::DPadLeft,
+    GamepadButton::DPadRight,
+];
+
+fn button_control(button: GamepadButton) -> usize {
+    STICKS.len()
+        + BUTTONS
+            .iter()
+            .position(|b| *b == button)
+            .expect("All buttons are controls")
+}
+
+/// The analog triggers share a control with their trigger button.
+fn axis_control(axis: GamepadAxis) -> usize {
+    match axis {
+        GamepadAxis::LeftTrigger => button_control(GamepadButton::LeftTrigger2),
+        GamepadAxis::RightTrigger => button_control(GamepadButton::RightTrigger2),
+        stick => STICKS
+            .iter()
+            .position(|s| *s == stick)
+            .expect("All sticks are controls"),
+    }
+}
+
+/// Implements `GameInput.isSupported`
+pub fn get_is_supported<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.ui.gamepad_input_available().into())
+}
+
+/// Implements `GameInput.createDevices`, which creates the devices of the
+/// gamepads that were connected before `GameInput` was first used.
+pub fn create_devices<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let gamepads = activation.context.input.connected_gamepads().to_vec();
+    let mut devices = Vec::with_capacity(gamepads.len());
+    for gamepad in gamepads {
+        devices.push(create_device(activation, gamepad)?.into());
+    }
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&devices))?.into())
+}
+
+/// Passes a gamepad event on to `flash.ui.GameInput`.
+///
+/// This must be called before the input manager learns about a newly
+/// connected gamepad, as its device would otherwise be created twice.
+pub fn handle_gamepad_event(context: &mut UpdateContext<'_>, event: PlayerEvent) {
+    let mut activation = Activation::from_nothing(context);
+    let result = match event {
+        PlayerEvent::GamepadConnected { gamepad } => add_device(&mut activation, gamepad),
+        PlayerEvent::GamepadDisconnected { gamepad } => remove_device(&mut activation, gamepad),
+        PlayerEvent::GamepadAxisChanged {
+            gamepad,
+            axis,
+            value,
+        } => set_control_value(&mut activation, gamepad, axis_control(axis), value),
+        PlayerEvent::GamepadButtonDown { gamepad, button } => {
+            set_control_value(&mut activation, gamepad, button_control(button), 1.0)
+        }
+        PlayerEvent::GamepadButtonUp { gamepad, button } => {
+            set_control_value(&mut activation, gamepad, button_control(button), 0.0)
+        }
+        _ => Ok(()),
+    };
+
+    if let Err(e) = result {
+        tracing::error!("Encountered AVM2 error when handling gamepad event: {e:?}");
+    }
+}
+
+fn call_game_input<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    name: &'static str,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let game_input = activation.avm2().classes().gameinput;
+    let name = Multiname::new(activation.avm2().namespaces.flash_ui_internal, name);
+    game_input.call_property(&name, args, activation)
+}
+
+/// Broadcasts a `GameInputEvent` to every `GameInput` listening for it.
+fn broadcast_device_event<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    event_type: &'static str,
+    device: Object<'gc>,
+) -> Result<(), Error<'gc>> {
+    let event = activation.avm2().classes().gameinputevent.construct(
+        activation,
+        &[event_type.into(), false.into(), false.into(), device.into()],
+    )?;
+    let game_input = activation.avm2().classes().gameinput;
+    Avm2::broadcast_event(activation.context, event, game_input);
+    Ok(())
+}
+
+fn set_control_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    gamepad: usize,
+    index: usize,
+    value: f64,
+) -> Result<(), Error<'gc>> {
+    call_game_input(
+        activation,
+        "setControlValue",
+        &[gamepad.into(), index.into(), value.into()],
+    )?;
+    Ok(())
+}
+
+/// Creates an instance of a class that can't be constructed from
+/// ActionScript, skipping its constructor.
+fn instantiate<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    class: ClassObject<'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let object = ScriptObject::custom_object(
+        activation.gc(),
+        class.inner_class_definition(),
+        Some(class.prototype()),
+        class.instance_vtable(),
+    );
+    if let Some(superclass) = class.superclass_object() {
+        superclass.call_super_init(object.into(), &[], activation)?;
+    }
+    Ok(object)
+}
+
+fn add_device<'gc>(activation: &mut Activation<'_, 'gc>, gamepad: usize) -> Result<(), Error<'gc>> {
+    // Make sure the devices of already connected gamepads exist first.
+    let game_input = activation.avm2().classes().gameinput;
+    let devices = Multiname::new(activation.avm2().namespaces.flash_ui_internal, "devices");
+    game_input.get_property(&devices, activation)?;
+    let device = create_device(activation, gamepad)?;
+    call_game_input(activation, "addDevice", &[device.into()])?;
+    broadcast_device_event(activation, "deviceAdded", device)
+}
+
+fn remove_device<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    gamepad: usize,
+) -> Result<(), Error<'gc>> {
+    let device = call_game_input(activation, "removeDevice", &[gamepad.into()])?;
+    match device {
+        Value::Object(device) => broadcast_device_event(activation, "deviceRemoved", device),
+        _ => Ok(()),
+    }
+}
+
+fn create_device<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    gamepad: usize,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let namespace = activation.avm2().namespaces.flash_ui_internal;
+    let device_class = activation.avm2().classes().gameinputdevice;
+    let control_class = activation.avm2().classes().gameinputcontrol;
+
+    // The frontend doesn't tell us anything about the gamepad itself.
+    let device = instantiate(activation, device_class)?;
+    let id = AvmString::new_utf8(activation.gc(), format!("gamepad-{gamepad}"));
+    device.set_property(
+        &Multiname::new(namespace, "_gamepad"),
+        gamepad.into(),
+        activation,
+    )?;
+    device.set_property(&Multiname::new(namespace, "_id"), id.into(), activation)?;
+    device.set_property(
+        &Multiname::new(namespace, "_name"),
+        "Gamepad".into(),
+        activation,
+    )?;
+
+    let mut controls = Vec::with_capacity(STICKS.len() + BUTTONS.len());
+    for index in 0..STICKS.len() + BUTTONS.len() {
+        let (id, min_value) = if index < STICKS.len() {
+            (format!("AXIS_{index}"), -1.0)
+        } else {
+            (format!("BUTTON_{index}"), 0.0)
+        };
+        let id = AvmString::new_utf8(activation.gc(), id);
+
+        let control = instantiate(activation, control_class)?;
+        control.set_property(
+            &Multiname::new(namespace, "_device"),
+            device.into(),
+            activation,
+        )?;
+        control.set_property(&Multiname::new(namespace, "_id"), id.into(), activation)?;
+        control.set_property(
+            &Multiname::new(namespace, "_minValue"),
+            min_value.into(),
+            activation,
+        )?;
+        control.set_property(
+            &Multiname::new(namespace, "_maxValue"),
+            1.0.into(),
+            activation,
+        )?;
+        controls.push(control.into());
+    }
+
+    let storage = VectorStorage::from_values(
+        controls,
+        false,
+        Some(control_class.inner_class_definition()),
+    );
+    let controls = VectorObject::from_vector(storage, activation)?;
+    device.set_property(
+        &Multiname::new(namespace, "_controls"),
+        controls.into(),
+        activation,
+    )?;
+
+    Ok(device)
+}
@@ -0,0 +1,43 @@
+//! `flash.ui.Multitouch` builtin
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::make_error_2008;
+use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::events::MultitouchInputMode;
+use crate::string::AvmString;
+
+pub fn get_supports_touch_events<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.ui.touch_input_available().into())
+}
+
+pub fn get_input_mode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let input_mode = activation.context.mouse_data.multitouch_input_mode;
+    Ok(AvmString::new_utf8(activation.gc(), input_mode.as_str()).into())
+}
+
+pub fn set_input_mode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Ok(input_mode) = args
+        .get_string(activation, 0)?
+        .parse::<MultitouchInputMode>()
+    else {
+        return Err(make_error_2008(activation, "inputMode"));
+    };
+
+    activation.context.mouse_data.multitouch_input_mode = input_mode;
+    Ok(Value::Undefined)
+}
@@ -24,6 +24,19 @@ package flash.utils {
 		public native function get position():uint;
 		public native function set position(value:uint):void;
 
+		// Whether this ByteArray is shared by reference with other workers.
+		internal var _shareable:Boolean = false;
+
+		[API("684")]
+		public function get shareable():Boolean {
+			return this._shareable;
+		}
+
+		[API("684")]
+		public function set shareable(value:Boolean):void {
+			this._shareable = value;
+		}
+
 		public function ByteArray() {
 			this.objectEncoding = _defaultObjectEncoding;
 		}
@@ -44,6 +57,11 @@ package flash.utils {
 
 		public native function toString():String;
 
+		[API("684")]
+		public native function atomicCompareAndSwapIntAt(byteIndex:int, expectedValue:int, newValue:int):int;
+		[API("684")]
+		public native function atomicCompareAndSwapLength(expectedLength:int, newLength:int):int;
+
 		public native function readBoolean():Boolean;
 		public native function readByte():int;
 		public native function readBytes(bytes:ByteArray, offset:uint = 0, length:uint = 0):void;
@@ -2,7 +2,7 @@ use std::rc::Rc;
 
 use crate::avm2::activation::Activation;
 use crate::avm2::bytearray::{Endian, ObjectEncoding};
-use crate::avm2::error::make_error_2008;
+use crate::avm2::error::{make_error_2006, make_error_2008};
 pub use crate::avm2::object::byte_array_allocator;
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::parameters::ParametersExt;
@@ -849,3 +849,59 @@ pub fn set_object_encoding<'gc>(
 
     Ok(Value::Undefined)
 }
+
+/// The bytes of a shared `ByteArray` stay locked while its storage is
+/// borrowed, so atomic operations are just regular reads and writes.
+pub fn atomic_compare_and_swap_int_at<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let index = args.get_i32(activation, 0)?;
+    let expected = args.get_i32(activation, 1)?;
+    let new_value = args.get_i32(activation, 2)?;
+
+    if let Some(mut bytearray) = this.as_bytearray_mut() {
+        if index < 0 || index % 4 != 0 {
+            return Err(make_error_2006(activation));
+        }
+        let index = index as usize;
+        let current = bytearray
+            .read_int_at(index)
+            .map_err(|e| e.to_avm(activation))?;
+        if current == expected {
+            let bytes = match bytearray.endian() {
+                Endian::Big => new_value.to_be_bytes(),
+                Endian::Little => new_value.to_le_bytes(),
+            };
+            bytearray
+                .write_at_nongrowing(&bytes, index)
+                .map_err(|e| e.to_avm(activation))?;
+        }
+        return Ok(current.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn atomic_compare_and_swap_length<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let expected = args.get_i32(activation, 0)?;
+    let new_length = args.get_i32(activation, 1)?;
+
+    if let Some(mut bytearray) = this.as_bytearray_mut() {
+        let current = bytearray.len() as i32;
+        if current == expected {
+            if new_length < 0 {
+                return Err(make_error_2006(activation));
+            }
+            bytearray.set_length(new_length as usize);
+        }
+        return Ok(current.into());
+    }
+
+    Ok(Value::Undefined)
+}
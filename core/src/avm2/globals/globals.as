@@ -145,6 +145,8 @@ include "flash/errors/IllegalOperationError.as"
 include "flash/errors/InvalidSWFError.as"
 include "flash/errors/MemoryError.as"
 include "flash/errors/ScriptTimeoutError.as"
+include "flash/errors/SQLError.as"
+include "flash/errors/SQLErrorOperation.as"
 include "flash/errors/StackOverflowError.as"
 
 // Event needs to come before its subclasses
@@ -169,6 +171,7 @@ include "flash/events/DRMLicenseRequestEvent.as"
 include "flash/events/DRMReturnVoucherCompleteEvent.as"
 include "flash/events/DRMReturnVoucherErrorEvent.as"
 include "flash/events/EventPhase.as"
+include "flash/events/FileListEvent.as"
 include "flash/events/FocusEvent.as"
 include "flash/events/FullScreenEvent.as"
 include "flash/events/GameInputEvent.as"
@@ -189,6 +192,8 @@ include "flash/events/SecurityErrorEvent.as"
 include "flash/events/ShaderEvent.as"
 include "flash/events/SoftKeyboardEvent.as"
 include "flash/events/SoftKeyboardTrigger.as"
+include "flash/events/SQLErrorEvent.as"
+include "flash/events/SQLEvent.as"
 include "flash/events/StageVideoAvailabilityEvent.as"
 include "flash/events/StageVideoEvent.as"
 include "flash/events/StatusEvent.as"
@@ -306,6 +311,15 @@ include "flash/net/URLVariables.as"
 include "flash/net/XMLSocket.as"
 
 include "flash/filesystem/File.as" // File extends FileReference
+include "flash/filesystem/FileMode.as"
+include "flash/filesystem/FileStream.as"
+
+include "flash/data/EncryptedLocalStore.as"
+include "flash/data/SQLConnection.as"
+include "flash/data/SQLMode.as"
+include "flash/data/SQLResult.as"
+include "flash/data/SQLStatement.as"
+include "flash/data/SQLTransactionLockType.as"
 
 include "flash/net/drm/AuthenticationMethod.as"
 include "flash/net/drm/LoadVoucherSetting.as"
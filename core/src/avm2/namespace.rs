@@ -350,6 +350,12 @@ pub struct CommonNamespaces<'gc> {
     pub(super) flash_events_internal: Namespace<'gc>,
     pub(super) flash_text_engine_internal: Namespace<'gc>,
     pub(super) flash_net_internal: Namespace<'gc>,
+    pub(super) flash_globalization_internal: Namespace<'gc>,
+    pub(super) flash_printing_internal: Namespace<'gc>,
+    pub(super) flash_sampler_internal: Namespace<'gc>,
+    pub(super) flash_system_internal: Namespace<'gc>,
+    pub(super) flash_concurrent_internal: Namespace<'gc>,
+    pub(super) flash_ui_internal: Namespace<'gc>,
 
     pub(super) __ruffle__: Namespace<'gc>,
 }
@@ -381,6 +387,12 @@ impl<'gc> CommonNamespaces<'gc> {
             flash_events_internal: Namespace::internal("flash.events", context),
             flash_text_engine_internal: Namespace::internal("flash.text.engine", context),
             flash_net_internal: Namespace::internal("flash.net", context),
+            flash_globalization_internal: Namespace::internal("flash.globalization", context),
+            flash_printing_internal: Namespace::internal("flash.printing", context),
+            flash_sampler_internal: Namespace::internal("flash.sampler", context),
+            flash_system_internal: Namespace::internal("flash.system", context),
+            flash_concurrent_internal: Namespace::internal("flash.concurrent", context),
+            flash_ui_internal: Namespace::internal("flash.ui", context),
 
             __ruffle__: Namespace::package("__ruffle__", ApiVersion::AllVersions, context),
         }
@@ -2,7 +2,6 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::array::ArrayStorage;
-use crate::avm2::bytearray::ByteArrayStorage;
 use crate::avm2::class::Class;
 use crate::avm2::domain::Domain;
 use crate::avm2::error;
@@ -21,7 +20,7 @@ use crate::display_object::DisplayObject;
 use crate::html::TextFormat;
 use crate::streams::NetStream;
 use crate::string::{AvmString, StringContext};
-use gc_arena::{Collect, Gc, Mutation};
+use gc_arena::{Collect, Finalization, Gc, Mutation};
 use ruffle_macros::enum_trait_object;
 use std::cell::{Ref, RefMut};
 use std::fmt::Debug;
@@ -73,7 +72,7 @@ pub use crate::avm2::object::bitmapdata_object::{
     bitmap_data_allocator, BitmapDataObject, BitmapDataObjectWeak,
 };
 pub use crate::avm2::object::bytearray_object::{
-    byte_array_allocator, ByteArrayObject, ByteArrayObjectWeak,
+    byte_array_allocator, ByteArrayObject, ByteArrayObjectWeak, ByteArrayRef, ByteArrayRefMut,
 };
 pub use crate::avm2::object::class_object::{ClassObject, ClassObjectWeak};
 pub use crate::avm2::object::context3d_object::{Context3DObject, Context3DObjectWeak};
@@ -1156,11 +1155,11 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     }
 
     /// Unwrap this object as bytearray.
-    fn as_bytearray(&self) -> Option<Ref<ByteArrayStorage>> {
+    fn as_bytearray(&self) -> Option<ByteArrayRef<'_>> {
         None
     }
 
-    fn as_bytearray_mut(&self) -> Option<RefMut<ByteArrayStorage>> {
+    fn as_bytearray_mut(&self) -> Option<ByteArrayRefMut<'_>> {
         None
     }
 
@@ -1530,4 +1529,51 @@ impl<'gc> WeakObject<'gc> {
             Self::SharedObjectObject(o) => SharedObjectObject(o.0.upgrade(mc)?).into(),
         })
     }
+
+    /// Whether the object is about to be collected, during the finalization
+    /// phase of garbage collection.
+    pub fn is_dead(self, fc: &Finalization<'gc>) -> bool {
+        match self {
+            Self::ScriptObject(o) => o.0.is_dead(fc),
+            Self::FunctionObject(o) => o.0.is_dead(fc),
+            Self::PrimitiveObject(o) => o.0.is_dead(fc),
+            Self::NamespaceObject(o) => o.0.is_dead(fc),
+            Self::ArrayObject(o) => o.0.is_dead(fc),
+            Self::StageObject(o) => o.0.is_dead(fc),
+            Self::DomainObject(o) => o.0.is_dead(fc),
+            Self::EventObject(o) => o.0.is_dead(fc),
+            Self::DispatchObject(o) => o.0.is_dead(fc),
+            Self::XmlObject(o) => o.0.is_dead(fc),
+            Self::XmlListObject(o) => o.0.is_dead(fc),
+            Self::RegExpObject(o) => o.0.is_dead(fc),
+            Self::ByteArrayObject(o) => o.0.is_dead(fc),
+            Self::LoaderInfoObject(o) => o.0.is_dead(fc),
+            Self::ClassObject(o) => o.0.is_dead(fc),
+            Self::VectorObject(o) => o.0.is_dead(fc),
+            Self::SoundObject(o) => o.0.is_dead(fc),
+            Self::SoundChannelObject(o) => o.0.is_dead(fc),
+            Self::BitmapDataObject(o) => o.0.is_dead(fc),
+            Self::DateObject(o) => o.0.is_dead(fc),
+            Self::DictionaryObject(o) => o.0.is_dead(fc),
+            Self::QNameObject(o) => o.0.is_dead(fc),
+            Self::TextFormatObject(o) => o.0.is_dead(fc),
+            Self::ProxyObject(o) => o.0.is_dead(fc),
+            Self::ErrorObject(o) => o.0.is_dead(fc),
+            Self::Stage3DObject(o) => o.0.is_dead(fc),
+            Self::Context3DObject(o) => o.0.is_dead(fc),
+            Self::IndexBuffer3DObject(o) => o.0.is_dead(fc),
+            Self::VertexBuffer3DObject(o) => o.0.is_dead(fc),
+            Self::TextureObject(o) => o.0.is_dead(fc),
+            Self::Program3DObject(o) => o.0.is_dead(fc),
+            Self::NetStreamObject(o) => o.0.is_dead(fc),
+            Self::NetConnectionObject(o) => o.0.is_dead(fc),
+            Self::ResponderObject(o) => o.0.is_dead(fc),
+            Self::ShaderDataObject(o) => o.0.is_dead(fc),
+            Self::SocketObject(o) => o.0.is_dead(fc),
+            Self::FileReferenceObject(o) => o.0.is_dead(fc),
+            Self::FontObject(o) => o.0.is_dead(fc),
+            Self::LocalConnectionObject(o) => o.0.is_dead(fc),
+            Self::SharedObjectObject(o) => o.0.is_dead(fc),
+        }
+    }
 }
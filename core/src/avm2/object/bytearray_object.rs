@@ -8,7 +8,9 @@ use crate::avm2::Multiname;
 use crate::character::Character;
 use core::fmt;
 use gc_arena::{Collect, Gc, GcWeak};
-use std::cell::{Ref, RefCell, RefMut};
+use std::cell::{OnceCell, Ref, RefCell, RefMut};
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 
 /// A class instance allocator that allocates ByteArray objects.
 pub fn byte_array_allocator<'gc>(
@@ -45,6 +47,7 @@ pub fn byte_array_allocator<'gc>(
         ByteArrayObjectData {
             base,
             storage: RefCell::new(storage),
+            shared: OnceCell::new(),
         },
     ))
     .into())
@@ -74,6 +77,14 @@ pub struct ByteArrayObjectData<'gc> {
     base: ScriptObjectData<'gc>,
 
     storage: RefCell<ByteArrayStorage>,
+
+    /// The bytes of a shareable `ByteArray` once it has been passed to another
+    /// worker, which are the same for the `ByteArray` of every worker.
+    ///
+    /// They're moved into `storage` whenever it's borrowed, and the mutex
+    /// stays locked until the borrow ends.
+    #[collect(require_static)]
+    shared: OnceCell<Arc<Mutex<Vec<u8>>>>,
 }
 
 const _: () = assert!(std::mem::offset_of!(ByteArrayObjectData, base) == 0);
@@ -85,6 +96,22 @@ impl<'gc> ByteArrayObject<'gc> {
     pub fn from_storage(
         activation: &mut Activation<'_, 'gc>,
         bytes: ByteArrayStorage,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        Self::from_parts(activation, bytes, OnceCell::new())
+    }
+
+    /// Creates a `ByteArray` that uses bytes shared with other workers.
+    pub fn from_shared(
+        activation: &mut Activation<'_, 'gc>,
+        bytes: Arc<Mutex<Vec<u8>>>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        Self::from_parts(activation, ByteArrayStorage::new(), OnceCell::from(bytes))
+    }
+
+    fn from_parts(
+        activation: &mut Activation<'_, 'gc>,
+        bytes: ByteArrayStorage,
+        shared: OnceCell<Arc<Mutex<Vec<u8>>>>,
     ) -> Result<Object<'gc>, Error<'gc>> {
         let class = activation.avm2().classes().bytearray;
         let base = ScriptObjectData::new(class);
@@ -94,6 +121,7 @@ impl<'gc> ByteArrayObject<'gc> {
             ByteArrayObjectData {
                 base,
                 storage: RefCell::new(bytes),
+                shared,
             },
         ))
         .into();
@@ -103,8 +131,96 @@ impl<'gc> ByteArrayObject<'gc> {
         Ok(instance)
     }
 
-    pub fn storage(&self) -> Ref<ByteArrayStorage> {
-        self.0.storage.borrow()
+    pub fn storage(&self) -> ByteArrayRef<'_> {
+        match self.0.shared.get() {
+            Some(shared) => ByteArrayRef::Shared(ByteArrayRefMut::new(
+                self.0.storage.borrow_mut(),
+                Some(shared),
+            )),
+            None => ByteArrayRef::Local(self.0.storage.borrow()),
+        }
+    }
+
+    fn storage_mut(&self) -> ByteArrayRefMut<'_> {
+        ByteArrayRefMut::new(self.0.storage.borrow_mut(), self.0.shared.get())
+    }
+
+    /// Moves the bytes of this `ByteArray` to where other workers can share
+    /// them, unless they're shared already.
+    pub fn share(&self) -> Arc<Mutex<Vec<u8>>> {
+        self.0
+            .shared
+            .get_or_init(|| {
+                let mut bytes = Vec::new();
+                self.0.storage.borrow_mut().swap_bytes(&mut bytes);
+                Arc::new(Mutex::new(bytes))
+            })
+            .clone()
+    }
+}
+
+/// A borrow of the storage of a `ByteArray`.
+pub enum ByteArrayRef<'a> {
+    Local(Ref<'a, ByteArrayStorage>),
+
+    /// Shared bytes have to be moved into the storage to be read, so the
+    /// storage is borrowed mutably.
+    Shared(ByteArrayRefMut<'a>),
+}
+
+impl Deref for ByteArrayRef<'_> {
+    type Target = ByteArrayStorage;
+
+    fn deref(&self) -> &ByteArrayStorage {
+        match self {
+            ByteArrayRef::Local(storage) => storage,
+            ByteArrayRef::Shared(storage) => storage,
+        }
+    }
+}
+
+/// A mutable borrow of the storage of a `ByteArray`.
+///
+/// If the bytes are shared with other workers, they stay in the storage until
+/// this is dropped, and no other worker can use them in the meantime.
+pub struct ByteArrayRefMut<'a> {
+    storage: RefMut<'a, ByteArrayStorage>,
+    shared: Option<MutexGuard<'a, Vec<u8>>>,
+}
+
+impl<'a> ByteArrayRefMut<'a> {
+    fn new(
+        mut storage: RefMut<'a, ByteArrayStorage>,
+        shared: Option<&'a Arc<Mutex<Vec<u8>>>>,
+    ) -> Self {
+        let shared = shared.map(|shared| {
+            let mut bytes = shared.lock().unwrap_or_else(PoisonError::into_inner);
+            storage.swap_bytes(&mut bytes);
+            bytes
+        });
+        Self { storage, shared }
+    }
+}
+
+impl Deref for ByteArrayRefMut<'_> {
+    type Target = ByteArrayStorage;
+
+    fn deref(&self) -> &ByteArrayStorage {
+        &self.storage
+    }
+}
+
+impl DerefMut for ByteArrayRefMut<'_> {
+    fn deref_mut(&mut self) -> &mut ByteArrayStorage {
+        &mut self.storage
+    }
+}
+
+impl Drop for ByteArrayRefMut<'_> {
+    fn drop(&mut self) {
+        if let Some(bytes) = &mut self.shared {
+            self.storage.swap_bytes(bytes);
+        }
     }
 }
 
@@ -140,9 +256,7 @@ impl<'gc> TObject<'gc> for ByteArrayObject<'gc> {
     fn get_index_property(self, index: usize) -> Option<Value<'gc>> {
         // ByteArrays never forward to base even for out-of-bounds access.
         Some(
-            self.0
-                .storage
-                .borrow()
+            self.storage()
                 .get(index)
                 .map_or(Value::Undefined, |val| Value::Integer(val as i32)),
         )
@@ -157,9 +271,7 @@ impl<'gc> TObject<'gc> for ByteArrayObject<'gc> {
         if name.contains_public_namespace() {
             if let Some(name) = name.local_name() {
                 if let Ok(index) = name.parse::<usize>() {
-                    self.0
-                        .storage
-                        .borrow_mut()
+                    self.storage_mut()
                         .set(index, value.coerce_to_u32(activation)? as u8);
 
                     return Ok(());
@@ -179,9 +291,7 @@ impl<'gc> TObject<'gc> for ByteArrayObject<'gc> {
         if name.contains_public_namespace() {
             if let Some(name) = name.local_name() {
                 if let Ok(index) = name.parse::<usize>() {
-                    self.0
-                        .storage
-                        .borrow_mut()
+                    self.storage_mut()
                         .set(index, value.coerce_to_u32(activation)? as u8);
 
                     return Ok(());
@@ -200,7 +310,7 @@ impl<'gc> TObject<'gc> for ByteArrayObject<'gc> {
         if name.contains_public_namespace() {
             if let Some(name) = name.local_name() {
                 if let Ok(index) = name.parse::<usize>() {
-                    self.0.storage.borrow_mut().delete(index);
+                    self.storage_mut().delete(index);
                     return Ok(true);
                 }
             }
@@ -213,7 +323,7 @@ impl<'gc> TObject<'gc> for ByteArrayObject<'gc> {
         if name.contains_public_namespace() {
             if let Some(name) = name.local_name() {
                 if let Ok(index) = name.parse::<usize>() {
-                    return self.0.storage.borrow().get(index).is_some();
+                    return self.storage().get(index).is_some();
                 }
             }
         }
@@ -221,12 +331,12 @@ impl<'gc> TObject<'gc> for ByteArrayObject<'gc> {
         self.base().has_own_property(name)
     }
 
-    fn as_bytearray(&self) -> Option<Ref<ByteArrayStorage>> {
-        Some(self.0.storage.borrow())
+    fn as_bytearray(&self) -> Option<ByteArrayRef<'_>> {
+        Some(self.storage())
     }
 
-    fn as_bytearray_mut(&self) -> Option<RefMut<ByteArrayStorage>> {
-        Some(self.0.storage.borrow_mut())
+    fn as_bytearray_mut(&self) -> Option<ByteArrayRefMut<'_>> {
+        Some(self.storage_mut())
     }
 
     fn as_bytearray_object(&self) -> Option<ByteArrayObject<'gc>> {
@@ -9,6 +9,7 @@ use crate::avm2::object::function_object::FunctionObject;
 use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{Object, ObjectPtr, TObject};
 use crate::avm2::property::Property;
+use crate::avm2::sampler;
 use crate::avm2::scope::{Scope, ScopeChain};
 use crate::avm2::value::Value;
 use crate::avm2::vtable::{ClassBoundMethod, VTable};
@@ -792,6 +793,7 @@ impl<'gc> TObject<'gc> for ClassObject<'gc> {
         let instance_allocator = self.instance_allocator();
 
         let instance = instance_allocator(self, activation)?;
+        sampler::sample_new_object(activation, instance, self);
 
         self.call_init(instance.into(), arguments, activation)?;
 
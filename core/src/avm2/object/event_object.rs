@@ -9,12 +9,13 @@ use crate::avm2::Error;
 use crate::context::UpdateContext;
 use crate::display_object::TDisplayObject;
 use crate::display_object::{DisplayObject, InteractiveObject, TInteractiveObject};
-use crate::events::{KeyCode, MouseButton};
+use crate::events::{Gesture, GesturePhase, KeyCode, MouseButton};
 use crate::string::AvmString;
 use gc_arena::barrier::unlock;
 use gc_arena::{lock::RefLock, Collect, Gc, GcWeak, Mutation};
 use std::cell::{Ref, RefMut};
 use std::fmt::Debug;
+use swf::{Point, Twips};
 
 /// A class instance allocator that allocates Event objects.
 pub fn event_allocator<'gc>(
@@ -344,6 +345,157 @@ impl<'gc> EventObject<'gc> {
             )
             .unwrap()
     }
+
+    pub fn touch_event<S>(
+        activation: &mut Activation<'_, 'gc>,
+        event_type: S,
+        target: DisplayObject<'gc>,
+        touch_point_id: i32,
+        primary: bool,
+        position: Point<Twips>,
+    ) -> Object<'gc>
+    where
+        S: Into<AvmString<'gc>>,
+    {
+        let local = target.global_to_local(position).unwrap_or_default();
+        let event_type: AvmString<'gc> = event_type.into();
+
+        let class = activation.avm2().classes().touchevent;
+        class
+            .construct(
+                activation,
+                &[
+                    event_type.into(),
+                    // bubbles
+                    true.into(),
+                    // cancelable
+                    false.into(),
+                    touch_point_id.into(),
+                    primary.into(),
+                    local.x.to_pixels().into(),
+                    local.y.to_pixels().into(),
+                    // sizeX, sizeY and pressure aren't reported by any frontend
+                    f64::NAN.into(),
+                    f64::NAN.into(),
+                    f64::NAN.into(),
+                    // relatedObject
+                    Value::Null,
+                    activation
+                        .context
+                        .input
+                        .is_key_down(KeyCode::CONTROL)
+                        .into(),
+                    activation.context.input.is_key_down(KeyCode::ALT).into(),
+                    activation.context.input.is_key_down(KeyCode::SHIFT).into(),
+                ],
+            )
+            .unwrap() // we don't expect to break here
+    }
+
+    pub fn transform_gesture_event(
+        activation: &mut Activation<'_, 'gc>,
+        target: DisplayObject<'gc>,
+        gesture: Gesture,
+        phase: GesturePhase,
+        position: Point<Twips>,
+    ) -> Object<'gc> {
+        let local = target.global_to_local(position).unwrap_or_default();
+        let (scale_x, scale_y, rotation, offset_x, offset_y) = gesture.transform();
+        let ctrl_key = activation.context.input.is_key_down(KeyCode::CONTROL);
+
+        let class = activation.avm2().classes().transformgestureevent;
+        class
+            .construct(
+                activation,
+                &[
+                    gesture.event_type().into(),
+                    // bubbles
+                    true.into(),
+                    // cancelable
+                    false.into(),
+                    phase.as_str().into(),
+                    local.x.to_pixels().into(),
+                    local.y.to_pixels().into(),
+                    scale_x.into(),
+                    scale_y.into(),
+                    rotation.into(),
+                    offset_x.into(),
+                    offset_y.into(),
+                    ctrl_key.into(),
+                    activation.context.input.is_key_down(KeyCode::ALT).into(),
+                    activation.context.input.is_key_down(KeyCode::SHIFT).into(),
+                    // controlKey
+                    ctrl_key.into(),
+                ],
+            )
+            .unwrap() // we don't expect to break here
+    }
+
+    pub fn two_finger_tap_gesture_event(
+        activation: &mut Activation<'_, 'gc>,
+        target: DisplayObject<'gc>,
+        position: Point<Twips>,
+    ) -> Object<'gc> {
+        let local = target.global_to_local(position).unwrap_or_default();
+        let ctrl_key = activation.context.input.is_key_down(KeyCode::CONTROL);
+
+        let class = activation.avm2().classes().gestureevent;
+        class
+            .construct(
+                activation,
+                &[
+                    "gestureTwoFingerTap".into(),
+                    // bubbles
+                    true.into(),
+                    // cancelable
+                    false.into(),
+                    GesturePhase::All.as_str().into(),
+                    local.x.to_pixels().into(),
+                    local.y.to_pixels().into(),
+                    ctrl_key.into(),
+                    activation.context.input.is_key_down(KeyCode::ALT).into(),
+                    activation.context.input.is_key_down(KeyCode::SHIFT).into(),
+                    // controlKey
+                    ctrl_key.into(),
+                ],
+            )
+            .unwrap() // we don't expect to break here
+    }
+
+    pub fn press_and_tap_gesture_event(
+        activation: &mut Activation<'_, 'gc>,
+        target: DisplayObject<'gc>,
+        position: Point<Twips>,
+        tap_position: Point<Twips>,
+    ) -> Object<'gc> {
+        let local = target.global_to_local(position).unwrap_or_default();
+        let tap_local = target.global_to_local(tap_position).unwrap_or_default();
+        let ctrl_key = activation.context.input.is_key_down(KeyCode::CONTROL);
+
+        let class = activation.avm2().classes().pressandtapgestureevent;
+        class
+            .construct(
+                activation,
+                &[
+                    "gesturePressAndTap".into(),
+                    // bubbles
+                    true.into(),
+                    // cancelable
+                    false.into(),
+                    GesturePhase::All.as_str().into(),
+                    local.x.to_pixels().into(),
+                    local.y.to_pixels().into(),
+                    tap_local.x.to_pixels().into(),
+                    tap_local.y.to_pixels().into(),
+                    ctrl_key.into(),
+                    activation.context.input.is_key_down(KeyCode::ALT).into(),
+                    activation.context.input.is_key_down(KeyCode::SHIFT).into(),
+                    // controlKey
+                    ctrl_key.into(),
+                ],
+            )
+            .unwrap() // we don't expect to break here
+    }
 }
 
 impl<'gc> TObject<'gc> for EventObject<'gc> {
@@ -1,6 +1,7 @@
 use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
 use crate::avm2::{Activation, Error};
+use crate::backend::filesystem::FilePath;
 use crate::backend::ui::FileDialogResult;
 use gc_arena::GcWeak;
 use gc_arena::{Collect, Gc};
@@ -57,6 +58,12 @@ impl FileReferenceObject<'_> {
             .replace(FileReference::FileDialogResult(result))
     }
 
+    /// Points a `flash.filesystem.File` to a path in the AIR filesystem.
+    pub fn init_from_path(&self, path: FilePath) -> FileReference {
+        self.set_loaded(false);
+        self.0.reference.replace(FileReference::Path(path))
+    }
+
     pub fn file_reference(&self) -> Ref<'_, FileReference> {
         self.0.reference.borrow()
     }
@@ -73,6 +80,9 @@ impl FileReferenceObject<'_> {
 pub enum FileReference {
     None,
     FileDialogResult(Box<dyn FileDialogResult>),
+
+    /// A `flash.filesystem.File`, which is read through the filesystem backend.
+    Path(FilePath),
 }
 
 #[derive(Collect)]
@@ -161,6 +161,10 @@ impl<'gc> FunctionObject<'gc> {
         unlock!(Gc::write(mc, self.0), FunctionObjectData, prototype).set(proto);
     }
 
+    pub fn executable(&self) -> Ref<'_, BoundMethod<'gc>> {
+        self.0.exec.borrow()
+    }
+
     pub fn num_parameters(&self) -> usize {
         self.0.exec.borrow().num_parameters()
     }
@@ -2,7 +2,7 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
-use crate::avm2::dynamic_map::{DynamicKey, DynamicMap};
+use crate::avm2::dynamic_map::{DynamicKey, DynamicMap, DynamicProperty};
 use crate::avm2::error;
 use crate::avm2::object::{ClassObject, FunctionObject, Object, ObjectPtr, TObject};
 use crate::avm2::value::Value;
@@ -308,6 +308,14 @@ impl<'gc> ScriptObjectWrapper<'gc> {
         }
     }
 
+    /// A rough estimate of how much memory this object takes up, in bytes.
+    pub fn size_estimate(&self) -> usize {
+        let num_values = self.values().as_hashmap().len();
+        size_of::<ScriptObjectData>()
+            + self.0.slots.len() * size_of::<Lock<Value>>()
+            + num_values * size_of::<(DynamicKey, DynamicProperty<Value>)>()
+    }
+
     #[inline(always)]
     pub fn get_slot(&self, id: u32) -> Value<'gc> {
         self.0
@@ -5,8 +5,10 @@ use crate::avm2::error::argument_error;
 use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
 use crate::avm2::Error;
+use crate::net_connection::NetConnectionHandle;
 use gc_arena::barrier::unlock;
 use gc_arena::{lock::Lock, Collect, Gc, GcWeak};
+use std::cell::Cell;
 use std::fmt::Debug;
 
 /// SharedObjects cannot be constructed by AS.
@@ -43,6 +45,11 @@ pub struct SharedObjectObjectData<'gc> {
 
     /// The name of this SharedObject.
     name: String,
+
+    /// The connection this SharedObject is synchronized over, if it's a
+    /// remote SharedObject that has been connected.
+    #[collect(require_static)]
+    remote_connection: Cell<Option<NetConnectionHandle>>,
 }
 
 const _: () = assert!(std::mem::offset_of!(SharedObjectObjectData, base) == 0);
@@ -65,6 +72,7 @@ impl<'gc> SharedObjectObject<'gc> {
                 base,
                 data: Lock::new(data),
                 name,
+                remote_connection: Cell::new(None),
             },
         ))
     }
@@ -93,6 +101,14 @@ impl<'gc> SharedObjectObject<'gc> {
     pub fn name(&self) -> &String {
         &self.0.name
     }
+
+    pub fn remote_connection(&self) -> Option<NetConnectionHandle> {
+        self.0.remote_connection.get()
+    }
+
+    pub fn set_remote_connection(&self, connection: Option<NetConnectionHandle>) {
+        self.0.remote_connection.set(connection);
+    }
 }
 
 impl<'gc> TObject<'gc> for SharedObjectObject<'gc> {
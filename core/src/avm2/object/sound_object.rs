@@ -1,12 +1,13 @@
 //! Object representation for sounds
 
 use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::ByteArrayStorage;
 use crate::avm2::object::script_object::ScriptObjectData;
-use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::object::{ByteArrayObject, ClassObject, Object, ObjectPtr, TObject};
 use crate::avm2::Avm2;
 use crate::avm2::Error;
 use crate::avm2::EventObject;
-use crate::backend::audio::{AudioManager, SoundHandle};
+use crate::backend::audio::{AudioManager, SoundHandle, SoundInstanceHandle};
 use crate::context::UpdateContext;
 use crate::display_object::SoundTransform;
 use crate::string::AvmString;
@@ -17,6 +18,7 @@ use gc_arena::{
     Collect, Gc, GcWeak, Mutation,
 };
 use id3::{Tag, TagLike};
+use std::cell::Cell;
 use std::io::Cursor;
 use swf::SoundInfo;
 
@@ -37,6 +39,7 @@ pub fn sound_allocator<'gc>(
                 queued_plays: Vec::new(),
             }),
             id3: Lock::new(None),
+            extract_position: Cell::new(0),
         },
     ))
     .into())
@@ -70,6 +73,10 @@ pub struct SoundObjectData<'gc> {
 
     /// ID3Info Object
     id3: Lock<Option<Object<'gc>>>,
+
+    /// The sample frame that the next `Sound.extract` call without
+    /// an explicit start position will read from.
+    extract_position: Cell<u32>,
 }
 
 const _: () = assert!(std::mem::offset_of!(SoundObjectData, base) == 0);
@@ -114,6 +121,15 @@ impl<'gc> SoundObject<'gc> {
         queued: QueuedPlay<'gc>,
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<bool, Error<'gc>> {
+        // A sound without any audio data of its own that has `sampleData` listeners
+        // plays whatever those listeners generate.
+        if self.sound_handle().is_none() && self.has_sample_data_listener(activation.context) {
+            if let Some(instance) = activation.context.start_dynamic_sound(self) {
+                attach_sound_channel(queued, instance, activation);
+            }
+            return Ok(true);
+        }
+
         let mut sound_data = unlock!(
             Gc::write(activation.context.gc_context, self.0),
             SoundObjectData,
@@ -162,6 +178,68 @@ impl<'gc> SoundObject<'gc> {
         Ok(())
     }
 
+    pub fn extract_position(self) -> u32 {
+        self.0.extract_position.get()
+    }
+
+    pub fn set_extract_position(self, position: u32) {
+        self.0.extract_position.set(position);
+    }
+
+    fn has_sample_data_listener(self, context: &mut UpdateContext<'gc>) -> bool {
+        let event = EventObject::bare_default_event(context, "sampleData");
+        Avm2::simulate_event_dispatch(context, event, self.into())
+    }
+
+    /// Dispatches a `sampleData` event to this sound, requesting the audio data starting at the
+    /// given sample frame.
+    ///
+    /// Returns the stereo samples that the event handlers wrote to the event's `ByteArray`.
+    pub fn request_sample_data(
+        self,
+        context: &mut UpdateContext<'gc>,
+        position: u32,
+    ) -> Vec<[f32; 2]> {
+        let mut activation = Activation::from_nothing(context);
+        let data = match ByteArrayObject::from_storage(&mut activation, ByteArrayStorage::new()) {
+            Ok(data) => data,
+            Err(e) => {
+                tracing::error!("Failed to construct ByteArray for sampleData event: {e:?}");
+                return Vec::new();
+            }
+        };
+        let event = match activation.avm2().classes().sampledataevent.construct(
+            &mut activation,
+            &[
+                "sampleData".into(),
+                false.into(),
+                false.into(),
+                position.into(),
+                data.into(),
+            ],
+        ) {
+            Ok(event) => event,
+            Err(e) => {
+                tracing::error!("Failed to construct sampleData event: {e:?}");
+                return Vec::new();
+            }
+        };
+        Avm2::dispatch_event(activation.context, event, self.into());
+
+        let Some(data) = data.as_bytearray() else {
+            tracing::error!("sampleData event data is not a ByteArray");
+            return Vec::new();
+        };
+        (0..data.len() / 8)
+            .map(|i| {
+                [
+                    data.read_float_at(i * 8).unwrap_or_default(),
+                    data.read_float_at(i * 8 + 4).unwrap_or_default(),
+                ]
+            })
+            .collect()
+    }
+
     pub fn id3(self) -> Option<Object<'gc>> {
         self.0.id3.get()
     }
@@ -265,23 +343,32 @@ fn play_queued<'gc>(
         .context
         .start_sound(sound, &queued.sound_info, None, None)
     {
-        if let Some(sound_transform) = queued.sound_transform {
-            activation
-                .context
-                .set_local_sound_transform(instance, sound_transform);
-        }
-
-        queued
-            .sound_channel
-            .as_sound_channel()
-            .unwrap()
-            .set_sound_instance(activation, instance);
+        attach_sound_channel(queued, instance, activation);
+    }
+    Ok(true)
+}
 
+/// Associates a newly started sound instance with the `SoundChannel` of a play request.
+fn attach_sound_channel<'gc>(
+    queued: QueuedPlay<'gc>,
+    instance: SoundInstanceHandle,
+    activation: &mut Activation<'_, 'gc>,
+) {
+    if let Some(sound_transform) = queued.sound_transform {
         activation
             .context
-            .attach_avm2_sound_channel(instance, queued.sound_channel);
+            .set_local_sound_transform(instance, sound_transform);
     }
-    Ok(true)
+
+    queued
+        .sound_channel
+        .as_sound_channel()
+        .unwrap()
+        .set_sound_instance(activation, instance);
+
+    activation
+        .context
+        .attach_avm2_sound_channel(instance, queued.sound_channel);
 }
 
 impl<'gc> TObject<'gc> for SoundObject<'gc> {
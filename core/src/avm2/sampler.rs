@@ -0,0 +1,375 @@
+//! Allocation and invocation sampling, as exposed by `flash.sampler`.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::call_stack::CallNode;
+use crate::avm2::method::Method;
+use crate::avm2::object::{ClassObject, Object, TObject, WeakObject};
+use crate::avm2::script::Script;
+use crate::avm2::{Error, Value};
+use crate::context::UpdateContext;
+use fnv::FnvHashMap;
+use gc_arena::{Collect, Finalization, Gc, GcCell, Mutation};
+use std::mem::size_of;
+use std::rc::Rc;
+use web_time::Instant;
+
+/// A single recorded sample.
+#[derive(Collect, Clone)]
+#[collect(no_drop)]
+pub enum Sample<'gc> {
+    /// An object was created.
+    NewObject {
+        id: u64,
+
+        /// Microseconds since the player started.
+        time: f64,
+
+        /// The call stack at the time of allocation, innermost call last.
+        ///
+        /// Objects allocated from the same place share their stack.
+        stack: Rc<[CallNode<'gc>]>,
+
+        class: ClassObject<'gc>,
+
+        /// The estimated size of the object when it was created.
+        size: f64,
+    },
+
+    /// An object that was previously sampled as created was collected.
+    DeleteObject { id: u64, time: f64, size: f64 },
+}
+
+/// An object that was created while sampling.
+#[derive(Collect, Clone, Copy)]
+#[collect(no_drop)]
+struct SampledObject<'gc> {
+    object: WeakObject<'gc>,
+
+    /// The estimated size of the object when it was created.
+    size: f64,
+}
+
+/// The state of the `flash.sampler` API.
+///
+/// Samples are only recorded between `startSampling` and `stopSampling`, and
+/// not while paused. Deletions are noticed by the garbage collector, which
+/// calls `Sampler::finalize` before it frees anything.
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct Sampler<'gc> {
+    started: bool,
+
+    paused: bool,
+
+    samples: Vec<Sample<'gc>>,
+
+    next_id: u64,
+
+    /// Every sampled object that hasn't been collected yet, by ID.
+    objects: FnvHashMap<u64, SampledObject<'gc>>,
+
+    /// The call stack of the last sampled object.
+    last_stack: Option<Rc<[CallNode<'gc>]>>,
+
+    /// How many times each method has been called while sampling, keyed by
+    /// the address of the method.
+    invocations: FnvHashMap<usize, (Method<'gc>, u32)>,
+
+    /// The function to call when there are samples waiting.
+    callback: Option<Object<'gc>>,
+
+    sample_internal_allocs: bool,
+}
+
+impl<'gc> Sampler<'gc> {
+    /// Whether samples are being recorded right now.
+    #[inline]
+    pub fn is_sampling(&self) -> bool {
+        self.started && !self.paused
+    }
+
+    /// Whether sampling has been started, even if it's paused right now.
+    pub fn is_started(&self) -> bool {
+        self.started
+    }
+
+    pub fn start(&mut self) {
+        self.started = true;
+        self.paused = false;
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Stop sampling, throwing away everything recorded so far.
+    pub fn stop(&mut self) {
+        self.started = false;
+        self.paused = false;
+        self.samples.clear();
+        self.objects.clear();
+        self.last_stack = None;
+        self.invocations.clear();
+    }
+
+    pub fn clear_samples(&mut self) {
+        self.samples.clear();
+    }
+
+    pub fn samples(&self) -> &[Sample<'gc>] {
+        &self.samples
+    }
+
+    pub fn set_callback(&mut self, callback: Option<Object<'gc>>) {
+        self.callback = callback;
+    }
+
+    pub fn set_sample_internal_allocs(&mut self, sample_internal_allocs: bool) {
+        self.sample_internal_allocs = sample_internal_allocs;
+    }
+
+    /// Count a call to a method.
+    #[inline]
+    pub fn record_invocation(&mut self, method: Method<'gc>) {
+        if self.is_sampling() {
+            self.invocations
+                .entry(method_key(method))
+                .or_insert((method, 0))
+                .1 += 1;
+        }
+    }
+
+    /// How many times a method has been called while sampling.
+    pub fn invocation_count(&self, method: Method<'gc>) -> u32 {
+        self.invocations
+            .get(&method_key(method))
+            .map(|(_, count)| *count)
+            .unwrap_or_default()
+    }
+
+    /// Get a sampled object by ID, if it's still alive.
+    pub fn object(&self, id: u64, mc: &Mutation<'gc>) -> Option<Object<'gc>> {
+        self.objects
+            .get(&id)
+            .and_then(|sampled| sampled.object.upgrade(mc))
+    }
+
+    /// Forget sampled objects that are about to be collected, recording a
+    /// sample for each of them.
+    ///
+    /// This is called during garbage collection, once everything that's
+    /// still reachable has been marked.
+    pub fn finalize(&mut self, fc: &Finalization<'gc>, time: f64) {
+        let mut deleted = Vec::new();
+        self.objects.retain(|id, sampled| {
+            let dead = sampled.object.is_dead(fc);
+            if dead {
+                deleted.push((*id, sampled.size));
+            }
+            !dead
+        });
+        deleted.sort_by_key(|(id, _)| *id);
+
+        if !self.paused {
+            self.samples
+                .extend(deleted.into_iter().map(|(id, size)| Sample::DeleteObject {
+                    id,
+                    time,
+                    size,
+                }));
+        }
+    }
+
+    /// Get a shared copy of the given call stack, reusing the last one if
+    /// nothing has changed since.
+    fn share_stack(&mut self, frames: &[CallNode<'gc>]) -> Rc<[CallNode<'gc>]> {
+        match &self.last_stack {
+            Some(last)
+                if last.len() == frames.len()
+                    && last.iter().zip(frames).all(|(a, b)| same_call(a, b)) =>
+            {
+                last.clone()
+            }
+            _ => {
+                let stack: Rc<[CallNode<'gc>]> = frames.into();
+                self.last_stack = Some(stack.clone());
+                stack
+            }
+        }
+    }
+}
+
+/// Record the creation of an object, if we're sampling.
+pub fn sample_new_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    class: ClassObject<'gc>,
+) {
+    if !activation.avm2().sampler.is_sampling() {
+        return;
+    }
+
+    let call_stack = activation.avm2().call_stack();
+    let time = sample_time(activation.context);
+    let size = estimate_size(object.into());
+
+    let sampler = &mut activation.context.avm2.sampler;
+    let stack = sampler.share_stack(call_stack.borrow().frames());
+    let id = sampler.next_id;
+    sampler.next_id += 1;
+    sampler.objects.insert(
+        id,
+        SampledObject {
+            object: object.downgrade(),
+            size,
+        },
+    );
+    sampler.samples.push(Sample::NewObject {
+        id,
+        time,
+        stack,
+        class,
+        size,
+    });
+}
+
+/// Let the sampler callback know if there are samples waiting.
+///
+/// This is called once per frame.
+pub fn update_sampler<'gc>(context: &mut UpdateContext<'gc>) {
+    let sampler = &context.avm2.sampler;
+    let callback = sampler.callback.filter(|_| !sampler.samples.is_empty());
+    if let Some(callback) = callback {
+        let mut activation = Activation::from_nothing(context);
+        if let Err(e) = callback.call(Value::Null, &[], &mut activation) {
+            tracing::error!("Unhandled error in sampler callback: {e:?}");
+        }
+    }
+}
+
+/// Microseconds since the player started.
+pub fn sample_time(context: &UpdateContext<'_>) -> f64 {
+    micros_since(context.start_time)
+}
+
+/// Microseconds since `start_time`, as used for sample times.
+pub fn micros_since(start_time: Instant) -> f64 {
+    Instant::now().duration_since(start_time).as_micros() as f64
+}
+
+fn method_key(method: Method<'_>) -> usize {
+    match method {
+        Method::Native(method) => Gc::as_ptr(method) as usize,
+        Method::Bytecode(method) => Gc::as_ptr(method) as usize,
+    }
+}
+
+fn same_call<'gc>(a: &CallNode<'gc>, b: &CallNode<'gc>) -> bool {
+    match (a, b) {
+        (CallNode::GlobalInit(Script(a)), CallNode::GlobalInit(Script(b))) => {
+            GcCell::ptr_eq(*a, *b)
+        }
+        (
+            CallNode::Method {
+                method: a,
+                class: a_class,
+            },
+            CallNode::Method {
+                method: b,
+                class: b_class,
+            },
+        ) => method_key(*a) == method_key(*b) && a_class == b_class,
+        _ => false,
+    }
+}
+
+/// Estimate how much memory a value takes up, in bytes.
+///
+/// We don't have a way to measure the real size of objects, so this only
+/// counts the object itself, its properties and any large buffers it owns.
+pub fn estimate_size(value: Value<'_>) -> f64 {
+    let size = match value {
+        Value::Undefined | Value::Null => 0,
+        Value::Bool(_) | Value::Integer(_) => 4,
+        Value::Number(_) => 8,
+        Value::String(string) => {
+            let char_size = if string.is_wide() { 2 } else { 1 };
+            4 * size_of::<usize>() + string.len() * char_size
+        }
+        Value::Object(object) => {
+            let base = object.base();
+            let mut size = base.size_estimate();
+
+            if let Some(bytearray) = object.as_bytearray() {
+                size += bytearray.len();
+            }
+            if let Some(array) = object.as_array_storage() {
+                size += array.length() * size_of::<Value>();
+            }
+            if let Some(vector) = object.as_vector_storage() {
+                size += vector.length() * size_of::<Value>();
+            }
+            if let Some(bitmap_data) = object.as_bitmap_data() {
+                size += bitmap_data.width() as usize * bitmap_data.height() as usize * 4;
+            }
+
+            size
+        }
+    };
+
+    size as f64
+}
+
+/// Look up the method behind a property of an object.
+///
+/// If `object` is a class, then instance methods are looked up first.
+pub fn find_method<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    name: Option<&crate::avm2::Multiname<'gc>>,
+    accessor: Accessor,
+) -> Result<Option<Method<'gc>>, Error<'gc>> {
+    use crate::avm2::property::Property;
+
+    let Some(name) = name else {
+        // No name on a class means its constructor.
+        return Ok(object
+            .as_class_object()
+            .map(|class| class.inner_class_definition().instance_init()));
+    };
+
+    let mut vtables = Vec::new();
+    if let Some(class) = object.as_class_object() {
+        vtables.push(class.instance_vtable());
+    }
+    vtables.push(object.vtable());
+
+    for vtable in vtables {
+        let disp_id = match (vtable.get_trait(name), accessor) {
+            (Some(Property::Method { disp_id }), Accessor::Method) => disp_id,
+            (Some(Property::Virtual { get: Some(get), .. }), Accessor::Getter) => get,
+            (Some(Property::Virtual { set: Some(set), .. }), Accessor::Setter) => set,
+            (Some(_), _) => return Ok(None),
+            (None, _) => continue,
+        };
+        return Ok(vtable.get_method(disp_id));
+    }
+
+    // Functions stored in dynamic properties count too.
+    if accessor == Accessor::Method && object.base().has_own_dynamic_property(name) {
+        let value = object.get_property(name, activation)?;
+        if let Some(function) = value.as_object().and_then(|o| o.as_function_object()) {
+            return Ok(Some(function.executable().as_method()));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Which part of a property to look up with `find_method`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Accessor {
+    Method,
+    Getter,
+    Setter,
+}
@@ -1,5 +1,8 @@
 pub mod audio;
+pub mod debugger;
+pub mod filesystem;
 pub mod log;
 pub mod navigator;
+pub mod print;
 pub mod storage;
 pub mod ui;
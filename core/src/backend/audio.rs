@@ -1,6 +1,6 @@
 use crate::{
     avm1::{NativeObject, Object as Avm1Object, TObject as _},
-    avm2::{Avm2, EventObject as Avm2EventObject, SoundChannelObject},
+    avm2::{Avm2, EventObject as Avm2EventObject, SoundChannelObject, SoundObject},
     buffer::Substream,
     context::UpdateContext,
     display_object::{self, DisplayObject, MovieClip, TDisplayObject},
@@ -64,6 +64,10 @@ pub struct SoundStreamInfo {
     pub stream_format: swf::SoundFormat,
     pub num_samples_per_block: u16,
     pub latency_seek: i16,
+
+    /// Out-of-band decoder configuration, such as the AAC
+    /// `AudioSpecificConfig` from an FLV sequence header.
+    pub codec_config: Option<Vec<u8>>,
 }
 
 impl From<swf::SoundStreamHead> for SoundStreamInfo {
@@ -73,6 +77,7 @@ impl From<swf::SoundStreamHead> for SoundStreamInfo {
             stream_format: swfhead.stream_format,
             num_samples_per_block: swfhead.num_samples_per_block,
             latency_seek: swfhead.latency_seek,
+            codec_config: None,
         }
     }
 }
@@ -130,6 +135,40 @@ pub trait AudioBackend: Downcast {
         stream_info: &SoundStreamInfo,
     ) -> Result<SoundInstanceHandle, DecodeError>;
 
+    /// Starts playing a "dynamic" sound, whose audio data is generated by
+    /// ActionScript in response to `SampleDataEvent`s.
+    ///
+    /// The sound plays silence until samples are provided with
+    /// `append_dynamic_sound_samples`.
+    fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, DecodeError>;
+
+    /// Queues stereo 44.1KHz samples to be played by a dynamic sound instance.
+    ///
+    /// If `is_final` is `true`, the sound ends once these samples have been played.
+    /// No-op if the sound is not playing.
+    fn append_dynamic_sound_samples(
+        &mut self,
+        instance: SoundInstanceHandle,
+        samples: &[[f32; 2]],
+        is_final: bool,
+    );
+
+    /// Get the number of sample frames that a dynamic sound instance has buffered,
+    /// but not played yet.
+    /// Returns `None` if the sound is not/no longer playing.
+    fn dynamic_sound_buffered_frames(&self, instance: SoundInstanceHandle) -> Option<u32>;
+
+    /// Decodes up to `num_sample_frames` stereo 44.1KHz samples of a sound,
+    /// starting at `start_sample_frame`. This is used by `Sound.extract`.
+    ///
+    /// Returns `None` if the sound is not registered or cannot be decoded.
+    fn extract_sound_data(
+        &mut self,
+        sound: SoundHandle,
+        start_sample_frame: u32,
+        num_sample_frames: u32,
+    ) -> Option<Vec<[f32; 2]>>;
+
     /// Stops a playing sound instance.
     /// No-op if the sound is not playing.
     fn stop_sound(&mut self, sound: SoundInstanceHandle);
@@ -207,9 +246,26 @@ struct NullSound {
     format: swf::SoundFormat,
 }
 
+/// A dynamic sound played by `NullAudioBackend`.
+///
+/// The samples themselves are thrown away, but we keep count of them so that
+/// the sound asks for more data at the same rate as it would when audible.
+struct NullDynamicSound {
+    /// The number of sample frames that were provided, but not played yet.
+    queued_frames: u32,
+
+    /// The number of sample frames played so far.
+    played_frames: u32,
+
+    /// Whether the final samples of this sound have been provided.
+    is_final: bool,
+}
+
 /// Audio backend that ignores all audio.
 pub struct NullAudioBackend {
     sounds: SlotMap<SoundHandle, NullSound>,
+    dynamic_sounds: SlotMap<SoundInstanceHandle, NullDynamicSound>,
+    frame_rate: f64,
     volume: f32,
 }
 
@@ -217,6 +273,8 @@ impl NullAudioBackend {
     pub fn new() -> NullAudioBackend {
         NullAudioBackend {
             sounds: SlotMap::with_key(),
+            dynamic_sounds: SlotMap::with_key(),
+            frame_rate: 24.0,
             volume: 1.0,
         }
     }
@@ -282,11 +340,61 @@ impl AudioBackend for NullAudioBackend {
         Ok(SoundInstanceHandle::null())
     }
 
-    fn stop_sound(&mut self, _sound: SoundInstanceHandle) {}
+    fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, DecodeError> {
+        Ok(self.dynamic_sounds.insert(NullDynamicSound {
+            queued_frames: 0,
+            played_frames: 0,
+            is_final: false,
+        }))
+    }
 
-    fn stop_all_sounds(&mut self) {}
-    fn get_sound_position(&self, _instance: SoundInstanceHandle) -> Option<f64> {
-        Some(0.0)
+    fn append_dynamic_sound_samples(
+        &mut self,
+        instance: SoundInstanceHandle,
+        samples: &[[f32; 2]],
+        is_final: bool,
+    ) {
+        if let Some(sound) = self.dynamic_sounds.get_mut(instance) {
+            sound.queued_frames += samples.len() as u32;
+            sound.is_final |= is_final;
+        }
+    }
+
+    fn dynamic_sound_buffered_frames(&self, instance: SoundInstanceHandle) -> Option<u32> {
+        self.dynamic_sounds
+            .get(instance)
+            .map(|sound| sound.queued_frames)
+    }
+
+    fn extract_sound_data(
+        &mut self,
+        sound: SoundHandle,
+        start_sample_frame: u32,
+        num_sample_frames: u32,
+    ) -> Option<Vec<[f32; 2]>> {
+        // We don't decode anything, so pretend that the sound is silent.
+        let sound = self.sounds.get(sound)?;
+        let total_sample_frames = (sound.duration * 44.1) as u32;
+        let num_sample_frames =
+            num_sample_frames.min(total_sample_frames.saturating_sub(start_sample_frame));
+        Some(vec![[0.0; 2]; num_sample_frames as usize])
+    }
+
+    fn stop_sound(&mut self, sound: SoundInstanceHandle) {
+        self.dynamic_sounds.remove(sound);
+    }
+
+    fn stop_all_sounds(&mut self) {
+        self.dynamic_sounds.clear();
+    }
+    fn get_sound_position(&self, instance: SoundInstanceHandle) -> Option<f64> {
+        if instance.is_null() {
+            return Some(0.0);
+        }
+        // Only dynamic sounds get a handle of their own.
+        self.dynamic_sounds
+            .get(instance)
+            .map(|sound| f64::from(sound.played_frames) / 44.1)
     }
     fn get_sound_duration(&self, sound: SoundHandle) -> Option<f64> {
         if let Some(sound) = self.sounds.get(sound) {
@@ -313,6 +421,21 @@ impl AudioBackend for NullAudioBackend {
         None
     }
 
+    fn tick(&mut self) {
+        // Pretend that a frame's worth of each dynamic sound has been played.
+        let frames_per_tick = (44100.0 / self.frame_rate) as u32;
+        self.dynamic_sounds.retain(|_, sound| {
+            let played = sound.queued_frames.min(frames_per_tick);
+            sound.queued_frames -= played;
+            sound.played_frames += played;
+            !(sound.is_final && sound.queued_frames == 0)
+        });
+    }
+
+    fn set_frame_rate(&mut self, frame_rate: f64) {
+        self.frame_rate = frame_rate;
+    }
+
     fn volume(&self) -> f32 {
         self.volume
     }
@@ -368,6 +491,14 @@ impl<'gc> AudioManager<'gc> {
     /// The player will adjust animation speed to stay within this many seconds of the audio track.
     pub const STREAM_DEFAULT_SYNC_THRESHOLD: f64 = 0.2;
 
+    /// The minimum number of sample frames that a dynamic sound tries to keep buffered.
+    ///
+    /// See `dynamic_sound_buffer_frames` for the actual amount at a given frame rate.
+    pub const DYNAMIC_SOUND_BUFFER_FRAMES: u32 = 4096;
+
+    /// A dynamic sound ends when a `sampleData` event provides fewer sample frames than this.
+    pub const DYNAMIC_SOUND_MIN_FRAMES: usize = 2048;
+
     pub fn new() -> Self {
         Self {
             sounds: Vec::with_capacity(Self::MAX_SOUNDS),
@@ -434,10 +565,84 @@ impl<'gc> AudioManager<'gc> {
             Avm2::dispatch_event(context, event, target.into());
         }
 
+        Self::update_dynamic_sounds(context);
+
         // Update sound transforms, if dirty.
         context.audio_manager.update_sound_transforms(context.audio);
     }
 
+    /// Requests more audio data from the `sampleData` handlers of dynamic sounds
+    /// that are running low on buffered samples.
+    fn update_dynamic_sounds(context: &mut UpdateContext<'gc>) {
+        let dynamic_sounds: Vec<_> = context
+            .audio_manager
+            .sounds
+            .iter()
+            .filter_map(|sound| {
+                let dynamic_sound = sound.dynamic_sound.as_ref()?;
+                (!dynamic_sound.is_finished).then_some((sound.instance, dynamic_sound.object))
+            })
+            .collect();
+
+        let buffer_frames = Self::dynamic_sound_buffer_frames(*context.frame_rate);
+        for (instance, object) in dynamic_sounds {
+            // Keep asking for more data until the backend has enough to play.
+            while context
+                .audio
+                .dynamic_sound_buffered_frames(instance)
+                .is_some_and(|buffered| buffered < buffer_frames)
+            {
+                // The sound may have been stopped by an event handler.
+                let Some(position) = context
+                    .audio_manager
+                    .dynamic_sound_mut(instance)
+                    .map(|dynamic_sound| dynamic_sound.position)
+                else {
+                    break;
+                };
+
+                let samples = object.request_sample_data(context, position);
+                let is_final = samples.len() < Self::DYNAMIC_SOUND_MIN_FRAMES;
+                context
+                    .audio
+                    .append_dynamic_sound_samples(instance, &samples, is_final);
+                if let Some(dynamic_sound) = context.audio_manager.dynamic_sound_mut(instance) {
+                    dynamic_sound.position += samples.len() as u32;
+                    dynamic_sound.is_finished = is_final;
+                }
+                if is_final {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// The number of sample frames that a dynamic sound tries to keep buffered.
+    ///
+    /// `sampleData` events are dispatched until this many sample frames are waiting to be played.
+    /// Dynamic sounds are only refilled once per frame, so this covers two frames worth of
+    /// audio, to keep the sound from running dry at low frame rates.
+    fn dynamic_sound_buffer_frames(frame_rate: f64) -> u32 {
+        // Don't buffer more than a second per frame, even if the frame rate is tiny or invalid.
+        let samples_per_frame = if frame_rate >= 1.0 {
+            (44100.0 / frame_rate).ceil() as u32
+        } else {
+            44100
+        };
+        (samples_per_frame * 2).max(Self::DYNAMIC_SOUND_BUFFER_FRAMES)
+    }
+
+    fn dynamic_sound_mut(
+        &mut self,
+        instance: SoundInstanceHandle,
+    ) -> Option<&mut DynamicSound<'gc>> {
+        self.sounds
+            .iter_mut()
+            .find(|other| other.instance == instance)?
+            .dynamic_sound
+            .as_mut()
+    }
+
     /// Starts a sound and optionally associates it with a Display Object.
     /// Sounds associated with DOs are an AVM1/Timeline concept and should not be called from AVM2 scripts.
     pub fn start_sound(
@@ -458,6 +663,38 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object,
                 avm2_object: None,
                 stream_start_frame: None,
+                dynamic_sound: None,
+            };
+            audio.set_sound_transform(handle, self.transform_for_sound(&instance));
+            self.sounds.push(instance);
+            Some(handle)
+        } else {
+            None
+        }
+    }
+
+    /// Starts a dynamic sound, whose audio data is provided by the
+    /// `sampleData` handlers of the given AVM2 `Sound`.
+    pub fn start_dynamic_sound(
+        &mut self,
+        audio: &mut dyn AudioBackend,
+        object: SoundObject<'gc>,
+    ) -> Option<SoundInstanceHandle> {
+        if self.sounds.len() < Self::MAX_SOUNDS {
+            let handle = audio.start_dynamic_sound().ok()?;
+            let instance = SoundInstance {
+                sound: None,
+                instance: handle,
+                display_object: None,
+                transform: display_object::SoundTransform::default(),
+                avm1_object: None,
+                avm2_object: None,
+                stream_start_frame: None,
+                dynamic_sound: Some(DynamicSound {
+                    object,
+                    position: 0,
+                    is_finished: false,
+                }),
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -563,6 +800,7 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object: None,
                 avm2_object: None,
                 stream_start_frame: Some(clip_frame),
+                dynamic_sound: None,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -589,6 +827,7 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object: None,
                 avm2_object: None,
                 stream_start_frame: None,
+                dynamic_sound: None,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -804,6 +1043,22 @@ pub struct SoundInstance<'gc> {
     avm2_object: Option<SoundChannelObject<'gc>>,
 
     stream_start_frame: Option<u16>,
+
+    /// The state of this sound if it's a dynamic sound, generated by `sampleData` handlers.
+    dynamic_sound: Option<DynamicSound<'gc>>,
+}
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+struct DynamicSound<'gc> {
+    /// The AVM2 `Sound` object that `sampleData` events are dispatched to.
+    object: SoundObject<'gc>,
+
+    /// The position in sample frames of the next audio data to request.
+    position: u32,
+
+    /// Whether the `sampleData` handlers have provided the final samples of this sound.
+    is_finished: bool,
 }
 
 /// A sound transform for a playing sound, for use by audio backends.
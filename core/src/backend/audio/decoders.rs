@@ -1,20 +1,30 @@
 //! Audio decoders.
 
+#[cfg(feature = "aac")]
+mod aac;
 mod adpcm;
 #[cfg(feature = "mp3")]
 mod mp3;
 #[cfg(feature = "nellymoser")]
 mod nellymoser;
 mod pcm;
+#[cfg(feature = "speex")]
+mod speex;
 
+#[cfg(feature = "aac")]
+pub use aac::{AacDecoder, AudioSpecificConfig};
 pub use adpcm::AdpcmDecoder;
 #[cfg(feature = "mp3")]
 pub use mp3::{mp3_metadata, Mp3Decoder};
 #[cfg(feature = "nellymoser")]
 pub use nellymoser::NellymoserDecoder;
 pub use pcm::PcmDecoder;
+#[cfg(feature = "speex")]
+pub use speex::SpeexDecoder;
 
 use crate::backend::audio::{SoundStreamInfo, SoundStreamWrapping};
+#[cfg(feature = "speex")]
+use crate::buffer::Buffer;
 use crate::buffer::{Slice, SliceCursor, Substream, SubstreamChunksIter};
 use crate::tag_utils::{ControlFlow, SwfSlice};
 use std::io::{Cursor, Read};
@@ -27,6 +37,18 @@ pub enum Error {
     #[error("Couldn't decode MP3: {0}")]
     InvalidMp3(#[from] mp3::Error),
 
+    #[cfg(feature = "aac")]
+    #[error("Couldn't decode AAC: {0}")]
+    InvalidAac(#[from] aac::Error),
+
+    #[cfg(feature = "aac")]
+    #[error("AAC stream is missing its AudioSpecificConfig")]
+    MissingAacConfig,
+
+    #[cfg(feature = "speex")]
+    #[error("Couldn't read Speex data: {0}")]
+    InvalidSpeex(#[from] std::io::Error),
+
     #[error("Couldn't decode ADPCM: {0}")]
     InvalidAdpcm(#[from] adpcm::Error),
 
@@ -80,6 +102,16 @@ pub fn make_decoder<R: 'static + Read + Send + Sync>(
         AudioCompression::Nellymoser => {
             Box::new(NellymoserDecoder::new(data, format.sample_rate.into()))
         }
+        #[cfg(feature = "speex")]
+        AudioCompression::Speex => {
+            // Event sounds are a single packet containing all of the frames,
+            // each padded to a whole byte.
+            let mut data = data;
+            let mut packet = Vec::new();
+            data.read_to_end(&mut packet)?;
+            let packet = Buffer::from(packet).to_full_slice();
+            Box::new(SpeexDecoder::with_aligned_frames(std::iter::once(packet)))
+        }
         _ => return Err(Error::UnhandledCompression(format.compression)),
     };
     Ok(decoder)
@@ -496,12 +528,26 @@ pub fn make_substream_decoder(
     stream_info: &SoundStreamInfo,
     data_stream: Substream,
 ) -> Result<Box<dyn Decoder + Send>, Error> {
-    let decoder: Box<dyn Decoder + Send> =
-        if stream_info.stream_format.compression == AudioCompression::Adpcm {
-            Box::new(AdpcmSubstreamDecoder::new(stream_info, data_stream)?)
-        } else {
-            Box::new(StandardSubstreamDecoder::new(stream_info, data_stream)?)
-        };
+    let decoder: Box<dyn Decoder + Send> = match stream_info.stream_format.compression {
+        AudioCompression::Adpcm => Box::new(AdpcmSubstreamDecoder::new(stream_info, data_stream)?),
+        #[cfg(feature = "aac")]
+        AudioCompression::Aac => {
+            // AAC frames can't be split across reads, so hand each chunk of
+            // the substream to the decoder as a whole frame.
+            let config = stream_info
+                .codec_config
+                .as_deref()
+                .ok_or(Error::MissingAacConfig)?;
+            Box::new(AacDecoder::new(config, data_stream.iter_chunks())?)
+        }
+        #[cfg(feature = "speex")]
+        AudioCompression::Speex => {
+            // Speex frames aren't byte-aligned, so each chunk of the
+            // substream is decoded as a separate packet.
+            Box::new(SpeexDecoder::new(data_stream.iter_chunks()))
+        }
+        _ => Box::new(StandardSubstreamDecoder::new(stream_info, data_stream)?),
+    };
     Ok(decoder)
 }
 
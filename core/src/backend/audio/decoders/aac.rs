@@ -0,0 +1,225 @@
+use crate::backend::audio::decoders::Decoder;
+use crate::buffer::Slice;
+use symphonia::core::{audio, codecs, errors, formats};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Couldn't decode AAC frame: {0}")]
+    FrameDecode(#[from] errors::Error),
+
+    #[error("Invalid AudioSpecificConfig")]
+    InvalidConfig,
+
+    #[error("Invalid sample rate")]
+    InvalidSampleRate,
+
+    #[error("Invalid channels")]
+    InvalidChannels,
+}
+
+/// Sample rates indexed by the `samplingFrequencyIndex` of an
+/// `AudioSpecificConfig` (ISO/IEC 14496-3 1.6.3.4).
+const SAMPLE_RATES: [u32; 13] = [
+    96000, 88200, 64000, 48000, 44100, 32000, 24000, 22050, 16000, 12000, 11025, 8000, 7350,
+];
+
+/// The parts of an MPEG-4 `AudioSpecificConfig` that we care about.
+///
+/// FLV and F4V files carry this in the AAC sequence header; it tells us the
+/// real sample rate and channel count, which the FLV audio tag flags don't.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AudioSpecificConfig {
+    pub object_type: u8,
+    pub sample_rate: u32,
+    pub num_channels: u8,
+}
+
+impl AudioSpecificConfig {
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
+        let mut bits = BitReader { data, pos: 0 };
+
+        let mut object_type = bits.read(5)? as u8;
+        if object_type == 31 {
+            object_type = 32 + bits.read(6)? as u8;
+        }
+
+        let sample_rate = match bits.read(4)? {
+            15 => bits.read(24)?,
+            index => *SAMPLE_RATES
+                .get(index as usize)
+                .ok_or(Error::InvalidSampleRate)?,
+        };
+
+        let num_channels = match bits.read(4)? {
+            channels @ 1..=2 => channels as u8,
+            _ => return Err(Error::InvalidChannels),
+        };
+
+        Ok(Self {
+            object_type,
+            sample_rate,
+            num_channels,
+        })
+    }
+}
+
+/// Minimal MSB-first bit reader for parsing the `AudioSpecificConfig`.
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl BitReader<'_> {
+    fn read(&mut self, num_bits: u32) -> Result<u32, Error> {
+        let mut value = 0;
+        for _ in 0..num_bits {
+            let byte = self.data.get(self.pos / 8).ok_or(Error::InvalidConfig)?;
+            let bit = (byte >> (7 - self.pos % 8)) & 1;
+            value = (value << 1) | u32::from(bit);
+            self.pos += 1;
+        }
+        Ok(value)
+    }
+}
+
+/// Decoder for AAC-LC audio.
+///
+/// AAC doesn't have a self-synchronizing bitstream like MP3, so instead of a
+/// `Read` this takes an iterator that yields one raw AAC frame at a time
+/// (e.g. the payloads of `AacRaw` FLV audio tags).
+pub struct AacDecoder<I> {
+    packets: I,
+    decoder: Box<dyn codecs::Decoder>,
+    sample_buf: audio::SampleBuffer<i16>,
+    cur_sample: usize,
+    sample_rate: u16,
+    num_channels: u8,
+}
+
+impl<I: Iterator<Item = Slice> + Send + Sync> AacDecoder<I> {
+    // AAC-LC frames contain 1024 samples.
+    const SAMPLE_BUFFER_DURATION: u64 = 1024;
+
+    /// Creates a new decoder from the `AudioSpecificConfig` found in the AAC
+    /// sequence header.
+    pub fn new(config: &[u8], packets: I) -> Result<Self, Error> {
+        let asc = AudioSpecificConfig::parse(config)?;
+        let channels = match asc.num_channels {
+            1 => audio::Channels::FRONT_CENTRE,
+            _ => audio::Channels::FRONT_LEFT | audio::Channels::FRONT_RIGHT,
+        };
+
+        let mut codec_params = codecs::CodecParameters::new();
+        codec_params
+            .for_codec(codecs::CODEC_TYPE_AAC)
+            .with_sample_rate(asc.sample_rate)
+            .with_channels(channels)
+            .with_extra_data(config.into());
+        let decoder = symphonia::default::get_codecs().make(&codec_params, &Default::default())?;
+
+        Ok(Self {
+            packets,
+            decoder,
+            sample_buf: audio::SampleBuffer::new(
+                Self::SAMPLE_BUFFER_DURATION,
+                audio::SignalSpec::new(asc.sample_rate, channels),
+            ),
+            cur_sample: 0,
+            sample_rate: asc
+                .sample_rate
+                .try_into()
+                .map_err(|_| Error::InvalidSampleRate)?,
+            num_channels: asc.num_channels,
+        })
+    }
+
+    /// Decodes the next available AAC frame into the sample buffer.
+    /// Returns `false` if there are no more frames right now.
+    fn next_frame(&mut self) -> bool {
+        self.cur_sample = 0;
+        for data in self.packets.by_ref() {
+            let packet = formats::Packet::new_from_slice(0, 0, 0, &data.data());
+            match self.decoder.decode(&packet) {
+                Ok(decoded) => {
+                    if self.sample_buf.capacity() < decoded.capacity() {
+                        // Ensure our buffer has enough space for the decoded samples.
+                        self.sample_buf =
+                            audio::SampleBuffer::new(decoded.capacity() as u64, *decoded.spec());
+                    }
+                    self.sample_buf.copy_interleaved_ref(decoded);
+                    return true;
+                }
+                // Decode errors are not fatal.
+                Err(errors::Error::DecodeError(_)) => (),
+                Err(_) => break,
+            }
+        }
+        self.sample_buf.clear();
+        false
+    }
+}
+
+impl<I: Iterator<Item = Slice> + Send + Sync> Iterator for AacDecoder<I> {
+    type Item = [i16; 2];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.cur_sample >= self.sample_buf.len() {
+            if !self.next_frame() {
+                return None;
+            }
+        }
+
+        let sample_buf = self.sample_buf.samples();
+        if self.num_channels == 2 {
+            let samples: [i16; 2] = [sample_buf[self.cur_sample], sample_buf[self.cur_sample + 1]];
+            self.cur_sample += 2;
+            Some(samples)
+        } else {
+            let sample = sample_buf[self.cur_sample];
+            self.cur_sample += 1;
+            Some([sample, sample])
+        }
+    }
+}
+
+impl<I: Iterator<Item = Slice> + Send + Sync> Decoder for AacDecoder<I> {
+    #[inline]
+    fn num_channels(&self) -> u8 {
+        self.num_channels
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> u16 {
+        self.sample_rate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_audio_specific_config() {
+        // AAC-LC, 44.1kHz, stereo.
+        assert_eq!(
+            AudioSpecificConfig::parse(&[0x12, 0x10]).unwrap(),
+            AudioSpecificConfig {
+                object_type: 2,
+                sample_rate: 44100,
+                num_channels: 2,
+            }
+        );
+        // AAC-LC, 22.05kHz, mono.
+        assert_eq!(
+            AudioSpecificConfig::parse(&[0x13, 0x88]).unwrap(),
+            AudioSpecificConfig {
+                object_type: 2,
+                sample_rate: 22050,
+                num_channels: 1,
+            }
+        );
+        assert!(AudioSpecificConfig::parse(&[0x12]).is_err());
+    }
+}
@@ -0,0 +1,277 @@
+//! Speex decoder.
+//!
+//! This is a port of the floating-point decoder of libspeex 1.2, which is
+//! Copyright 2002-2008 Xiph.org Foundation and Jean-Marc Valin, and is
+//! distributed under the BSD 3-clause license. See `speex/COPYING` for the
+//! full copyright notice and license text.
+//!
+//! Flash only ever uses Speex in wideband mode (16kHz), so that's the only
+//! mode that's exposed. Narrowband frames are still understood, as they are
+//! the lower layer of every wideband frame.
+
+mod filters;
+mod narrowband;
+mod tables;
+mod wideband;
+
+use crate::backend::audio::decoders::Decoder;
+use crate::buffer::Slice;
+use wideband::WidebandDecoder;
+
+/// The number of samples in a wideband frame.
+const FRAME_SIZE: usize = 320;
+
+/// Why a frame couldn't be decoded.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum DecodeError {
+    /// There are no more frames in this packet.
+    EndOfPacket,
+
+    /// The packet is corrupt.
+    Corrupt,
+}
+
+/// A bit reader over a single Speex packet.
+///
+/// Reading past the end of the packet isn't an error; it returns zeros and
+/// marks the reader as overflowed, which is how libspeex behaves.
+struct Bits<'a> {
+    data: &'a [u8],
+    pos: usize,
+    overflow: bool,
+}
+
+impl<'a> Bits<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            pos: 0,
+            overflow: false,
+        }
+    }
+
+    /// The number of bits left in the packet, or `None` if we've read past
+    /// its end.
+    fn remaining(&self) -> Option<usize> {
+        if self.overflow {
+            None
+        } else {
+            Some(self.data.len() * 8 - self.pos)
+        }
+    }
+
+    fn has_remaining(&self, num_bits: usize) -> bool {
+        self.remaining()
+            .is_some_and(|remaining| remaining >= num_bits)
+    }
+
+    fn read(&mut self, num_bits: usize) -> u32 {
+        if !self.has_remaining(num_bits) {
+            self.overflow = true;
+            return 0;
+        }
+        let mut value = 0;
+        for _ in 0..num_bits {
+            let bit = (self.data[self.pos / 8] >> (7 - self.pos % 8)) & 1;
+            value = (value << 1) | u32::from(bit);
+            self.pos += 1;
+        }
+        value
+    }
+
+    fn peek(&mut self) -> u32 {
+        if !self.has_remaining(1) {
+            self.overflow = true;
+            return 0;
+        }
+        u32::from((self.data[self.pos / 8] >> (7 - self.pos % 8)) & 1)
+    }
+
+    fn advance(&mut self, num_bits: usize) {
+        if self.has_remaining(num_bits) {
+            self.pos += num_bits;
+        } else {
+            self.overflow = true;
+        }
+    }
+}
+
+/// Decoder for Speex audio.
+///
+/// Speex frames aren't byte-aligned, so this takes an iterator that yields
+/// one packet at a time (e.g. the payloads of Speex FLV audio tags). Each
+/// packet may contain several frames.
+pub struct SpeexDecoder<I> {
+    packets: I,
+    decoder: Box<WidebandDecoder>,
+
+    /// The packet that is being decoded, and the bit position of its next frame.
+    packet: Option<(Slice, usize)>,
+
+    /// Whether each frame of a packet starts on a byte boundary.
+    aligned_frames: bool,
+
+    samples: [i16; FRAME_SIZE],
+    cur_sample: usize,
+}
+
+impl<I: Iterator<Item = Slice> + Send + Sync> SpeexDecoder<I> {
+    pub fn new(packets: I) -> Self {
+        Self {
+            packets,
+            decoder: Box::default(),
+            packet: None,
+            aligned_frames: false,
+            samples: [0; FRAME_SIZE],
+            cur_sample: FRAME_SIZE,
+        }
+    }
+
+    /// Creates a decoder for packets whose frames each start on a byte boundary.
+    ///
+    /// This is how Flash stores event sounds: the `DefineSound` payload is a
+    /// sequence of frames that were encoded one at a time, so each frame is
+    /// padded to a whole byte with a terminator.
+    pub fn with_aligned_frames(packets: I) -> Self {
+        Self {
+            aligned_frames: true,
+            ..Self::new(packets)
+        }
+    }
+
+    /// Decodes the next available frame into the sample buffer.
+    /// Returns `false` if there are no more frames right now.
+    fn next_frame(&mut self) -> bool {
+        loop {
+            let (packet, pos) = match self.packet.take() {
+                Some(packet) => packet,
+                None => match self.packets.next() {
+                    Some(packet) => (packet, 0),
+                    None => return false,
+                },
+            };
+
+            let data = packet.data();
+            let mut bits = Bits::new(&data);
+            bits.pos = pos;
+            let mut frame = [0.0; FRAME_SIZE];
+            match self.decoder.decode(&mut bits, &mut frame) {
+                Ok(()) => {
+                    for (sample, &decoded) in self.samples.iter_mut().zip(frame.iter()) {
+                        // Round to the nearest sample, like `speex_decode_int`.
+                        *sample = (decoded + 0.5).floor().clamp(-32768.0, 32767.0) as i16;
+                    }
+                    self.cur_sample = 0;
+                    let mut pos = bits.pos;
+                    if self.aligned_frames {
+                        // Skip the terminator that pads the frame to a whole byte.
+                        pos = pos.next_multiple_of(8);
+                    }
+                    drop(data);
+                    self.packet = Some((packet, pos));
+                    return true;
+                }
+                Err(DecodeError::EndOfPacket) => (),
+                Err(DecodeError::Corrupt) => tracing::warn!("Corrupt Speex packet"),
+            }
+        }
+    }
+}
+
+impl<I: Iterator<Item = Slice> + Send + Sync> Iterator for SpeexDecoder<I> {
+    type Item = [i16; 2];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cur_sample >= self.samples.len() && !self.next_frame() {
+            return None;
+        }
+
+        let sample = self.samples[self.cur_sample];
+        self.cur_sample += 1;
+        Some([sample, sample])
+    }
+}
+
+impl<I: Iterator<Item = Slice> + Send + Sync> Decoder for SpeexDecoder<I> {
+    #[inline]
+    fn num_channels(&self) -> u8 {
+        1
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> u16 {
+        16000
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::audio::decoders::make_decoder;
+    use crate::buffer::Buffer;
+    use std::io::Cursor;
+    use swf::{AudioCompression, SoundFormat};
+
+    /// Wideband packets of a synthetic signal encoded at every quality level,
+    /// each prefixed with its length as a little-endian `u16`.
+    /// Each packet holds one frame.
+    const PACKETS: &[u8] = include_bytes!("speex/test_packets.bin");
+
+    /// The output of libspeex's floating-point decoder for `PACKETS`, as
+    /// little-endian `i16` samples.
+    const REFERENCE: &[u8] = include_bytes!("speex/test_reference.pcm");
+
+    fn packets() -> Vec<&'static [u8]> {
+        let mut packets = vec![];
+        let mut data = PACKETS;
+        while let [lo, hi, rest @ ..] = data {
+            let len = usize::from(u16::from_le_bytes([*lo, *hi]));
+            packets.push(&rest[..len]);
+            data = &rest[len..];
+        }
+        packets
+    }
+
+    fn assert_matches_reference(decoded: impl Iterator<Item = [i16; 2]>) {
+        let decoded: Vec<i16> = decoded.map(|[left, _]| left).collect();
+        let expected: Vec<i16> = REFERENCE
+            .chunks_exact(2)
+            .map(|sample| i16::from_le_bytes([sample[0], sample[1]]))
+            .collect();
+
+        assert_eq!(decoded.len(), expected.len());
+        for (i, (decoded, expected)) in decoded.iter().zip(&expected).enumerate() {
+            // Allow for rounding differences in the floating-point math.
+            assert!(
+                decoded.abs_diff(*expected) <= 2,
+                "sample {i}: decoded {decoded}, expected {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn decode_matches_libspeex() {
+        let packets = packets()
+            .into_iter()
+            .map(|packet| Buffer::from(packet.to_vec()).to_full_slice());
+        let decoder = SpeexDecoder::new(packets);
+        assert_eq!(decoder.num_channels(), 1);
+        assert_eq!(decoder.sample_rate(), 16000);
+        assert_matches_reference(decoder);
+    }
+
+    #[test]
+    fn decode_event_sound() {
+        // An event sound stores all of its frames back to back, each padded to a whole byte.
+        let data = packets().concat();
+        let format = SoundFormat {
+            compression: AudioCompression::Speex,
+            sample_rate: 16000,
+            is_stereo: false,
+            is_16_bit: true,
+        };
+        let decoder = make_decoder(&format, Cursor::new(data)).expect("Speex is supported");
+        assert_matches_reference(decoder);
+    }
+}
@@ -0,0 +1,371 @@
+//! Signal processing helpers shared by the narrowband and wideband decoders.
+
+use std::f32::consts::{FRAC_PI_2, PI};
+
+/// Generates a pseudo-random number with the given standard deviation.
+pub fn speex_rand(std: f32, seed: &mut u32) -> f32 {
+    *seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+    let ran = f32::from_bits(0x3f800000 | (*seed & 0x007fffff)) - 1.5;
+    3.4642 * std * ran
+}
+
+/// A polynomial approximation of `cos` that matches libspeex.
+fn spx_cos(x: f32) -> f32 {
+    const C1: f32 = 0.999_993_3;
+    const C2: f32 = -0.499_912_44;
+    const C3: f32 = 0.041_487_746;
+    const C4: f32 = -0.0012712095;
+
+    if x < FRAC_PI_2 {
+        let x = x * x;
+        C1 + x * (C2 + x * (C3 + C4 * x))
+    } else {
+        let x = PI - x;
+        let x = x * x;
+        -(C1 + x * (C2 + x * (C3 + C4 * x)))
+    }
+}
+
+pub fn compute_rms(x: &[f32]) -> f32 {
+    let sum: f32 = x.iter().map(|x| x * x).sum();
+    (0.1 + sum / x.len() as f32).sqrt()
+}
+
+pub fn inner_prod(x: &[f32], y: &[f32]) -> f32 {
+    // Sum in blocks of four, like libspeex does.
+    x.chunks_exact(4)
+        .zip(y.chunks_exact(4))
+        .map(|(x, y)| x[0] * y[0] + x[1] * y[1] + x[2] * y[2] + x[3] * y[3])
+        .sum()
+}
+
+/// Applies bandwidth expansion to an LPC filter.
+pub fn bw_lpc(gamma: f32, lpc_in: &[f32], lpc_out: &mut [f32]) {
+    let mut tmp = gamma;
+    for (out, lpc) in lpc_out.iter_mut().zip(lpc_in) {
+        *out = tmp * lpc;
+        tmp *= gamma;
+    }
+}
+
+/// Clamps values to the given range, replacing NaNs with zero.
+pub fn sanitize_values(values: &mut [f32], min: f32, max: f32) {
+    for value in values {
+        if value.is_nan() {
+            *value = 0.0;
+        } else {
+            *value = value.clamp(min, max);
+        }
+    }
+}
+
+/// Which of libspeex's output high-pass filters to use.
+#[derive(Clone, Copy)]
+pub enum Highpass {
+    Narrowband,
+    Wideband,
+}
+
+pub fn highpass(buf: &mut [f32], filter: Highpass, mem: &mut [f32; 2]) {
+    let (den, num) = match filter {
+        Highpass::Narrowband => ([1.00000, -1.92683, 0.93071], [0.96446, -1.92879, 0.96446]),
+        Highpass::Wideband => ([1.00000, -1.97226, 0.97332], [0.98645, -1.97277, 0.98645]),
+    };
+
+    for x in buf {
+        let vout = num[0] * *x + mem[0];
+        mem[0] = mem[1] + num[1] * *x + -den[1] * vout;
+        mem[1] = num[2] * *x + -den[2] * vout;
+        *x = vout;
+    }
+}
+
+/// Filters `x` through the all-pole filter `1/A(z)`, writing the result to `y`.
+pub fn iir_mem(x: &[f32], den: &[f32], y: &mut [f32], mem: &mut [f32]) {
+    let order = den.len();
+    for (x, y) in x.iter().zip(y) {
+        let yi = x + mem[0];
+        for j in 0..order - 1 {
+            mem[j] = mem[j + 1] - den[j] * yi;
+        }
+        mem[order - 1] = -den[order - 1] * yi;
+        *y = yi;
+    }
+}
+
+/// Like `iir_mem`, but filters the buffer in place.
+pub fn iir_mem_in_place(buf: &mut [f32], den: &[f32], mem: &mut [f32]) {
+    let order = den.len();
+    for x in buf {
+        let yi = *x + mem[0];
+        for j in 0..order - 1 {
+            mem[j] = mem[j + 1] - den[j] * yi;
+        }
+        mem[order - 1] = -den[order - 1] * yi;
+        *x = yi;
+    }
+}
+
+/// Interpolates between the LSPs of the previous and current frame for the
+/// given subframe, keeping them at least `margin` apart so the filter stays
+/// stable.
+pub fn lsp_interpolate(
+    old_lsp: &[f32],
+    new_lsp: &[f32],
+    lsp: &mut [f32],
+    subframe: usize,
+    num_subframes: usize,
+    margin: f32,
+) {
+    let len = lsp.len();
+    let tmp = (1.0 + subframe as f32) / num_subframes as f32;
+    for i in 0..len {
+        lsp[i] = (1.0 - tmp) * old_lsp[i] + tmp * new_lsp[i];
+    }
+
+    if lsp[0] < margin {
+        lsp[0] = margin;
+    }
+    if lsp[len - 1] > PI - margin {
+        lsp[len - 1] = PI - margin;
+    }
+    for i in 1..len - 1 {
+        if lsp[i] < lsp[i - 1] + margin {
+            lsp[i] = lsp[i - 1] + margin;
+        }
+        if lsp[i] > lsp[i + 1] - margin {
+            lsp[i] = 0.5 * (lsp[i] + lsp[i + 1] - margin);
+        }
+    }
+}
+
+/// Converts line spectral pairs to LPC coefficients.
+pub fn lsp_to_lpc(freq: &[f32], ak: &mut [f32]) {
+    let order = freq.len();
+    let m = order / 2;
+    let mut wp = [0.0f32; 4 * 5 + 2];
+    let mut x_freq = [0.0f32; 10];
+    for (x, freq) in x_freq.iter_mut().zip(freq) {
+        *x = spx_cos(*freq);
+    }
+
+    let mut xin1 = 1.0;
+    let mut xin2 = 1.0;
+    for j in 0..=order {
+        for i in 0..m {
+            let n = i * 4;
+            let xout1 = xin1 - 2.0 * x_freq[2 * i] * wp[n] + wp[n + 1];
+            let xout2 = xin2 - 2.0 * x_freq[2 * i + 1] * wp[n + 2] + wp[n + 3];
+            wp[n + 1] = wp[n];
+            wp[n + 3] = wp[n + 2];
+            wp[n] = xin1;
+            wp[n + 2] = xin2;
+            xin1 = xout1;
+            xin2 = xout2;
+        }
+        let xout1 = xin1 + wp[4 * m];
+        let xout2 = xin2 - wp[4 * m + 1];
+        if j > 0 {
+            ak[j - 1] = (xout1 + xout2) * 0.5;
+        }
+        wp[4 * m] = xin1;
+        wp[4 * m + 1] = xin2;
+
+        xin1 = 0.0;
+        xin2 = 0.0;
+    }
+}
+
+/// The quadrature mirror filter used to combine the low and high bands.
+pub const QMF_ORDER: usize = 64;
+
+/// Recombines the low band `x1` and high band `x2` into the full band `y`.
+pub fn qmf_synth(
+    x1: &[f32],
+    x2: &[f32],
+    a: &[f32; QMF_ORDER],
+    y: &mut [f32],
+    mem1: &mut [f32; QMF_ORDER],
+    mem2: &mut [f32; QMF_ORDER],
+) {
+    const M2: usize = QMF_ORDER / 2;
+    let n2 = y.len() / 2;
+    let mut xx1 = vec![0.0f32; M2 + n2];
+    let mut xx2 = vec![0.0f32; M2 + n2];
+
+    for i in 0..n2 {
+        xx1[i] = x1[n2 - 1 - i];
+        xx2[i] = x2[n2 - 1 - i];
+    }
+    for i in 0..M2 {
+        xx1[n2 + i] = mem1[2 * i + 1];
+        xx2[n2 + i] = mem2[2 * i + 1];
+    }
+
+    for i in (0..n2).step_by(2) {
+        let (mut y0, mut y1, mut y2, mut y3) = (0.0, 0.0, 0.0, 0.0);
+        let mut x10 = xx1[n2 - 2 - i];
+        let mut x20 = xx2[n2 - 2 - i];
+
+        for j in (0..M2).step_by(2) {
+            let a0 = a[2 * j];
+            let a1 = a[2 * j + 1];
+            let x11 = xx1[n2 - 1 + j - i];
+            let x21 = xx2[n2 - 1 + j - i];
+
+            y0 += a0 * (x11 - x21);
+            y1 += a1 * (x11 + x21);
+            y2 += a0 * (x10 - x20);
+            y3 += a1 * (x10 + x20);
+
+            let a0 = a[2 * j + 2];
+            let a1 = a[2 * j + 3];
+            x10 = xx1[n2 + j - i];
+            x20 = xx2[n2 + j - i];
+
+            y0 += a0 * (x10 - x20);
+            y1 += a1 * (x10 + x20);
+            y2 += a0 * (x11 - x21);
+            y3 += a1 * (x11 + x21);
+        }
+
+        y[2 * i] = 2.0 * y0;
+        y[2 * i + 1] = 2.0 * y1;
+        y[2 * i + 2] = 2.0 * y2;
+        y[2 * i + 3] = 2.0 * y3;
+    }
+
+    for i in 0..M2 {
+        mem1[2 * i + 1] = xx1[i];
+        mem2[2 * i + 1] = xx2[i];
+    }
+}
+
+const SHIFT_FILT: [[f32; 7]; 3] = [
+    [
+        -0.011915, 0.046995, -0.152373, 0.614108, 0.614108, -0.152373, 0.046995,
+    ],
+    [
+        -0.0324855, 0.0859768, -0.2042986, 0.9640297, 0.208_642, -0.0302054, -0.0063646,
+    ],
+    [
+        -0.0063646, -0.0302054, 0.208_642, 0.9640297, -0.2042986, 0.0859768, -0.0324855,
+    ],
+];
+
+/// Finds the fractional pitch delay around `pitch` that best matches the
+/// excitation starting at `exc[start]`, and writes the delayed excitation to
+/// `interp`.
+fn interp_pitch(exc: &[f32], start: usize, interp: &mut [f32], pitch: isize) {
+    let len = interp.len();
+    let at = |offset: isize| exc[start.wrapping_add_signed(offset)];
+
+    let mut corr = [[0.0f32; 7]; 4];
+    for (i, corr) in corr[0].iter_mut().enumerate() {
+        let other = start.wrapping_add_signed(i as isize - pitch - 3);
+        *corr = inner_prod(&exc[start..start + len], &exc[other..other + len]);
+    }
+    for i in 0..3 {
+        for j in 0..7 {
+            let i1 = 3usize.saturating_sub(j);
+            let i2 = (10 - j).min(7);
+            let mut tmp = 0.0;
+            for k in i1..i2 {
+                tmp += SHIFT_FILT[i][k] * corr[0][j + k - 3];
+            }
+            corr[i + 1][j] = tmp;
+        }
+    }
+
+    let (mut maxi, mut maxj) = (0, 0);
+    let mut maxcorr = corr[0][0];
+    for (i, row) in corr.iter().enumerate() {
+        for (j, &value) in row.iter().enumerate() {
+            if value > maxcorr {
+                maxcorr = value;
+                maxi = i;
+                maxj = j;
+            }
+        }
+    }
+
+    let delay = pitch - maxj as isize + 3;
+    for (i, out) in interp.iter_mut().enumerate() {
+        let i = i as isize;
+        *out = if maxi > 0 {
+            (0..7)
+                .map(|k| at(i - delay + k - 3) * SHIFT_FILT[maxi - 1][k as usize])
+                .sum()
+        } else {
+            at(i - delay)
+        };
+    }
+}
+
+/// The perceptual enhancer: a comb filter that emphasizes the pitch
+/// harmonics of the excitation starting at `exc[start]`.
+pub fn multicomb(
+    exc: &[f32],
+    start: usize,
+    new_exc: &mut [f32],
+    pitch: usize,
+    max_pitch: usize,
+    comb_gain: f32,
+) {
+    let nsf = new_exc.len();
+    let exc_sub = &exc[start..start + nsf];
+    let mut iexc = [0.0f32; 160];
+    let (iexc0, iexc1) = iexc[..2 * nsf].split_at_mut(nsf);
+
+    let corr_pitch = pitch as isize;
+    interp_pitch(exc, start, iexc0, corr_pitch);
+    if pitch > max_pitch {
+        interp_pitch(exc, start, iexc1, 2 * corr_pitch);
+    } else {
+        interp_pitch(exc, start, iexc1, -corr_pitch);
+    }
+
+    let iexc0_mag = (1000.0 + inner_prod(iexc0, iexc0)).sqrt();
+    let iexc1_mag = (1000.0 + inner_prod(iexc1, iexc1)).sqrt();
+    let exc_mag = (1.0 + inner_prod(exc_sub, exc_sub)).sqrt();
+    let corr0 = inner_prod(iexc0, exc_sub).max(0.0);
+    let corr1 = inner_prod(iexc1, exc_sub).max(0.0);
+
+    let pgain1 = if corr0 > iexc0_mag * exc_mag {
+        1.0
+    } else {
+        corr0 / exc_mag / iexc0_mag
+    };
+    let pgain2 = if corr1 > iexc1_mag * exc_mag {
+        1.0
+    } else {
+        corr1 / exc_mag / iexc1_mag
+    };
+    let gg1 = exc_mag / iexc0_mag;
+    let gg2 = exc_mag / iexc1_mag;
+
+    let (c1, c2) = if comb_gain > 0.0 {
+        let c1 = 0.4 * comb_gain + 0.07;
+        (c1, 0.5 + 1.72 * (c1 - 0.07))
+    } else {
+        (0.0, 0.0)
+    };
+    let g1 = c1 / (1.0 - c2 * pgain1 * pgain1).max(c1);
+    let g2 = c1 / (1.0 - c2 * pgain2 * pgain2).max(c1);
+    let (gain0, gain1) = if pitch > max_pitch {
+        (0.7 * g1 * gg1, 0.3 * g2 * gg2)
+    } else {
+        (0.6 * g1 * gg1, 0.6 * g2 * gg2)
+    };
+
+    for i in 0..nsf {
+        new_exc[i] = exc_sub[i] + (gain0 * iexc0[i] + gain1 * iexc1[i]);
+    }
+
+    let new_ener = compute_rms(new_exc).max(1.0);
+    let old_ener = compute_rms(exc_sub).max(1.0).min(new_ener);
+    let ngain = old_ener / new_ener;
+    for x in new_exc {
+        *x *= ngain;
+    }
+}
@@ -0,0 +1,640 @@
+//! The narrowband (8kHz) CELP decoder, which also decodes the low band of
+//! wideband frames.
+
+use super::filters::{
+    bw_lpc, compute_rms, highpass, iir_mem, iir_mem_in_place, lsp_interpolate, lsp_to_lpc,
+    multicomb, sanitize_values, speex_rand, Highpass,
+};
+use super::tables::*;
+use super::{Bits, DecodeError};
+
+const ORDER: usize = 10;
+pub const FRAME_SIZE: usize = 160;
+pub const SUBFRAME_SIZE: usize = 40;
+pub const NUM_SUBFRAMES: usize = 4;
+const PITCH_START: usize = 17;
+const PITCH_END: usize = 144;
+
+/// The excitation buffer holds the current frame, plus enough history for the
+/// longest pitch period (twice, for the enhancer).
+const EXC_BUF_SIZE: usize = FRAME_SIZE + 2 * PITCH_END + SUBFRAME_SIZE + 12;
+/// Where the current frame starts in the excitation buffer.
+const EXC_OFFSET: usize = 2 * PITCH_END + SUBFRAME_SIZE + 6;
+
+const LSP_MARGIN: f32 = 0.002;
+
+const EXC_GAIN_QUANT_SCAL3: [f32; 8] = [
+    0.061130, 0.163546, 0.310413, 0.428220, 0.555887, 0.719055, 0.938694, 1.326874,
+];
+const EXC_GAIN_QUANT_SCAL1: [f32; 2] = [0.70469, 1.05127];
+
+/// The number of bits in each wideband submode, so that a narrowband decoder
+/// can skip over the high band.
+const WB_SKIP_TABLE: [usize; 8] = [0, 36, 112, 192, 352, 0, 0, 0];
+
+/// How line spectral pairs are quantized.
+#[derive(Clone, Copy)]
+pub enum LspQuant {
+    Nb,
+    Lbr,
+    High,
+}
+
+impl LspQuant {
+    pub fn unquant(self, lsp: &mut [f32], bits: &mut Bits) {
+        fn add(lsp: &mut [f32], cdbk: &[i8], id: u32, scale: f32) {
+            let len = lsp.len();
+            let entry = &cdbk[id as usize * len..][..len];
+            for (lsp, &value) in lsp.iter_mut().zip(entry) {
+                *lsp += scale * f32::from(value);
+            }
+        }
+
+        match self {
+            LspQuant::Nb | LspQuant::Lbr => {
+                for (i, lsp) in lsp.iter_mut().enumerate() {
+                    *lsp = 0.25 * i as f32 + 0.25;
+                }
+                add(lsp, &CDBK_NB, bits.read(6), 0.0039062);
+                if matches!(self, LspQuant::Nb) {
+                    add(&mut lsp[..5], &CDBK_NB_LOW1, bits.read(6), 0.0019531);
+                    add(&mut lsp[..5], &CDBK_NB_LOW2, bits.read(6), 0.00097656);
+                    add(&mut lsp[5..], &CDBK_NB_HIGH1, bits.read(6), 0.0019531);
+                    add(&mut lsp[5..], &CDBK_NB_HIGH2, bits.read(6), 0.00097656);
+                } else {
+                    add(&mut lsp[..5], &CDBK_NB_LOW1, bits.read(6), 0.0019531);
+                    add(&mut lsp[5..], &CDBK_NB_HIGH1, bits.read(6), 0.0019531);
+                }
+            }
+            LspQuant::High => {
+                for (i, lsp) in lsp.iter_mut().enumerate() {
+                    *lsp = 0.3125 * i as f32 + 0.75;
+                }
+                add(lsp, &HIGH_LSP_CDBK, bits.read(6), 0.0039062);
+                add(lsp, &HIGH_LSP_CDBK2, bits.read(6), 0.0019531);
+            }
+        }
+    }
+}
+
+/// Parameters of the 3-tap long-term (pitch) predictor.
+struct LtpParams {
+    gain_cdbk: &'static [i8],
+    gain_bits: usize,
+    pitch_bits: usize,
+}
+
+/// How the adaptive codebook (pitch) contribution is coded.
+enum Ltp {
+    /// A single pitch and gain for the whole frame.
+    Forced,
+    ThreeTap(LtpParams),
+}
+
+/// Parameters of a split vector quantization codebook.
+pub struct SplitCbParams {
+    subvect_size: usize,
+    num_subvect: usize,
+    shape_cb: &'static [i8],
+    shape_bits: usize,
+    have_sign: bool,
+}
+
+/// How the fixed codebook (innovation) contribution is coded.
+pub enum Innovation {
+    /// Random noise, for the comfort noise mode.
+    Noise,
+    SplitCb(SplitCbParams),
+}
+
+impl Innovation {
+    /// Adds the decoded innovation to `exc`.
+    pub fn unquant(&self, exc: &mut [f32], bits: &mut Bits, seed: &mut u32) {
+        match self {
+            Innovation::Noise => {
+                for x in exc {
+                    *x = speex_rand(1.0, seed);
+                }
+            }
+            Innovation::SplitCb(params) => {
+                let mut signs = [false; 8];
+                let mut indices = [0; 8];
+                for i in 0..params.num_subvect {
+                    signs[i] = params.have_sign && bits.read(1) != 0;
+                    indices[i] = bits.read(params.shape_bits) as usize;
+                }
+                for i in 0..params.num_subvect {
+                    let sign = if signs[i] { -1.0 } else { 1.0 };
+                    let size = params.subvect_size;
+                    let shape = &params.shape_cb[indices[i] * size..][..size];
+                    for (x, &value) in exc[size * i..][..size].iter_mut().zip(shape) {
+                        *x += sign * 0.03125 * f32::from(value);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The parameters of a bit-rate mode.
+struct Submode {
+    /// `None` for "normal" modes, otherwise the pitch is coded once per
+    /// frame and may vary by this much in each subframe.
+    lbr_pitch: Option<usize>,
+    forced_pitch_gain: bool,
+    /// The number of bits used for the subframe innovation gain.
+    have_subframe_gain: u8,
+    double_codebook: bool,
+    lsp: LspQuant,
+    ltp: Option<Ltp>,
+    innovation: Option<Innovation>,
+    /// The gain of the enhancer comb filter.
+    comb_gain: f32,
+}
+
+const LTP_PARAMS_NB: LtpParams = LtpParams {
+    gain_cdbk: &GAIN_CDBK_NB,
+    gain_bits: 7,
+    pitch_bits: 7,
+};
+
+const LTP_PARAMS_VLBR: LtpParams = LtpParams {
+    gain_cdbk: &GAIN_CDBK_LBR,
+    gain_bits: 5,
+    pitch_bits: 0,
+};
+
+const LTP_PARAMS_LBR: LtpParams = LtpParams {
+    gain_cdbk: &GAIN_CDBK_LBR,
+    gain_bits: 5,
+    pitch_bits: 7,
+};
+
+pub const fn split_cb(
+    subvect_size: usize,
+    num_subvect: usize,
+    shape_cb: &'static [i8],
+    shape_bits: usize,
+    have_sign: bool,
+) -> Option<Innovation> {
+    Some(Innovation::SplitCb(SplitCbParams {
+        subvect_size,
+        num_subvect,
+        shape_cb,
+        shape_bits,
+        have_sign,
+    }))
+}
+
+/// The narrowband submodes, indexed by the 4-bit mode number.
+static SUBMODES: [Option<Submode>; 9] = [
+    None,
+    // 2150 bps "vocoder-like" mode for comfort noise.
+    Some(Submode {
+        lbr_pitch: Some(0),
+        forced_pitch_gain: true,
+        have_subframe_gain: 0,
+        double_codebook: false,
+        lsp: LspQuant::Lbr,
+        ltp: Some(Ltp::Forced),
+        innovation: Some(Innovation::Noise),
+        comb_gain: -1.0,
+    }),
+    // 5.95 kbps very low bit-rate mode.
+    Some(Submode {
+        lbr_pitch: Some(0),
+        forced_pitch_gain: false,
+        have_subframe_gain: 0,
+        double_codebook: false,
+        lsp: LspQuant::Lbr,
+        ltp: Some(Ltp::ThreeTap(LTP_PARAMS_VLBR)),
+        innovation: split_cb(10, 4, &EXC_10_16, 4, false),
+        comb_gain: 0.6,
+    }),
+    // 8 kbps low bit-rate mode.
+    Some(Submode {
+        lbr_pitch: None,
+        forced_pitch_gain: false,
+        have_subframe_gain: 1,
+        double_codebook: false,
+        lsp: LspQuant::Lbr,
+        ltp: Some(Ltp::ThreeTap(LTP_PARAMS_LBR)),
+        innovation: split_cb(10, 4, &EXC_10_32, 5, false),
+        comb_gain: 0.55,
+    }),
+    // 11 kbps medium bit-rate mode.
+    Some(Submode {
+        lbr_pitch: None,
+        forced_pitch_gain: false,
+        have_subframe_gain: 1,
+        double_codebook: false,
+        lsp: LspQuant::Lbr,
+        ltp: Some(Ltp::ThreeTap(LTP_PARAMS_LBR)),
+        innovation: split_cb(8, 5, &EXC_8_128, 7, false),
+        comb_gain: 0.45,
+    }),
+    // 15 kbps high bit-rate mode.
+    Some(Submode {
+        lbr_pitch: None,
+        forced_pitch_gain: false,
+        have_subframe_gain: 3,
+        double_codebook: false,
+        lsp: LspQuant::Nb,
+        ltp: Some(Ltp::ThreeTap(LTP_PARAMS_NB)),
+        innovation: split_cb(5, 8, &EXC_5_64, 6, false),
+        comb_gain: 0.25,
+    }),
+    // 18.2 kbps high bit-rate mode.
+    Some(Submode {
+        lbr_pitch: None,
+        forced_pitch_gain: false,
+        have_subframe_gain: 3,
+        double_codebook: false,
+        lsp: LspQuant::Nb,
+        ltp: Some(Ltp::ThreeTap(LTP_PARAMS_NB)),
+        innovation: split_cb(5, 8, &EXC_5_256, 8, false),
+        comb_gain: 0.15,
+    }),
+    // 24.6 kbps high bit-rate mode.
+    Some(Submode {
+        lbr_pitch: None,
+        forced_pitch_gain: false,
+        have_subframe_gain: 3,
+        double_codebook: true,
+        lsp: LspQuant::Nb,
+        ltp: Some(Ltp::ThreeTap(LTP_PARAMS_NB)),
+        innovation: split_cb(5, 8, &EXC_5_64, 6, false),
+        comb_gain: 0.05,
+    }),
+    // 3.95 kbps very low bit-rate mode.
+    Some(Submode {
+        lbr_pitch: Some(0),
+        forced_pitch_gain: true,
+        have_subframe_gain: 0,
+        double_codebook: false,
+        lsp: LspQuant::Lbr,
+        ltp: Some(Ltp::Forced),
+        innovation: split_cb(20, 2, &EXC_20_32, 5, false),
+        comb_gain: 0.5,
+    }),
+];
+
+fn gain_3tap_to_1tap(g: [f32; 3]) -> f32 {
+    let side = |g: f32| if g > 0.0 { g } else { -0.5 * g };
+    g[1].abs() + side(g[0]) + side(g[2])
+}
+
+pub struct NarrowbandDecoder {
+    first: bool,
+    exc_buf: [f32; EXC_BUF_SIZE],
+    old_qlsp: [f32; ORDER],
+    interp_qlpc: [f32; ORDER],
+    mem_sp: [f32; ORDER],
+    mem_hp: [f32; 2],
+    seed: u32,
+    submode_id: usize,
+    lpc_enh_enabled: bool,
+
+    // Vocoder state.
+    voc_m1: f32,
+    voc_m2: f32,
+    voc_mean: f32,
+    voc_offset: isize,
+
+    pub dtx_enabled: bool,
+    /// Whether this decodes the low band of a wideband stream.
+    pub is_wideband: bool,
+
+    /// The gain of the LPC filter at half the sampling rate, per subframe.
+    pub pi_gain: [f32; NUM_SUBFRAMES],
+    /// The innovation of the last frame, which the wideband decoder folds
+    /// into the high band.
+    pub innov_save: [f32; FRAME_SIZE],
+}
+
+impl Default for NarrowbandDecoder {
+    fn default() -> Self {
+        Self {
+            first: true,
+            exc_buf: [0.0; EXC_BUF_SIZE],
+            old_qlsp: [0.0; ORDER],
+            interp_qlpc: [0.0; ORDER],
+            mem_sp: [0.0; ORDER],
+            mem_hp: [0.0; 2],
+            seed: 1000,
+            submode_id: 5,
+            lpc_enh_enabled: true,
+            voc_m1: 0.0,
+            voc_m2: 0.0,
+            voc_mean: 0.0,
+            voc_offset: 0,
+            dtx_enabled: false,
+            is_wideband: false,
+            pi_gain: [0.0; NUM_SUBFRAMES],
+            innov_save: [0.0; FRAME_SIZE],
+        }
+    }
+}
+
+impl NarrowbandDecoder {
+    /// The RMS of the excitation of each subframe of the last frame.
+    pub fn exc_rms(&self) -> [f32; NUM_SUBFRAMES] {
+        std::array::from_fn(|i| {
+            compute_rms(&self.exc_buf[EXC_OFFSET + i * SUBFRAME_SIZE..][..SUBFRAME_SIZE])
+        })
+    }
+
+    /// Reads the mode of the next frame, skipping over any wideband layers and
+    /// in-band requests.
+    fn read_mode(bits: &mut Bits) -> Result<usize, DecodeError> {
+        loop {
+            if !bits.has_remaining(5) {
+                return Err(DecodeError::EndOfPacket);
+            }
+            let mut layers = 0;
+            while bits.read(1) != 0 {
+                // Skip wideband layers, for compatibility.
+                layers += 1;
+                if layers > 2 {
+                    return Err(DecodeError::Corrupt);
+                }
+                let submode = bits.read(3) as usize;
+                bits.advance(WB_SKIP_TABLE[submode].saturating_sub(4));
+                if !bits.has_remaining(5) {
+                    return Err(DecodeError::EndOfPacket);
+                }
+            }
+
+            if !bits.has_remaining(4) {
+                return Err(DecodeError::EndOfPacket);
+            }
+            match bits.read(4) {
+                // Terminator.
+                15 => return Err(DecodeError::EndOfPacket),
+                // Speex in-band request. Nobody is listening, so skip it.
+                14 => {
+                    let skip = match bits.read(4) {
+                        0..=1 => 1,
+                        2..=7 => 4,
+                        8..=9 => 8,
+                        10..=11 => 16,
+                        12..=13 => 32,
+                        _ => 64,
+                    };
+                    bits.advance(skip);
+                }
+                // User in-band request.
+                13 => {
+                    let size = bits.read(4) as usize;
+                    bits.advance(5 + 8 * size);
+                }
+                mode @ 0..=8 => return Ok(mode as usize),
+                _ => return Err(DecodeError::Corrupt),
+            }
+        }
+    }
+
+    pub fn decode(&mut self, bits: &mut Bits, out: &mut [f32]) -> Result<(), DecodeError> {
+        self.submode_id = Self::read_mode(bits)?;
+
+        // Shift all buffers by one frame.
+        self.exc_buf.copy_within(FRAME_SIZE.., 0);
+
+        let Some(submode) = &SUBMODES[self.submode_id] else {
+            // Null mode (no transmission): synthesize noise with the same energy.
+            let mut lpc = [0.0; ORDER];
+            bw_lpc(0.93, &self.interp_qlpc, &mut lpc);
+            let exc = &mut self.exc_buf[EXC_OFFSET..][..FRAME_SIZE];
+            let innov_gain = compute_rms(exc);
+            for x in exc.iter_mut() {
+                *x = speex_rand(innov_gain, &mut self.seed);
+            }
+            self.first = true;
+            iir_mem(exc, &lpc, out, &mut self.mem_sp);
+            return Ok(());
+        };
+
+        let mut qlsp = [0.0; ORDER];
+        submode.lsp.unquant(&mut qlsp, bits);
+        if self.first {
+            self.old_qlsp = qlsp;
+        }
+
+        // Open-loop pitch estimation, for low bit-rate pitch coding.
+        let ol_pitch = if submode.lbr_pitch.is_some() {
+            PITCH_START + bits.read(7) as usize
+        } else {
+            0
+        };
+        let ol_pitch_coef = if submode.forced_pitch_gain {
+            0.066667 * bits.read(4) as f32
+        } else {
+            0.0
+        };
+        // Global excitation gain.
+        let ol_gain = (f64::from(bits.read(5)) / 3.5).exp() as f32;
+
+        if self.submode_id == 1 {
+            self.dtx_enabled = bits.read(4) == 15;
+        } else if self.submode_id > 1 {
+            self.dtx_enabled = false;
+        }
+
+        let mut best_pitch = 40;
+        let mut best_pitch_gain = 0.0;
+        for sub in 0..NUM_SUBFRAMES {
+            let offset = SUBFRAME_SIZE * sub;
+            let exc_start = EXC_OFFSET + offset;
+            self.exc_buf[exc_start..][..SUBFRAME_SIZE].fill(0.0);
+
+            // Adaptive codebook contribution.
+            // The decoded pitch is an offset from the smallest allowed pitch.
+            let pit_min = match submode.lbr_pitch {
+                Some(0) => ol_pitch,
+                Some(margin) => (ol_pitch + 1).saturating_sub(margin).max(PITCH_START),
+                None => PITCH_START,
+            };
+            let mut exc32 = [0.0; SUBFRAME_SIZE];
+            let (pitch, pitch_gain) = match &submode.ltp {
+                Some(Ltp::Forced) => {
+                    let coef = ol_pitch_coef.min(0.99);
+                    for (i, exc32) in exc32.iter_mut().enumerate() {
+                        *exc32 = self.exc_buf[exc_start + i - pit_min] * coef;
+                        self.exc_buf[exc_start + i] = *exc32;
+                    }
+                    (pit_min, [0.0, coef, 0.0])
+                }
+                Some(Ltp::ThreeTap(params)) => {
+                    self.pitch_unquant_3tap(params, exc_start, &mut exc32, pit_min, bits)
+                }
+                None => return Err(DecodeError::Corrupt),
+            };
+            sanitize_values(&mut exc32, -32000.0, 32000.0);
+
+            let tmp = gain_3tap_to_1tap(pitch_gain);
+            let near = |a: usize, b: usize, n: usize, max: usize| a.abs_diff(b * n) < max;
+            let is_multiple =
+                |a: usize, b: usize| near(a, b, 2, 3) || near(a, b, 3, 4) || near(a, b, 4, 5);
+            if (tmp > best_pitch_gain && !is_multiple(pitch, best_pitch))
+                || (tmp > 0.6 * best_pitch_gain && is_multiple(best_pitch, pitch))
+                || (0.67 * tmp > best_pitch_gain && is_multiple(pitch, best_pitch))
+            {
+                best_pitch = pitch;
+                if tmp > best_pitch_gain {
+                    best_pitch_gain = tmp;
+                }
+            }
+
+            // Fixed codebook contribution.
+            let ener = match submode.have_subframe_gain {
+                3 => EXC_GAIN_QUANT_SCAL3[bits.read(3) as usize] * ol_gain,
+                1 => EXC_GAIN_QUANT_SCAL1[bits.read(1) as usize] * ol_gain,
+                _ => ol_gain,
+            };
+            let Some(innovation) = &submode.innovation else {
+                return Err(DecodeError::Corrupt);
+            };
+            let mut innov = [0.0; SUBFRAME_SIZE];
+            innovation.unquant(&mut innov, bits, &mut self.seed);
+            for x in &mut innov {
+                *x *= ener;
+            }
+            if submode.double_codebook {
+                let mut innov2 = [0.0; SUBFRAME_SIZE];
+                innovation.unquant(&mut innov2, bits, &mut self.seed);
+                for (x, y) in innov.iter_mut().zip(innov2) {
+                    *x += y * (0.454545 * ener);
+                }
+            }
+            for i in 0..SUBFRAME_SIZE {
+                self.exc_buf[exc_start + i] = exc32[i] + innov[i];
+            }
+            self.innov_save[offset..][..SUBFRAME_SIZE].copy_from_slice(&innov);
+
+            if self.submode_id == 1 {
+                self.vocoder(exc_start, &innov, ol_pitch, ol_pitch_coef, ol_gain);
+            }
+        }
+
+        if self.lpc_enh_enabled && submode.comb_gain > 0.0 {
+            for half in 0..2 {
+                let len = 2 * SUBFRAME_SIZE;
+                multicomb(
+                    &self.exc_buf,
+                    EXC_OFFSET - SUBFRAME_SIZE + half * len,
+                    &mut out[half * len..][..len],
+                    best_pitch,
+                    40,
+                    submode.comb_gain,
+                );
+            }
+        } else {
+            out[..FRAME_SIZE]
+                .copy_from_slice(&self.exc_buf[EXC_OFFSET - SUBFRAME_SIZE..][..FRAME_SIZE]);
+        }
+
+        let mut interp_qlsp = [0.0; ORDER];
+        let mut ak = [0.0; ORDER];
+        for sub in 0..NUM_SUBFRAMES {
+            lsp_interpolate(
+                &self.old_qlsp,
+                &qlsp,
+                &mut interp_qlsp,
+                sub,
+                NUM_SUBFRAMES,
+                LSP_MARGIN,
+            );
+            lsp_to_lpc(&interp_qlsp, &mut ak);
+
+            // The analysis filter at w=pi.
+            self.pi_gain[sub] = 1.0 + ak.chunks_exact(2).map(|a| a[1] - a[0]).sum::<f32>();
+
+            iir_mem_in_place(
+                &mut out[SUBFRAME_SIZE * sub..][..SUBFRAME_SIZE],
+                &self.interp_qlpc,
+                &mut self.mem_sp,
+            );
+            self.interp_qlpc = ak;
+        }
+
+        let filter = if self.is_wideband {
+            Highpass::Wideband
+        } else {
+            Highpass::Narrowband
+        };
+        highpass(&mut out[..FRAME_SIZE], filter, &mut self.mem_hp);
+
+        self.old_qlsp = qlsp;
+        self.first = false;
+        Ok(())
+    }
+
+    /// Decodes the 3-tap pitch predictor of the subframe at `exc_start`.
+    /// Returns the pitch and its gains.
+    fn pitch_unquant_3tap(
+        &self,
+        params: &LtpParams,
+        exc_start: usize,
+        exc_out: &mut [f32; SUBFRAME_SIZE],
+        start: usize,
+        bits: &mut Bits,
+    ) -> (usize, [f32; 3]) {
+        let pitch = bits.read(params.pitch_bits) as usize + start;
+        let gain_index = bits.read(params.gain_bits) as usize;
+        let gain: [f32; 3] = std::array::from_fn(|i| {
+            0.015625 * f32::from(params.gain_cdbk[gain_index * 4 + i]) + 0.5
+        });
+
+        for (i, &gain) in gain.iter().rev().enumerate() {
+            let pp = pitch + 1 - i;
+            // Repeat the last pitch period if the pitch is shorter than the
+            // subframe.
+            for (j, out) in exc_out.iter_mut().enumerate() {
+                let delay = if j < pp {
+                    pp
+                } else if j < pp + pitch {
+                    pp + pitch
+                } else {
+                    break;
+                };
+                *out += gain * self.exc_buf[exc_start + j - delay];
+            }
+        }
+
+        (pitch, gain)
+    }
+
+    /// Replaces the excitation of the subframe at `exc_start` with pulses at
+    /// the pitch period, for the comfort noise mode.
+    fn vocoder(
+        &mut self,
+        exc_start: usize,
+        innov: &[f32; SUBFRAME_SIZE],
+        ol_pitch: usize,
+        ol_pitch_coef: f32,
+        ol_gain: f32,
+    ) {
+        let g = (1.5 * (ol_pitch_coef - 0.2)).clamp(0.0, 1.0);
+        let exc = &mut self.exc_buf[exc_start..][..SUBFRAME_SIZE];
+
+        exc.fill(0.0);
+        while self.voc_offset < SUBFRAME_SIZE as isize {
+            if self.voc_offset >= 0 {
+                exc[self.voc_offset as usize] = ((2 * ol_pitch) as f32).sqrt() * (g * ol_gain);
+            }
+            self.voc_offset += ol_pitch as isize;
+        }
+        self.voc_offset -= SUBFRAME_SIZE as isize;
+
+        for i in 0..SUBFRAME_SIZE {
+            let exci = exc[i];
+            exc[i] = 0.7 * exc[i]
+                + 0.3 * self.voc_m1
+                + ((1.0 - 0.85 * g) * innov[i] - 0.15 * g * self.voc_m2);
+            self.voc_m1 = exci;
+            self.voc_m2 = innov[i];
+            self.voc_mean = 0.8 * self.voc_mean + 0.2 * exc[i];
+            exc[i] -= self.voc_mean;
+        }
+    }
+}
@@ -0,0 +1,278 @@
+//! The wideband (16kHz) sub-band CELP decoder, which decodes the high band on
+//! top of a narrowband frame and recombines the two.
+
+use super::filters::{
+    compute_rms, iir_mem, iir_mem_in_place, lsp_interpolate, lsp_to_lpc, qmf_synth, speex_rand,
+    QMF_ORDER,
+};
+use super::narrowband::{
+    split_cb, Innovation, LspQuant, NarrowbandDecoder, FRAME_SIZE, NUM_SUBFRAMES, SUBFRAME_SIZE,
+};
+use super::tables::{HEXC, HEXC_10_32};
+use super::{Bits, DecodeError};
+
+const ORDER: usize = 8;
+const LSP_MARGIN: f32 = 0.05;
+const FOLDING_GAIN: f32 = 0.9;
+
+/// The coefficients of the quadrature mirror filter.
+const H0: [f32; QMF_ORDER] = [
+    3.596189e-05,
+    -0.0001123515,
+    -0.0001104587,
+    0.0002790277,
+    0.0002298438,
+    -0.0005953563,
+    -0.0003823631,
+    0.00113826,
+    0.0005308539,
+    -0.001986177,
+    -0.0006243724,
+    0.003235877,
+    0.0005743159,
+    -0.004989147,
+    -0.0002584767,
+    0.007367171,
+    -0.0004857935,
+    -0.01050689,
+    0.001894714,
+    0.01459396,
+    -0.004313674,
+    -0.01994365,
+    0.00828756,
+    0.02716055,
+    -0.01485397,
+    -0.03764973,
+    0.026447,
+    0.05543245,
+    -0.05095487,
+    -0.09779096,
+    0.1382363,
+    0.4600981,
+    0.4600981,
+    0.1382363,
+    -0.09779096,
+    -0.05095487,
+    0.05543245,
+    0.026447,
+    -0.03764973,
+    -0.01485397,
+    0.02716055,
+    0.00828756,
+    -0.01994365,
+    -0.004313674,
+    0.01459396,
+    0.001894714,
+    -0.01050689,
+    -0.0004857935,
+    0.007367171,
+    -0.0002584767,
+    -0.004989147,
+    0.0005743159,
+    0.003235877,
+    -0.0006243724,
+    -0.001986177,
+    0.0005308539,
+    0.00113826,
+    -0.0003823631,
+    -0.0005953563,
+    0.0002298438,
+    0.0002790277,
+    -0.0001104587,
+    -0.0001123515,
+    3.596189e-05,
+];
+
+const GC_QUANT_BOUND: [f32; 16] = [
+    0.97979, 1.28384, 1.68223, 2.20426, 2.88829, 3.78458, 4.95900, 6.49787, 8.51428, 11.15642,
+    14.61846, 19.15484, 25.09895, 32.88761, 43.09325, 56.46588,
+];
+
+/// The parameters of a high band bit-rate mode.
+struct Submode {
+    /// `None` if the high band is folded from the low band innovation.
+    innovation: Option<Innovation>,
+    double_codebook: bool,
+}
+
+/// The wideband submodes, indexed by the 3-bit mode number.
+static SUBMODES: [Option<Submode>; 8] = [
+    None,
+    Some(Submode {
+        innovation: None,
+        double_codebook: false,
+    }),
+    Some(Submode {
+        innovation: split_cb(10, 4, &HEXC_10_32, 5, false),
+        double_codebook: false,
+    }),
+    Some(Submode {
+        innovation: split_cb(8, 5, &HEXC, 7, true),
+        double_codebook: false,
+    }),
+    Some(Submode {
+        innovation: split_cb(8, 5, &HEXC, 7, true),
+        double_codebook: true,
+    }),
+    None,
+    None,
+    None,
+];
+
+pub struct WidebandDecoder {
+    low: NarrowbandDecoder,
+    first: bool,
+    g0_mem: [f32; QMF_ORDER],
+    g1_mem: [f32; QMF_ORDER],
+    exc_buf: [f32; SUBFRAME_SIZE],
+    old_qlsp: [f32; ORDER],
+    interp_qlpc: [f32; ORDER],
+    mem_sp: [f32; ORDER],
+    last_ener: f32,
+    seed: u32,
+}
+
+impl Default for WidebandDecoder {
+    fn default() -> Self {
+        let mut low = NarrowbandDecoder::default();
+        low.is_wideband = true;
+        Self {
+            low,
+            first: true,
+            g0_mem: [0.0; QMF_ORDER],
+            g1_mem: [0.0; QMF_ORDER],
+            exc_buf: [0.0; SUBFRAME_SIZE],
+            old_qlsp: [0.0; ORDER],
+            interp_qlpc: [0.0; ORDER],
+            mem_sp: [0.0; ORDER],
+            last_ener: 0.0,
+            seed: 1000,
+        }
+    }
+}
+
+impl WidebandDecoder {
+    pub fn decode(
+        &mut self,
+        bits: &mut Bits,
+        out: &mut [f32; 2 * FRAME_SIZE],
+    ) -> Result<(), DecodeError> {
+        let (low, high) = out.split_at_mut(FRAME_SIZE);
+        self.low.decode(bits, low)?;
+
+        // A frame without the wideband bit is a narrowband-only frame.
+        let submode_id = if bits.has_remaining(1) && bits.peek() != 0 {
+            bits.read(1);
+            bits.read(3) as usize
+        } else {
+            0
+        };
+
+        let Some(submode) = &SUBMODES[submode_id] else {
+            if submode_id != 0 {
+                return Err(DecodeError::Corrupt);
+            }
+            self.first = true;
+            if self.low.dtx_enabled {
+                // Comfort noise with the energy of the last frame.
+                for x in high.iter_mut() {
+                    *x = speex_rand(self.last_ener, &mut self.seed);
+                }
+            } else {
+                high.fill(1e-15);
+            }
+            iir_mem_in_place(high, &self.interp_qlpc, &mut self.mem_sp);
+            self.synthesize(out);
+            return Ok(());
+        };
+
+        let low_pi_gain = self.low.pi_gain;
+        let low_exc_rms = self.low.exc_rms();
+
+        let mut qlsp = [0.0; ORDER];
+        LspQuant::High.unquant(&mut qlsp, bits);
+        if self.first {
+            self.old_qlsp = qlsp;
+        }
+
+        let mut interp_qlsp = [0.0; ORDER];
+        let mut ak = [0.0; ORDER];
+        let mut exc_ener_sum = 0.0;
+        for sub in 0..NUM_SUBFRAMES {
+            let offset = SUBFRAME_SIZE * sub;
+
+            lsp_interpolate(
+                &self.old_qlsp,
+                &qlsp,
+                &mut interp_qlsp,
+                sub,
+                NUM_SUBFRAMES,
+                LSP_MARGIN,
+            );
+            lsp_to_lpc(&interp_qlsp, &mut ak);
+
+            // The response ratio between the low and high band filters in the
+            // middle of the band (4000 Hz).
+            let rh = 1.0 + ak.chunks_exact(2).map(|a| a[1] - a[0]).sum::<f32>();
+            let rl = low_pi_gain[sub];
+            let filter_ratio = (rl + 0.01) / (rh + 0.01);
+
+            let mut exc = [0.0; SUBFRAME_SIZE];
+            match &submode.innovation {
+                None => {
+                    // Fold the low band innovation into the high band.
+                    let quant = bits.read(5);
+                    let g = (0.125 * (f64::from(quant) - 10.0)).exp() as f32 / filter_ratio;
+                    let low_innov = &self.low.innov_save[offset..][..SUBFRAME_SIZE];
+                    for (i, (exc, &innov)) in exc.iter_mut().zip(low_innov).enumerate() {
+                        let folded = FOLDING_GAIN * innov * g;
+                        *exc = if i % 2 == 0 { folded } else { -folded };
+                    }
+                }
+                Some(innovation) => {
+                    let qgc = bits.read(4) as usize;
+                    let gc = 0.87360 * GC_QUANT_BOUND[qgc];
+                    let scale = gc * low_exc_rms[sub] / filter_ratio;
+
+                    innovation.unquant(&mut exc, bits, &mut self.seed);
+                    for x in &mut exc {
+                        *x *= scale;
+                    }
+                    if submode.double_codebook {
+                        let mut innov2 = [0.0; SUBFRAME_SIZE];
+                        innovation.unquant(&mut innov2, bits, &mut self.seed);
+                        for (x, y) in exc.iter_mut().zip(innov2) {
+                            *x += y * (0.4 * scale);
+                        }
+                    }
+                }
+            }
+
+            iir_mem(
+                &self.exc_buf,
+                &self.interp_qlpc,
+                &mut high[offset..][..SUBFRAME_SIZE],
+                &mut self.mem_sp,
+            );
+            self.exc_buf = exc;
+            self.interp_qlpc = ak;
+
+            let exc_rms = compute_rms(&self.exc_buf);
+            exc_ener_sum += exc_rms * exc_rms / NUM_SUBFRAMES as f32;
+        }
+        self.last_ener = exc_ener_sum.sqrt();
+
+        self.synthesize(out);
+
+        self.old_qlsp = qlsp;
+        self.first = false;
+        Ok(())
+    }
+
+    /// Recombines the decoded low and high bands in `out` into the full band.
+    fn synthesize(&mut self, out: &mut [f32; 2 * FRAME_SIZE]) {
+        let bands = *out;
+        let (low, high) = bands.split_at(FRAME_SIZE);
+        qmf_synth(low, high, &H0, out, &mut self.g0_mem, &mut self.g1_mem);
+    }
+}
@@ -4,10 +4,14 @@ use crate::backend::audio::{DecodeError, RegisterError};
 use crate::buffer::Substream;
 use crate::tag_utils::SwfSlice;
 use slotmap::SlotMap;
+use std::collections::VecDeque;
 use std::io::Cursor;
 use std::sync::{Arc, Mutex, RwLock};
 use swf::AudioCompression;
 
+/// The sample rate used by `Sound.extract` and dynamic sounds.
+const DYNAMIC_SAMPLE_RATE: u16 = 44100;
+
 /// Holds the last 2048 output audio frames. Frames can be written to it one by
 /// one, and the last completely filled 1024-wide window can be read from it.
 struct CircBuf {
@@ -159,6 +163,85 @@ struct Sound {
     /// `skip_sample_frames` indicates how many sample frames to skip to bypass the delay.
     /// This is `0` unless `format.compression` is `AudioCompression::Mp3`.
     skip_sample_frames: u16,
+
+    /// The stream that the last `Sound.extract` call read from.
+    ///
+    /// This lets a sound be extracted piece by piece without decoding it
+    /// from the start every time.
+    extractor: Option<SoundExtractor>,
+}
+
+/// A stream of a sound's audio data at 44.1KHz, as read by `Sound.extract`.
+struct SoundExtractor {
+    stream: Box<dyn Stream>,
+
+    /// The sample frame that `stream` will produce next.
+    position: u32,
+}
+
+/// The audio data of a dynamic sound that was provided by `SampleDataEvent` handlers,
+/// but was not played yet.
+#[derive(Default)]
+struct DynamicSoundBuffer {
+    /// The buffered sample frames, at 44.1KHz.
+    samples: VecDeque<[i16; 2]>,
+
+    /// Whether the last samples of this sound have been provided.
+    ///
+    /// The sound will end once `samples` is drained.
+    is_final: bool,
+}
+
+/// A stream that plays the samples provided to a `DynamicSoundBuffer`.
+///
+/// If the buffer runs dry before the final samples have been provided, silence is played.
+struct DynamicSoundStream {
+    buffer: Arc<Mutex<DynamicSoundBuffer>>,
+    position: u32,
+    is_exhausted: bool,
+}
+
+impl DynamicSoundStream {
+    fn new(buffer: Arc<Mutex<DynamicSoundBuffer>>) -> Self {
+        Self {
+            buffer,
+            position: 0,
+            is_exhausted: false,
+        }
+    }
+}
+
+impl Stream for DynamicSoundStream {
+    #[inline]
+    fn source_position(&self) -> u32 {
+        self.position
+    }
+
+    #[inline]
+    fn source_sample_rate(&self) -> u16 {
+        DYNAMIC_SAMPLE_RATE
+    }
+}
+
+impl dasp::signal::Signal for DynamicSoundStream {
+    type Frame = [i16; 2];
+
+    #[inline]
+    fn next(&mut self) -> [i16; 2] {
+        let mut buffer = self.buffer.lock().expect("Cannot be called reentrant");
+        if let Some(frame) = buffer.samples.pop_front() {
+            self.position += 1;
+            frame
+        } else {
+            self.is_exhausted = buffer.is_final;
+            Default::default()
+        }
+    }
+
+    #[inline]
+    fn is_exhausted(&self) -> bool {
+        self.is_exhausted
+    }
 }
 
 /// An actively playing instance of a sound.
@@ -195,6 +278,9 @@ struct SoundInstance {
     /// (respectively) of this sound over the buffer currently being
     /// mixed. Used to compute `peak`, and is reset after every time.
     range: ([f32; 2], [f32; 2]),
+
+    /// The buffer that feeds this sound, if this is a dynamic sound.
+    dynamic_buffer: Option<Arc<Mutex<DynamicSoundBuffer>>>,
 }
 
 impl SoundInstance {
@@ -208,6 +294,7 @@ impl SoundInstance {
             right_transform: [0.0, 1.0],
             peak: [0.0, 0.0],
             range: ([f32::INFINITY; 2], [f32::NEG_INFINITY; 2]),
+            dynamic_buffer: None,
         }
     }
 
@@ -223,6 +310,21 @@ impl SoundInstance {
             right_transform: [0.0, 1.0],
             peak: [0.0, 0.0],
             range: ([f32::INFINITY; 2], [f32::NEG_INFINITY; 2]),
+            dynamic_buffer: None,
+        }
+    }
+
+    /// Creates a new `SoundInstance` for a dynamic sound, fed by the given buffer.
+    fn new_dynamic(stream: Box<dyn Stream>, buffer: Arc<Mutex<DynamicSoundBuffer>>) -> Self {
+        SoundInstance {
+            handle: None,
+            stream,
+            active: true,
+            left_transform: [1.0, 0.0],
+            right_transform: [0.0, 1.0],
+            peak: [0.0, 0.0],
+            range: ([f32::INFINITY; 2], [f32::NEG_INFINITY; 2]),
+            dynamic_buffer: Some(buffer),
         }
     }
 
@@ -332,20 +434,52 @@ impl AudioMixer {
     }
 
     /// Transforms a `Stream` into a new `Stream` that matches the output sample rate.
-    fn make_resampler(&self, mut stream: impl Stream) -> impl Stream {
+    fn make_resampler(&self, stream: impl Stream) -> impl Stream {
+        Self::make_resampler_to_rate(stream, self.output_sample_rate)
+    }
+
+    /// Transforms a `Stream` into a new `Stream` with the given sample rate.
+    fn make_resampler_to_rate(mut stream: impl Stream, sample_rate: u32) -> impl Stream {
         // TODO: Allow interpolator to be user-configurable?
         let left = stream.next();
         let right = stream.next();
         let interpolator = dasp::interpolate::linear::Linear::new(left, right);
-        let sample_rate = stream.source_sample_rate().into();
+        let source_sample_rate = stream.source_sample_rate().into();
         ConverterStream(dasp::signal::interpolate::Converter::from_hz_to_hz(
             stream,
             interpolator,
-            sample_rate,
-            self.output_sample_rate.into(),
+            source_sample_rate,
+            sample_rate.into(),
         ))
     }
 
+    /// Creates a `Stream` that decodes a sound starting at the given sample frame, and
+    /// resamples it to 44.1KHz.
+    fn make_extract_stream(
+        sound: &Sound,
+        start_sample_frame: u32,
+    ) -> Result<Box<dyn Stream>, DecodeError> {
+        let data = Cursor::new(ArcAsRef(Arc::clone(&sound.data)));
+        let decoder = Self::make_seekable_decoder(&sound.format, data)?;
+        let settings = swf::SoundInfo {
+            event: swf::SoundEvent::Start,
+            in_sample: Some(start_sample_frame),
+            out_sample: None,
+            num_loops: 1,
+            envelope: None,
+        };
+        let stream = EventSoundStream::new_with_settings(
+            decoder,
+            &settings,
+            sound.num_sample_frames,
+            sound.skip_sample_frames,
+        );
+        Ok(Box::new(Self::make_resampler_to_rate(
+            stream,
+            DYNAMIC_SAMPLE_RATE.into(),
+        )))
+    }
+
     /// Creates a `Stream` for an "event" that decodes and resamples the audio stream to the
     /// output format.
     ///
@@ -522,6 +656,7 @@ impl AudioMixer {
             data: Arc::from(data),
             num_sample_frames: swf_sound.num_samples,
             skip_sample_frames,
+            extractor: None,
         };
         Ok(self.sounds.insert(sound))
     }
@@ -542,6 +677,7 @@ impl AudioMixer {
             data,
             num_sample_frames: metadata.num_sample_frames,
             skip_sample_frames: 0,
+            extractor: None,
         };
         Ok(self.sounds.insert(sound))
     }
@@ -622,6 +758,110 @@ impl AudioMixer {
         Ok(handle)
     }
 
+    /// Starts a dynamic sound, which plays the samples provided by `append_dynamic_sound_samples`.
+    pub fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, DecodeError> {
+        let buffer = Arc::new(Mutex::new(DynamicSoundBuffer::default()));
+        let stream = DynamicSoundStream::new(Arc::clone(&buffer));
+        let stream = Box::new(self.make_resampler(stream));
+
+        let mut sound_instances = self
+            .sound_instances
+            .lock()
+            .expect("Cannot be called reentrant");
+        let handle = sound_instances.insert(SoundInstance::new_dynamic(stream, buffer));
+        Ok(handle)
+    }
+
+    /// Queues 44.1KHz samples to be played by a dynamic sound.
+    ///
+    /// If `is_final` is `true`, the sound will end after these samples have been played.
+    pub fn append_dynamic_sound_samples(
+        &mut self,
+        instance: SoundInstanceHandle,
+        samples: &[[f32; 2]],
+        is_final: bool,
+    ) {
+        use dasp::Sample;
+
+        let sound_instances = self
+            .sound_instances
+            .lock()
+            .expect("Cannot be called reentrant");
+        if let Some(buffer) = sound_instances
+            .get(instance)
+            .and_then(|instance| instance.dynamic_buffer.as_ref())
+        {
+            let mut buffer = buffer.lock().expect("Cannot be called reentrant");
+            buffer.samples.extend(
+                samples
+                    .iter()
+                    .map(|&[left, right]| [left.to_sample(), right.to_sample()]),
+            );
+            buffer.is_final |= is_final;
+        }
+    }
+
+    /// Returns the number of sample frames that a dynamic sound has buffered, but not played yet.
+    ///
+    /// Returns `None` if the sound is no longer playing, or is not a dynamic sound.
+    pub fn dynamic_sound_buffered_frames(&self, instance: SoundInstanceHandle) -> Option<u32> {
+        let sound_instances = self
+            .sound_instances
+            .lock()
+            .expect("Cannot be called reentrant");
+        let buffer = sound_instances.get(instance)?.dynamic_buffer.as_ref()?;
+        let buffer = buffer.lock().expect("Cannot be called reentrant");
+        Some(buffer.samples.len() as u32)
+    }
+
+    /// Returns decoded 44.1KHz samples of a registered sound, starting at the given sample frame.
+    ///
+    /// Fewer than `num_sample_frames` samples are returned if the end of the sound is reached.
+    /// Returns `None` if the sound is not registered or could not be decoded.
+    pub fn extract_sound_data(
+        &mut self,
+        sound: SoundHandle,
+        start_sample_frame: u32,
+        num_sample_frames: u32,
+    ) -> Option<Vec<[f32; 2]>> {
+        use dasp::Sample;
+
+        let sound = self.sounds.get_mut(sound)?;
+        let total_sample_frames = u64::from(sound.num_sample_frames)
+            * u64::from(DYNAMIC_SAMPLE_RATE)
+            / u64::from(sound.format.sample_rate.max(1));
+        let total_sample_frames = total_sample_frames as u32;
+        let start = start_sample_frame.min(total_sample_frames);
+        let end = start
+            .saturating_add(num_sample_frames)
+            .min(total_sample_frames);
+
+        // Carry on from the last extraction if possible, as seeking may mean
+        // decoding everything before `start` again.
+        let mut extractor = match sound.extractor.take() {
+            Some(extractor) if extractor.position == start => extractor,
+            _ => match Self::make_extract_stream(sound, start) {
+                Ok(stream) => SoundExtractor {
+                    stream,
+                    position: start,
+                },
+                Err(e) => {
+                    tracing::error!("Failed to decode sound for extraction: {e}");
+                    return None;
+                }
+            },
+        };
+
+        let mut samples = Vec::with_capacity((end - start) as usize);
+        while extractor.position < end && !extractor.stream.is_exhausted() {
+            let [left, right] = extractor.stream.next();
+            samples.push([left.to_sample(), right.to_sample()]);
+            extractor.position += 1;
+        }
+        sound.extractor = Some(extractor);
+        Some(samples)
+    }
+
     /// Stops a playing sound instance.
     pub fn stop_sound(&mut self, sound: SoundInstanceHandle) {
         let mut sound_instances = self
@@ -1105,6 +1345,38 @@ macro_rules! impl_audio_mixer_backend {
             self.$mixer.start_substream(stream_data, stream_info)
         }
 
+        #[inline]
+        fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, DecodeError> {
+            self.$mixer.start_dynamic_sound()
+        }
+
+        #[inline]
+        fn append_dynamic_sound_samples(
+            &mut self,
+            instance: SoundInstanceHandle,
+            samples: &[[f32; 2]],
+            is_final: bool,
+        ) {
+            self.$mixer
+                .append_dynamic_sound_samples(instance, samples, is_final)
+        }
+
+        #[inline]
+        fn dynamic_sound_buffered_frames(&self, instance: SoundInstanceHandle) -> Option<u32> {
+            self.$mixer.dynamic_sound_buffered_frames(instance)
+        }
+
+        #[inline]
+        fn extract_sound_data(
+            &mut self,
+            sound: SoundHandle,
+            start_sample_frame: u32,
+            num_sample_frames: u32,
+        ) -> Option<Vec<[f32; 2]>> {
+            self.$mixer
+                .extract_sound_data(sound, start_sample_frame, num_sample_frames)
+        }
+
         #[inline]
         fn stop_sound(&mut self, sound: SoundInstanceHandle) {
             self.$mixer.stop_sound(sound)
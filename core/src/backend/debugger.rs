@@ -0,0 +1,117 @@
+use downcast_rs::Downcast;
+
+/// A line of ActionScript source code.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourceLocation {
+    /// The source file, as named by the compiler.
+    pub file: String,
+
+    /// The line number, counted from 1.
+    pub line: u32,
+}
+
+/// Why script execution was paused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseReason {
+    /// A breakpoint was hit, either one set by the debugger or a `bkpt` instruction.
+    Breakpoint,
+
+    /// A step requested by the debugger finished.
+    Step,
+
+    /// The debugger asked for execution to be paused.
+    Requested,
+}
+
+/// A call on the stack of a paused script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackFrame {
+    /// The name of the called function.
+    pub name: String,
+
+    /// The line being executed in this call, if known.
+    pub location: Option<SourceLocation>,
+}
+
+/// A variable of a paused script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variable {
+    pub name: String,
+
+    /// The value of the variable, as displayed to the user.
+    pub value: String,
+}
+
+/// A request sent by a debugger.
+///
+/// Frames are counted from the innermost call, which is frame 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DebuggerCommand {
+    SetBreakpoint(SourceLocation),
+    RemoveBreakpoint(SourceLocation),
+
+    /// Pause at the next line that runs.
+    Pause,
+
+    /// Resume a paused script.
+    Continue,
+
+    /// Resume a paused script, pausing again at the next line that runs.
+    StepIn,
+
+    /// Resume a paused script, pausing again at the next line of the current
+    /// call or of its callers.
+    StepOver,
+
+    /// Resume a paused script, pausing again after the current call returns.
+    StepOut,
+
+    GetStack,
+    GetLocals {
+        frame: u32,
+    },
+    Evaluate {
+        frame: u32,
+        expression: String,
+    },
+}
+
+/// A message sent to a debugger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DebuggerEvent {
+    Paused {
+        reason: PauseReason,
+        location: SourceLocation,
+    },
+    Resumed,
+    Stack(Vec<StackFrame>),
+    Locals(Vec<Variable>),
+
+    /// The result of an `Evaluate` command: the value, or why it couldn't be evaluated.
+    Evaluated(Result<String, String>),
+
+    /// A command couldn't be carried out.
+    Error(String),
+}
+
+/// A connection to an ActionScript debugger.
+///
+/// While a script is paused, the whole player waits for commands from the
+/// debugger through [`DebuggerBackend::wait_command`].
+pub trait DebuggerBackend: Downcast {
+    /// Returns the next command sent by the debugger, if there's one already.
+    fn poll_command(&mut self) -> Option<DebuggerCommand>;
+
+    /// Waits for the next command sent by the debugger.
+    ///
+    /// Returns `None` if the debugger disconnected.
+    fn wait_command(&mut self) -> Option<DebuggerCommand>;
+
+    fn send_event(&mut self, event: DebuggerEvent);
+
+    /// Loads the SWD file holding the AVM1 debugging information of a movie, if there's one.
+    fn load_swd(&mut self, _movie_url: &str) -> Option<Vec<u8>> {
+        None
+    }
+}
+impl_downcast!(DebuggerBackend);
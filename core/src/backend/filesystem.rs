@@ -0,0 +1,463 @@
+use chrono::{DateTime, Utc};
+use downcast_rs::Downcast;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// The characters that are escaped in the components of a file URL.
+const URL_COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'.')
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'~');
+
+/// A directory that AIR applications are given access to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum FileRoot {
+    /// The directory the application was installed to, `app:/`.
+    ///
+    /// This directory is read-only.
+    Application,
+
+    /// The private storage directory of the application, `app-storage:/`.
+    ApplicationStorage,
+}
+
+impl FileRoot {
+    pub fn url_scheme(self) -> &'static str {
+        match self {
+            FileRoot::Application => "app",
+            FileRoot::ApplicationStorage => "app-storage",
+        }
+    }
+
+    pub fn is_writable(self) -> bool {
+        match self {
+            FileRoot::Application => false,
+            FileRoot::ApplicationStorage => true,
+        }
+    }
+}
+
+/// A path to a file or directory inside one of the roots.
+///
+/// A path can never point outside of its root.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FilePath {
+    root: FileRoot,
+    components: Vec<String>,
+}
+
+impl FilePath {
+    /// The root directory itself.
+    pub fn root(root: FileRoot) -> Self {
+        Self {
+            root,
+            components: Vec::new(),
+        }
+    }
+
+    /// Parses an `app:/` or `app-storage:/` URL.
+    pub fn from_url(url: &str) -> Option<Self> {
+        let (scheme, path) = url.split_once(':')?;
+        let root = [FileRoot::Application, FileRoot::ApplicationStorage]
+            .into_iter()
+            .find(|root| root.url_scheme().eq_ignore_ascii_case(scheme))?;
+        let path = percent_decode_str(path).decode_utf8().ok()?;
+        Self::root(root).resolve(&path)
+    }
+
+    pub fn to_url(&self) -> String {
+        let mut url = format!("{}:/", self.root.url_scheme());
+        let components: Vec<_> = self
+            .components
+            .iter()
+            .map(|component| utf8_percent_encode(component, URL_COMPONENT).to_string())
+            .collect();
+        url.push_str(&components.join("/"));
+        url
+    }
+
+    pub fn file_root(&self) -> FileRoot {
+        self.root
+    }
+
+    /// The names of the directories leading to this path, followed by the
+    /// name of the file itself.
+    pub fn components(&self) -> &[String] {
+        &self.components
+    }
+
+    /// The name of the file or directory, or `None` for a root.
+    pub fn name(&self) -> Option<&str> {
+        self.components.last().map(String::as_str)
+    }
+
+    /// The directory containing this path, or `None` for a root.
+    pub fn parent(&self) -> Option<Self> {
+        let (_, parent) = self.components.split_last()?;
+        Some(Self {
+            root: self.root,
+            components: parent.to_vec(),
+        })
+    }
+
+    pub fn join(&self, name: &str) -> Self {
+        let mut path = self.clone();
+        path.components.push(name.to_string());
+        path
+    }
+
+    /// Resolves a path relative to this one, as `File.resolvePath` does.
+    ///
+    /// Both `/` and `\` are separators, and a leading separator makes the
+    /// path relative to the root. Returns `None` if the path leaves the root.
+    pub fn resolve(&self, relative: &str) -> Option<Self> {
+        let mut path = if relative.starts_with(['/', '\\']) {
+            Self::root(self.root)
+        } else {
+            self.clone()
+        };
+
+        for component in relative.split(['/', '\\']) {
+            match component {
+                "" | "." => {}
+                ".." => {
+                    path.components.pop()?;
+                }
+                component => path.components.push(component.to_string()),
+            }
+        }
+
+        Some(path)
+    }
+
+    /// Whether `other` is this path or inside of it.
+    pub fn contains(&self, other: &FilePath) -> bool {
+        self.root == other.root && other.components.starts_with(&self.components)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileMetadata {
+    pub is_directory: bool,
+
+    /// The size of the file in bytes, or 0 for a directory.
+    pub size: u64,
+
+    pub creation_time: Option<DateTime<Utc>>,
+    pub modification_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Error)]
+pub enum FilesystemError {
+    #[error("File or directory does not exist")]
+    NotFound,
+
+    #[error("File or directory already exists")]
+    AlreadyExists,
+
+    #[error("Access denied")]
+    AccessDenied,
+
+    #[error("Directory is not empty")]
+    DirectoryNotEmpty,
+
+    #[error("{0}")]
+    Other(String),
+}
+
+impl From<std::io::Error> for FilesystemError {
+    fn from(error: std::io::Error) -> Self {
+        match error.kind() {
+            std::io::ErrorKind::NotFound => FilesystemError::NotFound,
+            std::io::ErrorKind::AlreadyExists => FilesystemError::AlreadyExists,
+            std::io::ErrorKind::PermissionDenied => FilesystemError::AccessDenied,
+            _ => FilesystemError::Other(error.to_string()),
+        }
+    }
+}
+
+/// The filesystem used by `flash.filesystem.File` and `FileStream` in AIR.
+///
+/// Every path is inside one of the `FileRoot`s, so a backend decides where
+/// (and whether) each root is actually stored.
+pub trait FilesystemBackend: Downcast {
+    /// The path shown to the application as `File.nativePath`.
+    fn native_path(&self, path: &FilePath) -> String;
+
+    /// Finds the path that a `File.nativePath` refers to, if it's inside of a
+    /// root.
+    fn path_from_native(&self, native_path: &str) -> Option<FilePath>;
+
+    /// Returns `None` if nothing exists at this path.
+    fn metadata(&self, path: &FilePath) -> Option<FileMetadata>;
+
+    fn read(&self, path: &FilePath) -> Result<Vec<u8>, FilesystemError>;
+
+    /// Replaces the contents of a file, creating it if it doesn't exist yet.
+    ///
+    /// The directory containing the file must already exist.
+    fn write(&mut self, path: &FilePath, data: &[u8]) -> Result<(), FilesystemError>;
+
+    /// Overwrites part of an existing file, starting at `offset`.
+    ///
+    /// The file grows if the data goes past its end. Backends that can write
+    /// in place should override this, as by default the whole file is read
+    /// and written again.
+    fn write_at(
+        &mut self,
+        path: &FilePath,
+        offset: u64,
+        data: &[u8],
+    ) -> Result<(), FilesystemError> {
+        let mut contents = self.read(path)?;
+        let start = usize::try_from(offset).map_err(|_| FilesystemError::AccessDenied)?;
+        let end = start + data.len();
+        if contents.len() < end {
+            contents.resize(end, 0);
+        }
+        contents[start..end].copy_from_slice(data);
+        self.write(path, &contents)
+    }
+
+    /// Cuts an existing file off at `length`, or pads it with zeros up to it.
+    fn set_length(&mut self, path: &FilePath, length: u64) -> Result<(), FilesystemError> {
+        let mut contents = self.read(path)?;
+        let length = usize::try_from(length).map_err(|_| FilesystemError::AccessDenied)?;
+        contents.resize(length, 0);
+        self.write(path, &contents)
+    }
+
+    /// Lists the names of the entries in a directory.
+    fn list_directory(&self, path: &FilePath) -> Result<Vec<String>, FilesystemError>;
+
+    /// Creates a directory, along with any missing parent directories.
+    fn create_directory(&mut self, path: &FilePath) -> Result<(), FilesystemError>;
+
+    /// Deletes a file or an empty directory.
+    fn delete(&mut self, path: &FilePath) -> Result<(), FilesystemError>;
+}
+impl_downcast!(FilesystemBackend);
+
+/// Deletes a file, or a directory along with everything inside of it.
+pub fn delete_recursive(
+    backend: &mut dyn FilesystemBackend,
+    path: &FilePath,
+) -> Result<(), FilesystemError> {
+    let metadata = backend.metadata(path).ok_or(FilesystemError::NotFound)?;
+    if metadata.is_directory {
+        for name in backend.list_directory(path)? {
+            delete_recursive(backend, &path.join(&name))?;
+        }
+    }
+    backend.delete(path)
+}
+
+/// Copies a file, or a directory along with everything inside of it.
+///
+/// The destination must not exist yet.
+pub fn copy_recursive(
+    backend: &mut dyn FilesystemBackend,
+    from: &FilePath,
+    to: &FilePath,
+) -> Result<(), FilesystemError> {
+    if from.contains(to) {
+        // A directory can't be copied into itself.
+        return Err(FilesystemError::AccessDenied);
+    }
+
+    let metadata = backend.metadata(from).ok_or(FilesystemError::NotFound)?;
+    if metadata.is_directory {
+        backend.create_directory(to)?;
+        for name in backend.list_directory(from)? {
+            copy_recursive(backend, &from.join(&name), &to.join(&name))?;
+        }
+        Ok(())
+    } else {
+        let data = backend.read(from)?;
+        backend.write(to, &data)
+    }
+}
+
+enum MemoryEntry {
+    File(Vec<u8>),
+    Directory,
+}
+
+/// A filesystem that only exists in memory, with empty roots.
+#[derive(Default)]
+pub struct MemoryFilesystemBackend {
+    entries: BTreeMap<FilePath, MemoryEntry>,
+}
+
+impl MemoryFilesystemBackend {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn is_directory(&self, path: &FilePath) -> bool {
+        path.components.is_empty() || matches!(self.entries.get(path), Some(MemoryEntry::Directory))
+    }
+}
+
+impl FilesystemBackend for MemoryFilesystemBackend {
+    fn native_path(&self, path: &FilePath) -> String {
+        path.to_url()
+    }
+
+    fn path_from_native(&self, native_path: &str) -> Option<FilePath> {
+        FilePath::from_url(native_path)
+    }
+
+    fn metadata(&self, path: &FilePath) -> Option<FileMetadata> {
+        let size = match self.entries.get(path) {
+            Some(MemoryEntry::File(data)) => data.len() as u64,
+            Some(MemoryEntry::Directory) => 0,
+            None if path.components.is_empty() => 0,
+            None => return None,
+        };
+        Some(FileMetadata {
+            is_directory: self.is_directory(path),
+            size,
+            creation_time: None,
+            modification_time: None,
+        })
+    }
+
+    fn read(&self, path: &FilePath) -> Result<Vec<u8>, FilesystemError> {
+        match self.entries.get(path) {
+            Some(MemoryEntry::File(data)) => Ok(data.clone()),
+            Some(MemoryEntry::Directory) => Err(FilesystemError::AccessDenied),
+            None if path.components.is_empty() => Err(FilesystemError::AccessDenied),
+            None => Err(FilesystemError::NotFound),
+        }
+    }
+
+    fn write(&mut self, path: &FilePath, data: &[u8]) -> Result<(), FilesystemError> {
+        let parent = path.parent().ok_or(FilesystemError::AccessDenied)?;
+        if !self.is_directory(&parent) {
+            return Err(FilesystemError::NotFound);
+        }
+        if self.is_directory(path) {
+            return Err(FilesystemError::AccessDenied);
+        }
+        self.entries
+            .insert(path.clone(), MemoryEntry::File(data.to_vec()));
+        Ok(())
+    }
+
+    fn list_directory(&self, path: &FilePath) -> Result<Vec<String>, FilesystemError> {
+        if !self.is_directory(path) {
+            return Err(FilesystemError::NotFound);
+        }
+        Ok(self
+            .entries
+            .keys()
+            .filter(|entry| entry.parent().as_ref() == Some(path))
+            .filter_map(|entry| entry.name().map(str::to_string))
+            .collect())
+    }
+
+    fn create_directory(&mut self, path: &FilePath) -> Result<(), FilesystemError> {
+        if let Some(parent) = path.parent() {
+            self.create_directory(&parent)?;
+            match self.entries.get(path) {
+                Some(MemoryEntry::File(_)) => return Err(FilesystemError::AlreadyExists),
+                Some(MemoryEntry::Directory) => {}
+                None => {
+                    self.entries.insert(path.clone(), MemoryEntry::Directory);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn delete(&mut self, path: &FilePath) -> Result<(), FilesystemError> {
+        if path.components.is_empty() {
+            return Err(FilesystemError::AccessDenied);
+        }
+        if self.is_directory(path) && !self.list_directory(path)?.is_empty() {
+            return Err(FilesystemError::DirectoryNotEmpty);
+        }
+        self.entries
+            .remove(path)
+            .map(|_| ())
+            .ok_or(FilesystemError::NotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn storage(path: &str) -> FilePath {
+        FilePath::root(FileRoot::ApplicationStorage)
+            .resolve(path)
+            .unwrap()
+    }
+
+    #[test]
+    fn resolve_paths() {
+        let dir = storage("saves/slot1");
+        assert_eq!(dir.components(), ["saves", "slot1"]);
+        assert_eq!(
+            dir.resolve("../slot2\\data.sav"),
+            Some(storage("saves/slot2/data.sav"))
+        );
+        assert_eq!(dir.resolve("/other"), Some(storage("other")));
+        assert_eq!(dir.resolve("./"), Some(dir.clone()));
+        assert_eq!(dir.resolve("../../.."), None);
+    }
+
+    #[test]
+    fn url_round_trip() {
+        let path = storage("my saves/data.sav");
+        assert_eq!(path.to_url(), "app-storage:/my%20saves/data.sav");
+        assert_eq!(FilePath::from_url(&path.to_url()), Some(path));
+        assert_eq!(
+            FilePath::from_url("app:/"),
+            Some(FilePath::root(FileRoot::Application))
+        );
+        assert_eq!(FilePath::from_url("file:///etc/passwd"), None);
+    }
+
+    #[test]
+    fn memory_backend() {
+        let mut backend = MemoryFilesystemBackend::new();
+        let file = storage("saves/data.sav");
+
+        assert!(matches!(
+            backend.write(&file, b"data"),
+            Err(FilesystemError::NotFound)
+        ));
+        backend.create_directory(&storage("saves")).unwrap();
+        backend.write(&file, b"data").unwrap();
+        assert_eq!(backend.read(&file).unwrap(), b"data");
+        assert_eq!(backend.metadata(&file).unwrap().size, 4);
+        backend.write_at(&file, 2, b"ta!").unwrap();
+        assert_eq!(backend.read(&file).unwrap(), b"data!");
+        backend.set_length(&file, 4).unwrap();
+        assert_eq!(backend.read(&file).unwrap(), b"data");
+        assert_eq!(
+            backend.list_directory(&storage("saves")).unwrap(),
+            ["data.sav"]
+        );
+
+        copy_recursive(&mut backend, &storage("saves"), &storage("backup")).unwrap();
+        assert_eq!(backend.read(&storage("backup/data.sav")).unwrap(), b"data");
+
+        assert!(matches!(
+            backend.delete(&storage("saves")),
+            Err(FilesystemError::DirectoryNotEmpty)
+        ));
+        delete_recursive(&mut backend, &storage("saves")).unwrap();
+        assert!(backend.metadata(&file).is_none());
+        assert_eq!(
+            backend
+                .list_directory(&FilePath::root(FileRoot::ApplicationStorage))
+                .unwrap(),
+            ["backup"]
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use downcast_rs::Downcast;
+
+/// The orientation of a printed page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PrintOrientation {
+    #[default]
+    Portrait,
+    Landscape,
+}
+
+/// The page setup chosen by the user when starting a print job.
+///
+/// All sizes are in points (1/72 of an inch), which Flash treats as pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageSettings {
+    /// The size of the whole sheet of paper.
+    pub paper_width: u32,
+    pub paper_height: u32,
+
+    /// The size of the printable area of the paper.
+    pub page_width: u32,
+    pub page_height: u32,
+
+    pub orientation: PrintOrientation,
+
+    /// The resolution that pages are rendered at, in dots per inch.
+    pub dpi: u32,
+}
+
+impl Default for PageSettings {
+    /// US Letter paper with quarter-inch margins.
+    fn default() -> Self {
+        Self {
+            paper_width: 612,
+            paper_height: 792,
+            page_width: 576,
+            page_height: 756,
+            orientation: PrintOrientation::Portrait,
+            dpi: 144,
+        }
+    }
+}
+
+/// A page that has been rendered by a print job.
+pub struct PrintPage {
+    /// The size of the rendered page in pixels, at the resolution given by
+    /// `PageSettings::dpi`.
+    pub width: u32,
+    pub height: u32,
+
+    /// The pixels of the page, as unmultiplied RGBA.
+    pub rgba: Vec<u8>,
+}
+
+pub trait PrintBackend: Downcast {
+    /// Whether printing is possible at all, as reported by `PrintJob.isSupported`.
+    fn is_supported(&self) -> bool {
+        true
+    }
+
+    /// Starts a print job, usually by showing a print dialog to the user.
+    ///
+    /// Returns the page setup to use, or `None` if the user cancelled.
+    fn start_job(&mut self) -> Option<PageSettings>;
+
+    /// Prints the pages of a finished print job.
+    ///
+    /// This is only called for jobs that have at least one page.
+    fn send_job(&mut self, settings: &PageSettings, pages: Vec<PrintPage>);
+}
+impl_downcast!(PrintBackend);
+
+/// A print backend that doesn't support printing.
+#[derive(Default)]
+pub struct NullPrintBackend;
+
+impl NullPrintBackend {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl PrintBackend for NullPrintBackend {
+    fn is_supported(&self) -> bool {
+        false
+    }
+
+    fn start_job(&mut self) -> Option<PageSettings> {
+        None
+    }
+
+    fn send_job(&mut self, _settings: &PageSettings, _pages: Vec<PrintPage>) {}
+}
@@ -76,6 +76,18 @@ pub trait UiBackend: Downcast {
         !self.clipboard_content().is_empty()
     }
 
+    /// Check if the frontend sends gamepad events, as reported by
+    /// `GameInput.isSupported`.
+    fn gamepad_input_available(&self) -> bool {
+        false
+    }
+
+    /// Check if the frontend sends touch and gesture events, as reported by
+    /// `Multitouch.supportsTouchEvents` and `Multitouch.supportsGestureEvents`.
+    fn touch_input_available(&self) -> bool {
+        false
+    }
+
     /// Sets the clipboard to the given content.
     fn set_clipboard_content(&mut self, content: String);
 
@@ -1705,6 +1705,32 @@ pub fn get_pixels_as_byte_array<'gc>(
     Ok(())
 }
 
+/// Reads a region of a bitmap as unmultiplied RGBA, for encoding it.
+///
+/// Returns the size of the region after clamping it to the bitmap.
+pub fn get_pixels_as_rgba(
+    renderer: &mut dyn RenderBackend,
+    target: BitmapDataWrapper,
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+) -> (u32, u32, Vec<u8>) {
+    let mut region = PixelRegion::for_region_i32(x, y, width, height);
+    region.clamp(target.width(), target.height());
+
+    let read = target.read_area(region, renderer);
+    let mut result = Vec::with_capacity(region.width() as usize * region.height() as usize * 4);
+    for y in region.y_min..region.y_max {
+        for x in region.x_min..region.x_max {
+            let color = read.get_pixel32_raw(x, y).to_un_multiplied_alpha();
+            result.extend_from_slice(&[color.red(), color.green(), color.blue(), color.alpha()]);
+        }
+    }
+
+    (region.width(), region.height(), result)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn set_pixels_from_byte_array<'gc>(
     mc: &Mutation<'gc>,
@@ -12,15 +12,18 @@ use crate::avm2::api_version::ApiVersion;
 use crate::avm2::object::LoaderInfoObject;
 use crate::avm2::Activation as Avm2Activation;
 use crate::avm2::TObject as _;
-use crate::avm2::{Avm2, Object as Avm2Object, SoundChannelObject};
+use crate::avm2::{Avm2, Object as Avm2Object, SoundChannelObject, SoundObject};
 use crate::backend::{
     audio::{AudioBackend, AudioManager, SoundHandle, SoundInstanceHandle},
+    filesystem::FilesystemBackend,
     log::LogBackend,
     navigator::NavigatorBackend,
+    print::PrintBackend,
     storage::StorageBackend,
     ui::UiBackend,
 };
 use crate::context_menu::ContextMenuState;
+use crate::debugger::Debugger;
 use crate::display_object::{EditText, MovieClip, SoundTransform, Stage};
 use crate::external::ExternalInterface;
 use crate::focus_tracker::FocusTracker;
@@ -33,13 +36,16 @@ use crate::net_connection::NetConnections;
 use crate::player::PostFrameCallback;
 use crate::player::{MouseData, Player};
 use crate::prelude::*;
+use crate::print_job::PrintJob;
 use crate::socket::Sockets;
+use crate::sql::SqlConnections;
 use crate::streams::StreamManager;
 use crate::string::{AvmString, StringContext};
 use crate::stub::StubCollection;
 use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::timer::Timers;
 use crate::vminterface::Instantiator;
+use crate::worker::Workers;
 use core::fmt;
 use gc_arena::{Collect, Mutation};
 use rand::rngs::SmallRng;
@@ -107,6 +113,21 @@ pub struct UpdateContext<'gc> {
     /// The storage backend, used for storing persistent state
     pub storage: &'gc mut dyn StorageBackend,
 
+    /// The filesystem backend, used by `flash.filesystem` in AIR.
+    pub filesystem: &'gc mut dyn FilesystemBackend,
+
+    /// The print backend, used to print pages from `PrintJob`.
+    pub print: &'gc mut dyn PrintBackend,
+
+    /// The print job that is currently running, if any.
+    pub print_job: &'gc mut Option<PrintJob>,
+
+    /// The SQL databases opened by `SQLConnection` in AIR.
+    pub sql_connections: &'gc mut SqlConnections<'gc>,
+
+    /// The ActionScript debugger, which may be attached.
+    pub debugger: &'gc mut Debugger,
+
     /// The logging backend, used for trace output capturing.
     ///
     /// **DO NOT** use this field directly, use the `avm_trace` method instead.
@@ -218,6 +239,9 @@ pub struct UpdateContext<'gc> {
 
     pub local_connections: &'gc mut LocalConnections<'gc>,
 
+    /// The worker that this player runs, and the objects it shares with other workers.
+    pub workers: &'gc mut Workers<'gc>,
+
     /// Dynamic root for allowing handles to GC objects to exist outside of the GC.
     pub dynamic_root: gc_arena::DynamicRootSet<'gc>,
 
@@ -264,6 +288,10 @@ impl<'gc> UpdateContext<'gc> {
             .start_sound(self.audio, sound, settings, owner, avm1_object)
     }
 
+    pub fn start_dynamic_sound(&mut self, object: SoundObject<'gc>) -> Option<SoundInstanceHandle> {
+        self.audio_manager.start_dynamic_sound(self.audio, object)
+    }
+
     pub fn attach_avm2_sound_channel(
         &mut self,
         instance: SoundInstanceHandle,
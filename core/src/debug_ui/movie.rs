@@ -2,9 +2,10 @@ use crate::character::Character;
 use crate::context::UpdateContext;
 use crate::debug_ui::{ItemToSave, Message};
 use crate::tag_utils::SwfMovie;
-use egui::{CollapsingHeader, Grid, Id, TextEdit, Ui, Window};
+use egui::{CollapsingHeader, ComboBox, Grid, Id, TextEdit, Ui, Window};
+use std::ops::Range;
 use std::sync::Arc;
-use swf::CharacterId;
+use swf::{CharacterId, Tag};
 use url::Url;
 
 #[derive(Debug, Eq, PartialEq, Hash, Default, Copy, Clone)]
@@ -12,6 +13,7 @@ enum Panel {
     #[default]
     Information,
     Characters,
+    Actions,
 }
 
 #[derive(Debug, Default)]
@@ -66,6 +68,19 @@ impl MovieListWindow {
 pub struct MovieWindow {
     open_panel: Panel,
     character_search: String,
+    action_blocks: Option<Vec<ActionBlock>>,
+    selected_action_block: usize,
+    decompile_actions: bool,
+    /// The text of the selected action block, and whether it was decompiled.
+    action_text: Option<(usize, bool, String)>,
+}
+
+/// A block of AVM1 code in a movie.
+#[derive(Debug)]
+struct ActionBlock {
+    description: String,
+    /// The range of the code in the movie data.
+    range: Range<usize>,
 }
 
 impl MovieWindow {
@@ -95,12 +110,17 @@ impl MovieWindow {
                             );
                         }
                     }
+
+                    if !movie.is_action_script_3() && !movie.data().is_empty() {
+                        ui.selectable_value(&mut self.open_panel, Panel::Actions, "Actions");
+                    }
                 });
                 ui.separator();
 
                 match self.open_panel {
                     Panel::Information => self.show_information(ui, &movie, messages),
                     Panel::Characters => self.show_characters(ui, context, &movie),
+                    Panel::Actions => self.show_actions(ui, &movie),
                 }
             });
         keep_open
@@ -151,6 +171,51 @@ impl MovieWindow {
             });
     }
 
+    fn show_actions(&mut self, ui: &mut Ui, movie: &Arc<SwfMovie>) {
+        let blocks = self
+            .action_blocks
+            .get_or_insert_with(|| action_blocks(movie));
+        if blocks.is_empty() {
+            ui.weak("(No actions)");
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            ComboBox::from_id_salt(ui.id().with("action_block"))
+                .width(300.0)
+                .show_index(ui, &mut self.selected_action_block, blocks.len(), |i| {
+                    blocks[i].description.clone()
+                });
+            ui.selectable_value(&mut self.decompile_actions, false, "Disassembly");
+            ui.selectable_value(&mut self.decompile_actions, true, "Decompiled");
+        });
+
+        let Some(block) = blocks.get(self.selected_action_block) else {
+            return;
+        };
+        let key = (self.selected_action_block, self.decompile_actions);
+        if self
+            .action_text
+            .as_ref()
+            .is_none_or(|(block, decompile, _)| (*block, *decompile) != key)
+        {
+            let actions = &movie.data()[block.range.clone()];
+            let text = if self.decompile_actions {
+                swf::avm1::disasm::decompile(actions, movie.version())
+            } else {
+                swf::avm1::disasm::disassemble(actions, movie.version())
+            };
+            self.action_text = Some((key.0, key.1, text));
+        }
+
+        if let Some((_, _, text)) = &self.action_text {
+            TextEdit::multiline(&mut text.as_str())
+                .code_editor()
+                .desired_width(f32::INFINITY)
+                .show(ui);
+        }
+    }
+
     fn show_information(
         &mut self,
         ui: &mut Ui,
@@ -246,6 +311,71 @@ impl MovieWindow {
     }
 }
 
+/// Finds the blocks of AVM1 code in the tags of a movie, including those of sprites.
+fn action_blocks(movie: &SwfMovie) -> Vec<ActionBlock> {
+    let mut reader = swf::read::Reader::new(movie.data(), movie.version());
+    let mut tags = Vec::new();
+    while let Ok(tag) = reader.read_tag() {
+        if tag == Tag::End {
+            break;
+        }
+        tags.push(tag);
+    }
+
+    let mut blocks = Vec::new();
+    collect_action_blocks(movie.data(), &tags, "", &mut blocks);
+    blocks
+}
+
+fn collect_action_blocks(data: &[u8], tags: &[Tag], owner: &str, blocks: &mut Vec<ActionBlock>) {
+    let push = |blocks: &mut Vec<ActionBlock>, description: String, actions: &[u8]| {
+        let start = actions.as_ptr() as usize - data.as_ptr() as usize;
+        blocks.push(ActionBlock {
+            description,
+            range: start..start + actions.len(),
+        });
+    };
+
+    let mut frame = 1;
+    for tag in tags {
+        match tag {
+            Tag::ShowFrame => frame += 1,
+            Tag::DoAction(actions) => push(blocks, format!("{owner}Frame {frame}"), actions),
+            Tag::DoInitAction { id, action_data } => push(
+                blocks,
+                format!("Init actions of character {id}"),
+                action_data,
+            ),
+            Tag::DefineButton(button) | Tag::DefineButton2(button) => {
+                for action in &button.actions {
+                    push(
+                        blocks,
+                        format!("Button {} {:?}", button.id, action.conditions),
+                        action.action_data,
+                    );
+                }
+            }
+            Tag::PlaceObject(place_object) => {
+                for clip_action in place_object.clip_actions.iter().flatten() {
+                    push(
+                        blocks,
+                        format!(
+                            "{owner}Frame {frame}, depth {} {:?}",
+                            place_object.depth, clip_action.events
+                        ),
+                        clip_action.action_data,
+                    );
+                }
+            }
+            Tag::DefineSprite(sprite) => {
+                let owner = format!("Sprite {}, ", sprite.id);
+                collect_action_blocks(data, &sprite.tags, &owner, blocks);
+            }
+            _ => {}
+        }
+    }
+}
+
 pub fn movie_name(movie: &Arc<SwfMovie>) -> String {
     format!("SWF {:p}", Arc::as_ptr(movie))
 }
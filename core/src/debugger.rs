@@ -0,0 +1,387 @@
+//! The ActionScript debugger.
+//!
+//! A debugger attaches to the player through a [`DebuggerBackend`]. Both AVMs
+//! report every line of source code that starts running, and pause there when
+//! a breakpoint or a step asks for it. While a script is paused, the player
+//! waits for commands from the debugger, which can inspect the stack and the
+//! variables of the paused script and of its callers.
+//!
+//! Lines are known from the `debugfile` and `debugline` instructions of AVM2
+//! bytecode, and from the SWD file of AVM1 movies with a `DebugID` tag.
+
+mod swd;
+
+use crate::backend::debugger::{
+    DebuggerBackend, DebuggerCommand, DebuggerEvent, PauseReason, SourceLocation, StackFrame,
+    Variable,
+};
+use std::collections::{HashMap, HashSet};
+use swd::Swd;
+use swf::DebugId;
+
+/// Where a step requested by the debugger ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    /// At the next line.
+    In,
+
+    /// At the next line with a call depth of at most this.
+    Over(usize),
+
+    /// At the next line with a call depth below this.
+    Out(usize),
+}
+
+/// The state of the debugger, shared by both AVMs.
+pub struct Debugger {
+    backend: Option<Box<dyn DebuggerBackend>>,
+    breakpoints: HashSet<SourceLocation>,
+    step: Option<Step>,
+    pause_requested: bool,
+
+    /// The SWD files of AVM1 movies, by the URL of the movie.
+    swds: HashMap<String, Swd>,
+}
+
+impl Debugger {
+    pub fn new(backend: Option<Box<dyn DebuggerBackend>>) -> Self {
+        Self {
+            backend,
+            breakpoints: HashSet::new(),
+            step: None,
+            pause_requested: false,
+            swds: HashMap::new(),
+        }
+    }
+
+    #[inline]
+    pub fn is_attached(&self) -> bool {
+        self.backend.is_some()
+    }
+
+    fn detach(&mut self) {
+        tracing::info!("Debugger disconnected");
+        self.backend = None;
+        self.breakpoints.clear();
+        self.step = None;
+        self.pause_requested = false;
+    }
+
+    fn send(&mut self, event: DebuggerEvent) {
+        if let Some(backend) = &mut self.backend {
+            backend.send_event(event);
+        }
+    }
+
+    fn wait_command(&mut self) -> Option<DebuggerCommand> {
+        let command = self.backend.as_mut()?.wait_command();
+        if command.is_none() {
+            self.detach();
+        }
+        command
+    }
+
+    /// Handles the commands sent by the debugger while scripts are running.
+    pub fn poll(&mut self) {
+        while let Some(command) = self.backend.as_mut().and_then(|b| b.poll_command()) {
+            match command {
+                DebuggerCommand::Pause => self.pause_requested = true,
+                DebuggerCommand::SetBreakpoint(_) | DebuggerCommand::RemoveBreakpoint(_) => {
+                    self.update_breakpoints(command)
+                }
+                DebuggerCommand::Continue
+                | DebuggerCommand::StepIn
+                | DebuggerCommand::StepOver
+                | DebuggerCommand::StepOut => {}
+                DebuggerCommand::GetStack
+                | DebuggerCommand::GetLocals { .. }
+                | DebuggerCommand::Evaluate { .. } => {
+                    self.send(DebuggerEvent::Error("No script is paused".to_string()))
+                }
+            }
+        }
+    }
+
+    fn update_breakpoints(&mut self, command: DebuggerCommand) {
+        match command {
+            DebuggerCommand::SetBreakpoint(location) => {
+                self.breakpoints.insert(location);
+            }
+            DebuggerCommand::RemoveBreakpoint(location) => {
+                self.breakpoints.remove(&location);
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether a line may have to be paused at, before knowing its file.
+    ///
+    /// This lets the AVMs skip looking up the file of most lines.
+    #[inline]
+    pub fn may_pause_at_line(&self, line: u32) -> bool {
+        self.is_attached()
+            && (self.pause_requested
+                || self.step.is_some()
+                || self.breakpoints.iter().any(|b| b.line == line))
+    }
+
+    /// Whether to pause at a line that starts running, at the given call depth.
+    pub fn pause_reason(&self, location: &SourceLocation, depth: usize) -> Option<PauseReason> {
+        if !self.is_attached() {
+            return None;
+        }
+        if self.breakpoints.contains(location) {
+            return Some(PauseReason::Breakpoint);
+        }
+        if self.pause_requested {
+            return Some(PauseReason::Requested);
+        }
+        match self.step? {
+            Step::In => Some(PauseReason::Step),
+            Step::Over(step_depth) if depth <= step_depth => Some(PauseReason::Step),
+            Step::Out(step_depth) if depth < step_depth => Some(PauseReason::Step),
+            _ => None,
+        }
+    }
+
+    /// Loads the SWD file of an AVM1 movie with the given `DebugID`.
+    pub fn load_swd(&mut self, movie_url: &str, debug_id: &DebugId) {
+        let Some(backend) = &mut self.backend else {
+            return;
+        };
+        let Some(swd) = backend
+            .load_swd(movie_url)
+            .and_then(|data| Swd::parse(&data))
+        else {
+            tracing::warn!("No valid SWD file for {movie_url}, its lines can't be debugged");
+            return;
+        };
+        if swd.debug_id() != Some(debug_id) {
+            tracing::warn!("The SWD file for {movie_url} belongs to another movie");
+            return;
+        }
+        self.swds.insert(movie_url.to_string(), swd);
+    }
+
+    /// The line starting at the action at the given offset of an AVM1 movie, if any.
+    #[inline]
+    pub fn avm1_line(&self, movie_url: &str, offset: u32) -> Option<&SourceLocation> {
+        if self.swds.is_empty() {
+            return None;
+        }
+        self.swds.get(movie_url)?.line_at(offset)
+    }
+}
+
+/// A paused script, as seen by the debugger.
+pub trait DebugTarget {
+    fn debugger(&mut self) -> &mut Debugger;
+
+    /// The calls on the stack, innermost first.
+    fn stack(&mut self) -> Vec<StackFrame>;
+
+    fn locals(&mut self, frame: u32) -> Result<Vec<Variable>, String>;
+
+    /// Evaluates an expression, without running any ActionScript code.
+    fn evaluate(&mut self, frame: u32, expression: &str) -> Result<String, String>;
+}
+
+/// Pauses a script, until the debugger resumes it.
+///
+/// The time spent paused shouldn't count towards the script timeout, which
+/// is up to the caller.
+pub fn pause(
+    target: &mut dyn DebugTarget,
+    reason: PauseReason,
+    location: SourceLocation,
+    depth: usize,
+) {
+    let debugger = target.debugger();
+    debugger.step = None;
+    debugger.pause_requested = false;
+    debugger.send(DebuggerEvent::Paused { reason, location });
+
+    while let Some(command) = target.debugger().wait_command() {
+        let event = match command {
+            DebuggerCommand::SetBreakpoint(_) | DebuggerCommand::RemoveBreakpoint(_) => {
+                target.debugger().update_breakpoints(command);
+                continue;
+            }
+            DebuggerCommand::Pause => continue,
+            DebuggerCommand::Continue => break,
+            DebuggerCommand::StepIn => {
+                target.debugger().step = Some(Step::In);
+                break;
+            }
+            DebuggerCommand::StepOver => {
+                target.debugger().step = Some(Step::Over(depth));
+                break;
+            }
+            DebuggerCommand::StepOut => {
+                target.debugger().step = Some(Step::Out(depth));
+                break;
+            }
+            DebuggerCommand::GetStack => DebuggerEvent::Stack(target.stack()),
+            DebuggerCommand::GetLocals { frame } => match target.locals(frame) {
+                Ok(locals) => DebuggerEvent::Locals(locals),
+                Err(error) => DebuggerEvent::Error(error),
+            },
+            DebuggerCommand::Evaluate { frame, expression } => {
+                DebuggerEvent::Evaluated(target.evaluate(frame, &expression))
+            }
+        };
+        target.debugger().send(event);
+    }
+
+    target.debugger().send(DebuggerEvent::Resumed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+
+    /// A debugger that sends a fixed list of commands, and then disconnects.
+    struct ScriptedBackend {
+        commands: VecDeque<DebuggerCommand>,
+        events: Rc<RefCell<Vec<DebuggerEvent>>>,
+    }
+
+    impl DebuggerBackend for ScriptedBackend {
+        fn poll_command(&mut self) -> Option<DebuggerCommand> {
+            self.commands.pop_front()
+        }
+
+        fn wait_command(&mut self) -> Option<DebuggerCommand> {
+            self.commands.pop_front()
+        }
+
+        fn send_event(&mut self, event: DebuggerEvent) {
+            self.events.borrow_mut().push(event);
+        }
+    }
+
+    struct Target(Debugger);
+
+    impl DebugTarget for Target {
+        fn debugger(&mut self) -> &mut Debugger {
+            &mut self.0
+        }
+
+        fn stack(&mut self) -> Vec<StackFrame> {
+            vec![StackFrame {
+                name: "run()".to_string(),
+                location: Some(location(3)),
+            }]
+        }
+
+        fn locals(&mut self, frame: u32) -> Result<Vec<Variable>, String> {
+            Err(format!("No frame {frame}"))
+        }
+
+        fn evaluate(&mut self, _frame: u32, expression: &str) -> Result<String, String> {
+            Ok(expression.to_uppercase())
+        }
+    }
+
+    fn location(line: u32) -> SourceLocation {
+        SourceLocation {
+            file: "Main.as".to_string(),
+            line,
+        }
+    }
+
+    fn target(commands: Vec<DebuggerCommand>) -> (Target, Rc<RefCell<Vec<DebuggerEvent>>>) {
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let backend = ScriptedBackend {
+            commands: commands.into(),
+            events: events.clone(),
+        };
+        (Target(Debugger::new(Some(Box::new(backend)))), events)
+    }
+
+    #[test]
+    fn pause_answers_commands_until_resumed() {
+        let (mut target, events) = target(vec![
+            DebuggerCommand::SetBreakpoint(location(7)),
+            DebuggerCommand::GetStack,
+            DebuggerCommand::GetLocals { frame: 1 },
+            DebuggerCommand::Evaluate {
+                frame: 0,
+                expression: "x".to_string(),
+            },
+            DebuggerCommand::Continue,
+            DebuggerCommand::GetStack,
+        ]);
+        target.0.pause_requested = true;
+
+        pause(&mut target, PauseReason::Requested, location(3), 1);
+
+        assert_eq!(
+            *events.borrow(),
+            vec![
+                DebuggerEvent::Paused {
+                    reason: PauseReason::Requested,
+                    location: location(3),
+                },
+                DebuggerEvent::Stack(target.stack()),
+                DebuggerEvent::Error("No frame 1".to_string()),
+                DebuggerEvent::Evaluated(Ok("X".to_string())),
+                DebuggerEvent::Resumed,
+            ]
+        );
+        assert!(target.0.is_attached());
+        assert!(!target.0.pause_requested);
+        assert_eq!(target.0.pause_reason(&location(3), 1), None);
+        assert_eq!(
+            target.0.pause_reason(&location(7), 5),
+            Some(PauseReason::Breakpoint)
+        );
+    }
+
+    #[test]
+    fn pause_resumes_when_debugger_disconnects() {
+        let (mut target, events) = target(vec![DebuggerCommand::StepIn]);
+        target.0.step = Some(Step::In);
+        pause(&mut target, PauseReason::Step, location(3), 1);
+        assert_eq!(target.0.step, Some(Step::In));
+        assert!(target.0.is_attached());
+
+        pause(&mut target, PauseReason::Step, location(4), 1);
+        assert!(!target.0.is_attached());
+        assert_eq!(target.0.pause_reason(&location(5), 1), None);
+        assert_eq!(
+            events.borrow().last(),
+            Some(&DebuggerEvent::Paused {
+                reason: PauseReason::Step,
+                location: location(4),
+            })
+        );
+    }
+
+    #[test]
+    fn step_depth() {
+        let (mut target, _) = target(vec![DebuggerCommand::StepOver, DebuggerCommand::StepOut]);
+
+        pause(&mut target, PauseReason::Breakpoint, location(3), 2);
+        assert_eq!(target.0.pause_reason(&location(10), 3), None);
+        assert_eq!(
+            target.0.pause_reason(&location(4), 2),
+            Some(PauseReason::Step)
+        );
+        assert_eq!(
+            target.0.pause_reason(&location(20), 1),
+            Some(PauseReason::Step)
+        );
+
+        pause(&mut target, PauseReason::Step, location(4), 2);
+        assert_eq!(target.0.pause_reason(&location(10), 3), None);
+        assert_eq!(target.0.pause_reason(&location(5), 2), None);
+        assert_eq!(
+            target.0.pause_reason(&location(20), 1),
+            Some(PauseReason::Step)
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! SWD files, which hold the debugging information of AVM1 movies.
+//!
+//! They're written next to a movie by the Flash authoring tool when publishing
+//! for debugging, and are tied to it by the `DebugID` tag. They map offsets of
+//! actions in the SWF file to lines of the source scripts.
+
+use crate::backend::debugger::SourceLocation;
+use std::collections::HashMap;
+use swf::DebugId;
+
+const TAG_SCRIPT: u32 = 0;
+const TAG_OFFSET: u32 = 1;
+const TAG_BREAKPOINT: u32 = 2;
+const TAG_DEBUG_ID: u32 = 3;
+const TAG_REGISTERS: u32 = 5;
+
+pub struct Swd {
+    debug_id: Option<DebugId>,
+
+    /// The source location of the first action of each line, by its offset in the SWF file.
+    lines: HashMap<u32, SourceLocation>,
+}
+
+impl Swd {
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let mut reader = SwdReader(data.strip_prefix(b"FWD")?);
+        let _version = reader.read_u8()?;
+
+        let mut debug_id = None;
+        let mut scripts = HashMap::new();
+        let mut offsets = Vec::new();
+        while !reader.0.is_empty() {
+            match reader.read_u32()? {
+                TAG_SCRIPT => {
+                    let id = reader.read_u32()?;
+                    let _bitmap = reader.read_u32()?;
+                    let name = reader.read_str()?;
+                    let _source = reader.read_str()?;
+                    scripts.insert(id, name);
+                }
+                TAG_OFFSET => {
+                    let id = reader.read_u32()?;
+                    let line = reader.read_u32()?;
+                    let offset = reader.read_u32()?;
+                    offsets.push((id, line, offset));
+                }
+                TAG_BREAKPOINT => {
+                    // Breakpoints saved by the authoring tool are set by the debugger instead.
+                    let _offset = reader.read_u32()?;
+                }
+                TAG_DEBUG_ID => debug_id = Some(reader.read_bytes(16)?.try_into().ok()?),
+                TAG_REGISTERS => {
+                    let _offset = reader.read_u32()?;
+                    for _ in 0..reader.read_u8()? {
+                        let _register = reader.read_u8()?;
+                        let _name = reader.read_str()?;
+                    }
+                }
+                tag => {
+                    tracing::warn!("Unknown SWD tag {tag}");
+                    return None;
+                }
+            }
+        }
+
+        let lines = offsets
+            .into_iter()
+            .filter_map(|(id, line, offset)| {
+                let file = scripts.get(&id)?.clone();
+                Some((offset, SourceLocation { file, line }))
+            })
+            .collect();
+        Some(Self { debug_id, lines })
+    }
+
+    pub fn debug_id(&self) -> Option<&DebugId> {
+        self.debug_id.as_ref()
+    }
+
+    /// The line starting at the action at the given offset of the SWF file, if any.
+    pub fn line_at(&self, offset: u32) -> Option<&SourceLocation> {
+        self.lines.get(&offset)
+    }
+}
+
+struct SwdReader<'a>(&'a [u8]);
+
+impl<'a> SwdReader<'a> {
+    fn read_bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Some(bytes)
+    }
+
+    fn read_u8(&mut self) -> Option<u8> {
+        Some(self.read_bytes(1)?[0])
+    }
+
+    fn read_u32(&mut self) -> Option<u32> {
+        Some(u32::from_le_bytes(self.read_bytes(4)?.try_into().ok()?))
+    }
+
+    fn read_str(&mut self) -> Option<String> {
+        let len = self.0.iter().position(|&b| b == 0)?;
+        let string = String::from_utf8_lossy(self.read_bytes(len)?).into_owned();
+        self.read_u8()?;
+        Some(string)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_swd() {
+        let mut data = b"FWD\x07".to_vec();
+        data.extend_from_slice(&TAG_SCRIPT.to_le_bytes());
+        data.extend_from_slice(&7u32.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(b"Actions for Scene 1: Frame 1\0trace(1);\0");
+        data.extend_from_slice(&TAG_OFFSET.to_le_bytes());
+        data.extend_from_slice(&7u32.to_le_bytes());
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&120u32.to_le_bytes());
+        data.extend_from_slice(&TAG_DEBUG_ID.to_le_bytes());
+        data.extend_from_slice(&[0xAB; 16]);
+
+        let swd = Swd::parse(&data).unwrap();
+        assert_eq!(swd.debug_id(), Some(&[0xAB; 16]));
+        assert_eq!(
+            swd.line_at(120),
+            Some(&SourceLocation {
+                file: "Actions for Scene 1: Frame 1".to_string(),
+                line: 1,
+            })
+        );
+        assert_eq!(swd.line_at(121), None);
+        assert!(Swd::parse(b"FWS\x07").is_none());
+    }
+}
@@ -168,6 +168,9 @@ pub struct EditTextData<'gc> {
     /// Whether this EditText represents an AVM2 TextLine.
     is_tlf: bool,
 
+    /// The graphics of the `GraphicElement`s in this TextLine, drawn over its text.
+    tlf_graphics: Vec<DisplayObject<'gc>>,
+
     /// Restrict what characters the user may input.
     #[collect(require_static)]
     restrict: EditTextRestrict,
@@ -178,6 +181,15 @@ pub struct EditTextData<'gc> {
 }
 
 impl EditTextData<'_> {
+    /// How far the reported position of this text field is from its origin.
+    fn bounds_offset(&self) -> (Twips, Twips) {
+        if self.is_tlf {
+            (Twips::ZERO, Twips::ZERO)
+        } else {
+            (self.bounds.x_min, self.bounds.y_min)
+        }
+    }
+
     fn vertical_scroll_offset(&self) -> Twips {
         if self.scroll > 1 {
             let lines = self.layout.lines();
@@ -202,7 +214,7 @@ impl<'gc> EditText<'gc> {
     ///
     /// See <https://open-flash.github.io/mirrors/as2-language-reference/TextFormat.html#getTextExtent()>.
     /// See <https://help.adobe.com/en_US/FlashPlatform/reference/actionscript/3/flash/text/TextLineMetrics.html>.
-    const GUTTER: Twips = Twips::new(40);
+    pub const GUTTER: Twips = Twips::new(40);
 
     /// Creates a new `EditText` from an SWF `DefineEditText` tag.
     pub fn from_swf_tag(
@@ -307,6 +319,7 @@ impl<'gc> EditText<'gc> {
                 max_chars: swf_tag.max_length().unwrap_or_default() as i32,
                 mouse_wheel_enabled: true,
                 is_tlf: false,
+                tlf_graphics: Vec::new(),
                 restrict: EditTextRestrict::allow_all(),
                 last_click: None,
                 layout_debug_boxes_flags: LayoutDebugBoxesFlag::empty(),
@@ -623,6 +636,46 @@ impl<'gc> EditText<'gc> {
             .set(EditTextFlag::HTML, is_html);
     }
 
+    /// Replace the text of a TextLine, along with the format of each part of it.
+    ///
+    /// Unlike calling `set_text_format` for each part, this only lays out the
+    /// text once.
+    pub fn set_tlf_text(
+        self,
+        text: &WStr,
+        formats: &[(usize, usize, TextFormat)],
+        context: &mut UpdateContext<'gc>,
+    ) {
+        let mut edit_text = self.0.write(context.gc_context);
+        let default_format = edit_text.text_spans.default_format().clone();
+        edit_text.text_spans = FormatSpans::from_text(text.into(), default_format);
+        for (from, to, format) in formats {
+            edit_text.text_spans.set_text_format(*from, *to, format);
+        }
+        drop(edit_text);
+
+        self.relayout(context);
+    }
+
+    /// Set the graphics shown in a TextLine.
+    ///
+    /// Each graphic is drawn with its own position relative to the origin of
+    /// the line.
+    pub fn set_tlf_graphics(self, gc_context: &Mutation<'gc>, graphics: Vec<DisplayObject<'gc>>) {
+        self.0.write(gc_context).tlf_graphics = graphics;
+        self.invalidate_cached_bitmap(gc_context);
+    }
+
+    /// Set the area covered by a TextLine, relative to the origin of its baseline.
+    pub fn set_tlf_bounds(self, context: &mut UpdateContext<'gc>, bounds: Rectangle<Twips>) {
+        let mut edit_text = self.0.write(context.gc_context);
+        edit_text.requested_width = bounds.width();
+        edit_text.requested_height = bounds.height();
+        edit_text.bounds = bounds;
+        drop(edit_text);
+        self.relayout(context);
+    }
+
     pub fn is_tlf(self) -> bool {
         self.0.read().is_tlf
     }
@@ -1031,11 +1084,17 @@ impl<'gc> EditText<'gc> {
 
                     // Update caret position
                     if let Some(caret) = caret {
+                        // Right-to-left text starts at the right of each glyph.
+                        let (glyph_start, glyph_end) = if params.right_to_left() {
+                            (x + advance, x)
+                        } else {
+                            (x, x + advance)
+                        };
                         if pos == caret {
-                            caret_x = x;
+                            caret_x = glyph_start;
                         } else if caret > 0 && pos == caret - 1 {
                             // The caret may be rendered at the end, after all glyphs.
-                            caret_x = x + advance;
+                            caret_x = glyph_end;
                         }
                     }
                 },
@@ -1332,14 +1391,23 @@ impl<'gc> EditText<'gc> {
         let line = text.layout.lines().get(line_index)?;
 
         // ...then find the box within that line that is the closest match to the X position.
+        // Boxes of right-to-left text may be out of order, so look at all of them.
         let mut closest_layout_box: Option<&LayoutBox<'gc>> = None;
-        for layout_box in line.boxes_iter() {
-            if layout_box.is_text_box() {
-                if position.x >= layout_box.bounds().offset_x() || closest_layout_box.is_none() {
-                    closest_layout_box = Some(layout_box);
-                } else {
-                    break;
+        for layout_box in line.boxes_iter().filter(|b| b.is_text_box()) {
+            let offset_x = layout_box.bounds().offset_x();
+            let is_closer = match closest_layout_box {
+                None => true,
+                Some(closest) => {
+                    let closest_x = closest.bounds().offset_x();
+                    if position.x >= offset_x {
+                        closest_x > position.x || offset_x >= closest_x
+                    } else {
+                        closest_x > position.x && offset_x < closest_x
+                    }
                 }
+            };
+            if is_closer {
+                closest_layout_box = Some(layout_box);
             }
         }
 
@@ -1352,7 +1420,10 @@ impl<'gc> EditText<'gc> {
             if let Some((text, _tf, font, params, color)) =
                 layout_box.as_renderable_text(text.text_spans.text())
             {
-                let mut result = 0;
+                // Right-to-left glyphs come last character first, so the
+                // caret goes after a character when on its left half.
+                let right_to_left = params.right_to_left();
+                let mut result = if right_to_left { text.len() } else { 0 };
                 let baseline_adjustment =
                     font.get_baseline_for_height(params.height()) - params.height();
                 font.evaluate(
@@ -1361,7 +1432,8 @@ impl<'gc> EditText<'gc> {
                     params,
                     |pos, _transform, _glyph: &Glyph, advance, x| {
                         if local_position.x >= x {
-                            if local_position.x > x + (advance / 2) {
+                            let is_right_half = local_position.x > x + (advance / 2);
+                            if is_right_half != right_to_left {
                                 result = string_utils::next_char_boundary(text, pos);
                             } else {
                                 result = pos;
@@ -2221,15 +2293,16 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
     }
 
     // The returned position x and y of a text field is offset by the text bounds.
+    // A TextLine's position is the origin of its baseline instead, so it isn't.
     fn x(&self) -> Twips {
         let edit_text = self.0.read();
-        let offset = edit_text.bounds.x_min;
+        let offset = edit_text.bounds_offset().0;
         edit_text.base.base.x() + offset
     }
 
     fn set_x(&self, gc_context: &Mutation<'gc>, x: Twips) {
         let mut edit_text = self.0.write(gc_context);
-        let offset = edit_text.bounds.x_min;
+        let offset = edit_text.bounds_offset().0;
         edit_text.base.base.set_x(x - offset);
         drop(edit_text);
         self.invalidate_cached_bitmap(gc_context);
@@ -2237,13 +2310,13 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
 
     fn y(&self) -> Twips {
         let edit_text = self.0.read();
-        let offset = edit_text.bounds.y_min;
+        let offset = edit_text.bounds_offset().1;
         edit_text.base.base.y() + offset
     }
 
     fn set_y(&self, gc_context: &Mutation<'gc>, y: Twips) {
         let mut edit_text = self.0.write(gc_context);
-        let offset = edit_text.bounds.y_min;
+        let offset = edit_text.bounds_offset().1;
         edit_text.base.base.set_y(y - offset);
         drop(edit_text);
         self.invalidate_cached_bitmap(gc_context);
@@ -2380,6 +2453,10 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         context.commands.pop_mask();
 
         context.transform_stack.pop();
+
+        for graphic in &edit_text.tlf_graphics {
+            graphic.render(context);
+        }
     }
 
     fn allow_as_mask(&self) -> bool {
@@ -35,8 +35,7 @@ pub struct GraphicData<'gc> {
     static_data: gc_arena::Gc<'gc, GraphicStatic>,
     avm2_object: Option<Avm2Object<'gc>>,
     /// This is lazily allocated on demand, to make `GraphicData` smaller in the common case.
-    #[collect(require_static)]
-    drawing: Option<Box<Drawing>>,
+    drawing: Option<Box<Drawing<'gc>>>,
 }
 
 impl<'gc> Graphic<'gc> {
@@ -102,7 +101,19 @@ impl<'gc> Graphic<'gc> {
         ))
     }
 
-    pub fn drawing_mut(&self, gc_context: &Mutation<'gc>) -> RefMut<'_, Drawing> {
+    pub fn drawing(&self) -> Option<Ref<'_, Drawing<'gc>>> {
+        let read = Ref::map(self.0.read(), |r| &r.drawing);
+        Ref::filter_map(read, Option::as_deref).ok()
+    }
+
+    /// The shape that this graphic was defined by in the SWF.
+    ///
+    /// This is empty for graphics that were created by ActionScript.
+    pub fn swf_shape(&self) -> Ref<'_, swf::Shape> {
+        Ref::map(self.0.read(), |r| &r.static_data.shape)
+    }
+
+    pub fn drawing_mut(&self, gc_context: &Mutation<'gc>) -> RefMut<'_, Drawing<'gc>> {
         RefMut::map(self.0.write(gc_context), |w| {
             &mut **w.drawing.get_or_insert_with(Default::default)
         })
@@ -257,7 +268,7 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
         self.0.write(context.gc_context).avm2_object = Some(to);
     }
 
-    fn as_drawing(&self, gc_context: &Mutation<'gc>) -> Option<RefMut<'_, Drawing>> {
+    fn as_drawing(&self, gc_context: &Mutation<'gc>) -> Option<RefMut<'_, Drawing<'gc>>> {
         Some(self.drawing_mut(gc_context))
     }
 }
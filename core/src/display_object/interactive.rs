@@ -474,6 +474,80 @@ pub trait TInteractiveObject<'gc>:
 
                 Avm2::dispatch_event(activation.context, avm2_event, target).into()
             }
+            ClipEvent::TouchBegin {
+                touch_point_id,
+                primary,
+                position,
+            }
+            | ClipEvent::TouchMove {
+                touch_point_id,
+                primary,
+                position,
+            }
+            | ClipEvent::TouchEnd {
+                touch_point_id,
+                primary,
+                position,
+            }
+            | ClipEvent::TouchTap {
+                touch_point_id,
+                primary,
+                position,
+            } => {
+                let event_type = match event {
+                    ClipEvent::TouchBegin { .. } => "touchBegin",
+                    ClipEvent::TouchMove { .. } => "touchMove",
+                    ClipEvent::TouchEnd { .. } => "touchEnd",
+                    _ => "touchTap",
+                };
+                let avm2_event = Avm2EventObject::touch_event(
+                    &mut activation,
+                    event_type,
+                    self.as_displayobject(),
+                    touch_point_id,
+                    primary,
+                    position,
+                );
+
+                Avm2::dispatch_event(activation.context, avm2_event, target).into()
+            }
+            ClipEvent::Gesture {
+                gesture,
+                phase,
+                position,
+            } => {
+                let avm2_event = Avm2EventObject::transform_gesture_event(
+                    &mut activation,
+                    self.as_displayobject(),
+                    gesture,
+                    phase,
+                    position,
+                );
+
+                Avm2::dispatch_event(activation.context, avm2_event, target).into()
+            }
+            ClipEvent::TwoFingerTap { position } => {
+                let avm2_event = Avm2EventObject::two_finger_tap_gesture_event(
+                    &mut activation,
+                    self.as_displayobject(),
+                    position,
+                );
+
+                Avm2::dispatch_event(activation.context, avm2_event, target).into()
+            }
+            ClipEvent::PressAndTap {
+                position,
+                tap_position,
+            } => {
+                let avm2_event = Avm2EventObject::press_and_tap_gesture_event(
+                    &mut activation,
+                    self.as_displayobject(),
+                    position,
+                    tap_position,
+                );
+
+                Avm2::dispatch_event(activation.context, avm2_event, target).into()
+            }
             ClipEvent::MouseMoveInside => {
                 let avm2_event = Avm2EventObject::mouse_event(
                     &mut activation,
@@ -161,8 +161,7 @@ pub struct MovieClipData<'gc> {
     #[collect(require_static)]
     flags: MovieClipFlags,
     /// This is lazily allocated on demand, to make `MovieClipData` smaller in the common case.
-    #[collect(require_static)]
-    drawing: Option<Box<Drawing>>,
+    drawing: Option<Box<Drawing<'gc>>>,
     avm2_enabled: bool,
 
     /// Show a hand cursor when the clip is in button mode.
@@ -754,6 +753,7 @@ impl<'gc> MovieClip<'gc> {
                     .0
                     .write(context.gc_context)
                     .script_limits(reader, context.avm1),
+                TagCode::DebugId => self.0.read().debug_id(context, reader),
                 TagCode::SoundStreamHead => {
                     self.0
                         .write(context.gc_context)
@@ -1107,6 +1107,47 @@ impl<'gc> MovieClip<'gc> {
         self.0.read().current_frame()
     }
 
+    /// Creates a detached copy of this clip's timeline, with its playhead on
+    /// the given frame.
+    ///
+    /// The copy doesn't run any frame scripts or sounds of its own, which lets
+    /// a frame other than the current one be rendered, as done by
+    /// `PrintJob.addPage`. Any actions queued by its children should be
+    /// discarded, and the copy must be released with
+    /// [`MovieClip::release_timeline_copy`] once it's no longer used.
+    pub fn timeline_copy(self, context: &mut UpdateContext<'gc>, frame: FrameNumber) -> Self {
+        let read = self.0.read();
+        let copy = MovieClip::new_with_data(
+            context.gc_context,
+            read.id(),
+            read.static_data.swf.clone(),
+            read.total_frames(),
+        );
+        {
+            let mut write = copy.0.write(context.gc_context);
+            write.static_data = read.static_data;
+            write.importer_movie = read.importer_movie.clone();
+            write.flags = MovieClipFlags::TIMELINE_COPY;
+            #[cfg(feature = "timeline_debug")]
+            {
+                // The copy starts its first goto before any frame has run.
+                write.tag_frame_boundaries = read.tag_frame_boundaries.clone();
+                write.tag_frame_boundaries.insert(0, (0, 0));
+            }
+        }
+        drop(read);
+
+        copy.run_goto(context, frame.max(1), false);
+        copy
+    }
+
+    /// Releases a copy made by [`MovieClip::timeline_copy`], so that none of
+    /// its children keep running.
+    pub fn release_timeline_copy(self, context: &mut UpdateContext<'gc>) {
+        debug_assert!(self.0.read().flags.contains(MovieClipFlags::TIMELINE_COPY));
+        self.avm1_unload(context);
+    }
+
     /// Return the current scene.
     pub fn current_scene(self) -> Option<Scene> {
         let current_frame = self.0.read().current_frame();
@@ -2004,10 +2045,11 @@ impl<'gc> MovieClip<'gc> {
             // However, if we executed a 'no-op goto' (start and end frames are the same),
             // then do *not* run sounds. Some SWFS (e.g. 'This is the only level too')
             // rely on this behavior.
+            let is_timeline_copy = self.0.read().flags.contains(MovieClipFlags::TIMELINE_COPY);
             self.run_frame_internal(
                 context,
                 false,
-                frame != frame_before_rewind,
+                frame != frame_before_rewind && !is_timeline_copy,
                 self.movie().is_action_script_3(),
             );
         } else {
@@ -2026,7 +2068,7 @@ impl<'gc> MovieClip<'gc> {
         // frame".
         //
         // Our queued place tags will now run at this time, too.
-        if !is_implicit {
+        if !is_implicit && !self.0.read().flags.contains(MovieClipFlags::TIMELINE_COPY) {
             run_inner_goto_frame(context, &removed_frame_scripts, self);
         }
 
@@ -2351,7 +2393,7 @@ impl<'gc> MovieClip<'gc> {
         self.0.write(context.gc_context).button_mode = button_mode;
     }
 
-    pub fn drawing_mut(&self, gc_context: &Mutation<'gc>) -> RefMut<'_, Drawing> {
+    pub fn drawing_mut(&self, gc_context: &Mutation<'gc>) -> RefMut<'_, Drawing<'gc>> {
         // We're about to change graphics, so invalidate on the next frame
         self.invalidate_cached_bitmap(gc_context);
         RefMut::map(self.0.write(gc_context), |this| {
@@ -2359,7 +2401,7 @@ impl<'gc> MovieClip<'gc> {
         })
     }
 
-    pub fn drawing(&self) -> Option<Ref<'_, Drawing>> {
+    pub fn drawing(&self) -> Option<Ref<'_, Drawing<'gc>>> {
         let read = Ref::map(self.0.read(), |s| &s.drawing);
         Ref::filter_map(read, Option::as_deref).ok()
     }
@@ -2833,7 +2875,7 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         Some(self.into())
     }
 
-    fn as_drawing(&self, gc_context: &Mutation<'gc>) -> Option<RefMut<'_, Drawing>> {
+    fn as_drawing(&self, gc_context: &Mutation<'gc>) -> Option<RefMut<'_, Drawing<'gc>>> {
         Some(self.drawing_mut(gc_context))
     }
 
@@ -4219,6 +4261,21 @@ impl<'gc, 'a> MovieClipData<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn debug_id(
+        &self,
+        context: &mut UpdateContext<'gc>,
+        reader: &mut SwfStream<'a>,
+    ) -> Result<(), Error> {
+        let debug_id = reader.read_debug_id()?;
+        // AVM2 lines are known from the bytecode, only AVM1 needs the SWD file.
+        let movie = self.movie();
+        if context.debugger.is_attached() && !movie.is_action_script_3() {
+            context.debugger.load_swd(movie.url(), &debug_id);
+        }
+        Ok(())
+    }
+
     #[inline]
     fn get_registered_character_by_id(
         &mut self,
@@ -5175,6 +5232,10 @@ bitflags! {
 
         /// Whether this `MovieClip` has been post-instantiated yet.
         const POST_INSTANTIATED = 1 << 5;
+
+        /// Whether this `MovieClip` is a detached copy of another clip's
+        /// timeline, which never runs frame scripts or starts sounds.
+        const TIMELINE_COPY = 1 << 6;
     }
 }
 
@@ -1,4 +1,6 @@
+use crate::bitmap::bitmap_data::BitmapDataWrapper;
 use crate::context::RenderContext;
+use gc_arena::Collect;
 use ruffle_render::backend::{RenderBackend, ShapeHandle};
 use ruffle_render::bitmap::{BitmapHandle, BitmapInfo, BitmapSize, BitmapSource};
 use ruffle_render::commands::CommandHandler;
@@ -8,29 +10,41 @@ use ruffle_render::shape_utils::{
 use std::cell::{Cell, RefCell};
 use swf::{FillStyle, LineStyle, Point, Rectangle, Twips};
 
-#[derive(Clone, Debug)]
-pub struct Drawing {
+#[derive(Clone, Debug, Collect)]
+#[collect(no_drop)]
+pub struct Drawing<'gc> {
+    #[collect(require_static)]
     render_handle: RefCell<Option<ShapeHandle>>,
+    #[collect(require_static)]
     shape_bounds: Rectangle<Twips>,
+    #[collect(require_static)]
     edge_bounds: Rectangle<Twips>,
+    #[collect(require_static)]
     dirty: Cell<bool>,
+    #[collect(require_static)]
     paths: Vec<DrawingPath>,
-    bitmaps: Vec<BitmapInfo>,
+    bitmaps: Vec<DrawingBitmap<'gc>>,
+    #[collect(require_static)]
     current_fill: Option<DrawingFill>,
+    #[collect(require_static)]
     current_line: Option<DrawingLine>,
+    #[collect(require_static)]
     pending_lines: Vec<DrawingLine>,
+    #[collect(require_static)]
     cursor: Point<Twips>,
+    #[collect(require_static)]
     fill_start: Point<Twips>,
+    #[collect(require_static)]
     default_winding_rule: FillRule,
 }
 
-impl Default for Drawing {
+impl Default for Drawing<'_> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl Drawing {
+impl<'gc> Drawing<'gc> {
     pub fn new() -> Self {
         Self {
             render_handle: RefCell::new(None),
@@ -103,7 +117,7 @@ impl Drawing {
         this
     }
 
-    pub fn copy_from(&mut self, other: &Drawing) {
+    pub fn copy_from(&mut self, other: &Drawing<'gc>) {
         *self = Drawing {
             render_handle: RefCell::new(None),
             dirty: Cell::new(true),
@@ -120,6 +134,21 @@ impl Drawing {
         }
     }
 
+    /// The style of the current fill, if there is one.
+    pub fn fill_style(&self) -> Option<&FillStyle> {
+        self.current_fill.as_ref().map(|fill| &fill.style)
+    }
+
+    /// The fill rule of the current fill, if there is one.
+    pub fn fill_rule(&self) -> Option<FillRule> {
+        self.current_fill.as_ref().map(|fill| fill.rule)
+    }
+
+    /// The style of the current line, if there is one.
+    pub fn line_style(&self) -> Option<&LineStyle> {
+        self.current_line.as_ref().map(|line| &line.style)
+    }
+
     /// Set fill style and reset fill rule to default.
     pub fn set_fill_style(&mut self, style: Option<FillStyle>) {
         self.new_fill(style, Some(self.default_winding_rule));
@@ -244,12 +273,63 @@ impl Drawing {
         self.dirty.set(true);
     }
 
-    pub fn add_bitmap(&mut self, bitmap: BitmapInfo) -> u16 {
+    /// Draw a self-contained path that is filled with its own fill rule, as
+    /// `drawPath` and `drawTriangles` do.
+    ///
+    /// The path gets a fill of its own with the current fill style, so its
+    /// winding neither affects nor is affected by anything drawn with the
+    /// current fill before or after it. The current line strokes the path as
+    /// usual.
+    pub fn draw_path(&mut self, rule: FillRule, commands: impl IntoIterator<Item = DrawCommand>) {
+        let previous_rule = self.fill_rule();
+        self.set_fill_rule(Some(rule));
+        for command in commands {
+            self.draw_command(command);
+        }
+        self.set_fill_rule(previous_rule);
+    }
+
+    /// Fill triangles that each have a style of their own, such as the bitmap
+    /// fills that `drawTriangles` maps onto each triangle.
+    ///
+    /// The triangles are added as they are, without affecting the current fill
+    /// or line.
+    pub fn add_triangle_fills(
+        &mut self,
+        triangles: impl IntoIterator<Item = (FillStyle, [Point<Twips>; 3])>,
+    ) {
+        for (style, [a, b, c]) in triangles {
+            for point in [a, b, c] {
+                let command = DrawCommand::MoveTo(point);
+                self.shape_bounds =
+                    stretch_bounds(&self.shape_bounds, &command, Twips::ZERO, point);
+                self.edge_bounds = stretch_bounds(&self.edge_bounds, &command, Twips::ZERO, point);
+            }
+            self.paths.push(DrawingPath::Fill(DrawingFill {
+                style,
+                rule: FillRule::NonZero,
+                commands: vec![
+                    DrawCommand::MoveTo(a),
+                    DrawCommand::LineTo(b),
+                    DrawCommand::LineTo(c),
+                    DrawCommand::LineTo(a),
+                ],
+            }));
+        }
+        self.dirty.set(true);
+    }
+
+    pub fn add_bitmap(&mut self, bitmap_data: BitmapDataWrapper<'gc>, info: BitmapInfo) -> u16 {
         let id = self.bitmaps.len() as u16;
-        self.bitmaps.push(bitmap);
+        self.bitmaps.push(DrawingBitmap { bitmap_data, info });
         id
     }
 
+    /// The `BitmapData` that the bitmap with the given ID was added from.
+    pub fn bitmap_data(&self, id: u16) -> Option<BitmapDataWrapper<'gc>> {
+        self.bitmaps.get(id as usize).map(|bm| bm.bitmap_data)
+    }
+
     /// Obtain a `ShapeHandle` that represents this `Drawing`, or `None` if it is empty.
     pub fn register_or_replace(&self, renderer: &mut dyn RenderBackend) -> Option<ShapeHandle> {
         if self.dirty.get() {
@@ -353,7 +433,7 @@ impl Drawing {
         for path in &self.paths {
             match path {
                 DrawingPath::Fill(fill) => {
-                    if shape_utils::draw_command_fill_hit_test(&fill.commands, point) {
+                    if shape_utils::draw_command_fill_hit_test(&fill.commands, point, fill.rule) {
                         return true;
                     }
                 }
@@ -372,7 +452,7 @@ impl Drawing {
 
         // The pending fill will auto-close.
         if let Some(fill) = &self.current_fill {
-            if shape_utils::draw_command_fill_hit_test(&fill.commands, point) {
+            if shape_utils::draw_command_fill_hit_test(&fill.commands, point, fill.rule) {
                 return true;
             }
         }
@@ -418,6 +498,70 @@ impl Drawing {
         false
     }
 
+    /// Describe this drawing as a sequence of fills, strokes and paths, in the
+    /// way that `Graphics.readGraphicsData` reports it.
+    ///
+    /// Fills are always followed by their path and an `EndFill`. Strokes are
+    /// only reported when they change.
+    pub fn graphics_data(&self) -> Vec<DrawingData<'_>> {
+        fn push_fill<'a>(
+            data: &mut Vec<DrawingData<'a>>,
+            stroke: &mut Option<&'a LineStyle>,
+            fill: &'a DrawingFill,
+        ) {
+            if !has_segments(&fill.commands) {
+                return;
+            }
+            if stroke.take().is_some() {
+                data.push(DrawingData::Stroke(None));
+            }
+            data.push(DrawingData::Fill(&fill.style));
+            data.push(DrawingData::Path {
+                commands: &fill.commands,
+                winding_rule: fill.rule,
+            });
+            data.push(DrawingData::EndFill);
+        }
+
+        fn push_line<'a>(
+            data: &mut Vec<DrawingData<'a>>,
+            stroke: &mut Option<&'a LineStyle>,
+            line: &'a DrawingLine,
+            winding_rule: FillRule,
+        ) {
+            if !has_segments(&line.commands) {
+                return;
+            }
+            if *stroke != Some(&line.style) {
+                *stroke = Some(&line.style);
+                data.push(DrawingData::Stroke(Some(&line.style)));
+            }
+            data.push(DrawingData::Path {
+                commands: &line.commands,
+                winding_rule,
+            });
+        }
+
+        let mut data = Vec::new();
+        let mut stroke = None;
+        let winding_rule = self.default_winding_rule;
+
+        for path in &self.paths {
+            match path {
+                DrawingPath::Fill(fill) => push_fill(&mut data, &mut stroke, fill),
+                DrawingPath::Line(line) => push_line(&mut data, &mut stroke, line, winding_rule),
+            }
+        }
+        if let Some(fill) = &self.current_fill {
+            push_fill(&mut data, &mut stroke, fill);
+        }
+        for line in self.pending_lines.iter().chain(&self.current_line) {
+            push_line(&mut data, &mut stroke, line, winding_rule);
+        }
+
+        data
+    }
+
     // Ensures that the path is closed for a pending fill.
     pub fn close_path(&mut self) {
         if let Some(fill) = &mut self.current_fill {
@@ -432,18 +576,49 @@ impl Drawing {
     }
 }
 
-impl BitmapSource for Drawing {
+impl BitmapSource for Drawing<'_> {
     fn bitmap_size(&self, id: u16) -> Option<BitmapSize> {
         self.bitmaps.get(id as usize).map(|bm| BitmapSize {
-            width: bm.width,
-            height: bm.height,
+            width: bm.info.width,
+            height: bm.info.height,
         })
     }
     fn bitmap_handle(&self, id: u16, _backend: &mut dyn RenderBackend) -> Option<BitmapHandle> {
-        self.bitmaps.get(id as usize).map(|bm| bm.handle.clone())
+        self.bitmaps
+            .get(id as usize)
+            .map(|bm| bm.info.handle.clone())
     }
 }
 
+/// A bitmap used by the bitmap fills of a drawing.
+#[derive(Debug, Clone, Collect)]
+#[collect(no_drop)]
+struct DrawingBitmap<'gc> {
+    bitmap_data: BitmapDataWrapper<'gc>,
+    #[collect(require_static)]
+    info: BitmapInfo,
+}
+
+/// A piece of a drawing, as returned by [`Drawing::graphics_data`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrawingData<'a> {
+    /// Start filling the following paths with this style.
+    Fill(&'a FillStyle),
+
+    /// Stop filling paths.
+    EndFill,
+
+    /// Start stroking the following paths with this style, or stop stroking
+    /// them if there's no style.
+    Stroke(Option<&'a LineStyle>),
+
+    /// A path, drawn with the current fill and stroke.
+    Path {
+        commands: &'a [DrawCommand],
+        winding_rule: FillRule,
+    },
+}
+
 #[derive(Debug, Clone)]
 struct DrawingFill {
     style: FillStyle,
@@ -464,6 +639,13 @@ enum DrawingPath {
     Line(DrawingLine),
 }
 
+/// Whether a path draws anything, rather than just moving around.
+fn has_segments(commands: &[DrawCommand]) -> bool {
+    commands
+        .iter()
+        .any(|command| !matches!(command, DrawCommand::MoveTo(_)))
+}
+
 fn stretch_bounds(
     bounds: &Rectangle<Twips>,
     command: &DrawCommand,
@@ -1,5 +1,7 @@
 use crate::display_object::InteractiveObject;
-use swf::ClipEventFlag;
+use crate::player::ParseEnumError;
+use crate::string::{FromWStr, WStr};
+use swf::{ClipEventFlag, Point, Twips};
 
 #[derive(Debug, Clone, Copy)]
 pub enum PlayerEvent {
@@ -31,11 +33,33 @@ pub enum PlayerEvent {
         delta: MouseWheelDelta,
     },
     GamepadButtonDown {
+        gamepad: usize,
         button: GamepadButton,
     },
     GamepadButtonUp {
+        gamepad: usize,
         button: GamepadButton,
     },
+
+    /// A gamepad was connected, and can now be used through `GameInput`.
+    ///
+    /// Gamepads are identified by an ID chosen by the frontend, which must be
+    /// used by all events of that gamepad.
+    GamepadConnected {
+        gamepad: usize,
+    },
+    GamepadDisconnected {
+        gamepad: usize,
+    },
+
+    /// An analog stick or trigger of a gamepad moved.
+    ///
+    /// Sticks range from -1.0 to 1.0, and triggers from 0.0 to 1.0.
+    GamepadAxisChanged {
+        gamepad: usize,
+        axis: GamepadAxis,
+        value: f64,
+    },
     TextInput {
         codepoint: char,
     },
@@ -44,6 +68,161 @@ pub enum PlayerEvent {
     },
     FocusGained,
     FocusLost,
+
+    /// A touch point started touching the screen.
+    ///
+    /// The primary touch point (the first one while no other is active) is
+    /// also handled as left mouse button input, so frontends shouldn't send
+    /// mouse events for it too.
+    TouchBegin {
+        id: i32,
+        x: f64,
+        y: f64,
+    },
+    TouchMove {
+        id: i32,
+        x: f64,
+        y: f64,
+    },
+    TouchEnd {
+        id: i32,
+        x: f64,
+        y: f64,
+    },
+
+    /// A gesture that the frontend recognized from touch input.
+    Gesture {
+        gesture: Gesture,
+        phase: GesturePhase,
+        x: f64,
+        y: f64,
+    },
+
+    /// Two touch points tapped the screen at once, centered on a position.
+    TwoFingerTap {
+        x: f64,
+        y: f64,
+    },
+
+    /// A touch point tapped the screen while another one was held down.
+    ///
+    /// `x` and `y` are the position of the held touch point, and `tap_x` and
+    /// `tap_y` are the position of the tap.
+    PressAndTap {
+        x: f64,
+        y: f64,
+        tap_x: f64,
+        tap_y: f64,
+    },
+}
+
+/// A transform gesture, as reported by `TransformGestureEvent`.
+#[derive(Debug, Clone, Copy)]
+pub enum Gesture {
+    Pan { offset_x: f64, offset_y: f64 },
+    Rotate { rotation: f64 },
+    Swipe { offset_x: f64, offset_y: f64 },
+    Zoom { scale_x: f64, scale_y: f64 },
+}
+
+impl Gesture {
+    /// Returns the AVM2 event type of this gesture.
+    pub fn event_type(self) -> &'static str {
+        match self {
+            Self::Pan { .. } => "gesturePan",
+            Self::Rotate { .. } => "gestureRotate",
+            Self::Swipe { .. } => "gestureSwipe",
+            Self::Zoom { .. } => "gestureZoom",
+        }
+    }
+
+    /// Returns the scale, rotation and offset of this gesture, in the order
+    /// `TransformGestureEvent` takes them.
+    pub fn transform(self) -> (f64, f64, f64, f64, f64) {
+        match self {
+            Self::Pan { offset_x, offset_y } | Self::Swipe { offset_x, offset_y } => {
+                (1.0, 1.0, 0.0, offset_x, offset_y)
+            }
+            Self::Rotate { rotation } => (1.0, 1.0, rotation, 0.0, 0.0),
+            Self::Zoom { scale_x, scale_y } => (scale_x, scale_y, 0.0, 0.0, 0.0),
+        }
+    }
+}
+
+impl PartialEq for Gesture {
+    fn eq(&self, rhs: &Self) -> bool {
+        // Compare bit patterns, so that NaN values are equal to themselves.
+        let bits = |gesture: Self| {
+            let (scale_x, scale_y, rotation, offset_x, offset_y) = gesture.transform();
+            [scale_x, scale_y, rotation, offset_x, offset_y].map(f64::to_bits)
+        };
+        self.event_type() == rhs.event_type() && bits(*self) == bits(*rhs)
+    }
+}
+
+impl Eq for Gesture {}
+
+/// The progress of a gesture, as reported by `GestureEvent.phase`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GesturePhase {
+    Begin,
+    Update,
+    End,
+
+    /// A gesture that happens all at once, such as a swipe.
+    All,
+}
+
+impl GesturePhase {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Begin => "begin",
+            Self::Update => "update",
+            Self::End => "end",
+            Self::All => "all",
+        }
+    }
+}
+
+/// How touch input is interpreted, as set by `Multitouch.inputMode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MultitouchInputMode {
+    /// Gestures are dispatched as gesture events, and touch points are only
+    /// handled as mouse input.
+    #[default]
+    Gesture,
+
+    /// All touch input is handled as mouse input.
+    None,
+
+    /// Touch points are dispatched as touch events, and gestures are ignored.
+    TouchPoint,
+}
+
+impl MultitouchInputMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Gesture => "gesture",
+            Self::None => "none",
+            Self::TouchPoint => "touchPoint",
+        }
+    }
+}
+
+impl FromWStr for MultitouchInputMode {
+    type Err = ParseEnumError;
+
+    fn from_wstr(s: &WStr) -> Result<Self, Self::Err> {
+        if s == WStr::from_units(b"gesture") {
+            Ok(Self::Gesture)
+        } else if s == WStr::from_units(b"none") {
+            Ok(Self::None)
+        } else if s == WStr::from_units(b"touchPoint") {
+            Ok(Self::TouchPoint)
+        } else {
+            Err(ParseEnumError)
+        }
+    }
 }
 
 /// The distance scrolled by the mouse wheel.
@@ -309,6 +488,61 @@ pub enum ClipEvent<'gc> {
     MouseWheel {
         delta: MouseWheelDelta,
     },
+
+    /// A touch point started touching a display object.
+    ///
+    /// Touch events are targeted to the interactive object under the touch
+    /// point, with the position given in stage coordinates. Only AVM2 can
+    /// receive these events.
+    TouchBegin {
+        touch_point_id: i32,
+        primary: bool,
+        position: Point<Twips>,
+    },
+
+    /// A touch point moved over a display object.
+    TouchMove {
+        touch_point_id: i32,
+        primary: bool,
+        position: Point<Twips>,
+    },
+
+    /// A touch point stopped touching a display object.
+    TouchEnd {
+        touch_point_id: i32,
+        primary: bool,
+        position: Point<Twips>,
+    },
+
+    /// A touch point stopped touching the same display object it started
+    /// touching.
+    TouchTap {
+        touch_point_id: i32,
+        primary: bool,
+        position: Point<Twips>,
+    },
+
+    /// A gesture was performed over a display object.
+    ///
+    /// This is a targeted event, like the touch events. Only AVM2 can receive
+    /// these events.
+    Gesture {
+        gesture: Gesture,
+        phase: GesturePhase,
+        position: Point<Twips>,
+    },
+
+    /// Two touch points tapped a display object at once.
+    TwoFingerTap {
+        position: Point<Twips>,
+    },
+
+    /// A touch point tapped the screen while another one was held down on a
+    /// display object.
+    PressAndTap {
+        position: Point<Twips>,
+        tap_position: Point<Twips>,
+    },
 }
 
 impl ClipEvent<'_> {
@@ -801,3 +1035,13 @@ pub enum GamepadButton {
     DPadLeft,
     DPadRight,
 }
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
+}
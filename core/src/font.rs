@@ -2,6 +2,7 @@ use crate::drawing::Drawing;
 use crate::html::TextSpan;
 use crate::prelude::*;
 use crate::string::WStr;
+use either::Either;
 use gc_arena::{Collect, Gc, Mutation};
 use ruffle_render::backend::null::NullBitmapSource;
 use ruffle_render::backend::{RenderBackend, ShapeHandle};
@@ -10,6 +11,7 @@ use ruffle_render::transform::Transform;
 use std::borrow::Cow;
 use std::cell::{OnceCell, RefCell};
 use std::cmp::max;
+use std::fmt;
 use std::hash::{Hash, Hasher};
 use swf::FillStyle;
 
@@ -56,6 +58,12 @@ pub struct EvalParameters {
     /// pairs of letters, separate from the ordinary width between glyphs. This
     /// parameter allows enabling or disabling that feature.
     kerning: bool,
+
+    /// Whether the text is laid out from right to left.
+    ///
+    /// This is set by the layout engine for text in a right-to-left bidi run,
+    /// and causes glyphs to be produced in visual order, last character first.
+    right_to_left: bool,
 }
 
 impl EvalParameters {
@@ -66,6 +74,7 @@ impl EvalParameters {
             height,
             letter_spacing,
             kerning,
+            right_to_left: false,
         }
     }
 
@@ -76,6 +85,7 @@ impl EvalParameters {
             height: Twips::from_pixels(span.font.size),
             letter_spacing: Twips::from_pixels(span.font.letter_spacing),
             kerning: span.font.kerning,
+            right_to_left: false,
         }
     }
 
@@ -83,9 +93,22 @@ impl EvalParameters {
     pub fn height(&self) -> Twips {
         self.height
     }
+
+    /// Whether the text is evaluated from right to left.
+    pub fn right_to_left(&self) -> bool {
+        self.right_to_left
+    }
+
+    /// Evaluate the text in the given direction.
+    pub fn with_right_to_left(self, right_to_left: bool) -> Self {
+        Self {
+            right_to_left,
+            ..self
+        }
+    }
 }
 
-struct GlyphToDrawing<'a>(&'a mut Drawing);
+struct GlyphToDrawing<'a>(&'a mut Drawing<'static>);
 
 /// Convert from a TTF outline, to a flash Drawing.
 ///
@@ -125,18 +148,54 @@ impl ttf_parser::OutlineBuilder for GlyphToDrawing<'_> {
     }
 }
 
-/// Represents a raw font file (ie .ttf).
-/// This should be shared and reused where possible, and it's reparsed every time a new glyph is required.
+/// The bytes of a font file, along with the face parsed from them.
 ///
-/// Parsing of a font is near-free (according to [ttf_parser::Face::parse]), but the storage isn't.
+/// Parsing the OpenType layout tables needed to shape text isn't free, so
+/// the face is kept for as long as the font.
+struct ParsedFont {
+    /// Borrows from `bytes`, see [`ParsedFont::parse`].
+    ///
+    /// This is declared before `bytes` so that it's dropped first.
+    face: rustybuzz::Face<'static>,
+    bytes: Cow<'static, [u8]>,
+}
+
+impl ParsedFont {
+    fn parse(
+        bytes: Cow<'static, [u8]>,
+        font_index: u32,
+    ) -> Result<Self, ttf_parser::FaceParsingError> {
+        // SAFETY: The data is either static or in the heap buffer of an owned `Vec`, which
+        // doesn't move when `bytes` does and is never mutated. The face can't outlive it,
+        // as both are only dropped together and `face()` ties the face to `&self`.
+        let data: &'static [u8] =
+            unsafe { std::slice::from_raw_parts(bytes.as_ptr(), bytes.len()) };
+        let face = rustybuzz::Face::from_face(ttf_parser::Face::parse(data, font_index)?);
+        Ok(Self { face, bytes })
+    }
+
+    fn face(&self) -> &rustybuzz::Face<'_> {
+        &self.face
+    }
+}
+
+impl fmt::Debug for ParsedFont {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParsedFont")
+            .field("len", &self.bytes.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Represents a raw font file (ie .ttf).
+/// This should be shared and reused where possible, as it's parsed once and kept in memory.
 ///
 /// Font files may contain multiple individual font faces, but those font faces may reuse the same
 /// Glyph from the same file. For this reason, glyphs are reused where possible.
 #[derive(Debug)]
 pub struct FontFace {
-    bytes: Cow<'static, [u8]>,
+    parsed: ParsedFont,
     glyphs: Vec<OnceCell<Option<Glyph>>>,
-    font_index: u32,
 
     ascender: i32,
     descender: i32,
@@ -152,8 +211,8 @@ impl FontFace {
     ) -> Result<Self, ttf_parser::FaceParsingError> {
         // TODO: Support font collections
 
-        // We validate that the font is good here, so we can just `.expect()` it later
-        let face = ttf_parser::Face::parse(&bytes, font_index)?;
+        let parsed = ParsedFont::parse(bytes, font_index)?;
+        let face = parsed.face();
 
         let ascender = face.ascender() as i32;
         let descender = -face.descender() as i32;
@@ -174,8 +233,7 @@ impl FontFace {
             .unwrap_or_default();
 
         Ok(Self {
-            bytes,
-            font_index,
+            parsed,
             glyphs,
             ascender,
             descender,
@@ -186,43 +244,85 @@ impl FontFace {
     }
 
     pub fn get_glyph(&self, character: char) -> Option<&Glyph> {
-        let face = ttf_parser::Face::parse(&self.bytes, self.font_index)
-            .expect("Font was already checked to be valid");
-        if let Some(glyph_id) = face.glyph_index(character) {
-            return self.glyphs[glyph_id.0 as usize]
-                .get_or_init(|| {
-                    let mut drawing = Drawing::new();
-                    // TTF uses NonZero
-                    drawing.new_fill(
-                        Some(FillStyle::Color(Color::WHITE)),
-                        Some(FillRule::NonZero),
-                    );
-                    if face
-                        .outline_glyph(glyph_id, &mut GlyphToDrawing(&mut drawing))
-                        .is_some()
-                    {
-                        let advance = face.glyph_hor_advance(glyph_id).map_or_else(
-                            || drawing.self_bounds().width(),
-                            |a| Twips::new(a as i32),
-                        );
-                        Some(Glyph {
-                            shape_handle: Default::default(),
-                            shape: GlyphShape::Drawing(drawing),
-                            advance,
-                        })
-                    } else {
-                        let advance = Twips::new(face.glyph_hor_advance(glyph_id)? as i32);
-                        // If we have advance, then this is either an image, SVG or simply missing (ie whitespace)
-                        Some(Glyph {
-                            shape_handle: Default::default(),
-                            shape: GlyphShape::None,
-                            advance,
-                        })
-                    }
-                })
-                .as_ref();
+        let glyph_id = self.parsed.face().glyph_index(character)?;
+        self.get_glyph_by_id(glyph_id)
+    }
+
+    fn get_glyph_by_id(&self, glyph_id: ttf_parser::GlyphId) -> Option<&Glyph> {
+        let face = self.parsed.face();
+        self.glyphs
+            .get(glyph_id.0 as usize)?
+            .get_or_init(|| {
+                let mut drawing = Drawing::new();
+                // TTF uses NonZero
+                drawing.new_fill(
+                    Some(FillStyle::Color(Color::WHITE)),
+                    Some(FillRule::NonZero),
+                );
+                if face
+                    .outline_glyph(glyph_id, &mut GlyphToDrawing(&mut drawing))
+                    .is_some()
+                {
+                    let advance = face
+                        .glyph_hor_advance(glyph_id)
+                        .map_or_else(|| drawing.self_bounds().width(), |a| Twips::new(a as i32));
+                    Some(Glyph {
+                        shape_handle: Default::default(),
+                        shape: GlyphShape::Drawing(drawing),
+                        advance,
+                    })
+                } else {
+                    let advance = Twips::new(face.glyph_hor_advance(glyph_id)? as i32);
+                    // If we have advance, then this is either an image, SVG or simply missing (ie whitespace)
+                    Some(Glyph {
+                        shape_handle: Default::default(),
+                        shape: GlyphShape::None,
+                        advance,
+                    })
+                }
+            })
+            .as_ref()
+    }
+
+    /// Shape a run of text using the OpenType layout tables of this font.
+    ///
+    /// The resulting glyphs are in visual order. Their clusters are indices
+    /// into `text`, and all metrics are in font units.
+    pub fn shape(&self, text: &WStr, right_to_left: bool, kerning: bool) -> Vec<ShapedGlyph> {
+        let mut buffer = rustybuzz::UnicodeBuffer::new();
+        for (pos, c) in text.char_indices() {
+            buffer.add(c.unwrap_or(char::REPLACEMENT_CHARACTER), pos as u32);
         }
-        None
+        buffer.guess_segment_properties();
+        buffer.set_direction(if right_to_left {
+            rustybuzz::Direction::RightToLeft
+        } else {
+            rustybuzz::Direction::LeftToRight
+        });
+
+        let features = if kerning {
+            vec![]
+        } else {
+            vec![rustybuzz::Feature::new(
+                ttf_parser::Tag::from_bytes(b"kern"),
+                0,
+                ..,
+            )]
+        };
+        let glyphs = rustybuzz::shape(self.parsed.face(), &features, buffer);
+
+        glyphs
+            .glyph_infos()
+            .iter()
+            .zip(glyphs.glyph_positions())
+            .map(|(info, position)| ShapedGlyph {
+                glyph_id: info.glyph_id as u16,
+                cluster: info.cluster as usize,
+                advance: position.x_advance,
+                x_offset: position.x_offset,
+                y_offset: position.y_offset,
+            })
+            .collect()
     }
 
     pub fn has_kerning_info(&self) -> bool {
@@ -230,8 +330,7 @@ impl FontFace {
     }
 
     pub fn get_kerning_offset(&self, left: char, right: char) -> Twips {
-        let face = ttf_parser::Face::parse(&self.bytes, self.font_index)
-            .expect("Font was already checked to be valid");
+        let face = self.parsed.face();
 
         if let (Some(left_glyph), Some(right_glyph)) =
             (face.glyph_index(left), face.glyph_index(right))
@@ -251,6 +350,41 @@ impl FontFace {
     }
 }
 
+/// A glyph produced by shaping text with [`FontFace::shape`].
+#[derive(Debug, Clone, Copy)]
+pub struct ShapedGlyph {
+    glyph_id: u16,
+
+    /// The index of the first character in the text that this glyph
+    /// represents.
+    cluster: usize,
+
+    advance: i32,
+    x_offset: i32,
+    y_offset: i32,
+}
+
+/// Whether some text contains characters that can't be laid out correctly
+/// one at a time, because their glyphs depend on the characters around them.
+fn needs_shaping(text: &WStr) -> bool {
+    text.iter().any(|c| {
+        matches!(
+            c,
+            // Hebrew, Arabic, Syriac, Thaana, NKo, Samaritan, Mandaic and Arabic Extended
+            0x0590..=0x08FF
+                // Indic scripts, Sinhala, Thai, Lao, Tibetan and Myanmar
+                | 0x0900..=0x109F
+                // Khmer and Mongolian
+                | 0x1780..=0x18AF
+                // Zero-width non-joiner and joiner
+                | 0x200C..=0x200D
+                // Hebrew and Arabic presentation forms
+                | 0xFB1D..=0xFDFF
+                | 0xFE70..=0xFEFF
+        )
+    })
+}
+
 #[derive(Debug)]
 pub enum GlyphSource {
     Memory {
@@ -583,31 +717,76 @@ impl<'gc> Font<'gc> {
 
         transform.matrix.a = scale;
         transform.matrix.d = scale;
-        let mut char_indices = text.char_indices().peekable();
+
+        let is_device_font = self.font_type() == FontType::Device;
+        let scale_advance = |advance: Twips| {
+            if is_device_font {
+                let unspaced_advance =
+                    round_to_pixel(Twips::new((advance.get() as f32 * scale) as i32));
+                let spaced_advance =
+                    unspaced_advance + params.letter_spacing.round_to_pixel_ties_even();
+                if spaced_advance > Twips::ZERO {
+                    spaced_advance
+                } else {
+                    unspaced_advance
+                }
+            } else {
+                Twips::new((advance.get() as f32 * scale) as i32) + params.letter_spacing
+            }
+        };
+
+        if let GlyphSource::FontFace(face) = &self.0.glyphs {
+            if params.right_to_left || needs_shaping(text) {
+                let mut x = Twips::ZERO;
+                for shaped in face.shape(text, params.right_to_left, params.kerning) {
+                    let glyph_id = ttf_parser::GlyphId(shaped.glyph_id);
+                    let Some(glyph) = face.get_glyph_by_id(glyph_id) else {
+                        continue;
+                    };
+
+                    // Combining marks don't take up any space of their own.
+                    let twips_advance = if shaped.advance == 0 {
+                        Twips::ZERO
+                    } else {
+                        scale_advance(Twips::new(shaped.advance))
+                    };
+
+                    let mut glyph_transform = transform.clone();
+                    glyph_transform.matrix.tx +=
+                        Twips::new((shaped.x_offset as f32 * scale) as i32);
+                    glyph_transform.matrix.ty -=
+                        Twips::new((shaped.y_offset as f32 * scale) as i32);
+
+                    glyph_func(shaped.cluster, &glyph_transform, glyph, twips_advance, x);
+
+                    // Step horizontally.
+                    transform.matrix.tx += twips_advance;
+                    x += twips_advance;
+                }
+                return;
+            }
+        }
+
+        // Right-to-left text is produced in visual order, last character first.
+        let char_indices = text
+            .char_indices()
+            .map(|(pos, c)| (pos, c.unwrap_or(char::REPLACEMENT_CHARACTER)));
+        let char_indices = if params.right_to_left {
+            Either::Right(char_indices.collect::<Vec<_>>().into_iter().rev())
+        } else {
+            Either::Left(char_indices)
+        };
+        let mut char_indices = char_indices.peekable();
         let has_kerning_info = self.has_kerning_info();
         let mut x = Twips::ZERO;
         while let Some((pos, c)) = char_indices.next() {
-            let c = c.unwrap_or(char::REPLACEMENT_CHARACTER);
             if let Some(glyph) = self.get_glyph_for_char(c) {
                 let mut advance = glyph.advance();
                 if has_kerning_info && params.kerning {
-                    let next_char = char_indices.peek().cloned().unwrap_or((0, Ok('\0'))).1;
-                    let next_char = next_char.unwrap_or(char::REPLACEMENT_CHARACTER);
+                    let next_char = char_indices.peek().map_or('\0', |(_, c)| *c);
                     advance += self.get_kerning_offset(c, next_char);
                 }
-                let twips_advance = if self.font_type() == FontType::Device {
-                    let unspaced_advance =
-                        round_to_pixel(Twips::new((advance.get() as f32 * scale) as i32));
-                    let spaced_advance =
-                        unspaced_advance + params.letter_spacing.round_to_pixel_ties_even();
-                    if spaced_advance > Twips::ZERO {
-                        spaced_advance
-                    } else {
-                        unspaced_advance
-                    }
-                } else {
-                    Twips::new((advance.get() as f32 * scale) as i32) + params.letter_spacing
-                };
+                let twips_advance = scale_advance(advance);
 
                 glyph_func(pos, &transform, glyph, twips_advance, x);
 
@@ -765,7 +944,7 @@ impl SwfGlyphOrShape {
 #[derive(Debug, Clone)]
 enum GlyphShape {
     Swf(RefCell<SwfGlyphOrShape>),
-    Drawing(Drawing),
+    Drawing(Drawing<'static>),
     None,
 }
 
@@ -1141,6 +1320,41 @@ mod tests {
         });
     }
 
+    #[test]
+    fn evaluate_right_to_left() {
+        with_device_font(|_mc, df| {
+            let params =
+                EvalParameters::from_parts(Twips::from_pixels(12.0), Twips::from_pixels(0.0), true);
+            let string = WStr::from_units(b"abc");
+
+            let mut ltr = vec![];
+            df.evaluate(string, Default::default(), params, |pos, _, _, _, x| {
+                ltr.push((pos, x));
+            });
+            let mut rtl = vec![];
+            df.evaluate(
+                string,
+                Default::default(),
+                params.with_right_to_left(true),
+                |pos, _, _, _, x| rtl.push((pos, x)),
+            );
+
+            assert_eq!(
+                vec![0, 1, 2],
+                ltr.iter().map(|(pos, _)| *pos).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                vec![2, 1, 0],
+                rtl.iter().map(|(pos, _)| *pos).collect::<Vec<_>>()
+            );
+            assert_eq!(Twips::ZERO, rtl[0].1);
+            assert_eq!(
+                df.measure(string, params),
+                df.measure(string, params.with_right_to_left(true))
+            );
+        });
+    }
+
     #[test]
     fn wrap_line_breakpoint_every_word() {
         with_device_font(|_mc, df| {
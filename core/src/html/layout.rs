@@ -17,12 +17,33 @@ use std::ops::{Deref, Range};
 use std::slice::Iter;
 use std::sync::Arc;
 use swf::{Point, Rectangle, Twips};
+use unicode_bidi::utf16::ParagraphBidiInfo;
+
+/// Returns `true` if the given utf16 code unit may start a right-to-left run
+/// of text.
+///
+/// This is a quick check to skip bidi resolution for lines that can't need it.
+fn may_be_right_to_left(c: u16) -> bool {
+    matches!(
+        c,
+        // Hebrew, Arabic, Syriac, Thaana, NKo, Samaritan, Mandaic and Arabic Extended
+        0x0590..=0x08FF
+            // Right-to-left mark, embedding, override and isolate
+            | 0x200F | 0x202B | 0x202E | 0x2067
+            // Hebrew and Arabic presentation forms
+            | 0xFB1D..=0xFDFF
+            | 0xFE70..=0xFEFF
+            // High surrogates of right-to-left scripts outside of the BMP
+            | 0xD802..=0xD803
+            | 0xD83A..=0xD83B
+    )
+}
 
 /// Draw an underline on a particular drawing.
 ///
 /// This will not draw underlines shorter than a pixel in width.
 fn draw_underline(
-    drawing: &mut Drawing,
+    drawing: &mut Drawing<'_>,
     starting_pos: Position<Twips>,
     width: Twips,
     color: swf::Color,
@@ -174,7 +195,11 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         let mut line_drawing = Drawing::new();
         let mut has_underline: bool = false;
 
-        for linebox in self.boxes.iter() {
+        // Right-to-left runs may have put boxes out of order, so go from left to right.
+        let mut boxes: Vec<&LayoutBox<'gc>> = self.boxes.iter().collect();
+        boxes.sort_by_key(|linebox| linebox.bounds().offset_x());
+
+        for linebox in boxes {
             if linebox.is_text_box() {
                 if let Some((_t, tf, font, params, color)) = linebox.as_renderable_text(self.text) {
                     let underline_baseline =
@@ -264,6 +289,8 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         span: &TextSpan,
         font_type: FontType,
     ) {
+        self.reorder_line();
+
         let mut line_bounds = None;
         let mut box_count: i32 = 0;
         for linebox in self.boxes.iter_mut() {
@@ -326,11 +353,15 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
 
         let baseline_adjustment = self.max_ascent;
 
-        box_count = 0;
-        for layout_box in self.boxes.iter_mut() {
+        // Justification spreads boxes out in the order they're displayed.
+        let mut visual_order: Vec<usize> = (0..self.boxes.len()).collect();
+        visual_order.sort_by_key(|&index| self.boxes[index].bounds().offset_x());
+
+        for (box_count, index) in visual_order.into_iter().enumerate() {
+            let layout_box = &mut self.boxes[index];
             if layout_box.is_text_box() {
                 let position = Position::from((
-                    left_adjustment + align_adjustment + (interim_adjustment * box_count),
+                    left_adjustment + align_adjustment + (interim_adjustment * box_count as i32),
                     baseline_adjustment,
                 ));
                 layout_box.bounds += position;
@@ -338,8 +369,6 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
                 let position = Position::from((Twips::ZERO, baseline_adjustment));
                 layout_box.bounds += position;
             }
-
-            box_count += 1;
         }
 
         self.append_underlines();
@@ -356,6 +385,80 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         }
     }
 
+    /// Resolve the bidirectional text on the current line, and move its boxes
+    /// to where they're displayed.
+    ///
+    /// Text boxes are split so that each one has a single direction, and
+    /// right-to-left boxes are evaluated last character first. The boxes stay
+    /// in logical order; only their positions change.
+    fn reorder_line(&mut self) {
+        let Some(line_start) = self
+            .boxes
+            .iter()
+            .find(|b| b.is_text_box())
+            .map(|b| b.start())
+        else {
+            return;
+        };
+        let line_end = self.last_box_end_position();
+        let Some(line_text) = self.text.slice(line_start..line_end) else {
+            return;
+        };
+
+        // Only strongly right-to-left characters can change anything.
+        if !line_text.iter().any(may_be_right_to_left) {
+            return;
+        }
+
+        let units: Vec<u16> = line_text.iter().collect();
+        let bidi = ParagraphBidiInfo::new(&units, None);
+        if !bidi.has_rtl() {
+            return;
+        }
+        let levels = bidi.reordered_levels_per_char(0..units.len());
+        let paragraph_level = bidi.paragraph_level;
+
+        let mut boxes = Vec::with_capacity(self.boxes.len());
+        let mut box_levels = Vec::with_capacity(self.boxes.len());
+        for layout_box in mem::take(&mut self.boxes) {
+            if !layout_box.is_text_box() || layout_box.start() == layout_box.end() {
+                boxes.push(layout_box);
+                box_levels.push(paragraph_level);
+                continue;
+            }
+
+            let mut run_start = layout_box.start();
+            while run_start < layout_box.end() {
+                let level = levels[run_start - line_start];
+                let run_end = (run_start..layout_box.end())
+                    .find(|&i| levels[i - line_start] != level)
+                    .unwrap_or(layout_box.end());
+                boxes.push(layout_box.with_text_range(
+                    self.text,
+                    run_start..run_end,
+                    level.is_rtl(),
+                ));
+                box_levels.push(level);
+                run_start = run_end;
+            }
+        }
+
+        let mut x = boxes
+            .iter()
+            .map(|b| b.bounds().offset_x())
+            .min()
+            .unwrap_or_default();
+        for index in ParagraphBidiInfo::reorder_visual(&box_levels) {
+            let layout_box = &mut boxes[index];
+            let (position, size) = layout_box.bounds.into_position_and_size();
+            layout_box.bounds =
+                BoxBounds::from_position_and_size(Position::from((x, position.y())), size);
+            x += size.width();
+        }
+
+        self.boxes = boxes;
+    }
+
     fn flush_line(&mut self, end: usize) {
         if self.boxes.is_empty() {
             return;
@@ -1009,7 +1112,8 @@ pub enum LayoutContent<'gc> {
         #[collect(require_static)]
         color: swf::Color,
 
-        /// List of end positions (relative to this box) for each character.
+        /// List of start and end positions (relative to this box) for each
+        /// character.
         ///
         /// By having this here, we do not have to reevaluate the font
         /// each time we want to get the position of a character,
@@ -1019,10 +1123,13 @@ pub enum LayoutContent<'gc> {
         /// For instance, for the text "hello", this field may contain:
         ///
         /// ```text
-        /// [100, 200, 250, 300, 400]
+        /// [(0, 100), (100, 200), (200, 250), (250, 300), (300, 400)]
         /// ```
+        ///
+        /// In right-to-left text, the first character is on the right. Characters
+        /// that were shaped into a single glyph share its bounds.
         #[collect(require_static)]
-        char_end_pos: Vec<Twips>,
+        char_x_bounds: Vec<(Twips, Twips)>,
     },
 
     /// A layout box containing a bullet.
@@ -1059,8 +1166,7 @@ pub enum LayoutContent<'gc> {
         /// The position of the drawing in text.
         position: usize,
 
-        #[collect(require_static)]
-        drawing: Drawing,
+        drawing: Drawing<'gc>,
     },
 }
 
@@ -1094,12 +1200,6 @@ impl<'gc> LayoutBox<'gc> {
         span: &TextSpan,
     ) -> Self {
         let params = EvalParameters::from_span(span);
-        let mut char_end_pos = Vec::with_capacity(end - start);
-
-        font.evaluate(text, Default::default(), params, |_, _, _, advance, x| {
-            char_end_pos.push(x + advance);
-        });
-
         Self {
             bounds: Default::default(),
             content: LayoutContent::Text {
@@ -1109,11 +1209,74 @@ impl<'gc> LayoutBox<'gc> {
                 font,
                 params,
                 color: span.font.color,
-                char_end_pos,
+                char_x_bounds: Self::evaluate_char_x_bounds(text, font, params),
+            },
+        }
+    }
+
+    /// Construct a text box for part of the text of this one, evaluated in the
+    /// given direction.
+    ///
+    /// The new box keeps the vertical position of this one.
+    fn with_text_range(&self, text: &WStr, range: Range<usize>, right_to_left: bool) -> Self {
+        let LayoutContent::Text {
+            text_format,
+            font,
+            params,
+            color,
+            ..
+        } = &self.content
+        else {
+            return self.clone();
+        };
+
+        let params = params.with_right_to_left(right_to_left);
+        let run_text = &text[range.clone()];
+        let char_x_bounds = Self::evaluate_char_x_bounds(run_text, *font, params);
+        let width = font.measure(run_text, params).0;
+
+        Self {
+            bounds: self.bounds.with_width(width),
+            content: LayoutContent::Text {
+                start: range.start,
+                end: range.end,
+                text_format: text_format.clone(),
+                font: *font,
+                params,
+                color: *color,
+                char_x_bounds,
             },
         }
     }
 
+    fn evaluate_char_x_bounds(
+        text: &WStr,
+        font: Font<'gc>,
+        params: EvalParameters,
+    ) -> Vec<(Twips, Twips)> {
+        let mut char_x_bounds: Vec<Option<(Twips, Twips)>> = vec![None; text.len()];
+        font.evaluate(text, Default::default(), params, |pos, _, _, advance, x| {
+            if let Some(bounds) = char_x_bounds.get_mut(pos) {
+                *bounds = Some(match *bounds {
+                    // Several glyphs may make up a single character.
+                    Some((x_min, x_max)) => (x_min.min(x), x_max.max(x + advance)),
+                    None => (x, x + advance),
+                });
+            }
+        });
+
+        // Characters without a glyph of their own belong to the one before them.
+        let mut previous = (Twips::ZERO, Twips::ZERO);
+        char_x_bounds
+            .into_iter()
+            .map(|bounds| {
+                let bounds = bounds.unwrap_or(previous);
+                previous = bounds;
+                bounds
+            })
+            .collect()
+    }
+
     /// Construct a bullet.
     pub fn from_bullet(position: usize, font: Font<'gc>, span: &TextSpan) -> Self {
         let params = EvalParameters::from_span(span);
@@ -1131,7 +1294,7 @@ impl<'gc> LayoutBox<'gc> {
     }
 
     /// Construct a drawing.
-    pub fn from_drawing(position: usize, drawing: Drawing) -> Self {
+    pub fn from_drawing(position: usize, drawing: Drawing<'gc>) -> Self {
         Self {
             bounds: Default::default(),
             content: LayoutContent::Drawing { position, drawing },
@@ -1332,7 +1495,7 @@ impl<'gc> LayoutBox<'gc> {
     }
 
     /// Returns a reference to the drawing this box contains, if it has one.
-    pub fn as_renderable_drawing(&self) -> Option<&Drawing> {
+    pub fn as_renderable_drawing(&self) -> Option<&Drawing<'gc>> {
         match &self.content {
             LayoutContent::Text { .. } => None,
             LayoutContent::Bullet { .. } => None,
@@ -1368,18 +1531,11 @@ impl<'gc> LayoutBox<'gc> {
     pub fn char_x_bounds(&self, position: usize) -> Option<(Twips, Twips)> {
         let relative_position = position.checked_sub(self.start())?;
 
-        let LayoutContent::Text { char_end_pos, .. } = &self.content else {
+        let LayoutContent::Text { char_x_bounds, .. } = &self.content else {
             return None;
         };
 
-        Some(if relative_position == 0 {
-            (Twips::ZERO, *char_end_pos.get(0)?)
-        } else {
-            (
-                *char_end_pos.get(relative_position - 1)?,
-                *char_end_pos.get(relative_position)?,
-            )
-        })
+        char_x_bounds.get(relative_position).copied()
     }
 }
 
@@ -1420,3 +1576,165 @@ impl<'layout, 'gc> Iterator for LayoutBoxIter<'layout, 'gc> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::ui::FontDefinition;
+    use crate::font::FontType;
+    use crate::html::layout::{lower_from_text_spans, Layout, LayoutContent};
+    use crate::html::text_format::{FormatSpans, TextFormat};
+    use crate::player::PlayerBuilder;
+    use crate::string::WString;
+    use crate::tag_utils::SwfMovie;
+    use std::sync::Arc;
+    use swf::{Rectangle, Twips};
+
+    /// Every glyph of the test font is 20 pixels wide at the test size.
+    const GLYPH_WIDTH: Twips = Twips::from_pixels_i32(20);
+
+    /// Lay out `text` with a font that has fixed width Latin and Hebrew
+    /// glyphs, so that positions can be checked without shaping.
+    fn with_layout<F>(text: &str, callback: F)
+    where
+        F: for<'gc> FnOnce(&Layout<'gc>),
+    {
+        let movie = SwfMovie::empty(10);
+        let player = PlayerBuilder::new().with_movie(movie.clone()).build();
+        let mut player = player.lock().unwrap();
+
+        let glyphs = "abcd ".chars().chain('\u{05D0}'..='\u{05D3}');
+        player.register_device_font(FontDefinition::SwfTag(
+            swf::Font {
+                version: 2,
+                id: 1,
+                name: swf::SwfStr::from_utf8_str("Test"),
+                language: swf::Language::Unknown,
+                layout: Some(swf::FontLayout {
+                    ascent: 800,
+                    descent: 224,
+                    leading: 0,
+                    kerning: vec![],
+                }),
+                glyphs: glyphs
+                    .map(|c| swf::Glyph {
+                        shape_records: vec![],
+                        code: c as u16,
+                        advance: 1024,
+                        bounds: None,
+                    })
+                    .collect(),
+                flags: swf::FontFlag::HAS_WIDE_CODES | swf::FontFlag::HAS_LAYOUT,
+            },
+            swf::UTF_8,
+        ));
+
+        let format = TextFormat {
+            font: Some(WString::from_utf8("Test")),
+            size: Some(20.0),
+            ..TextFormat::default()
+        };
+        let spans = FormatSpans::from_text(WString::from_utf8(text), format);
+        player.mutate_with_update_context(|context| {
+            let layout = lower_from_text_spans(
+                &spans,
+                context,
+                Arc::new(movie),
+                Twips::from_pixels(1000.0),
+                false,
+                FontType::Device,
+            );
+            callback(&layout);
+        });
+    }
+
+    /// Horizontal caret and selection extents of each character, relative to
+    /// the start of the line, in multiples of the glyph width.
+    fn char_columns(layout: &Layout<'_>, len: usize) -> Vec<(i32, i32)> {
+        let line_start = layout.lines()[0].bounds().offset_x();
+        (0..len)
+            .map(|i| {
+                let Rectangle { x_min, x_max, .. } = layout.char_bounds(i).unwrap();
+                (
+                    (x_min - line_start).get() / GLYPH_WIDTH.get(),
+                    (x_max - line_start).get() / GLYPH_WIDTH.get(),
+                )
+            })
+            .collect()
+    }
+
+    fn box_ranges(layout: &Layout<'_>) -> Vec<(usize, usize, bool)> {
+        layout
+            .boxes_iter()
+            .filter_map(|b| match b.content() {
+                LayoutContent::Text {
+                    start, end, params, ..
+                } => Some((*start, *end, params.right_to_left())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn left_to_right_text_is_not_split() {
+        with_layout("abc dab", |layout| {
+            assert_eq!(vec![(0, 7, false)], box_ranges(layout));
+            assert_eq!(
+                vec![(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 6), (6, 7)],
+                char_columns(layout, 7)
+            );
+        });
+    }
+
+    #[test]
+    fn right_to_left_run_in_left_to_right_line() {
+        // "ab אבג cd": the Hebrew word is displayed last letter first.
+        with_layout("ab \u{05D0}\u{05D1}\u{05D2} cd", |layout| {
+            assert_eq!(1, layout.lines().len());
+            assert_eq!(
+                vec![(0, 3, false), (3, 6, true), (6, 9, false)],
+                box_ranges(layout)
+            );
+            assert_eq!(
+                vec![
+                    (0, 1),
+                    (1, 2),
+                    (2, 3),
+                    (5, 6),
+                    (4, 5),
+                    (3, 4),
+                    (6, 7),
+                    (7, 8),
+                    (8, 9)
+                ],
+                char_columns(layout, 9)
+            );
+        });
+    }
+
+    #[test]
+    fn left_to_right_run_in_right_to_left_line() {
+        // "אב cd": the line starts with Hebrew, so the Latin word is
+        // displayed first, followed by the space and the Hebrew word.
+        with_layout("\u{05D0}\u{05D1} cd", |layout| {
+            assert_eq!(vec![(0, 3, true), (3, 5, false)], box_ranges(layout));
+            assert_eq!(
+                vec![(4, 5), (3, 4), (2, 3), (0, 1), (1, 2)],
+                char_columns(layout, 5)
+            );
+        });
+    }
+
+    #[test]
+    fn selection_of_mixed_text_covers_whole_line() {
+        // Selecting everything highlights every column exactly once,
+        // even though the characters are not in visual order.
+        with_layout("a\u{05D0}\u{05D1} b\u{05D2}", |layout| {
+            let mut columns = char_columns(layout, 6);
+            columns.sort();
+            assert_eq!(
+                vec![(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 6)],
+                columns
+            );
+        });
+    }
+}
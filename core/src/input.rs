@@ -27,6 +27,9 @@ pub struct InputManager {
 
     /// A map from gamepad buttons to key codes.
     gamepad_button_mapping: HashMap<GamepadButton, KeyCode>,
+
+    /// The connected gamepads, in the order they were connected.
+    gamepads: Vec<usize>,
 }
 
 impl InputManager {
@@ -39,6 +42,7 @@ impl InputManager {
             last_text_control: None,
             last_click: None,
             gamepad_button_mapping,
+            gamepads: Vec::new(),
         }
     }
 
@@ -70,7 +74,7 @@ impl InputManager {
     pub fn map_input_event(&mut self, event: PlayerEvent) -> Option<PlayerEvent> {
         // Optionally transform gamepad button events into key events.
         match event {
-            PlayerEvent::GamepadButtonDown { button } => {
+            PlayerEvent::GamepadButtonDown { button, .. } => {
                 if let Some(key_code) = self.gamepad_button_mapping.get(&button) {
                     Some(PlayerEvent::KeyDown {
                         key_code: *key_code,
@@ -81,7 +85,7 @@ impl InputManager {
                     None
                 }
             }
-            PlayerEvent::GamepadButtonUp { button } => {
+            PlayerEvent::GamepadButtonUp { button, .. } => {
                 if let Some(key_code) = self.gamepad_button_mapping.get(&button) {
                     Some(PlayerEvent::KeyUp {
                         key_code: *key_code,
@@ -122,6 +126,12 @@ impl InputManager {
                 self.update_last_click(x, y, index);
             }
             PlayerEvent::MouseUp { button, .. } => self.remove_key(button.into()),
+            PlayerEvent::GamepadConnected { gamepad } if !self.gamepads.contains(&gamepad) => {
+                self.gamepads.push(gamepad);
+            }
+            PlayerEvent::GamepadDisconnected { gamepad } => {
+                self.gamepads.retain(|g| *g != gamepad);
+            }
             _ => {}
         }
     }
@@ -172,6 +182,10 @@ impl InputManager {
             .unwrap_or_default()
     }
 
+    pub fn connected_gamepads(&self) -> &[usize] {
+        &self.gamepads
+    }
+
     pub fn is_mouse_down(&self, button: MouseButton) -> bool {
         self.is_key_down(button.into())
     }
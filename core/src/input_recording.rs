@@ -0,0 +1,566 @@
+//! Recording and replaying of input.
+//!
+//! Input is recorded in the format of the test framework's `input.json`
+//! files, so that a recorded session can be turned into a regression test.
+//! Positions are recorded relative to the stage rather than the viewport,
+//! and every tick of the player after the first ends a frame of input. A
+//! frame of input is replayed right after the tick that it was recorded
+//! after, the same way the test framework injects input with `num_ticks`.
+
+use crate::events::{
+    GamepadAxis, GamepadButton, Gesture, GesturePhase, KeyCode, MouseButton, MouseWheelDelta,
+    PlayerEvent, TextControlCode,
+};
+use ruffle_input_format as format;
+use ruffle_input_format::MousePosition;
+use ruffle_render::matrix::Matrix;
+use swf::Point;
+
+pub use ruffle_input_format::AutomatedEvent;
+
+/// Converts an event sent to the player, using the given matrix to convert
+/// viewport positions to stage positions.
+///
+/// Returns `None` for mouse buttons that can't be recorded.
+pub fn from_player_event(
+    event: PlayerEvent,
+    inverse_view_matrix: Matrix,
+) -> Option<AutomatedEvent> {
+    let pos = |x, y| transform(inverse_view_matrix, MousePosition(x, y));
+    Some(match event {
+        PlayerEvent::MouseMove { x, y } => AutomatedEvent::MouseMove { pos: pos(x, y) },
+        PlayerEvent::MouseDown {
+            x,
+            y,
+            button,
+            index,
+        } => AutomatedEvent::MouseDown {
+            pos: pos(x, y),
+            btn: recordable_button(button)?,
+            index,
+            assert_handled: None,
+        },
+        PlayerEvent::MouseUp { x, y, button } => AutomatedEvent::MouseUp {
+            pos: pos(x, y),
+            btn: recordable_button(button)?,
+        },
+        PlayerEvent::MouseLeave => AutomatedEvent::MouseLeave,
+        PlayerEvent::MouseWheel { delta } => match delta {
+            MouseWheelDelta::Lines(lines) => AutomatedEvent::MouseWheel {
+                lines: Some(lines),
+                pixels: None,
+            },
+            MouseWheelDelta::Pixels(pixels) => AutomatedEvent::MouseWheel {
+                lines: None,
+                pixels: Some(pixels),
+            },
+        },
+        PlayerEvent::KeyDown { key_code, .. } => AutomatedEvent::KeyDown {
+            key_code: key_code.value(),
+        },
+        PlayerEvent::KeyUp { key_code, .. } => AutomatedEvent::KeyUp {
+            key_code: key_code.value(),
+        },
+        PlayerEvent::TextInput { codepoint } => AutomatedEvent::TextInput { codepoint },
+        PlayerEvent::TextControl { code } => AutomatedEvent::TextControl { code: code.into() },
+        PlayerEvent::FocusGained => AutomatedEvent::FocusGained,
+        PlayerEvent::FocusLost => AutomatedEvent::FocusLost,
+        PlayerEvent::TouchBegin { id, x, y } => AutomatedEvent::TouchBegin { id, pos: pos(x, y) },
+        PlayerEvent::TouchMove { id, x, y } => AutomatedEvent::TouchMove { id, pos: pos(x, y) },
+        PlayerEvent::TouchEnd { id, x, y } => AutomatedEvent::TouchEnd { id, pos: pos(x, y) },
+        PlayerEvent::Gesture {
+            gesture,
+            phase,
+            x,
+            y,
+        } => AutomatedEvent::Gesture {
+            gesture: gesture.into(),
+            phase: phase.into(),
+            pos: pos(x, y),
+        },
+        PlayerEvent::TwoFingerTap { x, y } => AutomatedEvent::TwoFingerTap { pos: pos(x, y) },
+        PlayerEvent::PressAndTap { x, y, tap_x, tap_y } => AutomatedEvent::PressAndTap {
+            pos: pos(x, y),
+            tap_pos: pos(tap_x, tap_y),
+        },
+        PlayerEvent::GamepadConnected { gamepad } => AutomatedEvent::GamepadConnected { gamepad },
+        PlayerEvent::GamepadDisconnected { gamepad } => {
+            AutomatedEvent::GamepadDisconnected { gamepad }
+        }
+        PlayerEvent::GamepadButtonDown { gamepad, button } => AutomatedEvent::GamepadButtonDown {
+            gamepad,
+            button: button.into(),
+        },
+        PlayerEvent::GamepadButtonUp { gamepad, button } => AutomatedEvent::GamepadButtonUp {
+            gamepad,
+            button: button.into(),
+        },
+        PlayerEvent::GamepadAxisChanged {
+            gamepad,
+            axis,
+            value,
+        } => AutomatedEvent::GamepadAxisChanged {
+            gamepad,
+            axis: axis.into(),
+            value,
+        },
+    })
+}
+
+/// Converts recorded input back into an event to send to the player, using
+/// the given matrix to convert stage positions to viewport positions.
+///
+/// Returns `None` for entries that aren't sent to the player as events.
+pub fn to_player_event(event: &AutomatedEvent, view_matrix: Matrix) -> Option<PlayerEvent> {
+    let viewport = |pos| transform(view_matrix, pos);
+    Some(match *event {
+        AutomatedEvent::Wait | AutomatedEvent::SetClipboardText { .. } => return None,
+        AutomatedEvent::MouseMove { pos } => {
+            let MousePosition(x, y) = viewport(pos);
+            PlayerEvent::MouseMove { x, y }
+        }
+        AutomatedEvent::MouseDown {
+            pos, btn, index, ..
+        } => {
+            let MousePosition(x, y) = viewport(pos);
+            PlayerEvent::MouseDown {
+                x,
+                y,
+                button: btn.into(),
+                index,
+            }
+        }
+        AutomatedEvent::MouseUp { pos, btn } => {
+            let MousePosition(x, y) = viewport(pos);
+            PlayerEvent::MouseUp {
+                x,
+                y,
+                button: btn.into(),
+            }
+        }
+        AutomatedEvent::MouseLeave => PlayerEvent::MouseLeave,
+        AutomatedEvent::MouseWheel { lines, pixels } => PlayerEvent::MouseWheel {
+            delta: match (lines, pixels) {
+                (Some(lines), _) => MouseWheelDelta::Lines(lines),
+                (None, Some(pixels)) => MouseWheelDelta::Pixels(pixels),
+                (None, None) => return None,
+            },
+        },
+        AutomatedEvent::KeyDown { key_code } => PlayerEvent::KeyDown {
+            key_code: KeyCode::from_code(key_code),
+            key_char: None,
+        },
+        AutomatedEvent::KeyUp { key_code } => PlayerEvent::KeyUp {
+            key_code: KeyCode::from_code(key_code),
+            key_char: None,
+        },
+        AutomatedEvent::TextInput { codepoint } => PlayerEvent::TextInput { codepoint },
+        AutomatedEvent::TextControl { code } => PlayerEvent::TextControl { code: code.into() },
+        AutomatedEvent::FocusGained => PlayerEvent::FocusGained,
+        AutomatedEvent::FocusLost => PlayerEvent::FocusLost,
+        AutomatedEvent::TouchBegin { id, pos } => {
+            let MousePosition(x, y) = viewport(pos);
+            PlayerEvent::TouchBegin { id, x, y }
+        }
+        AutomatedEvent::TouchMove { id, pos } => {
+            let MousePosition(x, y) = viewport(pos);
+            PlayerEvent::TouchMove { id, x, y }
+        }
+        AutomatedEvent::TouchEnd { id, pos } => {
+            let MousePosition(x, y) = viewport(pos);
+            PlayerEvent::TouchEnd { id, x, y }
+        }
+        AutomatedEvent::Gesture {
+            gesture,
+            phase,
+            pos,
+        } => {
+            let MousePosition(x, y) = viewport(pos);
+            PlayerEvent::Gesture {
+                gesture: gesture.into(),
+                phase: phase.into(),
+                x,
+                y,
+            }
+        }
+        AutomatedEvent::TwoFingerTap { pos } => {
+            let MousePosition(x, y) = viewport(pos);
+            PlayerEvent::TwoFingerTap { x, y }
+        }
+        AutomatedEvent::PressAndTap { pos, tap_pos } => {
+            let MousePosition(x, y) = viewport(pos);
+            let MousePosition(tap_x, tap_y) = viewport(tap_pos);
+            PlayerEvent::PressAndTap { x, y, tap_x, tap_y }
+        }
+        AutomatedEvent::GamepadConnected { gamepad } => PlayerEvent::GamepadConnected { gamepad },
+        AutomatedEvent::GamepadDisconnected { gamepad } => {
+            PlayerEvent::GamepadDisconnected { gamepad }
+        }
+        AutomatedEvent::GamepadButtonDown { gamepad, button } => PlayerEvent::GamepadButtonDown {
+            gamepad,
+            button: button.into(),
+        },
+        AutomatedEvent::GamepadButtonUp { gamepad, button } => PlayerEvent::GamepadButtonUp {
+            gamepad,
+            button: button.into(),
+        },
+        AutomatedEvent::GamepadAxisChanged {
+            gamepad,
+            axis,
+            value,
+        } => PlayerEvent::GamepadAxisChanged {
+            gamepad,
+            axis: axis.into(),
+            value,
+        },
+    })
+}
+
+fn transform(matrix: Matrix, MousePosition(x, y): MousePosition) -> MousePosition {
+    let position = matrix * Point::from_pixels(x, y);
+    MousePosition(position.x.to_pixels(), position.y.to_pixels())
+}
+
+fn recordable_button(button: MouseButton) -> Option<format::MouseButton> {
+    match button {
+        MouseButton::Unknown => None,
+        MouseButton::Left => Some(format::MouseButton::Left),
+        MouseButton::Middle => Some(format::MouseButton::Middle),
+        MouseButton::Right => Some(format::MouseButton::Right),
+    }
+}
+
+impl From<format::MouseButton> for MouseButton {
+    fn from(button: format::MouseButton) -> Self {
+        match button {
+            format::MouseButton::Left => Self::Left,
+            format::MouseButton::Middle => Self::Middle,
+            format::MouseButton::Right => Self::Right,
+        }
+    }
+}
+
+impl From<format::Gesture> for Gesture {
+    fn from(gesture: format::Gesture) -> Self {
+        match gesture {
+            format::Gesture::Pan { offset_x, offset_y } => Self::Pan { offset_x, offset_y },
+            format::Gesture::Rotate { rotation } => Self::Rotate { rotation },
+            format::Gesture::Swipe { offset_x, offset_y } => Self::Swipe { offset_x, offset_y },
+            format::Gesture::Zoom { scale_x, scale_y } => Self::Zoom { scale_x, scale_y },
+        }
+    }
+}
+
+impl From<Gesture> for format::Gesture {
+    fn from(gesture: Gesture) -> Self {
+        match gesture {
+            Gesture::Pan { offset_x, offset_y } => Self::Pan { offset_x, offset_y },
+            Gesture::Rotate { rotation } => Self::Rotate { rotation },
+            Gesture::Swipe { offset_x, offset_y } => Self::Swipe { offset_x, offset_y },
+            Gesture::Zoom { scale_x, scale_y } => Self::Zoom { scale_x, scale_y },
+        }
+    }
+}
+
+/// Converts between an enum of the player and the enum of the same name in
+/// the input format, which have the same unit variants.
+macro_rules! convert_variants {
+    ($name:ident { $($variant:ident),* $(,)? }) => {
+        impl From<format::$name> for $name {
+            fn from(value: format::$name) -> Self {
+                match value {
+                    $(format::$name::$variant => Self::$variant,)*
+                }
+            }
+        }
+
+        impl From<$name> for format::$name {
+            fn from(value: $name) -> Self {
+                match value {
+                    $($name::$variant => Self::$variant,)*
+                }
+            }
+        }
+    };
+}
+
+convert_variants!(GesturePhase {
+    Begin,
+    Update,
+    End,
+    All
+});
+convert_variants!(GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
+});
+convert_variants!(GamepadButton {
+    South,
+    East,
+    North,
+    West,
+    LeftTrigger,
+    LeftTrigger2,
+    RightTrigger,
+    RightTrigger2,
+    Select,
+    Start,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+});
+convert_variants!(TextControlCode {
+    MoveLeft,
+    MoveLeftWord,
+    MoveLeftLine,
+    MoveLeftDocument,
+    MoveRight,
+    MoveRightWord,
+    MoveRightLine,
+    MoveRightDocument,
+    SelectLeft,
+    SelectLeftWord,
+    SelectLeftLine,
+    SelectLeftDocument,
+    SelectRight,
+    SelectRightWord,
+    SelectRightLine,
+    SelectRightDocument,
+    SelectAll,
+    Copy,
+    Paste,
+    Cut,
+    Backspace,
+    BackspaceWord,
+    Enter,
+    Delete,
+    DeleteWord,
+});
+
+/// Input that was recorded from a player, or that is going to be replayed.
+#[derive(Debug, Clone, Default)]
+pub struct InputRecording {
+    events: Vec<AutomatedEvent>,
+}
+
+impl InputRecording {
+    pub fn new(events: Vec<AutomatedEvent>) -> Self {
+        Self { events }
+    }
+
+    pub fn events(&self) -> &[AutomatedEvent] {
+        &self.events
+    }
+
+    /// The number of ticks that it takes to replay all of this input, which
+    /// is what `num_ticks` of a test replaying it should be set to.
+    pub fn ticks(&self) -> usize {
+        1 + self
+            .events
+            .iter()
+            .filter(|event| matches!(event, AutomatedEvent::Wait))
+            .count()
+    }
+
+    /// Reads input in the format of `input.json` files.
+    pub fn from_reader(reader: impl std::io::Read) -> Result<Self, serde_json::Error> {
+        Ok(Self::new(serde_json::from_reader(reader)?))
+    }
+
+    /// Writes input in the format of `input.json` files.
+    pub fn to_writer(&self, writer: impl std::io::Write) -> Result<(), serde_json::Error> {
+        serde_json::to_writer_pretty(writer, &self.events)
+    }
+}
+
+/// What the player is doing with its input.
+pub(crate) enum InputSession {
+    Record {
+        recording: InputRecording,
+
+        /// Whether the player has ticked since recording started.
+        ticked: bool,
+    },
+    Replay {
+        recording: InputRecording,
+
+        /// The index of the next event to replay.
+        position: usize,
+    },
+}
+
+impl InputSession {
+    pub fn record() -> Self {
+        Self::Record {
+            recording: InputRecording::default(),
+            ticked: false,
+        }
+    }
+
+    pub fn replay(recording: InputRecording) -> Self {
+        Self::Replay {
+            recording,
+            position: 0,
+        }
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        match self {
+            Self::Record { .. } => false,
+            Self::Replay {
+                recording,
+                position,
+            } => *position < recording.events.len(),
+        }
+    }
+
+    pub fn record_event(&mut self, event: AutomatedEvent) {
+        if let Self::Record { recording, .. } = self {
+            recording.events.push(event);
+        }
+    }
+
+    /// Ends the current frame of recorded input, unless this is the first tick.
+    pub fn record_tick(&mut self) {
+        if let Self::Record { recording, ticked } = self {
+            if *ticked {
+                recording.events.push(AutomatedEvent::Wait);
+            }
+            *ticked = true;
+        }
+    }
+
+    /// Takes the next frame of input to replay.
+    pub fn next_replayed_frame(&mut self) -> Vec<AutomatedEvent> {
+        let Self::Replay {
+            recording,
+            position,
+        } = self
+        else {
+            return Vec::new();
+        };
+
+        let events = &recording.events[*position..];
+        let len = events
+            .iter()
+            .position(|event| matches!(event, AutomatedEvent::Wait))
+            .unwrap_or(events.len());
+        *position += (len + 1).min(events.len());
+        events[..len].to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replays_recorded_frames() {
+        let mut session = InputSession::record();
+        session.record_event(AutomatedEvent::MouseMove {
+            pos: MousePosition(10.0, 20.0),
+        });
+        session.record_tick();
+        session.record_event(AutomatedEvent::KeyDown { key_code: 65 });
+        session.record_event(AutomatedEvent::MouseLeave);
+        session.record_tick();
+        session.record_tick();
+        session.record_event(AutomatedEvent::KeyUp { key_code: 65 });
+        let InputSession::Record { recording, .. } = session else {
+            unreachable!();
+        };
+        assert_eq!(recording.ticks(), 3);
+
+        let mut session = InputSession::replay(recording);
+        let mut frames = Vec::new();
+        while session.is_replaying() {
+            frames.push(session.next_replayed_frame());
+        }
+        assert_eq!(
+            frames,
+            vec![
+                vec![
+                    AutomatedEvent::MouseMove {
+                        pos: MousePosition(10.0, 20.0),
+                    },
+                    AutomatedEvent::KeyDown { key_code: 65 },
+                    AutomatedEvent::MouseLeave,
+                ],
+                vec![],
+                vec![AutomatedEvent::KeyUp { key_code: 65 }],
+            ]
+        );
+    }
+
+    #[test]
+    fn converts_between_viewport_and_stage() {
+        let matrix = Matrix::scale(2.0, 2.0);
+        let event = PlayerEvent::MouseDown {
+            x: 20.0,
+            y: 40.0,
+            button: MouseButton::Left,
+            index: None,
+        };
+        let recorded = from_player_event(event, matrix.inverse().unwrap()).unwrap();
+        assert_eq!(
+            recorded,
+            AutomatedEvent::MouseDown {
+                pos: MousePosition(10.0, 20.0),
+                btn: format::MouseButton::Left,
+                index: None,
+                assert_handled: None,
+            }
+        );
+        assert!(matches!(
+            to_player_event(&recorded, matrix),
+            Some(PlayerEvent::MouseDown { x, y, .. }) if x == 20.0 && y == 40.0
+        ));
+
+        let unknown = PlayerEvent::MouseUp {
+            x: 0.0,
+            y: 0.0,
+            button: MouseButton::Unknown,
+        };
+        assert_eq!(from_player_event(unknown, matrix), None);
+    }
+
+    #[test]
+    fn reads_input_json() {
+        let json = r#"[
+            { "type": "MouseMove", "pos": [1.0, 2.0] },
+            {
+                "type": "MouseDown",
+                "pos": [1.0, 2.0],
+                "btn": "Left",
+                "assert_handled": { "value": true, "message": "should be handled" }
+            },
+            { "type": "Wait" },
+            { "type": "MouseLeave" },
+            { "type": "Gesture", "gesture": { "Zoom": { "scale_x": 2.0, "scale_y": 2.0 } }, "phase": "All", "pos": [0.0, 0.0] }
+        ]"#;
+        let recording = InputRecording::from_reader(json.as_bytes()).unwrap();
+        assert_eq!(recording.ticks(), 2);
+        assert_eq!(
+            recording.events()[1],
+            AutomatedEvent::MouseDown {
+                pos: MousePosition(1.0, 2.0),
+                btn: format::MouseButton::Left,
+                index: None,
+                assert_handled: Some(format::EventHandledAssertion {
+                    value: true,
+                    message: "should be handled".to_string(),
+                }),
+            }
+        );
+
+        let mut written = Vec::new();
+        recording.to_writer(&mut written).unwrap();
+        let reread = InputRecording::from_reader(&written[..]).unwrap();
+        assert_eq!(reread.events(), recording.events());
+    }
+}
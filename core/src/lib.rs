@@ -27,6 +27,7 @@ pub mod buffer;
 mod character;
 pub mod context;
 pub mod context_menu;
+mod debugger;
 mod drawing;
 mod ecma_conversions;
 pub mod events;
@@ -35,6 +36,7 @@ mod font;
 mod frame_lifecycle;
 mod html;
 mod input;
+pub mod input_recording;
 mod library;
 pub mod limits;
 pub mod loader;
@@ -44,14 +46,17 @@ mod net_connection;
 pub mod pixel_bender;
 mod player;
 mod prelude;
+mod print_job;
 pub mod sandbox;
 pub mod socket;
+mod sql;
 mod streams;
 pub mod string;
 pub mod tag_utils;
 pub mod timer;
 mod types;
 mod vminterface;
+mod worker;
 mod xml;
 
 pub mod backend;
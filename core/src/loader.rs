@@ -103,6 +103,7 @@ pub enum ContentType {
     Jpeg,
     Png,
     Gif,
+    JpegXr,
     Unknown,
 }
 
@@ -124,6 +125,7 @@ impl fmt::Display for ContentType {
             Self::Jpeg => write!(f, "JPEG"),
             Self::Png => write!(f, "PNG"),
             Self::Gif => write!(f, "GIF"),
+            Self::JpegXr => write!(f, "JPEG XR"),
             Self::Unknown => write!(f, "Unknown"),
         }
     }
@@ -133,6 +135,8 @@ impl ContentType {
     fn sniff(data: &[u8]) -> ContentType {
         if read_compression_type(data).is_ok() {
             ContentType::Swf
+        } else if cfg!(feature = "jpegxr") && data.starts_with(b"II\xbc") {
+            ContentType::JpegXr
         } else {
             determine_jpeg_tag_format(data).into()
         }
@@ -2084,7 +2088,7 @@ impl<'gc> Loader<'gc> {
             ContentType::Swf => {
                 Arc::new(SwfMovie::from_data(data, url.clone(), loader_url.clone())?)
             }
-            ContentType::Gif | ContentType::Jpeg | ContentType::Png => {
+            ContentType::Gif | ContentType::Jpeg | ContentType::Png | ContentType::JpegXr => {
                 Arc::new(SwfMovie::from_loaded_image(url.clone(), length))
             }
             ContentType::Unknown => Arc::new(SwfMovie::error_movie(url.clone())),
@@ -2209,7 +2213,7 @@ impl<'gc> Loader<'gc> {
 
                 return Ok(());
             }
-            ContentType::Gif | ContentType::Jpeg | ContentType::Png => {
+            ContentType::Gif | ContentType::Jpeg | ContentType::Png | ContentType::JpegXr => {
                 let library = activation
                     .context
                     .library
@@ -2219,7 +2223,11 @@ impl<'gc> Loader<'gc> {
 
                 // This will construct AVM2-side objects even under AVM1, but it doesn't matter,
                 // since Bitmap and BitmapData never have AVM1-side objects.
-                let bitmap = ruffle_render::utils::decode_define_bits_jpeg(data, None)?;
+                let bitmap = match sniffed_type {
+                    #[cfg(feature = "jpegxr")]
+                    ContentType::JpegXr => ruffle_render::jpegxr::decode_jpeg_xr(data)?,
+                    _ => ruffle_render::utils::decode_define_bits_jpeg(data, None)?,
+                };
 
                 let transparency = true;
                 let bitmap_data = BitmapData::new_with_pixels(
@@ -1,21 +1,37 @@
+mod rtmp;
+
 use crate::avm1::globals::netconnection::NetConnection as Avm1NetConnectionObject;
 use crate::avm1::Object as Avm1Object;
 use crate::avm2::object::{
-    NetConnectionObject as Avm2NetConnectionObject, ResponderObject as Avm2ResponderObject,
+    ArrayObject as Avm2ArrayObject, NetConnectionObject as Avm2NetConnectionObject,
+    ResponderObject as Avm2ResponderObject, SharedObjectObject as Avm2SharedObjectObject,
+    TObject as Avm2TObject,
+};
+use crate::avm2::{
+    Activation as Avm2Activation, ArrayStorage as Avm2ArrayStorage, Avm2,
+    EventObject as Avm2EventObject, Value as Avm2Value,
 };
-use crate::avm2::{Activation as Avm2Activation, Avm2, EventObject as Avm2EventObject};
 use crate::backend::navigator::{ErrorResponse, NavigatorBackend, OwnedFuture, Request};
 use crate::context::UpdateContext;
 use crate::loader::Error;
+use crate::socket::{SocketHandle, Sockets};
+use crate::streams::NetStream;
 use crate::string::AvmString;
 use crate::Player;
+use flash_lso::amf0::read::AMF0Decoder;
 use flash_lso::packet::{Header, Message, Packet};
-use flash_lso::types::{AMFVersion, Value as AmfValue};
+use flash_lso::types::{AMFVersion, Element, Value as AmfValue};
+use fnv::FnvHashMap;
 use gc_arena::{Collect, DynamicRoot, Rootable};
+use rtmp::{
+    Event as RtmpEvent, Message as RtmpMessage, MessageType as RtmpMessageType, RtmpConnection,
+    COMMAND_CHUNK_STREAM, STREAM_CHUNK_STREAM,
+};
 use slotmap::{new_key_type, SlotMap};
 use std::fmt::{Debug, Formatter};
 use std::rc::Rc;
 use std::sync::{Mutex, Weak};
+use url::Url;
 
 new_key_type! {
     pub struct NetConnectionHandle;
@@ -129,10 +145,7 @@ impl<'gc> NetConnections<'gc> {
         target: O,
     ) {
         let target = target.into();
-        let connection = NetConnection {
-            object: target,
-            protocol: NetConnectionProtocol::Local,
-        };
+        let connection = NetConnection::new(target, NetConnectionProtocol::Local);
         let handle = context.net_connections.connections.insert(connection);
 
         if let Some(existing_handle) = target.set_handle(Some(handle)) {
@@ -170,14 +183,14 @@ impl<'gc> NetConnections<'gc> {
         url: String,
     ) {
         let target = target.into();
-        let connection = NetConnection {
-            object: target,
-            protocol: NetConnectionProtocol::FlashRemoting(FlashRemoting {
+        let connection = NetConnection::new(
+            target,
+            NetConnectionProtocol::FlashRemoting(FlashRemoting {
                 url,
                 headers: vec![],
                 outgoing_queue: vec![],
             }),
-        };
+        );
         let handle = context.net_connections.connections.insert(connection);
 
         if let Some(existing_handle) = target.set_handle(Some(handle)) {
@@ -187,11 +200,74 @@ impl<'gc> NetConnections<'gc> {
         // No open event here
     }
 
+    /// Connect to an RTMP server, with `arguments` passed on to the server's
+    /// `connect` handler.
+    ///
+    /// Only plain `rtmp://` is supported; the tunnelled, TLS and encrypted
+    /// variants fail to connect.
+    pub fn connect_to_rtmp<O: Into<NetConnectionObject<'gc>>>(
+        context: &mut UpdateContext<'gc>,
+        target: O,
+        url: String,
+        arguments: Vec<AmfValue>,
+    ) {
+        let target = target.into();
+        let parsed_url = Url::parse(&url).ok().filter(|url| url.scheme() == "rtmp");
+        let connection = NetConnection::new(
+            target,
+            NetConnectionProtocol::Rtmp(Box::new(Rtmp {
+                url,
+                connect_arguments: arguments,
+                socket: None,
+                connection: RtmpConnection::new(),
+                connected: false,
+                next_transaction_id: CONNECT_TRANSACTION_ID + 1,
+                responders: FnvHashMap::default(),
+            })),
+        );
+        let handle = context.net_connections.connections.insert(connection);
+
+        if let Some(existing_handle) = target.set_handle(Some(handle)) {
+            NetConnections::close(context, existing_handle, false);
+        }
+
+        let Some(host) = parsed_url
+            .as_ref()
+            .and_then(|url| url.host_str())
+            .map(|host| host.to_string())
+        else {
+            tracing::warn!("Unsupported RTMP URL {:?}", parsed_url.map(String::from));
+            Self::fail_rtmp(context, handle);
+            return;
+        };
+        let port = parsed_url
+            .and_then(|url| url.port())
+            .unwrap_or(rtmp::DEFAULT_PORT);
+
+        let socket = context
+            .sockets
+            .connect_net_connection(context.navigator, handle, host, port);
+        if let Some(NetConnectionProtocol::Rtmp(rtmp)) = context
+            .net_connections
+            .connections
+            .get_mut(handle)
+            .map(|c| &mut c.protocol)
+        {
+            rtmp.socket = Some(socket);
+        }
+    }
+
     pub fn close(context: &mut UpdateContext<'gc>, handle: NetConnectionHandle, is_explicit: bool) {
         let Some(connection) = context.net_connections.connections.remove(handle) else {
             return;
         };
 
+        if let NetConnectionProtocol::Rtmp(rtmp) = &connection.protocol {
+            if let Some(socket) = rtmp.socket {
+                context.sockets.close(socket);
+            }
+        }
+
         match connection.object {
             NetConnectionObject::Avm2(object) => {
                 let mut activation = Avm2Activation::from_nothing(context);
@@ -244,7 +320,628 @@ impl<'gc> NetConnections<'gc> {
 
     pub fn update_connections(context: &mut UpdateContext<'gc>) {
         for (handle, connection) in context.net_connections.connections.iter_mut() {
-            connection.update(handle, context.navigator, context.player.clone());
+            connection.update(
+                handle,
+                context.navigator,
+                context.sockets,
+                context.player.clone(),
+            );
+        }
+    }
+
+    pub fn is_rtmp(&self, handle: NetConnectionHandle) -> bool {
+        self.connections
+            .get(handle)
+            .is_some_and(|c| matches!(c.protocol, NetConnectionProtocol::Rtmp(_)))
+    }
+
+    /// Handle the RTMP socket of a connection opening (or failing to).
+    pub fn on_socket_connect(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        success: bool,
+    ) {
+        if success {
+            // The start of the handshake has been waiting for this.
+            Self::flush_rtmp(context, handle);
+        } else {
+            if let Some(NetConnectionProtocol::Rtmp(rtmp)) = context
+                .net_connections
+                .connections
+                .get_mut(handle)
+                .map(|c| &mut c.protocol)
+            {
+                rtmp.socket = None;
+            }
+            Self::fail_rtmp(context, handle);
+        }
+    }
+
+    /// Handle data arriving on the RTMP socket of a connection.
+    pub fn on_socket_data(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        data: &[u8],
+    ) {
+        let Some(NetConnectionProtocol::Rtmp(rtmp)) = context
+            .net_connections
+            .connections
+            .get_mut(handle)
+            .map(|c| &mut c.protocol)
+        else {
+            return;
+        };
+
+        let events = match rtmp.connection.receive(data) {
+            Ok(events) => events,
+            Err(e) => {
+                tracing::error!("RTMP connection to {} failed: {e}", rtmp.url);
+                if rtmp.connected {
+                    Self::close(context, handle, false);
+                } else {
+                    Self::fail_rtmp(context, handle);
+                }
+                return;
+            }
+        };
+
+        for event in events {
+            match event {
+                RtmpEvent::Connected => Self::send_rtmp_connect(context, handle),
+                RtmpEvent::Message(message) => Self::handle_rtmp_message(context, handle, message),
+                RtmpEvent::UserControl { event, stream_id } => {
+                    tracing::debug!("RTMP user control event {event:?} for stream {stream_id}");
+                }
+            }
+        }
+
+        Self::flush_rtmp(context, handle);
+    }
+
+    /// Handle the RTMP socket of a connection being closed by the server.
+    pub fn on_socket_close(context: &mut UpdateContext<'gc>, handle: NetConnectionHandle) {
+        let Some(NetConnectionProtocol::Rtmp(rtmp)) = context
+            .net_connections
+            .connections
+            .get_mut(handle)
+            .map(|c| &mut c.protocol)
+        else {
+            return;
+        };
+
+        rtmp.socket = None;
+        if rtmp.connected {
+            Self::close(context, handle, false);
+        } else {
+            Self::fail_rtmp(context, handle);
+        }
+    }
+
+    /// Send any queued RTMP data of a connection to its socket.
+    fn flush_rtmp(context: &mut UpdateContext<'gc>, handle: NetConnectionHandle) {
+        if let Some(NetConnectionProtocol::Rtmp(rtmp)) = context
+            .net_connections
+            .connections
+            .get_mut(handle)
+            .map(|c| &mut c.protocol)
+        {
+            rtmp.flush(context.sockets);
+        }
+    }
+
+    /// Give up on an RTMP connection that never got connected.
+    fn fail_rtmp(context: &mut UpdateContext<'gc>, handle: NetConnectionHandle) {
+        let Some(connection) = context.net_connections.connections.remove(handle) else {
+            return;
+        };
+
+        if let NetConnectionProtocol::Rtmp(rtmp) = &connection.protocol {
+            if let Some(socket) = rtmp.socket {
+                context.sockets.close(socket);
+            }
+        }
+
+        connection.object.set_handle(None);
+        dispatch_status(
+            context,
+            connection.object,
+            &status_info("NetConnection.Connect.Failed", "error"),
+        );
+    }
+
+    /// Send the `connect` command, once the handshake is done.
+    fn send_rtmp_connect(context: &mut UpdateContext<'gc>, handle: NetConnectionHandle) {
+        let flash_version = context.system.get_version_string(context.avm1);
+        let swf_url = context.swf.url().to_string();
+        let Some(NetConnectionProtocol::Rtmp(rtmp)) = context
+            .net_connections
+            .connections
+            .get_mut(handle)
+            .map(|c| &mut c.protocol)
+        else {
+            return;
+        };
+
+        let app = Url::parse(&rtmp.url)
+            .map(|url| url.path().trim_start_matches('/').to_string())
+            .unwrap_or_default();
+        let properties = [
+            ("app", AmfValue::String(app)),
+            ("flashVer", AmfValue::String(flash_version)),
+            ("swfUrl", AmfValue::String(swf_url)),
+            ("tcUrl", AmfValue::String(rtmp.url.clone())),
+            ("fpad", AmfValue::Bool(false)),
+            ("capabilities", AmfValue::Number(239.0)),
+            ("audioCodecs", AmfValue::Number(3575.0)),
+            ("videoCodecs", AmfValue::Number(252.0)),
+            ("videoFunction", AmfValue::Number(1.0)),
+            // We only speak AMF0.
+            ("objectEncoding", AmfValue::Number(0.0)),
+        ];
+        let command_object = AmfValue::Object(
+            flash_lso::types::ObjectId::INVALID,
+            properties
+                .into_iter()
+                .map(|(name, value)| Element::new(name, Rc::new(value)))
+                .collect(),
+            None,
+        );
+
+        let arguments = std::mem::take(&mut rtmp.connect_arguments);
+        let mut payload = Vec::new();
+        write_amf0(&mut payload, &AmfValue::String("connect".to_string()));
+        write_amf0(
+            &mut payload,
+            &AmfValue::Number(CONNECT_TRANSACTION_ID.into()),
+        );
+        write_amf0(&mut payload, &command_object);
+        for argument in &arguments {
+            write_amf0(&mut payload, argument);
+        }
+        rtmp.connection.send(
+            COMMAND_CHUNK_STREAM,
+            &RtmpMessage::new(RtmpMessageType::CommandAmf0, 0, payload),
+        );
+    }
+
+    fn handle_rtmp_message(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        message: RtmpMessage,
+    ) {
+        // AMF3 command, data and shared object messages start with an extra
+        // format byte, but are otherwise AMF0 that switches to AMF3 as needed.
+        let payload = match message.message_type {
+            RtmpMessageType::CommandAmf3
+            | RtmpMessageType::DataAmf3
+            | RtmpMessageType::SharedObjectAmf3 => message.payload.get(1..).unwrap_or_default(),
+            _ => &message.payload[..],
+        };
+
+        match message.message_type {
+            RtmpMessageType::CommandAmf0 | RtmpMessageType::CommandAmf3 => {
+                Self::handle_rtmp_command(context, handle, message.stream_id, read_amf0(payload));
+            }
+            RtmpMessageType::Audio
+            | RtmpMessageType::Video
+            | RtmpMessageType::DataAmf0
+            | RtmpMessageType::DataAmf3 => {
+                let stream = context
+                    .net_connections
+                    .connections
+                    .get(handle)
+                    .and_then(|c| c.streams.get(&message.stream_id).copied());
+                let tag_type = match message.message_type {
+                    RtmpMessageType::Audio => 8,
+                    RtmpMessageType::Video => 9,
+                    _ => 18,
+                };
+                if let Some(stream) = stream {
+                    stream.append_rtmp_message(context, tag_type, message.timestamp, payload);
+                }
+            }
+            RtmpMessageType::Aggregate => {
+                // Aggregate messages are a sequence of FLV tags, each followed
+                // by its size, with timestamps relative to the message.
+                let mut data = payload;
+                let mut first_timestamp = None;
+                while let Some(header) = data.get(0..11) {
+                    let size = u32::from_be_bytes([0, header[1], header[2], header[3]]) as usize;
+                    let timestamp =
+                        u32::from_be_bytes([header[7], header[4], header[5], header[6]]);
+                    let Some(body) = data.get(11..11 + size) else {
+                        break;
+                    };
+                    let Some(message_type) = rtmp::MessageType::from_u8(header[0]) else {
+                        break;
+                    };
+
+                    let first_timestamp = *first_timestamp.get_or_insert(timestamp);
+                    Self::handle_rtmp_message(
+                        context,
+                        handle,
+                        RtmpMessage {
+                            message_type,
+                            timestamp: message
+                                .timestamp
+                                .wrapping_add(timestamp.wrapping_sub(first_timestamp)),
+                            stream_id: message.stream_id,
+                            payload: body.to_vec(),
+                        },
+                    );
+                    data = data.get(11 + size + 4..).unwrap_or_default();
+                }
+            }
+            RtmpMessageType::SharedObjectAmf0 | RtmpMessageType::SharedObjectAmf3 => {
+                match SharedObjectMessage::parse(payload) {
+                    Some(message) => Self::handle_shared_object_message(context, handle, message),
+                    None => tracing::warn!("Ignoring invalid RTMP shared object message"),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_rtmp_command(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        stream_id: u32,
+        values: Vec<Rc<AmfValue>>,
+    ) {
+        let Some(connection) = context.net_connections.connections.get(handle) else {
+            return;
+        };
+        let object = connection.object;
+        let stream = connection.streams.get(&stream_id).copied();
+
+        let name = values
+            .first()
+            .and_then(|v| amf_string(v))
+            .unwrap_or_default();
+        let transaction_id = values.get(1).and_then(|v| amf_number(v)).unwrap_or(0.0) as u32;
+        // The third value is a command object, which is always null from
+        // servers nowadays.
+        let arguments = values.get(3..).unwrap_or_default();
+        let info = arguments
+            .first()
+            .cloned()
+            .unwrap_or_else(|| Rc::new(AmfValue::Null));
+
+        match name {
+            "_result" | "_error" => {
+                Self::handle_rtmp_response(
+                    context,
+                    handle,
+                    transaction_id,
+                    name == "_result",
+                    info,
+                );
+            }
+            "onStatus" if stream_id != 0 => {
+                if let Some(stream) = stream {
+                    let info = status_pairs(context, &info);
+                    stream.trigger_status_event(context, info);
+                }
+            }
+            "onStatus" => dispatch_status(context, object, &info),
+            "close" => Self::close(context, handle, false),
+            _ => {
+                call_client_method(context, object, name, arguments);
+
+                // The server may be waiting for an answer. We don't pass on
+                // the return value, but at least let it know the call went
+                // through.
+                if transaction_id != 0 {
+                    if let Some(NetConnectionProtocol::Rtmp(rtmp)) = context
+                        .net_connections
+                        .connections
+                        .get_mut(handle)
+                        .map(|c| &mut c.protocol)
+                    {
+                        rtmp.send_command(0, "_result", transaction_id, [&AmfValue::Null]);
+                    }
+                }
+            }
+        }
+    }
+
+    fn handle_rtmp_response(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        transaction_id: u32,
+        success: bool,
+        info: Rc<AmfValue>,
+    ) {
+        let Some(connection) = context.net_connections.connections.get_mut(handle) else {
+            return;
+        };
+        let object = connection.object;
+        let NetConnectionProtocol::Rtmp(rtmp) = &mut connection.protocol else {
+            return;
+        };
+
+        if transaction_id == CONNECT_TRANSACTION_ID && !rtmp.connected {
+            rtmp.connected = success;
+            dispatch_status(context, object, &info);
+            if !success {
+                // The server rejected us, and will close the socket.
+                Self::close(context, handle, false);
+            }
+            return;
+        }
+
+        if let Some((stream, name)) = connection.pending_streams.remove(&transaction_id) {
+            match amf_number(&info) {
+                Some(stream_id) if success => {
+                    let stream_id = stream_id as u32;
+                    connection.streams.insert(stream_id, stream);
+                    stream.start_rtmp_playback(context, stream_id);
+                    Self::send_play(context, handle, stream, name);
+                }
+                _ => stream.trigger_status_event(
+                    context,
+                    vec![("code", "NetStream.Play.Failed"), ("level", "error")],
+                ),
+            }
+            return;
+        }
+
+        if let Some(responder) = rtmp.responders.remove(&transaction_id) {
+            let callback = if success {
+                ResponderCallback::Result
+            } else {
+                ResponderCallback::Status
+            };
+            responder.call(context, callback, info);
+        }
+    }
+
+    /// Play the named stream from the server on a `NetStream`.
+    pub fn play_stream(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        stream: NetStream<'gc>,
+        name: String,
+    ) {
+        let Some(connection) = context.net_connections.connections.get_mut(handle) else {
+            return;
+        };
+        let NetConnectionProtocol::Rtmp(rtmp) = &mut connection.protocol else {
+            return;
+        };
+
+        match stream.rtmp_stream_id() {
+            Some(stream_id) if connection.streams.contains_key(&stream_id) => {
+                stream.start_rtmp_playback(context, stream_id);
+                Self::send_play(context, handle, stream, name);
+            }
+            _ => {
+                // We need a stream on the server first.
+                let transaction_id = rtmp.next_transaction_id();
+                rtmp.send_command(0, "createStream", transaction_id, []);
+                connection
+                    .pending_streams
+                    .insert(transaction_id, (stream, name));
+            }
+        }
+    }
+
+    fn send_play(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        stream: NetStream<'gc>,
+        name: String,
+    ) {
+        let Some(stream_id) = stream.rtmp_stream_id() else {
+            return;
+        };
+        let buffer_time = (stream.buffer_time() * 1000.0) as u32;
+        if let Some(NetConnectionProtocol::Rtmp(rtmp)) = context
+            .net_connections
+            .connections
+            .get_mut(handle)
+            .map(|c| &mut c.protocol)
+        {
+            rtmp.send_command(stream_id, "play", 0, [&AmfValue::String(name)]);
+            rtmp.connection.set_buffer_length(stream_id, buffer_time);
+        }
+    }
+
+    /// Send a command (such as `pause` or `seek`) for an RTMP stream.
+    pub fn send_stream_command(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        stream_id: u32,
+        command: &str,
+        arguments: Vec<AmfValue>,
+    ) {
+        if let Some(NetConnectionProtocol::Rtmp(rtmp)) = context
+            .net_connections
+            .connections
+            .get_mut(handle)
+            .map(|c| &mut c.protocol)
+        {
+            rtmp.send_command(stream_id, command, 0, &arguments);
+        }
+    }
+
+    /// Start synchronizing a remote shared object over a connection.
+    pub fn connect_shared_object(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        shared_object: Avm2SharedObjectObject<'gc>,
+    ) -> bool {
+        let Some(connection) = context.net_connections.connections.get_mut(handle) else {
+            return false;
+        };
+        let NetConnectionProtocol::Rtmp(rtmp) = &mut connection.protocol else {
+            return false;
+        };
+        if !rtmp.connected {
+            return false;
+        }
+
+        let name = shared_object.name().clone();
+        rtmp.send_shared_object_events(&name, vec![SharedObjectEvent::Use]);
+        connection.shared_objects.insert(name, shared_object);
+        true
+    }
+
+    /// Send changes to a remote shared object to the server.
+    pub fn send_shared_object_events(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        name: &str,
+        events: Vec<SharedObjectEvent>,
+    ) {
+        if let Some(NetConnectionProtocol::Rtmp(rtmp)) = context
+            .net_connections
+            .connections
+            .get_mut(handle)
+            .map(|c| &mut c.protocol)
+        {
+            rtmp.send_shared_object_events(name, events);
+        }
+    }
+
+    /// Stop synchronizing a remote shared object.
+    pub fn close_shared_object(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        name: &str,
+    ) {
+        let Some(connection) = context.net_connections.connections.get_mut(handle) else {
+            return;
+        };
+        if connection.shared_objects.remove(name).is_some() {
+            if let NetConnectionProtocol::Rtmp(rtmp) = &mut connection.protocol {
+                rtmp.send_shared_object_events(name, vec![SharedObjectEvent::Release]);
+            }
+        }
+    }
+
+    fn handle_shared_object_message(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        message: SharedObjectMessage,
+    ) {
+        let Some(shared_object) = context
+            .net_connections
+            .connections
+            .get(handle)
+            .and_then(|c| c.shared_objects.get(&message.name).copied())
+        else {
+            return;
+        };
+
+        let mut activation = Avm2Activation::from_nothing(context);
+        let mut changes = Vec::new();
+        for event in message.events {
+            let data = shared_object.data();
+            let result = match event {
+                SharedObjectEvent::Change { name, value } => {
+                    let property = AvmString::new_utf8(activation.gc(), &name);
+                    let old_value = data
+                        .get_public_property(property, &mut activation)
+                        .unwrap_or(Avm2Value::Undefined);
+                    crate::avm2::amf::deserialize_value(&mut activation, &value)
+                        .and_then(|value| {
+                            data.set_public_property(property, value, &mut activation)
+                        })
+                        .map(|_| changes.push(("change", Some(name), Some(old_value))))
+                }
+                SharedObjectEvent::Success { name } => {
+                    changes.push(("success", Some(name), None));
+                    Ok(())
+                }
+                SharedObjectEvent::Remove { name } => {
+                    let property = AvmString::new_utf8(activation.gc(), &name);
+                    data.delete_public_property(&mut activation, property)
+                        .map(|_| changes.push(("delete", Some(name), None)))
+                }
+                SharedObjectEvent::Clear => {
+                    changes.push(("clear", None, None));
+                    shared_object.reset_data(&mut activation)
+                }
+                SharedObjectEvent::SendMessage { values } => {
+                    let handler = values
+                        .first()
+                        .and_then(|v| amf_string(v))
+                        .unwrap_or_default();
+                    let handler = AvmString::new_utf8(activation.gc(), handler);
+                    let mut arguments = Vec::new();
+                    for value in values.iter().skip(1) {
+                        arguments.push(
+                            crate::avm2::amf::deserialize_value(&mut activation, value)
+                                .unwrap_or(Avm2Value::Undefined),
+                        );
+                    }
+                    shared_object
+                        .call_public_property(handler, &arguments, &mut activation)
+                        .map(|_| ())
+                }
+                SharedObjectEvent::Status { code, level } => {
+                    let code = AvmString::new_utf8(activation.gc(), code);
+                    let level = AvmString::new_utf8(activation.gc(), level);
+                    let event = Avm2EventObject::net_status_event(
+                        &mut activation,
+                        "netStatus",
+                        vec![("code", code), ("level", level)],
+                    );
+                    Avm2::dispatch_event(activation.context, event, shared_object.into());
+                    Ok(())
+                }
+                _ => Ok(()),
+            };
+
+            if let Err(e) = result {
+                tracing::error!("Unhandled error applying remote shared object event: {e:?}");
+            }
+        }
+
+        if changes.is_empty() {
+            return;
+        }
+
+        let mut change_list = Vec::new();
+        for (code, name, old_value) in changes {
+            let change = activation
+                .avm2()
+                .classes()
+                .object
+                .construct(&mut activation, &[])
+                .expect("Object is constructible");
+            let _ = change.set_public_property("code", code.into(), &mut activation);
+            if let Some(name) = name {
+                let name = AvmString::new_utf8(activation.gc(), name);
+                let _ = change.set_public_property("name", name.into(), &mut activation);
+            }
+            if let Some(old_value) = old_value {
+                let _ = change.set_public_property("oldValue", old_value, &mut activation);
+            }
+            change_list.push(change.into());
+        }
+
+        let change_list = Avm2ArrayObject::from_storage(
+            &mut activation,
+            Avm2ArrayStorage::from_args(&change_list),
+        );
+        let event = change_list.and_then(|change_list| {
+            activation.avm2().classes().syncevent.construct(
+                &mut activation,
+                &[
+                    "sync".into(),
+                    false.into(),
+                    false.into(),
+                    change_list.into(),
+                ],
+            )
+        });
+        match event {
+            Ok(event) => {
+                Avm2::dispatch_event(activation.context, event, shared_object.into());
+            }
+            Err(e) => tracing::error!("Couldn't create SyncEvent: {e:?}"),
         }
     }
 
@@ -342,33 +1039,55 @@ pub struct NetConnection<'gc> {
 
     #[collect(require_static)]
     protocol: NetConnectionProtocol,
+
+    /// The `NetStream`s playing over this connection, by RTMP stream ID.
+    streams: FnvHashMap<u32, NetStream<'gc>>,
+
+    /// `NetStream`s waiting for the server to create a stream for them, by
+    /// the transaction ID of the `createStream` call, along with the name of
+    /// the stream to play.
+    pending_streams: FnvHashMap<u32, (NetStream<'gc>, String)>,
+
+    /// The remote shared objects synchronized over this connection, by name.
+    shared_objects: FnvHashMap<String, Avm2SharedObjectObject<'gc>>,
 }
 
-impl NetConnection<'_> {
+impl<'gc> NetConnection<'gc> {
+    fn new(object: NetConnectionObject<'gc>, protocol: NetConnectionProtocol) -> Self {
+        Self {
+            object,
+            protocol,
+            streams: FnvHashMap::default(),
+            pending_streams: FnvHashMap::default(),
+            shared_objects: FnvHashMap::default(),
+        }
+    }
+
     pub fn is_connected(&self) -> bool {
-        match self.protocol {
+        match &self.protocol {
             NetConnectionProtocol::Local => true,
             NetConnectionProtocol::FlashRemoting(_) => false,
+            NetConnectionProtocol::Rtmp(rtmp) => rtmp.connected,
         }
     }
 
     pub fn connected_proxy_type(&self) -> Option<&'static str> {
         match self.protocol {
-            NetConnectionProtocol::Local => Some("none"),
+            NetConnectionProtocol::Local | NetConnectionProtocol::Rtmp(_) => Some("none"),
             NetConnectionProtocol::FlashRemoting(_) => None,
         }
     }
 
     pub fn far_id(&self) -> Option<&'static str> {
         match self.protocol {
-            NetConnectionProtocol::Local => Some(""),
+            NetConnectionProtocol::Local | NetConnectionProtocol::Rtmp(_) => Some(""),
             NetConnectionProtocol::FlashRemoting(_) => None,
         }
     }
 
     pub fn far_nonce(&self) -> Option<&'static str> {
         match self.protocol {
-            NetConnectionProtocol::Local => {
+            NetConnectionProtocol::Local | NetConnectionProtocol::Rtmp(_) => {
                 Some("0000000000000000000000000000000000000000000000000000000000000000")
             }
             NetConnectionProtocol::FlashRemoting(_) => None,
@@ -377,14 +1096,14 @@ impl NetConnection<'_> {
 
     pub fn near_id(&self) -> Option<&'static str> {
         match self.protocol {
-            NetConnectionProtocol::Local => Some(""),
+            NetConnectionProtocol::Local | NetConnectionProtocol::Rtmp(_) => Some(""),
             NetConnectionProtocol::FlashRemoting(_) => None,
         }
     }
 
     pub fn near_nonce(&self) -> Option<&'static str> {
         match self.protocol {
-            NetConnectionProtocol::Local => {
+            NetConnectionProtocol::Local | NetConnectionProtocol::Rtmp(_) => {
                 Some("0000000000000000000000000000000000000000000000000000000000000000")
             }
             NetConnectionProtocol::FlashRemoting(_) => None,
@@ -393,7 +1112,7 @@ impl NetConnection<'_> {
 
     pub fn protocol(&self) -> Option<&'static str> {
         match self.protocol {
-            NetConnectionProtocol::Local => Some("rtmp"),
+            NetConnectionProtocol::Local | NetConnectionProtocol::Rtmp(_) => Some("rtmp"),
             NetConnectionProtocol::FlashRemoting(_) => None,
         }
     }
@@ -402,12 +1121,13 @@ impl NetConnection<'_> {
         match &self.protocol {
             NetConnectionProtocol::Local => Some("null".to_string()), // Yes, it's a string "null", not a real null.
             NetConnectionProtocol::FlashRemoting(remoting) => Some(remoting.url.to_string()),
+            NetConnectionProtocol::Rtmp(rtmp) => Some(rtmp.url.to_string()),
         }
     }
 
     pub fn using_tls(&self) -> Option<bool> {
         match &self.protocol {
-            NetConnectionProtocol::Local => Some(false),
+            NetConnectionProtocol::Local | NetConnectionProtocol::Rtmp(_) => Some(false),
             NetConnectionProtocol::FlashRemoting(_) => None,
         }
     }
@@ -423,6 +1143,7 @@ impl NetConnection<'_> {
             NetConnectionProtocol::FlashRemoting(remoting) => {
                 remoting.send(command, responder_handle, message)
             }
+            NetConnectionProtocol::Rtmp(rtmp) => rtmp.call(command, responder_handle, message),
         }
     }
 
@@ -430,6 +1151,7 @@ impl NetConnection<'_> {
         &mut self,
         self_handle: NetConnectionHandle,
         navigator: &mut dyn NavigatorBackend,
+        sockets: &mut Sockets<'gc>,
         player: Weak<Mutex<Player>>,
     ) {
         match &mut self.protocol {
@@ -439,12 +1161,13 @@ impl NetConnection<'_> {
                     navigator.spawn_future(remoting.flush_queue(self_handle, player));
                 }
             }
+            NetConnectionProtocol::Rtmp(rtmp) => rtmp.flush(sockets),
         }
     }
 
     pub fn set_header(&mut self, header: Header) {
         match &mut self.protocol {
-            NetConnectionProtocol::Local => {}
+            NetConnectionProtocol::Local | NetConnectionProtocol::Rtmp(_) => {}
             NetConnectionProtocol::FlashRemoting(remoting) => {
                 remoting.set_header(header);
             }
@@ -459,6 +1182,512 @@ pub enum NetConnectionProtocol {
 
     /// Flash Remoting protocol, caused by connecting to a `http://` address.
     FlashRemoting(FlashRemoting),
+
+    /// Real Time Messaging Protocol, caused by connecting to a `rtmp://`
+    /// address.
+    Rtmp(Box<Rtmp>),
+}
+
+/// The transaction ID of the `connect` command.
+const CONNECT_TRANSACTION_ID: u32 = 1;
+
+pub struct Rtmp {
+    url: String,
+
+    /// The extra arguments to `NetConnection.connect`, to be sent along with
+    /// the `connect` command.
+    connect_arguments: Vec<AmfValue>,
+
+    socket: Option<SocketHandle>,
+
+    connection: RtmpConnection,
+
+    /// True once the server has accepted our `connect` command.
+    connected: bool,
+
+    next_transaction_id: u32,
+
+    /// The responders waiting for the result of a call, by transaction ID.
+    responders: FnvHashMap<u32, ResponderHandle>,
+}
+
+impl Debug for Rtmp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Rtmp")
+            .field("url", &self.url)
+            .field("socket", &self.socket)
+            .field("connected", &self.connected)
+            .finish()
+    }
+}
+
+impl Rtmp {
+    fn next_transaction_id(&mut self) -> u32 {
+        let id = self.next_transaction_id;
+        self.next_transaction_id += 1;
+        id
+    }
+
+    /// Queue up a command message.
+    ///
+    /// Commands on stream `0` are for the connection itself.
+    fn send_command<'a>(
+        &mut self,
+        stream_id: u32,
+        command: &str,
+        transaction_id: u32,
+        arguments: impl IntoIterator<Item = &'a AmfValue>,
+    ) {
+        let mut payload = Vec::new();
+        write_amf0(&mut payload, &AmfValue::String(command.to_string()));
+        write_amf0(&mut payload, &AmfValue::Number(transaction_id.into()));
+        write_amf0(&mut payload, &AmfValue::Null);
+        for argument in arguments {
+            write_amf0(&mut payload, argument);
+        }
+
+        let chunk_stream = if stream_id == 0 {
+            COMMAND_CHUNK_STREAM
+        } else {
+            STREAM_CHUNK_STREAM
+        };
+        self.connection.send(
+            chunk_stream,
+            &RtmpMessage::new(RtmpMessageType::CommandAmf0, stream_id, payload),
+        );
+    }
+
+    /// Call a method on the server, as `NetConnection.call` does.
+    fn call(
+        &mut self,
+        command: String,
+        responder_handle: Option<ResponderHandle>,
+        message: AmfValue,
+    ) {
+        if !self.connected {
+            tracing::warn!("Ignoring call to {command} on an unconnected RTMP connection");
+            return;
+        }
+
+        let transaction_id = match responder_handle {
+            Some(responder_handle) => {
+                let transaction_id = self.next_transaction_id();
+                self.responders.insert(transaction_id, responder_handle);
+                transaction_id
+            }
+            None => 0,
+        };
+
+        let arguments = match &message {
+            AmfValue::StrictArray(_, arguments) => arguments.as_slice(),
+            _ => &[],
+        };
+        self.send_command(
+            0,
+            &command,
+            transaction_id,
+            arguments.iter().map(|argument| argument.as_ref()),
+        );
+    }
+
+    fn send_shared_object_events(&mut self, name: &str, events: Vec<SharedObjectEvent>) {
+        let message = SharedObjectMessage {
+            name: name.to_string(),
+            version: 0,
+            persistent: false,
+            events,
+        };
+        self.connection.send(
+            COMMAND_CHUNK_STREAM,
+            &RtmpMessage::new(RtmpMessageType::SharedObjectAmf0, 0, message.serialize()),
+        );
+    }
+
+    /// Send everything queued up to the socket.
+    fn flush(&mut self, sockets: &mut Sockets<'_>) {
+        if let Some(socket) = self.socket {
+            if self.connection.has_output() {
+                sockets.send(socket, self.connection.take_output());
+            }
+        }
+    }
+}
+
+/// An event in an RTMP shared object message.
+#[derive(Debug)]
+pub enum SharedObjectEvent {
+    Use,
+    Release,
+    RequestChange { name: String, value: Rc<AmfValue> },
+    Change { name: String, value: Rc<AmfValue> },
+    Success { name: String },
+    SendMessage { values: Vec<Rc<AmfValue>> },
+    Status { code: String, level: String },
+    Clear,
+    Remove { name: String },
+    RequestRemove { name: String },
+    UseSuccess,
+}
+
+/// An RTMP shared object message, which carries any number of events for a
+/// single shared object.
+#[derive(Debug)]
+struct SharedObjectMessage {
+    name: String,
+    version: u32,
+    persistent: bool,
+    events: Vec<SharedObjectEvent>,
+}
+
+impl SharedObjectMessage {
+    fn parse(data: &[u8]) -> Option<Self> {
+        let (name, mut data) = read_utf8(data)?;
+        let version = u32::from_be_bytes(data.get(0..4)?.try_into().ok()?);
+        let flags = u32::from_be_bytes(data.get(4..8)?.try_into().ok()?);
+        data = data.get(12..)?;
+
+        let mut events = Vec::new();
+        while let Some(&event_type) = data.first() {
+            let length = u32::from_be_bytes(data.get(1..5)?.try_into().ok()?) as usize;
+            let body = data.get(5..5 + length)?;
+            data = &data[5 + length..];
+
+            let event = match event_type {
+                1 => SharedObjectEvent::Use,
+                2 => SharedObjectEvent::Release,
+                3 | 4 => {
+                    let (name, value) = read_utf8(body)?;
+                    let value = read_amf0(value).into_iter().next()?;
+                    if event_type == 3 {
+                        SharedObjectEvent::RequestChange { name, value }
+                    } else {
+                        SharedObjectEvent::Change { name, value }
+                    }
+                }
+                5 => SharedObjectEvent::Success {
+                    name: read_utf8(body)?.0,
+                },
+                6 => SharedObjectEvent::SendMessage {
+                    values: read_amf0(body),
+                },
+                7 => {
+                    let (code, rest) = read_utf8(body)?;
+                    let (level, _) = read_utf8(rest)?;
+                    SharedObjectEvent::Status { code, level }
+                }
+                8 => SharedObjectEvent::Clear,
+                9 => SharedObjectEvent::Remove {
+                    name: read_utf8(body)?.0,
+                },
+                10 => SharedObjectEvent::RequestRemove {
+                    name: read_utf8(body)?.0,
+                },
+                11 => SharedObjectEvent::UseSuccess,
+                _ => continue,
+            };
+            events.push(event);
+        }
+
+        Some(Self {
+            name,
+            version,
+            persistent: flags & 2 != 0,
+            events,
+        })
+    }
+
+    fn serialize(&self) -> Vec<u8> {
+        let mut output = Vec::new();
+        write_utf8(&mut output, &self.name);
+        output.extend_from_slice(&self.version.to_be_bytes());
+        output.extend_from_slice(&(if self.persistent { 2u32 } else { 0 }).to_be_bytes());
+        output.extend_from_slice(&[0; 4]);
+
+        for event in &self.events {
+            let mut body = Vec::new();
+            let event_type = match event {
+                SharedObjectEvent::Use => 1,
+                SharedObjectEvent::Release => 2,
+                SharedObjectEvent::RequestChange { name, value }
+                | SharedObjectEvent::Change { name, value } => {
+                    write_utf8(&mut body, name);
+                    write_amf0(&mut body, value);
+                    if matches!(event, SharedObjectEvent::RequestChange { .. }) {
+                        3
+                    } else {
+                        4
+                    }
+                }
+                SharedObjectEvent::Success { name } => {
+                    write_utf8(&mut body, name);
+                    5
+                }
+                SharedObjectEvent::SendMessage { values } => {
+                    for value in values {
+                        write_amf0(&mut body, value);
+                    }
+                    6
+                }
+                SharedObjectEvent::Status { code, level } => {
+                    write_utf8(&mut body, code);
+                    write_utf8(&mut body, level);
+                    7
+                }
+                SharedObjectEvent::Clear => 8,
+                SharedObjectEvent::Remove { name } => {
+                    write_utf8(&mut body, name);
+                    9
+                }
+                SharedObjectEvent::RequestRemove { name } => {
+                    write_utf8(&mut body, name);
+                    10
+                }
+                SharedObjectEvent::UseSuccess => 11,
+            };
+            output.push(event_type);
+            output.extend_from_slice(&(body.len() as u32).to_be_bytes());
+            output.extend_from_slice(&body);
+        }
+
+        output
+    }
+}
+
+/// Read a string with a 16-bit length prefix, returning it and the data after
+/// it.
+fn read_utf8(data: &[u8]) -> Option<(String, &[u8])> {
+    let length = u16::from_be_bytes(data.get(0..2)?.try_into().ok()?) as usize;
+    let string = data.get(2..2 + length)?;
+    Some((
+        String::from_utf8_lossy(string).into_owned(),
+        &data[2 + length..],
+    ))
+}
+
+fn write_utf8(output: &mut Vec<u8>, string: &str) {
+    // Longer strings can't be represented, and are cut short.
+    let bytes = &string.as_bytes()[..string.len().min(u16::MAX.into())];
+    output.extend_from_slice(&(bytes.len() as u16).to_be_bytes());
+    output.extend_from_slice(bytes);
+}
+
+/// Read a sequence of AMF0 values, as found in RTMP messages.
+fn read_amf0(mut data: &[u8]) -> Vec<Rc<AmfValue>> {
+    let mut decoder = AMF0Decoder::default();
+    let mut values = Vec::new();
+    while !data.is_empty() {
+        match decoder.parse_single_element(data) {
+            Ok((rest, value)) => {
+                values.push(Rc::new(AmfValue::clone(&value)));
+                data = rest;
+            }
+            Err(_) => {
+                tracing::warn!("Ignoring invalid AMF0 data in RTMP message");
+                break;
+            }
+        }
+    }
+    values
+}
+
+/// Write a value in AMF0, as used by RTMP messages.
+///
+/// Values that can only be represented in AMF3 are written as `undefined`.
+fn write_amf0(output: &mut Vec<u8>, value: &AmfValue) {
+    match value {
+        AmfValue::Number(n) => {
+            output.push(0x00);
+            output.extend_from_slice(&n.to_be_bytes());
+        }
+        AmfValue::Integer(n) => {
+            output.push(0x00);
+            output.extend_from_slice(&f64::from(*n).to_be_bytes());
+        }
+        AmfValue::Bool(b) => output.extend_from_slice(&[0x01, u8::from(*b)]),
+        AmfValue::String(s) if s.len() <= u16::MAX.into() => {
+            output.push(0x02);
+            write_utf8(output, s);
+        }
+        AmfValue::String(s) | AmfValue::XML(s, _) => {
+            output.push(if matches!(value, AmfValue::XML(..)) {
+                0x0F
+            } else {
+                0x0C
+            });
+            output.extend_from_slice(&(s.len() as u32).to_be_bytes());
+            output.extend_from_slice(s.as_bytes());
+        }
+        AmfValue::Object(_, elements, class) => {
+            match class {
+                Some(class) if !class.name.is_empty() => {
+                    output.push(0x10);
+                    write_utf8(output, &class.name);
+                }
+                _ => output.push(0x03),
+            }
+            for element in elements {
+                write_utf8(output, element.name());
+                write_amf0(output, element.value());
+            }
+            output.extend_from_slice(&[0, 0, 0x09]);
+        }
+        AmfValue::Null => output.push(0x05),
+        AmfValue::ECMAArray(_, dense, elements, length) => {
+            output.push(0x08);
+            output.extend_from_slice(&length.to_be_bytes());
+            for (i, value) in dense.iter().enumerate() {
+                write_utf8(output, &i.to_string());
+                write_amf0(output, value);
+            }
+            for element in elements {
+                write_utf8(output, element.name());
+                write_amf0(output, element.value());
+            }
+            output.extend_from_slice(&[0, 0, 0x09]);
+        }
+        AmfValue::StrictArray(_, values) => {
+            output.push(0x0A);
+            output.extend_from_slice(&(values.len() as u32).to_be_bytes());
+            for value in values {
+                write_amf0(output, value);
+            }
+        }
+        AmfValue::Date(time, _) => {
+            output.push(0x0B);
+            output.extend_from_slice(&time.to_be_bytes());
+            output.extend_from_slice(&[0, 0]);
+        }
+        _ => output.push(0x06),
+    }
+}
+
+fn amf_string(value: &AmfValue) -> Option<&str> {
+    match value {
+        AmfValue::String(s) => Some(s),
+        AmfValue::AMF3(value) => amf_string(value),
+        _ => None,
+    }
+}
+
+fn amf_number(value: &AmfValue) -> Option<f64> {
+    match value {
+        AmfValue::Number(n) => Some(*n),
+        AmfValue::Integer(n) => Some((*n).into()),
+        AmfValue::AMF3(value) => amf_number(value),
+        _ => None,
+    }
+}
+
+/// Build the info object of a status event.
+fn status_info(code: &str, level: &str) -> AmfValue {
+    AmfValue::Object(
+        flash_lso::types::ObjectId::INVALID,
+        vec![
+            Element::new("code", Rc::new(AmfValue::String(code.to_string()))),
+            Element::new("level", Rc::new(AmfValue::String(level.to_string()))),
+        ],
+        None,
+    )
+}
+
+/// Get the string properties of an info object received from a server.
+fn status_pairs<'gc>(
+    context: &UpdateContext<'gc>,
+    info: &AmfValue,
+) -> Vec<(AvmString<'gc>, AvmString<'gc>)> {
+    let elements = match info {
+        AmfValue::Object(_, elements, _) | AmfValue::ECMAArray(_, _, elements, _) => elements,
+        _ => return vec![],
+    };
+
+    elements
+        .iter()
+        .filter_map(|element| {
+            let value = amf_string(element.value())?;
+            Some((
+                AvmString::new_utf8(context.gc_context, element.name()),
+                AvmString::new_utf8(context.gc_context, value),
+            ))
+        })
+        .collect()
+}
+
+/// Dispatch a status event with the given info object to a `NetConnection`.
+fn dispatch_status<'gc>(
+    context: &mut UpdateContext<'gc>,
+    object: NetConnectionObject<'gc>,
+    info: &AmfValue,
+) {
+    match object {
+        NetConnectionObject::Avm2(object) => {
+            let mut activation = Avm2Activation::from_nothing(context);
+            let info = crate::avm2::amf::deserialize_value(&mut activation, info)
+                .unwrap_or(Avm2Value::Null);
+            let event = activation.avm2().classes().netstatusevent.construct(
+                &mut activation,
+                &["netStatus".into(), false.into(), false.into(), info],
+            );
+            match event {
+                Ok(event) => {
+                    Avm2::dispatch_event(activation.context, event, object.into());
+                }
+                Err(e) => tracing::error!("Couldn't create NetStatusEvent: {e:?}"),
+            }
+        }
+        NetConnectionObject::Avm1(object) => {
+            if let Err(e) = Avm1NetConnectionObject::send_callback(
+                context,
+                object,
+                ResponderCallback::Status,
+                info,
+            ) {
+                tracing::error!("Unhandled error sending connection callback: {e}");
+            }
+        }
+    }
+}
+
+/// Call a method on a `NetConnection`'s client, on behalf of the server.
+fn call_client_method<'gc>(
+    context: &mut UpdateContext<'gc>,
+    object: NetConnectionObject<'gc>,
+    name: &str,
+    arguments: &[Rc<AmfValue>],
+) {
+    match object {
+        NetConnectionObject::Avm2(object) => {
+            let mut activation = Avm2Activation::from_nothing(context);
+            let name = AvmString::new_utf8(activation.gc(), name);
+            let mut avm_arguments = Vec::new();
+            for argument in arguments {
+                avm_arguments.push(
+                    crate::avm2::amf::deserialize_value(&mut activation, argument)
+                        .unwrap_or(Avm2Value::Undefined),
+                );
+            }
+
+            let result = object
+                .get_public_property("client", &mut activation)
+                .and_then(|client| match client.as_object() {
+                    Some(client) => {
+                        client.call_public_property(name, &avm_arguments, &mut activation)
+                    }
+                    None => Ok(Avm2Value::Undefined),
+                });
+            if let Err(e) = result {
+                tracing::error!(
+                    "Unhandled error calling NetConnection client method {name}: {e:?}"
+                );
+            }
+        }
+        NetConnectionObject::Avm1(object) => {
+            if let Err(e) =
+                Avm1NetConnectionObject::call_client_method(context, object, name, arguments)
+            {
+                tracing::error!("Unhandled error calling NetConnection client method {name}: {e}");
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
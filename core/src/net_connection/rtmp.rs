@@ -0,0 +1,759 @@
+//! The RTMP handshake and chunk stream layers.
+//!
+//! This only turns bytes into messages and back again, and answers the
+//! protocol control messages that don't concern anybody else. The commands
+//! carried in those messages are handled by `NetConnections`.
+
+use fnv::FnvHashMap;
+use thiserror::Error;
+
+/// The port used by `rtmp://` URLs that don't specify one.
+pub const DEFAULT_PORT: u16 = 1935;
+
+/// The chunk stream used for protocol control messages.
+pub const CONTROL_CHUNK_STREAM: u32 = 2;
+
+/// The chunk stream used for commands on the connection itself.
+pub const COMMAND_CHUNK_STREAM: u32 = 3;
+
+/// The chunk stream used for commands on a `NetStream`.
+pub const STREAM_CHUNK_STREAM: u32 = 8;
+
+const RTMP_VERSION: u8 = 3;
+
+/// The size of the C1/S1 and C2/S2 handshake packets.
+const HANDSHAKE_SIZE: usize = 1536;
+
+/// The chunk size both ends use until told otherwise.
+const DEFAULT_CHUNK_SIZE: usize = 128;
+
+/// The chunk size we switch to for data we send, as soon as we're connected.
+const OUTGOING_CHUNK_SIZE: usize = 4096;
+
+/// The acknowledgement window both ends use until told otherwise.
+const DEFAULT_WINDOW_ACK_SIZE: u32 = 2_500_000;
+
+/// Timestamps that don't fit in 24 bits are stored in an extra field after
+/// the chunk header, and the header field is set to this value.
+const EXTENDED_TIMESTAMP: u32 = 0xFFFFFF;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error("Unsupported RTMP version {0}")]
+    UnsupportedVersion(u8),
+
+    #[error("Chunk stream {0} was continued before it was started")]
+    UnknownChunkStream(u32),
+
+    #[error("Invalid chunk size {0}")]
+    InvalidChunkSize(u32),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessageType {
+    SetChunkSize = 1,
+    Abort = 2,
+    Acknowledgement = 3,
+    UserControl = 4,
+    WindowAckSize = 5,
+    SetPeerBandwidth = 6,
+    Audio = 8,
+    Video = 9,
+    DataAmf3 = 15,
+    SharedObjectAmf3 = 16,
+    CommandAmf3 = 17,
+    DataAmf0 = 18,
+    SharedObjectAmf0 = 19,
+    CommandAmf0 = 20,
+    Aggregate = 22,
+}
+
+impl MessageType {
+    pub fn from_u8(n: u8) -> Option<Self> {
+        Some(match n {
+            1 => Self::SetChunkSize,
+            2 => Self::Abort,
+            3 => Self::Acknowledgement,
+            4 => Self::UserControl,
+            5 => Self::WindowAckSize,
+            6 => Self::SetPeerBandwidth,
+            8 => Self::Audio,
+            9 => Self::Video,
+            15 => Self::DataAmf3,
+            16 => Self::SharedObjectAmf3,
+            17 => Self::CommandAmf3,
+            18 => Self::DataAmf0,
+            19 => Self::SharedObjectAmf0,
+            20 => Self::CommandAmf0,
+            22 => Self::Aggregate,
+            _ => return None,
+        })
+    }
+}
+
+/// The User Control events we send or pass on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UserControlEvent {
+    StreamBegin = 0,
+    StreamEof = 1,
+    StreamDry = 2,
+    SetBufferLength = 3,
+    StreamIsRecorded = 4,
+    PingRequest = 6,
+    PingResponse = 7,
+}
+
+impl UserControlEvent {
+    pub fn from_u16(n: u16) -> Option<Self> {
+        Some(match n {
+            0 => Self::StreamBegin,
+            1 => Self::StreamEof,
+            2 => Self::StreamDry,
+            3 => Self::SetBufferLength,
+            4 => Self::StreamIsRecorded,
+            6 => Self::PingRequest,
+            7 => Self::PingResponse,
+            _ => return None,
+        })
+    }
+}
+
+/// A complete RTMP message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Message {
+    pub message_type: MessageType,
+
+    /// The message timestamp in milliseconds.
+    pub timestamp: u32,
+
+    /// The message stream this message belongs to. `0` is the connection
+    /// itself; other IDs are handed out by the server's `createStream`.
+    pub stream_id: u32,
+
+    pub payload: Vec<u8>,
+}
+
+impl Message {
+    pub fn new(message_type: MessageType, stream_id: u32, payload: Vec<u8>) -> Self {
+        Self {
+            message_type,
+            timestamp: 0,
+            stream_id,
+            payload,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// The handshake has completed and messages can now be sent.
+    Connected,
+
+    /// A message that isn't a protocol control message.
+    Message(Message),
+
+    /// A User Control event that the connection didn't handle itself.
+    UserControl {
+        event: UserControlEvent,
+        stream_id: u32,
+    },
+}
+
+/// The state of an incoming chunk stream, as required to decode the
+/// compressed headers of the chunks that follow.
+#[derive(Default)]
+struct ChunkStream {
+    /// The timestamp of the current message.
+    timestamp: u32,
+
+    /// The timestamp (or timestamp delta) field of the last header.
+    timestamp_field: u32,
+
+    /// Whether the last header had an extended timestamp.
+    extended_timestamp: bool,
+
+    message_length: usize,
+
+    message_type: u8,
+
+    stream_id: u32,
+
+    /// The part of the current message received so far.
+    payload: Vec<u8>,
+}
+
+/// The client end of an RTMP connection.
+///
+/// Data from the socket goes into `receive`, and data for the socket is
+/// collected with `take_output`.
+pub struct RtmpConnection {
+    /// True once the handshake has completed.
+    connected: bool,
+
+    /// Received data that hasn't been processed yet.
+    input: Vec<u8>,
+
+    /// Data waiting to be sent.
+    output: Vec<u8>,
+
+    in_chunk_size: usize,
+
+    out_chunk_size: usize,
+
+    chunk_streams: FnvHashMap<u32, ChunkStream>,
+
+    /// The number of bytes we may receive before we have to send an
+    /// acknowledgement.
+    window_ack_size: u32,
+
+    /// The total number of bytes received, wrapping around at 2^32 as the
+    /// spec requires.
+    bytes_received: u32,
+
+    /// The value of `bytes_received` when we last sent an acknowledgement.
+    bytes_acknowledged: u32,
+}
+
+impl RtmpConnection {
+    /// Start a new connection, queueing up the first part of the handshake.
+    pub fn new() -> Self {
+        let mut output = Vec::with_capacity(1 + HANDSHAKE_SIZE);
+        output.push(RTMP_VERSION);
+        // C1 is a timestamp, four zero bytes, and then arbitrary data. Flash
+        // Player uses random data, but servers don't look at it, and a fixed
+        // pattern keeps the handshake reproducible.
+        output.extend_from_slice(&[0; 8]);
+        output.extend((8..HANDSHAKE_SIZE).map(|i| i as u8));
+
+        Self {
+            connected: false,
+            input: Vec::new(),
+            output,
+            in_chunk_size: DEFAULT_CHUNK_SIZE,
+            out_chunk_size: DEFAULT_CHUNK_SIZE,
+            chunk_streams: FnvHashMap::default(),
+            window_ack_size: DEFAULT_WINDOW_ACK_SIZE,
+            bytes_received: 0,
+            bytes_acknowledged: 0,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    pub fn has_output(&self) -> bool {
+        !self.output.is_empty()
+    }
+
+    /// Take all of the data that should be sent to the server.
+    pub fn take_output(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.output)
+    }
+
+    /// Process data received from the server, returning everything that
+    /// happened as a result.
+    pub fn receive(&mut self, data: &[u8]) -> Result<Vec<Event>, Error> {
+        let mut events = Vec::new();
+        self.input.extend_from_slice(data);
+        self.bytes_received = self.bytes_received.wrapping_add(data.len() as u32);
+
+        if !self.connected {
+            // S0, S1 and S2 all arrive before the server says anything else.
+            if self.input.len() < 1 + HANDSHAKE_SIZE * 2 {
+                return Ok(events);
+            }
+
+            if self.input[0] != RTMP_VERSION {
+                return Err(Error::UnsupportedVersion(self.input[0]));
+            }
+
+            // C2 is an echo of S1; S2 is an echo of C1, which we don't check.
+            let s1 = &self.input[1..1 + HANDSHAKE_SIZE];
+            self.output.extend_from_slice(s1);
+            self.input.drain(..1 + HANDSHAKE_SIZE * 2);
+            self.connected = true;
+
+            self.send_control(
+                MessageType::SetChunkSize,
+                (OUTGOING_CHUNK_SIZE as u32).to_be_bytes().to_vec(),
+            );
+            self.out_chunk_size = OUTGOING_CHUNK_SIZE;
+            events.push(Event::Connected);
+        }
+
+        let mut input = std::mem::take(&mut self.input);
+        let mut position = 0;
+        let result = loop {
+            match self.read_chunk(&input[position..], &mut events) {
+                Ok(Some(length)) => position += length,
+                Ok(None) => break Ok(()),
+                Err(e) => break Err(e),
+            }
+        };
+        input.drain(..position);
+        self.input = input;
+        result?;
+
+        let unacknowledged = self.bytes_received.wrapping_sub(self.bytes_acknowledged);
+        if unacknowledged >= self.window_ack_size {
+            self.bytes_acknowledged = self.bytes_received;
+            self.send_control(
+                MessageType::Acknowledgement,
+                self.bytes_received.to_be_bytes().to_vec(),
+            );
+        }
+
+        Ok(events)
+    }
+
+    /// Read a single chunk from the start of `data`, returning its length.
+    ///
+    /// If the chunk isn't complete yet, `None` is returned and nothing is
+    /// changed.
+    fn read_chunk(&mut self, data: &[u8], events: &mut Vec<Event>) -> Result<Option<usize>, Error> {
+        let Some(&first) = data.first() else {
+            return Ok(None);
+        };
+        let format = first >> 6;
+        let (chunk_stream_id, mut position) = match first & 0x3F {
+            0 => match data.get(1) {
+                Some(&id) => (64 + u32::from(id), 2),
+                None => return Ok(None),
+            },
+            1 => match data.get(1..3) {
+                Some(id) => (64 + u32::from(id[0]) + u32::from(id[1]) * 256, 3),
+                None => return Ok(None),
+            },
+            id => (u32::from(id), 1),
+        };
+
+        let header_length = [11, 7, 3, 0][format as usize];
+        let Some(header) = data.get(position..position + header_length) else {
+            return Ok(None);
+        };
+        position += header_length;
+
+        let new_stream = ChunkStream::default();
+        let stream = match (format, self.chunk_streams.get(&chunk_stream_id)) {
+            (_, Some(stream)) => stream,
+            (0, None) => &new_stream,
+            (_, None) => return Err(Error::UnknownChunkStream(chunk_stream_id)),
+        };
+
+        let mut timestamp_field = stream.timestamp_field;
+        let mut message_length = stream.message_length;
+        let mut message_type = stream.message_type;
+        let mut stream_id = stream.stream_id;
+        if format <= 2 {
+            timestamp_field = read_u24(&header[0..3]);
+        }
+        if format <= 1 {
+            message_length = read_u24(&header[3..6]) as usize;
+            message_type = header[6];
+        }
+        if format == 0 {
+            stream_id = u32::from_le_bytes(header[7..11].try_into().expect("four bytes"));
+        }
+
+        let extended_timestamp = if format == 3 {
+            stream.extended_timestamp
+        } else {
+            timestamp_field == EXTENDED_TIMESTAMP
+        };
+        if extended_timestamp {
+            let Some(extended) = data.get(position..position + 4) else {
+                return Ok(None);
+            };
+            timestamp_field = u32::from_be_bytes(extended.try_into().expect("four bytes"));
+            position += 4;
+        }
+
+        // Anything but a type 3 chunk starts a new message, even if the last
+        // one wasn't finished.
+        let is_new_message = format != 3 || stream.payload.is_empty();
+        let received = if is_new_message {
+            0
+        } else {
+            stream.payload.len()
+        };
+        let chunk_length = message_length
+            .saturating_sub(received)
+            .min(self.in_chunk_size);
+        let Some(chunk) = data.get(position..position + chunk_length) else {
+            return Ok(None);
+        };
+        position += chunk_length;
+
+        let timestamp = match (is_new_message, format) {
+            (true, 0) => timestamp_field,
+            (true, _) => stream.timestamp.wrapping_add(timestamp_field),
+            (false, _) => stream.timestamp,
+        };
+
+        let stream = self.chunk_streams.entry(chunk_stream_id).or_default();
+        if is_new_message {
+            stream.payload.clear();
+        }
+        stream.timestamp = timestamp;
+        stream.timestamp_field = timestamp_field;
+        stream.extended_timestamp = extended_timestamp;
+        stream.message_length = message_length;
+        stream.message_type = message_type;
+        stream.stream_id = stream_id;
+        stream.payload.extend_from_slice(chunk);
+
+        if stream.payload.len() >= message_length {
+            let payload = std::mem::take(&mut stream.payload);
+            match MessageType::from_u8(message_type) {
+                Some(message_type) => self.handle_message(
+                    Message {
+                        message_type,
+                        timestamp,
+                        stream_id,
+                        payload,
+                    },
+                    events,
+                )?,
+                None => tracing::warn!("Ignoring unknown RTMP message type {message_type}"),
+            }
+        }
+
+        Ok(Some(position))
+    }
+
+    /// Handle the protocol control messages, and pass on everything else.
+    fn handle_message(&mut self, message: Message, events: &mut Vec<Event>) -> Result<(), Error> {
+        let value = message
+            .payload
+            .get(0..4)
+            .map(|v| u32::from_be_bytes(v.try_into().expect("four bytes")));
+
+        match (message.message_type, value) {
+            (MessageType::SetChunkSize, Some(size)) => {
+                let size = size & 0x7FFFFFFF;
+                if size == 0 {
+                    return Err(Error::InvalidChunkSize(size));
+                }
+                self.in_chunk_size = size as usize;
+            }
+            (MessageType::Abort, Some(chunk_stream_id)) => {
+                if let Some(stream) = self.chunk_streams.get_mut(&chunk_stream_id) {
+                    stream.payload.clear();
+                }
+            }
+            (MessageType::WindowAckSize, Some(size)) => {
+                self.window_ack_size = size;
+            }
+            (MessageType::SetPeerBandwidth, Some(size)) => {
+                // We don't limit our output, but the server expects us to
+                // confirm the new window.
+                self.send_control(MessageType::WindowAckSize, size.to_be_bytes().to_vec());
+            }
+            (MessageType::UserControl, _) => {
+                let Some(event) = message.payload.get(0..2) else {
+                    return Ok(());
+                };
+                let event = u16::from_be_bytes([event[0], event[1]]);
+                let data = message.payload.get(2..6).unwrap_or_default();
+                match UserControlEvent::from_u16(event) {
+                    Some(UserControlEvent::PingRequest) => {
+                        self.send_user_control(UserControlEvent::PingResponse, data);
+                    }
+                    Some(event) if data.len() == 4 => events.push(Event::UserControl {
+                        event,
+                        stream_id: u32::from_be_bytes(data.try_into().expect("four bytes")),
+                    }),
+                    _ => {}
+                }
+            }
+            (
+                MessageType::SetChunkSize
+                | MessageType::Abort
+                | MessageType::Acknowledgement
+                | MessageType::WindowAckSize
+                | MessageType::SetPeerBandwidth,
+                _,
+            ) => {}
+            _ => events.push(Event::Message(message)),
+        }
+
+        Ok(())
+    }
+
+    /// Queue up a message to be sent on the given chunk stream.
+    ///
+    /// This must not be called before the connection is connected.
+    pub fn send(&mut self, chunk_stream_id: u32, message: &Message) {
+        let extended_timestamp = message.timestamp >= EXTENDED_TIMESTAMP;
+        let mut chunks = message.payload.chunks(self.out_chunk_size);
+
+        write_basic_header(&mut self.output, 0, chunk_stream_id);
+        write_u24(&mut self.output, message.timestamp.min(EXTENDED_TIMESTAMP));
+        write_u24(&mut self.output, message.payload.len() as u32);
+        self.output.push(message.message_type as u8);
+        self.output
+            .extend_from_slice(&message.stream_id.to_le_bytes());
+        if extended_timestamp {
+            self.output
+                .extend_from_slice(&message.timestamp.to_be_bytes());
+        }
+        self.output
+            .extend_from_slice(chunks.next().unwrap_or_default());
+
+        for chunk in chunks {
+            write_basic_header(&mut self.output, 3, chunk_stream_id);
+            if extended_timestamp {
+                self.output
+                    .extend_from_slice(&message.timestamp.to_be_bytes());
+            }
+            self.output.extend_from_slice(chunk);
+        }
+    }
+
+    fn send_control(&mut self, message_type: MessageType, payload: Vec<u8>) {
+        self.send(
+            CONTROL_CHUNK_STREAM,
+            &Message::new(message_type, 0, payload),
+        );
+    }
+
+    fn send_user_control(&mut self, event: UserControlEvent, data: &[u8]) {
+        let mut payload = (event as u16).to_be_bytes().to_vec();
+        payload.extend_from_slice(data);
+        self.send_control(MessageType::UserControl, payload);
+    }
+
+    /// Tell the server how many milliseconds of a stream we want buffered.
+    pub fn set_buffer_length(&mut self, stream_id: u32, buffer_length: u32) {
+        let mut data = stream_id.to_be_bytes().to_vec();
+        data.extend_from_slice(&buffer_length.to_be_bytes());
+        self.send_user_control(UserControlEvent::SetBufferLength, &data);
+    }
+}
+
+impl Default for RtmpConnection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn read_u24(data: &[u8]) -> u32 {
+    u32::from_be_bytes([0, data[0], data[1], data[2]])
+}
+
+fn write_u24(output: &mut Vec<u8>, value: u32) {
+    output.extend_from_slice(&value.to_be_bytes()[1..]);
+}
+
+fn write_basic_header(output: &mut Vec<u8>, format: u8, chunk_stream_id: u32) {
+    match chunk_stream_id {
+        0..=63 => output.push(format << 6 | chunk_stream_id as u8),
+        64..=319 => output.extend_from_slice(&[format << 6, (chunk_stream_id - 64) as u8]),
+        _ => {
+            let id = (chunk_stream_id - 64) as u16;
+            output.push(format << 6 | 1);
+            output.extend_from_slice(&id.to_le_bytes());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run a connection through the handshake, discarding its output.
+    fn connected() -> RtmpConnection {
+        let mut connection = RtmpConnection::new();
+        let mut handshake = vec![RTMP_VERSION];
+        handshake.extend(std::iter::repeat_n(0xAB, HANDSHAKE_SIZE * 2));
+        assert_eq!(
+            connection.receive(&handshake).unwrap(),
+            vec![Event::Connected]
+        );
+        connection.take_output();
+        connection
+    }
+
+    #[test]
+    fn handshake() {
+        let mut connection = RtmpConnection::new();
+        let c0c1 = connection.take_output();
+        assert_eq!(c0c1.len(), 1 + HANDSHAKE_SIZE);
+        assert_eq!(c0c1[0], RTMP_VERSION);
+
+        let mut s0s1s2 = vec![RTMP_VERSION];
+        let s1: Vec<u8> = (0..HANDSHAKE_SIZE).map(|i| (i * 7) as u8).collect();
+        s0s1s2.extend_from_slice(&s1);
+        s0s1s2.extend_from_slice(&c0c1[1..]);
+
+        // Nothing happens until S2 has been received completely.
+        assert_eq!(connection.receive(&s0s1s2[..100]).unwrap(), vec![]);
+        assert!(!connection.is_connected());
+        assert_eq!(
+            connection.receive(&s0s1s2[100..]).unwrap(),
+            vec![Event::Connected]
+        );
+        assert!(connection.is_connected());
+
+        // C2 echoes S1, followed by our chunk size.
+        let output = connection.take_output();
+        assert_eq!(&output[..HANDSHAKE_SIZE], &s1[..]);
+        assert_eq!(
+            &output[HANDSHAKE_SIZE..],
+            &[0x02, 0, 0, 0, 0, 0, 4, 1, 0, 0, 0, 0, 0, 0, 0x10, 0]
+        );
+    }
+
+    #[test]
+    fn bad_version() {
+        let mut connection = RtmpConnection::new();
+        let mut handshake = vec![6];
+        handshake.extend(std::iter::repeat_n(0, HANDSHAKE_SIZE * 2));
+        assert_eq!(
+            connection.receive(&handshake),
+            Err(Error::UnsupportedVersion(6))
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut sender = connected();
+        let mut receiver = connected();
+        receiver.in_chunk_size = OUTGOING_CHUNK_SIZE;
+
+        let messages = [
+            Message {
+                message_type: MessageType::CommandAmf0,
+                timestamp: 0,
+                stream_id: 0,
+                payload: (0..10000).map(|i| i as u8).collect(),
+            },
+            Message {
+                message_type: MessageType::Video,
+                timestamp: 0x1234567,
+                stream_id: 1,
+                payload: vec![1; 5000],
+            },
+            Message {
+                message_type: MessageType::Audio,
+                timestamp: 40,
+                stream_id: 1,
+                payload: vec![],
+            },
+        ];
+        for message in &messages {
+            sender.send(STREAM_CHUNK_STREAM, message);
+        }
+
+        // Feed the data through in awkward pieces.
+        let data = sender.take_output();
+        let mut events = vec![];
+        for piece in data.chunks(1000) {
+            events.extend(receiver.receive(piece).unwrap());
+        }
+
+        let expected: Vec<_> = messages.into_iter().map(Event::Message).collect();
+        assert_eq!(events, expected);
+    }
+
+    #[test]
+    fn compressed_headers() {
+        let mut connection = connected();
+        #[rustfmt::skip]
+        let data = [
+            // Type 0: timestamp 1000, length 2, audio, stream 1.
+            0x04, 0x00, 0x03, 0xE8, 0x00, 0x00, 0x02, 0x08, 0x01, 0x00, 0x00, 0x00, 0xAA, 0xBB,
+            // Type 1: delta 20, length 1, video.
+            0x44, 0x00, 0x00, 0x14, 0x00, 0x00, 0x01, 0x09, 0xCC,
+            // Type 2: delta 30.
+            0x84, 0x00, 0x00, 0x1E, 0xDD,
+            // Type 3: same delta again.
+            0xC4, 0xEE,
+        ];
+
+        let events = connection.receive(&data).unwrap();
+        let summary: Vec<_> = events
+            .into_iter()
+            .map(|event| match event {
+                Event::Message(m) => (m.message_type, m.timestamp, m.stream_id, m.payload),
+                _ => panic!("unexpected event {event:?}"),
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (MessageType::Audio, 1000, 1, vec![0xAA, 0xBB]),
+                (MessageType::Video, 1020, 1, vec![0xCC]),
+                (MessageType::Video, 1050, 1, vec![0xDD]),
+                (MessageType::Video, 1080, 1, vec![0xEE]),
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_chunk_stream() {
+        let mut connection = connected();
+        assert_eq!(
+            connection.receive(&[0xC5, 0x00]),
+            Err(Error::UnknownChunkStream(5))
+        );
+    }
+
+    #[test]
+    fn control_messages() {
+        let mut connection = connected();
+        #[rustfmt::skip]
+        let data = [
+            // Ping Request.
+            0x02, 0, 0, 0, 0, 0, 6, 0x04, 0, 0, 0, 0, 0, 6, 0, 0, 0x12, 0x34,
+            // Stream Begin 1.
+            0x02, 0, 0, 0, 0, 0, 6, 0x04, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+            // Set Chunk Size 4.
+            0x02, 0, 0, 0, 0, 0, 4, 0x01, 0, 0, 0, 0, 0, 0, 0, 4,
+            // A six byte message, now split into two chunks.
+            0x03, 0, 0, 0, 0, 0, 6, 0x14, 0, 0, 0, 0, 1, 2, 3, 4, 0xC3, 5, 6,
+        ];
+
+        let events = connection.receive(&data).unwrap();
+        assert_eq!(
+            events,
+            vec![
+                Event::UserControl {
+                    event: UserControlEvent::StreamBegin,
+                    stream_id: 1
+                },
+                Event::Message(Message::new(
+                    MessageType::CommandAmf0,
+                    0,
+                    vec![1, 2, 3, 4, 5, 6]
+                )),
+            ]
+        );
+
+        // The ping was answered.
+        assert_eq!(
+            connection.take_output(),
+            vec![0x02, 0, 0, 0, 0, 0, 6, 0x04, 0, 0, 0, 0, 0, 7, 0, 0, 0x12, 0x34]
+        );
+    }
+
+    #[test]
+    fn acknowledgements() {
+        let mut connection = connected();
+        #[rustfmt::skip]
+        connection.receive(&[
+            // Window Acknowledgement Size 3200.
+            0x02, 0, 0, 0, 0, 0, 4, 0x05, 0, 0, 0, 0, 0, 0, 0x0C, 0x80,
+        ]).unwrap();
+        assert!(!connection.has_output());
+
+        connection.receive(&[0; 200]).unwrap();
+        let output = connection.take_output();
+        let received = (1 + HANDSHAKE_SIZE * 2 + 16 + 200) as u32;
+        let mut expected = vec![0x02, 0, 0, 0, 0, 0, 4, 0x03, 0, 0, 0, 0];
+        expected.extend_from_slice(&received.to_be_bytes());
+        assert_eq!(output, expected);
+    }
+}
@@ -11,8 +11,11 @@ use crate::avm2::{
 use crate::backend::ui::FontDefinition;
 use crate::backend::{
     audio::{AudioBackend, AudioManager},
+    debugger::DebuggerBackend,
+    filesystem::FilesystemBackend,
     log::LogBackend,
     navigator::{NavigatorBackend, Request},
+    print::PrintBackend,
     storage::StorageBackend,
     ui::{MouseCursor, UiBackend},
 };
@@ -22,18 +25,23 @@ use crate::context::{ActionQueue, ActionType, RenderContext, UpdateContext};
 use crate::context_menu::{
     BuiltInItemFlags, ContextMenuCallback, ContextMenuItem, ContextMenuState,
 };
+use crate::debugger::Debugger;
 use crate::display_object::Avm2MousePick;
 use crate::display_object::{
     EditText, InteractiveObject, Stage, StageAlign, StageDisplayState, StageScaleMode,
     TInteractiveObject, WindowMode,
 };
 use crate::events::GamepadButton;
-use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, MouseButton, PlayerEvent};
+use crate::events::{
+    ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, MouseButton, MultitouchInputMode,
+    PlayerEvent, TextControlCode,
+};
 use crate::external::{ExternalInterface, ExternalInterfaceProvider, NullFsCommandProvider};
 use crate::external::{FsCommandProvider, Value as ExternalValue};
 use crate::focus_tracker::NavigationDirection;
 use crate::frame_lifecycle::{run_all_phases_avm2, FramePhase};
 use crate::input::InputManager;
+use crate::input_recording::{self, AutomatedEvent, InputRecording, InputSession};
 use crate::library::Library;
 use crate::limits::ExecutionLimit;
 use crate::loader::{LoadBehavior, LoadManager};
@@ -41,7 +49,9 @@ use crate::local_connection::LocalConnections;
 use crate::locale::get_current_date_time;
 use crate::net_connection::NetConnections;
 use crate::prelude::*;
+use crate::print_job::PrintJob;
 use crate::socket::Sockets;
+use crate::sql::SqlConnections;
 use crate::streams::StreamManager;
 use crate::string::StringContext;
 use crate::string::{AvmString, AvmStringInterner};
@@ -49,6 +59,7 @@ use crate::stub::StubCollection;
 use crate::tag_utils::SwfMovie;
 use crate::timer::Timers;
 use crate::vminterface::Instantiator;
+use crate::worker::{WorkerId, WorkerLogBackend, WorkerRegistry, WorkerRegistryOwner, Workers};
 use crate::DefaultFont;
 use gc_arena::lock::GcRefLock;
 use gc_arena::{Collect, DynamicRootSet, Mutation, Rootable};
@@ -112,6 +123,24 @@ pub struct MouseData<'gc> {
     pub pressed: Option<InteractiveObject<'gc>>,
     pub right_pressed: Option<InteractiveObject<'gc>>,
     pub middle_pressed: Option<InteractiveObject<'gc>>,
+
+    /// The touch points that are currently touching the screen, by ID.
+    pub touch_points: HashMap<i32, TouchPoint<'gc>>,
+
+    /// How touch input is interpreted, as set by `Multitouch.inputMode`.
+    #[collect(require_static)]
+    pub multitouch_input_mode: MultitouchInputMode,
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct TouchPoint<'gc> {
+    /// The object that the touch point started touching.
+    pub began_on: Option<InteractiveObject<'gc>>,
+
+    /// Whether this touch point is also handled as mouse input.
+    #[collect(require_static)]
+    pub primary: bool,
 }
 
 impl<'gc> MouseData<'gc> {
@@ -191,6 +220,12 @@ struct GcRootData<'gc> {
 
     local_connections: LocalConnections<'gc>,
 
+    /// The SQL databases that are currently open.
+    sql_connections: SqlConnections<'gc>,
+
+    /// The worker that this player runs, and the objects it shares with other workers.
+    workers: Workers<'gc>,
+
     /// Dynamic root for allowing handles to GC objects to exist outside of the GC.
     dynamic_root: DynamicRootSet<'gc>,
 
@@ -232,6 +267,8 @@ impl<'gc> GcRootData<'gc> {
         &mut Sockets<'gc>,
         &mut NetConnections<'gc>,
         &mut LocalConnections<'gc>,
+        &mut SqlConnections<'gc>,
+        &mut Workers<'gc>,
         &mut Vec<PostFrameCallback<'gc>>,
         &mut MouseData<'gc>,
         DynamicRootSet<'gc>,
@@ -256,6 +293,8 @@ impl<'gc> GcRootData<'gc> {
             &mut self.sockets,
             &mut self.net_connections,
             &mut self.local_connections,
+            &mut self.sql_connections,
+            &mut self.workers,
             &mut self.post_frame_callbacks,
             &mut self.mouse_data,
             self.dynamic_root,
@@ -269,6 +308,8 @@ type Audio = Box<dyn AudioBackend>;
 type Navigator = Box<dyn NavigatorBackend>;
 type Renderer = Box<dyn RenderBackend>;
 type Storage = Box<dyn StorageBackend>;
+type Print = Box<dyn PrintBackend>;
+type Filesystem = Box<dyn FilesystemBackend>;
 type Log = Box<dyn LogBackend>;
 type Ui = Box<dyn UiBackend>;
 type Video = Box<dyn VideoBackend>;
@@ -300,16 +341,35 @@ pub struct Player {
     audio: Audio,
     navigator: Navigator,
     storage: Storage,
+    print: Print,
+    filesystem: Filesystem,
     log: Log,
     ui: Ui,
     video: Video,
 
+    /// The print job that is currently running, if any.
+    print_job: Option<PrintJob>,
+
+    /// The ActionScript debugger, which may be attached.
+    debugger: Debugger,
+
     transform_stack: TransformStack,
 
     rng: SmallRng,
 
     gc_arena: Rc<RefCell<GcArena>>,
 
+    /// The worker that this player runs.
+    worker_id: WorkerId,
+
+    /// The state shared by all workers. The primordial worker owns the
+    /// players of every background worker through this.
+    worker_registry: Rc<RefCell<WorkerRegistry>>,
+
+    /// Set on the primordial worker, which terminates the background workers
+    /// when it's dropped.
+    _worker_registry_owner: Option<WorkerRegistryOwner>,
+
     frame_rate: f64,
     forced_frame_rate: bool,
     actions_since_timeout_check: u16,
@@ -330,6 +390,9 @@ pub struct Player {
 
     input: InputManager,
 
+    /// The input that's being recorded or replayed, if any.
+    input_session: Option<InputSession>,
+
     mouse_in_stage: bool,
     mouse_position: Point<Twips>,
 
@@ -490,7 +553,14 @@ impl Player {
     }
 
     pub fn tick(&mut self, dt: f64) {
-        if self.is_playing() {
+        if let Some(session) = &mut self.input_session {
+            session.record_tick();
+        }
+
+        if self.is_playing() && self.worker_registry.borrow().is_parked(self.worker_id) {
+            // A parked worker doesn't run any scripts until it's resumed.
+            self.update_workers(dt);
+        } else if self.is_playing() {
             self.frame_accumulator += dt;
             let frame_rate = self.frame_rate;
             let frame_time = 1000.0 / frame_rate;
@@ -546,9 +616,67 @@ impl Player {
             self.update(|context| {
                 StreamManager::tick(context, dt);
             });
+            self.update_workers(dt);
             self.audio.tick();
         }
+
+        self.replay_input_frame();
+    }
+
+    /// Runs background workers, and dispatches events for what the other
+    /// workers did.
+    ///
+    /// Only the primordial worker ticks background workers, which run after
+    /// it in the order they were created. Parked workers are resumed here
+    /// once their wait is over, and don't run until then.
+    fn update_workers(&mut self, dt: f64) {
+        if self.worker_id.is_primordial() {
+            let pending = self.worker_registry.borrow_mut().take_pending_movies();
+            for (id, movie) in pending {
+                let player = PlayerBuilder::new()
+                    .with_movie(movie)
+                    .with_autoplay(true)
+                    .with_log(WorkerLogBackend::new(self.worker_registry.clone()))
+                    .with_player_version(Some(self.player_version))
+                    .with_player_runtime(self.player_runtime)
+                    .with_max_execution_duration(self.max_execution_duration)
+                    .with_worker(self.worker_registry.clone(), id)
+                    .build();
+                self.worker_registry.borrow_mut().set_player(id, player);
+            }
+
+            // Shared objects can only be collected once every worker has
+            // reported which ones it can still reach.
+            let mut reachable =
+                self.mutate_with_update_context(|context| context.workers.handles(context.gc()));
+
+            let players = self.worker_registry.borrow().players();
+            for (id, player) in players {
+                let mut player = player.lock().expect("Player lock must be available");
+                if self.worker_registry.borrow_mut().resume(id, dt) {
+                    player.tick(dt);
+                }
+                player.mutate_with_update_context(|context| {
+                    reachable.extend(context.workers.handles(context.gc()));
+                });
+            }
+
+            self.worker_registry.borrow_mut().resume(self.worker_id, dt);
+            self.worker_registry.borrow_mut().collect(reachable);
+
+            let traces = self.worker_registry.borrow_mut().take_traces();
+            for trace in traces {
+                self.log.avm_trace(&trace);
+            }
+        }
+
+        if !self.worker_registry.borrow().is_parked(self.worker_id) {
+            self.update(|context| {
+                Workers::dispatch_events(context);
+            });
+        }
     }
+
     pub fn time_til_next_timer(&self) -> Option<f64> {
         self.time_til_next_timer
     }
@@ -586,6 +714,108 @@ impl Player {
         self.mouse_in_stage = is_in;
     }
 
+    /// Starts recording the input sent to this player through `handle_event`.
+    ///
+    /// See the `input_recording` module for how input is recorded.
+    pub fn start_recording_input(&mut self) {
+        self.input_session = Some(InputSession::record());
+    }
+
+    /// Stops recording input, returning what was recorded.
+    pub fn stop_recording_input(&mut self) -> Option<InputRecording> {
+        match self.input_session.take() {
+            Some(InputSession::Record { recording, .. }) => Some(recording),
+            session => {
+                self.input_session = session;
+                None
+            }
+        }
+    }
+
+    /// Replays recorded input, one frame of it after every tick.
+    pub fn replay_input(&mut self, recording: InputRecording) {
+        self.input_session = Some(InputSession::replay(recording));
+    }
+
+    pub fn is_replaying_input(&self) -> bool {
+        self.input_session
+            .as_ref()
+            .is_some_and(InputSession::is_replaying)
+    }
+
+    fn record_event(&mut self, event: PlayerEvent) {
+        // Pasting reads the system clipboard, which won't be the same when replaying.
+        let clipboard = matches!(
+            event,
+            PlayerEvent::TextControl {
+                code: TextControlCode::Paste
+            }
+        )
+        .then(|| self.ui.clipboard_content());
+        let inverse_view_matrix =
+            self.mutate_with_update_context(|context| context.stage.inverse_view_matrix());
+
+        let Some(session) = &mut self.input_session else {
+            return;
+        };
+        if let Some(text) = clipboard {
+            session.record_event(AutomatedEvent::SetClipboardText { text });
+        }
+        match input_recording::from_player_event(event, inverse_view_matrix) {
+            Some(event) => session.record_event(event),
+            None => tracing::debug!("Event can't be recorded: {event:?}"),
+        }
+    }
+
+    fn replay_input_frame(&mut self) {
+        let Some(session) = &mut self.input_session else {
+            return;
+        };
+        if !session.is_replaying() {
+            return;
+        }
+        let events = session.next_replayed_frame();
+        let finished = !session.is_replaying();
+
+        let view_matrix = self.mutate_with_update_context(|context| context.stage.view_matrix());
+        for event in events {
+            match &event {
+                AutomatedEvent::SetClipboardText { text } => {
+                    self.ui.set_clipboard_content(text.to_owned());
+                }
+                // The frontend tells us where the mouse is separately from its events.
+                AutomatedEvent::MouseLeave => self.set_mouse_in_stage(false),
+                AutomatedEvent::MouseMove { .. }
+                | AutomatedEvent::MouseDown { .. }
+                | AutomatedEvent::MouseUp { .. } => self.set_mouse_in_stage(true),
+                _ => {}
+            }
+            if let Some(event) = input_recording::to_player_event(&event, view_matrix) {
+                self.dispatch_event(event);
+            }
+        }
+
+        if finished {
+            tracing::info!("Finished replaying input");
+            self.input_session = None;
+        }
+    }
+
+    /// Converts a position in the viewport to a position on the stage, both in pixels.
+    pub fn viewport_to_stage_position(&mut self, x: f64, y: f64) -> (f64, f64) {
+        let inverse_view_matrix =
+            self.mutate_with_update_context(|context| context.stage.inverse_view_matrix());
+        let position = inverse_view_matrix * Point::from_pixels(x, y);
+        (position.x.to_pixels(), position.y.to_pixels())
+    }
+
+    /// Converts a position on the stage to a position in the viewport, both in pixels.
+    pub fn stage_to_viewport_position(&mut self, x: f64, y: f64) -> (f64, f64) {
+        let view_matrix = self.mutate_with_update_context(|context| context.stage.view_matrix());
+        let position = view_matrix * Point::from_pixels(x, y);
+        (position.x.to_pixels(), position.y.to_pixels())
+    }
+
     /// Returns the master volume of the player. 1.0 is 100% volume.
     ///
     /// The volume is linear and not adapted for logarithmic hearing.
@@ -953,7 +1183,20 @@ impl Player {
 
     /// Handle an event sent into the player from the external windowing system
     /// or an HTML element.
+    ///
+    /// While input is being replayed, events are ignored so that they can't
+    /// interfere with the replayed input.
     pub fn handle_event(&mut self, event: PlayerEvent) -> bool {
+        if self.is_replaying_input() {
+            return false;
+        }
+        if self.input_session.is_some() {
+            self.record_event(event);
+        }
+        self.dispatch_event(event)
+    }
+
+    fn dispatch_event(&mut self, event: PlayerEvent) -> bool {
         match event {
             PlayerEvent::FocusGained | PlayerEvent::FocusLost => self.handle_focus_event(event),
             PlayerEvent::KeyDown { .. }
@@ -963,13 +1206,188 @@ impl Player {
             | PlayerEvent::MouseDown { .. }
             | PlayerEvent::MouseLeave
             | PlayerEvent::MouseWheel { .. }
-            | PlayerEvent::GamepadButtonDown { .. }
-            | PlayerEvent::GamepadButtonUp { .. }
             | PlayerEvent::TextInput { .. }
             | PlayerEvent::TextControl { .. } => self.handle_input_event(event),
+            PlayerEvent::GamepadButtonDown { .. } | PlayerEvent::GamepadButtonUp { .. } => {
+                self.handle_game_input_event(event);
+                self.handle_input_event(event)
+            }
+            PlayerEvent::GamepadConnected { .. }
+            | PlayerEvent::GamepadDisconnected { .. }
+            | PlayerEvent::GamepadAxisChanged { .. } => {
+                self.handle_game_input_event(event);
+                false
+            }
+            PlayerEvent::TouchBegin { .. }
+            | PlayerEvent::TouchMove { .. }
+            | PlayerEvent::TouchEnd { .. }
+            | PlayerEvent::Gesture { .. }
+            | PlayerEvent::TwoFingerTap { .. }
+            | PlayerEvent::PressAndTap { .. } => self.handle_touch_event(event),
         }
     }
 
+    /// Passes gamepad input on to `flash.ui.GameInput`.
+    ///
+    /// Gamepad buttons are also mapped to keys by `handle_input_event`.
+    fn handle_game_input_event(&mut self, event: PlayerEvent) {
+        if let PlayerEvent::GamepadConnected { gamepad } = event {
+            if self.input.connected_gamepads().contains(&gamepad) {
+                return;
+            }
+        }
+
+        self.mutate_with_update_context(|context| {
+            if context.swf.is_action_script_3() {
+                crate::avm2::globals::flash::ui::game_input::handle_gamepad_event(context, event);
+            }
+        });
+
+        // Gamepads connected before `GameInput` is first used are picked up
+        // from here.
+        self.input.handle_event(&event);
+    }
+
+    /// Handles touch points and gestures.
+    ///
+    /// The primary touch point is always handled as left mouse button input
+    /// too. Then, depending on `Multitouch.inputMode`, touch points are
+    /// dispatched as touch events, or gestures as gesture events.
+    fn handle_touch_event(&mut self, event: PlayerEvent) -> bool {
+        if let PlayerEvent::Gesture { x, y, .. }
+        | PlayerEvent::TwoFingerTap { x, y }
+        | PlayerEvent::PressAndTap { x, y, .. } = event
+        {
+            return self.mutate_with_update_context(|context| {
+                if context.mouse_data.multitouch_input_mode != MultitouchInputMode::Gesture {
+                    return false;
+                }
+
+                let view_matrix = context.stage.inverse_view_matrix();
+                let position = view_matrix * Point::from_pixels(x, y);
+                let target =
+                    run_pick(context, position, true).unwrap_or_else(|| context.stage.into());
+                let event = match event {
+                    PlayerEvent::Gesture { gesture, phase, .. } => ClipEvent::Gesture {
+                        gesture,
+                        phase,
+                        position,
+                    },
+                    PlayerEvent::PressAndTap { tap_x, tap_y, .. } => ClipEvent::PressAndTap {
+                        position,
+                        tap_position: view_matrix * Point::from_pixels(tap_x, tap_y),
+                    },
+                    _ => ClipEvent::TwoFingerTap { position },
+                };
+                target.event_dispatch_to_avm2(context, event) == ClipEventResult::Handled
+            });
+        }
+
+        let (PlayerEvent::TouchBegin { id, x, y }
+        | PlayerEvent::TouchMove { id, x, y }
+        | PlayerEvent::TouchEnd { id, x, y }) = event
+        else {
+            return false;
+        };
+
+        let primary = self.mutate_with_update_context(|context| {
+            let touch_points = &mut context.mouse_data.touch_points;
+            if let PlayerEvent::TouchBegin { .. } = event {
+                let primary = touch_points.is_empty();
+                touch_points.insert(
+                    id,
+                    TouchPoint {
+                        began_on: None,
+                        primary,
+                    },
+                );
+                Some(primary)
+            } else {
+                touch_points.get(&id).map(|point| point.primary)
+            }
+        });
+        let Some(primary) = primary else {
+            // This touch point never began.
+            return false;
+        };
+
+        let mut handled = false;
+        if primary {
+            let button = MouseButton::Left;
+            handled = match event {
+                PlayerEvent::TouchBegin { .. } => {
+                    self.handle_input_event(PlayerEvent::MouseMove { x, y });
+                    self.handle_input_event(PlayerEvent::MouseDown {
+                        x,
+                        y,
+                        button,
+                        index: None,
+                    })
+                }
+                PlayerEvent::TouchMove { .. } => {
+                    self.handle_input_event(PlayerEvent::MouseMove { x, y })
+                }
+                _ => self.handle_input_event(PlayerEvent::MouseUp { x, y, button }),
+            };
+        }
+
+        self.mutate_with_update_context(|context| {
+            let position = context.stage.inverse_view_matrix() * Point::from_pixels(x, y);
+            let target = run_pick(context, position, true).unwrap_or_else(|| context.stage.into());
+
+            let began_on = match event {
+                PlayerEvent::TouchBegin { .. } => {
+                    if let Some(point) = context.mouse_data.touch_points.get_mut(&id) {
+                        point.began_on = Some(target);
+                    }
+                    None
+                }
+                PlayerEvent::TouchEnd { .. } => context
+                    .mouse_data
+                    .touch_points
+                    .remove(&id)
+                    .and_then(|point| point.began_on),
+                _ => None,
+            };
+
+            if context.mouse_data.multitouch_input_mode != MultitouchInputMode::TouchPoint {
+                return;
+            }
+
+            let event = match event {
+                PlayerEvent::TouchBegin { .. } => ClipEvent::TouchBegin {
+                    touch_point_id: id,
+                    primary,
+                    position,
+                },
+                PlayerEvent::TouchMove { .. } => ClipEvent::TouchMove {
+                    touch_point_id: id,
+                    primary,
+                    position,
+                },
+                _ => ClipEvent::TouchEnd {
+                    touch_point_id: id,
+                    primary,
+                    position,
+                },
+            };
+            handled |= target.event_dispatch_to_avm2(context, event) == ClipEventResult::Handled;
+
+            // A touch point that ends on the object it began on taps it.
+            if began_on.is_some_and(|began_on| InteractiveObject::ptr_eq(began_on, target)) {
+                let event = ClipEvent::TouchTap {
+                    touch_point_id: id,
+                    primary,
+                    position,
+                };
+                handled |=
+                    target.event_dispatch_to_avm2(context, event) == ClipEventResult::Handled;
+            }
+        });
+
+        handled
+    }
+
     fn handle_focus_event(&mut self, event: PlayerEvent) -> bool {
         if let PlayerEvent::FocusLost = event {
             self.mutate_with_update_context(|context| {
@@ -1893,11 +2311,14 @@ impl Player {
         }
 
         self.update(|context| {
+            context.debugger.poll();
+
             // TODO: Is this order correct?
             run_all_phases_avm2(context);
             Avm1::run_frame(context);
             AudioManager::update_sounds(context);
             LocalConnections::update_connections(context);
+            context.sql_connections.flush(context.filesystem);
 
             // Only run the current list of callbacks - any callbacks added during callback execution
             // will be run at the end of the *next* frame.
@@ -2011,6 +2432,14 @@ impl Player {
         self.renderer
     }
 
+    pub fn print(&self) -> &Print {
+        &self.print
+    }
+
+    pub fn print_mut(&mut self) -> &mut Print {
+        &mut self.print
+    }
+
     pub fn ui(&self) -> &Ui {
         &self.ui
     }
@@ -2142,6 +2571,8 @@ impl Player {
                 sockets,
                 net_connections,
                 local_connections,
+                sql_connections,
+                workers,
                 post_frame_callbacks,
                 mouse_data,
                 dynamic_root,
@@ -2170,6 +2601,11 @@ impl Player {
                 page_url: &mut this.page_url,
                 instance_counter: &mut this.instance_counter,
                 storage: this.storage.deref_mut(),
+                print: this.print.deref_mut(),
+                filesystem: this.filesystem.deref_mut(),
+                print_job: &mut this.print_job,
+                sql_connections,
+                debugger: &mut this.debugger,
                 log: this.log.deref_mut(),
                 video: this.video.deref_mut(),
                 avm1_shared_objects,
@@ -2197,6 +2633,7 @@ impl Player {
                 sockets,
                 net_connections,
                 local_connections,
+                workers,
                 dynamic_root,
                 post_frame_callbacks,
             };
@@ -2262,11 +2699,30 @@ impl Player {
         // Update mouse state (check for new hovered button, etc.)
         self.mutate_with_update_context(|context| {
             Self::update_drag(context);
+            crate::avm2::sampler::update_sampler(context);
         });
         self.update_mouse_state(&HashSet::new(), false, &mut false);
 
         // GC
-        self.gc_arena.borrow_mut().collect_debt();
+        let mut arena = self.gc_arena.borrow_mut();
+        if let Some(marked) = arena.mark_debt() {
+            // Everything that's still reachable has been marked, so this is
+            // the last chance to notice what's about to be collected.
+            let start_time = self.start_time;
+            let filesystem = self.filesystem.deref_mut();
+            marked.finalize(|fc, root| {
+                if root.data.borrow().avm2.sampler.is_started() {
+                    let time = crate::avm2::sampler::micros_since(start_time);
+                    let mut data = root.data.borrow_mut(fc);
+                    data.avm2.sampler.finalize(fc, time);
+                }
+                root.data
+                    .borrow_mut(fc)
+                    .sql_connections
+                    .finalize(fc, filesystem);
+            });
+        }
+        arena.collect_debt();
 
         rval
     }
@@ -2295,6 +2751,9 @@ impl Player {
                     tracing::error!("Error flushing AVM2 shared object `{:?}`: {:?}", so, e);
                 }
             }
+
+            let context = &mut *avm2_activation.context;
+            context.sql_connections.flush(context.filesystem);
         });
     }
 
@@ -2401,6 +2860,9 @@ pub struct PlayerBuilder {
     log: Option<Log>,
     navigator: Option<Navigator>,
     renderer: Option<Renderer>,
+    print: Option<Print>,
+    filesystem: Option<Filesystem>,
+    debugger: Option<Box<dyn DebuggerBackend>>,
     storage: Option<Storage>,
     ui: Option<Ui>,
     video: Option<Video>,
@@ -2432,6 +2894,7 @@ pub struct PlayerBuilder {
     #[cfg(feature = "known_stubs")]
     stub_report_output: Option<std::path::PathBuf>,
     avm2_optimizer_enabled: bool,
+    worker: Option<(Rc<RefCell<WorkerRegistry>>, WorkerId)>,
 }
 
 impl PlayerBuilder {
@@ -2448,6 +2911,9 @@ impl PlayerBuilder {
             log: None,
             navigator: None,
             renderer: None,
+            print: None,
+            filesystem: None,
+            debugger: None,
             storage: None,
             ui: None,
             video: None,
@@ -2483,6 +2949,7 @@ impl PlayerBuilder {
             #[cfg(feature = "known_stubs")]
             stub_report_output: None,
             avm2_optimizer_enabled: true,
+            worker: None,
         }
     }
 
@@ -2535,6 +3002,27 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets the print backend of the player.
+    #[inline]
+    pub fn with_print(mut self, print: impl 'static + PrintBackend) -> Self {
+        self.print = Some(Box::new(print));
+        self
+    }
+
+    /// Sets the filesystem backend of the player, used by AIR movies.
+    #[inline]
+    pub fn with_filesystem(mut self, filesystem: impl 'static + FilesystemBackend) -> Self {
+        self.filesystem = Some(Box::new(filesystem));
+        self
+    }
+
+    /// Attaches an ActionScript debugger to the player.
+    #[inline]
+    pub fn with_debugger(mut self, debugger: impl 'static + DebuggerBackend) -> Self {
+        self.debugger = Some(Box::new(debugger));
+        self
+    }
+
     /// Sets the storage backend of the player.
     #[inline]
     pub fn with_storage(mut self, storage: Box<dyn StorageBackend>) -> Self {
@@ -2691,6 +3179,13 @@ impl PlayerBuilder {
         self
     }
 
+    /// Makes the player run a background worker, instead of the primordial worker.
+    fn with_worker(mut self, registry: Rc<RefCell<WorkerRegistry>>, id: WorkerId) -> Self {
+        self.worker = Some((registry, id));
+        self
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn create_gc_root<'gc>(
         gc_context: &'gc Mutation<'gc>,
         player_version: u8,
@@ -2699,6 +3194,7 @@ impl PlayerBuilder {
         fake_movie: Arc<SwfMovie>,
         external_interface_provider: Option<Box<dyn ExternalInterfaceProvider>>,
         fs_command_provider: Box<dyn FsCommandProvider>,
+        workers: Workers<'gc>,
     ) -> GcRoot<'gc> {
         let mut interner = AvmStringInterner::new(gc_context);
         let (avm1, avm2) = {
@@ -2731,6 +3227,8 @@ impl PlayerBuilder {
                 pressed: None,
                 right_pressed: None,
                 middle_pressed: None,
+                touch_points: HashMap::new(),
+                multitouch_input_mode: MultitouchInputMode::default(),
             },
             avm1_shared_objects: HashMap::new(),
             avm2_shared_objects: HashMap::new(),
@@ -2741,6 +3239,8 @@ impl PlayerBuilder {
             sockets: Sockets::empty(),
             net_connections: NetConnections::default(),
             local_connections: LocalConnections::empty(),
+            sql_connections: SqlConnections::new(),
+            workers,
             dynamic_root: DynamicRootSet::new(gc_context),
             post_frame_callbacks: Vec::new(),
         };
@@ -2771,6 +3271,12 @@ impl PlayerBuilder {
                 scale_factor: self.viewport_scale_factor,
             }))
         });
+        let print = self
+            .print
+            .unwrap_or_else(|| Box::new(print::NullPrintBackend::new()));
+        let filesystem = self
+            .filesystem
+            .unwrap_or_else(|| Box::new(filesystem::MemoryFilesystemBackend::new()));
         let storage = self
             .storage
             .unwrap_or_else(|| Box::new(storage::MemoryStorageBackend::new()));
@@ -2782,6 +3288,9 @@ impl PlayerBuilder {
             .unwrap_or_else(|| Box::new(null::NullVideoBackend::new()));
 
         let player_version = self.player_version.unwrap_or(NEWEST_PLAYER_VERSION);
+        let (worker_registry, worker_id) = self
+            .worker
+            .unwrap_or_else(|| (Default::default(), WorkerId::PRIMORDIAL));
 
         // Instantiate the player.
         let fake_movie = Arc::new(SwfMovie::empty(player_version));
@@ -2794,9 +3303,13 @@ impl PlayerBuilder {
                 log,
                 navigator,
                 renderer,
+                print,
+                filesystem,
                 storage,
                 ui,
                 video,
+                print_job: None,
+                debugger: Debugger::new(self.debugger),
 
                 // SWF info
                 swf: fake_movie.clone(),
@@ -2816,6 +3329,7 @@ impl PlayerBuilder {
 
                 // Input
                 input: InputManager::new(self.gamepad_button_mapping),
+                input_session: None,
                 mouse_in_stage: true,
                 mouse_position: Point::ZERO,
                 mouse_cursor: MouseCursor::Arrow,
@@ -2849,8 +3363,14 @@ impl PlayerBuilder {
                         fake_movie.clone(),
                         self.external_interface_provider,
                         self.fs_command_provider,
+                        Workers::new(worker_id, worker_registry.clone()),
                     )
                 }))),
+                worker_id,
+                _worker_registry_owner: worker_id
+                    .is_primordial()
+                    .then(|| WorkerRegistryOwner::new(worker_registry.clone())),
+                worker_registry,
             })
         });
 
@@ -2953,13 +3473,22 @@ pub struct DragObject<'gc> {
 fn run_mouse_pick<'gc>(
     context: &mut UpdateContext<'gc>,
     require_button_mode: bool,
+) -> Option<InteractiveObject<'gc>> {
+    run_pick(context, *context.mouse_position, require_button_mode)
+}
+
+/// Determines the interactive object at a point on the stage.
+fn run_pick<'gc>(
+    context: &mut UpdateContext<'gc>,
+    point: Point<Twips>,
+    require_button_mode: bool,
 ) -> Option<InteractiveObject<'gc>> {
     context.stage.iter_render_list().rev().find_map(|level| {
         level.as_interactive().and_then(|l| {
             if l.as_displayobject().movie().is_action_script_3() {
                 let mut res = None;
                 if let Avm2MousePick::Hit(target) =
-                    l.mouse_pick_avm2(context, *context.mouse_position, require_button_mode)
+                    l.mouse_pick_avm2(context, point, require_button_mode)
                 {
                     // Flash Player appears to never target events at the root object
                     if !target.as_displayobject().is_root() {
@@ -2969,7 +3498,7 @@ fn run_mouse_pick<'gc>(
 
                 res
             } else {
-                l.mouse_pick_avm1(context, *context.mouse_position, require_button_mode)
+                l.mouse_pick_avm1(context, point, require_button_mode)
             }
         })
     })
@@ -0,0 +1,220 @@
+//! Print jobs, shared by the AVM1 and AVM2 `PrintJob` classes.
+
+use crate::backend::print::{PageSettings, PrintPage};
+use crate::bitmap::bitmap_data::{BitmapData, BitmapDataWrapper, IBitmapDrawable};
+use crate::bitmap::operations;
+use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, TDisplayObject};
+use gc_arena::GcCell;
+use ruffle_render::matrix::Matrix;
+use ruffle_render::quality::StageQuality;
+use ruffle_render::transform::Transform;
+use std::num::NonZeroU32;
+use std::sync::atomic::{AtomicU32, Ordering};
+use swf::{BlendMode, Rectangle, Twips};
+
+/// The print job that is currently running.
+///
+/// Only one print job can run at a time. It's started with
+/// [`PrintJob::start`], and is finished when it's sent to the print backend.
+pub struct PrintJob {
+    id: PrintJobId,
+    settings: PageSettings,
+    pages: Vec<PrintPage>,
+}
+
+/// Identifies the `PrintJob` object that started a print job.
+///
+/// A job is only continued by the object that started it, so that a job that
+/// was abandoned without being sent doesn't block later ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrintJobId(NonZeroU32);
+
+impl PrintJobId {
+    fn next() -> Self {
+        static NEXT_ID: AtomicU32 = AtomicU32::new(1);
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        Self(NonZeroU32::new(id).unwrap_or(NonZeroU32::MIN))
+    }
+
+    /// The raw value of this ID, as stored by AVM2 `PrintJob` objects.
+    pub fn get(self) -> u32 {
+        self.0.get()
+    }
+
+    pub fn from_raw(id: u32) -> Option<Self> {
+        NonZeroU32::new(id).map(Self)
+    }
+}
+
+/// How content is placed on a page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageScaling {
+    /// Content is printed at its original size, with one point per pixel,
+    /// and clipped to the page.
+    None,
+
+    /// Content is scaled down to fit the page, as done by the AVM1 `print`
+    /// functions.
+    Fit,
+}
+
+impl PrintJob {
+    /// Starts a new print job.
+    ///
+    /// A job that is still running was never sent by the object that started
+    /// it, so it's discarded. Returns the ID and page setup of the new job, or
+    /// `None` if printing isn't possible or the user cancelled.
+    pub fn start(context: &mut UpdateContext<'_>) -> Option<(PrintJobId, PageSettings)> {
+        if context.print_job.take().is_some() {
+            tracing::warn!("PrintJob.start: Discarding a print job that was never sent");
+        }
+
+        let settings = context.print.start_job()?;
+        let id = PrintJobId::next();
+        *context.print_job = Some(PrintJob {
+            id,
+            settings,
+            pages: Vec::new(),
+        });
+        Some((id, settings))
+    }
+
+    /// Renders a display object as a new page of the print job `id`.
+    ///
+    /// `print_area` is in the coordinate space of `target`, and defaults to
+    /// the area of a page starting at the target's origin. If `frame` is
+    /// given and `target` is a movie clip, that frame is printed instead of
+    /// the current one, without running any of its scripts. Returns `false`
+    /// if the job isn't running or the page couldn't be rendered.
+    pub fn add_page<'gc>(
+        context: &mut UpdateContext<'gc>,
+        id: PrintJobId,
+        target: DisplayObject<'gc>,
+        print_area: Option<Rectangle<Twips>>,
+        frame: Option<u16>,
+        scaling: PageScaling,
+        print_as_bitmap: bool,
+    ) -> bool {
+        let Some(settings) = context
+            .print_job
+            .as_ref()
+            .filter(|job| job.id == id)
+            .map(|job| job.settings)
+        else {
+            return false;
+        };
+
+        let print_area = print_area.unwrap_or_else(|| Rectangle {
+            x_min: Twips::ZERO,
+            y_min: Twips::ZERO,
+            x_max: Twips::from_pixels(settings.page_width.into()),
+            y_max: Twips::from_pixels(settings.page_height.into()),
+        });
+        let area_width = print_area.width().to_pixels();
+        let area_height = print_area.height().to_pixels();
+        if area_width <= 0.0 || area_height <= 0.0 {
+            return false;
+        }
+
+        let page_scale = match scaling {
+            PageScaling::None => 1.0,
+            PageScaling::Fit => (f64::from(settings.page_width) / area_width)
+                .min(f64::from(settings.page_height) / area_height)
+                .min(1.0),
+        };
+        let pixel_scale = f64::from(settings.dpi) / 72.0;
+        let scale = (page_scale * pixel_scale) as f32;
+
+        let width = (f64::from(settings.page_width) * pixel_scale).ceil() as u32;
+        let height = (f64::from(settings.page_height) * pixel_scale).ceil() as u32;
+        let bitmap_data = BitmapData::new(width, height, false, 0xFFFFFFFF);
+        let bitmap_data = BitmapDataWrapper::new(GcCell::new(context.gc_context, bitmap_data));
+
+        let transform = Transform {
+            matrix: Matrix::scale(scale, scale)
+                * Matrix::translate(-print_area.x_min, -print_area.y_min),
+            color_transform: Default::default(),
+        };
+        // Flash prints vectors directly when it can, but we always go through
+        // a bitmap. Smoothing keeps bitmaps in vector jobs looking sharp enough.
+        let smoothing = !print_as_bitmap || page_scale < 1.0;
+        // The clip rectangle is in the space of the rendered page.
+        let clip_rect = Rectangle {
+            x_min: Twips::ZERO,
+            y_min: Twips::ZERO,
+            x_max: Twips::from_pixels(area_width * page_scale * pixel_scale),
+            y_max: Twips::from_pixels(area_height * page_scale * pixel_scale),
+        };
+
+        // Other frames are rendered from a copy of the clip's timeline, and
+        // any actions that it queues are dropped.
+        let timeline_copy = target
+            .as_movie_clip()
+            .zip(frame)
+            .filter(|(clip, frame)| *frame != clip.current_frame())
+            .map(|(clip, frame)| {
+                let queued_actions = std::mem::take(context.action_queue);
+                let copy = clip.timeline_copy(context, frame);
+                (copy, queued_actions)
+            });
+        let source = timeline_copy
+            .as_ref()
+            .map_or(target, |(copy, _)| (*copy).into());
+
+        let result = operations::draw(
+            context,
+            bitmap_data,
+            IBitmapDrawable::DisplayObject(source),
+            transform,
+            smoothing,
+            BlendMode::Normal,
+            Some(clip_rect),
+            StageQuality::High,
+        );
+
+        if let Some((copy, queued_actions)) = timeline_copy {
+            copy.release_timeline_copy(context);
+            *context.action_queue = queued_actions;
+        }
+
+        if result.is_err() {
+            tracing::error!(
+                "PrintJob.addPage: Render backend does not support offscreen rendering"
+            );
+            return false;
+        }
+
+        let (width, height, rgba) = operations::get_pixels_as_rgba(
+            context.renderer,
+            bitmap_data,
+            0,
+            0,
+            width as i32,
+            height as i32,
+        );
+        if let Some(job) = context.print_job.as_mut() {
+            job.pages.push(PrintPage {
+                width,
+                height,
+                rgba,
+            });
+        }
+        true
+    }
+
+    /// Sends the pages of the print job `id` to the print backend, and
+    /// finishes the job.
+    pub fn send(context: &mut UpdateContext<'_>, id: PrintJobId) {
+        if !context.print_job.as_ref().is_some_and(|job| job.id == id) {
+            return;
+        }
+        let Some(job) = context.print_job.take() else {
+            return;
+        };
+
+        if !job.pages.is_empty() {
+            context.print.send_job(&job.settings, job.pages);
+        }
+    }
+}
@@ -9,6 +9,7 @@ use crate::{
     },
     backend::navigator::NavigatorBackend,
     context::UpdateContext,
+    net_connection::{NetConnectionHandle, NetConnections},
     string::AvmString,
 };
 use async_channel::{unbounded, Receiver, Sender as AsyncSender, Sender};
@@ -28,6 +29,8 @@ new_key_type! {
 enum SocketKind<'gc> {
     Avm2(SocketObject<'gc>),
     Avm1(Avm1Object<'gc>),
+    /// The socket carries an RTMP connection for a `NetConnection`.
+    NetConnection(#[collect(require_static)] NetConnectionHandle),
 }
 
 #[derive(Collect)]
@@ -152,6 +155,34 @@ impl<'gc> Sockets<'gc> {
         }
     }
 
+    /// Open a socket for an RTMP `NetConnection`.
+    ///
+    /// All events on the socket are passed on to `NetConnections` rather than
+    /// to any AVM object.
+    pub fn connect_net_connection(
+        &mut self,
+        backend: &mut dyn NavigatorBackend,
+        connection: NetConnectionHandle,
+        host: String,
+        port: u16,
+    ) -> SocketHandle {
+        let (sender, receiver) = unbounded();
+
+        let socket = Socket::new(SocketKind::NetConnection(connection), sender);
+        let handle = self.sockets.insert(socket);
+
+        backend.connect_socket(
+            sanitize_host(&host).to_string(),
+            port,
+            NET_CONNECTION_TIMEOUT,
+            handle,
+            receiver,
+            self.sender.clone(),
+        );
+
+        handle
+    }
+
     pub fn is_connected(&self, handle: SocketHandle) -> bool {
         if let Some(socket) = self.sockets.get(handle) {
             socket.connected.get()
@@ -202,6 +233,7 @@ impl<'gc> Sockets<'gc> {
                 target.read_buffer().clear();
                 target.write_buffer().clear();
             }
+            SocketKind::NetConnection(_) => {}
         }
     }
 
@@ -245,6 +277,9 @@ impl<'gc> Sockets<'gc> {
                                 ExecutionReason::Special,
                             );
                         }
+                        SocketKind::NetConnection(connection) => {
+                            NetConnections::on_socket_connect(context, connection, true);
+                        }
                     }
                 }
                 SocketAction::Connect(
@@ -293,6 +328,10 @@ impl<'gc> Sockets<'gc> {
                                 ExecutionReason::Special,
                             );
                         }
+                        SocketKind::NetConnection(connection) => {
+                            context.sockets.sockets.remove(handle);
+                            NetConnections::on_socket_connect(context, connection, false);
+                        }
                     }
                 }
                 SocketAction::Data(handle, mut data) => {
@@ -386,6 +425,9 @@ impl<'gc> Sockets<'gc> {
                                 }
                             }
                         }
+                        SocketKind::NetConnection(connection) => {
+                            NetConnections::on_socket_data(context, connection, &data);
+                        }
                     }
                 }
                 SocketAction::Close(handle) => {
@@ -429,6 +471,9 @@ impl<'gc> Sockets<'gc> {
                                 ExecutionReason::Special,
                             );
                         }
+                        SocketKind::NetConnection(connection) => {
+                            NetConnections::on_socket_close(context, connection);
+                        }
                     }
                 }
             }
@@ -436,6 +481,9 @@ impl<'gc> Sockets<'gc> {
     }
 }
 
+/// How long an RTMP connection may take to open its socket.
+const NET_CONNECTION_TIMEOUT: Duration = Duration::from_secs(20);
+
 /// Flash treats a socket host as a cstring, and stops reading at a null byte.
 /// We need to account for this here.
 fn sanitize_host(host: &str) -> &str {
@@ -0,0 +1,507 @@
+//! SQL databases, used by the AIR `flash.data.SQLConnection` class.
+//!
+//! Databases are kept in memory while they're open. Databases stored in a file
+//! are read through the filesystem backend when they're opened. Committed
+//! changes are written back at the end of the frame, so that a movie making
+//! many changes only rewrites the file once, and when the database is closed.
+//! A database is also closed when its `SQLConnection` is garbage collected.
+//!
+//! The SQLite engine is only available with the `sqlite` feature; without it,
+//! opening a database always fails.
+
+use crate::avm2::object::{Object, WeakObject};
+use crate::backend::filesystem::{FilePath, FilesystemBackend, FilesystemError};
+use gc_arena::{Collect, Finalization};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// A value stored in, or bound to, an SQL statement.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqlValue {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+/// Where a parameter is bound in an SQL statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SqlParameter {
+    /// A named parameter, including its `:`, `@` or `$` prefix.
+    Name(String),
+
+    /// An unnamed parameter, counted from 0.
+    Index(usize),
+}
+
+/// How a database is opened, as given by `flash.data.SQLMode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlMode {
+    /// The database is opened for reading and writing, and is created if it doesn't exist.
+    Create,
+
+    /// The database is opened for reading only.
+    Read,
+
+    /// The database is opened for reading and writing, and must already exist.
+    Update,
+}
+
+/// The result of executing an SQL statement.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SqlResult {
+    /// The names of the columns of the returned rows.
+    pub columns: Vec<String>,
+
+    /// The returned rows, with one value per column.
+    pub rows: Vec<Vec<SqlValue>>,
+
+    /// How many rows were inserted, changed or deleted by the statement.
+    pub rows_affected: u64,
+
+    /// The row ID of the row inserted by the statement, or 0 if it didn't insert any.
+    pub last_insert_row_id: i64,
+}
+
+#[derive(Debug, Error)]
+pub enum SqlError {
+    #[error("SQL databases aren't supported in this build")]
+    Unsupported,
+
+    #[error("The database connection is closed")]
+    NotConnected,
+
+    #[error("Filesystem error: {0:?}")]
+    Filesystem(FilesystemError),
+
+    #[error("{0}")]
+    Database(String),
+}
+
+impl From<FilesystemError> for SqlError {
+    fn from(error: FilesystemError) -> Self {
+        SqlError::Filesystem(error)
+    }
+}
+
+/// A handle to an open database, as stored by `SQLConnection` objects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Collect)]
+#[collect(require_static)]
+pub struct SqlConnectionHandle(u32);
+
+impl SqlConnectionHandle {
+    pub fn from_raw(raw: u32) -> Self {
+        Self(raw)
+    }
+
+    pub fn raw(self) -> u32 {
+        self.0
+    }
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct OpenDatabase<'gc> {
+    #[collect(require_static)]
+    database: database::Database,
+
+    /// The file the database is stored in, or `None` if it's only kept in memory.
+    #[collect(require_static)]
+    file: Option<FilePath>,
+
+    /// The `SQLConnection` that opened the database, if any.
+    owner: Option<WeakObject<'gc>>,
+
+    /// The number of rows changed since the database was opened.
+    total_changes: u64,
+
+    /// Whether committed changes haven't been written to the file yet.
+    dirty: bool,
+}
+
+impl OpenDatabase<'_> {
+    fn save(&mut self, filesystem: &mut dyn FilesystemBackend) -> Result<(), SqlError> {
+        self.dirty = false;
+        if let Some(file) = &self.file {
+            filesystem.write(file, &self.database.serialize()?)?;
+        }
+        Ok(())
+    }
+
+    /// Writes committed changes to the file, unless a transaction is open,
+    /// as its changes would be written too.
+    fn flush(&mut self, filesystem: &mut dyn FilesystemBackend) -> Result<(), SqlError> {
+        if self.dirty && !self.database.in_transaction() {
+            self.save(filesystem)?;
+        }
+        Ok(())
+    }
+
+    /// Rolls back any open transaction, and writes committed changes to the file.
+    fn close(mut self, filesystem: &mut dyn FilesystemBackend) -> Result<(), SqlError> {
+        if self.database.in_transaction() {
+            self.database.execute("ROLLBACK", &[])?;
+        }
+        self.flush(filesystem)
+    }
+}
+
+/// All databases that are currently open.
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct SqlConnections<'gc> {
+    databases: HashMap<SqlConnectionHandle, OpenDatabase<'gc>>,
+    next_handle: u32,
+}
+
+impl<'gc> SqlConnections<'gc> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Opens a database stored in a file, or a new in-memory database if `file` is `None`.
+    ///
+    /// The database is closed when `owner` is garbage collected.
+    pub fn open(
+        &mut self,
+        filesystem: &mut dyn FilesystemBackend,
+        file: Option<FilePath>,
+        mode: SqlMode,
+        owner: Option<Object<'gc>>,
+    ) -> Result<SqlConnectionHandle, SqlError> {
+        let read_only = mode == SqlMode::Read;
+        let (database, create) = match &file {
+            Some(file) => {
+                if !read_only && !file.file_root().is_writable() {
+                    return Err(FilesystemError::AccessDenied.into());
+                }
+                match filesystem.read(file) {
+                    Ok(data) => (database::Database::open(Some(data), read_only)?, false),
+                    Err(FilesystemError::NotFound) if mode == SqlMode::Create => {
+                        (database::Database::open(None, false)?, true)
+                    }
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            None => (database::Database::open(None, read_only)?, false),
+        };
+
+        let mut database = OpenDatabase {
+            database,
+            file,
+            owner: owner.map(|owner| owner.downgrade()),
+            total_changes: 0,
+            dirty: false,
+        };
+        if create {
+            database.save(filesystem)?;
+        }
+
+        let handle = SqlConnectionHandle(self.next_handle);
+        self.next_handle = self.next_handle.wrapping_add(1);
+        self.databases.insert(handle, database);
+        Ok(handle)
+    }
+
+    /// Closes a database. Any transaction that's still open is rolled back.
+    pub fn close(
+        &mut self,
+        filesystem: &mut dyn FilesystemBackend,
+        handle: SqlConnectionHandle,
+    ) -> Result<(), SqlError> {
+        match self.databases.remove(&handle) {
+            Some(database) => database.close(filesystem),
+            None => Ok(()),
+        }
+    }
+
+    /// Executes an SQL statement.
+    ///
+    /// Changes made outside of a transaction, or committed by the statement,
+    /// are written to the file by the next call to `flush`.
+    pub fn execute(
+        &mut self,
+        handle: SqlConnectionHandle,
+        sql: &str,
+        parameters: &[(SqlParameter, SqlValue)],
+    ) -> Result<SqlResult, SqlError> {
+        let database = self
+            .databases
+            .get_mut(&handle)
+            .ok_or(SqlError::NotConnected)?;
+
+        let was_in_transaction = database.database.in_transaction();
+        let (result, modified) = database.database.execute(sql, parameters)?;
+        database.total_changes += result.rows_affected;
+
+        // Transaction statements don't count as modifying the database,
+        // but the changes they commit still have to be saved.
+        if !database.database.in_transaction() && (modified || was_in_transaction) {
+            database.dirty = true;
+        }
+
+        Ok(result)
+    }
+
+    /// Writes the committed changes of all databases to their files.
+    pub fn flush(&mut self, filesystem: &mut dyn FilesystemBackend) {
+        for database in self.databases.values_mut() {
+            if let Err(e) = database.flush(filesystem) {
+                tracing::error!("Error saving SQL database {:?}: {e}", database.file);
+            }
+        }
+    }
+
+    /// Closes the databases whose `SQLConnection` is about to be collected.
+    pub fn finalize(&mut self, fc: &Finalization<'gc>, filesystem: &mut dyn FilesystemBackend) {
+        let dead: Vec<_> = self
+            .databases
+            .iter()
+            .filter(|(_, database)| database.owner.is_some_and(|owner| owner.is_dead(fc)))
+            .map(|(handle, _)| *handle)
+            .collect();
+        for handle in dead {
+            if let Err(e) = self.close(filesystem, handle) {
+                tracing::error!("Error closing collected SQL database: {e}");
+            }
+        }
+    }
+
+    pub fn in_transaction(&self, handle: SqlConnectionHandle) -> bool {
+        self.databases
+            .get(&handle)
+            .is_some_and(|database| database.database.in_transaction())
+    }
+
+    pub fn total_changes(&self, handle: SqlConnectionHandle) -> u64 {
+        self.databases
+            .get(&handle)
+            .map_or(0, |database| database.total_changes)
+    }
+}
+
+/// Whether SQL databases can be opened in this build.
+pub fn is_supported() -> bool {
+    cfg!(feature = "sqlite")
+}
+
+#[cfg(feature = "sqlite")]
+mod database {
+    use super::{SqlError, SqlParameter, SqlResult, SqlValue};
+    use rusqlite::serialize::OwnedData;
+    use rusqlite::types::{ToSqlOutput, ValueRef};
+    use rusqlite::{ffi, Connection, DatabaseName, ToSql};
+    use std::ptr::NonNull;
+
+    impl From<rusqlite::Error> for SqlError {
+        fn from(error: rusqlite::Error) -> Self {
+            SqlError::Database(error.to_string())
+        }
+    }
+
+    impl ToSql for SqlValue {
+        fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+            Ok(ToSqlOutput::Borrowed(match self {
+                SqlValue::Null => ValueRef::Null,
+                SqlValue::Integer(value) => ValueRef::Integer(*value),
+                SqlValue::Real(value) => ValueRef::Real(*value),
+                SqlValue::Text(value) => ValueRef::Text(value.as_bytes()),
+                SqlValue::Blob(value) => ValueRef::Blob(value),
+            }))
+        }
+    }
+
+    impl From<ValueRef<'_>> for SqlValue {
+        fn from(value: ValueRef<'_>) -> Self {
+            match value {
+                ValueRef::Null => SqlValue::Null,
+                ValueRef::Integer(value) => SqlValue::Integer(value),
+                ValueRef::Real(value) => SqlValue::Real(value),
+                ValueRef::Text(value) => {
+                    SqlValue::Text(String::from_utf8_lossy(value).into_owned())
+                }
+                ValueRef::Blob(value) => SqlValue::Blob(value.to_vec()),
+            }
+        }
+    }
+
+    pub struct Database {
+        connection: Connection,
+    }
+
+    impl Database {
+        pub fn open(data: Option<Vec<u8>>, read_only: bool) -> Result<Self, SqlError> {
+            let mut connection = Connection::open_in_memory()?;
+            // An empty file is an empty database.
+            if let Some(data) = data.filter(|data| !data.is_empty()) {
+                connection.deserialize(DatabaseName::Main, sqlite_owned(&data)?, read_only)?;
+            }
+            Ok(Self { connection })
+        }
+
+        pub fn serialize(&self) -> Result<Vec<u8>, SqlError> {
+            Ok(self.connection.serialize(DatabaseName::Main)?.to_vec())
+        }
+
+        pub fn in_transaction(&self) -> bool {
+            !self.connection.is_autocommit()
+        }
+
+        /// Executes a statement, also returning whether it may have modified the database.
+        pub fn execute(
+            &mut self,
+            sql: &str,
+            parameters: &[(SqlParameter, SqlValue)],
+        ) -> Result<(SqlResult, bool), SqlError> {
+            let mut statement = self.connection.prepare(sql)?;
+            for (parameter, value) in parameters {
+                let index = match parameter {
+                    SqlParameter::Index(index) => index + 1,
+                    SqlParameter::Name(name) => statement
+                        .parameter_index(name)?
+                        .ok_or_else(|| SqlError::Database(format!("Unknown parameter {name}")))?,
+                };
+                statement.raw_bind_parameter(index, value)?;
+            }
+
+            let columns: Vec<String> = statement
+                .column_names()
+                .into_iter()
+                .map(String::from)
+                .collect();
+            let modified = !statement.readonly();
+            let changes_before = self.connection.total_changes();
+            let row_id_before = self.connection.last_insert_rowid();
+
+            let mut rows = Vec::new();
+            let mut query = statement.raw_query();
+            while let Some(row) = query.next()? {
+                let row = (0..columns.len())
+                    .map(|i| row.get_ref(i).map(SqlValue::from))
+                    .collect::<Result<_, _>>()?;
+                rows.push(row);
+            }
+            drop(query);
+            drop(statement);
+
+            let row_id = self.connection.last_insert_rowid();
+            let result = SqlResult {
+                columns,
+                rows,
+                rows_affected: self.connection.total_changes() - changes_before,
+                last_insert_row_id: if row_id != row_id_before { row_id } else { 0 },
+            };
+            Ok((result, modified))
+        }
+    }
+
+    /// Copies data into a buffer allocated by SQLite, which can take ownership of it.
+    fn sqlite_owned(data: &[u8]) -> Result<OwnedData, SqlError> {
+        // SAFETY: The buffer is allocated with the requested size, and is
+        // entirely initialized before being handed to `OwnedData`.
+        unsafe {
+            let ptr = ffi::sqlite3_malloc64(data.len() as u64) as *mut u8;
+            let ptr =
+                NonNull::new(ptr).ok_or_else(|| SqlError::Database("Out of memory".into()))?;
+            std::ptr::copy_nonoverlapping(data.as_ptr(), ptr.as_ptr(), data.len());
+            Ok(OwnedData::from_raw_nonnull(ptr, data.len()))
+        }
+    }
+}
+
+#[cfg(not(feature = "sqlite"))]
+mod database {
+    use super::{SqlError, SqlParameter, SqlResult, SqlValue};
+    use std::convert::Infallible;
+
+    /// A database can never be opened without SQLite.
+    pub struct Database(Infallible);
+
+    impl Database {
+        pub fn open(_data: Option<Vec<u8>>, _read_only: bool) -> Result<Self, SqlError> {
+            Err(SqlError::Unsupported)
+        }
+
+        pub fn serialize(&self) -> Result<Vec<u8>, SqlError> {
+            match self.0 {}
+        }
+
+        pub fn in_transaction(&self) -> bool {
+            match self.0 {}
+        }
+
+        pub fn execute(
+            &mut self,
+            _sql: &str,
+            _parameters: &[(SqlParameter, SqlValue)],
+        ) -> Result<(SqlResult, bool), SqlError> {
+            match self.0 {}
+        }
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+    use crate::backend::filesystem::{FileRoot, MemoryFilesystemBackend};
+
+    #[test]
+    fn test_persisted_database() {
+        let mut filesystem = MemoryFilesystemBackend::new();
+        let file = FilePath::root(FileRoot::ApplicationStorage).join("test.db");
+        let mut connections = SqlConnections::new();
+
+        let handle = connections
+            .open(&mut filesystem, Some(file.clone()), SqlMode::Create, None)
+            .unwrap();
+        connections
+            .execute(handle, "CREATE TABLE items (name TEXT, count INTEGER)", &[])
+            .unwrap();
+        connections.execute(handle, "BEGIN", &[]).unwrap();
+        let result = connections
+            .execute(
+                handle,
+                "INSERT INTO items VALUES (:name, ?2)",
+                &[
+                    (
+                        SqlParameter::Name(":name".into()),
+                        SqlValue::Text("apple".into()),
+                    ),
+                    (SqlParameter::Index(1), SqlValue::Integer(3)),
+                ],
+            )
+            .unwrap();
+        assert_eq!(result.rows_affected, 1);
+        assert_eq!(result.last_insert_row_id, 1);
+        assert!(connections.in_transaction(handle));
+        connections.execute(handle, "COMMIT", &[]).unwrap();
+
+        // Committed changes are only written when flushed.
+        let empty = filesystem.read(&file).unwrap();
+        connections.flush(&mut filesystem);
+        let saved = filesystem.read(&file).unwrap();
+        assert_ne!(saved, empty);
+
+        // A transaction that's still open is rolled back when closing.
+        connections.execute(handle, "BEGIN", &[]).unwrap();
+        connections
+            .execute(handle, "DELETE FROM items", &[])
+            .unwrap();
+        connections.flush(&mut filesystem);
+        assert_eq!(filesystem.read(&file).unwrap(), saved);
+        connections.close(&mut filesystem, handle).unwrap();
+
+        let handle = connections
+            .open(&mut filesystem, Some(file), SqlMode::Read, None)
+            .unwrap();
+        let result = connections
+            .execute(handle, "SELECT * FROM items", &[])
+            .unwrap();
+        assert_eq!(result.columns, vec!["name", "count"]);
+        assert_eq!(
+            result.rows,
+            vec![vec![SqlValue::Text("apple".into()), SqlValue::Integer(3)]]
+        );
+    }
+}
@@ -18,21 +18,28 @@ use crate::buffer::{Buffer, Slice, Substream, SubstreamError};
 use crate::context::UpdateContext;
 use crate::display_object::{MovieClip, TDisplayObject};
 use crate::loader::Error;
+use crate::net_connection::{NetConnectionHandle, NetConnections};
 use crate::string::AvmString;
 use crate::vminterface::AvmObject;
+use flash_lso::types::Value as AmfValue;
 use flv_rs::{
     AudioData as FlvAudioData, AudioDataType as FlvAudioDataType, Error as FlvError, FlvReader,
     FrameType as FlvFrameType, Header as FlvHeader, ScriptData as FlvScriptData,
     SoundFormat as FlvSoundFormat, SoundRate as FlvSoundRate, SoundSize as FlvSoundSize,
     SoundType as FlvSoundType, Tag as FlvTag, TagData as FlvTagData, Value as FlvValue,
-    VideoData as FlvVideoData, VideoPacket as FlvVideoPacket,
+    Variable as FlvVariable, VideoData as FlvVideoData, VideoPacket as FlvVideoPacket,
 };
 use gc_arena::{Collect, GcCell, Mutation};
+use mp4_rs::{
+    AudioCodec as Mp4AudioCodec, Error as Mp4Error, Media as Mp4Media, Movie as Mp4Movie,
+    Mp4Reader, Track as Mp4Track, VideoCodec as Mp4VideoCodec,
+};
 use ruffle_render::bitmap::BitmapInfo;
 use ruffle_video::frame::EncodedFrame;
 use ruffle_video::VideoStreamHandle;
 use std::cmp::max;
 use std::io::{Seek, SeekFrom};
+use std::sync::Arc;
 use swf::{AudioCompression, SoundFormat, VideoCodec, VideoDeblocking};
 use thiserror::Error;
 use url::Url;
@@ -177,6 +184,35 @@ pub enum NetStreamType {
         /// frame IDs ourselves for various API related purposes.
         frame_id: u32,
     },
+
+    /// The stream is an MP4 or F4V (ISO base media file format).
+    ///
+    /// Unlike FLV, samples are located through the tables in the `moov` box
+    /// rather than by reading through the file, so we track our position as
+    /// sample indices instead of a buffer offset. The buffer offset instead
+    /// tracks how far we've parsed movie fragments, whose samples get
+    /// appended to the tracks as they load.
+    Mp4 {
+        movie: Arc<Mp4Movie>,
+
+        /// The index of the video track we're playing in `movie.tracks`.
+        video_track: Option<usize>,
+
+        /// The index of the audio track we're playing in `movie.tracks`.
+        audio_track: Option<usize>,
+
+        /// The currently playing video track's stream instance.
+        video_stream: Option<VideoStreamHandle>,
+
+        /// The index of the next video sample to be processed.
+        next_video_sample: usize,
+
+        /// The index of the next audio sample to be processed.
+        next_audio_sample: usize,
+
+        /// The number of video samples that have been preloaded.
+        preloaded_video_samples: usize,
+    },
 }
 
 #[derive(Clone, Debug, Collect)]
@@ -243,6 +279,13 @@ pub struct NetStreamData<'gc> {
     #[collect(require_static)]
     audio_stream: Option<(Substream, SoundStreamInfo)>,
 
+    /// The AAC `AudioSpecificConfig` from the most recent AAC sequence header.
+    ///
+    /// This is retained across seeks, since the sequence header is usually
+    /// only present at the start of the file.
+    #[collect(require_static)]
+    aac_config: Option<Vec<u8>>,
+
     /// The currently playing sound stream
     #[collect(require_static)]
     sound_instance: Option<SoundInstanceHandle>,
@@ -252,6 +295,51 @@ pub struct NetStreamData<'gc> {
 
     /// True if the stream should play when ticked.
     playing: bool,
+
+    /// True if the stream is in "data generation mode", i.e. it was started
+    /// with `play(null)` and media data is pushed into it with `appendBytes`
+    /// rather than downloaded.
+    data_generation_mode: bool,
+
+    /// True if `stream_time` should be taken from the next FLV tag played.
+    ///
+    /// Data appended after a `RESET_BEGIN` or `RESET_SEEK` action does not
+    /// have to start at the current stream time.
+    rebase_stream_time: bool,
+
+    /// True if `offset` points at the start of an FLV tag header rather than
+    /// at the back pointer that normally precedes it.
+    ///
+    /// Data appended after a `RESET_SEEK` action starts directly with a tag.
+    at_tag_boundary: bool,
+
+    /// True if we ran out of appended data and already reported
+    /// `NetStream.Buffer.Empty`.
+    buffer_empty: bool,
+
+    /// The `NetConnection` this stream was created with.
+    #[collect(require_static)]
+    net_connection: Option<NetConnectionHandle>,
+
+    /// The RTMP message stream ID the server assigned to this stream.
+    ///
+    /// Streams played over RTMP run in data generation mode, with the server
+    /// supplying the data.
+    rtmp_stream_id: Option<u32>,
+}
+
+/// The actions that can be passed to `NetStream.appendBytesAction`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AppendBytesAction {
+    /// No more data will be appended; the stream should play out what it has.
+    EndSequence,
+
+    /// The next appended data starts a new file, beginning with an FLV header.
+    ResetBegin,
+
+    /// The next appended data continues the current file from a new position,
+    /// beginning with an FLV tag.
+    ResetSeek,
 }
 
 impl<'gc> NetStream<'gc> {
@@ -273,10 +361,17 @@ impl<'gc> NetStream<'gc> {
                 avm2_client: None,
                 url: None,
                 audio_stream: None,
+                aac_config: None,
                 sound_instance: None,
                 attached_to: None,
                 playing: false,
                 expected_length: Some(0),
+                data_generation_mode: false,
+                rebase_stream_time: false,
+                at_tag_boundary: false,
+                buffer_empty: false,
+                net_connection: None,
+                rtmp_stream_id: None,
             },
         ))
     }
@@ -293,6 +388,76 @@ impl<'gc> NetStream<'gc> {
         self.0.write(gc_context).avm_object = Some(avm_object);
     }
 
+    pub fn set_net_connection(
+        self,
+        gc_context: &Mutation<'gc>,
+        net_connection: Option<NetConnectionHandle>,
+    ) {
+        self.0.write(gc_context).net_connection = net_connection;
+    }
+
+    pub fn rtmp_stream_id(self) -> Option<u32> {
+        self.0.read().rtmp_stream_id
+    }
+
+    /// Start playing data sent by an RTMP server on the given stream.
+    pub fn start_rtmp_playback(self, context: &mut UpdateContext<'gc>, stream_id: u32) {
+        self.reset_buffer(context);
+
+        let mut write = self.0.write(context.gc_context);
+        write.rtmp_stream_id = Some(stream_id);
+        write.data_generation_mode = true;
+        write.playing = true;
+        // The messages we get are turned into FLV tags, so they need an FLV
+        // header (with audio and video) in front of them.
+        write
+            .buffer
+            .append(&mut vec![b'F', b'L', b'V', 1, 0x05, 0, 0, 0, 9]);
+        drop(write);
+
+        StreamManager::activate(context, self);
+    }
+
+    /// Append an audio, video or script data message received over RTMP.
+    pub fn append_rtmp_message(
+        self,
+        context: &mut UpdateContext<'gc>,
+        tag_type: u8,
+        timestamp: u32,
+        data: &[u8],
+    ) {
+        // We don't keep track of the size of the previous tag, but nothing
+        // reads it in data generation mode.
+        let mut tag = vec![0, 0, 0, 0, tag_type];
+        tag.extend_from_slice(&(data.len() as u32).to_be_bytes()[1..]);
+        tag.extend_from_slice(&timestamp.to_be_bytes()[1..]);
+        tag.push((timestamp >> 24) as u8);
+        tag.extend_from_slice(&[0, 0, 0]);
+        tag.extend_from_slice(data);
+
+        self.append_bytes(context, &mut tag);
+    }
+
+    /// Send a command about this stream to the RTMP server it's playing from,
+    /// if any.
+    ///
+    /// Returns `false` if this stream isn't playing over RTMP.
+    fn send_rtmp_command(
+        self,
+        context: &mut UpdateContext<'gc>,
+        command: &str,
+        arguments: Vec<AmfValue>,
+    ) -> bool {
+        let read = self.0.read();
+        let (Some(connection), Some(stream_id)) = (read.net_connection, read.rtmp_stream_id) else {
+            return false;
+        };
+        drop(read);
+
+        NetConnections::send_stream_command(context, connection, stream_id, command, arguments);
+        true
+    }
+
     /// Reset the `NetStream` buffer to accept new source data.
     ///
     /// This must be done once per source change and should ideally be done
@@ -320,8 +485,13 @@ impl<'gc> NetStream<'gc> {
         write.stream_time = 0.0;
         write.queued_seek_time = None;
         write.audio_stream = None;
+        write.aac_config = None;
         write.sound_instance = None;
         write.expected_length = Some(0);
+        write.data_generation_mode = false;
+        write.rebase_stream_time = false;
+        write.at_tag_boundary = false;
+        write.buffer_empty = false;
     }
 
     /// Set the total number of bytes expected to be downloaded.
@@ -358,6 +528,96 @@ impl<'gc> NetStream<'gc> {
         );
     }
 
+    /// Append data pushed in from `NetStream.appendBytes`.
+    ///
+    /// This is only valid in data generation mode; otherwise the data is
+    /// ignored.
+    pub fn append_bytes(self, context: &mut UpdateContext<'gc>, data: &mut Vec<u8>) {
+        let mut write = self.0.write(context.gc_context);
+        if !write.data_generation_mode {
+            tracing::warn!("NetStream.appendBytes called outside of data generation mode");
+            return;
+        }
+
+        let was_empty = std::mem::take(&mut write.buffer_empty);
+        write.buffer.append(data);
+        drop(write);
+
+        StreamManager::activate(context, self);
+
+        if was_empty {
+            self.trigger_status_event(
+                context,
+                vec![("code", "NetStream.Buffer.Full"), ("level", "status")],
+            );
+        }
+    }
+
+    /// Handle a `NetStream.appendBytesAction` call.
+    pub fn append_bytes_action(self, context: &mut UpdateContext<'gc>, action: AppendBytesAction) {
+        let mut write = self.0.write(context.gc_context);
+        if !write.data_generation_mode {
+            tracing::warn!("NetStream.appendBytesAction called outside of data generation mode");
+            return;
+        }
+
+        match action {
+            AppendBytesAction::EndSequence => {
+                // Let the stream play out; the next underrun ends playback.
+                write.expected_length = None;
+                write.buffer_empty = false;
+            }
+            AppendBytesAction::ResetBegin => {
+                Self::discard_buffer(context, &mut write);
+                write.stream_type = None;
+                write.aac_config = None;
+                write.expected_length = Some(0);
+            }
+            AppendBytesAction::ResetSeek => {
+                // The container stays the same, so we keep the stream type
+                // (and with it, the video decoder) around.
+                Self::discard_buffer(context, &mut write);
+                write.at_tag_boundary = true;
+                write.expected_length = Some(0);
+            }
+        }
+        drop(write);
+
+        StreamManager::activate(context, self);
+    }
+
+    /// Throw away all data in the buffer and stop any sound that is playing
+    /// from it.
+    ///
+    /// Used in data generation mode, where the movie replaces the buffered
+    /// data on seeks.
+    fn discard_buffer(context: &mut UpdateContext<'gc>, write: &mut NetStreamData<'gc>) {
+        if let Some(instance) = write.sound_instance.take() {
+            context.audio.stop_sound(instance);
+            context.audio_manager.stop_sound(context.audio, instance);
+        }
+
+        write.buffer = Buffer::new();
+        write.offset = 0;
+        write.preload_offset = 0;
+        write.audio_stream = None;
+        write.rebase_stream_time = true;
+        write.at_tag_boundary = false;
+    }
+
+    /// Parse the FLV tag at the reader's position, which may or may not be
+    /// preceded by a back pointer.
+    fn parse_flv_tag<'a>(
+        reader: &mut FlvReader<'a>,
+        at_tag_boundary: bool,
+    ) -> Result<FlvTag<'a>, FlvError> {
+        if at_tag_boundary {
+            FlvTag::parse_without_back_pointer(reader)
+        } else {
+            FlvTag::parse(reader)
+        }
+    }
+
     /// Indicate that the buffer has finished loading and that no further data
     /// is expected to be downloaded to it.
     pub fn finish_buffer(self, context: &mut UpdateContext<'gc>) {
@@ -395,6 +655,17 @@ impl<'gc> NetStream<'gc> {
     ///
     /// `offset` is in milliseconds.
     pub fn seek(self, context: &mut UpdateContext<'gc>, offset: f64, notify: bool) {
+        if self.send_rtmp_command(context, "seek", vec![AmfValue::Number(offset)]) {
+            // The server reports the seek itself, and then sends data from
+            // the new position.
+            let mut write = self.0.write(context.gc_context);
+            if write.stream_type.is_some() {
+                Self::discard_buffer(context, &mut write);
+                write.stream_time = offset;
+            }
+            return;
+        }
+
         self.0.write(context.gc_context).queued_seek_time = Some(offset);
         StreamManager::activate(context, self);
 
@@ -431,6 +702,23 @@ impl<'gc> NetStream<'gc> {
     /// This function should be run during stream ticks and *not* called by AVM
     /// code to service seek requests.
     pub fn execute_seek(self, context: &mut UpdateContext<'gc>, offset: f64) {
+        if self.0.read().data_generation_mode {
+            // We can't seek within appended data. Instead, the movie is
+            // expected to respond to `Seek.Notify` with
+            // `appendBytesAction(RESET_SEEK)` and data from the new position,
+            // so the old data has to be gone before we notify it.
+            let mut write = self.0.write(context.gc_context);
+            Self::discard_buffer(context, &mut write);
+            write.stream_time = offset;
+            drop(write);
+
+            self.trigger_status_event(
+                context,
+                vec![("code", "NetStream.Seek.Notify"), ("level", "status")],
+            );
+            return;
+        }
+
         self.trigger_status_event(
             context,
             vec![("code", "NetStream.Seek.Notify"), ("level", "status")],
@@ -526,6 +814,30 @@ impl<'gc> NetStream<'gc> {
             write.offset = reader
                 .stream_position()
                 .expect("FLV reader stream position") as usize;
+        } else if let Some(NetStreamType::Mp4 {
+            movie,
+            video_track,
+            audio_track,
+            next_video_sample,
+            next_audio_sample,
+            ..
+        }) = &mut write.stream_type
+        {
+            // Snap to the closest keyframe at or before the requested time,
+            // and pick the audio back up from there.
+            let mut seek_time = offset;
+            if let Some(track) = video_track.map(|i| &movie.tracks[i]) {
+                if let Some(index) = track.sync_sample_at(offset) {
+                    *next_video_sample = index;
+                    seek_time = track.sample_time_ms(index).unwrap_or_default();
+                }
+            }
+
+            if let Some(track) = audio_track.map(|i| &movie.tracks[i]) {
+                *next_audio_sample = track.sample_at(seek_time);
+            }
+
+            write.stream_time = seek_time;
         }
 
         drop(write);
@@ -545,9 +857,20 @@ impl<'gc> NetStream<'gc> {
     /// Start playing media from this NetStream.
     ///
     /// If `name` is specified, this will also trigger streaming download of
-    /// the given resource. Otherwise, the stream will play whatever data is
-    /// available in the buffer.
+    /// the given resource. Otherwise, the stream enters data generation mode
+    /// and plays whatever data is appended with `append_bytes`.
     pub fn play(self, context: &mut UpdateContext<'gc>, name: Option<AvmString<'gc>>) {
+        let rtmp_connection = self
+            .0
+            .read()
+            .net_connection
+            .filter(|handle| context.net_connections.is_rtmp(*handle));
+        if let (Some(connection), Some(name)) = (rtmp_connection, name) {
+            // The server reports `NetStream.Play.Start` itself.
+            NetConnections::play_stream(context, connection, self, name.to_string());
+            return;
+        }
+
         if let Some(name) = name {
             let request = if let Ok(stream_url) =
                 Url::parse(context.swf.url()).and_then(|url| url.join(name.to_string().as_str()))
@@ -564,6 +887,9 @@ impl<'gc> NetStream<'gc> {
                 .load_netstream(context.player.clone(), self, request);
 
             context.navigator.spawn_future(future);
+        } else {
+            self.reset_buffer(context);
+            self.0.write(context.gc_context).data_generation_mode = true;
         }
 
         self.0.write(context.gc_context).playing = true;
@@ -581,7 +907,16 @@ impl<'gc> NetStream<'gc> {
         // work to be done at tick time.
         self.0.write(context.gc_context).playing = false;
 
-        if notify {
+        let time = self.0.read().stream_time;
+        let is_rtmp = notify
+            && self.send_rtmp_command(
+                context,
+                "pause",
+                vec![AmfValue::Bool(true), AmfValue::Number(time)],
+            );
+
+        // RTMP servers send their own notification.
+        if notify && !is_rtmp {
             self.trigger_status_event(
                 context,
                 vec![
@@ -597,16 +932,31 @@ impl<'gc> NetStream<'gc> {
     pub fn resume(self, context: &mut UpdateContext<'gc>) {
         self.0.write(context.gc_context).playing = true;
         StreamManager::activate(context, self);
+
+        let time = self.0.read().stream_time;
+        self.send_rtmp_command(
+            context,
+            "pause",
+            vec![AmfValue::Bool(false), AmfValue::Number(time)],
+        );
     }
 
     /// Resume stream playback if paused, pause otherwise.
     pub fn toggle_paused(self, context: &mut UpdateContext<'gc>) {
         let mut write = self.0.write(context.gc_context);
         write.playing = !write.playing;
+        let (playing, time) = (write.playing, write.stream_time);
+        drop(write);
 
-        if write.playing {
+        if playing {
             StreamManager::activate(context, self);
         }
+
+        self.send_rtmp_command(
+            context,
+            "pause",
+            vec![AmfValue::Bool(!playing), AmfValue::Number(time)],
+        );
     }
 
     /// Indicates that this `NetStream`'s audio was detached from a `MovieClip` (AVM1)
@@ -652,9 +1002,20 @@ impl<'gc> NetStream<'gc> {
         audio_data: FlvAudioData<'_>,
     ) -> Result<(), NetstreamError> {
         let data = match audio_data.data {
-            FlvAudioDataType::Raw(data)
-            | FlvAudioDataType::AacSequenceHeader(data)
-            | FlvAudioDataType::AacRaw(data) => slice.to_subslice(data),
+            FlvAudioDataType::Raw(data) => slice.to_subslice(data),
+            FlvAudioDataType::AacSequenceHeader(config) => {
+                // The sequence header isn't audio; the decoder needs it out of
+                // band when the sound stream is started.
+                write.aac_config = Some(config.to_vec());
+                return Ok(());
+            }
+            FlvAudioDataType::AacRaw(data) => {
+                if write.aac_config.is_none() {
+                    // Raw AAC frames can't be decoded without a config.
+                    return Ok(());
+                }
+                slice.to_subslice(data)
+            }
         };
         let substream = match &mut write.audio_stream {
             Some((substream, _sound_stream_info)) => {
@@ -691,7 +1052,7 @@ impl<'gc> NetStream<'gc> {
                         FlvSoundFormat::Nellymoser => AudioCompression::Nellymoser,
                         FlvSoundFormat::G711ALawPCM => return Err(NetstreamError::UnknownCodec),
                         FlvSoundFormat::G711MuLawPCM => return Err(NetstreamError::UnknownCodec),
-                        FlvSoundFormat::Aac => return Err(NetstreamError::UnknownCodec),
+                        FlvSoundFormat::Aac => AudioCompression::Aac,
                         FlvSoundFormat::Speex => AudioCompression::Speex,
                         FlvSoundFormat::MP38kHz => AudioCompression::Mp3,
                         FlvSoundFormat::DeviceSpecific => return Err(NetstreamError::UnknownCodec),
@@ -718,6 +1079,7 @@ impl<'gc> NetStream<'gc> {
                     stream_format: swf_format,
                     num_samples_per_block: 0,
                     latency_seek: 0,
+                    codec_config: write.aac_config.clone(),
                 };
 
                 *audio_stream = Some((substream, sound_stream_head));
@@ -812,6 +1174,29 @@ impl<'gc> NetStream<'gc> {
             return false;
         }
 
+        if buffer.get(4..8) == Some(&b"ftyp"[..]) {
+            let mut reader = Mp4Reader::from_parts(&buffer, write.offset);
+            let result = Mp4Movie::parse(&mut reader);
+            write.offset = reader.position();
+            return match result {
+                Ok(movie) => {
+                    drop(buffer);
+                    drop(write);
+                    self.mp4_movie_loaded(context, movie);
+                    true
+                }
+                // The `moov` box may be at the end of the file, so we pick up
+                // from the last box we looked at once more data has loaded.
+                Err(Mp4Error::EndOfData) => false,
+                Err(e) => {
+                    //TODO: Fire an error event to AS & stop playing too
+                    tracing::error!("MP4 movie parsing failed: {}", e);
+                    write.preload_offset = 3;
+                    false
+                }
+            };
+        }
+
         match buffer.get(0..3) {
             Some([0x46, 0x4C, 0x56]) => {
                 let mut reader = FlvReader::from_parts(&buffer, write.offset);
@@ -835,6 +1220,7 @@ impl<'gc> NetStream<'gc> {
                     }
                 }
             }
+            Some(_) if buffer.len() < 8 => false, //Not enough data to sniff an MP4
             Some(magic) => {
                 //Unrecognized signature
                 //TODO: Fire an error event to AS & stop playing too
@@ -1046,6 +1432,303 @@ impl<'gc> NetStream<'gc> {
         };
     }
 
+    /// Set up playback of a parsed MP4 movie.
+    ///
+    /// MP4 files have no script data of their own, so this also synthesizes
+    /// the `onMetaData` event that Flash Player generates for them.
+    fn mp4_movie_loaded(self, context: &mut UpdateContext<'gc>, movie: Mp4Movie) {
+        let mut video_track = movie.video_track();
+        let mut audio_track = movie.audio_track();
+        let mut video_stream = None;
+
+        if let Some(track) = video_track.map(|i| &movie.tracks[i]) {
+            match &track.media {
+                Mp4Media::Video(info) => match &info.codec {
+                    Mp4VideoCodec::Avc { config } => {
+                        match context.video.register_video_stream(
+                            track.samples.len() as u32,
+                            (info.width, info.height),
+                            VideoCodec::H264,
+                            VideoDeblocking::UseVideoPacketValue,
+                        ) {
+                            Ok(stream_handle) => {
+                                if let Err(e) = context
+                                    .video
+                                    .configure_video_stream_decoder(stream_handle, config)
+                                {
+                                    tracing::error!("Configuring MP4 video decoder failed: {}", e);
+                                }
+                                video_stream = Some(stream_handle);
+                            }
+                            Err(e) => {
+                                tracing::error!(
+                                    "Got error when registering MP4 video stream: {}",
+                                    e
+                                )
+                            }
+                        }
+                    }
+                    Mp4VideoCodec::Unknown(codec) => {
+                        tracing::error!("MP4 video track has unsupported codec {:?}", codec);
+                        video_track = None;
+                    }
+                },
+                _ => unreachable!(),
+            }
+        }
+
+        if let Some(Mp4Media::Audio(info)) = audio_track.map(|i| &movie.tracks[i].media) {
+            if let Mp4AudioCodec::Unknown(codec) = info.codec {
+                tracing::error!("MP4 audio track has unsupported codec {:?}", codec);
+                audio_track = None;
+            }
+        }
+
+        let metadata = Self::mp4_metadata(&movie, video_track, audio_track);
+
+        let mut write = self.0.write(context.gc_context);
+        write.stream_type = Some(NetStreamType::Mp4 {
+            movie: Arc::new(movie),
+            video_track,
+            audio_track,
+            video_stream,
+            next_video_sample: 0,
+            next_audio_sample: 0,
+            preloaded_video_samples: 0,
+        });
+
+        let avm_object = write.avm_object;
+        drop(write);
+        // Any errors while trying to lookup or call AVM2 properties are silently swallowed.
+        let _ = self.handle_script_data(avm_object, context, b"onMetaData", metadata);
+    }
+
+    /// Build the `onMetaData` object for an MP4 movie.
+    ///
+    /// The property names and values mirror what Flash Player reports.
+    fn mp4_metadata(
+        movie: &Mp4Movie,
+        video_track: Option<usize>,
+        audio_track: Option<usize>,
+    ) -> FlvValue<'static> {
+        fn var(name: &'static [u8], data: FlvValue<'static>) -> FlvVariable<'static> {
+            FlvVariable { name, data }
+        }
+
+        let mut vars = vec![
+            var(b"duration", FlvValue::Number(movie.duration_secs())),
+            var(
+                b"moovposition",
+                FlvValue::Number(movie.moov_position as f64),
+            ),
+        ];
+
+        if let Some(track) = video_track.map(|i| &movie.tracks[i]) {
+            if let Mp4Media::Video(info) = &track.media {
+                vars.push(var(b"width", FlvValue::Number(info.width.into())));
+                vars.push(var(b"height", FlvValue::Number(info.height.into())));
+                vars.push(var(b"videocodecid", FlvValue::String(b"avc1")));
+                if let Mp4VideoCodec::Avc { config } = &info.codec {
+                    if let (Some(profile), Some(level)) = (config.get(1), config.get(3)) {
+                        vars.push(var(b"avcprofile", FlvValue::Number((*profile).into())));
+                        vars.push(var(b"avclevel", FlvValue::Number((*level).into())));
+                    }
+                }
+            }
+
+            if let Some(extends) = track.extends.filter(|e| e.default_sample_duration > 0) {
+                // The samples of fragmented tracks are yet to be loaded.
+                vars.push(var(
+                    b"videoframerate",
+                    FlvValue::Number(
+                        track.timescale as f64 / extends.default_sample_duration as f64,
+                    ),
+                ));
+            } else if track.duration > 0 && track.timescale > 0 {
+                let duration = track.duration as f64 / track.timescale as f64;
+                vars.push(var(
+                    b"videoframerate",
+                    FlvValue::Number(track.samples.len() as f64 / duration),
+                ));
+            }
+
+            let seekpoints = track
+                .samples
+                .iter()
+                .enumerate()
+                .filter(|(_, sample)| sample.is_sync)
+                .map(|(i, sample)| {
+                    FlvValue::Object(vec![
+                        var(
+                            b"time",
+                            FlvValue::Number(track.sample_time_ms(i).unwrap_or_default() / 1000.0),
+                        ),
+                        var(b"offset", FlvValue::Number(sample.offset as f64)),
+                    ])
+                })
+                .collect();
+            vars.push(var(b"seekpoints", FlvValue::StrictArray(seekpoints)));
+        }
+
+        if let Some(track) = audio_track.map(|i| &movie.tracks[i]) {
+            if let Mp4Media::Audio(info) = &track.media {
+                let codec_id: &'static [u8] = match &info.codec {
+                    Mp4AudioCodec::Mp3 => b".mp3",
+                    _ => b"mp4a",
+                };
+                vars.push(var(b"audiocodecid", FlvValue::String(codec_id)));
+                vars.push(var(
+                    b"audiosamplerate",
+                    FlvValue::Number(info.sample_rate.into()),
+                ));
+                vars.push(var(
+                    b"audiochannels",
+                    FlvValue::Number(info.channels.into()),
+                ));
+                if let Mp4AudioCodec::Aac { config } = &info.codec {
+                    if let Some(object_type) = config.first() {
+                        vars.push(var(b"aacaot", FlvValue::Number((object_type >> 3).into())));
+                    }
+                }
+            }
+        }
+
+        let trackinfo = [video_track, audio_track]
+            .into_iter()
+            .flatten()
+            .map(|i| {
+                let track = &movie.tracks[i];
+                FlvValue::Object(vec![
+                    var(b"length", FlvValue::Number(track.duration as f64)),
+                    var(b"timescale", FlvValue::Number(track.timescale.into())),
+                    var(b"language", FlvValue::String(b"und")),
+                ])
+            })
+            .collect();
+        vars.push(var(b"trackinfo", FlvValue::StrictArray(trackinfo)));
+
+        FlvValue::EcmaArray(vars)
+    }
+
+    /// Process an MP4 video sample.
+    ///
+    /// `write` must be an active borrow of the current `NetStream`. `data` is
+    /// the sample data, which for H.264 is the same length-prefixed NAL units
+    /// as FLV AVC packets.
+    fn mp4_video_sample(
+        self,
+        context: &mut UpdateContext<'gc>,
+        write: &mut NetStreamData<'gc>,
+        data: &[u8],
+        sample_index: usize,
+    ) {
+        let (video_handle, tag_needs_preloading) = match &mut write.stream_type {
+            Some(NetStreamType::Mp4 {
+                video_stream: Some(video_stream),
+                preloaded_video_samples,
+                ..
+            }) => {
+                let needs_preloading = sample_index >= *preloaded_video_samples;
+                *preloaded_video_samples = max(*preloaded_video_samples, sample_index + 1);
+                (*video_stream, needs_preloading)
+            }
+            _ => return,
+        };
+        let frame_id = sample_index as u32;
+
+        if tag_needs_preloading {
+            let encoded_frame = EncodedFrame {
+                codec: VideoCodec::H264,
+                data,
+                frame_id,
+            };
+
+            if let Err(e) = context
+                .video
+                .preload_video_stream_frame(video_handle, encoded_frame)
+            {
+                tracing::error!("Preloading video frame {} failed: {}", frame_id, e);
+            }
+        }
+
+        let encoded_frame = EncodedFrame {
+            codec: VideoCodec::H264,
+            data,
+            frame_id,
+        };
+
+        match context
+            .video
+            .decode_video_stream_frame(video_handle, encoded_frame, context.renderer)
+        {
+            Ok(bitmap_info) => {
+                write.last_decoded_bitmap = Some(bitmap_info);
+                if let Some(mc) = write.attached_to {
+                    mc.invalidate_cached_bitmap(context.gc_context);
+                    *context.needs_render = true;
+                }
+            }
+            Err(e) => {
+                tracing::error!("Decoding video frame {} failed: {}", frame_id, e);
+            }
+        }
+    }
+
+    /// Process an MP4 audio sample.
+    ///
+    /// `write` must be an active borrow of the current `NetStream`. `data`
+    /// must reference the underlying backing buffer.
+    fn mp4_audio_sample(
+        self,
+        write: &mut NetStreamData<'gc>,
+        data: Slice,
+        track: &Mp4Track,
+    ) -> Result<(), NetstreamError> {
+        let substream = match &mut write.audio_stream {
+            Some((substream, _sound_stream_info)) => {
+                if substream
+                    .last_chunk()
+                    .map(|lc| lc.end() > data.start())
+                    .unwrap_or(false)
+                {
+                    // Reject repeats of lookahead samples, as with FLV tags.
+                    return Ok(());
+                }
+
+                substream
+            }
+            audio_stream => {
+                let Mp4Media::Audio(info) = &track.media else {
+                    return Err(NetstreamError::UnknownCodec);
+                };
+                let (compression, codec_config) = match &info.codec {
+                    Mp4AudioCodec::Aac { config } => (AudioCompression::Aac, Some(config.clone())),
+                    Mp4AudioCodec::Mp3 => (AudioCompression::Mp3, None),
+                    Mp4AudioCodec::Unknown(_) => return Err(NetstreamError::UnknownCodec),
+                };
+
+                let sound_stream_head = SoundStreamInfo {
+                    wrapping: SoundStreamWrapping::Unwrapped,
+                    stream_format: SoundFormat {
+                        compression,
+                        sample_rate: info.sample_rate.try_into().unwrap_or(u16::MAX),
+                        is_stereo: info.channels > 1,
+                        is_16_bit: true,
+                    },
+                    num_samples_per_block: 0,
+                    latency_seek: 0,
+                    codec_config,
+                };
+
+                *audio_stream = Some((Substream::new(data.buffer().clone()), sound_stream_head));
+
+                &mut audio_stream.as_mut().unwrap().0
+            }
+        };
+
+        Ok(substream.append(data)?)
+    }
+
     /// Process a parsed FLV script tag.
     ///
     /// This function attempts to borrow the current `NetStream`, you must drop
@@ -1160,6 +1843,27 @@ impl<'gc> NetStream<'gc> {
         let slice = write.buffer.to_full_slice();
         let buffer = slice.data();
 
+        if write.rebase_stream_time && matches!(write.stream_type, Some(NetStreamType::Flv { .. }))
+        {
+            let mut reader = FlvReader::from_parts(&buffer, write.offset);
+            if let Ok(tag) = Self::parse_flv_tag(&mut reader, write.at_tag_boundary) {
+                write.stream_time = tag.timestamp as f64;
+                write.rebase_stream_time = false;
+            }
+        }
+
+        // Pick up the samples of any movie fragments loaded since the last tick.
+        let offset = write.offset;
+        if let Some(NetStreamType::Mp4 { movie, .. }) = &mut write.stream_type {
+            if movie.is_fragmented() {
+                let mut reader = Mp4Reader::from_parts(&buffer, offset);
+                if let Err(e) = Arc::make_mut(movie).parse_fragments(&mut reader) {
+                    tracing::error!("MP4 fragment parsing failed: {}", e);
+                }
+                write.offset = reader.position();
+            }
+        }
+
         let max_time = write.stream_time + dt;
         let mut buffer_underrun = false;
         let mut error = false;
@@ -1169,9 +1873,10 @@ impl<'gc> NetStream<'gc> {
         //At this point we should know our stream type.
         if matches!(write.stream_type, Some(NetStreamType::Flv { .. })) {
             let mut reader = FlvReader::from_parts(&buffer, write.offset);
+            let mut at_tag_boundary = write.at_tag_boundary;
 
             loop {
-                let tag = FlvTag::parse(&mut reader);
+                let tag = Self::parse_flv_tag(&mut reader, at_tag_boundary);
                 if let Err(e) = tag {
                     // `is_lookahead_tag` gets set once we start reading tags
                     // after the end & won't ever be set back. We don't want
@@ -1189,6 +1894,7 @@ impl<'gc> NetStream<'gc> {
                 }
 
                 let tag = tag.expect("valid tag");
+                at_tag_boundary = false;
                 is_lookahead_tag = tag.timestamp as f64 >= max_time; //FLV timestamps are also ms
                 if is_lookahead_tag && max_lookahead_audio_tags == 0 {
                     break;
@@ -1233,6 +1939,94 @@ impl<'gc> NetStream<'gc> {
                         .expect("FLV reader stream position")
                         as usize;
                     write.preload_offset = max(write.offset, write.preload_offset);
+                    write.at_tag_boundary = false;
+                }
+            }
+        } else if let Some(NetStreamType::Mp4 {
+            movie,
+            video_track,
+            audio_track,
+            next_video_sample,
+            next_audio_sample,
+            ..
+        }) = &write.stream_type
+        {
+            let movie = movie.clone();
+            let video_track = video_track.map(|i| &movie.tracks[i]);
+            let audio_track = audio_track.map(|i| &movie.tracks[i]);
+            let mut next_video_sample = *next_video_sample;
+            let mut next_audio_sample = *next_audio_sample;
+
+            // Interleave the samples of both tracks by their decode time, as
+            // if they were FLV tags.
+            loop {
+                let video_time = video_track.and_then(|t| t.sample_time_ms(next_video_sample));
+                let audio_time = audio_track.and_then(|t| t.sample_time_ms(next_audio_sample));
+                let (is_video, time) = match (video_time, audio_time) {
+                    (Some(video_time), Some(audio_time)) if audio_time < video_time => {
+                        (false, audio_time)
+                    }
+                    (Some(video_time), _) => (true, video_time),
+                    (None, Some(audio_time)) => (false, audio_time),
+                    (None, None) => {
+                        if !is_lookahead_tag {
+                            buffer_underrun = true;
+                        }
+                        break;
+                    }
+                };
+
+                is_lookahead_tag = time >= max_time;
+                if is_lookahead_tag && max_lookahead_audio_tags == 0 {
+                    break;
+                }
+
+                let (track, sample_index) = if is_video {
+                    (video_track.expect("video track"), next_video_sample)
+                } else {
+                    (audio_track.expect("audio track"), next_audio_sample)
+                };
+                let sample = &track.samples[sample_index];
+                let data = usize::try_from(sample.offset)
+                    .ok()
+                    .and_then(|start| Some(start..start.checked_add(sample.size as usize)?))
+                    .and_then(|range| buffer.get(range));
+                let Some(data) = data else {
+                    // The sample hasn't been downloaded yet.
+                    if !is_lookahead_tag {
+                        buffer_underrun = true;
+                    }
+                    break;
+                };
+
+                if is_video {
+                    if !is_lookahead_tag {
+                        self.mp4_video_sample(context, &mut write, data, sample_index);
+                    }
+                    next_video_sample += 1;
+                } else {
+                    if is_lookahead_tag {
+                        max_lookahead_audio_tags -= 1;
+                    }
+
+                    let data = slice.to_subslice(data);
+                    if let Err(e) = self.mp4_audio_sample(&mut write, data, track) {
+                        //TODO: Fire an error event at AS.
+                        tracing::error!("Error committing sound stream: {}", e);
+                    }
+                    next_audio_sample += 1;
+                }
+
+                if !is_lookahead_tag {
+                    if let Some(NetStreamType::Mp4 {
+                        next_video_sample: committed_video_sample,
+                        next_audio_sample: committed_audio_sample,
+                        ..
+                    }) = &mut write.stream_type
+                    {
+                        *committed_video_sample = next_video_sample;
+                        *committed_audio_sample = next_audio_sample;
+                    }
                 }
             }
         }
@@ -1244,7 +2038,13 @@ impl<'gc> NetStream<'gc> {
         }
         drop(write);
 
-        if buffer_underrun {
+        // Appended data runs out all the time, so only report it once.
+        let already_empty = buffer_underrun && {
+            let mut write = self.0.write(context.gc_context);
+            write.data_generation_mode && std::mem::replace(&mut write.buffer_empty, true)
+        };
+
+        if buffer_underrun && !already_empty {
             let is_end_of_video = self.0.read().expected_length.is_none();
 
             self.trigger_status_event(
@@ -0,0 +1,982 @@
+//! Background workers, and the state they share with each other.
+//!
+//! Every worker is a separate [`Player`] with its own GC arena, so workers
+//! can only communicate through the [`WorkerRegistry`], which lives outside
+//! of every arena. Background workers are ticked one after the other by the
+//! primordial worker, on the same thread.
+//!
+//! Since workers can't run while another one waits, a worker that has to wait
+//! for another one is parked instead: the script that was running is stopped,
+//! and the worker doesn't run until the wait is over.
+
+use crate::avm2::object::{EventObject, WeakObject};
+use crate::avm2::{Avm2, Error as Avm2Error, Object as Avm2Object};
+use crate::backend::log::LogBackend;
+use crate::context::UpdateContext;
+use crate::player::Player;
+use crate::tag_utils::SwfMovie;
+use flash_lso::types::Value as AmfValue;
+use gc_arena::{Collect, Mutation};
+use std::cell::{RefCell, RefMut};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+/// Identifies a worker across all workers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WorkerId(u32);
+
+impl WorkerId {
+    /// The worker that runs the movie the player was started with.
+    pub const PRIMORDIAL: Self = Self(0);
+
+    pub fn from_raw(id: u32) -> Self {
+        Self(id)
+    }
+
+    pub fn raw(self) -> u32 {
+        self.0
+    }
+
+    pub fn is_primordial(self) -> bool {
+        self == Self::PRIMORDIAL
+    }
+}
+
+/// The lifecycle of a worker, as exposed by `Worker.state`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WorkerStatus {
+    New,
+    Running,
+    Terminated,
+}
+
+impl WorkerStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WorkerStatus::New => "new",
+            WorkerStatus::Running => "running",
+            WorkerStatus::Terminated => "terminated",
+        }
+    }
+}
+
+/// The lifecycle of a message channel, as exposed by `MessageChannel.state`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChannelStatus {
+    Open,
+    Closing,
+    Closed,
+}
+
+impl ChannelStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ChannelStatus::Open => "open",
+            ChannelStatus::Closing => "closing",
+            ChannelStatus::Closed => "closed",
+        }
+    }
+}
+
+/// An object that is shared between workers by reference, instead of being
+/// copied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Collect)]
+#[collect(require_static)]
+pub enum SharedHandle {
+    Worker(WorkerId),
+    MessageChannel(usize),
+    Mutex(usize),
+    Condition(usize),
+    ByteArray(usize),
+}
+
+/// A value that has been passed from one worker to another.
+#[derive(Clone, Debug)]
+pub enum SharedValue {
+    /// A value that is copied into the receiving worker with AMF3.
+    Copied(AmfValue),
+
+    /// An object that refers to the same shared object in every worker.
+    Shared(SharedHandle),
+}
+
+struct WorkerEntry {
+    status: WorkerStatus,
+
+    /// The movie that the worker runs, until the worker is started.
+    movie: Option<SwfMovie>,
+
+    /// The player running the worker, once it's started.
+    ///
+    /// The primordial worker doesn't have one, as it's the player that owns
+    /// every other worker.
+    player: Option<Arc<Mutex<Player>>>,
+
+    shared_properties: HashMap<String, SharedValue>,
+}
+
+struct ChannelEntry {
+    sender: WorkerId,
+    receiver: WorkerId,
+    status: ChannelStatus,
+    messages: VecDeque<SharedValue>,
+
+    /// The number of messages ever sent on this channel, used to tell
+    /// receivers how many `channelMessage` events to dispatch.
+    sent: u64,
+}
+
+struct MutexEntry {
+    owner: Option<WorkerId>,
+    depth: u32,
+}
+
+struct ConditionEntry {
+    mutex: usize,
+
+    /// The workers waiting to be notified, in the order they started waiting.
+    waiting: VecDeque<WorkerId>,
+
+    /// The workers that have been notified, but haven't woken up yet.
+    notified: Vec<WorkerId>,
+}
+
+/// What a parked worker is waiting for.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Wait {
+    /// Waiting to own a mutex that another worker owns, having locked it
+    /// `depth` times.
+    Lock { mutex: usize, depth: u32 },
+
+    /// Waiting to be notified through a condition, or for `timeout`
+    /// milliseconds to pass. The mutex of the condition is locked `depth`
+    /// times again once this is over.
+    Notify {
+        condition: usize,
+        depth: u32,
+        timeout: Option<f64>,
+    },
+
+    /// Waiting for a message on a channel, or for the channel to be closed.
+    Receive { channel: usize },
+}
+
+/// The state shared by all workers of a player.
+///
+/// Shared objects are removed by [`WorkerRegistry::collect`] once no worker
+/// can reach them anymore, so their IDs are never reused.
+pub struct WorkerRegistry {
+    workers: BTreeMap<WorkerId, WorkerEntry>,
+    next_worker_id: u32,
+    channels: BTreeMap<usize, ChannelEntry>,
+    mutexes: BTreeMap<usize, MutexEntry>,
+    conditions: BTreeMap<usize, ConditionEntry>,
+    byte_arrays: BTreeMap<usize, Arc<Mutex<Vec<u8>>>>,
+
+    /// The workers that are waiting for another worker.
+    parked: BTreeMap<WorkerId, Wait>,
+
+    /// The ID of the next channel, mutex, condition or byte array.
+    next_object_id: usize,
+
+    /// Traces from background workers, waiting to be logged by the
+    /// primordial worker.
+    traces: Vec<String>,
+}
+
+impl WorkerRegistry {
+    pub fn new() -> Self {
+        let mut workers = BTreeMap::new();
+        workers.insert(
+            WorkerId::PRIMORDIAL,
+            WorkerEntry {
+                status: WorkerStatus::Running,
+                movie: None,
+                player: None,
+                shared_properties: HashMap::new(),
+            },
+        );
+
+        Self {
+            workers,
+            next_worker_id: 1,
+            channels: BTreeMap::new(),
+            mutexes: BTreeMap::new(),
+            conditions: BTreeMap::new(),
+            byte_arrays: BTreeMap::new(),
+            parked: BTreeMap::new(),
+            next_object_id: 0,
+            traces: Vec::new(),
+        }
+    }
+
+    /// Creates a new worker for a movie. A worker for an invalid movie is
+    /// created anyway, but terminates as soon as it's started.
+    pub fn create_worker(&mut self, movie: Option<SwfMovie>) -> WorkerId {
+        let id = WorkerId(self.next_worker_id);
+        self.next_worker_id += 1;
+        self.workers.insert(
+            id,
+            WorkerEntry {
+                status: WorkerStatus::New,
+                movie,
+                player: None,
+                shared_properties: HashMap::new(),
+            },
+        );
+        id
+    }
+
+    pub fn worker_status(&self, id: WorkerId) -> WorkerStatus {
+        self.workers
+            .get(&id)
+            .map_or(WorkerStatus::Terminated, |worker| worker.status)
+    }
+
+    /// The workers that haven't been terminated, as listed by
+    /// `WorkerDomain.listWorkers`.
+    pub fn live_workers(&self) -> Vec<WorkerId> {
+        self.workers
+            .iter()
+            .filter(|(_, worker)| worker.status != WorkerStatus::Terminated)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// Marks a new worker as running. Its player is created by the primordial
+    /// worker before the next time background workers are ticked.
+    pub fn start_worker(&mut self, id: WorkerId) -> bool {
+        let Some(worker) = self.workers.get_mut(&id) else {
+            return false;
+        };
+        if worker.status != WorkerStatus::New {
+            return false;
+        }
+
+        worker.status = if worker.movie.is_some() {
+            WorkerStatus::Running
+        } else {
+            WorkerStatus::Terminated
+        };
+        true
+    }
+
+    /// Terminates a background worker. Returns `false` if it wasn't running.
+    pub fn terminate_worker(&mut self, id: WorkerId) -> bool {
+        let Some(worker) = self.workers.get_mut(&id) else {
+            return false;
+        };
+        if id.is_primordial() || worker.status == WorkerStatus::Terminated {
+            return false;
+        }
+
+        worker.status = WorkerStatus::Terminated;
+        worker.movie = None;
+        worker.player = None;
+        worker.shared_properties.clear();
+        for channel in self.channels.values_mut() {
+            if channel.sender == id || channel.receiver == id {
+                channel.status = ChannelStatus::Closed;
+                channel.messages.clear();
+            }
+        }
+        for mutex in self.mutexes.values_mut() {
+            if mutex.owner == Some(id) {
+                mutex.owner = None;
+                mutex.depth = 0;
+            }
+        }
+        for condition in self.conditions.values_mut() {
+            condition.waiting.retain(|waiting| *waiting != id);
+            condition.notified.retain(|notified| *notified != id);
+        }
+        self.parked.remove(&id);
+        true
+    }
+
+    /// Terminates every background worker, as done when the player is dropped.
+    pub fn terminate_all(&mut self) {
+        let ids: Vec<WorkerId> = self.workers.keys().copied().collect();
+        for id in ids {
+            self.terminate_worker(id);
+        }
+    }
+
+    /// Takes the movies of workers that have been started, but don't have a
+    /// player yet.
+    pub fn take_pending_movies(&mut self) -> Vec<(WorkerId, SwfMovie)> {
+        self.workers
+            .iter_mut()
+            .filter(|(_, worker)| worker.status == WorkerStatus::Running && worker.player.is_none())
+            .filter_map(|(id, worker)| Some((*id, worker.movie.take()?)))
+            .collect()
+    }
+
+    pub fn set_player(&mut self, id: WorkerId, player: Arc<Mutex<Player>>) {
+        if let Some(worker) = self.workers.get_mut(&id) {
+            worker.player = Some(player);
+        }
+    }
+
+    /// The players of every running background worker.
+    pub fn players(&self) -> Vec<(WorkerId, Arc<Mutex<Player>>)> {
+        self.workers
+            .iter()
+            .filter_map(|(id, worker)| Some((*id, worker.player.clone()?)))
+            .collect()
+    }
+
+    pub fn set_shared_property(&mut self, id: WorkerId, key: String, value: SharedValue) {
+        if let Some(worker) = self.workers.get_mut(&id) {
+            worker.shared_properties.insert(key, value);
+        }
+    }
+
+    pub fn shared_property(&self, id: WorkerId, key: &str) -> Option<&SharedValue> {
+        self.workers.get(&id)?.shared_properties.get(key)
+    }
+
+    fn next_object_id(&mut self) -> usize {
+        let id = self.next_object_id;
+        self.next_object_id += 1;
+        id
+    }
+
+    pub fn create_channel(&mut self, sender: WorkerId, receiver: WorkerId) -> usize {
+        let id = self.next_object_id();
+        self.channels.insert(
+            id,
+            ChannelEntry {
+                sender,
+                receiver,
+                status: ChannelStatus::Open,
+                messages: VecDeque::new(),
+                sent: 0,
+            },
+        );
+        id
+    }
+
+    pub fn channel_status(&self, id: usize) -> ChannelStatus {
+        self.channels[&id].status
+    }
+
+    pub fn channel_receiver(&self, id: usize) -> WorkerId {
+        self.channels[&id].receiver
+    }
+
+    pub fn channel_sender(&self, id: usize) -> WorkerId {
+        self.channels[&id].sender
+    }
+
+    pub fn message_available(&self, id: usize) -> bool {
+        !self.channels[&id].messages.is_empty()
+    }
+
+    /// Queues a message on a channel. Returns `false` if the channel isn't
+    /// open, or `queue_limit` messages are already waiting.
+    pub fn send_message(&mut self, id: usize, message: SharedValue, queue_limit: i32) -> bool {
+        let channel = self.channels.get_mut(&id).expect("channel should exist");
+        if channel.status != ChannelStatus::Open {
+            return false;
+        }
+        if queue_limit >= 0 && channel.messages.len() >= queue_limit as usize {
+            return false;
+        }
+
+        channel.messages.push_back(message);
+        channel.sent += 1;
+        true
+    }
+
+    pub fn receive_message(&mut self, id: usize) -> Option<SharedValue> {
+        let channel = self.channels.get_mut(&id).expect("channel should exist");
+        let message = channel.messages.pop_front();
+        if channel.status == ChannelStatus::Closing && channel.messages.is_empty() {
+            channel.status = ChannelStatus::Closed;
+        }
+        message
+    }
+
+    /// Closes a channel. Messages that have already been sent can still be
+    /// received, until the queue is empty.
+    pub fn close_channel(&mut self, id: usize) {
+        let channel = self.channels.get_mut(&id).expect("channel should exist");
+        if channel.status == ChannelStatus::Open {
+            channel.status = if channel.messages.is_empty() {
+                ChannelStatus::Closed
+            } else {
+                ChannelStatus::Closing
+            };
+        }
+    }
+
+    pub fn create_mutex(&mut self) -> usize {
+        let id = self.next_object_id();
+        self.mutexes.insert(
+            id,
+            MutexEntry {
+                owner: None,
+                depth: 0,
+            },
+        );
+        id
+    }
+
+    fn mutex_mut(&mut self, id: usize) -> &mut MutexEntry {
+        self.mutexes.get_mut(&id).expect("mutex should exist")
+    }
+
+    /// Acquires a mutex for a worker, which may already own it. Returns
+    /// `false` if another worker owns the mutex.
+    pub fn lock_mutex(&mut self, id: usize, worker: WorkerId) -> bool {
+        let mutex = self.mutex_mut(id);
+        match mutex.owner {
+            Some(owner) if owner != worker => false,
+            _ => {
+                mutex.owner = Some(worker);
+                mutex.depth += 1;
+                true
+            }
+        }
+    }
+
+    /// Releases a mutex once. Returns `false` if the worker doesn't own it.
+    pub fn unlock_mutex(&mut self, id: usize, worker: WorkerId) -> bool {
+        let mutex = self.mutex_mut(id);
+        if mutex.owner != Some(worker) {
+            return false;
+        }
+
+        mutex.depth -= 1;
+        if mutex.depth == 0 {
+            mutex.owner = None;
+        }
+        true
+    }
+
+    pub fn mutex_owner(&self, id: usize) -> Option<WorkerId> {
+        self.mutexes[&id].owner
+    }
+
+    pub fn create_condition(&mut self, mutex: usize) -> usize {
+        let id = self.next_object_id();
+        self.conditions.insert(
+            id,
+            ConditionEntry {
+                mutex,
+                waiting: VecDeque::new(),
+                notified: Vec::new(),
+            },
+        );
+        id
+    }
+
+    fn condition_mut(&mut self, id: usize) -> &mut ConditionEntry {
+        self.conditions
+            .get_mut(&id)
+            .expect("condition should exist")
+    }
+
+    pub fn condition_mutex(&self, id: usize) -> usize {
+        self.conditions[&id].mutex
+    }
+
+    /// Releases the mutex of a condition entirely, and starts waiting to be
+    /// notified. Returns how many times the worker had locked the mutex.
+    pub fn start_waiting(&mut self, id: usize, worker: WorkerId) -> u32 {
+        let mutex = self.condition_mutex(id);
+        self.condition_mut(id).waiting.push_back(worker);
+
+        let mutex = self.mutex_mut(mutex);
+        let depth = mutex.depth;
+        mutex.owner = None;
+        mutex.depth = 0;
+        depth
+    }
+
+    /// Stops waiting on a condition, whether the worker was notified or not.
+    fn stop_waiting(&mut self, id: usize, worker: WorkerId) {
+        let condition = self.condition_mut(id);
+        condition.waiting.retain(|waiting| *waiting != worker);
+        condition.notified.retain(|notified| *notified != worker);
+    }
+
+    /// Wakes up the worker that has been waiting on a condition the longest,
+    /// or every waiting worker.
+    pub fn notify_condition(&mut self, id: usize, all: bool) {
+        let condition = self.condition_mut(id);
+        let count = if all { condition.waiting.len() } else { 1 };
+        for _ in 0..count {
+            if let Some(worker) = condition.waiting.pop_front() {
+                condition.notified.push(worker);
+            }
+        }
+    }
+
+    /// Acquires a mutex that isn't owned by any worker, as many times as a
+    /// waiting worker had locked it. Returns `false` if another worker owns
+    /// it.
+    pub fn relock_mutex(&mut self, id: usize, worker: WorkerId, depth: u32) -> bool {
+        let mutex = self.mutex_mut(id);
+        if mutex.owner.is_some() {
+            return false;
+        }
+
+        mutex.owner = Some(worker);
+        mutex.depth = depth;
+        true
+    }
+
+    pub fn create_byte_array(&mut self, bytes: Arc<Mutex<Vec<u8>>>) -> usize {
+        let id = self.next_object_id();
+        self.byte_arrays.insert(id, bytes);
+        id
+    }
+
+    pub fn byte_array(&self, id: usize) -> Arc<Mutex<Vec<u8>>> {
+        self.byte_arrays[&id].clone()
+    }
+
+    pub fn take_traces(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.traces)
+    }
+
+    /// Parks a worker until `wait` is over. It shouldn't run again until
+    /// [`Self::resume`] returns `true`.
+    pub fn park(&mut self, worker: WorkerId, wait: Wait) {
+        self.parked.insert(worker, wait);
+    }
+
+    pub fn is_parked(&self, worker: WorkerId) -> bool {
+        self.parked.contains_key(&worker)
+    }
+
+    /// Finishes the wait of a parked worker if it's over, as `dt`
+    /// milliseconds have passed since the last time. Returns `true` if the
+    /// worker can run.
+    pub fn resume(&mut self, worker: WorkerId, dt: f64) -> bool {
+        let Some(mut wait) = self.parked.get(&worker).copied() else {
+            return true;
+        };
+
+        if let Wait::Notify {
+            condition,
+            depth,
+            timeout,
+        } = wait
+        {
+            let timeout = timeout.map(|timeout| timeout - dt);
+            let notified = self.conditions[&condition].notified.contains(&worker);
+            wait = if notified || timeout.is_some_and(|timeout| timeout <= 0.0) {
+                self.stop_waiting(condition, worker);
+                Wait::Lock {
+                    mutex: self.condition_mutex(condition),
+                    depth,
+                }
+            } else {
+                Wait::Notify {
+                    condition,
+                    depth,
+                    timeout,
+                }
+            };
+        }
+
+        let resumed = match wait {
+            Wait::Lock { mutex, depth } => self.relock_mutex(mutex, worker, depth),
+            Wait::Notify { .. } => false,
+            Wait::Receive { channel } => {
+                self.message_available(channel)
+                    || self.channel_status(channel) == ChannelStatus::Closed
+            }
+        };
+        if resumed {
+            self.parked.remove(&worker);
+        } else {
+            self.parked.insert(worker, wait);
+        }
+        resumed
+    }
+
+    /// Removes the shared objects that can't be reached anymore, and the
+    /// workers that aren't running and that nothing refers to.
+    ///
+    /// `reachable` holds the shared objects that any worker has an object
+    /// for. Objects that are waiting in a channel, or are stored as a shared
+    /// property, are reachable too.
+    pub fn collect(&mut self, mut reachable: BTreeSet<SharedHandle>) {
+        let values = self
+            .workers
+            .values()
+            .flat_map(|worker| worker.shared_properties.values())
+            .chain(
+                self.channels
+                    .values()
+                    .flat_map(|channel| channel.messages.iter()),
+            );
+        for value in values {
+            if let SharedValue::Shared(handle) = value {
+                reachable.insert(*handle);
+            }
+        }
+
+        // The script of a parked worker has been stopped, so nothing else may
+        // refer to what it waits for.
+        for wait in self.parked.values() {
+            reachable.insert(match *wait {
+                Wait::Lock { mutex, .. } => SharedHandle::Mutex(mutex),
+                Wait::Notify { condition, .. } => SharedHandle::Condition(condition),
+                Wait::Receive { channel } => SharedHandle::MessageChannel(channel),
+            });
+        }
+
+        // A condition keeps its mutex alive, and a channel keeps its workers
+        // alive.
+        let mut implied = Vec::new();
+        for handle in &reachable {
+            match *handle {
+                SharedHandle::Condition(id) => {
+                    implied.push(SharedHandle::Mutex(self.conditions[&id].mutex));
+                }
+                SharedHandle::MessageChannel(id) => {
+                    let channel = &self.channels[&id];
+                    implied.push(SharedHandle::Worker(channel.sender));
+                    implied.push(SharedHandle::Worker(channel.receiver));
+                }
+                _ => {}
+            }
+        }
+        reachable.extend(implied);
+
+        self.workers.retain(|id, worker| {
+            worker.status == WorkerStatus::Running || reachable.contains(&SharedHandle::Worker(*id))
+        });
+        self.channels
+            .retain(|id, _| reachable.contains(&SharedHandle::MessageChannel(*id)));
+        self.mutexes
+            .retain(|id, _| reachable.contains(&SharedHandle::Mutex(*id)));
+        self.conditions
+            .retain(|id, _| reachable.contains(&SharedHandle::Condition(*id)));
+        self.byte_arrays
+            .retain(|id, _| reachable.contains(&SharedHandle::ByteArray(*id)));
+    }
+}
+
+/// Terminates every background worker when dropped.
+///
+/// Background workers hold on to the worker registry, so the primordial
+/// worker's player has to drop them explicitly to avoid a reference cycle.
+pub struct WorkerRegistryOwner(Rc<RefCell<WorkerRegistry>>);
+
+impl WorkerRegistryOwner {
+    pub fn new(registry: Rc<RefCell<WorkerRegistry>>) -> Self {
+        Self(registry)
+    }
+}
+
+impl Drop for WorkerRegistryOwner {
+    fn drop(&mut self) {
+        self.0.borrow_mut().terminate_all();
+    }
+}
+
+impl Default for WorkerRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The objects in one worker that represent shared objects.
+#[derive(Collect)]
+#[collect(no_drop)]
+struct LocalObject<'gc> {
+    /// The object is only held weakly, so that it can be collected, and then
+    /// replaced by a new object if the shared object is seen again.
+    object: WeakObject<'gc>,
+
+    /// The last status of a worker or channel that this worker was told about.
+    #[collect(require_static)]
+    seen_status: &'static str,
+
+    /// The number of channel messages that this worker was told about.
+    #[collect(require_static)]
+    seen_count: u64,
+}
+
+/// The worker that a player runs, and its view of the objects shared with
+/// other workers.
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct Workers<'gc> {
+    #[collect(require_static)]
+    current: WorkerId,
+
+    #[collect(require_static)]
+    registry: Rc<RefCell<WorkerRegistry>>,
+
+    objects: BTreeMap<SharedHandle, LocalObject<'gc>>,
+}
+
+impl<'gc> Workers<'gc> {
+    pub fn new(current: WorkerId, registry: Rc<RefCell<WorkerRegistry>>) -> Self {
+        Self {
+            current,
+            registry,
+            objects: BTreeMap::new(),
+        }
+    }
+
+    /// The worker that this player runs.
+    pub fn current(&self) -> WorkerId {
+        self.current
+    }
+
+    pub fn registry(&self) -> RefMut<'_, WorkerRegistry> {
+        self.registry.borrow_mut()
+    }
+
+    pub fn registry_rc(&self) -> Rc<RefCell<WorkerRegistry>> {
+        self.registry.clone()
+    }
+
+    /// The object representing a shared object in this worker, if there is one.
+    pub fn object(&self, handle: SharedHandle, mc: &Mutation<'gc>) -> Option<Avm2Object<'gc>> {
+        self.objects.get(&handle)?.object.upgrade(mc)
+    }
+
+    /// Finds the handle of an object that's already shared.
+    pub fn handle(&self, object: Avm2Object<'gc>, mc: &Mutation<'gc>) -> Option<SharedHandle> {
+        self.objects
+            .iter()
+            .find(|(_, local)| {
+                local
+                    .object
+                    .upgrade(mc)
+                    .is_some_and(|local| Avm2Object::ptr_eq(local, object))
+            })
+            .map(|(handle, _)| *handle)
+    }
+
+    /// The shared objects that this worker still has an object for.
+    pub fn handles(&self, mc: &Mutation<'gc>) -> BTreeSet<SharedHandle> {
+        self.objects
+            .iter()
+            .filter(|(_, local)| local.object.upgrade(mc).is_some())
+            .map(|(handle, _)| *handle)
+            .collect()
+    }
+
+    /// Makes an object represent a shared object in this worker.
+    ///
+    /// Events are only dispatched for changes that happen after this.
+    pub fn insert_object(&mut self, handle: SharedHandle, object: Avm2Object<'gc>) {
+        let registry = self.registry.borrow();
+        let (seen_status, seen_count) = match handle {
+            SharedHandle::Worker(id) => (registry.worker_status(id).as_str(), 0),
+            SharedHandle::MessageChannel(id) => {
+                let channel = &registry.channels[&id];
+                (channel.status.as_str(), channel.sent)
+            }
+            SharedHandle::Mutex(_) | SharedHandle::Condition(_) | SharedHandle::ByteArray(_) => {
+                ("", 0)
+            }
+        };
+        drop(registry);
+
+        self.objects.insert(
+            handle,
+            LocalObject {
+                object: object.downgrade(),
+                seen_status,
+                seen_count,
+            },
+        );
+    }
+
+    /// Dispatches events for changes that other workers made to shared
+    /// workers and channels, and forgets about objects that have been
+    /// collected.
+    pub fn dispatch_events(context: &mut UpdateContext<'gc>) {
+        let mc = context.gc();
+        let mut events = Vec::new();
+        {
+            let workers = &mut *context.workers;
+            let registry = workers.registry.borrow();
+            workers.objects.retain(|handle, local| {
+                let Some(object) = local.object.upgrade(mc) else {
+                    return false;
+                };
+                match *handle {
+                    SharedHandle::Worker(id) => {
+                        let status = registry.worker_status(id).as_str();
+                        if status != local.seen_status {
+                            local.seen_status = status;
+                            events.push((object, "workerState"));
+                        }
+                    }
+                    SharedHandle::MessageChannel(id) => {
+                        let channel = &registry.channels[&id];
+                        if channel.receiver == workers.current {
+                            for _ in local.seen_count..channel.sent {
+                                events.push((object, "channelMessage"));
+                            }
+                        }
+                        local.seen_count = channel.sent;
+
+                        let status = channel.status.as_str();
+                        if status != local.seen_status {
+                            local.seen_status = status;
+                            events.push((object, "channelState"));
+                        }
+                    }
+                    _ => {}
+                }
+                true
+            });
+        }
+
+        for (object, event_type) in events {
+            let event = EventObject::bare_default_event(context, event_type);
+            Avm2::dispatch_event(context, event, object);
+        }
+    }
+
+    /// Parks the current worker until `wait` is over.
+    ///
+    /// Workers run one after another on the same thread, so a worker can't
+    /// wait inside of a native method for another worker to run. Instead,
+    /// the returned error stops the script that's running, and the worker
+    /// doesn't run any more frames until the next time workers are updated
+    /// after the wait is over.
+    pub fn park(&self, wait: Wait) -> Avm2Error<'gc> {
+        self.registry().park(self.current, wait);
+        "The worker is waiting for another worker, so its script has been stopped.".into()
+    }
+}
+
+/// Collects the traces of a background worker, so that the primordial worker
+/// can log them.
+pub struct WorkerLogBackend {
+    registry: Rc<RefCell<WorkerRegistry>>,
+}
+
+impl WorkerLogBackend {
+    pub fn new(registry: Rc<RefCell<WorkerRegistry>>) -> Self {
+        Self { registry }
+    }
+}
+
+impl LogBackend for WorkerLogBackend {
+    fn avm_trace(&self, message: &str) {
+        self.registry.borrow_mut().traces.push(message.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collect_unreachable_objects() {
+        let mut registry = WorkerRegistry::new();
+        let worker = registry.create_worker(None);
+        let channel = registry.create_channel(WorkerId::PRIMORDIAL, worker);
+        let mutex = registry.create_mutex();
+        let condition = registry.create_condition(mutex);
+        let bytes = registry.create_byte_array(Arc::new(Mutex::new(vec![1, 2, 3])));
+        let unused = registry.create_byte_array(Arc::default());
+        registry.send_message(
+            channel,
+            SharedValue::Shared(SharedHandle::ByteArray(bytes)),
+            -1,
+        );
+
+        registry.collect(BTreeSet::from([
+            SharedHandle::MessageChannel(channel),
+            SharedHandle::Condition(condition),
+        ]));
+        assert_eq!(registry.condition_mutex(condition), mutex);
+        assert_eq!(registry.mutex_owner(mutex), None);
+        assert_eq!(*registry.byte_array(bytes).lock().unwrap(), [1, 2, 3]);
+        assert!(!registry.byte_arrays.contains_key(&unused));
+        assert_eq!(registry.live_workers(), [WorkerId::PRIMORDIAL, worker]);
+
+        // Once the message is received, nothing refers to the byte array.
+        registry.receive_message(channel);
+        registry.collect(BTreeSet::new());
+        assert!(registry.channels.is_empty());
+        assert!(registry.mutexes.is_empty());
+        assert!(registry.conditions.is_empty());
+        assert!(registry.byte_arrays.is_empty());
+        assert_eq!(registry.live_workers(), [WorkerId::PRIMORDIAL]);
+    }
+
+    #[test]
+    fn parked_worker_relocks_mutex_once_notified() {
+        let mut registry = WorkerRegistry::new();
+        let worker = registry.create_worker(None);
+        let mutex = registry.create_mutex();
+        let condition = registry.create_condition(mutex);
+
+        assert!(registry.lock_mutex(mutex, WorkerId::PRIMORDIAL));
+        assert!(registry.lock_mutex(mutex, WorkerId::PRIMORDIAL));
+        let depth = registry.start_waiting(condition, WorkerId::PRIMORDIAL);
+        assert_eq!(depth, 2);
+        assert_eq!(registry.mutex_owner(mutex), None);
+        registry.park(
+            WorkerId::PRIMORDIAL,
+            Wait::Notify {
+                condition,
+                depth,
+                timeout: None,
+            },
+        );
+        assert!(!registry.resume(WorkerId::PRIMORDIAL, 1000.0));
+
+        assert!(registry.lock_mutex(mutex, worker));
+        registry.notify_condition(condition, false);
+        assert!(!registry.resume(WorkerId::PRIMORDIAL, 1000.0));
+        assert!(registry.is_parked(WorkerId::PRIMORDIAL));
+        assert!(registry.unlock_mutex(mutex, worker));
+
+        assert!(registry.resume(WorkerId::PRIMORDIAL, 1000.0));
+        assert!(!registry.is_parked(WorkerId::PRIMORDIAL));
+        assert!(registry.unlock_mutex(mutex, WorkerId::PRIMORDIAL));
+        assert_eq!(registry.mutex_owner(mutex), Some(WorkerId::PRIMORDIAL));
+    }
+
+    #[test]
+    fn parked_worker_keeps_what_it_waits_for() {
+        let mut registry = WorkerRegistry::new();
+        let worker = registry.create_worker(None);
+        let mutex = registry.create_mutex();
+        let condition = registry.create_condition(mutex);
+        let channel = registry.create_channel(WorkerId::PRIMORDIAL, worker);
+
+        assert!(registry.lock_mutex(mutex, worker));
+        let depth = registry.start_waiting(condition, worker);
+        registry.park(
+            worker,
+            Wait::Notify {
+                condition,
+                depth,
+                timeout: Some(20.0),
+            },
+        );
+        registry.park(WorkerId::PRIMORDIAL, Wait::Receive { channel });
+        registry.collect(BTreeSet::new());
+        assert_eq!(registry.condition_mutex(condition), mutex);
+        assert!(registry.channels.contains_key(&channel));
+
+        // The wait times out even though the condition is never notified.
+        assert!(!registry.resume(worker, 16.0));
+        assert!(registry.resume(worker, 16.0));
+        assert_eq!(registry.mutex_owner(mutex), Some(worker));
+
+        assert!(!registry.resume(WorkerId::PRIMORDIAL, 16.0));
+        registry.send_message(channel, SharedValue::Copied(AmfValue::Null), -1);
+        assert!(registry.resume(WorkerId::PRIMORDIAL, 16.0));
+    }
+}
@@ -3,8 +3,9 @@ use crate::gui::{GuiController, MENU_HEIGHT};
 use crate::player::{LaunchOptions, PlayerController};
 use crate::preferences::GlobalPreferences;
 use crate::util::{
-    get_screen_size, gilrs_button_to_gamepad_button, parse_url, plot_stats_in_tracy,
-    winit_to_ruffle_key_code, winit_to_ruffle_text_control,
+    get_screen_size, gilrs_axis_to_gamepad_axis, gilrs_button_to_gamepad_axis,
+    gilrs_button_to_gamepad_button, parse_url, plot_stats_in_tracy, winit_to_ruffle_key_code,
+    winit_to_ruffle_text_control,
 };
 use anyhow::Error;
 use gilrs::{Event, EventType, Gilrs};
@@ -341,23 +342,57 @@ impl MainWindow {
     }
 
     fn about_to_wait(&mut self, gilrs: Option<&mut Gilrs>) {
-        if let Some(Event { event, .. }) = gilrs.and_then(|gilrs| gilrs.next_event()) {
-            match event {
-                EventType::ButtonPressed(button, _) => {
-                    if let Some(button) = gilrs_button_to_gamepad_button(button) {
+        if let Some(gilrs) = gilrs {
+            while let Some(Event { id, event, .. }) = gilrs.next_event() {
+                let gamepad = usize::from(id);
+                match event {
+                    EventType::ButtonPressed(button, _) => {
+                        if let Some(button) = gilrs_button_to_gamepad_button(button) {
+                            self.player
+                                .handle_event(PlayerEvent::GamepadButtonDown { gamepad, button });
+                            self.check_redraw();
+                        }
+                    }
+                    EventType::ButtonReleased(button, _) => {
+                        if let Some(button) = gilrs_button_to_gamepad_button(button) {
+                            self.player
+                                .handle_event(PlayerEvent::GamepadButtonUp { gamepad, button });
+                            self.check_redraw();
+                        }
+                    }
+                    EventType::ButtonChanged(button, value, _) => {
+                        // The analog triggers are reported as buttons.
+                        if let Some(axis) = gilrs_button_to_gamepad_axis(button) {
+                            self.player.handle_event(PlayerEvent::GamepadAxisChanged {
+                                gamepad,
+                                axis,
+                                value: value.into(),
+                            });
+                            self.check_redraw();
+                        }
+                    }
+                    EventType::AxisChanged(axis, value, _) => {
+                        if let Some(axis) = gilrs_axis_to_gamepad_axis(axis) {
+                            self.player.handle_event(PlayerEvent::GamepadAxisChanged {
+                                gamepad,
+                                axis,
+                                value: value.into(),
+                            });
+                            self.check_redraw();
+                        }
+                    }
+                    EventType::Connected => {
                         self.player
-                            .handle_event(PlayerEvent::GamepadButtonDown { button });
+                            .handle_event(PlayerEvent::GamepadConnected { gamepad });
                         self.check_redraw();
                     }
-                }
-                EventType::ButtonReleased(button, _) => {
-                    if let Some(button) = gilrs_button_to_gamepad_button(button) {
+                    EventType::Disconnected => {
                         self.player
-                            .handle_event(PlayerEvent::GamepadButtonUp { button });
+                            .handle_event(PlayerEvent::GamepadDisconnected { gamepad });
                         self.check_redraw();
                     }
+                    _ => {}
                 }
-                _ => {}
             }
         }
 
@@ -369,12 +404,10 @@ impl MainWindow {
             let dt = new_time.duration_since(self.time).as_micros();
             if dt > 0 {
                 self.time = new_time;
-                if let Some(mut player) = self.player.get() {
-                    player.tick(dt as f64 / 1000.0);
-                    self.next_frame_time = Some(new_time + player.time_til_next_frame());
-                } else {
-                    self.next_frame_time = None;
-                }
+                self.next_frame_time = self
+                    .player
+                    .tick(dt as f64 / 1000.0)
+                    .map(|time_til_next_frame| new_time + time_til_next_frame);
                 self.check_redraw();
             }
         }
@@ -465,6 +498,13 @@ impl ApplicationHandler<RuffleEvent> for App {
             )
             .expect("GUI controller should be created");
 
+            // GilRs doesn't send connection events for gamepads that were
+            // already connected when it was initialized.
+            let gamepads = self
+                .gilrs
+                .as_ref()
+                .map(|gilrs| gilrs.gamepads().map(|(id, _)| usize::from(id)).collect());
+
             let mut player = PlayerController::new(
                 event_loop_proxy.clone(),
                 window.clone(),
@@ -472,6 +512,7 @@ impl ApplicationHandler<RuffleEvent> for App {
                 font_database,
                 preferences.clone(),
                 gui.file_picker(),
+                gamepads,
             );
 
             if let Some(movie_url) = &movie_url {
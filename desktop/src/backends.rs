@@ -1,10 +1,12 @@
 mod external_interface;
 mod fscommand;
 mod navigator;
+mod print;
 mod ui;
 
 pub use external_interface::DesktopExternalInterfaceProvider;
 pub use fscommand::DesktopFSCommandProvider;
 pub use navigator::DesktopNavigatorInterface;
 pub use navigator::PathAllowList;
+pub use print::DesktopPrintBackend;
 pub use ui::DesktopUiBackend;
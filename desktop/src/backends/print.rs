@@ -0,0 +1,57 @@
+use rfd::FileDialog;
+use ruffle_core::backend::print::{PageSettings, PrintBackend, PrintPage};
+use ruffle_frontend_utils::backends::print::{write_print_job, PrintFileFormat};
+use std::path::PathBuf;
+
+/// A print backend that asks the user where to save each print job, as
+/// Ruffle doesn't print to a printer.
+pub struct DesktopPrintBackend {
+    /// The directory that the save dialog starts in.
+    directory: PathBuf,
+    format: PrintFileFormat,
+
+    /// The file chosen for the running print job.
+    path: Option<PathBuf>,
+}
+
+impl DesktopPrintBackend {
+    pub fn new(directory: PathBuf, format: PrintFileFormat) -> Self {
+        Self {
+            directory,
+            format,
+            path: None,
+        }
+    }
+}
+
+impl PrintBackend for DesktopPrintBackend {
+    fn start_job(&mut self) -> Option<PageSettings> {
+        let extension = self.format.extension();
+        let (description, file_name) = match self.format {
+            PrintFileFormat::Pdf => ("PDF document", "print.pdf"),
+            PrintFileFormat::Png => ("PNG image", "print.png"),
+        };
+
+        let mut dialog = FileDialog::new()
+            .set_title("Print to file")
+            .set_file_name(file_name)
+            .add_filter(description, &[extension]);
+        if self.directory.is_dir() {
+            dialog = dialog.set_directory(&self.directory);
+        }
+
+        // This blocks the movie, as a native print dialog would.
+        let Some(path) = dialog.save_file() else {
+            tracing::info!("The user cancelled a print job");
+            return None;
+        };
+        self.path = Some(path);
+        Some(PageSettings::default())
+    }
+
+    fn send_job(&mut self, settings: &PageSettings, pages: Vec<PrintPage>) {
+        if let Some(path) = self.path.take() {
+            write_print_job(&path, self.format, settings, &pages);
+        }
+    }
+}
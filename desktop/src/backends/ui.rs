@@ -126,6 +126,7 @@ pub struct DesktopUiBackend {
     open_url_mode: OpenURLMode,
     font_database: Rc<fontdb::Database>,
     file_picker: FilePicker,
+    gamepad_input_available: bool,
 }
 
 impl DesktopUiBackend {
@@ -136,6 +137,7 @@ impl DesktopUiBackend {
         font_database: Rc<fontdb::Database>,
         preferences: GlobalPreferences,
         file_picker: FilePicker,
+        gamepad_input_available: bool,
     ) -> Result<Self, Error> {
         // The window handle is only relevant to linux/wayland
         // If it fails it'll fallback to x11 or wlr-data-control
@@ -156,6 +158,7 @@ impl DesktopUiBackend {
             open_url_mode,
             font_database,
             file_picker,
+            gamepad_input_available,
         })
     }
 
@@ -194,6 +197,10 @@ impl UiBackend for DesktopUiBackend {
         self.clipboard.set(content);
     }
 
+    fn gamepad_input_available(&self) -> bool {
+        self.gamepad_input_available
+    }
+
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError> {
         self.window.set_fullscreen(if is_full {
             Some(Fullscreen::Borderless(None))
@@ -6,9 +6,10 @@ use ruffle_core::backend::navigator::{OpenURLMode, SocketMode};
 use ruffle_core::config::Letterbox;
 use ruffle_core::events::{GamepadButton, KeyCode};
 use ruffle_core::{LoadBehavior, PlayerRuntime, StageAlign, StageScaleMode};
+use ruffle_frontend_utils::backends::print::PrintFileFormat;
 use ruffle_render::quality::StageQuality;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::time::Duration;
 use url::Url;
@@ -20,6 +21,20 @@ fn get_default_save_directory() -> std::path::PathBuf {
         .join("SharedObjects")
 }
 
+fn get_default_print_directory() -> std::path::PathBuf {
+    dirs::data_local_dir()
+        .expect("Couldn't find a valid data_local dir")
+        .join("ruffle")
+        .join("Prints")
+}
+
+fn get_default_app_storage_directory() -> std::path::PathBuf {
+    dirs::data_local_dir()
+        .expect("Couldn't find a valid data_local dir")
+        .join("ruffle")
+        .join("AppStorage")
+}
+
 fn get_default_config_directory() -> std::path::PathBuf {
     dirs::config_local_dir()
         .expect("Couldn't find a valid config_local dir")
@@ -129,6 +144,21 @@ pub struct Opt {
     #[clap(long, default_value_os_t=get_default_save_directory())]
     pub save_directory: std::path::PathBuf,
 
+    /// Where the dialog to save a print job starts, as Ruffle doesn't print to a printer.
+    #[clap(long, default_value_os_t=get_default_print_directory())]
+    pub print_directory: std::path::PathBuf,
+
+    /// The kind of file that print jobs are saved as.
+    #[clap(long, default_value = "pdf")]
+    pub print_format: PrintFormat,
+
+    /// Location to store the files of AIR applications in.
+    ///
+    /// Each application gets its own `File.applicationStorageDirectory` inside
+    /// of this directory, and can't access anything outside of it.
+    #[clap(long, default_value_os_t=get_default_app_storage_directory())]
+    pub app_storage_directory: std::path::PathBuf,
+
     /// Location of a directory to store Ruffle configuration.
     #[clap(long, default_value_os_t=get_default_config_directory())]
     pub config: std::path::PathBuf,
@@ -243,6 +273,27 @@ pub struct Opt {
     /// (like inlining constant pool entries) can't be disabled.
     #[clap(long)]
     pub no_avm2_optimizer: bool,
+
+    /// Record all input sent to the movie into the given file, in the input format used by tests.
+    ///
+    /// While recording, the movie runs exactly one frame per tick, and the file is written when the movie is closed.
+    #[clap(long, value_name = "FILE")]
+    pub record_input: Option<PathBuf>,
+
+    /// Replay input previously recorded with --record-input from the given file.
+    ///
+    /// Input from the user is ignored until the replay has finished.
+    #[clap(long, value_name = "FILE", conflicts_with = "record_input")]
+    pub replay_input: Option<PathBuf>,
+
+    /// Connect to an ActionScript debugger listening at the given address, such as `127.0.0.1:9000`.
+    ///
+    /// The debugger can set breakpoints, step through scripts and inspect their variables.
+    /// It has to speak Ruffle's own debugger protocol, as described in `ruffle_frontend_utils`;
+    /// Flash debuggers such as fdb can't be used.
+    /// AVM1 movies can only be debugged with the SWD file published next to them.
+    #[clap(long, value_name = "ADDRESS")]
+    pub debugger: Option<String>,
 }
 
 fn parse_movie_file_or_url(path: &str) -> Result<Url, Error> {
@@ -464,6 +515,24 @@ enum NamedKeyCode {
     Apostrophe = 222,
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub enum PrintFormat {
+    /// Save each print job as a PDF document.
+    Pdf,
+
+    /// Save each printed page as a PNG image.
+    Png,
+}
+
+impl From<PrintFormat> for PrintFileFormat {
+    fn from(value: PrintFormat) -> Self {
+        match value {
+            PrintFormat::Pdf => PrintFileFormat::Pdf,
+            PrintFormat::Png => PrintFileFormat::Png,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
 pub enum FilesystemAccessMode {
     /// Always allow non-interactive access to the filesystem.
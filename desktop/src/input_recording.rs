@@ -0,0 +1,90 @@
+//! Recording and replaying of input to and from files.
+//!
+//! The player records and replays the input itself, see
+//! `ruffle_core::input_recording`. Since every tick ends a frame of input,
+//! the player is ticked exactly one frame's worth of time at a time while
+//! recording or replaying. This matches how the test framework runs tests
+//! with `num_ticks`, so a recorded session can be turned into a regression
+//! test by setting `num_ticks` to the number of recorded ticks and
+//! `tick_rate` to the frame time of the movie.
+
+use anyhow::{Context, Error};
+use ruffle_core::input_recording::InputRecording;
+use ruffle_core::Player;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// The most frames to catch up on in a single host tick, so that a long stall
+/// doesn't make us spin for a long time afterwards.
+const MAX_FRAMES_PER_TICK: u32 = 10;
+
+/// A session of input that's either being recorded to or replayed from a file.
+pub struct InputSession {
+    /// The file to write the recorded input to, if recording.
+    record_path: Option<PathBuf>,
+
+    /// Time that has passed but hasn't been given to the player yet, in milliseconds.
+    accumulator: f64,
+}
+
+impl InputSession {
+    /// Start recording input, to be written to the given path when the session finishes.
+    pub fn record(player: &mut Player, path: PathBuf) -> Self {
+        tracing::info!("Recording input to {}", path.display());
+        player.start_recording_input();
+        Self {
+            record_path: Some(path),
+            accumulator: 0.0,
+        }
+    }
+
+    /// Start replaying input from the given path.
+    pub fn replay(player: &mut Player, path: &Path) -> Result<Self, Error> {
+        let file = File::open(path)?;
+        let recording = InputRecording::from_reader(BufReader::new(file))?;
+        tracing::info!("Replaying input from {}", path.display());
+        player.replay_input(recording);
+        Ok(Self {
+            record_path: None,
+            accumulator: 0.0,
+        })
+    }
+
+    /// Let time pass, running the player one frame at a time.
+    pub fn tick(&mut self, player: &mut Player, dt: f64) {
+        let frame_time = 1000.0 / player.frame_rate();
+        self.accumulator = (self.accumulator + dt).min(frame_time * MAX_FRAMES_PER_TICK as f64);
+
+        while self.accumulator >= frame_time {
+            self.accumulator -= frame_time;
+            player.tick(frame_time);
+        }
+    }
+
+    /// Write the recorded input to its file, if recording.
+    pub fn finish(&mut self, player: &mut Player) {
+        let Some(path) = self.record_path.take() else {
+            return;
+        };
+        let Some(recording) = player.stop_recording_input() else {
+            return;
+        };
+
+        match write_recording(&recording, &path) {
+            Ok(()) => tracing::info!(
+                "Recorded {} ticks of input to {}",
+                recording.ticks(),
+                path.display()
+            ),
+            Err(e) => tracing::error!("Couldn't write input to {}: {e:#}", path.display()),
+        }
+    }
+}
+
+fn write_recording(recording: &InputRecording, path: &Path) -> Result<(), Error> {
+    let mut writer = BufWriter::new(File::create(path).context("Couldn't create file")?);
+    recording.to_writer(&mut writer)?;
+    writer.flush()?;
+    Ok(())
+}
@@ -12,6 +12,7 @@ mod cli;
 mod custom_event;
 mod dbus;
 mod gui;
+mod input_recording;
 mod log;
 mod player;
 mod preferences;
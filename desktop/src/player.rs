@@ -1,11 +1,13 @@
 use crate::backends::{
     DesktopExternalInterfaceProvider, DesktopFSCommandProvider, DesktopNavigatorInterface,
-    DesktopUiBackend,
+    DesktopPrintBackend, DesktopUiBackend,
 };
 use crate::cli::FilesystemAccessMode;
 use crate::cli::GameModePreference;
+use crate::cli::PrintFormat;
 use crate::custom_event::RuffleEvent;
 use crate::gui::{FilePicker, MovieView};
+use crate::input_recording::InputSession;
 use crate::preferences::GlobalPreferences;
 use crate::{CALLSTACK, RENDER_INFO, SWF_INFO};
 use anyhow::anyhow;
@@ -14,7 +16,9 @@ use ruffle_core::config::Letterbox;
 use ruffle_core::events::{GamepadButton, KeyCode};
 use ruffle_core::{DefaultFont, LoadBehavior, Player, PlayerBuilder, PlayerEvent};
 use ruffle_frontend_utils::backends::audio::CpalAudioBackend;
+use ruffle_frontend_utils::backends::debugger::TcpDebuggerBackend;
 use ruffle_frontend_utils::backends::executor::{AsyncExecutor, PollRequester};
+use ruffle_frontend_utils::backends::filesystem::DiskFilesystemBackend;
 use ruffle_frontend_utils::backends::navigator::ExternalNavigatorBackend;
 use ruffle_frontend_utils::bundle::source::BundleSourceError;
 use ruffle_frontend_utils::bundle::{Bundle, BundleError};
@@ -29,7 +33,7 @@ use ruffle_render_wgpu::descriptors::Descriptors;
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::Duration;
@@ -47,11 +51,17 @@ pub struct LaunchOptions {
     pub tcp_connections: Option<SocketMode>,
     pub fullscreen: bool,
     pub save_directory: PathBuf,
+    pub print_directory: PathBuf,
+    pub print_format: PrintFormat,
+    pub app_storage_directory: PathBuf,
     pub cache_directory: PathBuf,
     pub open_url_mode: OpenURLMode,
     pub filesystem_access_mode: FilesystemAccessMode,
     pub gamepad_button_mapping: HashMap<GamepadButton, KeyCode>,
     pub avm2_optimizer_enabled: bool,
+    pub record_input: Option<PathBuf>,
+    pub replay_input: Option<PathBuf>,
+    pub debugger: Option<String>,
 }
 
 impl From<&GlobalPreferences> for LaunchOptions {
@@ -96,6 +106,9 @@ impl From<&GlobalPreferences> for LaunchOptions {
             proxy: value.cli.proxy.clone(),
             fullscreen: value.cli.fullscreen,
             save_directory: value.cli.save_directory.clone(),
+            print_directory: value.cli.print_directory.clone(),
+            print_format: value.cli.print_format,
+            app_storage_directory: value.cli.app_storage_directory.clone(),
             cache_directory: value.cli.cache_directory.clone(),
             open_url_mode: value.cli.open_url_mode,
             filesystem_access_mode: value.cli.filesystem_access_mode,
@@ -103,10 +116,37 @@ impl From<&GlobalPreferences> for LaunchOptions {
             tcp_connections: value.cli.tcp_connections,
             gamepad_button_mapping: HashMap::from_iter(value.cli.gamepad_button.iter().cloned()),
             avm2_optimizer_enabled: !value.cli.no_avm2_optimizer,
+            record_input: value.cli.record_input.clone(),
+            replay_input: value.cli.replay_input.clone(),
+            debugger: value.cli.debugger.clone(),
         }
     }
 }
 
+/// The directory that an AIR application keeps its own files in.
+///
+/// Every movie gets a separate directory, named after its file name.
+fn app_storage_directory(base: &Path, movie_url: &Url) -> PathBuf {
+    let name: String = movie_url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name.is_empty() {
+        base.join("unnamed")
+    } else {
+        base.join(name)
+    }
+}
+
 #[derive(Clone)]
 struct WinitWaker(EventLoopProxy<RuffleEvent>);
 
@@ -123,6 +163,7 @@ impl PollRequester for WinitWaker {
 struct ActivePlayer {
     player: Arc<Mutex<Player>>,
     executor: Arc<AsyncExecutor<WinitWaker>>,
+    input_session: Option<InputSession>,
 
     #[cfg(target_os = "linux")]
     _gamemode_session: crate::dbus::GameModeSession,
@@ -140,6 +181,7 @@ impl ActivePlayer {
         font_database: Rc<fontdb::Database>,
         preferences: GlobalPreferences,
         file_picker: FilePicker,
+        gamepad_input_available: bool,
     ) -> Self {
         let mut builder = PlayerBuilder::new();
 
@@ -205,11 +247,17 @@ impl ActivePlayer {
                     tcp_connections: opt.tcp_connections,
                     fullscreen: opt.fullscreen,
                     save_directory: opt.save_directory.clone(),
+                    print_directory: opt.print_directory.clone(),
+                    print_format: opt.print_format,
+                    app_storage_directory: opt.app_storage_directory.clone(),
                     cache_directory: opt.cache_directory.clone(),
                     open_url_mode: opt.open_url_mode,
                     filesystem_access_mode: opt.filesystem_access_mode,
                     gamepad_button_mapping: opt.gamepad_button_mapping.clone(),
                     avm2_optimizer_enabled: opt.avm2_optimizer_enabled,
+                    record_input: opt.record_input.clone(),
+                    replay_input: opt.replay_input.clone(),
+                    debugger: opt.debugger.clone(),
                 })
             }
         };
@@ -289,6 +337,13 @@ impl ActivePlayer {
             }));
         }
 
+        if let Some(address) = &opt.debugger {
+            match TcpDebuggerBackend::connect(address.as_str()) {
+                Ok(debugger) => builder = builder.with_debugger(debugger),
+                Err(e) => tracing::warn!("Couldn't connect to debugger at {address}: {e}"),
+            }
+        }
+
         if !opt.gamepad_button_mapping.is_empty() {
             builder = builder.with_gamepad_button_mapping(opt.gamepad_button_mapping.clone());
         }
@@ -297,6 +352,17 @@ impl ActivePlayer {
             .with_navigator(navigator)
             .with_renderer(renderer)
             .with_storage(preferences.storage_backend().create_backend(&opt))
+            .with_print(DesktopPrintBackend::new(
+                opt.print_directory.clone(),
+                opt.print_format.into(),
+            ))
+            .with_filesystem(DiskFilesystemBackend::new(
+                movie_url
+                    .to_file_path()
+                    .ok()
+                    .and_then(|path| path.parent().map(Path::to_path_buf)),
+                app_storage_directory(&opt.app_storage_directory, &movie_url),
+            ))
             .with_fs_commands(Box::new(DesktopFSCommandProvider {
                 event_loop: event_loop.clone(),
             }))
@@ -308,6 +374,7 @@ impl ActivePlayer {
                     font_database,
                     preferences,
                     file_picker,
+                    gamepad_input_available,
                 )
                 .expect("Couldn't create ui backend"),
             )
@@ -408,15 +475,41 @@ impl ActivePlayer {
             );
         }
 
+        let input_session = {
+            let mut player_lock = player.lock().expect("Player lock must be available");
+            match (&opt.replay_input, &opt.record_input) {
+                (Some(path), _) => match InputSession::replay(&mut player_lock, path) {
+                    Ok(session) => Some(session),
+                    Err(e) => {
+                        tracing::error!("Couldn't read input from {}: {e:#}", path.display());
+                        None
+                    }
+                },
+                (None, Some(path)) => Some(InputSession::record(&mut player_lock, path.clone())),
+                (None, None) => None,
+            }
+        };
+
         Self {
             player,
             executor,
+            input_session,
             #[cfg(target_os = "linux")]
             _gamemode_session: crate::dbus::GameModeSession::new(gamemode_enable),
         }
     }
 }
 
+impl Drop for ActivePlayer {
+    fn drop(&mut self) {
+        if let Some(session) = &mut self.input_session {
+            if let Ok(mut player) = self.player.lock() {
+                session.finish(&mut player);
+            }
+        }
+    }
+}
+
 /// Owner of a Ruffle Player (via ActivePlayer),
 /// responsible for either creating, destroying or communicating with that player.
 pub struct PlayerController {
@@ -427,6 +520,10 @@ pub struct PlayerController {
     font_database: Rc<fontdb::Database>,
     preferences: GlobalPreferences,
     file_picker: FilePicker,
+
+    /// The IDs of the connected gamepads, or `None` if gamepad support
+    /// couldn't be initialized.
+    gamepads: Option<Vec<usize>>,
 }
 
 impl PlayerController {
@@ -437,6 +534,7 @@ impl PlayerController {
         font_database: fontdb::Database,
         preferences: GlobalPreferences,
         file_picker: FilePicker,
+        gamepads: Option<Vec<usize>>,
     ) -> Self {
         Self {
             player: None,
@@ -446,11 +544,12 @@ impl PlayerController {
             font_database: Rc::new(font_database),
             preferences,
             file_picker,
+            gamepads,
         }
     }
 
     pub fn create(&mut self, opt: &LaunchOptions, movie_url: &Url, movie_view: MovieView) {
-        self.player = Some(ActivePlayer::new(
+        let mut active = ActivePlayer::new(
             opt,
             self.event_loop.clone(),
             movie_url,
@@ -460,7 +559,22 @@ impl PlayerController {
             self.font_database.clone(),
             self.preferences.clone(),
             self.file_picker.clone(),
-        ));
+            self.gamepads.is_some(),
+        );
+
+        // Gamepads connected before the player was created are announced
+        // right away, so that they show up as devices in `GameInput`.
+        {
+            let mut player = active
+                .player
+                .try_lock()
+                .expect("Player lock must be available");
+            for &gamepad in self.gamepads.iter().flatten() {
+                player.handle_event(PlayerEvent::GamepadConnected { gamepad });
+            }
+        }
+
+        self.player = Some(active);
     }
 
     pub fn destroy(&mut self) {
@@ -480,9 +594,32 @@ impl PlayerController {
         }
     }
 
-    pub fn handle_event(&self, event: PlayerEvent) -> bool {
-        if let Some(mut player) = self.get() {
-            if player.is_playing() {
+    pub fn handle_event(&mut self, event: PlayerEvent) -> bool {
+        // The player has to know about gamepads even while it's paused.
+        let mut always_handled = false;
+        if let Some(gamepads) = &mut self.gamepads {
+            match event {
+                PlayerEvent::GamepadConnected { gamepad } => {
+                    if gamepads.contains(&gamepad) {
+                        return false;
+                    }
+                    gamepads.push(gamepad);
+                    always_handled = true;
+                }
+                PlayerEvent::GamepadDisconnected { gamepad } => {
+                    gamepads.retain(|g| *g != gamepad);
+                    always_handled = true;
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(active) = &mut self.player {
+            let mut player = active
+                .player
+                .try_lock()
+                .expect("Player lock must be available");
+            if player.is_playing() || always_handled {
                 return player.handle_event(event);
             }
         }
@@ -490,6 +627,20 @@ impl PlayerController {
         false
     }
 
+    /// Let time pass in the player, returning how long until the next frame is due.
+    pub fn tick(&mut self, dt: f64) -> Option<Duration> {
+        let active = self.player.as_mut()?;
+        let mut player = active
+            .player
+            .try_lock()
+            .expect("Player lock must be available");
+        match &mut active.input_session {
+            Some(session) => session.tick(&mut player, dt),
+            None => player.tick(dt),
+        }
+        Some(player.time_til_next_frame())
+    }
+
     pub fn poll(&self) {
         if let Some(player) = &self.player {
             player.executor.poll_all()
@@ -6,6 +6,7 @@ use rayon::prelude::*;
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::PlayerBuilder;
+use ruffle_render_software::SoftwareRenderBackend;
 use ruffle_render_wgpu::backend::{request_adapter_and_device, WgpuRenderBackend};
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use ruffle_render_wgpu::descriptors::Descriptors;
@@ -76,11 +77,24 @@ struct Opt {
     /// Skip unsupported movie types (currently AVM 2)
     #[clap(long, action)]
     skip_unsupported: bool,
+
+    /// Render on the CPU instead of the graphics device.
+    /// This is much slower, but works on machines without any usable GPU.
+    /// The `graphics` and `power` options are ignored when this is set.
+    #[clap(long, action)]
+    software: bool,
+}
+
+/// The renderer that frames are captured with.
+#[derive(Clone)]
+enum Renderer {
+    Wgpu(Arc<Descriptors>),
+    Software,
 }
 
 /// Captures a screenshot. The resulting image uses straight alpha
 fn take_screenshot(
-    descriptors: Arc<Descriptors>,
+    renderer: Renderer,
     swf_path: &Path,
     frames: u32,
     skipframes: u32,
@@ -106,12 +120,19 @@ fn take_screenshot(
         .unwrap_or_else(|| movie.height().to_pixels());
     let height = (height * size.scale).round() as u32;
 
-    let target = TextureTarget::new(&descriptors.device, (width, height))
-        .map_err(|e| anyhow!(e.to_string()))?;
-    let player = PlayerBuilder::new()
-        .with_renderer(
-            WgpuRenderBackend::new(descriptors, target).map_err(|e| anyhow!(e.to_string()))?,
-        )
+    let builder = match renderer {
+        Renderer::Wgpu(descriptors) => {
+            let target = TextureTarget::new(&descriptors.device, (width, height))
+                .map_err(|e| anyhow!(e.to_string()))?;
+            PlayerBuilder::new().with_renderer(
+                WgpuRenderBackend::new(descriptors, target).map_err(|e| anyhow!(e.to_string()))?,
+            )
+        }
+        Renderer::Software => {
+            PlayerBuilder::new().with_renderer(SoftwareRenderBackend::new(width, height))
+        }
+    };
+    let player = builder
         .with_movie(movie)
         .with_viewport_dimensions(width, height, size.scale)
         .build();
@@ -135,11 +156,15 @@ fn take_screenshot(
             let image = || {
                 player.lock().unwrap().render();
                 let mut player = player.lock().unwrap();
-                let renderer = player
-                    .renderer_mut()
-                    .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
-                    .unwrap();
-                renderer.capture_frame()
+                let renderer = player.renderer_mut();
+                if let Some(renderer) = renderer.downcast_mut::<SoftwareRenderBackend>() {
+                    renderer.capture_frame()
+                } else {
+                    renderer
+                        .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
+                        .unwrap()
+                        .capture_frame()
+                }
             };
             match catch_unwind(image) {
                 Ok(Some(image)) => result.push(image),
@@ -192,7 +217,7 @@ fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
     results
 }
 
-fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_single_swf(renderer: Renderer, opt: &Opt) -> Result<()> {
     let output = opt.output_path.clone().unwrap_or_else(|| {
         let mut result = PathBuf::new();
         result.set_file_name(opt.swf.file_stem().unwrap());
@@ -221,7 +246,7 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
     };
 
     let frames = take_screenshot(
-        descriptors,
+        renderer,
         &opt.swf,
         opt.frames,
         opt.skipframes,
@@ -286,7 +311,7 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
 }
 
 #[allow(clippy::branches_sharing_code)]
-fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_multiple_swfs(renderer: Renderer, opt: &Opt) -> Result<()> {
     let output = opt.output_path.clone().unwrap();
     let files = find_files(&opt.swf, !opt.silent);
 
@@ -315,7 +340,7 @@ fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()>
             );
         }
         if let Ok(frames) = take_screenshot(
-            descriptors.clone(),
+            renderer.clone(),
             file.path(),
             opt.frames,
             opt.skipframes,
@@ -381,8 +406,11 @@ fn trace_path(_opt: &Opt) -> Option<&Path> {
     None
 }
 
-fn main() -> Result<()> {
-    let opt: Opt = Opt::parse();
+fn create_renderer(opt: &Opt) -> Result<Renderer> {
+    if opt.software {
+        return Ok(Renderer::Software);
+    }
+
     let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
         backends: opt.graphics.into(),
         ..Default::default()
@@ -392,18 +420,25 @@ fn main() -> Result<()> {
         &instance,
         None,
         opt.power.into(),
-        trace_path(&opt),
+        trace_path(opt),
     ))
     .map_err(|e| anyhow!(e.to_string()))?;
 
-    let descriptors = Arc::new(Descriptors::new(instance, adapter, device, queue));
+    Ok(Renderer::Wgpu(Arc::new(Descriptors::new(
+        instance, adapter, device, queue,
+    ))))
+}
+
+fn main() -> Result<()> {
+    let opt: Opt = Opt::parse();
+    let renderer = create_renderer(&opt)?;
 
     if opt.swf.is_file() {
-        capture_single_swf(descriptors, &opt)?;
+        capture_single_swf(renderer, &opt)?;
     } else if !opt.swf.is_dir() {
         return Err(anyhow!("Given path is not a file or directory."));
     } else if opt.output_path.is_some() {
-        capture_multiple_swfs(descriptors, &opt)?;
+        capture_multiple_swfs(renderer, &opt)?;
     } else {
         return Err(anyhow!(
             "Output directory is required when exporting multiple files."
@@ -48,10 +48,26 @@ impl<'a> Tag<'a> {
     /// data, will seek the reader to the start of the next tag. This allows
     /// skipping past invalid tags.
     pub fn parse(reader: &mut FlvReader<'a>) -> Result<Self, Error> {
+        Self::parse_inner(reader, true)
+    }
+
+    /// Parse a single FLV tag structure that is not preceded by a back
+    /// pointer.
+    ///
+    /// This is the case for data appended to a `NetStream` after a seek,
+    /// which starts with a tag header rather than the size of the prior tag.
+    /// Otherwise, this behaves exactly like `parse`.
+    pub fn parse_without_back_pointer(reader: &mut FlvReader<'a>) -> Result<Self, Error> {
+        Self::parse_inner(reader, false)
+    }
+
+    fn parse_inner(reader: &mut FlvReader<'a>, has_back_pointer: bool) -> Result<Self, Error> {
         let old_position = reader.stream_position()?;
 
         let ret = (|| {
-            let _previous_tag_size = reader.read_u32()?;
+            if has_back_pointer {
+                let _previous_tag_size = reader.read_u32()?;
+            }
 
             let tag_type = reader.read_u8()?;
             let data_size = reader.read_u24()?;
@@ -171,6 +187,31 @@ mod tests {
         )
     }
 
+    #[test]
+    fn read_tag_without_back_pointer() {
+        let data = [
+            0x08, 0x00, 0x00, 0x05, 0x00, 0x00, 0x00, 0x00, 0x00, 0x50, 0x00, 0xBF, 0x12, 0x34,
+            0x56, 0x78,
+        ];
+        let mut reader = FlvReader::from_source(&data);
+
+        assert_eq!(
+            Tag::parse_without_back_pointer(&mut reader),
+            Ok(Tag {
+                timestamp: 0,
+                stream_id: 0x5000,
+                data: TagData::Audio(AudioData {
+                    format: SoundFormat::Speex,
+                    rate: SoundRate::R44_000,
+                    size: SoundSize::Bits16,
+                    sound_type: SoundType::Stereo,
+                    data: AudioDataType::Raw(&[0x12, 0x34, 0x56, 0x78])
+                })
+            })
+        );
+        assert_eq!(Tag::parse(&mut reader), Err(Error::EndOfData));
+    }
+
     #[test]
     fn read_tag_sounddata_invalid() {
         let data = [
@@ -1,5 +1,8 @@
 #[cfg(feature = "cpal")]
 pub mod audio;
+pub mod debugger;
 pub mod executor;
+pub mod filesystem;
 pub mod navigator;
+pub mod print;
 pub mod storage;
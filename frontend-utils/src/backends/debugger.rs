@@ -0,0 +1,312 @@
+//! A debugger backend talking to a debugger over TCP.
+//!
+//! This is a protocol of Ruffle's own: it isn't the protocol of the Flash
+//! Player debugger, so `fdb` and other Flash debuggers can't use it.
+//!
+//! Every message is framed as a little-endian `u32` payload length, a
+//! little-endian `u32` message type, and the payload. Payloads are made of
+//! little-endian `u32` integers and NUL-terminated UTF-8 strings.
+//!
+//! Commands sent by the debugger:
+//!
+//! | Type | Command          | Payload                  |
+//! |------|------------------|--------------------------|
+//! | 1    | Set breakpoint   | file, line               |
+//! | 2    | Remove breakpoint| file, line               |
+//! | 3    | Pause            |                          |
+//! | 4    | Continue         |                          |
+//! | 5    | Step in          |                          |
+//! | 6    | Step over        |                          |
+//! | 7    | Step out         |                          |
+//! | 8    | Get stack        |                          |
+//! | 9    | Get locals       | frame                    |
+//! | 10   | Evaluate         | frame, expression        |
+//!
+//! Events sent to the debugger:
+//!
+//! | Type | Event     | Payload                                                   |
+//! |------|-----------|-----------------------------------------------------------|
+//! | 1    | Paused    | reason (0: breakpoint, 1: step, 2: requested), file, line |
+//! | 2    | Resumed   |                                                           |
+//! | 3    | Stack     | count, then name, file, line for each frame               |
+//! | 4    | Locals    | count, then name, value for each variable                 |
+//! | 5    | Evaluated | success (0 or 1), value or error message                  |
+//! | 6    | Error     | message                                                   |
+//!
+//! Frames are counted from the innermost call, which is frame 0. Frames
+//! without a known location have an empty file and line 0. Evaluated
+//! expressions are names separated by dots, such as `this.x`; getters aren't
+//! called while paused, so reading a property with a getter is an error.
+
+use ruffle_core::backend::debugger::{
+    DebuggerBackend, DebuggerCommand, DebuggerEvent, PauseReason, SourceLocation,
+};
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use url::Url;
+
+pub struct TcpDebuggerBackend {
+    stream: TcpStream,
+    commands: Receiver<DebuggerCommand>,
+}
+
+impl TcpDebuggerBackend {
+    /// Connects to a debugger listening at the given address.
+    pub fn connect(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let stream = TcpStream::connect(address)?;
+        stream.set_nodelay(true)?;
+
+        let (sender, commands) = mpsc::channel();
+        let mut reader = stream.try_clone()?;
+        thread::Builder::new()
+            .name("debugger".to_string())
+            .spawn(move || loop {
+                let (kind, payload) = match read_message(&mut reader) {
+                    Ok(message) => message,
+                    Err(e) => {
+                        if e.kind() != io::ErrorKind::UnexpectedEof {
+                            tracing::warn!("Couldn't read from debugger: {e}");
+                        }
+                        break;
+                    }
+                };
+                match decode_command(kind, &payload) {
+                    Some(command) => {
+                        if sender.send(command).is_err() {
+                            break;
+                        }
+                    }
+                    None => tracing::warn!("Ignoring invalid debugger command of type {kind}"),
+                }
+            })?;
+
+        Ok(Self { stream, commands })
+    }
+}
+
+impl DebuggerBackend for TcpDebuggerBackend {
+    fn poll_command(&mut self) -> Option<DebuggerCommand> {
+        self.commands.try_recv().ok()
+    }
+
+    fn wait_command(&mut self) -> Option<DebuggerCommand> {
+        self.commands.recv().ok()
+    }
+
+    fn send_event(&mut self, event: DebuggerEvent) {
+        if let Err(e) = self.stream.write_all(&encode_event(&event)) {
+            tracing::warn!("Couldn't write to debugger: {e}");
+        }
+    }
+
+    fn load_swd(&mut self, movie_url: &str) -> Option<Vec<u8>> {
+        let path = Url::parse(movie_url).ok()?.to_file_path().ok()?;
+        std::fs::read(path.with_extension("swd")).ok()
+    }
+}
+
+fn read_message(reader: &mut impl Read) -> io::Result<(u32, Vec<u8>)> {
+    let mut len = [0; 4];
+    reader.read_exact(&mut len)?;
+    let mut kind = [0; 4];
+    reader.read_exact(&mut kind)?;
+    let mut payload = vec![0; u32::from_le_bytes(len) as usize];
+    reader.read_exact(&mut payload)?;
+    Ok((u32::from_le_bytes(kind), payload))
+}
+
+struct PayloadReader<'a>(&'a [u8]);
+
+impl PayloadReader<'_> {
+    fn read_u32(&mut self) -> Option<u32> {
+        let (bytes, rest) = self.0.split_first_chunk()?;
+        self.0 = rest;
+        Some(u32::from_le_bytes(*bytes))
+    }
+
+    fn read_str(&mut self) -> Option<String> {
+        let len = self.0.iter().position(|&b| b == 0)?;
+        let string = std::str::from_utf8(&self.0[..len]).ok()?.to_string();
+        self.0 = &self.0[len + 1..];
+        Some(string)
+    }
+
+    fn read_location(&mut self) -> Option<SourceLocation> {
+        let file = self.read_str()?;
+        let line = self.read_u32()?;
+        Some(SourceLocation { file, line })
+    }
+}
+
+fn decode_command(kind: u32, payload: &[u8]) -> Option<DebuggerCommand> {
+    let mut reader = PayloadReader(payload);
+    let command = match kind {
+        1 => DebuggerCommand::SetBreakpoint(reader.read_location()?),
+        2 => DebuggerCommand::RemoveBreakpoint(reader.read_location()?),
+        3 => DebuggerCommand::Pause,
+        4 => DebuggerCommand::Continue,
+        5 => DebuggerCommand::StepIn,
+        6 => DebuggerCommand::StepOver,
+        7 => DebuggerCommand::StepOut,
+        8 => DebuggerCommand::GetStack,
+        9 => DebuggerCommand::GetLocals {
+            frame: reader.read_u32()?,
+        },
+        10 => DebuggerCommand::Evaluate {
+            frame: reader.read_u32()?,
+            expression: reader.read_str()?,
+        },
+        _ => return None,
+    };
+    Some(command)
+}
+
+#[derive(Default)]
+struct PayloadWriter(Vec<u8>);
+
+impl PayloadWriter {
+    fn write_u32(&mut self, value: u32) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn write_str(&mut self, value: &str) {
+        // Strings can't hold NUL characters, as they end with one.
+        self.0.extend(value.bytes().filter(|&b| b != 0));
+        self.0.push(0);
+    }
+
+    fn write_location(&mut self, location: Option<&SourceLocation>) {
+        match location {
+            Some(location) => {
+                self.write_str(&location.file);
+                self.write_u32(location.line);
+            }
+            None => {
+                self.write_str("");
+                self.write_u32(0);
+            }
+        }
+    }
+}
+
+fn encode_event(event: &DebuggerEvent) -> Vec<u8> {
+    let mut payload = PayloadWriter::default();
+    let kind: u32 = match event {
+        DebuggerEvent::Paused { reason, location } => {
+            payload.write_u32(match reason {
+                PauseReason::Breakpoint => 0,
+                PauseReason::Step => 1,
+                PauseReason::Requested => 2,
+            });
+            payload.write_location(Some(location));
+            1
+        }
+        DebuggerEvent::Resumed => 2,
+        DebuggerEvent::Stack(frames) => {
+            payload.write_u32(frames.len() as u32);
+            for frame in frames {
+                payload.write_str(&frame.name);
+                payload.write_location(frame.location.as_ref());
+            }
+            3
+        }
+        DebuggerEvent::Locals(variables) => {
+            payload.write_u32(variables.len() as u32);
+            for variable in variables {
+                payload.write_str(&variable.name);
+                payload.write_str(&variable.value);
+            }
+            4
+        }
+        DebuggerEvent::Evaluated(result) => {
+            payload.write_u32(result.is_ok().into());
+            payload.write_str(match result {
+                Ok(value) | Err(value) => value,
+            });
+            5
+        }
+        DebuggerEvent::Error(message) => {
+            payload.write_str(message);
+            6
+        }
+    };
+
+    let mut message = Vec::with_capacity(payload.0.len() + 8);
+    message.extend_from_slice(&(payload.0.len() as u32).to_le_bytes());
+    message.extend_from_slice(&kind.to_le_bytes());
+    message.extend_from_slice(&payload.0);
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ruffle_core::backend::debugger::StackFrame;
+
+    fn message(kind: u32, payload: &[u8]) -> Vec<u8> {
+        let mut message = (payload.len() as u32).to_le_bytes().to_vec();
+        message.extend_from_slice(&kind.to_le_bytes());
+        message.extend_from_slice(payload);
+        message
+    }
+
+    #[test]
+    fn decode_commands() {
+        let mut data = message(1, b"Main.as\0\x0c\0\0\0");
+        data.extend(message(4, b""));
+        data.extend(message(10, b"\0\0\0\0this.x\0"));
+
+        let mut reader = &data[..];
+        let mut commands = vec![];
+        while let Ok((kind, payload)) = read_message(&mut reader) {
+            commands.push(decode_command(kind, &payload));
+        }
+        assert_eq!(
+            commands,
+            vec![
+                Some(DebuggerCommand::SetBreakpoint(SourceLocation {
+                    file: "Main.as".to_string(),
+                    line: 12,
+                })),
+                Some(DebuggerCommand::Continue),
+                Some(DebuggerCommand::Evaluate {
+                    frame: 0,
+                    expression: "this.x".to_string(),
+                }),
+            ]
+        );
+
+        assert_eq!(decode_command(1, b"Main.as\0"), None);
+        assert_eq!(decode_command(99, b""), None);
+    }
+
+    #[test]
+    fn encode_events() {
+        assert_eq!(encode_event(&DebuggerEvent::Resumed), message(2, b""));
+        assert_eq!(
+            encode_event(&DebuggerEvent::Stack(vec![
+                StackFrame {
+                    name: "Main/run()".to_string(),
+                    location: Some(SourceLocation {
+                        file: "Main.as".to_string(),
+                        line: 3,
+                    }),
+                },
+                StackFrame {
+                    name: "global$init()".to_string(),
+                    location: None,
+                },
+            ])),
+            message(
+                3,
+                b"\x02\0\0\0Main/run()\0Main.as\0\x03\0\0\0global$init()\0\0\0\0\0\0"
+            )
+        );
+        assert_eq!(
+            encode_event(&DebuggerEvent::Evaluated(Err("oops".to_string()))),
+            message(5, b"\0\0\0\0oops\0")
+        );
+    }
+}
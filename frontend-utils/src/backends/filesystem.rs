@@ -0,0 +1,155 @@
+use ruffle_core::backend::filesystem::{
+    FileMetadata, FilePath, FileRoot, FilesystemBackend, FilesystemError,
+};
+use std::fs::{self, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
+use std::path::{Component, Path, PathBuf};
+
+/// A filesystem backend that keeps each root in a directory on disk.
+///
+/// Applications can't reach anything outside of these directories.
+pub struct DiskFilesystemBackend {
+    /// The directory the movie was loaded from, if it's a local file.
+    application_directory: Option<PathBuf>,
+    storage_directory: PathBuf,
+}
+
+impl DiskFilesystemBackend {
+    pub fn new(application_directory: Option<PathBuf>, storage_directory: PathBuf) -> Self {
+        Self {
+            application_directory,
+            storage_directory,
+        }
+    }
+
+    fn root_directory(&self, root: FileRoot) -> Option<&Path> {
+        match root {
+            FileRoot::Application => self.application_directory.as_deref(),
+            FileRoot::ApplicationStorage => Some(&self.storage_directory),
+        }
+    }
+
+    /// Where a path is stored on disk, if anywhere.
+    fn disk_path(&self, path: &FilePath) -> Option<PathBuf> {
+        let mut disk_path = self.root_directory(path.file_root())?.to_path_buf();
+        for component in path.components() {
+            // Only allow plain names, so that something like `C:` can't
+            // escape the root.
+            let mut components = Path::new(component).components();
+            match (components.next(), components.next()) {
+                (Some(Component::Normal(name)), None) => disk_path.push(name),
+                _ => return None,
+            }
+        }
+        Some(disk_path)
+    }
+
+    fn readable_disk_path(&self, path: &FilePath) -> Result<PathBuf, FilesystemError> {
+        self.disk_path(path).ok_or(FilesystemError::AccessDenied)
+    }
+
+    fn writable_disk_path(&self, path: &FilePath) -> Result<PathBuf, FilesystemError> {
+        if !path.file_root().is_writable() {
+            return Err(FilesystemError::AccessDenied);
+        }
+        self.readable_disk_path(path)
+    }
+}
+
+impl FilesystemBackend for DiskFilesystemBackend {
+    fn native_path(&self, path: &FilePath) -> String {
+        match self.disk_path(path) {
+            Some(disk_path) => disk_path.to_string_lossy().into_owned(),
+            None => path.to_url(),
+        }
+    }
+
+    fn path_from_native(&self, native_path: &str) -> Option<FilePath> {
+        let native_path = Path::new(native_path);
+        [FileRoot::ApplicationStorage, FileRoot::Application]
+            .into_iter()
+            .find_map(|root| {
+                let relative = native_path.strip_prefix(self.root_directory(root)?).ok()?;
+                let mut path = FilePath::root(root);
+                for component in relative.components() {
+                    match component {
+                        Component::Normal(name) => path = path.join(name.to_str()?),
+                        Component::CurDir => {}
+                        Component::ParentDir => path = path.parent()?,
+                        Component::RootDir | Component::Prefix(_) => return None,
+                    }
+                }
+                Some(path)
+            })
+    }
+
+    fn metadata(&self, path: &FilePath) -> Option<FileMetadata> {
+        let metadata = fs::metadata(self.disk_path(path)?).ok()?;
+        Some(FileMetadata {
+            is_directory: metadata.is_dir(),
+            size: if metadata.is_dir() { 0 } else { metadata.len() },
+            creation_time: metadata.created().ok().map(Into::into),
+            modification_time: metadata.modified().ok().map(Into::into),
+        })
+    }
+
+    fn read(&self, path: &FilePath) -> Result<Vec<u8>, FilesystemError> {
+        Ok(fs::read(self.readable_disk_path(path)?)?)
+    }
+
+    fn write(&mut self, path: &FilePath, data: &[u8]) -> Result<(), FilesystemError> {
+        Ok(fs::write(self.writable_disk_path(path)?, data)?)
+    }
+
+    fn write_at(
+        &mut self,
+        path: &FilePath,
+        offset: u64,
+        data: &[u8],
+    ) -> Result<(), FilesystemError> {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .open(self.writable_disk_path(path)?)?;
+        file.seek(SeekFrom::Start(offset))?;
+        Ok(file.write_all(data)?)
+    }
+
+    fn set_length(&mut self, path: &FilePath, length: u64) -> Result<(), FilesystemError> {
+        let file = OpenOptions::new()
+            .write(true)
+            .open(self.writable_disk_path(path)?)?;
+        Ok(file.set_len(length)?)
+    }
+
+    fn list_directory(&self, path: &FilePath) -> Result<Vec<String>, FilesystemError> {
+        let mut names = Vec::new();
+        for entry in fs::read_dir(self.readable_disk_path(path)?)? {
+            // Names that aren't valid UTF-8 couldn't be opened again.
+            if let Ok(name) = entry?.file_name().into_string() {
+                names.push(name);
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    fn create_directory(&mut self, path: &FilePath) -> Result<(), FilesystemError> {
+        Ok(fs::create_dir_all(self.writable_disk_path(path)?)?)
+    }
+
+    fn delete(&mut self, path: &FilePath) -> Result<(), FilesystemError> {
+        if path.components().is_empty() {
+            return Err(FilesystemError::AccessDenied);
+        }
+
+        let disk_path = self.writable_disk_path(path)?;
+        if fs::metadata(&disk_path)?.is_dir() {
+            if fs::read_dir(&disk_path)?.next().is_some() {
+                return Err(FilesystemError::DirectoryNotEmpty);
+            }
+            Ok(fs::remove_dir(disk_path)?)
+        } else {
+            Ok(fs::remove_file(disk_path)?)
+        }
+    }
+}
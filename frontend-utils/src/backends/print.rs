@@ -0,0 +1,269 @@
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use ruffle_core::backend::print::{PageSettings, PrintBackend, PrintPage};
+use ruffle_render::utils::encode_png;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// The kind of file that print jobs are written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PrintFileFormat {
+    /// A single PDF file per job, with one page per printed page.
+    #[default]
+    Pdf,
+
+    /// One PNG file per printed page.
+    Png,
+}
+
+impl PrintFileFormat {
+    /// The file extension of a print job written in this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            PrintFileFormat::Pdf => "pdf",
+            PrintFileFormat::Png => "png",
+        }
+    }
+}
+
+/// A print backend that writes every print job to files in a directory,
+/// instead of sending it to a printer.
+pub struct FilePrintBackend {
+    output_dir: PathBuf,
+    format: PrintFileFormat,
+    settings: PageSettings,
+}
+
+impl FilePrintBackend {
+    pub fn new(output_dir: PathBuf, format: PrintFileFormat) -> Self {
+        Self {
+            output_dir,
+            format,
+            settings: PageSettings::default(),
+        }
+    }
+
+    pub fn with_page_settings(mut self, settings: PageSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Finds the first job name that doesn't overwrite an existing file.
+    fn next_job_path(&self) -> PathBuf {
+        let extension = self.format.extension();
+        (1..)
+            .map(|i| self.output_dir.join(format!("print-{i}.{extension}")))
+            .find(|path| !path.exists())
+            .expect("Ran out of print job names")
+    }
+}
+
+impl PrintBackend for FilePrintBackend {
+    fn start_job(&mut self) -> Option<PageSettings> {
+        if let Err(e) = fs::create_dir_all(&self.output_dir) {
+            tracing::error!("Couldn't create print directory: {e}");
+            return None;
+        }
+        Some(self.settings)
+    }
+
+    fn send_job(&mut self, settings: &PageSettings, pages: Vec<PrintPage>) {
+        write_print_job(&self.next_job_path(), self.format, settings, &pages);
+    }
+}
+
+/// Writes a print job to `path` in the given format.
+///
+/// PNG jobs are written as one file per page, named after `path` with the
+/// page number appended.
+pub fn write_print_job(
+    path: &Path,
+    format: PrintFileFormat,
+    settings: &PageSettings,
+    pages: &[PrintPage],
+) {
+    match format {
+        PrintFileFormat::Pdf => write_file(path, &write_pdf(settings, pages)),
+        PrintFileFormat::Png => {
+            let stem = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            for (i, page) in pages.iter().enumerate() {
+                match encode_png(page.width, page.height, &page.rgba, false, false) {
+                    Ok(data) => {
+                        let path = path.with_file_name(format!("{stem}-{}.png", i + 1));
+                        write_file(&path, &data);
+                    }
+                    Err(e) => tracing::error!("Couldn't encode printed page: {e}"),
+                }
+            }
+        }
+    }
+}
+
+fn write_file(path: &Path, data: &[u8]) {
+    match fs::write(path, data) {
+        Ok(()) => tracing::info!("Printed to {}", path.display()),
+        Err(e) => tracing::error!("Couldn't write print job to {}: {e}", path.display()),
+    }
+}
+
+/// Writes printed pages as a PDF document, with each page as an image
+/// placed in the printable area of the paper.
+pub fn write_pdf(settings: &PageSettings, pages: &[PrintPage]) -> Vec<u8> {
+    let mut pdf = PdfWriter::new();
+
+    // Objects 1 and 2 are the catalog and the page tree, and every page
+    // takes three objects after that: the page, its contents and its image.
+    let page_ids: Vec<usize> = (0..pages.len()).map(|i| 3 + i * 3).collect();
+
+    pdf.object(1, b"<< /Type /Catalog /Pages 2 0 R >>");
+    let kids: Vec<String> = page_ids.iter().map(|id| format!("{id} 0 R")).collect();
+    pdf.object(
+        2,
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            pages.len()
+        )
+        .as_bytes(),
+    );
+
+    let margin_x = settings.paper_width.saturating_sub(settings.page_width) / 2;
+    let margin_y = settings.paper_height.saturating_sub(settings.page_height) / 2;
+    for (page, id) in pages.iter().zip(page_ids) {
+        let mut page_dict = String::new();
+        let _ = write!(
+            page_dict,
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] ",
+            settings.paper_width, settings.paper_height
+        );
+        let _ = write!(
+            page_dict,
+            "/Resources << /XObject << /Im0 {} 0 R >> >> /Contents {} 0 R >>",
+            id + 2,
+            id + 1
+        );
+        pdf.object(id, page_dict.as_bytes());
+
+        let contents = format!(
+            "q {} 0 0 {} {margin_x} {margin_y} cm /Im0 Do Q",
+            settings.page_width, settings.page_height
+        );
+        pdf.stream(id + 1, "", contents.as_bytes());
+
+        let rgb: Vec<u8> = page
+            .rgba
+            .chunks_exact(4)
+            .flat_map(|rgba| [rgba[0], rgba[1], rgba[2]])
+            .collect();
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        let compressed = encoder
+            .write_all(&rgb)
+            .and_then(|()| encoder.finish())
+            .unwrap_or_default();
+        let image_dict = format!(
+            "/Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceRGB /BitsPerComponent 8 /Filter /FlateDecode",
+            page.width, page.height
+        );
+        pdf.stream(id + 2, &image_dict, &compressed);
+    }
+
+    pdf.finish(1)
+}
+
+/// A minimal writer for the PDF file structure.
+struct PdfWriter {
+    output: Vec<u8>,
+    offsets: Vec<usize>,
+}
+
+impl PdfWriter {
+    fn new() -> Self {
+        Self {
+            // The binary comment marks the file as containing binary data.
+            output: b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec(),
+            offsets: Vec::new(),
+        }
+    }
+
+    /// Starts the object with the given ID. Objects must be written in order.
+    fn begin_object(&mut self, id: usize) {
+        debug_assert_eq!(id, self.offsets.len() + 1);
+        self.offsets.push(self.output.len());
+        self.output
+            .extend_from_slice(format!("{id} 0 obj\n").as_bytes());
+    }
+
+    fn object(&mut self, id: usize, data: &[u8]) {
+        self.begin_object(id);
+        self.output.extend_from_slice(data);
+        self.output.extend_from_slice(b"\nendobj\n");
+    }
+
+    fn stream(&mut self, id: usize, dict: &str, data: &[u8]) {
+        self.begin_object(id);
+        self.output
+            .extend_from_slice(format!("<< {dict} /Length {} >>\nstream\n", data.len()).as_bytes());
+        self.output.extend_from_slice(data);
+        self.output.extend_from_slice(b"\nendstream\nendobj\n");
+    }
+
+    fn finish(mut self, root: usize) -> Vec<u8> {
+        let xref_offset = self.output.len();
+        let mut xref = format!("xref\n0 {}\n0000000000 65535 f \n", self.offsets.len() + 1);
+        for offset in &self.offsets {
+            let _ = writeln!(xref, "{offset:010} 00000 n ");
+        }
+        let _ = write!(
+            xref,
+            "trailer\n<< /Size {} /Root {root} 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n",
+            self.offsets.len() + 1
+        );
+        self.output.extend_from_slice(xref.as_bytes());
+        self.output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(width: u32, height: u32) -> PrintPage {
+        PrintPage {
+            width,
+            height,
+            rgba: vec![0xFF; (width * height * 4) as usize],
+        }
+    }
+
+    #[test]
+    fn pdf_has_all_pages() {
+        let settings = PageSettings::default();
+        let pdf = write_pdf(&settings, &[page(4, 4), page(4, 4)]);
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(text.starts_with("%PDF-1.4\n"));
+        assert!(text.contains("/Kids [3 0 R 6 0 R] /Count 2"));
+        assert!(text.contains("/MediaBox [0 0 612 792]"));
+        assert!(text.contains("q 576 0 0 756 18 18 cm /Im0 Do Q"));
+        assert!(text.contains("xref\n0 9\n"));
+        assert!(text.ends_with("%%EOF\n"));
+    }
+
+    #[test]
+    fn pdf_xref_offsets_point_at_objects() {
+        let pdf = write_pdf(&PageSettings::default(), &[page(2, 3)]);
+        let text = String::from_utf8_lossy(&pdf);
+
+        let xref_start = text.rfind("xref\n").unwrap();
+        let entries = text[xref_start..].lines().skip(3).take(5);
+        for (id, entry) in (1..).zip(entries) {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(format!("{id} 0 obj\n").as_bytes()));
+        }
+    }
+}
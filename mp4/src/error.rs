@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum Error {
+    #[error("the MP4 parser ran out of data")]
+    EndOfData,
+
+    #[error("the MP4 cannot be read as its length exceeds the maximum memory size for this architecture")]
+    PointerTooBig,
+
+    #[error("the MP4 does not start with a valid box")]
+    WrongMagic,
+
+    #[error("the MP4 is missing a required {0} box")]
+    MissingBox(&'static str),
+
+    #[error("the MP4 contains a box with invalid size {0}")]
+    InvalidBoxSize(u64),
+
+    #[error("the MP4 contains a {0} box with unsupported version {1}")]
+    UnsupportedVersion(&'static str, u8),
+
+    #[error("the MP4 contains an invalid sample table")]
+    InvalidSampleTable,
+
+    #[error("the MP4 contains an invalid movie fragment")]
+    InvalidFragment,
+
+    #[error("the MP4 contains an invalid elementary stream descriptor")]
+    InvalidDescriptor,
+}
@@ -0,0 +1,252 @@
+use crate::error::Error;
+use crate::movie::Movie;
+use crate::reader::{FourCC, Mp4Reader};
+use crate::sample::Sample;
+
+/// The `trex` box of a track, which sets the defaults for the samples of the
+/// track in movie fragments.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct TrackExtends {
+    pub track_id: u32,
+    pub default_sample_duration: u32,
+    pub default_sample_size: u32,
+    pub default_sample_flags: u32,
+}
+
+impl TrackExtends {
+    pub(crate) fn parse(mut reader: Mp4Reader<'_>) -> Result<Self, Error> {
+        reader.read_version_and_flags()?;
+        let track_id = reader.read_u32()?;
+        let _default_sample_description_index = reader.read_u32()?;
+
+        Ok(TrackExtends {
+            track_id,
+            default_sample_duration: reader.read_u32()?,
+            default_sample_size: reader.read_u32()?,
+            default_sample_flags: reader.read_u32()?,
+        })
+    }
+}
+
+/// Whether a sample with the given sample flags is a sync sample.
+fn is_sync_sample(flags: u32) -> bool {
+    const SAMPLE_IS_NON_SYNC_SAMPLE: u32 = 0x10000;
+
+    flags & SAMPLE_IS_NON_SYNC_SAMPLE == 0
+}
+
+impl Movie {
+    /// Parse all movie fragments that follow the `moov` box, appending their
+    /// samples to the tracks of this movie.
+    ///
+    /// `reader` must be where `parse` or a previous call to this function
+    /// left it. Parsing stops at the first box that hasn't been completely
+    /// loaded yet, leaving the reader at it so that the next call picks up
+    /// from there. If a fragment is invalid, the error is returned with the
+    /// reader positioned after it, so that the next call continues with the
+    /// following fragment.
+    pub fn parse_fragments(&mut self, reader: &mut Mp4Reader<'_>) -> Result<(), Error> {
+        while !reader.is_empty() {
+            let (kind, start, end) = match reader.read_box_header() {
+                Ok(header) => header,
+                Err(Error::EndOfData) => return Ok(()),
+                Err(e) => return Err(e),
+            };
+
+            if kind == FourCC::new(b"moof") {
+                reader.seek(start);
+                let contents = match reader.read_box() {
+                    Ok((_, contents)) => contents,
+                    Err(Error::EndOfData) => return Ok(()),
+                    Err(e) => return Err(e),
+                };
+
+                self.parse_moof(contents, start as u64)
+                    .map_err(|e| match e {
+                        Error::EndOfData => Error::InvalidFragment,
+                        e => e,
+                    })?;
+            } else {
+                reader.seek(end);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn parse_moof(&mut self, mut reader: Mp4Reader<'_>, moof_position: u64) -> Result<(), Error> {
+        // Without an explicit base data offset, the data of the first track
+        // fragment starts at the `moof` box, and that of each following track
+        // fragment after the data of the previous one.
+        let mut data_end = moof_position;
+
+        while !reader.is_empty() {
+            let (kind, contents) = reader.read_box()?;
+            if kind == FourCC::new(b"traf") {
+                data_end = self.parse_traf(contents, moof_position, data_end)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parse a track fragment, returning the end of its sample data.
+    fn parse_traf(
+        &mut self,
+        mut reader: Mp4Reader<'_>,
+        moof_position: u64,
+        previous_data_end: u64,
+    ) -> Result<u64, Error> {
+        const BASE_DATA_OFFSET_PRESENT: u32 = 0x1;
+        const SAMPLE_DESCRIPTION_INDEX_PRESENT: u32 = 0x2;
+        const DEFAULT_SAMPLE_DURATION_PRESENT: u32 = 0x8;
+        const DEFAULT_SAMPLE_SIZE_PRESENT: u32 = 0x10;
+        const DEFAULT_SAMPLE_FLAGS_PRESENT: u32 = 0x20;
+        const DEFAULT_BASE_IS_MOOF: u32 = 0x20000;
+
+        // The `tfhd` box always comes first.
+        let (kind, mut tfhd) = reader.read_box()?;
+        if kind != FourCC::new(b"tfhd") {
+            return Err(Error::MissingBox("tfhd"));
+        }
+
+        let (_version, flags) = tfhd.read_version_and_flags()?;
+        let track_id = tfhd.read_u32()?;
+        let Some(track) = self.tracks.iter_mut().find(|t| t.id == track_id) else {
+            // Nothing we know how to play.
+            return Ok(previous_data_end);
+        };
+        let Some(mut defaults) = track.extends else {
+            return Err(Error::MissingBox("trex"));
+        };
+
+        let base_data_offset = if flags & BASE_DATA_OFFSET_PRESENT != 0 {
+            tfhd.read_u64()?
+        } else if flags & DEFAULT_BASE_IS_MOOF != 0 {
+            moof_position
+        } else {
+            previous_data_end
+        };
+        if flags & SAMPLE_DESCRIPTION_INDEX_PRESENT != 0 {
+            tfhd.skip(4)?;
+        }
+        if flags & DEFAULT_SAMPLE_DURATION_PRESENT != 0 {
+            defaults.default_sample_duration = tfhd.read_u32()?;
+        }
+        if flags & DEFAULT_SAMPLE_SIZE_PRESENT != 0 {
+            defaults.default_sample_size = tfhd.read_u32()?;
+        }
+        if flags & DEFAULT_SAMPLE_FLAGS_PRESENT != 0 {
+            defaults.default_sample_flags = tfhd.read_u32()?;
+        }
+
+        let mut samples = vec![];
+        let mut decode_time = track.end_time;
+        let mut data_end = base_data_offset;
+
+        while !reader.is_empty() {
+            let (kind, mut contents) = reader.read_box()?;
+            match &kind.0 {
+                b"tfdt" => {
+                    let (version, _flags) = contents.read_version_and_flags()?;
+                    decode_time = match version {
+                        0 => contents.read_u32()?.into(),
+                        1 => contents.read_u64()?,
+                        v => return Err(Error::UnsupportedVersion("tfdt", v)),
+                    };
+                }
+                b"trun" => {
+                    data_end = read_track_run(
+                        contents,
+                        &defaults,
+                        base_data_offset,
+                        data_end,
+                        &mut decode_time,
+                        &mut samples,
+                    )?;
+                }
+                _ => {}
+            }
+        }
+
+        track.samples.extend(samples);
+        track.end_time = decode_time;
+
+        Ok(data_end)
+    }
+}
+
+/// Read the samples of a `trun` box, returning the end of their data.
+fn read_track_run(
+    mut reader: Mp4Reader<'_>,
+    defaults: &TrackExtends,
+    base_data_offset: u64,
+    previous_data_end: u64,
+    decode_time: &mut u64,
+    samples: &mut Vec<Sample>,
+) -> Result<u64, Error> {
+    const DATA_OFFSET_PRESENT: u32 = 0x1;
+    const FIRST_SAMPLE_FLAGS_PRESENT: u32 = 0x4;
+    const SAMPLE_DURATION_PRESENT: u32 = 0x100;
+    const SAMPLE_SIZE_PRESENT: u32 = 0x200;
+    const SAMPLE_FLAGS_PRESENT: u32 = 0x400;
+    const SAMPLE_COMPOSITION_TIME_OFFSETS_PRESENT: u32 = 0x800;
+
+    let (_version, flags) = reader.read_version_and_flags()?;
+    let count = reader.read_u32()?;
+
+    // Without a data offset, the data of this run follows that of the
+    // previous one.
+    let mut offset = if flags & DATA_OFFSET_PRESENT != 0 {
+        base_data_offset
+            .checked_add_signed(reader.read_i32()?.into())
+            .ok_or(Error::InvalidFragment)?
+    } else {
+        previous_data_end
+    };
+    let first_sample_flags = if flags & FIRST_SAMPLE_FLAGS_PRESENT != 0 {
+        Some(reader.read_u32()?)
+    } else {
+        None
+    };
+
+    for i in 0..count {
+        let duration = if flags & SAMPLE_DURATION_PRESENT != 0 {
+            reader.read_u32()?
+        } else {
+            defaults.default_sample_duration
+        };
+        let size = if flags & SAMPLE_SIZE_PRESENT != 0 {
+            reader.read_u32()?
+        } else {
+            defaults.default_sample_size
+        };
+        let sample_flags = if flags & SAMPLE_FLAGS_PRESENT != 0 {
+            reader.read_u32()?
+        } else {
+            first_sample_flags
+                .filter(|_| i == 0)
+                .unwrap_or(defaults.default_sample_flags)
+        };
+        // Version 0 offsets are unsigned, but as with `ctts` they're always
+        // small enough that reading them as signed is fine.
+        let composition_offset = if flags & SAMPLE_COMPOSITION_TIME_OFFSETS_PRESENT != 0 {
+            reader.read_i32()?
+        } else {
+            0
+        };
+
+        samples.push(Sample {
+            offset,
+            size,
+            decode_time: *decode_time,
+            composition_offset,
+            is_sync: is_sync_sample(sample_flags),
+        });
+
+        offset += u64::from(size);
+        *decode_time += u64::from(duration);
+    }
+
+    Ok(offset)
+}
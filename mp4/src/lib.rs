@@ -0,0 +1,18 @@
+//! Demuxer for the ISO base media file format (MP4, F4V, M4A, ...).
+//!
+//! Only the `moov` and `moof` boxes are parsed; sample data is left in place
+//! and is located through the samples of each `Track`. Files can be parsed
+//! incrementally as they are downloaded, and fragmented files have the samples
+//! of each movie fragment appended to their tracks as the fragments arrive.
+
+mod error;
+mod fragment;
+mod movie;
+mod reader;
+mod sample;
+
+pub use error::Error;
+pub use fragment::TrackExtends;
+pub use movie::{AudioCodec, AudioInfo, Media, Movie, Track, VideoCodec, VideoInfo};
+pub use reader::{FourCC, Mp4Reader};
+pub use sample::Sample;
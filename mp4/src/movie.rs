@@ -0,0 +1,829 @@
+use crate::error::Error;
+use crate::fragment::TrackExtends;
+use crate::reader::{FourCC, Mp4Reader};
+use crate::sample::{Sample, SampleTables};
+
+/// The `moov` box of an ISO base media file, containing everything we need to
+/// know to locate and decode the samples of each track.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Movie {
+    /// The number of time units per second for the movie duration.
+    pub timescale: u32,
+
+    /// The duration of the movie, in the movie's timescale.
+    pub duration: u64,
+
+    /// The absolute offset of the `moov` box in the file.
+    pub moov_position: u64,
+
+    pub tracks: Vec<Track>,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Track {
+    pub id: u32,
+
+    /// The number of time units per second for the samples of this track.
+    pub timescale: u32,
+
+    /// The duration of the track, in the track's timescale.
+    pub duration: u64,
+
+    pub media: Media,
+
+    /// All samples of the track, in decode order.
+    ///
+    /// For fragmented movies, this only includes the samples of the movie
+    /// fragments that have been parsed so far.
+    pub samples: Vec<Sample>,
+
+    /// The decode time following the last sample, in the track's timescale.
+    pub end_time: u64,
+
+    /// The sample defaults for movie fragments, if the track is fragmented.
+    pub extends: Option<TrackExtends>,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum Media {
+    Video(VideoInfo),
+    Audio(AudioInfo),
+
+    /// A track that isn't audio or video (e.g. hints or subtitles), or one
+    /// without a sample description we understand.
+    Other(FourCC),
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct VideoInfo {
+    pub codec: VideoCodec,
+    pub width: u16,
+    pub height: u16,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum VideoCodec {
+    /// H.264, with the contents of the `avcC` box.
+    ///
+    /// This is the same `AVCDecoderConfigurationRecord` that an FLV AVC
+    /// sequence header contains.
+    Avc {
+        config: Vec<u8>,
+    },
+
+    Unknown(FourCC),
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct AudioInfo {
+    pub codec: AudioCodec,
+    pub channels: u16,
+    pub sample_size: u16,
+    pub sample_rate: u32,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum AudioCodec {
+    /// AAC, with the `AudioSpecificConfig` from the `esds` box.
+    Aac {
+        config: Vec<u8>,
+    },
+
+    Mp3,
+
+    Unknown(FourCC),
+}
+
+impl Movie {
+    /// Parse the `moov` box out of an ISO base media file.
+    ///
+    /// `reader` must be at the start of the file, or where a previous call
+    /// left it. Other top-level boxes are skipped over without needing their
+    /// contents, so the `moov` box may come after the media data. `EndOfData`
+    /// is returned if the `moov` box is not (yet) completely loaded, in which
+    /// case the reader is left at the first box that hasn't been fully read,
+    /// so that parsing can be resumed once more data has loaded.
+    ///
+    /// On success, the reader is left after the `moov` box, which is where
+    /// any movie fragments start.
+    pub fn parse(reader: &mut Mp4Reader<'_>) -> Result<Self, Error> {
+        loop {
+            if reader.is_empty() {
+                return Err(Error::EndOfData);
+            }
+
+            let (kind, start, end) = reader.read_box_header()?;
+            if start == 0 && !is_top_level_box(kind) {
+                reader.seek(start);
+                return Err(Error::WrongMagic);
+            }
+
+            if kind == FourCC::new(b"moov") {
+                reader.seek(start);
+                let (_, contents) = reader.read_box()?;
+                let mut movie = Self::parse_moov(contents).map_err(|e| match e {
+                    // The whole `moov` box is loaded, so running out of data
+                    // within it means it's malformed.
+                    Error::EndOfData => Error::InvalidBoxSize((end - start) as u64),
+                    e => e,
+                })?;
+                movie.moov_position = start as u64;
+                return Ok(movie);
+            }
+
+            if kind == FourCC::new(b"moof") {
+                reader.seek(start);
+                return Err(Error::MissingBox("moov"));
+            }
+
+            reader.seek(end);
+        }
+    }
+
+    fn parse_moov(mut reader: Mp4Reader<'_>) -> Result<Self, Error> {
+        let mut header = None;
+        let mut tracks = vec![];
+        let mut extends = vec![];
+        let mut fragment_duration = None;
+
+        while !reader.is_empty() {
+            let (kind, mut contents) = reader.read_box()?;
+            match &kind.0 {
+                b"mvhd" => header = Some(parse_media_header(contents, "mvhd")?),
+                b"trak" => tracks.push(Track::parse(contents)?),
+                b"mvex" => {
+                    while !contents.is_empty() {
+                        let (kind, mut contents) = contents.read_box()?;
+                        match &kind.0 {
+                            b"mehd" => {
+                                let (version, _flags) = contents.read_version_and_flags()?;
+                                fragment_duration = Some(match version {
+                                    0 => contents.read_u32()?.into(),
+                                    1 => contents.read_u64()?,
+                                    v => return Err(Error::UnsupportedVersion("mehd", v)),
+                                });
+                            }
+                            b"trex" => extends.push(TrackExtends::parse(contents)?),
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let (timescale, mut duration) = header.ok_or(Error::MissingBox("mvhd"))?;
+
+        // Fragmented movies usually leave the duration of the `moov` box
+        // empty and put the duration of the whole movie in `mehd` instead.
+        if duration == 0 {
+            duration = fragment_duration.unwrap_or_default();
+        }
+
+        for track_extends in extends {
+            if let Some(track) = tracks.iter_mut().find(|t| t.id == track_extends.track_id) {
+                track.extends = Some(track_extends);
+            }
+        }
+
+        Ok(Movie {
+            timescale,
+            duration,
+            moov_position: 0,
+            tracks,
+        })
+    }
+
+    /// The duration of the movie in seconds.
+    pub fn duration_secs(&self) -> f64 {
+        if self.timescale == 0 {
+            return 0.0;
+        }
+
+        self.duration as f64 / self.timescale as f64
+    }
+
+    /// Whether the samples of this movie are (also) stored in movie
+    /// fragments, which need to be parsed with `parse_fragments`.
+    pub fn is_fragmented(&self) -> bool {
+        self.tracks.iter().any(|t| t.extends.is_some())
+    }
+
+    /// Find the first video track with at least one sample, or that may get
+    /// samples from movie fragments.
+    pub fn video_track(&self) -> Option<usize> {
+        self.tracks
+            .iter()
+            .position(|t| matches!(t.media, Media::Video(_)) && t.has_samples())
+    }
+
+    /// Find the first audio track with at least one sample, or that may get
+    /// samples from movie fragments.
+    pub fn audio_track(&self) -> Option<usize> {
+        self.tracks
+            .iter()
+            .position(|t| matches!(t.media, Media::Audio(_)) && t.has_samples())
+    }
+}
+
+impl Track {
+    fn parse(mut reader: Mp4Reader<'_>) -> Result<Self, Error> {
+        let mut id = None;
+        let mut width = 0;
+        let mut height = 0;
+        let mut mdia = None;
+
+        while !reader.is_empty() {
+            let (kind, mut contents) = reader.read_box()?;
+            match &kind.0 {
+                b"tkhd" => {
+                    let (version, _flags) = contents.read_version_and_flags()?;
+                    match version {
+                        0 => contents.skip(8)?,
+                        1 => contents.skip(16)?,
+                        v => return Err(Error::UnsupportedVersion("tkhd", v)),
+                    };
+                    id = Some(contents.read_u32()?);
+                    contents.skip(4)?;
+                    contents.skip(if version == 1 { 8 } else { 4 })?;
+                    // Reserved, layer, alternate group, volume, reserved and
+                    // the transformation matrix.
+                    contents.skip(8 + 2 + 2 + 2 + 2 + 36)?;
+                    // Width and height are 16.16 fixed point.
+                    width = (contents.read_u32()? >> 16) as u16;
+                    height = (contents.read_u32()? >> 16) as u16;
+                }
+                b"mdia" => mdia = Some(contents),
+                _ => {}
+            }
+        }
+
+        let id = id.ok_or(Error::MissingBox("tkhd"))?;
+        let mut mdia = mdia.ok_or(Error::MissingBox("mdia"))?;
+
+        let mut header = None;
+        let mut handler = None;
+        let mut stbl = None;
+        while !mdia.is_empty() {
+            let (kind, mut contents) = mdia.read_box()?;
+            match &kind.0 {
+                b"mdhd" => header = Some(parse_media_header(contents, "mdhd")?),
+                b"hdlr" => {
+                    contents.read_version_and_flags()?;
+                    contents.skip(4)?;
+                    handler = Some(contents.read_fourcc()?);
+                }
+                b"minf" => stbl = contents.find_box(FourCC::new(b"stbl"))?,
+                _ => {}
+            }
+        }
+
+        let (timescale, duration) = header.ok_or(Error::MissingBox("mdhd"))?;
+        let handler = handler.ok_or(Error::MissingBox("hdlr"))?;
+        let mut stbl = stbl.ok_or(Error::MissingBox("stbl"))?;
+
+        let mut media = Media::Other(handler);
+        let mut tables = SampleTables::default();
+        while !stbl.is_empty() {
+            let (kind, contents) = stbl.read_box()?;
+            if kind == FourCC::new(b"stsd") {
+                media = parse_sample_description(contents, handler, width, height)?;
+            } else {
+                tables.read_box(kind, contents)?;
+            }
+        }
+
+        let end_time = tables.duration();
+
+        Ok(Track {
+            id,
+            timescale,
+            duration,
+            media,
+            samples: tables.into_samples()?,
+            end_time,
+            extends: None,
+        })
+    }
+
+    fn has_samples(&self) -> bool {
+        !self.samples.is_empty() || self.extends.is_some()
+    }
+
+    /// The decode time of a sample in milliseconds.
+    pub fn sample_time_ms(&self, index: usize) -> Option<f64> {
+        let sample = self.samples.get(index)?;
+        if self.timescale == 0 {
+            return Some(0.0);
+        }
+
+        Some(sample.decode_time as f64 * 1000.0 / self.timescale as f64)
+    }
+
+    /// Find the index of the last sync sample at or before `time` (in
+    /// milliseconds), or the first sync sample if there are none before it.
+    pub fn sync_sample_at(&self, time: f64) -> Option<usize> {
+        let mut found = None;
+        for (i, sample) in self.samples.iter().enumerate() {
+            if !sample.is_sync {
+                continue;
+            }
+
+            if found.is_some() && self.sample_time_ms(i)? > time {
+                break;
+            }
+
+            found = Some(i);
+        }
+
+        found
+    }
+
+    /// Find the index of the first sample with a decode time at or after
+    /// `time` (in milliseconds).
+    pub fn sample_at(&self, time: f64) -> usize {
+        (0..self.samples.len())
+            .find(|i| self.sample_time_ms(*i).unwrap_or_default() >= time)
+            .unwrap_or(self.samples.len())
+    }
+}
+
+/// Parse the timescale and duration of an `mvhd` or `mdhd` box.
+fn parse_media_header(mut reader: Mp4Reader<'_>, name: &'static str) -> Result<(u32, u64), Error> {
+    let (version, _flags) = reader.read_version_and_flags()?;
+    match version {
+        0 => {
+            reader.skip(8)?;
+            let timescale = reader.read_u32()?;
+            let duration = reader.read_u32()?;
+            Ok((timescale, duration.into()))
+        }
+        1 => {
+            reader.skip(16)?;
+            let timescale = reader.read_u32()?;
+            let duration = reader.read_u64()?;
+            Ok((timescale, duration))
+        }
+        v => Err(Error::UnsupportedVersion(name, v)),
+    }
+}
+
+/// Parse the first sample entry of an `stsd` box.
+fn parse_sample_description(
+    mut reader: Mp4Reader<'_>,
+    handler: FourCC,
+    width: u16,
+    height: u16,
+) -> Result<Media, Error> {
+    reader.read_version_and_flags()?;
+    let count = reader.read_u32()?;
+    if count == 0 {
+        return Ok(Media::Other(handler));
+    }
+
+    let (format, mut entry) = reader.read_box()?;
+    // Reserved and data reference index.
+    entry.skip(8)?;
+
+    match &handler.0 {
+        b"vide" => {
+            // Pre-defined and reserved fields.
+            entry.skip(16)?;
+            let entry_width = entry.read_u16()?;
+            let entry_height = entry.read_u16()?;
+            // Resolution, reserved, frame count, compressor name, depth and
+            // pre-defined fields.
+            entry.skip(4 + 4 + 4 + 2 + 32 + 2 + 2)?;
+
+            let codec = match &format.0 {
+                b"avc1" | b"avc3" => match entry.find_box(FourCC::new(b"avcC"))? {
+                    Some(avcc) => VideoCodec::Avc {
+                        config: avcc.remaining().to_vec(),
+                    },
+                    None => return Err(Error::MissingBox("avcC")),
+                },
+                _ => VideoCodec::Unknown(format),
+            };
+
+            Ok(Media::Video(VideoInfo {
+                codec,
+                width: if width != 0 { width } else { entry_width },
+                height: if height != 0 { height } else { entry_height },
+            }))
+        }
+        b"soun" => {
+            // QuickTime sound descriptions reuse the first reserved field as
+            // a version number, which adds extra fields at the end.
+            let version = entry.read_u16()?;
+            entry.skip(6)?;
+            let channels = entry.read_u16()?;
+            let sample_size = entry.read_u16()?;
+            entry.skip(4)?;
+            let mut sample_rate = entry.read_u32()? >> 16;
+            match version {
+                0 => {}
+                1 => entry.skip(16)?,
+                2 => {
+                    entry.skip(4)?;
+                    sample_rate = f64::from_bits(entry.read_u64()?) as u32;
+                    entry.skip(20)?;
+                }
+                v => return Err(Error::UnsupportedVersion("stsd", v as u8)),
+            }
+
+            let codec = match &format.0 {
+                b"mp4a" => match entry.find_box(FourCC::new(b"esds"))? {
+                    Some(esds) => parse_esds(esds)?,
+                    None => AudioCodec::Unknown(format),
+                },
+                b".mp3" => AudioCodec::Mp3,
+                _ => AudioCodec::Unknown(format),
+            };
+
+            Ok(Media::Audio(AudioInfo {
+                codec,
+                channels,
+                sample_size,
+                sample_rate,
+            }))
+        }
+        _ => Ok(Media::Other(handler)),
+    }
+}
+
+/// Read the header of an MPEG-4 descriptor, returning its tag and a reader
+/// over its contents.
+fn read_descriptor<'a>(reader: &mut Mp4Reader<'a>) -> Result<(u8, Mp4Reader<'a>), Error> {
+    let tag = reader.read_u8()?;
+    let mut len = 0usize;
+    for _ in 0..4 {
+        let byte = reader.read_u8()?;
+        len = (len << 7) | usize::from(byte & 0x7F);
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+
+    Ok((tag, Mp4Reader::from_source(reader.read(len)?)))
+}
+
+/// Parse the elementary stream descriptor of an MPEG-4 audio sample entry.
+fn parse_esds(mut reader: Mp4Reader<'_>) -> Result<AudioCodec, Error> {
+    const ES_DESCRIPTOR: u8 = 0x03;
+    const DECODER_CONFIG_DESCRIPTOR: u8 = 0x04;
+    const DECODER_SPECIFIC_INFO: u8 = 0x05;
+
+    reader.read_version_and_flags()?;
+    let (tag, mut es) = read_descriptor(&mut reader)?;
+    if tag != ES_DESCRIPTOR {
+        return Err(Error::InvalidDescriptor);
+    }
+
+    es.skip(2)?;
+    let flags = es.read_u8()?;
+    if flags & 0x80 != 0 {
+        es.skip(2)?;
+    }
+    if flags & 0x40 != 0 {
+        let url_len = es.read_u8()?;
+        es.skip(url_len.into())?;
+    }
+    if flags & 0x20 != 0 {
+        es.skip(2)?;
+    }
+
+    let (tag, mut config) = read_descriptor(&mut es)?;
+    if tag != DECODER_CONFIG_DESCRIPTOR {
+        return Err(Error::InvalidDescriptor);
+    }
+
+    let object_type = config.read_u8()?;
+    // Stream type, buffer size and bitrates.
+    config.skip(1 + 3 + 4 + 4)?;
+
+    match object_type {
+        // MPEG-4 audio, and the MPEG-2 AAC profiles.
+        0x40 | 0x66 | 0x67 | 0x68 => {
+            let (tag, specific_info) = read_descriptor(&mut config)?;
+            if tag != DECODER_SPECIFIC_INFO {
+                return Err(Error::InvalidDescriptor);
+            }
+
+            Ok(AudioCodec::Aac {
+                config: specific_info.remaining().to_vec(),
+            })
+        }
+        0x69 | 0x6B => Ok(AudioCodec::Mp3),
+        _ => Ok(AudioCodec::Unknown(FourCC::new(b"mp4a"))),
+    }
+}
+
+/// Whether the given box can appear at the start of a file.
+fn is_top_level_box(kind: FourCC) -> bool {
+    matches!(
+        &kind.0,
+        b"ftyp"
+            | b"styp"
+            | b"moov"
+            | b"mdat"
+            | b"free"
+            | b"skip"
+            | b"wide"
+            | b"pdin"
+            | b"sidx"
+            | b"uuid"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_box(kind: &[u8; 4], contents: &[&[u8]]) -> Vec<u8> {
+        let contents = contents.concat();
+        let mut data = ((contents.len() + 8) as u32).to_be_bytes().to_vec();
+        data.extend_from_slice(kind);
+        data.extend(contents);
+        data
+    }
+
+    fn make_full_box(kind: &[u8; 4], contents: &[&[u8]]) -> Vec<u8> {
+        let mut full_contents: Vec<&[u8]> = vec![&[0, 0, 0, 0]];
+        full_contents.extend_from_slice(contents);
+        make_box(kind, &full_contents)
+    }
+
+    fn u32s(values: &[u32]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_be_bytes()).collect()
+    }
+
+    fn make_track(id: u32, handler: &[u8; 4], sample_entry: Vec<u8>, stbl: &[Vec<u8>]) -> Vec<u8> {
+        let tkhd = make_full_box(
+            b"tkhd",
+            &[
+                &u32s(&[0, 0, id, 0, 0]),
+                &[0; 52],
+                &u32s(&[320 << 16, 240 << 16]),
+            ],
+        );
+        let mdhd = make_full_box(b"mdhd", &[&u32s(&[0, 0, 1000, 3000]), &[0; 4]]);
+        let hdlr = make_full_box(b"hdlr", &[&[0; 4], handler, &[0; 13]]);
+        let stsd = make_full_box(b"stsd", &[&u32s(&[1]), &sample_entry]);
+        let mut stbl_children: Vec<&[u8]> = vec![&stsd];
+        stbl_children.extend(stbl.iter().map(|b| b.as_slice()));
+        let stbl = make_box(b"stbl", &stbl_children);
+        let minf = make_box(b"minf", &[&stbl]);
+        let mdia = make_box(b"mdia", &[&mdhd, &hdlr, &minf]);
+        make_box(b"trak", &[&tkhd, &mdia])
+    }
+
+    fn make_movie() -> Vec<u8> {
+        let avcc = make_box(b"avcC", &[&[1, 0x42, 0, 0x1E, 0xFF, 0xE0, 0]]);
+        let avc1 = make_box(
+            b"avc1",
+            &[&[0; 8], &[0; 16], &[1, 64, 0, 240], &[0; 50], &avcc],
+        );
+        let video = make_track(
+            1,
+            b"vide",
+            avc1,
+            &[
+                make_full_box(b"stts", &[&u32s(&[1, 4, 500])]),
+                make_full_box(b"stss", &[&u32s(&[2, 1, 3])]),
+                make_full_box(b"stsz", &[&u32s(&[0, 4, 10, 20, 30, 40])]),
+                make_full_box(b"stsc", &[&u32s(&[2, 1, 2, 1, 2, 2, 1])]),
+                make_full_box(b"stco", &[&u32s(&[2, 1000, 2000])]),
+            ],
+        );
+
+        let esds = make_full_box(
+            b"esds",
+            &[
+                &[0x03, 22, 0, 1, 0],
+                &[0x04, 17, 0x40, 0x15, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+                &[0x05, 2, 0x12, 0x10],
+            ],
+        );
+        let mp4a = make_box(
+            b"mp4a",
+            &[
+                &[0; 8],
+                &[0; 8],
+                &[0, 2, 0, 16, 0, 0, 0, 0],
+                &u32s(&[44100 << 16]),
+                &esds,
+            ],
+        );
+        let audio = make_track(
+            2,
+            b"soun",
+            mp4a,
+            &[
+                make_full_box(b"stts", &[&u32s(&[1, 3, 1024])]),
+                make_full_box(b"stsz", &[&u32s(&[100, 3])]),
+                make_full_box(b"stsc", &[&u32s(&[1, 1, 3, 1])]),
+                make_full_box(b"co64", &[&u32s(&[1, 0, 5000])]),
+            ],
+        );
+
+        let ftyp = make_box(b"ftyp", &[b"isom", &[0, 0, 2, 0], b"isomavc1"]);
+        let mdat = make_box(b"mdat", &[&[0; 16]]);
+        let mvhd = make_full_box(b"mvhd", &[&u32s(&[0, 0, 600, 1200]), &[0; 80]]);
+        let moov = make_box(b"moov", &[&mvhd, &video, &audio]);
+
+        [ftyp, mdat, moov].concat()
+    }
+
+    fn make_fragmented_movie() -> Vec<u8> {
+        let avcc = make_box(b"avcC", &[&[1, 0x42, 0, 0x1E, 0xFF, 0xE0, 0]]);
+        let avc1 = make_box(
+            b"avc1",
+            &[&[0; 8], &[0; 16], &[1, 64, 0, 240], &[0; 50], &avcc],
+        );
+        let video = make_track(
+            1,
+            b"vide",
+            avc1,
+            &[
+                make_full_box(b"stts", &[&u32s(&[0])]),
+                make_full_box(b"stsz", &[&u32s(&[0, 0])]),
+                make_full_box(b"stsc", &[&u32s(&[0])]),
+                make_full_box(b"stco", &[&u32s(&[0])]),
+            ],
+        );
+        let mehd = make_full_box(b"mehd", &[&u32s(&[1200])]);
+        let trex = make_full_box(b"trex", &[&u32s(&[1, 1, 500, 10, 0x10000])]);
+        let mvex = make_box(b"mvex", &[&mehd, &trex]);
+
+        let ftyp = make_box(b"ftyp", &[b"iso5", &[0, 0, 2, 0], b"iso5avc1"]);
+        let mvhd = make_full_box(b"mvhd", &[&u32s(&[0, 0, 600, 0]), &[0; 80]]);
+        let moov = make_box(b"moov", &[&mvhd, &video, &mvex]);
+
+        // The first fragment has explicit sizes and a sync first sample, and
+        // the second one relies on the defaults.
+        let make_moof = |sequence: u32, tfhd: &[u32], trun: &[u32]| {
+            let mfhd = make_full_box(b"mfhd", &[&u32s(&[sequence])]);
+            let tfhd = make_box(b"tfhd", &[&u32s(tfhd)]);
+            let trun = make_box(b"trun", &[&u32s(trun)]);
+            let tfdt = make_full_box(b"tfdt", &[&u32s(&[0])]);
+            let traf = if sequence == 1 {
+                make_box(b"traf", &[&tfhd, &tfdt, &trun])
+            } else {
+                make_box(b"traf", &[&tfhd, &trun])
+            };
+            make_box(b"moof", &[&mfhd, &traf])
+        };
+        let moof_1 = |data_offset| make_moof(1, &[0x20000, 1], &[0x205, 2, data_offset, 0, 10, 20]);
+        let moof_1 = moof_1(moof_1(0).len() as u32 + 8);
+        let moof_2 = |data_offset| make_moof(2, &[0x20008, 1, 250], &[0x1, 2, data_offset]);
+        let moof_2 = moof_2(moof_2(0).len() as u32 + 8);
+
+        [
+            ftyp,
+            moov,
+            moof_1,
+            make_box(b"mdat", &[&[0; 30]]),
+            moof_2,
+            make_box(b"mdat", &[&[0; 20]]),
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn parse_movie() {
+        let movie = Movie::parse(&mut Mp4Reader::from_source(&make_movie())).unwrap();
+
+        assert_eq!(movie.timescale, 600);
+        assert_eq!(movie.duration_secs(), 2.0);
+        assert_eq!(movie.moov_position, 48);
+        assert_eq!(movie.video_track(), Some(0));
+        assert_eq!(movie.audio_track(), Some(1));
+
+        let video = &movie.tracks[0];
+        assert_eq!(video.id, 1);
+        assert_eq!(
+            video.media,
+            Media::Video(VideoInfo {
+                codec: VideoCodec::Avc {
+                    config: vec![1, 0x42, 0, 0x1E, 0xFF, 0xE0, 0]
+                },
+                width: 320,
+                height: 240,
+            })
+        );
+        assert_eq!(
+            video
+                .samples
+                .iter()
+                .map(|s| (s.offset, s.size, s.decode_time, s.is_sync))
+                .collect::<Vec<_>>(),
+            vec![
+                (1000, 10, 0, true),
+                (1010, 20, 500, false),
+                (2000, 30, 1000, true),
+                (2030, 40, 1500, false),
+            ]
+        );
+        assert_eq!(video.end_time, 2000);
+        assert_eq!(video.sync_sample_at(1200.0), Some(2));
+        assert_eq!(video.sync_sample_at(600.0), Some(0));
+        assert_eq!(video.sample_at(600.0), 2);
+
+        let audio = &movie.tracks[1];
+        assert_eq!(
+            audio.media,
+            Media::Audio(AudioInfo {
+                codec: AudioCodec::Aac {
+                    config: vec![0x12, 0x10]
+                },
+                channels: 2,
+                sample_size: 16,
+                sample_rate: 44100,
+            })
+        );
+        assert_eq!(
+            audio.samples.iter().map(|s| s.offset).collect::<Vec<_>>(),
+            vec![5000, 5100, 5200]
+        );
+        assert_eq!(audio.sample_time_ms(2), Some(2048.0));
+        assert!(!movie.is_fragmented());
+    }
+
+    #[test]
+    fn parse_incomplete_movie() {
+        let data = make_movie();
+        let parse = |data, position| {
+            let mut reader = Mp4Reader::from_parts(data, position);
+            (Movie::parse(&mut reader), reader.position())
+        };
+
+        assert_eq!(parse(&data[..6], 0), (Err(Error::EndOfData), 0));
+        assert_eq!(parse(&data[..20], 0), (Err(Error::EndOfData), 24));
+        assert_eq!(parse(&data[..30], 0), (Err(Error::EndOfData), 24));
+        // The `mdat` box is skipped before its contents are loaded.
+        assert_eq!(parse(&data[..36], 24), (Err(Error::EndOfData), 48));
+        assert_eq!(parse(&data[..60], 48), (Err(Error::EndOfData), 48));
+
+        let (movie, position) = parse(&data, 48);
+        assert_eq!(movie, Movie::parse(&mut Mp4Reader::from_source(&data)));
+        assert_eq!(position, data.len());
+
+        assert_eq!(parse(b"FLV\x01", 0), (Err(Error::EndOfData), 0));
+        assert_eq!(parse(b"\0\0\0\x08FLV\x01", 0), (Err(Error::WrongMagic), 0));
+    }
+
+    #[test]
+    fn parse_fragmented_movie() {
+        let data = make_fragmented_movie();
+        let mut reader = Mp4Reader::from_source(&data);
+        let mut movie = Movie::parse(&mut reader).unwrap();
+
+        assert!(movie.is_fragmented());
+        assert_eq!(movie.duration_secs(), 2.0);
+        assert_eq!(movie.video_track(), Some(0));
+        assert_eq!(movie.tracks[0].samples, vec![]);
+
+        let moof_1 = reader.position();
+        movie.parse_fragments(&mut reader).unwrap();
+        let video = &movie.tracks[0];
+        let moof_2 = data.len() - 20 - 8 - 72;
+        let mdat_1 = moof_2 - 30;
+        let mdat_2 = data.len() - 20;
+        assert_eq!(moof_1, mdat_1 - 8 - 96);
+        assert_eq!(
+            video
+                .samples
+                .iter()
+                .map(|s| (s.offset, s.size, s.decode_time, s.is_sync))
+                .collect::<Vec<_>>(),
+            vec![
+                (mdat_1 as u64, 10, 0, true),
+                (mdat_1 as u64 + 10, 20, 500, false),
+                (mdat_2 as u64, 10, 1000, false),
+                (mdat_2 as u64 + 10, 10, 1250, false),
+            ]
+        );
+        assert_eq!(video.end_time, 1500);
+        assert_eq!(video.sync_sample_at(1200.0), Some(0));
+        assert_eq!(reader.position(), data.len());
+    }
+
+    #[test]
+    fn parse_incomplete_fragments() {
+        let data = make_fragmented_movie();
+        let mut reader = Mp4Reader::from_source(&data);
+        let mut movie = Movie::parse(&mut reader).unwrap();
+        let moof_2 = data.len() - 20 - 8 - 72;
+
+        // Everything up to the middle of the second `moof` box.
+        let mut reader = Mp4Reader::from_parts(&data[..moof_2 + 50], reader.position());
+        movie.parse_fragments(&mut reader).unwrap();
+        assert_eq!(movie.tracks[0].samples.len(), 2);
+        assert_eq!(reader.position(), moof_2);
+
+        let mut reader = Mp4Reader::from_parts(&data, reader.position());
+        movie.parse_fragments(&mut reader).unwrap();
+        assert_eq!(movie.tracks[0].samples.len(), 4);
+        assert_eq!(movie.tracks[0].end_time, 1500);
+    }
+}
@@ -0,0 +1,248 @@
+use crate::error::Error;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+
+/// A four-character code, used to identify box and codec types.
+#[derive(PartialEq, Eq, Clone, Copy, Hash)]
+pub struct FourCC(pub [u8; 4]);
+
+impl FourCC {
+    pub const fn new(code: &[u8; 4]) -> Self {
+        Self(*code)
+    }
+}
+
+impl Debug for FourCC {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "FourCC({:?})", String::from_utf8_lossy(&self.0))
+    }
+}
+
+/// A reader over a borrowed buffer of ISO base media file format data.
+///
+/// All values are big-endian.
+#[derive(Clone)]
+pub struct Mp4Reader<'a> {
+    source: &'a [u8],
+
+    position: usize,
+}
+
+impl<'a> Mp4Reader<'a> {
+    pub fn from_source(source: &'a [u8]) -> Self {
+        Mp4Reader {
+            source,
+            position: 0,
+        }
+    }
+
+    /// Reconstitute a reader from its source parts.
+    pub fn from_parts(source: &'a [u8], position: usize) -> Self {
+        Mp4Reader { source, position }
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Move the reader to an absolute position.
+    ///
+    /// The position may be past the end of the buffer, e.g. to skip over a box
+    /// that hasn't been completely loaded yet.
+    pub fn seek(&mut self, position: usize) {
+        self.position = position;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.position >= self.source.len()
+    }
+
+    /// All of the data after the current position.
+    pub fn remaining(&self) -> &'a [u8] {
+        self.source.get(self.position..).unwrap_or_default()
+    }
+
+    /// Read a certain number of bytes from the buffer.
+    ///
+    /// If the requested number of bytes are not available, `EndOfData` is
+    /// returned and the position is not advanced.
+    pub fn read(&mut self, count: usize) -> Result<&'a [u8], Error> {
+        let start = self.position;
+        let end = self
+            .position
+            .checked_add(count)
+            .ok_or(Error::PointerTooBig)?;
+        if end > self.source.len() {
+            return Err(Error::EndOfData);
+        }
+
+        self.position = end;
+
+        Ok(&self.source[start..end])
+    }
+
+    pub fn skip(&mut self, count: usize) -> Result<(), Error> {
+        self.read(count).map(|_| ())
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, Error> {
+        Ok(self.read(1)?[0])
+    }
+
+    pub fn read_u16(&mut self) -> Result<u16, Error> {
+        Ok(u16::from_be_bytes(
+            self.read(2)?.try_into().expect("two bytes"),
+        ))
+    }
+
+    pub fn read_u24(&mut self) -> Result<u32, Error> {
+        let bytes = self.read(3)?;
+
+        Ok(u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]))
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, Error> {
+        Ok(u32::from_be_bytes(
+            self.read(4)?.try_into().expect("four bytes"),
+        ))
+    }
+
+    pub fn read_i32(&mut self) -> Result<i32, Error> {
+        Ok(i32::from_be_bytes(
+            self.read(4)?.try_into().expect("four bytes"),
+        ))
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64, Error> {
+        Ok(u64::from_be_bytes(
+            self.read(8)?.try_into().expect("eight bytes"),
+        ))
+    }
+
+    pub fn read_fourcc(&mut self) -> Result<FourCC, Error> {
+        Ok(FourCC(self.read(4)?.try_into().expect("four bytes")))
+    }
+
+    /// Read the version and flags of a "full box".
+    pub fn read_version_and_flags(&mut self) -> Result<(u8, u32), Error> {
+        let version = self.read_u8()?;
+        let flags = self.read_u24()?;
+
+        Ok((version, flags))
+    }
+
+    /// Read a box header, returning the box type and the absolute start and
+    /// end offsets of the box, including the header.
+    ///
+    /// Only the header itself needs to be present in the buffer; the reader
+    /// is left at the start of the box contents.
+    pub fn read_box_header(&mut self) -> Result<(FourCC, usize, usize), Error> {
+        let start = self.position;
+        let ret = (|| {
+            let size = self.read_u32()?;
+            let kind = self.read_fourcc()?;
+            let size = match size {
+                // The box extends to the end of the file. We only know where
+                // that is if we've been given the whole file.
+                0 => (self.source.len() - start) as u64,
+                1 => self.read_u64()?,
+                size => size as u64,
+            };
+
+            let header_size = (self.position - start) as u64;
+            if size < header_size {
+                return Err(Error::InvalidBoxSize(size));
+            }
+
+            let end = usize::try_from(size)
+                .ok()
+                .and_then(|size| start.checked_add(size))
+                .ok_or(Error::PointerTooBig)?;
+
+            Ok((kind, start, end))
+        })();
+
+        if ret.is_err() {
+            self.position = start;
+        }
+
+        ret
+    }
+
+    /// Read an entire box, returning its type and a reader over its contents.
+    ///
+    /// If the box is not completely within the buffer, `EndOfData` is
+    /// returned and the position is not advanced.
+    pub fn read_box(&mut self) -> Result<(FourCC, Mp4Reader<'a>), Error> {
+        let start = self.position;
+        let (kind, _, end) = self.read_box_header()?;
+        let contents_start = self.position;
+        if end > self.source.len() {
+            self.position = start;
+            return Err(Error::EndOfData);
+        }
+
+        self.position = end;
+
+        Ok((
+            kind,
+            Mp4Reader::from_source(&self.source[contents_start..end]),
+        ))
+    }
+
+    /// Iterate over all remaining child boxes until the first one of the
+    /// given type is found.
+    pub fn find_box(&mut self, kind: FourCC) -> Result<Option<Mp4Reader<'a>>, Error> {
+        while !self.is_empty() {
+            let (child_kind, child) = self.read_box()?;
+            if child_kind == kind {
+                return Ok(Some(child));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_box_headers() {
+        let data = [
+            0, 0, 0, 12, b'f', b'r', b'e', b'e', 1, 2, 3, 4, // compact size
+            0, 0, 0, 1, b's', b'k', b'i', b'p', 0, 0, 0, 0, 0, 0, 0, 17, 5, // large size
+        ];
+        let mut reader = Mp4Reader::from_source(&data);
+
+        let (kind, contents) = reader.read_box().unwrap();
+        assert_eq!(kind, FourCC::new(b"free"));
+        assert_eq!(contents.source, &[1, 2, 3, 4]);
+
+        let (kind, contents) = reader.read_box().unwrap();
+        assert_eq!(kind, FourCC::new(b"skip"));
+        assert_eq!(contents.source, &[5]);
+
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn read_partial_box() {
+        let data = [0, 0, 0, 12, b'f', b'r', b'e', b'e', 1, 2];
+        let mut reader = Mp4Reader::from_source(&data);
+
+        assert_eq!(reader.read_box().err(), Some(Error::EndOfData));
+        assert_eq!(reader.position(), 0);
+
+        let (kind, start, end) = reader.read_box_header().unwrap();
+        assert_eq!((kind, start, end), (FourCC::new(b"free"), 0, 12));
+    }
+
+    #[test]
+    fn read_invalid_box() {
+        let data = [0, 0, 0, 4, b'f', b'r', b'e', b'e'];
+        let mut reader = Mp4Reader::from_source(&data);
+
+        assert_eq!(reader.read_box().err(), Some(Error::InvalidBoxSize(4)));
+    }
+}
@@ -0,0 +1,224 @@
+use crate::error::Error;
+use crate::reader::{FourCC, Mp4Reader};
+
+/// A single sample (access unit) of a track.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Sample {
+    /// The absolute offset of the sample data in the file.
+    pub offset: u64,
+
+    /// The size of the sample data in bytes.
+    pub size: u32,
+
+    /// The decode time of the sample, in the track's timescale.
+    pub decode_time: u64,
+
+    /// The offset from the decode time to the composition (presentation)
+    /// time, in the track's timescale.
+    pub composition_offset: i32,
+
+    /// Whether this sample can be decoded without any prior samples, i.e. is
+    /// a keyframe.
+    pub is_sync: bool,
+}
+
+/// The raw tables from an `stbl` box that describe where samples are.
+#[derive(Default)]
+pub struct SampleTables {
+    /// `(sample_count, sample_delta)` from `stts`.
+    time_to_sample: Vec<(u32, u32)>,
+
+    /// `(sample_count, sample_offset)` from `ctts`.
+    composition_offsets: Vec<(u32, i32)>,
+
+    /// 1-based sample numbers from `stss`, or `None` if every sample is a
+    /// sync sample.
+    sync_samples: Option<Vec<u32>>,
+
+    /// Sample sizes from `stsz` or `stz2`.
+    sample_sizes: Vec<u32>,
+
+    /// `(first_chunk, samples_per_chunk)` from `stsc`.
+    sample_to_chunk: Vec<(u32, u32)>,
+
+    /// Chunk offsets from `stco` or `co64`.
+    chunk_offsets: Vec<u64>,
+}
+
+impl SampleTables {
+    /// Read one of the sample table boxes, if it's one we care about.
+    pub fn read_box(&mut self, kind: FourCC, mut reader: Mp4Reader<'_>) -> Result<(), Error> {
+        match &kind.0 {
+            b"stts" => {
+                reader.read_version_and_flags()?;
+                let count = reader.read_u32()?;
+                for _ in 0..count {
+                    self.time_to_sample
+                        .push((reader.read_u32()?, reader.read_u32()?));
+                }
+            }
+            b"ctts" => {
+                // Version 0 offsets are unsigned, but in practice they're
+                // always small enough that reading them as signed is fine.
+                reader.read_version_and_flags()?;
+                let count = reader.read_u32()?;
+                for _ in 0..count {
+                    self.composition_offsets
+                        .push((reader.read_u32()?, reader.read_i32()?));
+                }
+            }
+            b"stss" => {
+                reader.read_version_and_flags()?;
+                let count = reader.read_u32()?;
+                let mut sync_samples = Vec::with_capacity(count.min(0x10000) as usize);
+                for _ in 0..count {
+                    sync_samples.push(reader.read_u32()?);
+                }
+                self.sync_samples = Some(sync_samples);
+            }
+            b"stsz" => {
+                reader.read_version_and_flags()?;
+                let sample_size = reader.read_u32()?;
+                let count = reader.read_u32()?;
+                if sample_size != 0 {
+                    self.sample_sizes = vec![sample_size; count as usize];
+                } else {
+                    for _ in 0..count {
+                        self.sample_sizes.push(reader.read_u32()?);
+                    }
+                }
+            }
+            b"stz2" => {
+                reader.read_version_and_flags()?;
+                reader.skip(3)?;
+                let field_size = reader.read_u8()?;
+                let count = reader.read_u32()?;
+                let mut packed_sizes = 0;
+                for i in 0..count {
+                    let size = match field_size {
+                        // Two sizes are packed into each byte, high nibble first.
+                        4 if i % 2 == 0 => {
+                            packed_sizes = reader.read_u8()?;
+                            u32::from(packed_sizes >> 4)
+                        }
+                        4 => u32::from(packed_sizes & 0xF),
+                        8 => u32::from(reader.read_u8()?),
+                        16 => u32::from(reader.read_u16()?),
+                        _ => return Err(Error::InvalidSampleTable),
+                    };
+                    self.sample_sizes.push(size);
+                }
+            }
+            b"stsc" => {
+                reader.read_version_and_flags()?;
+                let count = reader.read_u32()?;
+                for _ in 0..count {
+                    let first_chunk = reader.read_u32()?;
+                    let samples_per_chunk = reader.read_u32()?;
+                    let _sample_description_index = reader.read_u32()?;
+                    self.sample_to_chunk.push((first_chunk, samples_per_chunk));
+                }
+            }
+            b"stco" => {
+                reader.read_version_and_flags()?;
+                let count = reader.read_u32()?;
+                for _ in 0..count {
+                    self.chunk_offsets.push(reader.read_u32()?.into());
+                }
+            }
+            b"co64" => {
+                reader.read_version_and_flags()?;
+                let count = reader.read_u32()?;
+                for _ in 0..count {
+                    self.chunk_offsets.push(reader.read_u64()?);
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// The total duration of all samples, in the track's timescale.
+    pub fn duration(&self) -> u64 {
+        self.time_to_sample
+            .iter()
+            .map(|(count, delta)| u64::from(*count) * u64::from(*delta))
+            .sum()
+    }
+
+    /// Build the full list of samples out of the sample tables.
+    pub fn into_samples(self) -> Result<Vec<Sample>, Error> {
+        let num_samples = self.sample_sizes.len();
+        let mut samples = Vec::with_capacity(num_samples);
+
+        // Work out where each sample is from the chunk tables.
+        let mut sample_sizes = self.sample_sizes.iter();
+        for (i, entry) in self.sample_to_chunk.iter().enumerate() {
+            let (first_chunk, samples_per_chunk) = *entry;
+            let last_chunk = match self.sample_to_chunk.get(i + 1) {
+                Some((next_first_chunk, _)) => *next_first_chunk,
+                None => self.chunk_offsets.len() as u32 + 1,
+            };
+            if first_chunk == 0 || last_chunk < first_chunk {
+                return Err(Error::InvalidSampleTable);
+            }
+
+            for chunk in first_chunk..last_chunk {
+                let mut offset = *self
+                    .chunk_offsets
+                    .get(chunk as usize - 1)
+                    .ok_or(Error::InvalidSampleTable)?;
+                for _ in 0..samples_per_chunk {
+                    let Some(size) = sample_sizes.next() else {
+                        break;
+                    };
+                    samples.push(Sample {
+                        offset,
+                        size: *size,
+                        decode_time: 0,
+                        composition_offset: 0,
+                        is_sync: self.sync_samples.is_none(),
+                    });
+                    offset += u64::from(*size);
+                }
+            }
+        }
+
+        if samples.len() != num_samples {
+            return Err(Error::InvalidSampleTable);
+        }
+
+        let mut time = 0;
+        let mut deltas = self
+            .time_to_sample
+            .iter()
+            .flat_map(|(count, delta)| std::iter::repeat_n(*delta, *count as usize));
+        for sample in samples.iter_mut() {
+            sample.decode_time = time;
+            time += u64::from(deltas.next().unwrap_or(0));
+        }
+
+        let mut offsets = self
+            .composition_offsets
+            .iter()
+            .flat_map(|(count, offset)| std::iter::repeat_n(*offset, *count as usize));
+        for sample in samples.iter_mut() {
+            match offsets.next() {
+                Some(offset) => sample.composition_offset = offset,
+                None => break,
+            }
+        }
+
+        for sample_number in self.sync_samples.iter().flatten() {
+            if let Some(sample) = (*sample_number as usize)
+                .checked_sub(1)
+                .and_then(|i| samples.get_mut(i))
+            {
+                sample.is_sync = true;
+            }
+        }
+
+        Ok(samples)
+    }
+}
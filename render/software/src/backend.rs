@@ -0,0 +1,292 @@
+use crate::bitmap::{as_bitmap, SoftwareBitmap};
+use crate::filters;
+use crate::mesh::Mesh;
+use crate::pixmap::Pixmap;
+use crate::surface::Surface;
+use image::RgbaImage;
+use ruffle_render::backend::{
+    BitmapCacheEntry, Context3D, Context3DProfile, PixelBenderOutput, PixelBenderTarget,
+    RenderBackend, ShapeHandle, ViewportDimensions,
+};
+use ruffle_render::bitmap::{
+    Bitmap, BitmapHandle, BitmapSource, PixelRegion, RgbaBufRead, SyncHandle,
+};
+use ruffle_render::commands::CommandList;
+use ruffle_render::error::Error;
+use ruffle_render::filters::Filter;
+use ruffle_render::pixel_bender::{
+    PixelBenderShader, PixelBenderShaderArgument, PixelBenderShaderHandle,
+};
+use ruffle_render::quality::StageQuality;
+use ruffle_render::shape_utils::DistilledShape;
+use ruffle_render::tessellator::ShapeTessellator;
+use std::borrow::Cow;
+use std::sync::Arc;
+use swf::Color;
+
+/// The largest bitmap (in either dimension) that we are willing to allocate.
+const MAX_BITMAP_SIZE: u32 = 8192;
+
+/// A render backend that rasterizes everything on the CPU.
+///
+/// This needs no graphics hardware or drivers at all, which makes it suitable for
+/// headless environments such as CI machines, at the cost of being much slower
+/// than the GPU backends.
+///
+/// Not everything is implemented yet:
+/// - the gradient glow, gradient bevel, convolution, displacement map and shader filters
+///   are unsupported, so [`RenderBackend::apply_filter`] returns `None` for them and cached
+///   display objects are drawn without them;
+/// - Pixel Bender shaders and Stage3D aren't available.
+pub struct SoftwareRenderBackend {
+    viewport_dimensions: ViewportDimensions,
+    quality: StageQuality,
+    shape_tessellator: ShapeTessellator,
+    frame: Pixmap,
+}
+
+#[derive(Debug)]
+struct SoftwareSyncHandle {
+    handle: BitmapHandle,
+    region: PixelRegion,
+}
+
+impl SyncHandle for SoftwareSyncHandle {}
+
+impl SoftwareRenderBackend {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            viewport_dimensions: ViewportDimensions {
+                width,
+                height,
+                scale_factor: 1.0,
+            },
+            quality: StageQuality::High,
+            shape_tessellator: ShapeTessellator::new(),
+            frame: Pixmap::new(width, height),
+        }
+    }
+
+    /// Returns the last frame that was rendered, with straight (not premultiplied) alpha.
+    pub fn capture_frame(&self) -> Option<RgbaImage> {
+        let mut data = self.frame.data().to_vec();
+        ruffle_render::utils::unmultiply_alpha_rgba(&mut data);
+        RgbaImage::from_raw(self.frame.width(), self.frame.height(), data)
+    }
+
+    fn new_bitmap_handle(pixmap: Pixmap) -> BitmapHandle {
+        BitmapHandle(Arc::new(SoftwareBitmap::new(pixmap)))
+    }
+
+    fn check_size(width: u32, height: u32) -> Result<(), Error> {
+        if width == 0 || height == 0 {
+            return Err(Error::InvalidSize);
+        }
+        if width > MAX_BITMAP_SIZE || height > MAX_BITMAP_SIZE {
+            return Err(Error::TooLarge);
+        }
+        Ok(())
+    }
+}
+
+impl RenderBackend for SoftwareRenderBackend {
+    fn viewport_dimensions(&self) -> ViewportDimensions {
+        self.viewport_dimensions
+    }
+
+    fn set_viewport_dimensions(&mut self, dimensions: ViewportDimensions) {
+        self.viewport_dimensions = dimensions;
+        self.frame = Pixmap::new(dimensions.width, dimensions.height);
+    }
+
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
+        let mesh = self
+            .shape_tessellator
+            .tessellate_shape(shape, bitmap_source);
+        ShapeHandle(Arc::new(Mesh::new(mesh, bitmap_source, self)))
+    }
+
+    fn render_offscreen(
+        &mut self,
+        handle: BitmapHandle,
+        commands: CommandList,
+        quality: StageQuality,
+        bounds: PixelRegion,
+    ) -> Option<Box<dyn SyncHandle>> {
+        let bitmap = as_bitmap(&handle);
+        // The commands may draw the target bitmap itself, so render from a copy.
+        let mut surface = Surface::from_pixmap(&bitmap.pixmap(), quality);
+        surface.draw_commands(commands);
+        *bitmap.pixmap_mut() = surface.finish();
+
+        Some(Box::new(SoftwareSyncHandle {
+            handle,
+            region: bounds,
+        }))
+    }
+
+    fn apply_filter(
+        &mut self,
+        source: BitmapHandle,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        destination: BitmapHandle,
+        dest_point: (u32, u32),
+        filter: Filter,
+    ) -> Option<Box<dyn SyncHandle>> {
+        let source_region = as_bitmap(&source).pixmap().copy_region(
+            source_point.0,
+            source_point.1,
+            source_size.0,
+            source_size.1,
+        );
+        let result = filters::apply_filter(&source_region, &filter)?;
+
+        let dest_bitmap = as_bitmap(&destination);
+        let mut dest = dest_bitmap.pixmap_mut();
+        dest.copy_from(&result, dest_point.0, dest_point.1);
+        let region = PixelRegion::for_whole_size(dest.width(), dest.height());
+        drop(dest);
+
+        Some(Box::new(SoftwareSyncHandle {
+            handle: destination,
+            region,
+        }))
+    }
+
+    fn is_filter_supported(&self, filter: &Filter) -> bool {
+        filters::is_filter_supported(filter)
+    }
+
+    fn is_offscreen_supported(&self) -> bool {
+        true
+    }
+
+    fn submit_frame(
+        &mut self,
+        clear: Color,
+        commands: CommandList,
+        cache_entries: Vec<BitmapCacheEntry>,
+    ) {
+        for entry in cache_entries {
+            let bitmap = as_bitmap(&entry.handle);
+            let (width, height) = {
+                let pixmap = bitmap.pixmap();
+                (pixmap.width(), pixmap.height())
+            };
+            let mut surface = Surface::new(width, height, self.quality);
+            surface.clear(entry.clear);
+            surface.draw_commands(entry.commands);
+            let mut result = surface.finish();
+            // Filters that we can't apply are skipped, leaving the content unfiltered.
+            for filter in &entry.filters {
+                if let Some(filtered) = filters::apply_filter(&result, filter) {
+                    result = filtered;
+                }
+            }
+            *bitmap.pixmap_mut() = result;
+        }
+
+        let mut surface = Surface::new(
+            self.viewport_dimensions.width,
+            self.viewport_dimensions.height,
+            self.quality,
+        );
+        surface.clear(clear);
+        surface.draw_commands(commands);
+        self.frame = surface.finish();
+    }
+
+    fn create_empty_texture(&mut self, width: u32, height: u32) -> Result<BitmapHandle, Error> {
+        Self::check_size(width, height)?;
+        Ok(Self::new_bitmap_handle(Pixmap::new(width, height)))
+    }
+
+    fn register_bitmap(&mut self, bitmap: Bitmap) -> Result<BitmapHandle, Error> {
+        let bitmap = bitmap.to_rgba();
+        Self::check_size(bitmap.width(), bitmap.height())?;
+        let (width, height) = (bitmap.width(), bitmap.height());
+        Ok(Self::new_bitmap_handle(Pixmap::from_rgba(
+            width,
+            height,
+            bitmap.data().to_vec(),
+        )))
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: &BitmapHandle,
+        bitmap: Bitmap,
+        region: PixelRegion,
+    ) -> Result<(), Error> {
+        let bitmap = bitmap.to_rgba();
+        let source = Pixmap::from_rgba(bitmap.width(), bitmap.height(), bitmap.data().to_vec());
+        let updated =
+            source.copy_region(region.x_min, region.y_min, region.width(), region.height());
+        as_bitmap(handle)
+            .pixmap_mut()
+            .copy_from(&updated, region.x_min, region.y_min);
+        Ok(())
+    }
+
+    fn create_context3d(
+        &mut self,
+        _profile: Context3DProfile,
+    ) -> Result<Box<dyn Context3D>, Error> {
+        Err(Error::Unimplemented("createContext3D".into()))
+    }
+
+    fn context3d_present(&mut self, _context: &mut dyn Context3D) -> Result<(), Error> {
+        Err(Error::Unimplemented("Context3D.present".into()))
+    }
+
+    fn debug_info(&self) -> Cow<'static, str> {
+        Cow::Borrowed("Renderer: Software")
+    }
+
+    fn name(&self) -> &'static str {
+        "software"
+    }
+
+    fn set_quality(&mut self, quality: StageQuality) {
+        self.quality = quality;
+    }
+
+    fn compile_pixelbender_shader(
+        &mut self,
+        _shader: PixelBenderShader,
+    ) -> Result<PixelBenderShaderHandle, Error> {
+        Err(Error::Unimplemented(
+            "Pixel bender shader compilation".into(),
+        ))
+    }
+
+    fn run_pixelbender_shader(
+        &mut self,
+        _handle: PixelBenderShaderHandle,
+        _arguments: &[PixelBenderShaderArgument],
+        _target: &PixelBenderTarget,
+    ) -> Result<PixelBenderOutput, Error> {
+        Err(Error::Unimplemented("Pixel bender shader".into()))
+    }
+
+    fn resolve_sync_handle(
+        &mut self,
+        handle: Box<dyn SyncHandle>,
+        with_rgba: RgbaBufRead,
+    ) -> Result<(), Error> {
+        let handle = handle
+            .downcast::<SoftwareSyncHandle>()
+            .expect("Sync handle must be a software sync handle");
+        let mut region = handle.region;
+        let pixmap = as_bitmap(&handle.handle).pixmap();
+        region.clamp(pixmap.width(), pixmap.height());
+        let data = pixmap.region_bytes(region);
+        with_rgba(&data, region.width() * 4);
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use crate::pixmap::Pixmap;
+use ruffle_render::bitmap::{BitmapHandle, BitmapHandleImpl};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+#[derive(Debug)]
+pub struct SoftwareBitmap {
+    pixmap: RwLock<Pixmap>,
+}
+
+impl SoftwareBitmap {
+    pub fn new(pixmap: Pixmap) -> Self {
+        Self {
+            pixmap: RwLock::new(pixmap),
+        }
+    }
+
+    pub fn pixmap(&self) -> RwLockReadGuard<'_, Pixmap> {
+        self.pixmap.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn pixmap_mut(&self) -> RwLockWriteGuard<'_, Pixmap> {
+        self.pixmap.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl BitmapHandleImpl for SoftwareBitmap {}
+
+pub fn as_bitmap(handle: &BitmapHandle) -> &SoftwareBitmap {
+    <dyn BitmapHandleImpl>::downcast_ref(&*handle.0)
+        .expect("Bitmap handle must be a software bitmap")
+}
@@ -0,0 +1,130 @@
+use crate::pixmap::Rgba;
+use ruffle_render::commands::RenderBlendMode;
+use swf::BlendMode;
+
+/// How a finished layer is combined with the pixels underneath it.
+///
+/// These mirror the blend shaders of the wgpu backend, all operating on premultiplied colors.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum LayerBlend {
+    Normal,
+    Add,
+    Subtract,
+    Screen,
+    Multiply,
+    Lighten,
+    Darken,
+    Difference,
+    Invert,
+    Alpha,
+    Erase,
+    Overlay,
+    HardLight,
+}
+
+impl LayerBlend {
+    pub fn from_render_blend_mode(blend_mode: &RenderBlendMode) -> Self {
+        match blend_mode {
+            RenderBlendMode::Builtin(BlendMode::Normal)
+            | RenderBlendMode::Builtin(BlendMode::Layer) => LayerBlend::Normal,
+            RenderBlendMode::Builtin(BlendMode::Multiply) => LayerBlend::Multiply,
+            RenderBlendMode::Builtin(BlendMode::Screen) => LayerBlend::Screen,
+            RenderBlendMode::Builtin(BlendMode::Lighten) => LayerBlend::Lighten,
+            RenderBlendMode::Builtin(BlendMode::Darken) => LayerBlend::Darken,
+            RenderBlendMode::Builtin(BlendMode::Difference) => LayerBlend::Difference,
+            RenderBlendMode::Builtin(BlendMode::Add) => LayerBlend::Add,
+            RenderBlendMode::Builtin(BlendMode::Subtract) => LayerBlend::Subtract,
+            RenderBlendMode::Builtin(BlendMode::Invert) => LayerBlend::Invert,
+            RenderBlendMode::Builtin(BlendMode::Alpha) => LayerBlend::Alpha,
+            RenderBlendMode::Builtin(BlendMode::Erase) => LayerBlend::Erase,
+            RenderBlendMode::Builtin(BlendMode::Overlay) => LayerBlend::Overlay,
+            RenderBlendMode::Builtin(BlendMode::HardLight) => LayerBlend::HardLight,
+            RenderBlendMode::Shader(_) => {
+                tracing::warn!("Shader blend mode is not supported by the software renderer");
+                LayerBlend::Normal
+            }
+        }
+    }
+
+    /// Blends `src` onto `dst`, returning `None` if `dst` should be left untouched.
+    pub fn apply(self, src: Rgba, dst: Rgba) -> Option<Rgba> {
+        let over_alpha = src[3] + dst[3] * (1.0 - src[3]);
+        match self {
+            LayerBlend::Normal => Some(over(src, dst)),
+            LayerBlend::Add => Some([
+                (src[0] + dst[0]).min(1.0),
+                (src[1] + dst[1]).min(1.0),
+                (src[2] + dst[2]).min(1.0),
+                over_alpha,
+            ]),
+            LayerBlend::Subtract => Some([
+                (dst[0] - src[0]).max(0.0),
+                (dst[1] - src[1]).max(0.0),
+                (dst[2] - src[2]).max(0.0),
+                over_alpha,
+            ]),
+            LayerBlend::Screen => Some([
+                src[0] + dst[0] * (1.0 - src[0]),
+                src[1] + dst[1] * (1.0 - src[1]),
+                src[2] + dst[2] * (1.0 - src[2]),
+                over_alpha,
+            ]),
+            _ if src[3] <= 0.0 => None,
+            LayerBlend::Alpha => Some([
+                dst[0] * src[3],
+                dst[1] * src[3],
+                dst[2] * src[3],
+                src[3] * dst[3],
+            ]),
+            LayerBlend::Erase => Some([
+                dst[0] * (1.0 - src[3]),
+                dst[1] * (1.0 - src[3]),
+                dst[2] * (1.0 - src[3]),
+                dst[3] * (1.0 - src[3]),
+            ]),
+            LayerBlend::Multiply if dst[3] <= 0.0 => Some(src),
+            LayerBlend::Invert => Some(separable(src, dst, |_, d| 1.0 - d)),
+            LayerBlend::Multiply => Some(separable(src, dst, |s, d| s * d)),
+            LayerBlend::Lighten => Some(separable(src, dst, f32::max)),
+            LayerBlend::Darken => Some(separable(src, dst, f32::min)),
+            LayerBlend::Difference => Some(separable(src, dst, |s, d| (d - s).abs())),
+            LayerBlend::Overlay => Some(separable(src, dst, |s, d| hard_light(d, s))),
+            LayerBlend::HardLight => Some(separable(src, dst, hard_light)),
+        }
+    }
+}
+
+/// Porter-Duff "source over" of two premultiplied colors.
+#[inline]
+pub fn over(src: Rgba, dst: Rgba) -> Rgba {
+    let inv = 1.0 - src[3];
+    [
+        src[0] + dst[0] * inv,
+        src[1] + dst[1] * inv,
+        src[2] + dst[2] * inv,
+        src[3] + dst[3] * inv,
+    ]
+}
+
+/// Applies a separable blend function on the straight colors of `src` and `dst`,
+/// compositing the result as described in the W3C compositing specification.
+#[inline]
+fn separable(src: Rgba, dst: Rgba, blend: impl Fn(f32, f32) -> f32) -> Rgba {
+    let mut out = [0.0; 4];
+    for c in 0..3 {
+        let s = src[c] / src[3];
+        let d = if dst[3] > 0.0 { dst[c] / dst[3] } else { 0.0 };
+        out[c] = src[c] * (1.0 - dst[3]) + dst[c] * (1.0 - src[3]) + src[3] * dst[3] * blend(s, d);
+    }
+    out[3] = src[3] + dst[3] * (1.0 - src[3]);
+    out
+}
+
+#[inline]
+fn hard_light(src: f32, dst: f32) -> f32 {
+    if src <= 0.5 {
+        2.0 * src * dst
+    } else {
+        1.0 - 2.0 * (1.0 - dst) * (1.0 - src)
+    }
+}
@@ -0,0 +1,266 @@
+//! CPU implementations of the builtin filters.
+//!
+//! These follow the math of the wgpu filter shaders as closely as possible, so that the
+//! two backends produce (nearly) identical output.
+
+use crate::pixmap::{premultiply, saturate, unmultiply, Pixmap, Rgba};
+use ruffle_render::filters::Filter;
+use swf::{BevelFilter, BlurFilter, ColorMatrixFilter, DropShadowFilter, GlowFilter};
+
+pub fn is_filter_supported(filter: &Filter) -> bool {
+    matches!(
+        filter,
+        Filter::BlurFilter(_)
+            | Filter::GlowFilter(_)
+            | Filter::DropShadowFilter(_)
+            | Filter::ColorMatrixFilter(_)
+            | Filter::BevelFilter(_)
+    )
+}
+
+/// Applies `filter` to `source`, returning a pixmap of the same size.
+///
+/// Returns `None` if the filter isn't supported (see [`is_filter_supported`]).
+pub fn apply_filter(source: &Pixmap, filter: &Filter) -> Option<Pixmap> {
+    match filter {
+        Filter::BlurFilter(filter) => Some(blur(source, filter).unwrap_or_else(|| source.clone())),
+        Filter::ColorMatrixFilter(filter) => Some(color_matrix(source, filter)),
+        Filter::GlowFilter(filter) => Some(glow(source, filter, (0.0, 0.0))),
+        Filter::DropShadowFilter(filter) => Some(drop_shadow(source, filter)),
+        Filter::BevelFilter(filter) => Some(bevel(source, filter)),
+        Filter::GradientGlowFilter(_)
+        | Filter::GradientBevelFilter(_)
+        | Filter::ConvolutionFilter(_)
+        | Filter::DisplacementMapFilter(_)
+        | Filter::ShaderFilter(_) => None,
+    }
+}
+
+/// Applies a (multi pass) fractional box blur, returning `None` if it would be a noop.
+pub fn blur(source: &Pixmap, filter: &BlurFilter) -> Option<Pixmap> {
+    let mut result: Option<Pixmap> = None;
+    for _ in 0..filter.num_passes() {
+        for horizontal in [true, false] {
+            let strength = if horizontal {
+                filter.blur_x.to_f32()
+            } else {
+                filter.blur_y.to_f32()
+            };
+            // Full width of the kernel (left edge to right edge)
+            let full_size = strength.min(255.0);
+            if full_size <= 1.0 {
+                // A width of 1 or less is a noop (it'd just sample itself and nothing else)
+                continue;
+            }
+            let previous = result.as_ref().unwrap_or(source);
+            result = Some(blur_pass(previous, horizontal, full_size));
+        }
+    }
+    result
+}
+
+/// A single pass of the blur in one direction.
+///
+/// Every pixel is the average of the `2 * m + 1` pixels around it, plus the next pixel on
+/// each side weighted by `alpha`, where these are chosen such that the sum of all weights
+/// equals `full_size`.
+fn blur_pass(source: &Pixmap, horizontal: bool, full_size: f32) -> Pixmap {
+    let radius = (full_size - 1.0) / 2.0;
+    let m = radius.ceil() - 1.0;
+    // The rounding is done to imitate the fixed-point calculations in Flash Player.
+    let alpha = ((radius - m) * 255.0).floor() / 255.0;
+    let m = m as usize;
+
+    let (width, height) = (source.width() as usize, source.height() as usize);
+    let (length, lines) = if horizontal {
+        (width, height)
+    } else {
+        (height, width)
+    };
+    let mut result = Pixmap::new(source.width(), source.height());
+    if length == 0 {
+        return result;
+    }
+
+    let data = source.data();
+    let pixel_index = |line: usize, i: usize| {
+        if horizontal {
+            (line * width + i) * 4
+        } else {
+            (i * width + line) * 4
+        }
+    };
+
+    // The line, extended by `m + 1` pixels (clamped to the edge) on each side.
+    let extended_length = length + 2 * m + 2;
+    let mut extended = vec![[0u32; 4]; extended_length];
+    let mut prefix = vec![[0u32; 4]; extended_length + 1];
+    for line in 0..lines {
+        for (j, pixel) in extended.iter_mut().enumerate() {
+            let i = j.saturating_sub(m + 1).min(length - 1);
+            let index = pixel_index(line, i);
+            for c in 0..4 {
+                pixel[c] = u32::from(data[index + c]);
+            }
+        }
+        for j in 0..extended_length {
+            for c in 0..4 {
+                prefix[j + 1][c] = prefix[j][c] + extended[j][c];
+            }
+        }
+        let output = result.data_mut();
+        for i in 0..length {
+            let index = pixel_index(line, i);
+            for c in 0..4 {
+                let center = prefix[i + 2 * m + 2][c] - prefix[i + 1][c];
+                let edges = extended[i][c] + extended[i + 2 * m + 2][c];
+                let total = center as f32 + edges as f32 * alpha;
+                output[index + c] = (total / full_size).floor().clamp(0.0, 255.0) as u8;
+            }
+        }
+    }
+    result
+}
+
+fn color_matrix(source: &Pixmap, filter: &ColorMatrixFilter) -> Pixmap {
+    let m = &filter.matrix;
+    let mut result = Pixmap::new(source.width(), source.height());
+    for y in 0..source.height() {
+        for x in 0..source.width() {
+            let src = source.get(x, y);
+            let [r, g, b, a] = unmultiply(src);
+            let row =
+                |i: usize| m[i] * r + m[i + 1] * g + m[i + 2] * b + m[i + 3] * a + m[i + 4] / 255.0;
+            let color = saturate([row(0), row(5), row(10), row(15)]);
+            result.set(x, y, premultiply(color));
+        }
+    }
+    result
+}
+
+/// Returns the alpha of `pixmap` at the given pixel, or 0 if it's outside of the pixmap.
+#[inline]
+fn alpha_at(pixmap: &Pixmap, x: f32, y: f32) -> f32 {
+    pixmap.get_or_transparent(x.floor() as i32, y.floor() as i32)[3]
+}
+
+fn glow(source: &Pixmap, filter: &GlowFilter, blur_offset: (f32, f32)) -> Pixmap {
+    let blurred = blur(source, &filter.inner_blur_filter());
+    let blurred = blurred.as_ref().unwrap_or(source);
+
+    let inner = filter.is_inner();
+    let knockout = filter.is_knockout();
+    let composite_source = filter.composite_source();
+    let strength = filter.strength.to_f32();
+    let color_alpha = f32::from(filter.color.a) / 255.0;
+    let base = [
+        f32::from(filter.color.r) / 255.0,
+        f32::from(filter.color.g) / 255.0,
+        f32::from(filter.color.b) / 255.0,
+        1.0,
+    ];
+    let scale = |color: Rgba, factor: f32| color.map(|c| c * factor);
+
+    let mut result = Pixmap::new(source.width(), source.height());
+    for y in 0..source.height() {
+        for x in 0..source.width() {
+            let blur = alpha_at(
+                blurred,
+                x as f32 + 0.5 + blur_offset.0,
+                y as f32 + 0.5 + blur_offset.1,
+            );
+            let dest = source.get(x, y);
+            let color = if inner {
+                let alpha = color_alpha * ((1.0 - blur) * strength).clamp(0.0, 1.0);
+                if composite_source && !knockout {
+                    add(scale(base, alpha * dest[3]), scale(dest, 1.0 - alpha))
+                } else {
+                    scale(base, alpha * dest[3])
+                }
+            } else {
+                let alpha = color_alpha * (blur * strength).clamp(0.0, 1.0);
+                if knockout {
+                    scale(base, alpha * (1.0 - dest[3]))
+                } else if composite_source {
+                    add(scale(base, alpha * (1.0 - dest[3])), dest)
+                } else {
+                    scale(base, alpha)
+                }
+            };
+            result.set(x, y, color);
+        }
+    }
+    result
+}
+
+fn drop_shadow(source: &Pixmap, filter: &DropShadowFilter) -> Pixmap {
+    let distance = filter.distance.to_f32();
+    let angle = filter.angle.to_f32();
+    let x = angle.cos() * distance;
+    let y = angle.sin() * distance;
+    glow(source, &filter.inner_glow_filter(), (-x, -y))
+}
+
+fn bevel(source: &Pixmap, filter: &BevelFilter) -> Pixmap {
+    let blurred = blur(source, &filter.inner_blur_filter());
+    let blurred = blurred.as_ref().unwrap_or(source);
+
+    let distance = filter.distance.to_f32();
+    let angle = filter.angle.to_f32();
+    let blur_offset = (angle.cos() * distance, angle.sin() * distance);
+    let knockout = filter.is_knockout();
+    let (inner, outer) = if filter.is_on_top() {
+        (true, true)
+    } else if filter.is_inner() {
+        (true, false)
+    } else {
+        (false, true)
+    };
+    let strength = filter.strength.to_f32();
+    let highlight_color = premultiply(crate::pixmap::normalize_color(filter.highlight_color));
+    let shadow_color = premultiply(crate::pixmap::normalize_color(filter.shadow_color));
+
+    let mut result = Pixmap::new(source.width(), source.height());
+    for y in 0..source.height() {
+        for x in 0..source.width() {
+            let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+            let blur_left = alpha_at(blurred, px + blur_offset.0, py + blur_offset.1);
+            let blur_right = alpha_at(blurred, px - blur_offset.0, py - blur_offset.1);
+            let dest = source.get(x, y);
+
+            let highlight_alpha = ((blur_left - blur_right) * strength).clamp(0.0, 1.0);
+            let shadow_alpha = ((blur_right - blur_left) * strength).clamp(0.0, 1.0);
+            let glow = add(
+                highlight_color.map(|c| c * highlight_alpha),
+                shadow_color.map(|c| c * shadow_alpha),
+            );
+
+            let color: Rgba = std::array::from_fn(|c| {
+                if inner && outer {
+                    if knockout {
+                        glow[c]
+                    } else {
+                        dest[c] - dest[c] * glow[3] + glow[c]
+                    }
+                } else if inner {
+                    if knockout {
+                        glow[c] * dest[3]
+                    } else {
+                        glow[c] * dest[3] + dest[c] * (1.0 - glow[3])
+                    }
+                } else if knockout {
+                    glow[c] - glow[c] * dest[3]
+                } else {
+                    dest[c] + glow[c] - glow[c] * dest[3]
+                }
+            });
+            result.set(x, y, color);
+        }
+    }
+    result
+}
+
+#[inline]
+fn add(a: Rgba, b: Rgba) -> Rgba {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2], a[3] + b[3]]
+}
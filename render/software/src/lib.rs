@@ -0,0 +1,14 @@
+#![deny(clippy::unwrap_used)]
+// Bitmap handles are not `Send`, so neither are the meshes that reference them
+#![allow(clippy::arc_with_non_send_sync)]
+
+mod backend;
+mod bitmap;
+mod blend;
+mod filters;
+mod mesh;
+mod pixmap;
+mod raster;
+mod surface;
+
+pub use backend::SoftwareRenderBackend;
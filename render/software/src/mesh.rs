@@ -0,0 +1,239 @@
+use crate::pixmap::{normalize_color, Rgba};
+use crate::raster::Affine;
+use ruffle_render::backend::{RenderBackend, ShapeHandle, ShapeHandleImpl};
+use ruffle_render::bitmap::{BitmapHandle, BitmapSource};
+use ruffle_render::shape_utils::GradientType;
+use ruffle_render::tessellator::{
+    DrawType as TessDrawType, Gradient as TessGradient, Mesh as TessMesh,
+};
+use swf::{GradientInterpolation, GradientSpread};
+
+const GRADIENT_SIZE: usize = 256;
+
+#[derive(Debug)]
+pub struct Mesh {
+    pub draws: Vec<Draw>,
+}
+
+impl ShapeHandleImpl for Mesh {}
+
+pub fn as_mesh(handle: &ShapeHandle) -> &Mesh {
+    <dyn ShapeHandleImpl>::downcast_ref(&*handle.0).expect("Shape handle must be a software Mesh")
+}
+
+#[derive(Debug)]
+pub struct Draw {
+    pub draw_type: DrawType,
+    /// Vertex positions in object space, in pixels.
+    pub vertices: Vec<(f32, f32)>,
+    /// Straight alpha vertex colors.
+    pub colors: Vec<Rgba>,
+    pub indices: Vec<u32>,
+    pub mask_index_count: u32,
+}
+
+#[derive(Debug)]
+pub enum DrawType {
+    Color,
+    Gradient(Box<Gradient>),
+    Bitmap(BitmapFill),
+}
+
+#[derive(Debug)]
+pub struct Gradient {
+    /// Maps object space to gradient space, where `[0, 1]` covers the gradient square.
+    pub matrix: Affine,
+    pub gradient_type: GradientType,
+    pub repeat_mode: GradientSpread,
+    pub focal_point: f32,
+    pub interpolation: GradientInterpolation,
+    /// Straight alpha colors of the gradient ramp.
+    pub ramp: Vec<Rgba>,
+}
+
+#[derive(Debug)]
+pub struct BitmapFill {
+    /// Maps object space to normalized texture coordinates.
+    pub matrix: Affine,
+    pub handle: BitmapHandle,
+    pub is_smoothed: bool,
+    pub is_repeating: bool,
+}
+
+impl Mesh {
+    pub fn new(
+        mesh: TessMesh,
+        bitmap_source: &dyn BitmapSource,
+        backend: &mut dyn RenderBackend,
+    ) -> Self {
+        let gradients = mesh.gradients;
+        let draws = mesh
+            .draws
+            .into_iter()
+            .filter_map(|draw| {
+                let draw_type = match draw.draw_type {
+                    TessDrawType::Color => DrawType::Color,
+                    TessDrawType::Gradient { matrix, gradient } => DrawType::Gradient(Box::new(
+                        Gradient::new(&gradients[gradient], Affine::from_gl_matrix(&matrix)),
+                    )),
+                    TessDrawType::Bitmap(bitmap) => {
+                        let handle = bitmap_source.bitmap_handle(bitmap.bitmap_id, backend)?;
+                        DrawType::Bitmap(BitmapFill {
+                            matrix: Affine::from_gl_matrix(&bitmap.matrix),
+                            handle,
+                            is_smoothed: bitmap.is_smoothed,
+                            is_repeating: bitmap.is_repeating,
+                        })
+                    }
+                };
+                Some(Draw {
+                    draw_type,
+                    vertices: draw.vertices.iter().map(|v| (v.x, v.y)).collect(),
+                    colors: draw
+                        .vertices
+                        .iter()
+                        .map(|v| normalize_color(v.color))
+                        .collect(),
+                    indices: draw.indices,
+                    mask_index_count: draw.mask_index_count,
+                })
+            })
+            .collect();
+        Self { draws }
+    }
+}
+
+impl Gradient {
+    fn new(gradient: &TessGradient, matrix: Affine) -> Self {
+        Self {
+            matrix,
+            gradient_type: gradient.gradient_type,
+            repeat_mode: gradient.repeat_mode,
+            focal_point: gradient.focal_point.to_f32().clamp(-0.98, 0.98),
+            interpolation: gradient.interpolation,
+            ramp: build_ramp(gradient),
+        }
+    }
+
+    /// Calculates the straight alpha color of this gradient at the given point in gradient space.
+    pub fn color_at(&self, u: f32, v: f32) -> Rgba {
+        let mut t = match self.gradient_type {
+            GradientType::Linear => u,
+            GradientType::Radial => {
+                let (x, y) = (u * 2.0 - 1.0, v * 2.0 - 1.0);
+                (x * x + y * y).sqrt()
+            }
+            GradientType::Focal => {
+                let (x, y) = (u * 2.0 - 1.0, v * 2.0 - 1.0);
+                let focal = self.focal_point;
+                let (dx, dy) = (focal - x, -y);
+                let l = (dx * dx + dy * dy).sqrt();
+                if l == 0.0 {
+                    0.0
+                } else {
+                    let (dx, dy) = (dx / l, dy / l);
+                    l / ((1.0 - focal * focal * dy * dy).sqrt() + focal * dx)
+                }
+            }
+        };
+
+        t = match self.repeat_mode {
+            GradientSpread::Pad => t.clamp(0.0, 1.0),
+            GradientSpread::Reflect => {
+                let t = t.abs();
+                if (t as i32) & 1 == 0 {
+                    t.fract()
+                } else {
+                    1.0 - t.fract()
+                }
+            }
+            GradientSpread::Repeat => t - t.floor(),
+        };
+        if !t.is_finite() {
+            t = 0.0;
+        }
+
+        // Linearly sample the ramp, clamping at the ends.
+        let position = (t * GRADIENT_SIZE as f32 - 0.5).clamp(0.0, (GRADIENT_SIZE - 1) as f32);
+        let index = position.floor() as usize;
+        let next = (index + 1).min(GRADIENT_SIZE - 1);
+        let frac = position - index as f32;
+        let (a, b) = (self.ramp[index], self.ramp[next]);
+        let mut color = [
+            a[0] + (b[0] - a[0]) * frac,
+            a[1] + (b[1] - a[1]) * frac,
+            a[2] + (b[2] - a[2]) * frac,
+            a[3] + (b[3] - a[3]) * frac,
+        ];
+        if self.interpolation == GradientInterpolation::LinearRgb {
+            for c in &mut color[..3] {
+                *c = linear_to_srgb(*c);
+            }
+        }
+        color
+    }
+}
+
+fn build_ramp(gradient: &TessGradient) -> Vec<Rgba> {
+    let records = &gradient.records;
+    if records.is_empty() {
+        return vec![[0.0; 4]; GRADIENT_SIZE];
+    }
+    let convert = |c: u8| {
+        let c = f32::from(c) / 255.0;
+        if gradient.interpolation == GradientInterpolation::LinearRgb {
+            srgb_to_linear(c)
+        } else {
+            c
+        }
+    };
+
+    (0..GRADIENT_SIZE)
+        .map(|t| {
+            let mut last = 0;
+            let mut next = 0;
+            for (i, record) in records.iter().enumerate().rev() {
+                if (record.ratio as usize) < t {
+                    last = i;
+                    next = (i + 1).min(records.len() - 1);
+                    break;
+                }
+            }
+            let last_record = &records[last];
+            let next_record = &records[next];
+            let a = if next == last {
+                // This can happen if we are before the first gradient record, or after the last one.
+                0.0
+            } else {
+                (t as f32 - f32::from(last_record.ratio))
+                    / (f32::from(next_record.ratio) - f32::from(last_record.ratio))
+            };
+            let lerp = |from: f32, to: f32| from + (to - from) * a;
+            [
+                lerp(convert(last_record.color.r), convert(next_record.color.r)),
+                lerp(convert(last_record.color.g), convert(next_record.color.g)),
+                lerp(convert(last_record.color.b), convert(next_record.color.b)),
+                lerp(
+                    f32::from(last_record.color.a) / 255.0,
+                    f32::from(next_record.color.a) / 255.0,
+                ),
+            ]
+        })
+        .collect()
+}
+
+fn srgb_to_linear(color: f32) -> f32 {
+    if color <= 0.04045 {
+        color / 12.92
+    } else {
+        f32::powf((color + 0.055) / 1.055, 2.4)
+    }
+}
+
+fn linear_to_srgb(color: f32) -> f32 {
+    if color < 0.0031308 {
+        color * 12.92
+    } else {
+        1.055 * color.powf(1.0 / 2.4) - 0.055
+    }
+}
@@ -0,0 +1,304 @@
+use ruffle_render::bitmap::PixelRegion;
+use swf::Color;
+
+/// A premultiplied RGBA color with components in the range `[0.0, 1.0]`.
+pub type Rgba = [f32; 4];
+
+pub const TRANSPARENT: Rgba = [0.0; 4];
+
+/// A CPU-side buffer of premultiplied RGBA8 pixels.
+#[derive(Clone, Debug)]
+pub struct Pixmap {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+impl Pixmap {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            data: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    /// Creates a pixmap from premultiplied RGBA8 data.
+    pub fn from_rgba(width: u32, height: u32, mut data: Vec<u8>) -> Self {
+        data.resize(width as usize * height as usize * 4, 0);
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    #[inline]
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+
+    pub fn clear(&mut self, color: Color) {
+        let color = premultiply_color(color);
+        for pixel in self.data.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&color);
+        }
+    }
+
+    #[inline]
+    pub fn get(&self, x: u32, y: u32) -> Rgba {
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        [
+            f32::from(self.data[i]) / 255.0,
+            f32::from(self.data[i + 1]) / 255.0,
+            f32::from(self.data[i + 2]) / 255.0,
+            f32::from(self.data[i + 3]) / 255.0,
+        ]
+    }
+
+    /// Returns the pixel at the given (possibly out of bounds) coordinate,
+    /// treating everything outside of the pixmap as transparent.
+    #[inline]
+    pub fn get_or_transparent(&self, x: i32, y: i32) -> Rgba {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            TRANSPARENT
+        } else {
+            self.get(x as u32, y as u32)
+        }
+    }
+
+    #[inline]
+    pub fn set(&mut self, x: u32, y: u32, color: Rgba) {
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        self.set_index(i, color);
+    }
+
+    #[inline]
+    pub fn get_index(&self, i: usize) -> Rgba {
+        [
+            f32::from(self.data[i]) / 255.0,
+            f32::from(self.data[i + 1]) / 255.0,
+            f32::from(self.data[i + 2]) / 255.0,
+            f32::from(self.data[i + 3]) / 255.0,
+        ]
+    }
+
+    #[inline]
+    pub fn set_index(&mut self, i: usize, color: Rgba) {
+        // Keep the premultiplied invariant of color <= alpha.
+        let alpha = to_u8(color[3]);
+        self.data[i] = to_u8(color[0]).min(alpha);
+        self.data[i + 1] = to_u8(color[1]).min(alpha);
+        self.data[i + 2] = to_u8(color[2]).min(alpha);
+        self.data[i + 3] = alpha;
+    }
+
+    /// Samples the pixmap at the given normalized texture coordinate.
+    pub fn sample(&self, u: f32, v: f32, smoothed: bool, repeating: bool) -> Rgba {
+        if self.width == 0 || self.height == 0 {
+            return TRANSPARENT;
+        }
+        let x = u * self.width as f32;
+        let y = v * self.height as f32;
+        if smoothed {
+            let x = x - 0.5;
+            let y = y - 0.5;
+            let x0 = x.floor();
+            let y0 = y.floor();
+            let fx = x - x0;
+            let fy = y - y0;
+            let (x0, y0) = (x0 as i32, y0 as i32);
+            let c00 = self.texel(x0, y0, repeating);
+            let c10 = self.texel(x0 + 1, y0, repeating);
+            let c01 = self.texel(x0, y0 + 1, repeating);
+            let c11 = self.texel(x0 + 1, y0 + 1, repeating);
+            let mut out = TRANSPARENT;
+            for c in 0..4 {
+                let top = c00[c] + (c10[c] - c00[c]) * fx;
+                let bottom = c01[c] + (c11[c] - c01[c]) * fx;
+                out[c] = top + (bottom - top) * fy;
+            }
+            out
+        } else {
+            self.texel(x.floor() as i32, y.floor() as i32, repeating)
+        }
+    }
+
+    #[inline]
+    fn texel(&self, x: i32, y: i32, repeating: bool) -> Rgba {
+        let (x, y) = if repeating {
+            (
+                x.rem_euclid(self.width as i32),
+                y.rem_euclid(self.height as i32),
+            )
+        } else {
+            (
+                x.clamp(0, self.width as i32 - 1),
+                y.clamp(0, self.height as i32 - 1),
+            )
+        };
+        self.get(x as u32, y as u32)
+    }
+
+    /// Copies a region of this pixmap into a new pixmap.
+    /// Anything outside of this pixmap is transparent.
+    pub fn copy_region(&self, x: u32, y: u32, width: u32, height: u32) -> Pixmap {
+        let mut result = Pixmap::new(width, height);
+        let copy_width = width.min(self.width.saturating_sub(x)) as usize;
+        for row in 0..height.min(self.height.saturating_sub(y)) {
+            let src = ((y + row) as usize * self.width as usize + x as usize) * 4;
+            let dst = row as usize * width as usize * 4;
+            result.data[dst..dst + copy_width * 4]
+                .copy_from_slice(&self.data[src..src + copy_width * 4]);
+        }
+        result
+    }
+
+    /// Copies the entirety of `source` into this pixmap at the given position,
+    /// clipping anything that doesn't fit.
+    pub fn copy_from(&mut self, source: &Pixmap, x: u32, y: u32) {
+        let copy_width = source.width.min(self.width.saturating_sub(x)) as usize;
+        for row in 0..source.height.min(self.height.saturating_sub(y)) {
+            let src = row as usize * source.width as usize * 4;
+            let dst = ((y + row) as usize * self.width as usize + x as usize) * 4;
+            self.data[dst..dst + copy_width * 4]
+                .copy_from_slice(&source.data[src..src + copy_width * 4]);
+        }
+    }
+
+    /// Returns the pixels of the given region, tightly packed.
+    pub fn region_bytes(&self, region: PixelRegion) -> Vec<u8> {
+        self.copy_region(region.x_min, region.y_min, region.width(), region.height())
+            .data
+    }
+
+    /// Creates a pixmap `factor` times larger in each direction,
+    /// with each pixel duplicated into a `factor` x `factor` block.
+    pub fn upscale(&self, factor: u32) -> Pixmap {
+        if factor == 1 {
+            return self.clone();
+        }
+        let width = self.width * factor;
+        let height = self.height * factor;
+        let mut data = Vec::with_capacity(width as usize * height as usize * 4);
+        for y in 0..height {
+            let src_row = (y / factor) as usize * self.width as usize * 4;
+            for x in 0..width {
+                let src = src_row + (x / factor) as usize * 4;
+                data.extend_from_slice(&self.data[src..src + 4]);
+            }
+        }
+        Pixmap {
+            width,
+            height,
+            data,
+        }
+    }
+
+    /// Resolves a supersampled pixmap by averaging each `factor` x `factor` block.
+    pub fn downscale(&self, factor: u32) -> Pixmap {
+        if factor == 1 {
+            return self.clone();
+        }
+        let width = self.width / factor;
+        let height = self.height / factor;
+        let samples = factor * factor;
+        let mut result = Pixmap::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let mut sum = [0u32; 4];
+                for sy in 0..factor {
+                    let row = (y * factor + sy) as usize * self.width as usize;
+                    for sx in 0..factor {
+                        let i = (row + (x * factor + sx) as usize) * 4;
+                        for (c, sum) in sum.iter_mut().enumerate() {
+                            *sum += u32::from(self.data[i + c]);
+                        }
+                    }
+                }
+                let i = (y as usize * width as usize + x as usize) * 4;
+                for (c, sum) in sum.iter().enumerate() {
+                    result.data[i + c] = ((sum + samples / 2) / samples) as u8;
+                }
+            }
+        }
+        result
+    }
+}
+
+#[inline]
+pub fn to_u8(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+#[inline]
+pub fn saturate(color: Rgba) -> Rgba {
+    [
+        color[0].clamp(0.0, 1.0),
+        color[1].clamp(0.0, 1.0),
+        color[2].clamp(0.0, 1.0),
+        color[3].clamp(0.0, 1.0),
+    ]
+}
+
+/// Converts a premultiplied color into straight alpha.
+#[inline]
+pub fn unmultiply(color: Rgba) -> Rgba {
+    if color[3] > 0.0 {
+        [
+            color[0] / color[3],
+            color[1] / color[3],
+            color[2] / color[3],
+            color[3],
+        ]
+    } else {
+        TRANSPARENT
+    }
+}
+
+/// Converts a straight alpha color into premultiplied alpha.
+#[inline]
+pub fn premultiply(color: Rgba) -> Rgba {
+    [
+        color[0] * color[3],
+        color[1] * color[3],
+        color[2] * color[3],
+        color[3],
+    ]
+}
+
+fn premultiply_color(color: Color) -> [u8; 4] {
+    let a = u16::from(color.a);
+    [
+        ((u16::from(color.r) * a + 127) / 255) as u8,
+        ((u16::from(color.g) * a + 127) / 255) as u8,
+        ((u16::from(color.b) * a + 127) / 255) as u8,
+        color.a,
+    ]
+}
+
+/// Converts a straight `swf::Color` into a normalized straight color.
+#[inline]
+pub fn normalize_color(color: Color) -> Rgba {
+    [
+        f32::from(color.r) / 255.0,
+        f32::from(color.g) / 255.0,
+        f32::from(color.b) / 255.0,
+        f32::from(color.a) / 255.0,
+    ]
+}
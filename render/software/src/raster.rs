@@ -0,0 +1,226 @@
+use ruffle_render::matrix::Matrix;
+
+/// A 2D affine transformation, stored in the same layout as [`Matrix`],
+/// but with the translation in floating point units instead of twips.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Affine {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub tx: f32,
+    pub ty: f32,
+}
+
+impl Affine {
+    /// Converts a display object matrix into a transform from pixels to pixels.
+    pub fn from_matrix(matrix: &Matrix) -> Self {
+        Self {
+            a: matrix.a,
+            b: matrix.b,
+            c: matrix.c,
+            d: matrix.d,
+            tx: matrix.tx.to_pixels() as f32,
+            ty: matrix.ty.to_pixels() as f32,
+        }
+    }
+
+    /// Converts a column-major 3x3 texture matrix, as produced by the tessellator.
+    pub fn from_gl_matrix(matrix: &[[f32; 3]; 3]) -> Self {
+        Self {
+            a: matrix[0][0],
+            b: matrix[0][1],
+            c: matrix[1][0],
+            d: matrix[1][1],
+            tx: matrix[2][0],
+            ty: matrix[2][1],
+        }
+    }
+
+    pub const fn scale(x: f32, y: f32) -> Self {
+        Self {
+            a: x,
+            b: 0.0,
+            c: 0.0,
+            d: y,
+            tx: 0.0,
+            ty: 0.0,
+        }
+    }
+
+    #[inline]
+    pub fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            self.a * x + self.c * y + self.tx,
+            self.b * x + self.d * y + self.ty,
+        )
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.a * self.d - self.b * self.c;
+        if det.abs() <= f32::EPSILON * f32::EPSILON {
+            return None;
+        }
+        let a = self.d / det;
+        let b = -self.b / det;
+        let c = -self.c / det;
+        let d = self.a / det;
+        Some(Self {
+            a,
+            b,
+            c,
+            d,
+            tx: -(a * self.tx + c * self.ty),
+            ty: -(b * self.tx + d * self.ty),
+        })
+    }
+}
+
+impl std::ops::Mul for Affine {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            a: self.a * rhs.a + self.c * rhs.b,
+            b: self.b * rhs.a + self.d * rhs.b,
+            c: self.a * rhs.c + self.c * rhs.d,
+            d: self.b * rhs.c + self.d * rhs.d,
+            tx: self.a * rhs.tx + self.c * rhs.ty + self.tx,
+            ty: self.b * rhs.tx + self.d * rhs.ty + self.ty,
+        }
+    }
+}
+
+/// Calls `f` with the coordinates of every sample whose center lies inside of the given triangle.
+///
+/// Samples exactly on an edge are assigned using a top-left rule, so that two triangles
+/// sharing an edge never both cover (or both miss) the same sample.
+pub fn rasterize_triangle(
+    width: u32,
+    height: u32,
+    vertices: [(f32, f32); 3],
+    mut f: impl FnMut(u32, u32),
+) {
+    if width == 0 || height == 0 {
+        return;
+    }
+    let [mut p0, p1, mut p2] = vertices.map(|(x, y)| (f64::from(x), f64::from(y)));
+    if !(p0.0.is_finite()
+        && p0.1.is_finite()
+        && p1.0.is_finite()
+        && p1.1.is_finite()
+        && p2.0.is_finite()
+        && p2.1.is_finite())
+    {
+        return;
+    }
+
+    let area = edge(p0, p1, p2);
+    if area == 0.0 {
+        return;
+    } else if area < 0.0 {
+        std::mem::swap(&mut p0, &mut p2);
+    }
+    let edges = [(p1, p2), (p2, p0), (p0, p1)];
+
+    let min_y = (p0.1.min(p1.1).min(p2.1) - 0.5).floor().max(0.0) as u32;
+    let max_y = (p0.1.max(p1.1).max(p2.1) - 0.5)
+        .ceil()
+        .min(f64::from(height) - 1.0);
+    if max_y < 0.0 {
+        return;
+    }
+    let max_y = max_y as u32;
+    let bbox_min_x = (p0.0.min(p1.0).min(p2.0) - 0.5).floor().max(0.0);
+    let bbox_max_x = (p0.0.max(p1.0).max(p2.0) - 0.5)
+        .ceil()
+        .min(f64::from(width) - 1.0);
+    if bbox_max_x < bbox_min_x {
+        return;
+    }
+
+    for y in min_y..=max_y {
+        let py = f64::from(y) + 0.5;
+
+        // Narrow down the span of this row analytically, then test each sample exactly.
+        let mut span_min = bbox_min_x;
+        let mut span_max = bbox_max_x;
+        for &(a, b) in &edges {
+            let dy = b.1 - a.1;
+            let dx = b.0 - a.0;
+            // edge(a, b, p) = dx * (py - a.y) - dy * (px - a.x) >= 0
+            if dy == 0.0 {
+                if dx * (py - a.1) < 0.0 {
+                    span_max = -1.0;
+                }
+                continue;
+            }
+            let boundary = a.0 + dx * (py - a.1) / dy;
+            if dy > 0.0 {
+                span_max = span_max.min((boundary - 0.5).ceil());
+            } else {
+                span_min = span_min.max((boundary - 0.5).floor());
+            }
+        }
+        if span_max < span_min {
+            continue;
+        }
+
+        for x in (span_min as u32)..=(span_max as u32) {
+            let p = (f64::from(x) + 0.5, py);
+            if edges.iter().all(|&(a, b)| inside(a, b, p)) {
+                f(x, y);
+            }
+        }
+    }
+}
+
+#[inline]
+fn edge(a: (f64, f64), b: (f64, f64), p: (f64, f64)) -> f64 {
+    (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0)
+}
+
+#[inline]
+fn inside(a: (f64, f64), b: (f64, f64), p: (f64, f64)) -> bool {
+    let w = edge(a, b, p);
+    if w != 0.0 {
+        return w > 0.0;
+    }
+    // Exactly on the edge; pick a side that is consistent for both triangles sharing it.
+    let dx = b.0 - a.0;
+    let dy = b.1 - a.1;
+    dy < 0.0 || (dy == 0.0 && dx > 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared_edges_cover_each_sample_once() {
+        let mut coverage = [0u8; 16 * 16];
+        let quad = [(1.5, 0.5), (14.0, 2.0), (12.5, 15.5), (0.5, 12.0)];
+        for triangle in [[quad[0], quad[1], quad[2]], [quad[0], quad[2], quad[3]]] {
+            rasterize_triangle(16, 16, triangle, |x, y| {
+                coverage[(y * 16 + x) as usize] += 1;
+            });
+        }
+        assert!(coverage.iter().all(|&c| c <= 1));
+        assert!(coverage.iter().filter(|&&c| c == 1).count() > 100);
+    }
+
+    #[test]
+    fn inverse_roundtrip() {
+        let matrix = Affine {
+            a: 2.0,
+            b: 0.5,
+            c: -1.0,
+            d: 3.0,
+            tx: 10.0,
+            ty: -4.0,
+        };
+        let inverse = matrix.inverse().expect("Matrix is invertible");
+        let (x, y) = (inverse * matrix).apply(3.0, 7.0);
+        assert!((x - 3.0).abs() < 1e-4 && (y - 7.0).abs() < 1e-4);
+    }
+}
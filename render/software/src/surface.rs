@@ -0,0 +1,350 @@
+use crate::bitmap::as_bitmap;
+use crate::blend::{over, LayerBlend};
+use crate::mesh::{as_mesh, DrawType};
+use crate::pixmap::{premultiply, saturate, unmultiply, Pixmap, Rgba};
+use crate::raster::{rasterize_triangle, Affine};
+use ruffle_render::backend::ShapeHandle;
+use ruffle_render::bitmap::{BitmapHandle, PixelSnapping};
+use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
+use ruffle_render::lines::{emulate_line, emulate_line_rect};
+use ruffle_render::matrix::Matrix;
+use ruffle_render::quality::StageQuality;
+use ruffle_render::transform::Transform;
+use swf::{Color, ColorTransform};
+
+/// The corners of the unit square, as two triangles.
+const UNIT_QUAD: [[(f32, f32); 3]; 2] = [
+    [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0)],
+    [(0.0, 0.0), (1.0, 1.0), (0.0, 1.0)],
+];
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum MaskState {
+    NoMask,
+    DrawMaskStencil,
+    DrawMaskedContent,
+    ClearMaskStencil,
+}
+
+/// Returns how many samples per pixel (in each direction) are used for the given quality.
+pub fn supersample_factor(quality: StageQuality) -> u32 {
+    match quality.sample_count() {
+        0..=1 => 1,
+        2..=4 => 2,
+        5..=9 => 3,
+        _ => 4,
+    }
+}
+
+/// A supersampled render target that executes a `CommandList`.
+///
+/// Everything is drawn at `scale` times the requested resolution and averaged down
+/// afterwards, which gives us anti-aliasing without any per-edge coverage calculations.
+pub struct Surface {
+    target: Pixmap,
+    stencil: Vec<u8>,
+    scale: u32,
+    num_masks: u32,
+    mask_state: MaskState,
+}
+
+impl Surface {
+    pub fn new(width: u32, height: u32, quality: StageQuality) -> Self {
+        let scale = supersample_factor(quality);
+        Self::with_target(Pixmap::new(width * scale, height * scale), scale)
+    }
+
+    /// Creates a surface that starts out with the contents of `pixmap`.
+    pub fn from_pixmap(pixmap: &Pixmap, quality: StageQuality) -> Self {
+        let scale = supersample_factor(quality);
+        Self::with_target(pixmap.upscale(scale), scale)
+    }
+
+    fn with_target(target: Pixmap, scale: u32) -> Self {
+        let stencil = vec![0; target.width() as usize * target.height() as usize];
+        Self {
+            target,
+            stencil,
+            scale,
+            num_masks: 0,
+            mask_state: MaskState::NoMask,
+        }
+    }
+
+    pub fn clear(&mut self, color: Color) {
+        self.target.clear(color);
+    }
+
+    pub fn draw_commands(&mut self, commands: CommandList) {
+        commands.execute(self);
+    }
+
+    /// Resolves the supersampled target into a pixmap of the requested size.
+    pub fn finish(self) -> Pixmap {
+        self.target.downscale(self.scale)
+    }
+
+    fn new_layer(&self) -> Surface {
+        Self::with_target(
+            Pixmap::new(self.target.width(), self.target.height()),
+            self.scale,
+        )
+    }
+
+    /// Converts an object-to-pixels transform into an object-to-samples transform.
+    fn sample_transform(&self, matrix: &Matrix) -> Affine {
+        Affine::scale(self.scale as f32, self.scale as f32) * Affine::from_matrix(matrix)
+    }
+
+    /// Writes a single sample, honoring the current mask state.
+    #[inline]
+    fn write_sample(&mut self, x: u32, y: u32, color: impl FnOnce() -> Rgba) {
+        let index = y as usize * self.target.width() as usize + x as usize;
+        match self.mask_state {
+            MaskState::NoMask => {}
+            MaskState::DrawMaskStencil => {
+                if u32::from(self.stencil[index]) == self.num_masks - 1 {
+                    self.stencil[index] = self.stencil[index].saturating_add(1);
+                }
+                return;
+            }
+            MaskState::DrawMaskedContent => {
+                if u32::from(self.stencil[index]) != self.num_masks {
+                    return;
+                }
+            }
+            MaskState::ClearMaskStencil => {
+                if u32::from(self.stencil[index]) == self.num_masks {
+                    self.stencil[index] = self.stencil[index].saturating_sub(1);
+                }
+                return;
+            }
+        }
+        let src = color();
+        if src[3] <= 0.0 {
+            return;
+        }
+        let dst = self.target.get_index(index * 4);
+        self.target.set_index(index * 4, over(src, dst));
+    }
+
+    fn is_drawing_mask(&self) -> bool {
+        matches!(
+            self.mask_state,
+            MaskState::DrawMaskStencil | MaskState::ClearMaskStencil
+        )
+    }
+
+    /// Fills the given triangles (in sample space) with a color computed per sample.
+    fn fill_triangles(
+        &mut self,
+        triangles: impl IntoIterator<Item = [(f32, f32); 3]>,
+        mut paint: impl FnMut(f32, f32) -> Rgba,
+    ) {
+        let (width, height) = (self.target.width(), self.target.height());
+        for triangle in triangles {
+            rasterize_triangle(width, height, triangle, |x, y| {
+                self.write_sample(x, y, || paint(x as f32 + 0.5, y as f32 + 0.5));
+            });
+        }
+    }
+
+    fn draw_bitmap(
+        &mut self,
+        bitmap: &BitmapHandle,
+        matrix: Matrix,
+        color_transform: &ColorTransform,
+        smoothing: bool,
+    ) {
+        let bitmap = as_bitmap(bitmap);
+        let pixmap = bitmap.pixmap();
+        let to_samples = self.sample_transform(&matrix)
+            * Affine::scale(pixmap.width() as f32, pixmap.height() as f32);
+        let Some(to_uv) = to_samples.inverse() else {
+            return;
+        };
+        let triangles = UNIT_QUAD.map(|t| t.map(|(x, y)| to_samples.apply(x, y)));
+        let is_identity = *color_transform == ColorTransform::IDENTITY;
+        self.fill_triangles(triangles, |x, y| {
+            let (u, v) = to_uv.apply(x, y);
+            let color = pixmap.sample(u, v, smoothing, false);
+            if is_identity || color[3] <= 0.0 {
+                color
+            } else {
+                premultiply(saturate(transform_color(
+                    color_transform,
+                    unmultiply(color),
+                )))
+            }
+        });
+    }
+}
+
+/// Applies a color transform to a straight alpha color.
+#[inline]
+pub fn transform_color(color_transform: &ColorTransform, color: Rgba) -> Rgba {
+    let mult = color_transform.mult_rgba_normalized();
+    let add = color_transform.add_rgba_normalized();
+    [
+        color[0] * mult[0] + add[0],
+        color[1] * mult[1] + add[1],
+        color[2] * mult[2] + add[2],
+        color[3] * mult[3] + add[3],
+    ]
+}
+
+impl CommandHandler for Surface {
+    fn render_bitmap(
+        &mut self,
+        bitmap: BitmapHandle,
+        transform: Transform,
+        smoothing: bool,
+        pixel_snapping: PixelSnapping,
+    ) {
+        let mut matrix = transform.matrix;
+        pixel_snapping.apply(&mut matrix);
+        self.draw_bitmap(&bitmap, matrix, &transform.color_transform, smoothing);
+    }
+
+    fn render_stage3d(&mut self, bitmap: BitmapHandle, transform: Transform) {
+        self.draw_bitmap(&bitmap, transform.matrix, &transform.color_transform, false);
+    }
+
+    fn render_shape(&mut self, shape: ShapeHandle, transform: Transform) {
+        let mesh = as_mesh(&shape);
+        let to_samples = self.sample_transform(&transform.matrix);
+        let Some(from_samples) = to_samples.inverse() else {
+            return;
+        };
+        let color_transform = &transform.color_transform;
+        let drawing_mask = self.is_drawing_mask();
+
+        for draw in &mesh.draws {
+            let num_indices = if drawing_mask {
+                // Omit strokes when drawing a mask stencil.
+                draw.mask_index_count as usize
+            } else {
+                draw.indices.len()
+            };
+            let vertices: Vec<(f32, f32)> = draw
+                .vertices
+                .iter()
+                .map(|&(x, y)| to_samples.apply(x, y))
+                .collect();
+            let triangle = |indices: &[u32]| {
+                [
+                    vertices[indices[0] as usize],
+                    vertices[indices[1] as usize],
+                    vertices[indices[2] as usize],
+                ]
+            };
+            let triangles = draw.indices[..num_indices.min(draw.indices.len())].chunks_exact(3);
+
+            match &draw.draw_type {
+                DrawType::Color => {
+                    // Every triangle of a color draw has a single solid color.
+                    for indices in triangles {
+                        let color = premultiply(saturate(transform_color(
+                            color_transform,
+                            draw.colors[indices[0] as usize],
+                        )));
+                        self.fill_triangles([triangle(indices)], |_, _| color);
+                    }
+                }
+                DrawType::Gradient(gradient) => {
+                    let to_gradient = gradient.matrix * from_samples;
+                    self.fill_triangles(triangles.map(triangle), |x, y| {
+                        let (u, v) = to_gradient.apply(x, y);
+                        let color = gradient.color_at(u, v);
+                        premultiply(saturate(transform_color(color_transform, color)))
+                    });
+                }
+                DrawType::Bitmap(fill) => {
+                    let bitmap = as_bitmap(&fill.handle);
+                    let pixmap = bitmap.pixmap();
+                    let to_uv = fill.matrix * from_samples;
+                    let is_identity = *color_transform == ColorTransform::IDENTITY;
+                    self.fill_triangles(triangles.map(triangle), |x, y| {
+                        let (u, v) = to_uv.apply(x, y);
+                        let color = pixmap.sample(u, v, fill.is_smoothed, fill.is_repeating);
+                        if is_identity || color[3] <= 0.0 {
+                            color
+                        } else {
+                            premultiply(saturate(transform_color(
+                                color_transform,
+                                unmultiply(color),
+                            )))
+                        }
+                    });
+                }
+            }
+        }
+    }
+
+    fn draw_rect(&mut self, color: Color, matrix: Matrix) {
+        let to_samples = self.sample_transform(&matrix);
+        let color = premultiply(crate::pixmap::normalize_color(color));
+        let triangles = UNIT_QUAD.map(|t| t.map(|(x, y)| to_samples.apply(x, y)));
+        self.fill_triangles(triangles, |_, _| color);
+    }
+
+    fn draw_line(&mut self, color: Color, matrix: Matrix) {
+        let mut commands = CommandList::new();
+        emulate_line(&mut commands, color, matrix);
+        commands.execute(self);
+    }
+
+    fn draw_line_rect(&mut self, color: Color, matrix: Matrix) {
+        let mut commands = CommandList::new();
+        emulate_line_rect(&mut commands, color, matrix);
+        commands.execute(self);
+    }
+
+    fn push_mask(&mut self) {
+        debug_assert!(
+            self.mask_state == MaskState::NoMask || self.mask_state == MaskState::DrawMaskedContent
+        );
+        self.num_masks += 1;
+        self.mask_state = MaskState::DrawMaskStencil;
+    }
+
+    fn activate_mask(&mut self) {
+        debug_assert!(self.num_masks > 0 && self.mask_state == MaskState::DrawMaskStencil);
+        self.mask_state = MaskState::DrawMaskedContent;
+    }
+
+    fn deactivate_mask(&mut self) {
+        debug_assert!(self.num_masks > 0 && self.mask_state == MaskState::DrawMaskedContent);
+        self.mask_state = MaskState::ClearMaskStencil;
+    }
+
+    fn pop_mask(&mut self) {
+        debug_assert!(self.num_masks > 0 && self.mask_state == MaskState::ClearMaskStencil);
+        self.num_masks -= 1;
+        self.mask_state = if self.num_masks == 0 {
+            MaskState::NoMask
+        } else {
+            MaskState::DrawMaskedContent
+        };
+    }
+
+    fn blend(&mut self, commands: CommandList, blend_mode: RenderBlendMode) {
+        let mut layer = self.new_layer();
+        layer.draw_commands(commands);
+
+        let blend = LayerBlend::from_render_blend_mode(&blend_mode);
+        let num_masks = self.num_masks;
+        let masked = self.mask_state == MaskState::DrawMaskedContent;
+        let samples = self.target.width() as usize * self.target.height() as usize;
+        for index in 0..samples {
+            if masked && u32::from(self.stencil[index]) != num_masks {
+                continue;
+            }
+            let src = layer.target.get_index(index * 4);
+            let dst = self.target.get_index(index * 4);
+            if let Some(color) = blend.apply(src, dst) {
+                self.target.set_index(index * 4, color);
+            }
+        }
+    }
+}
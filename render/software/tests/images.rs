@@ -0,0 +1,336 @@
+//! Image tests for the software renderer.
+//!
+//! Each test renders a scene and compares it with `tests/images/<name>.png`, which was
+//! rendered by this backend. Set `RUFFLE_SOFTWARE_UPDATE_IMAGES=1` to rewrite the
+//! expected images after an intended change in rendering.
+
+use image::RgbaImage;
+use ruffle_render::backend::{RenderBackend, ShapeHandle};
+use ruffle_render::bitmap::{
+    Bitmap, BitmapFormat, BitmapHandle, BitmapSize, BitmapSource, PixelSnapping,
+};
+use ruffle_render::commands::{CommandHandler, CommandList};
+use ruffle_render::filters::Filter;
+use ruffle_render::matrix::Matrix;
+use ruffle_render::shape_utils::{DistilledShape, DrawCommand, DrawPath, FillRule};
+use ruffle_render::transform::Transform;
+use ruffle_render_software::SoftwareRenderBackend;
+use std::path::PathBuf;
+use swf::{
+    Color, ColorTransform, FillStyle, Fixed16, Gradient, GradientInterpolation, GradientRecord,
+    GradientSpread, LineStyle, Point, Rectangle, Twips,
+};
+
+const WIDTH: u32 = 64;
+const HEIGHT: u32 = 64;
+
+/// The largest difference allowed in any channel of a pixel.
+///
+/// The renderer is deterministic, but float rounding may differ slightly between platforms.
+const TOLERANCE: u8 = 2;
+
+fn point(x: i32, y: i32) -> Point<Twips> {
+    Point::new(Twips::from_pixels_i32(x), Twips::from_pixels_i32(y))
+}
+
+fn rectangle(x: i32, y: i32, width: i32, height: i32) -> Vec<DrawCommand> {
+    vec![
+        DrawCommand::MoveTo(point(x, y)),
+        DrawCommand::LineTo(point(x + width, y)),
+        DrawCommand::LineTo(point(x + width, y + height)),
+        DrawCommand::LineTo(point(x, y + height)),
+        DrawCommand::LineTo(point(x, y)),
+    ]
+}
+
+fn fill(style: &FillStyle, commands: Vec<DrawCommand>) -> DrawPath<'_> {
+    DrawPath::Fill {
+        style,
+        commands,
+        winding_rule: FillRule::EvenOdd,
+    }
+}
+
+fn distilled(paths: Vec<DrawPath<'_>>) -> DistilledShape<'_> {
+    let bounds = Rectangle {
+        x_min: Twips::ZERO,
+        y_min: Twips::ZERO,
+        x_max: Twips::from_pixels_i32(WIDTH as i32),
+        y_max: Twips::from_pixels_i32(HEIGHT as i32),
+    };
+    DistilledShape {
+        paths,
+        shape_bounds: bounds.clone(),
+        edge_bounds: bounds,
+        id: 0,
+    }
+}
+
+/// Provides a single bitmap with ID 1 to shapes that use bitmap fills.
+struct SingleBitmap(Option<BitmapHandle>);
+
+impl BitmapSource for SingleBitmap {
+    fn bitmap_size(&self, id: u16) -> Option<BitmapSize> {
+        (id == 1).then_some(BitmapSize {
+            width: 8,
+            height: 8,
+        })
+    }
+
+    fn bitmap_handle(&self, id: u16, _renderer: &mut dyn RenderBackend) -> Option<BitmapHandle> {
+        self.0.clone().filter(|_| id == 1)
+    }
+}
+
+fn register(renderer: &mut SoftwareRenderBackend, shape: DistilledShape) -> ShapeHandle {
+    renderer.register_shape(shape, &SingleBitmap(None))
+}
+
+/// An 8x8 checkerboard of opaque red and half transparent blue pixels.
+fn checkerboard(renderer: &mut SoftwareRenderBackend) -> BitmapHandle {
+    let mut data = Vec::new();
+    for y in 0..8 {
+        for x in 0..8 {
+            if (x + y) % 2 == 0 {
+                data.extend_from_slice(&[255, 0, 0, 255]);
+            } else {
+                data.extend_from_slice(&[0, 0, 128, 128]);
+            }
+        }
+    }
+    renderer
+        .register_bitmap(Bitmap::new(8, 8, BitmapFormat::Rgba, data))
+        .expect("Bitmap is valid")
+}
+
+fn at(x: i32, y: i32) -> Transform {
+    Transform {
+        matrix: Matrix::translate(Twips::from_pixels_i32(x), Twips::from_pixels_i32(y)),
+        color_transform: ColorTransform::IDENTITY,
+    }
+}
+
+fn render(renderer: &mut SoftwareRenderBackend, draw: impl FnOnce(&mut CommandList)) -> RgbaImage {
+    let mut commands = CommandList::new();
+    draw(&mut commands);
+    renderer.submit_frame(Color::WHITE, commands, vec![]);
+    renderer.capture_frame().expect("Frame was rendered")
+}
+
+fn compare(name: &str, actual: RgbaImage) {
+    let directory = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/images");
+    let expected_path = directory.join(format!("{name}.png"));
+    if std::env::var_os("RUFFLE_SOFTWARE_UPDATE_IMAGES").is_some() {
+        actual
+            .save(&expected_path)
+            .expect("Expected image is writable");
+        return;
+    }
+
+    let expected = image::open(&expected_path)
+        .unwrap_or_else(|e| panic!("Couldn't open {}: {e}", expected_path.display()))
+        .into_rgba8();
+    assert_eq!(actual.dimensions(), expected.dimensions());
+    let outliers = actual
+        .pixels()
+        .zip(expected.pixels())
+        .filter(|(a, e)| {
+            a.0.iter()
+                .zip(e.0.iter())
+                .any(|(&a, &e)| a.abs_diff(e) > TOLERANCE)
+        })
+        .count();
+    if outliers > 0 {
+        let actual_path = directory.join(format!("{name}.actual.png"));
+        actual.save(&actual_path).expect("Actual image is writable");
+        panic!(
+            "'{name}' differs from the expected image in {outliers} pixels, see {}",
+            actual_path.display()
+        );
+    }
+}
+
+#[test]
+fn shapes() {
+    let mut renderer = SoftwareRenderBackend::new(WIDTH, HEIGHT);
+    let red = FillStyle::Color(Color::RED);
+    let green = FillStyle::Color(Color::from_rgb(0x00FF00, 160));
+    let stroke = LineStyle::new()
+        .with_width(Twips::from_pixels_i32(3))
+        .with_color(Color::BLUE);
+    let curve = vec![
+        DrawCommand::MoveTo(point(4, 60)),
+        DrawCommand::QuadraticCurveTo {
+            control: point(32, 0),
+            anchor: point(60, 60),
+        },
+        DrawCommand::CubicCurveTo {
+            control_a: point(40, 40),
+            control_b: point(24, 40),
+            anchor: point(4, 60),
+        },
+    ];
+    let shape = register(
+        &mut renderer,
+        distilled(vec![
+            fill(&red, rectangle(4, 4, 32, 24)),
+            fill(&green, curve),
+            DrawPath::Stroke {
+                style: &stroke,
+                is_closed: false,
+                commands: vec![
+                    DrawCommand::MoveTo(point(8, 40)),
+                    DrawCommand::LineTo(point(56, 8)),
+                ],
+            },
+        ]),
+    );
+
+    compare(
+        "shapes",
+        render(&mut renderer, |commands| {
+            commands.render_shape(shape, at(0, 0));
+        }),
+    );
+}
+
+#[test]
+fn gradients() {
+    let mut renderer = SoftwareRenderBackend::new(WIDTH, HEIGHT);
+    let gradient = |spread| Gradient {
+        matrix: Matrix::create_gradient_box(32.0, 32.0, 0.0, Twips::ZERO, Twips::ZERO).into(),
+        spread,
+        interpolation: GradientInterpolation::Rgb,
+        records: vec![
+            GradientRecord {
+                ratio: 0,
+                color: Color::RED,
+            },
+            GradientRecord {
+                ratio: 128,
+                color: Color::from_rgb(0x00FF00, 128),
+            },
+            GradientRecord {
+                ratio: 255,
+                color: Color::BLUE,
+            },
+        ],
+    };
+    let linear = FillStyle::LinearGradient(gradient(GradientSpread::Pad));
+    let radial = FillStyle::RadialGradient(gradient(GradientSpread::Reflect));
+    let linear = register(
+        &mut renderer,
+        distilled(vec![fill(&linear, rectangle(0, 0, 32, 32))]),
+    );
+    let radial = register(
+        &mut renderer,
+        distilled(vec![fill(&radial, rectangle(0, 0, 32, 32))]),
+    );
+
+    compare(
+        "gradients",
+        render(&mut renderer, |commands| {
+            commands.render_shape(linear, at(0, 0));
+            commands.render_shape(radial, at(32, 32));
+        }),
+    );
+}
+
+#[test]
+fn bitmaps() {
+    let mut renderer = SoftwareRenderBackend::new(WIDTH, HEIGHT);
+    let bitmap = checkerboard(&mut renderer);
+    let scaled = |x, y| Transform {
+        matrix: Matrix::create_box(
+            3.0,
+            3.0,
+            Twips::from_pixels_i32(x),
+            Twips::from_pixels_i32(y),
+        ),
+        color_transform: ColorTransform::IDENTITY,
+    };
+    let bitmap_fill = FillStyle::Bitmap {
+        id: 1,
+        matrix: swf::Matrix::scale(Fixed16::from_f64(40.0), Fixed16::from_f64(40.0)),
+        is_smoothed: false,
+        is_repeating: true,
+    };
+    let shape = renderer.register_shape(
+        distilled(vec![fill(&bitmap_fill, rectangle(0, 0, 32, 24))]),
+        &SingleBitmap(Some(bitmap.clone())),
+    );
+
+    compare(
+        "bitmaps",
+        render(&mut renderer, |commands| {
+            commands.render_bitmap(bitmap.clone(), scaled(0, 0), false, PixelSnapping::Never);
+            commands.render_bitmap(bitmap, scaled(32, 0), true, PixelSnapping::Never);
+            commands.render_shape(shape, at(16, 36));
+        }),
+    );
+}
+
+#[test]
+fn masks() {
+    let mut renderer = SoftwareRenderBackend::new(WIDTH, HEIGHT);
+    let red = FillStyle::Color(Color::RED);
+    let black = FillStyle::Color(Color::BLACK);
+    let circle = vec![
+        DrawCommand::MoveTo(point(32, 8)),
+        DrawCommand::QuadraticCurveTo {
+            control: point(56, 8),
+            anchor: point(56, 32),
+        },
+        DrawCommand::QuadraticCurveTo {
+            control: point(56, 56),
+            anchor: point(32, 56),
+        },
+        DrawCommand::QuadraticCurveTo {
+            control: point(8, 56),
+            anchor: point(8, 32),
+        },
+        DrawCommand::QuadraticCurveTo {
+            control: point(8, 8),
+            anchor: point(32, 8),
+        },
+    ];
+    let content = register(
+        &mut renderer,
+        distilled(vec![fill(&red, rectangle(0, 0, 48, 48))]),
+    );
+    let mask = register(&mut renderer, distilled(vec![fill(&black, circle)]));
+
+    compare(
+        "masks",
+        render(&mut renderer, |commands| {
+            commands.push_mask();
+            commands.render_shape(mask.clone(), at(0, 0));
+            commands.activate_mask();
+            commands.render_shape(content, at(16, 16));
+            commands.deactivate_mask();
+            commands.render_shape(mask, at(0, 0));
+            commands.pop_mask();
+        }),
+    );
+}
+
+#[test]
+fn unsupported_filters() {
+    let mut renderer = SoftwareRenderBackend::new(WIDTH, HEIGHT);
+    let source = checkerboard(&mut renderer);
+    let destination = checkerboard(&mut renderer);
+    let filter = Filter::ConvolutionFilter(swf::ConvolutionFilter {
+        num_matrix_rows: 1,
+        num_matrix_cols: 1,
+        matrix: vec![2.0],
+        divisor: 1.0,
+        bias: 0.0,
+        default_color: Color::BLACK,
+        flags: swf::ConvolutionFilterFlags::CLAMP,
+    });
+
+    assert!(!renderer.is_filter_supported(&filter));
+    assert!(renderer
+        .apply_filter(source, (0, 0), (8, 8), destination, (0, 0), filter)
+        .is_none());
+}
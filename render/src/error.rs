@@ -24,9 +24,19 @@ pub enum Error {
     #[error("Invalid PNG")]
     InvalidPng(#[from] png::DecodingError),
 
+    #[error("Couldn't encode PNG")]
+    PngEncoding(#[from] png::EncodingError),
+
+    #[error("Couldn't encode JPEG")]
+    JpegEncoding(#[from] image::ImageError),
+
     #[error("Invalid GIF")]
     InvalidGif(#[from] gif::DecodingError),
 
+    #[cfg(feature = "jpegxr")]
+    #[error("Invalid JPEG XR: {0}")]
+    InvalidJpegXr(String),
+
     #[error("Empty GIF")]
     EmptyGif,
 
@@ -0,0 +1,1943 @@
+//! JPEG XR encoding, as used by `BitmapData.encode` with `JPEGXREncoderOptions`,
+//! and decoding of the images it produces.
+//!
+//! This follows the structure of the reference encoder (jxrlib), restricted to
+//! what Flash Player needs: 8-bit RGB input with optional interleaved alpha, a
+//! single tile in the spatial layout, no overlap filtering and uniform
+//! quantization across the image.
+
+use crate::bitmap::{Bitmap, BitmapFormat};
+use crate::error::Error;
+use crate::utils::premultiply_alpha_rgba;
+use std::io::Cursor;
+
+/// Options controlling JPEG XR compression.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct JpegXrOptions {
+    /// The quantization index. 0 is lossless, higher values are lossier.
+    pub quantization: u8,
+
+    /// How many of the least significant flexbits to drop from high pass
+    /// coefficients, from 0 to 15.
+    pub trim_flex_bits: u8,
+}
+
+/// Encodes unmultiplied RGBA pixels as a JPEG XR file.
+///
+/// If `has_alpha` is false, the alpha channel is ignored and a 24bpp BGR image
+/// is produced; otherwise the image is 32bpp BGRA with an interleaved alpha plane.
+pub fn encode_jpeg_xr(
+    width: u32,
+    height: u32,
+    rgba: &[u8],
+    has_alpha: bool,
+    options: JpegXrOptions,
+) -> Vec<u8> {
+    let codestream = encode_codestream(width, height, rgba, has_alpha, options);
+    write_container(width, height, has_alpha, &codestream)
+}
+
+/// Decodes a 24bpp BGR or 32bpp BGRA JPEG XR file into a bitmap.
+///
+/// The result is premultiplied RGBA if the image has alpha, and RGB otherwise.
+pub fn decode_jpeg_xr(data: &[u8]) -> Result<Bitmap, Error> {
+    let mut decoder =
+        ::jpegxr::ImageDecode::with_reader(Cursor::new(data)).map_err(invalid_jpeg_xr)?;
+    let pixel_format = decoder.get_pixel_format().map_err(invalid_jpeg_xr)?;
+    let (width, height) = decoder.get_size().map_err(invalid_jpeg_xr)?;
+    let (width, height) = (width as u32, height as u32);
+
+    let has_alpha = match pixel_format {
+        ::jpegxr::PixelFormat::PixelFormat24bppBGR => false,
+        ::jpegxr::PixelFormat::PixelFormat32bppBGRA => true,
+        _ => {
+            return Err(Error::Unimplemented(
+                format!("JPEG XR pixel format {pixel_format:?}").into(),
+            ))
+        }
+    };
+    let bytes_per_pixel = if has_alpha { 4 } else { 3 };
+    let stride = width as usize * bytes_per_pixel;
+    let mut data = vec![0; stride * height as usize];
+    decoder
+        .copy_all(&mut data, stride)
+        .map_err(invalid_jpeg_xr)?;
+
+    // Both formats store blue first.
+    data.chunks_exact_mut(bytes_per_pixel)
+        .for_each(|pixel| pixel.swap(0, 2));
+    if has_alpha {
+        premultiply_alpha_rgba(&mut data);
+        Ok(Bitmap::new(width, height, BitmapFormat::Rgba, data))
+    } else {
+        Ok(Bitmap::new(width, height, BitmapFormat::Rgb, data))
+    }
+}
+
+fn invalid_jpeg_xr(error: impl std::fmt::Debug) -> Error {
+    Error::InvalidJpegXr(format!("{error:?}"))
+}
+
+const PIXEL_FORMAT_24BPP_BGR: [u8; 16] = [
+    0x24, 0xc3, 0xdd, 0x6f, 0x03, 0x4e, 0xfe, 0x4b, 0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x0c,
+];
+const PIXEL_FORMAT_32BPP_BGRA: [u8; 16] = [
+    0x24, 0xc3, 0xdd, 0x6f, 0x03, 0x4e, 0xfe, 0x4b, 0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x0f,
+];
+
+/// Wraps a codestream in the TIFF-like JPEG XR container.
+fn write_container(width: u32, height: u32, has_alpha: bool, codestream: &[u8]) -> Vec<u8> {
+    const IFD_OFFSET: u32 = 0x20;
+    const IFD_ENTRIES: u16 = 8;
+    const IMAGE_OFFSET: u32 = IFD_OFFSET + 2 + 12 * IFD_ENTRIES as u32 + 4;
+    const BYTE: u16 = 1;
+    const LONG: u16 = 4;
+    const FLOAT: u16 = 11;
+    // 96 DPI.
+    let resolution = 96.0f32.to_bits();
+
+    let mut out = Vec::with_capacity(IMAGE_OFFSET as usize + codestream.len());
+    out.extend_from_slice(b"II\xbc\x01");
+    out.extend_from_slice(&IFD_OFFSET.to_le_bytes());
+    out.extend_from_slice(if has_alpha {
+        &PIXEL_FORMAT_32BPP_BGRA
+    } else {
+        &PIXEL_FORMAT_24BPP_BGR
+    });
+    out.resize(IFD_OFFSET as usize, 0);
+
+    out.extend_from_slice(&IFD_ENTRIES.to_le_bytes());
+    let entries: [(u16, u16, u32, u32); IFD_ENTRIES as usize] = [
+        // Pixel format, which is stored in the header.
+        (0xbc01, BYTE, 16, 8),
+        // Spatial transformation.
+        (0xbc02, LONG, 1, 0),
+        (0xbc80, LONG, 1, width),
+        (0xbc81, LONG, 1, height),
+        (0xbc82, FLOAT, 1, resolution),
+        (0xbc83, FLOAT, 1, resolution),
+        (0xbcc0, LONG, 1, IMAGE_OFFSET),
+        (0xbcc1, LONG, 1, codestream.len() as u32),
+    ];
+    for (tag, ty, count, value) in entries {
+        out.extend_from_slice(&tag.to_le_bytes());
+        out.extend_from_slice(&ty.to_le_bytes());
+        out.extend_from_slice(&count.to_le_bytes());
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+    // No further IFDs.
+    out.extend_from_slice(&0u32.to_le_bytes());
+
+    out.extend_from_slice(codestream);
+    out
+}
+
+/// Writes a big-endian bitstream.
+#[derive(Default)]
+struct BitWriter {
+    data: Vec<u8>,
+    accumulator: u64,
+    bits: u32,
+}
+
+impl BitWriter {
+    /// Writes the lowest `count` bits of `value`.
+    fn put(&mut self, value: u32, count: u32) {
+        debug_assert!(count <= 32);
+        let mask = ((1u64 << count) - 1) as u32;
+        self.accumulator = (self.accumulator << count) | (value & mask) as u64;
+        self.bits += count;
+        while self.bits >= 8 {
+            self.bits -= 8;
+            self.data.push((self.accumulator >> self.bits) as u8);
+        }
+        self.accumulator &= (1 << self.bits) - 1;
+    }
+
+    fn put_bool(&mut self, value: bool) {
+        self.put(value as u32, 1);
+    }
+
+    fn fill_to_byte(&mut self) {
+        if self.bits > 0 {
+            self.put(0, 8 - self.bits);
+        }
+    }
+}
+
+fn encode_codestream(
+    width: u32,
+    height: u32,
+    rgba: &[u8],
+    has_alpha: bool,
+    options: JpegXrOptions,
+) -> Vec<u8> {
+    // QP indices 0 and 1 are both lossless, which is also the only case that
+    // uses unscaled arithmetic.
+    let qp_index = if options.quantization < 2 {
+        0
+    } else {
+        options.quantization
+    };
+    let scaled = qp_index != 0;
+    let trim_flex_bits = options.trim_flex_bits.min(15);
+    let mb_width = width.div_ceil(16) as usize;
+    let mb_height = height.div_ceil(16) as usize;
+    let abbreviated = mb_width <= 255 && mb_height <= 255;
+
+    let mut w = BitWriter::default();
+    w.data.extend_from_slice(b"WMPHOTO\0");
+    // Codec version 1, soft tiles.
+    w.put(1, 4);
+    w.put(1, 4);
+    // No tiling, spatial layout, no rotation, no index table, no overlap.
+    w.put(0, 8);
+    w.put_bool(abbreviated);
+    // Long word length.
+    w.put_bool(true);
+    // No windowing.
+    w.put_bool(false);
+    w.put_bool(trim_flex_bits > 0);
+    // No tile stretching, reserved.
+    w.put(0, 3);
+    w.put_bool(has_alpha);
+    // RGB source with 8 bits per channel.
+    w.put(7, 4);
+    w.put(1, 4);
+    let size_bits = if abbreviated { 16 } else { 32 };
+    w.put(width - 1, size_bits);
+    w.put(height - 1, size_bits);
+    w.fill_to_byte();
+
+    let mut planes = vec![PlaneEncoder::new(
+        3,
+        qp_index,
+        scaled,
+        trim_flex_bits,
+        mb_width,
+    )];
+    if has_alpha {
+        planes.push(PlaneEncoder::new(
+            1,
+            qp_index,
+            scaled,
+            trim_flex_bits,
+            mb_width,
+        ));
+    }
+    for plane in &planes {
+        plane.write_header(&mut w);
+    }
+
+    // Profile and level, in lieu of an index table.
+    w.fill_to_byte();
+    w.put(4, 16);
+    w.put(111, 8);
+    w.put(255, 8);
+    w.put(1, 16);
+
+    // The only tile packet: spatial packet type, tile 0.
+    w.put(1, 24);
+    w.put(0, 8);
+    if trim_flex_bits > 0 {
+        w.put(trim_flex_bits as u32, 4);
+    }
+
+    for mb_y in 0..mb_height {
+        for mb_x in 0..mb_width {
+            let position = MacroblockPosition {
+                x: mb_x,
+                left: mb_x == 0,
+                top: mb_y == 0,
+                reset_totals: mb_x % 16 == 0,
+                reset_context: mb_x % 16 == 0 || mb_x + 1 == mb_width,
+            };
+
+            let (primary, rest) = planes.split_first_mut().expect("primary plane exists");
+            primary.read_color(rgba, width, height, mb_x, mb_y, scaled);
+            primary.encode_macroblock(&mut w, &position);
+            if let Some(alpha) = rest.first_mut() {
+                alpha.read_alpha(rgba, width, height, mb_x, mb_y, scaled);
+                alpha.encode_macroblock(&mut w, &position);
+            }
+        }
+        for plane in &mut planes {
+            plane.next_row();
+        }
+    }
+    w.fill_to_byte();
+    w.data
+}
+
+/// Where a macroblock is, and which parts of the coding state start over there.
+struct MacroblockPosition {
+    x: usize,
+    left: bool,
+    top: bool,
+    reset_totals: bool,
+    reset_context: bool,
+}
+
+/// Offset of each 4x4 block within a macroblock, in coding order.
+const BLOCK_OFFSETS: [usize; 16] = [
+    0, 64, 16, 80, 128, 192, 144, 208, 32, 96, 48, 112, 160, 224, 176, 240,
+];
+
+/// Position of each coefficient within a transformed 4x4 block, in raster order.
+const DCT_INDEX: [usize; 16] = [0, 5, 1, 6, 10, 12, 8, 14, 2, 4, 3, 7, 9, 13, 11, 15];
+
+/// Position of each block's DC coefficient after the second stage transform,
+/// in raster order.
+const DC_INDEX: [usize; 16] = [
+    0, 128, 64, 208, 32, 240, 48, 224, 16, 192, 80, 144, 112, 176, 96, 160,
+];
+
+const ZIGZAG_HORIZONTAL: [usize; 16] = [0, 1, 4, 5, 2, 8, 6, 9, 3, 12, 10, 7, 13, 11, 14, 15];
+const ZIGZAG_VERTICAL: [usize; 16] = [0, 4, 8, 5, 1, 12, 9, 6, 2, 13, 3, 15, 7, 10, 14, 11];
+
+/// Returns where the pixel at (`x`, `y`) within a macroblock is stored before
+/// the transform.
+fn pixel_index(x: usize, y: usize) -> usize {
+    const INNER: [[usize; 4]; 4] = [[0, 1, 5, 4], [2, 3, 7, 6], [10, 11, 15, 14], [8, 9, 13, 12]];
+    (x >> 2) * 64 + (y >> 2) * 16 + INNER[y & 3][x & 3]
+}
+
+/// `(mantissa, exponent)` of the reciprocal of each quantizer mantissa.
+const QP_RECIPROCALS: [(u32, i32); 32] = [
+    (0, 0),
+    (0, 0),
+    (0, 1),
+    (0xaaaaaaab, 1),
+    (0, 2),
+    (0xcccccccd, 2),
+    (0xaaaaaaab, 2),
+    (0x92492493, 2),
+    (0, 3),
+    (0xe38e38e4, 3),
+    (0xcccccccd, 3),
+    (0xba2e8ba3, 3),
+    (0xaaaaaaab, 3),
+    (0x9d89d89e, 3),
+    (0x92492493, 3),
+    (0x88888889, 3),
+    (0, 4),
+    (0xf0f0f0f1, 4),
+    (0xe38e38e4, 4),
+    (0xd79435e6, 4),
+    (0xcccccccd, 4),
+    (0xc30c30c4, 4),
+    (0xba2e8ba3, 4),
+    (0xb21642c9, 4),
+    (0xaaaaaaab, 4),
+    (0xa3d70a3e, 4),
+    (0x9d89d89e, 4),
+    (0x97b425ee, 4),
+    (0x92492493, 4),
+    (0x8d3dcb09, 4),
+    (0x88888889, 4),
+    (0x84210843, 4),
+];
+
+#[derive(Clone, Copy)]
+struct Quantizer {
+    index: u8,
+    mantissa: u32,
+    exponent: i32,
+    offset: i32,
+}
+
+impl Quantizer {
+    fn new(index: u8, shift: i32, scaled: bool) -> Self {
+        if index == 0 {
+            return Self {
+                index,
+                mantissa: 0,
+                exponent: 0,
+                offset: 0,
+            };
+        }
+        // Non-zero indices always use scaled arithmetic, see `encode_codestream`.
+        debug_assert!(scaled);
+        let (man, exp) = if index < 16 {
+            (index as i32, shift)
+        } else {
+            (16 + (index as i32 & 0xf), (index as i32 >> 4) - 1 + shift)
+        };
+        let qp = man << exp;
+        let (rec_man, rec_exp) = QP_RECIPROCALS[man as usize];
+        Self {
+            index,
+            mantissa: rec_man,
+            exponent: rec_exp + exp,
+            offset: (qp * 3 + 1) >> 3,
+        }
+    }
+
+    /// A quantizer for DC coefficients, which round to nearest.
+    fn new_dc(index: u8, shift: i32, scaled: bool) -> Self {
+        let mut quantizer = Self::new(index, shift, scaled);
+        if index != 0 {
+            let (man, exp) = if index < 16 {
+                (index as i32, shift)
+            } else {
+                (16 + (index as i32 & 0xf), (index as i32 >> 4) - 1 + shift)
+            };
+            quantizer.offset = (man << exp) >> 1;
+        }
+        quantizer
+    }
+
+    fn quantize(&self, value: i32) -> i32 {
+        let magnitude = (value.unsigned_abs() as i32 + self.offset) as u32;
+        let quantized = if self.mantissa == 0 {
+            magnitude >> self.exponent
+        } else {
+            (((magnitude as u64 * self.mantissa as u64) >> 32) as u32) >> self.exponent
+        } as i32;
+        if value < 0 {
+            -quantized
+        } else {
+            quantized
+        }
+    }
+}
+
+/// The "dn" 2x2 Hadamard butterfly.
+fn butterfly(p: &mut [i32], a: usize, b: usize, c: usize, d: usize) {
+    let (mut va, mut vb, vc, mut vd) = (p[a], p[b], p[c], p[d]);
+    va += vd;
+    vb -= vc;
+    let t = (va - vb) >> 1;
+    let c_out = t - vd;
+    vd = t - vc;
+    va -= vd;
+    vb += c_out;
+    (p[a], p[b], p[c], p[d]) = (va, vb, c_out, vd);
+}
+
+/// The "up" 2x2 Hadamard butterfly, which rounds the other way.
+fn butterfly_up(p: &mut [i32], a: usize, b: usize, c: usize, d: usize) {
+    let (mut va, mut vb, vc, mut vd) = (p[a], p[b], p[c], p[d]);
+    va += vd;
+    vb -= vc;
+    let t = (va - vb + 1) >> 1;
+    let c_out = t - vd;
+    vd = t - vc;
+    va -= vd;
+    vb += c_out;
+    (p[a], p[b], p[c], p[d]) = (va, vb, c_out, vd);
+}
+
+fn rotate(a: &mut i32, b: &mut i32) {
+    *b -= (*a * 3 + 4) >> 3;
+    *a += (*b * 3 + 4) >> 3;
+}
+
+fn fwd_odd(p: &mut [i32], a: usize, b: usize, c: usize, d: usize) {
+    let (mut va, mut vb, mut vc, mut vd) = (p[a], p[b], p[c], p[d]);
+    vb -= vc;
+    va += vd;
+    vc += (vb + 1) >> 1;
+    vd = ((va + 1) >> 1) - vd;
+    rotate(&mut va, &mut vb);
+    rotate(&mut vc, &mut vd);
+    vd += vb >> 1;
+    vc -= (va + 1) >> 1;
+    vb -= vd;
+    va += vc;
+    (p[a], p[b], p[c], p[d]) = (va, vb, vc, vd);
+}
+
+fn fwd_odd_odd(p: &mut [i32], a: usize, b: usize, c: usize, d: usize) {
+    let (mut va, mut vb, mut vc, mut vd) = (p[a], -p[b], -p[c], p[d]);
+    vd += va;
+    vc -= vb;
+    let t1 = vd >> 1;
+    let t2 = vc >> 1;
+    va -= t1;
+    vb += t2;
+    va += (vb * 3 + 4) >> 3;
+    vb -= (va * 3 + 3) >> 2;
+    va += (vb * 3 + 3) >> 3;
+    vb -= t2;
+    va += t1;
+    vc += vb;
+    vd -= va;
+    (p[a], p[b], p[c], p[d]) = (va, vb, vc, vd);
+}
+
+/// The first stage transform of a single 4x4 block.
+fn transform_block(p: &mut [i32]) {
+    butterfly(p, 0, 4, 8, 12);
+    butterfly(p, 1, 5, 9, 13);
+    butterfly(p, 2, 6, 10, 14);
+    butterfly(p, 3, 7, 11, 15);
+    butterfly_up(p, 0, 1, 2, 3);
+    fwd_odd_odd(p, 15, 14, 13, 12);
+    fwd_odd(p, 5, 4, 7, 6);
+    fwd_odd(p, 10, 8, 11, 9);
+}
+
+/// The second stage transform, over the DC coefficients of a macroblock's blocks.
+fn transform_dc(p: &mut [i32]) {
+    butterfly(p, 0, 192, 48, 240);
+    butterfly(p, 64, 128, 112, 176);
+    butterfly(p, 16, 208, 32, 224);
+    butterfly(p, 80, 144, 96, 160);
+    butterfly_up(p, 0, 64, 16, 80);
+    fwd_odd_odd(p, 160, 224, 176, 240);
+    fwd_odd(p, 128, 192, 144, 208);
+    fwd_odd(p, 32, 48, 96, 112);
+}
+
+const CODES_4: [(u32, u32); 4] = [(1, 1), (1, 2), (0, 3), (1, 3)];
+const CODES_5: [[(u32, u32); 5]; 2] = [
+    [(1, 1), (1, 2), (1, 3), (0, 4), (1, 4)],
+    [(1, 1), (0, 3), (1, 3), (2, 3), (3, 3)],
+];
+const DELTAS_5: [i32; 5] = [0, -1, 0, 1, 1];
+const CODES_6: [[(u32, u32); 6]; 4] = [
+    [(1, 1), (0, 5), (1, 3), (1, 5), (1, 2), (1, 4)],
+    [(1, 2), (0, 4), (2, 2), (1, 4), (3, 2), (1, 3)],
+    [(0, 4), (1, 4), (1, 2), (2, 2), (3, 2), (1, 3)],
+    [(0, 5), (1, 5), (1, 2), (1, 1), (1, 4), (1, 3)],
+];
+const DELTAS_6: [[i32; 6]; 3] = [
+    [-1, 1, 1, 1, 0, 1],
+    [-2, 0, 0, 2, 0, 0],
+    [-1, -1, 0, 1, -2, 0],
+];
+const CODES_7: [[(u32, u32); 7]; 2] = [
+    [(1, 2), (2, 2), (3, 2), (1, 3), (1, 4), (0, 5), (1, 5)],
+    [(1, 1), (1, 2), (1, 3), (1, 4), (1, 5), (0, 6), (1, 6)],
+];
+const DELTAS_7: [i32; 7] = [1, 0, -1, -1, -1, -1, -1];
+const CODES_8: [(u32, u32); 8] = [
+    (2, 2),
+    (1, 3),
+    (1, 5),
+    (1, 4),
+    (3, 2),
+    (2, 3),
+    (0, 5),
+    (3, 3),
+];
+const CODES_9: [[(u32, u32); 9]; 2] = [
+    [
+        (2, 3),
+        (0, 5),
+        (2, 4),
+        (1, 5),
+        (2, 5),
+        (1, 1),
+        (3, 3),
+        (3, 5),
+        (3, 4),
+    ],
+    [
+        (1, 1),
+        (1, 3),
+        (2, 3),
+        (1, 4),
+        (1, 6),
+        (3, 3),
+        (1, 5),
+        (0, 7),
+        (1, 7),
+    ],
+];
+const DELTAS_9: [i32; 9] = [2, 2, 1, 1, -1, -2, -2, -2, -3];
+const CODES_12: [[(u32, u32); 12]; 5] = [
+    [
+        (1, 5),
+        (1, 6),
+        (0, 7),
+        (1, 7),
+        (4, 5),
+        (2, 3),
+        (5, 5),
+        (1, 1),
+        (6, 5),
+        (1, 4),
+        (7, 5),
+        (3, 3),
+    ],
+    [
+        (2, 4),
+        (2, 5),
+        (0, 6),
+        (1, 6),
+        (3, 4),
+        (2, 3),
+        (3, 5),
+        (3, 2),
+        (3, 3),
+        (4, 3),
+        (1, 5),
+        (5, 3),
+    ],
+    [
+        (3, 2),
+        (1, 3),
+        (0, 7),
+        (1, 7),
+        (1, 5),
+        (2, 3),
+        (2, 7),
+        (3, 3),
+        (4, 3),
+        (5, 3),
+        (3, 7),
+        (1, 4),
+    ],
+    [
+        (1, 3),
+        (3, 2),
+        (0, 7),
+        (1, 5),
+        (2, 5),
+        (2, 3),
+        (1, 7),
+        (3, 3),
+        (3, 5),
+        (4, 3),
+        (1, 6),
+        (5, 3),
+    ],
+    [
+        (2, 3),
+        (1, 1),
+        (1, 7),
+        (1, 4),
+        (2, 7),
+        (3, 3),
+        (0, 8),
+        (2, 4),
+        (3, 7),
+        (3, 4),
+        (1, 8),
+        (1, 5),
+    ],
+];
+const DELTAS_12: [[i32; 12]; 4] = [
+    [1, 1, 1, 1, 1, 0, 0, -1, 2, 1, 0, 0],
+    [2, 2, -1, -1, -1, 0, -2, -1, 0, 0, -2, -1],
+    [-1, 1, 0, 2, 0, 0, 0, 0, -2, 0, 1, 1],
+    [0, 1, 0, 1, -2, 0, -1, -1, -2, -1, -2, -2],
+];
+
+/// A variable length code which switches between code tables depending on
+/// the statistics of the symbols coded so far.
+struct AdaptiveHuffman {
+    symbols: usize,
+    table: usize,
+    discriminant: i32,
+    discriminant1: i32,
+    lower_bound: i32,
+    upper_bound: i32,
+    initialized: bool,
+}
+
+impl AdaptiveHuffman {
+    fn new(symbols: usize) -> Self {
+        Self {
+            symbols,
+            table: 0,
+            discriminant: 0,
+            discriminant1: 0,
+            lower_bound: 0,
+            upper_bound: 0,
+            initialized: false,
+        }
+    }
+
+    fn table_count(&self) -> usize {
+        match self.symbols {
+            4 => 1,
+            6 => 4,
+            12 => 5,
+            _ => 2,
+        }
+    }
+
+    /// Whether this code tracks a second discriminant to decide when to move
+    /// to a later table.
+    fn has_second_discriminant(&self) -> bool {
+        matches!(self.symbols, 6 | 12)
+    }
+
+    fn reset(&mut self) {
+        self.initialized = false;
+    }
+
+    /// Picks the code table to use from now on.
+    fn adapt(&mut self) {
+        const THRESHOLD: i32 = 8;
+        const MEMORY: i32 = 8;
+
+        if !self.initialized {
+            self.initialized = true;
+            self.discriminant = 0;
+            self.discriminant1 = 0;
+            self.table = self.has_second_discriminant() as usize;
+        }
+
+        let low = self.discriminant;
+        let high = if self.has_second_discriminant() {
+            self.discriminant1
+        } else {
+            self.discriminant
+        };
+        let changed = if low < self.lower_bound {
+            self.table -= 1;
+            true
+        } else if high > self.upper_bound {
+            self.table += 1;
+            true
+        } else {
+            false
+        };
+        if changed {
+            self.discriminant = 0;
+            self.discriminant1 = 0;
+        }
+        self.discriminant = self
+            .discriminant
+            .clamp(-THRESHOLD * MEMORY, THRESHOLD * MEMORY);
+        self.discriminant1 = self
+            .discriminant1
+            .clamp(-THRESHOLD * MEMORY, THRESHOLD * MEMORY);
+
+        self.lower_bound = if self.table == 0 {
+            i32::MIN
+        } else {
+            -THRESHOLD
+        };
+        self.upper_bound = if self.table == self.table_count() - 1 {
+            1 << 30
+        } else {
+            THRESHOLD
+        };
+    }
+
+    fn code(&self, index: usize) -> (u32, u32) {
+        let t = self.table;
+        match self.symbols {
+            4 => CODES_4[index],
+            5 => CODES_5[t][index],
+            6 => CODES_6[t][index],
+            7 => CODES_7[t][index],
+            // Symbols of this size always use the first table.
+            8 => CODES_8[index],
+            9 => CODES_9[t][index],
+            12 => CODES_12[t][index],
+            _ => unreachable!("no code table with {} symbols", self.symbols),
+        }
+    }
+
+    fn delta(&self, index: usize) -> i32 {
+        let t = self.table;
+        match self.symbols {
+            5 => DELTAS_5[index],
+            6 => DELTAS_6[t.max(1) - 1][index],
+            7 => DELTAS_7[index],
+            9 => DELTAS_9[index],
+            12 => DELTAS_12[t.max(1) - 1][index],
+            _ => unreachable!("no delta table with {} symbols", self.symbols),
+        }
+    }
+
+    fn delta1(&self, index: usize) -> i32 {
+        let t = self.table;
+        let row = t - (t + 1 == self.table_count()) as usize;
+        match self.symbols {
+            6 => DELTAS_6[row][index],
+            12 => DELTAS_12[row][index],
+            _ => unreachable!("no second delta table with {} symbols", self.symbols),
+        }
+    }
+
+    /// Writes a symbol without updating the statistics.
+    fn put(&self, w: &mut BitWriter, index: usize) {
+        let (code, length) = self.code(index);
+        w.put(code, length);
+    }
+
+    /// Writes a symbol and updates the statistics.
+    fn put_adapting(&mut self, w: &mut BitWriter, index: usize) {
+        self.discriminant += self.delta(index);
+        self.put(w, index);
+    }
+
+    /// Writes a symbol followed by a sign bit, updating both discriminants.
+    fn put_signed(&mut self, w: &mut BitWriter, index: usize, negative: bool) {
+        self.discriminant += self.delta(index);
+        self.discriminant1 += self.delta1(index);
+        let (code, length) = self.code(index);
+        w.put(code * 2 + negative as u32, length + 1);
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct ScanEntry {
+    position: usize,
+    total: u32,
+}
+
+fn new_scan(order: impl Fn(usize) -> usize) -> [ScanEntry; 16] {
+    std::array::from_fn(|i| ScanEntry {
+        position: order(i),
+        total: 0,
+    })
+}
+
+fn reset_scan_totals(scan: &mut [ScanEntry; 16]) {
+    const MAX_TOTAL: u32 = 32767;
+    scan[0].total = MAX_TOTAL;
+    for (k, entry) in scan.iter_mut().enumerate().skip(1) {
+        entry.total = 34 - 2 * k as u32;
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Band {
+    Dc = 0,
+    Lowpass = 1,
+    Highpass = 2,
+}
+
+/// Tracks how many low bits of coefficients are sent uncoded, for luma and chroma.
+struct AdaptiveModel {
+    state: [i32; 2],
+    bits: [u32; 2],
+    band: Band,
+}
+
+impl AdaptiveModel {
+    fn new(band: Band) -> Self {
+        let bits = match band {
+            Band::Dc => 8,
+            Band::Lowpass => 4,
+            Band::Highpass => 0,
+        };
+        Self {
+            state: [0; 2],
+            bits: [bits; 2],
+            band,
+        }
+    }
+
+    fn update(&mut self, channels: usize, mut laplacian_mean: [i32; 2]) {
+        const MODEL_WEIGHT: i32 = 70;
+        const WEIGHT_LUMA: [i32; 3] = [240, 12, 1];
+        // Chroma weights for one and three channel images.
+        const WEIGHT_CHROMA: [[i32; 2]; 3] = [[0, 120], [0, 6], [0, 8]];
+
+        let band = self.band as usize;
+        laplacian_mean[0] *= WEIGHT_LUMA[band];
+        laplacian_mean[1] *= WEIGHT_CHROMA[band][(channels > 1) as usize];
+        if matches!(self.band, Band::Highpass) {
+            laplacian_mean[1] >>= 4;
+        }
+
+        let component_count = if channels == 1 { 1 } else { 2 };
+        for (j, &mean) in laplacian_mean.iter().enumerate().take(component_count) {
+            let mut state = self.state[j];
+            let mut delta = (mean - MODEL_WEIGHT) >> 2;
+            if delta <= -8 {
+                delta = (delta + 4).max(-16);
+                state += delta;
+                if state < -8 {
+                    if self.bits[j] == 0 {
+                        state = -8;
+                    } else {
+                        state = 0;
+                        self.bits[j] -= 1;
+                    }
+                }
+            } else if delta >= 8 {
+                delta = (delta - 4).min(15);
+                state += delta;
+                if state > 8 {
+                    if self.bits[j] >= 15 {
+                        self.bits[j] = 15;
+                        state = 8;
+                    } else {
+                        state = 0;
+                        self.bits[j] += 1;
+                    }
+                }
+            }
+            self.state[j] = state;
+        }
+    }
+}
+
+/// The entropy coding state of one plane.
+struct CodingContext {
+    cbpcy: AdaptiveHuffman,
+    cbpcy1: AdaptiveHuffman,
+    /// Codes for DC values, run lengths and levels. Indices 5..13 are used
+    /// for the lowpass band, 13..21 for the highpass band.
+    expt: [AdaptiveHuffman; 21],
+    scan_lowpass: [ScanEntry; 16],
+    scan_horizontal: [ScanEntry; 16],
+    scan_vertical: [ScanEntry; 16],
+    model_dc: AdaptiveModel,
+    model_lowpass: AdaptiveModel,
+    model_highpass: AdaptiveModel,
+    cbp_count_max: i32,
+    cbp_count_zero: i32,
+    cbp_count0: [i32; 2],
+    cbp_count1: [i32; 2],
+    cbp_state: [i32; 2],
+    trim_flex_bits: u32,
+}
+
+/// Offset of the lowpass codes within `CodingContext::expt`.
+const CONTEXT_LOWPASS: usize = 5;
+/// Offset of the highpass codes within `CodingContext::expt`.
+const CONTEXT_HIGHPASS: usize = 13;
+
+impl CodingContext {
+    fn new(channels: usize, trim_flex_bits: u32) -> Self {
+        const ALPHABETS: [usize; 21] = [
+            5, 4, 8, 7, 7, 12, 6, 6, 12, 6, 6, 7, 7, 12, 6, 6, 12, 6, 6, 7, 7,
+        ];
+        let mut context = Self {
+            cbpcy: AdaptiveHuffman::new(if channels == 1 { 5 } else { 9 }),
+            cbpcy1: AdaptiveHuffman::new(5),
+            expt: ALPHABETS.map(AdaptiveHuffman::new),
+            scan_lowpass: new_scan(|i| ZIGZAG_HORIZONTAL[i]),
+            scan_horizontal: new_scan(|i| DCT_INDEX[ZIGZAG_HORIZONTAL[i]]),
+            scan_vertical: new_scan(|i| DCT_INDEX[ZIGZAG_VERTICAL[i]]),
+            model_dc: AdaptiveModel::new(Band::Dc),
+            model_lowpass: AdaptiveModel::new(Band::Lowpass),
+            model_highpass: AdaptiveModel::new(Band::Highpass),
+            cbp_count_max: 1,
+            cbp_count_zero: 1,
+            cbp_count0: [-4; 2],
+            cbp_count1: [4; 2],
+            cbp_state: [0; 2],
+            trim_flex_bits,
+        };
+        for code in &mut context.expt {
+            code.reset();
+        }
+        context.adapt_lowpass();
+        context.adapt_highpass();
+        context
+    }
+
+    fn adapt_lowpass(&mut self) {
+        for code in &mut self.expt[..CONTEXT_HIGHPASS] {
+            code.adapt();
+        }
+    }
+
+    fn adapt_highpass(&mut self) {
+        self.cbpcy.adapt();
+        self.cbpcy1.adapt();
+        for code in &mut self.expt[CONTEXT_HIGHPASS..] {
+            code.adapt();
+        }
+    }
+}
+
+/// What the next macroblocks predict from.
+#[derive(Clone, Copy, Default)]
+struct PredictionInfo {
+    dc: i32,
+    /// The first row and column of lowpass coefficients.
+    ad: [i32; 6],
+    cbp: u32,
+}
+
+/// Encodes the macroblocks of one plane: either the color plane, or the alpha plane.
+struct PlaneEncoder {
+    channels: usize,
+    scaled: bool,
+    dc_quantizers: Vec<Quantizer>,
+    lowpass_quantizers: Vec<Quantizer>,
+    highpass_quantizers: Vec<Quantizer>,
+    context: CodingContext,
+    /// Prediction info for the current row, per channel.
+    predictions: Vec<Vec<PredictionInfo>>,
+    /// Prediction info for the previous row, per channel.
+    previous_predictions: Vec<Vec<PredictionInfo>>,
+    /// The coefficients of the current macroblock, per channel.
+    coefficients: Vec<[i32; 256]>,
+    /// The DC and lowpass coefficients of the current macroblock, per channel.
+    block_dc: Vec<[i32; 16]>,
+    cbp: Vec<u32>,
+    diff_cbp: Vec<u32>,
+    orientation: u32,
+}
+
+impl PlaneEncoder {
+    fn new(
+        channels: usize,
+        qp_index: u8,
+        scaled: bool,
+        trim_flex_bits: u8,
+        mb_width: usize,
+    ) -> Self {
+        // Chroma DC and lowpass coefficients have one bit less precision.
+        let shift = |channel: usize, shifted_chroma: bool| {
+            if channel > 0 && shifted_chroma {
+                0
+            } else {
+                1
+            }
+        };
+        Self {
+            channels,
+            scaled,
+            dc_quantizers: (0..channels)
+                .map(|c| Quantizer::new_dc(qp_index, shift(c, true), scaled))
+                .collect(),
+            lowpass_quantizers: (0..channels)
+                .map(|c| Quantizer::new(qp_index, shift(c, true), scaled))
+                .collect(),
+            highpass_quantizers: (0..channels)
+                .map(|c| Quantizer::new(qp_index, shift(c, false), scaled))
+                .collect(),
+            context: CodingContext::new(channels, trim_flex_bits as u32),
+            predictions: vec![vec![PredictionInfo::default(); mb_width]; channels],
+            previous_predictions: vec![vec![PredictionInfo::default(); mb_width]; channels],
+            coefficients: vec![[0; 256]; channels],
+            block_dc: vec![[0; 16]; channels],
+            cbp: vec![0; channels],
+            diff_cbp: vec![0; channels],
+            orientation: 0,
+        }
+    }
+
+    fn write_header(&self, w: &mut BitWriter) {
+        // Internal color format: YUV 4:4:4, or Y only for alpha.
+        w.put(if self.channels == 1 { 0 } else { 3 }, 3);
+        w.put_bool(self.scaled);
+        // All subbands.
+        w.put(0, 4);
+        if self.channels > 1 {
+            w.put(0, 8);
+        }
+
+        // Uniform DC quantization.
+        w.put_bool(true);
+        self.write_quantizers(w, &self.dc_quantizers);
+        // Lowpass has its own uniform quantization.
+        w.put_bool(false);
+        w.put_bool(true);
+        self.write_quantizers(w, &self.lowpass_quantizers);
+        // So does highpass.
+        w.put_bool(false);
+        w.put_bool(true);
+        self.write_quantizers(w, &self.highpass_quantizers);
+        w.fill_to_byte();
+    }
+
+    fn write_quantizers(&self, w: &mut BitWriter, quantizers: &[Quantizer]) {
+        if quantizers.len() > 1 {
+            // Independent quantizers per channel.
+            w.put(2, 2);
+        }
+        for quantizer in quantizers {
+            w.put(quantizer.index as u32, 8);
+        }
+    }
+
+    /// Reads and color converts the pixels of a macroblock. Pixels past the
+    /// edges of the image repeat the last row and column.
+    fn read_color(
+        &mut self,
+        rgba: &[u8],
+        width: u32,
+        height: u32,
+        mb_x: usize,
+        mb_y: usize,
+        scaled: bool,
+    ) {
+        let shift = if scaled { 3 } else { 0 };
+        for y in 0..16 {
+            let src_y = (mb_y * 16 + y).min(height as usize - 1);
+            for x in 0..16 {
+                let src_x = (mb_x * 16 + x).min(width as usize - 1);
+                let i = (src_y * width as usize + src_x) * 4;
+                let mut r = (rgba[i] as i32) << shift;
+                let mut g = (rgba[i + 1] as i32) << shift;
+                let mut b = (rgba[i + 2] as i32) << shift;
+                b -= r;
+                r += ((b + 1) >> 1) - g;
+                g += r >> 1;
+                let index = pixel_index(x, y);
+                self.coefficients[0][index] = g - (128 << shift);
+                self.coefficients[1][index] = -r;
+                self.coefficients[2][index] = b;
+            }
+        }
+    }
+
+    /// Reads the alpha channel of a macroblock.
+    fn read_alpha(
+        &mut self,
+        rgba: &[u8],
+        width: u32,
+        height: u32,
+        mb_x: usize,
+        mb_y: usize,
+        scaled: bool,
+    ) {
+        let shift = if scaled { 3 } else { 0 };
+        for y in 0..16 {
+            let src_y = (mb_y * 16 + y).min(height as usize - 1);
+            for x in 0..16 {
+                let src_x = (mb_x * 16 + x).min(width as usize - 1);
+                let a = rgba[(src_y * width as usize + src_x) * 4 + 3] as i32;
+                self.coefficients[0][pixel_index(x, y)] = (a - 128) << shift;
+            }
+        }
+    }
+
+    fn next_row(&mut self) {
+        std::mem::swap(&mut self.predictions, &mut self.previous_predictions);
+    }
+
+    fn encode_macroblock(&mut self, w: &mut BitWriter, position: &MacroblockPosition) {
+        self.transform();
+        self.quantize();
+        self.predict(position);
+        self.encode_dc(w);
+        self.encode_lowpass(w, position);
+        self.encode_highpass(w, position);
+    }
+
+    fn transform(&mut self) {
+        for (channel, coefficients) in self.coefficients.iter_mut().enumerate() {
+            for block in coefficients.chunks_exact_mut(16) {
+                transform_block(block);
+            }
+            if self.scaled && channel > 0 {
+                for i in (0..256).step_by(16) {
+                    coefficients[i] >>= 1;
+                }
+            }
+            transform_dc(coefficients);
+        }
+    }
+
+    fn quantize(&mut self) {
+        for channel in 0..self.channels {
+            let coefficients = &mut self.coefficients[channel];
+            for (j, &offset) in BLOCK_OFFSETS.iter().enumerate() {
+                let block = &mut coefficients[offset..offset + 16];
+                block[0] = if j == 0 {
+                    self.dc_quantizers[channel].quantize(block[0])
+                } else {
+                    self.lowpass_quantizers[channel].quantize(block[0])
+                };
+                for value in &mut block[1..] {
+                    *value = self.highpass_quantizers[channel].quantize(*value);
+                }
+            }
+            self.block_dc[channel] = DC_INDEX.map(|i| coefficients[i]);
+        }
+    }
+
+    /// Subtracts predictions from neighbouring macroblocks and blocks.
+    fn predict(&mut self, position: &MacroblockPosition) {
+        const ORIENT_WEIGHT: i32 = 4;
+        let x = position.x;
+
+        // 0: from the left, 1: from the top, 2: from both, 3: none.
+        let dc_mode = if position.left && position.top {
+            3
+        } else if position.left {
+            1
+        } else if position.top {
+            0
+        } else {
+            let left = &self.predictions;
+            let top = &self.previous_predictions;
+            let mut strength_h = (top[0][x - 1].dc - left[0][x - 1].dc).abs();
+            let mut strength_v = (top[0][x - 1].dc - top[0][x].dc).abs();
+            if self.channels > 1 {
+                strength_h *= 2;
+                strength_v *= 2;
+                for c in 1..3 {
+                    strength_h += (top[c][x - 1].dc - left[c][x - 1].dc).abs();
+                    strength_v += (top[c][x - 1].dc - top[c][x].dc).abs();
+                }
+            }
+            if strength_h * ORIENT_WEIGHT < strength_v {
+                1
+            } else if strength_v * ORIENT_WEIGHT < strength_h {
+                0
+            } else {
+                2
+            }
+        };
+
+        // 0: from the left, 1: from the top, 2: none.
+        let ac_mode = {
+            let dc = &self.block_dc;
+            let mut strength_h = dc[0][1].abs() + dc[0][2].abs() + dc[0][3].abs();
+            let mut strength_v = dc[0][4].abs() + dc[0][8].abs() + dc[0][12].abs();
+            if self.channels > 1 {
+                strength_h += dc[1][1].abs() + dc[2][1].abs();
+                strength_v += dc[1][4].abs() + dc[2][4].abs();
+            }
+            if strength_h * ORIENT_WEIGHT < strength_v {
+                1
+            } else if strength_v * ORIENT_WEIGHT < strength_h {
+                0
+            } else {
+                2
+            }
+        };
+        self.orientation = 2 - ac_mode;
+
+        for channel in 0..self.channels {
+            let dc = &mut self.block_dc[channel];
+            self.predictions[channel][x] = PredictionInfo {
+                dc: dc[0],
+                ad: [dc[1], dc[2], dc[3], dc[4], dc[8], dc[12]],
+                cbp: 0,
+            };
+
+            let left = self.predictions[channel].get(x.wrapping_sub(1));
+            let top = &self.previous_predictions[channel][x];
+            match dc_mode {
+                0 => dc[0] -= left.map_or(0, |l| l.dc),
+                1 => dc[0] -= top.dc,
+                2 => dc[0] -= (left.map_or(0, |l| l.dc) + top.dc) >> 1,
+                _ => {}
+            }
+            match dc_mode {
+                0 => {
+                    let ad = left.map_or([0; 6], |l| l.ad);
+                    dc[1] -= ad[0];
+                    dc[2] -= ad[1];
+                    dc[3] -= ad[2];
+                }
+                1 => {
+                    dc[4] -= top.ad[3];
+                    dc[8] -= top.ad[4];
+                    dc[12] -= top.ad[5];
+                }
+                _ => {}
+            }
+
+            let p = &mut self.coefficients[channel];
+            if ac_mode == 1 {
+                for k in (0..=192).step_by(64) {
+                    for j in [48, 32, 16] {
+                        p[k + j + 10] -= p[k + j + 10 - 16];
+                        p[k + j + 2] -= p[k + j + 2 - 16];
+                        p[k + j + 9] -= p[k + j + 9 - 16];
+                    }
+                }
+            } else if ac_mode == 0 {
+                for k in (0..64).step_by(16) {
+                    for j in [192, 128, 64] {
+                        p[k + j + 5] -= p[k + j + 5 - 64];
+                        p[k + j + 1] -= p[k + j + 1 - 64];
+                        p[k + j + 6] -= p[k + j + 6 - 64];
+                    }
+                }
+            }
+        }
+    }
+
+    fn encode_dc(&mut self, w: &mut BitWriter) {
+        let context = &mut self.context;
+        let mut laplacian_mean = [0; 2];
+        if self.channels == 1 {
+            let model_bits = context.model_dc.bits[0];
+            let dc = self.block_dc[0][0];
+            let magnitude = dc.unsigned_abs();
+            let quantized = magnitude >> model_bits;
+            w.put_bool(quantized != 0);
+            if quantized != 0 {
+                encode_significant_abs_level(w, &mut context.expt[3], quantized);
+                laplacian_mean[0] += 1;
+            }
+            w.put(magnitude, model_bits);
+            if dc != 0 {
+                w.put_bool(dc < 0);
+            }
+        } else {
+            let bits = context.model_dc.bits;
+            let dc = [
+                self.block_dc[0][0],
+                self.block_dc[1][0],
+                self.block_dc[2][0],
+            ];
+            let quantized = [
+                dc[0].unsigned_abs() >> bits[0],
+                dc[1].unsigned_abs() >> bits[1],
+                dc[2].unsigned_abs() >> bits[1],
+            ];
+            let index = (quantized[0] != 0) as usize * 4
+                + (quantized[1] != 0) as usize * 2
+                + (quantized[2] != 0) as usize;
+            context.expt[2].put(w, index);
+            for channel in 0..3 {
+                let (code, model_bits, mean) = if channel == 0 {
+                    (3, bits[0], 0)
+                } else {
+                    (4, bits[1], 1)
+                };
+                if quantized[channel] != 0 {
+                    encode_significant_abs_level(w, &mut context.expt[code], quantized[channel]);
+                    laplacian_mean[mean] += 1;
+                }
+                w.put(dc[channel].unsigned_abs(), model_bits);
+                if dc[channel] != 0 {
+                    w.put_bool(dc[channel] < 0);
+                }
+            }
+        }
+        context.model_dc.update(self.channels, laplacian_mean);
+    }
+
+    fn encode_lowpass(&mut self, w: &mut BitWriter, position: &MacroblockPosition) {
+        let context = &mut self.context;
+        if position.reset_totals {
+            reset_scan_totals(&mut context.scan_lowpass);
+        }
+
+        let mut runs = vec![Vec::new(); self.channels];
+        let mut residuals = vec![[0; 16]; self.channels];
+        for channel in 0..self.channels {
+            let model_bits = context.model_lowpass.bits[(channel > 0) as usize];
+            adaptive_scan(
+                &self.block_dc[channel],
+                &mut residuals[channel],
+                &mut context.scan_lowpass,
+                model_bits,
+                0,
+                &mut runs[channel],
+            );
+        }
+
+        if self.channels == 1 {
+            w.put_bool(!runs[0].is_empty());
+        } else {
+            const FULL: u32 = 3;
+            const MAX: u32 = 7;
+            let cbp = (!runs[0].is_empty()) as u32
+                + (!runs[1].is_empty()) as u32 * 2
+                + (!runs[2].is_empty()) as u32 * 4;
+            let count_max = context.cbp_count_max;
+            let count_zero = context.cbp_count_zero;
+            if count_zero <= 0 || count_max < 0 {
+                let value = if count_max < count_zero {
+                    MAX - cbp
+                } else {
+                    cbp
+                };
+                match value {
+                    0 => w.put(0, 1),
+                    1 => w.put((FULL + 1) & 6, FULL),
+                    _ => w.put(value + MAX + 1, FULL + 1),
+                }
+            } else {
+                w.put(cbp, FULL);
+            }
+            context.cbp_count_max = (count_max + 1 - 4 * (cbp == MAX) as i32).clamp(-8, 7);
+            context.cbp_count_zero = (count_zero + 1 - 4 * (cbp == 0) as i32).clamp(-8, 7);
+        }
+
+        let mut laplacian_mean = [0; 2];
+        for channel in 0..self.channels {
+            let chroma = channel > 0;
+            let model_bits = context.model_lowpass.bits[chroma as usize];
+            if !runs[channel].is_empty() {
+                laplacian_mean[chroma as usize] += runs[channel].len() as i32;
+                encode_block(
+                    w,
+                    &mut context.expt,
+                    &runs[channel],
+                    chroma,
+                    CONTEXT_LOWPASS,
+                );
+            }
+            if model_bits != 0 {
+                for &residual in &residuals[channel][1..] {
+                    w.put(residual >> 1, model_bits + (residual & 1));
+                }
+            }
+        }
+
+        context.model_lowpass.update(self.channels, laplacian_mean);
+        if position.reset_context {
+            context.adapt_lowpass();
+        }
+    }
+
+    fn encode_highpass(&mut self, w: &mut BitWriter, position: &MacroblockPosition) {
+        if position.reset_totals {
+            reset_scan_totals(&mut self.context.scan_horizontal);
+            reset_scan_totals(&mut self.context.scan_vertical);
+        }
+        self.encode_cbp(w, position);
+        self.encode_coefficients(w);
+        if position.reset_context {
+            self.context.adapt_highpass();
+        }
+    }
+
+    /// Computes and predicts which blocks have significant highpass coefficients.
+    fn predict_cbp(&mut self, position: &MacroblockPosition) {
+        const AVERAGE_DIFFERENCES: i32 = 3;
+        let x = position.x;
+        for channel in 0..self.channels {
+            let chroma = (channel > 0) as usize;
+            let threshold = (1i32 << self.context.model_highpass.bits[chroma]) - 1;
+            let mut cbp = 0;
+            for (j, &offset) in BLOCK_OFFSETS.iter().enumerate() {
+                let block = &self.coefficients[channel][offset + 1..offset + 16];
+                if block.iter().any(|&v| v.abs() > threshold) {
+                    cbp |= 1 << j;
+                }
+            }
+            self.cbp[channel] = cbp;
+            self.predictions[channel][x].cbp = cbp;
+
+            // Only the top left block is predicted from a neighbour.
+            let mut predicted = if position.left {
+                if position.top {
+                    1
+                } else {
+                    (self.previous_predictions[channel][x].cbp >> 10) & 1
+                }
+            } else {
+                (self.predictions[channel][x - 1].cbp >> 5) & 1
+            };
+            predicted |= (cbp & 0x3300) << 2;
+            predicted |= (cbp & 0xcc) << 6;
+            predicted |= (cbp & 0x33) << 2;
+            predicted |= (cbp & 0x11) << 1;
+            predicted |= (cbp & 0x2) << 3;
+
+            let context = &mut self.context;
+            self.diff_cbp[channel] = match context.cbp_state[chroma] {
+                0 => predicted ^ cbp,
+                1 => cbp,
+                _ => cbp ^ 0xffff,
+            };
+
+            let ones = cbp.count_ones() as i32;
+            context.cbp_count0[chroma] =
+                (context.cbp_count0[chroma] + ones - AVERAGE_DIFFERENCES).clamp(-16, 15);
+            context.cbp_count1[chroma] =
+                (context.cbp_count1[chroma] + 16 - ones - AVERAGE_DIFFERENCES).clamp(-16, 15);
+            context.cbp_state[chroma] = if context.cbp_count0[chroma] < 0 {
+                if context.cbp_count0[chroma] < context.cbp_count1[chroma] {
+                    1
+                } else {
+                    2
+                }
+            } else if context.cbp_count1[chroma] < 0 {
+                2
+            } else {
+                0
+            };
+        }
+    }
+
+    fn encode_cbp(&mut self, w: &mut BitWriter, position: &MacroblockPosition) {
+        const ONES: [usize; 16] = [0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3, 3, 4];
+        const PATTERN_LENGTHS: [u32; 16] = [0, 2, 2, 2, 2, 2, 3, 2, 2, 3, 3, 2, 3, 2, 2, 0];
+        const PATTERN_CODES: [u32; 16] = [0, 0, 1, 0, 2, 1, 4, 3, 3, 5, 6, 2, 7, 1, 0, 0];
+        const TAB0: [usize; 16] = [0, 1, 1, 2, 1, 3, 3, 4, 1, 3, 3, 4, 2, 4, 4, 5];
+        const FL0: [u32; 16] = [0, 2, 2, 1, 2, 2, 2, 2, 2, 2, 2, 2, 1, 2, 2, 0];
+        const CODE0: [u32; 16] = [0, 0, 1, 0, 2, 0, 1, 0, 3, 2, 3, 1, 1, 2, 3, 0];
+
+        self.predict_cbp(position);
+        let context = &mut self.context;
+
+        let mut diff_y = self.diff_cbp[0];
+        let (mut diff_u, mut diff_v) = if self.channels > 1 {
+            (self.diff_cbp[1], self.diff_cbp[2])
+        } else {
+            (0, 0)
+        };
+
+        // Which 8x8 quadrants have anything coded.
+        let any = diff_y | diff_u | diff_v;
+        let mut pattern = 0;
+        for quadrant in 0..4 {
+            if (any >> (quadrant * 4)) & 0xf != 0 {
+                pattern |= 1 << quadrant;
+            }
+        }
+        let count = ONES[pattern];
+        context.cbpcy1.put_adapting(w, count);
+        if PATTERN_LENGTHS[pattern] != 0 {
+            w.put(PATTERN_CODES[pattern], PATTERN_LENGTHS[pattern]);
+        }
+
+        for _ in 0..4 {
+            let code = (diff_y & 0xf) as usize;
+            let code_u = (diff_u & 0xf) as usize;
+            let code_v = (diff_v & 0xf) as usize;
+            diff_y >>= 4;
+            diff_u >>= 4;
+            diff_v >>= 4;
+            let chroma = (code_u != 0) as u32 + (code_v != 0) as u32 * 2;
+            if code == 0 && chroma == 0 {
+                continue;
+            }
+
+            let value = if chroma != 0 {
+                if TAB0[code] > 2 {
+                    8
+                } else {
+                    TAB0[code] + 5
+                }
+            } else {
+                TAB0[code] - 1
+            };
+            context.cbpcy.put_adapting(w, value);
+
+            match chroma {
+                0 => {}
+                1 => w.put(1, 1),
+                _ => w.put(3 - chroma, 2),
+            }
+            if value == 8 {
+                if TAB0[code] == 3 {
+                    w.put(1, 1);
+                } else {
+                    w.put(5 - TAB0[code] as u32, 2);
+                }
+            }
+            if FL0[code] != 0 {
+                w.put(CODE0[code], FL0[code]);
+            }
+
+            if self.channels > 1 {
+                for pattern in [code_u, code_v] {
+                    if pattern != 0 {
+                        context.expt[1].put(w, ONES[pattern] - 1);
+                        if PATTERN_LENGTHS[pattern] != 0 {
+                            w.put(PATTERN_CODES[pattern], PATTERN_LENGTHS[pattern]);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn encode_coefficients(&mut self, w: &mut BitWriter) {
+        let context = &mut self.context;
+        let flex_params = |model_bits: u32, trim: u32| {
+            if trim <= model_bits {
+                (trim, model_bits - trim)
+            } else {
+                (0, 0)
+            }
+        };
+
+        let mut model_bits = context.model_highpass.bits[0];
+        let (mut trim, mut flex) = flex_params(model_bits, context.trim_flex_bits);
+        let mut chroma = false;
+        let mut laplacian_mean = [0; 2];
+        let vertical = self.orientation == 1;
+
+        for channel in 0..self.channels {
+            let mut pattern = self.cbp[channel];
+            for block in 0..4 {
+                for subblock in 0..4 {
+                    let offset = BLOCK_OFFSETS[block * 4 + subblock];
+                    let coefficients = &self.coefficients[channel][offset..offset + 16];
+                    if pattern & 1 == 0 {
+                        if flex != 0 {
+                            for &position in &DCT_INDEX[1..] {
+                                let value = coefficients[position];
+                                let trimmed = value.unsigned_abs() >> trim;
+                                let mut word = trimmed & ((1 << flex) - 1);
+                                let mut length = flex;
+                                if trimmed != 0 {
+                                    word = word * 2 + (value < 0) as u32;
+                                    length += 1;
+                                }
+                                w.put(word, length);
+                            }
+                        }
+                    } else {
+                        let scan = if vertical {
+                            &mut context.scan_vertical
+                        } else {
+                            &mut context.scan_horizontal
+                        };
+                        let mut runs = Vec::new();
+                        let mut residuals = [0; 16];
+                        adaptive_scan(
+                            coefficients,
+                            &mut residuals,
+                            scan,
+                            model_bits,
+                            trim,
+                            &mut runs,
+                        );
+                        laplacian_mean[chroma as usize] += runs.len() as i32;
+                        encode_block(w, &mut context.expt, &runs, chroma, CONTEXT_HIGHPASS);
+                        if flex != 0 {
+                            for &position in &DCT_INDEX[1..] {
+                                let residual = residuals[position];
+                                w.put(residual >> 1, flex + (residual & 1));
+                            }
+                        }
+                    }
+                    pattern >>= 1;
+                }
+                if block == 3 {
+                    model_bits = context.model_highpass.bits[1];
+                    chroma = true;
+                    (trim, flex) = flex_params(model_bits, context.trim_flex_bits);
+                }
+            }
+        }
+
+        context.model_highpass.update(self.channels, laplacian_mean);
+    }
+}
+
+/// Collects the significant coefficients of a block as `(run, level)` pairs in
+/// scan order, updating the scan order as it goes. The bits below `model_bits`
+/// of every coefficient are stored in `residuals` as `value << 1 | has_sign`.
+fn adaptive_scan(
+    coefficients: &[i32],
+    residuals: &mut [u32; 16],
+    scan: &mut [ScanEntry; 16],
+    model_bits: u32,
+    trim_bits: u32,
+    runs: &mut Vec<(u32, i32)>,
+) {
+    let threshold = (1 << model_bits) - 1;
+    let mut run = 0;
+    for k in 1..16 {
+        let position = scan[k].position;
+        let level = coefficients[position];
+        let magnitude = level.unsigned_abs();
+        if magnitude > threshold {
+            residuals[position] = ((magnitude & threshold) >> trim_bits) * 2;
+            scan[k].total += 1;
+            if k > 1 && scan[k].total > scan[k - 1].total {
+                scan.swap(k, k - 1);
+            }
+            let significant = (magnitude >> model_bits) as i32;
+            runs.push((run, if level < 0 { -significant } else { significant }));
+            run = 0;
+        } else {
+            run += 1;
+            let trimmed = magnitude >> trim_bits;
+            residuals[position] =
+                trimmed * 4 + (level < 0 && trimmed != 0) as u32 * 2 + (trimmed != 0) as u32;
+        }
+    }
+}
+
+fn encode_significant_abs_level(w: &mut BitWriter, code: &mut AdaptiveHuffman, level: u32) {
+    const INDICES: [usize; 16] = [0, 1, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5];
+    const FIXED_LENGTHS: [u32; 6] = [0, 0, 1, 2, 2, 2];
+
+    let level = level - 1;
+    if level >= 16 {
+        let fixed = 4 + (32 - (level >> 5).leading_zeros());
+        code.put_adapting(w, 6);
+        if fixed > 18 {
+            w.put(15, 4);
+            if fixed > 21 {
+                w.put(3, 2);
+                w.put(fixed - 22, 3);
+            } else {
+                w.put(fixed - 19, 2);
+            }
+        } else {
+            w.put(fixed - 4, 4);
+        }
+        w.put(level, fixed);
+    } else {
+        let index = INDICES[level as usize];
+        code.put_adapting(w, index);
+        w.put(level, FIXED_LENGTHS[index]);
+    }
+}
+
+fn encode_significant_run(w: &mut BitWriter, code: &AdaptiveHuffman, run: u32, max_run: u32) {
+    const SHORT_LENGTHS: [u32; 4] = [3, 3, 2, 1];
+    const RUN_BINS: [usize; 15] = [0, 0, 0, 0, 2, 2, 2, 1, 1, 1, 1, 0, 0, 0, 0];
+    const RUN_FIXED_LENGTHS: [u32; 15] = [0, 0, 1, 1, 3, 0, 0, 1, 1, 2, 0, 0, 0, 0, 1];
+    const INDICES: [usize; 34] = [
+        0, 1, 2, 2, 3, 3, 4, 4, 4, 4, 4, 4, 4, 4, 0, 1, 2, 2, 3, 3, 4, 4, 4, 4, 0, 0, 0, 0, 0, 1,
+        2, 3, 4, 4,
+    ];
+
+    if max_run < 5 {
+        if max_run > 1 {
+            w.put(
+                (max_run != run) as u32,
+                SHORT_LENGTHS[(max_run - run) as usize] - (4 - max_run),
+            );
+        }
+        return;
+    }
+
+    let bin = RUN_BINS[max_run as usize];
+    let index = INDICES[run as usize + bin * 14 - 1];
+    code.put(w, index);
+    w.put(run + 1, RUN_FIXED_LENGTHS[index + bin * 5]);
+}
+
+/// Writes the `(run, level)` pairs of a block.
+fn encode_block(
+    w: &mut BitWriter,
+    expt: &mut [AdaptiveHuffman; 21],
+    runs: &[(u32, i32)],
+    chroma: bool,
+    context: usize,
+) {
+    let chroma_offset = chroma as usize * 3;
+    let is_significant = |level: i32| level.unsigned_abs() > 1;
+    // 0: last coefficient, 1: followed by a coefficient with no run, 2:
+    // followed by a run.
+    let next_run_kind = |k: usize| {
+        if k + 1 == runs.len() {
+            0
+        } else if runs[k + 1].0 > 0 {
+            2
+        } else {
+            1
+        }
+    };
+
+    let (run, level) = runs[0];
+    let no_run = (run == 0) as usize;
+    let significant = is_significant(level) as usize;
+    let mut next_kind = next_run_kind(0);
+    let index = next_kind * 4 + significant * 2 + no_run;
+    expt[context + chroma_offset].put_signed(w, index, level < 0);
+    let mut cont = no_run & next_kind;
+    if significant != 0 {
+        encode_significant_abs_level(w, &mut expt[6 + context + cont], level.unsigned_abs() - 1);
+    }
+    let mut location = 1;
+    if no_run == 0 {
+        encode_significant_run(w, &expt[0], run, 15 - location);
+    }
+    location += run + 1;
+
+    for (k, &(run, level)) in runs.iter().enumerate().skip(1) {
+        if next_kind == 2 {
+            encode_significant_run(w, &expt[0], run, 15 - location);
+        }
+        location += run + 1;
+        next_kind = next_run_kind(k);
+        let significant = is_significant(level) as usize;
+        let index = next_kind * 2 + significant;
+        let negative = level < 0;
+        if location < 15 {
+            expt[context + cont + 1 + chroma_offset].put_signed(w, index, negative);
+        } else if location == 15 {
+            const CODES: [u32; 4] = [0, 6, 2, 7];
+            const LENGTHS: [u32; 4] = [1, 3, 2, 3];
+            w.put(CODES[index] * 2 + negative as u32, LENGTHS[index] + 1);
+        } else {
+            w.put(index as u32 * 2 + negative as u32, 2);
+        }
+
+        cont &= next_kind;
+        if significant != 0 {
+            encode_significant_abs_level(
+                w,
+                &mut expt[6 + context + cont],
+                level.unsigned_abs() - 1,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An image whose channels vary independently, so every plane has detail.
+    fn test_image(width: u32, height: u32) -> Vec<u8> {
+        let mut rgba = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                let v = (x * 97 + y * 61 + x * y * 13) as u8;
+                rgba.extend_from_slice(&[v, v ^ 0x5a, v.wrapping_mul(3), 255 - v / 2]);
+            }
+        }
+        rgba
+    }
+
+    /// Checks the container around the codestream, and returns the codestream.
+    fn unwrap_container(data: &[u8], width: u32, height: u32, has_alpha: bool) -> &[u8] {
+        let long = |offset: usize| {
+            u32::from_le_bytes(data[offset..offset + 4].try_into().expect("4 bytes"))
+        };
+        assert_eq!(&data[..8], b"II\xbc\x01\x20\0\0\0");
+        assert_eq!(data[23], if has_alpha { 0x0f } else { 0x0c });
+        assert_eq!(long(0x42), width);
+        assert_eq!(long(0x4e), height);
+        let offset = long(0x72) as usize;
+        let length = long(0x7e) as usize;
+        assert_eq!(offset + length, data.len());
+        let codestream = &data[offset..];
+        assert_eq!(&codestream[..8], b"WMPHOTO\0");
+        codestream
+    }
+
+    #[test]
+    fn image_header() {
+        let data = encode_jpeg_xr(5, 3, &test_image(5, 3), false, JpegXrOptions::default());
+        let codestream = unwrap_container(&data, 5, 3, false);
+        // Version 1.1, spatial layout without tiles.
+        assert_eq!(codestream[8..10], [0x11, 0x00]);
+        // Abbreviated header with long words, and no alpha plane.
+        assert_eq!(codestream[10], 0xc0);
+        // 8 bits per RGB channel.
+        assert_eq!(codestream[11], 0x71);
+        assert_eq!(codestream[12..16], [0, 4, 0, 2]);
+
+        let options = JpegXrOptions {
+            quantization: 30,
+            trim_flex_bits: 2,
+        };
+        let data = encode_jpeg_xr(5, 3, &test_image(5, 3), true, options);
+        let codestream = unwrap_container(&data, 5, 3, true);
+        // Trimmed flexbits and an alpha plane.
+        assert_eq!(codestream[10], 0xd1);
+    }
+
+    #[test]
+    fn large_image_header() {
+        // 257 macroblocks wide doesn't fit an abbreviated header.
+        let (width, height) = (257 * 16, 1);
+        let data = encode_jpeg_xr(
+            width,
+            height,
+            &test_image(width, height),
+            false,
+            JpegXrOptions::default(),
+        );
+        let codestream = unwrap_container(&data, width, height, false);
+        assert_eq!(codestream[10], 0x40);
+        assert_eq!(codestream[12..20], [0, 0, 0x10, 0x0f, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn quantization_and_trimming_reduce_size() {
+        let image = test_image(32, 32);
+        let size = |quantization, trim_flex_bits| {
+            let options = JpegXrOptions {
+                quantization,
+                trim_flex_bits,
+            };
+            encode_jpeg_xr(32, 32, &image, true, options).len()
+        };
+        // 0 and 1 are both lossless.
+        assert_eq!(size(0, 0), size(1, 0));
+        assert!(size(20, 0) < size(0, 0));
+        assert!(size(100, 0) < size(20, 0));
+        assert!(size(0, 4) < size(0, 0));
+    }
+
+    /// Encodes and decodes an image, returning the decoded unmultiplied RGBA pixels.
+    fn round_trip(
+        width: u32,
+        height: u32,
+        rgba: &[u8],
+        has_alpha: bool,
+        options: JpegXrOptions,
+    ) -> Vec<u8> {
+        let data = encode_jpeg_xr(width, height, rgba, has_alpha, options);
+        let bitmap = decode_jpeg_xr(&data).expect("Decodable JPEG XR");
+        assert_eq!((bitmap.width(), bitmap.height()), (width, height));
+        let mut decoded = bitmap.to_rgba().data().to_vec();
+        if has_alpha {
+            crate::utils::unmultiply_alpha_rgba(&mut decoded);
+        }
+        decoded
+    }
+
+    /// The mean and maximum difference between the channels of two images.
+    fn difference(expected: &[u8], actual: &[u8], has_alpha: bool) -> (f64, u8) {
+        let channels = if has_alpha { 4 } else { 3 };
+        let differences: Vec<u8> = expected
+            .chunks_exact(4)
+            .zip(actual.chunks_exact(4))
+            .flat_map(|(e, a)| (0..channels).map(|i| e[i].abs_diff(a[i])))
+            .collect();
+        let mean =
+            differences.iter().map(|&d| f64::from(d)).sum::<f64>() / differences.len() as f64;
+        (mean, differences.into_iter().max().unwrap_or(0))
+    }
+
+    #[test]
+    fn lossless_round_trip() {
+        // Sizes that are and aren't multiples of a macroblock.
+        for (width, height) in [(1, 1), (5, 3), (16, 16), (33, 17)] {
+            let mut image = test_image(width, height);
+            let decoded = round_trip(width, height, &image, false, JpegXrOptions::default());
+            for pixel in image.chunks_exact_mut(4) {
+                pixel[3] = 255;
+            }
+            assert_eq!(decoded, image, "{width}x{height} without alpha");
+
+            // Unmultiplying loses precision, so compare the premultiplied pixels.
+            let mut expected = test_image(width, height);
+            let data = encode_jpeg_xr(width, height, &expected, true, JpegXrOptions::default());
+            let bitmap = decode_jpeg_xr(&data).expect("Decodable JPEG XR");
+            premultiply_alpha_rgba(&mut expected);
+            assert_eq!(
+                bitmap.to_rgba().data(),
+                expected,
+                "{width}x{height} with alpha"
+            );
+        }
+    }
+
+    #[test]
+    fn lossy_round_trip() {
+        // A smooth gradient, which survives quantization well.
+        let (width, height) = (48, 40);
+        let mut image = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                image.extend_from_slice(&[
+                    (x * 5) as u8,
+                    (y * 6) as u8,
+                    ((x + y) * 2) as u8,
+                    255 - (y * 2) as u8,
+                ]);
+            }
+        }
+
+        for has_alpha in [false, true] {
+            let options = JpegXrOptions {
+                quantization: 20,
+                trim_flex_bits: 0,
+            };
+            let decoded = round_trip(width, height, &image, has_alpha, options);
+            let (mean, max) = difference(&image, &decoded, has_alpha);
+            assert!(mean < 4.0, "mean difference {mean} with alpha {has_alpha}");
+            assert!(max <= 32, "max difference {max} with alpha {has_alpha}");
+
+            let options = JpegXrOptions {
+                quantization: 0,
+                trim_flex_bits: 4,
+            };
+            let decoded = round_trip(width, height, &image, has_alpha, options);
+            let (mean, max) = difference(&image, &decoded, has_alpha);
+            assert!(mean < 2.0, "mean difference {mean} with alpha {has_alpha}");
+            assert!(max <= 16, "max difference {max} with alpha {has_alpha}");
+        }
+    }
+}
@@ -669,7 +669,11 @@ pub fn shape_hit_test(
 }
 
 /// Test whether the given point is contained within the paths specified by the draw commands.
-pub fn draw_command_fill_hit_test(commands: &[DrawCommand], test_point: swf::Point<Twips>) -> bool {
+pub fn draw_command_fill_hit_test(
+    commands: &[DrawCommand],
+    test_point: swf::Point<Twips>,
+    rule: FillRule,
+) -> bool {
     let mut cursor = swf::Point::ZERO;
     let mut fill_start = swf::Point::ZERO;
     let mut winding = 0;
@@ -720,7 +724,10 @@ pub fn draw_command_fill_hit_test(commands: &[DrawCommand], test_point: swf::Poi
         winding += winding_number_line(test_point, cursor, fill_start);
     }
 
-    winding & 0b1 != 0
+    match rule {
+        FillRule::EvenOdd => winding & 0b1 != 0,
+        FillRule::NonZero => winding != 0,
+    }
 }
 
 /// Test whether the given point is contained within the strokes specified by the draw commands.
@@ -1462,4 +1469,52 @@ mod tests {
             1,
         );
     }
+
+    /// Two squares drawn in the same direction, one inside the other.
+    #[test]
+    fn draw_command_fill_hit_test_fill_rule() {
+        let square = |min: f64, max: f64| {
+            [
+                DrawCommand::MoveTo(swf::Point::from_pixels(min, min)),
+                DrawCommand::LineTo(swf::Point::from_pixels(max, min)),
+                DrawCommand::LineTo(swf::Point::from_pixels(max, max)),
+                DrawCommand::LineTo(swf::Point::from_pixels(min, max)),
+                DrawCommand::LineTo(swf::Point::from_pixels(min, min)),
+            ]
+        };
+        let commands: Vec<_> = square(0.0, 100.0)
+            .into_iter()
+            .chain(square(25.0, 75.0))
+            .collect();
+
+        let inner = swf::Point::from_pixels(50.0, 50.0);
+        let outer = swf::Point::from_pixels(10.0, 50.0);
+        let outside = swf::Point::from_pixels(150.0, 50.0);
+
+        assert!(!draw_command_fill_hit_test(
+            &commands,
+            inner,
+            FillRule::EvenOdd
+        ));
+        assert!(draw_command_fill_hit_test(
+            &commands,
+            outer,
+            FillRule::EvenOdd
+        ));
+        assert!(draw_command_fill_hit_test(
+            &commands,
+            inner,
+            FillRule::NonZero
+        ));
+        assert!(draw_command_fill_hit_test(
+            &commands,
+            outer,
+            FillRule::NonZero
+        ));
+        assert!(!draw_command_fill_hit_test(
+            &commands,
+            outside,
+            FillRule::NonZero
+        ));
+    }
 }
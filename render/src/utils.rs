@@ -1,5 +1,7 @@
 use crate::bitmap::{Bitmap, BitmapFormat};
 use crate::error::Error;
+use image::codecs::jpeg::JpegEncoder;
+use image::ExtendedColorType;
 use std::borrow::Cow;
 use std::io::Read;
 use swf::Color;
@@ -441,8 +443,64 @@ fn decode_gif(data: &[u8]) -> Result<Bitmap, Error> {
     ))
 }
 
+/// Encodes unmultiplied RGBA pixels as a PNG.
+///
+/// If `has_alpha` is false, the alpha channel is dropped and an RGB PNG is written.
+pub fn encode_png(
+    width: u32,
+    height: u32,
+    rgba: &[u8],
+    has_alpha: bool,
+    fast_compression: bool,
+) -> Result<Vec<u8>, Error> {
+    let mut out_data = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out_data, width, height);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_compression(if fast_compression {
+        png::Compression::Fast
+    } else {
+        png::Compression::Default
+    });
+
+    let data = if has_alpha {
+        encoder.set_color(png::ColorType::Rgba);
+        Cow::Borrowed(rgba)
+    } else {
+        encoder.set_color(png::ColorType::Rgb);
+        Cow::Owned(
+            rgba.chunks_exact(4)
+                .flat_map(|rgba| [rgba[0], rgba[1], rgba[2]])
+                .collect(),
+        )
+    };
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&data)?;
+    writer.finish()?;
+
+    Ok(out_data)
+}
+
+/// Encodes unmultiplied RGBA pixels as a baseline JPEG, with a quality from 1 to 100.
+///
+/// JPEG has no alpha channel, so it is ignored.
+pub fn encode_jpeg(width: u32, height: u32, rgba: &[u8], quality: u8) -> Result<Vec<u8>, Error> {
+    let rgb: Vec<u8> = rgba
+        .chunks_exact(4)
+        .flat_map(|rgba| [rgba[0], rgba[1], rgba[2]])
+        .collect();
+    let mut out_data = Vec::new();
+    JpegEncoder::new_with_quality(&mut out_data, quality).encode(
+        &rgb,
+        width,
+        height,
+        ExtendedColorType::Rgb8,
+    )?;
+    Ok(out_data)
+}
+
 /// Converts standard RBGA to premultiplied alpha.
-fn premultiply_alpha_rgba(rgba: &mut [u8]) {
+pub(crate) fn premultiply_alpha_rgba(rgba: &mut [u8]) {
     rgba.chunks_exact_mut(4).for_each(|rgba| {
         let a = f32::from(rgba[3]) / 255.0;
         rgba[0] = (f32::from(rgba[0]) * a) as u8;
@@ -0,0 +1,65 @@
+// NOTE: The `shader_filter_common.wgsl` source is prepended to this before compilation.
+
+struct Filter {
+    default_color: vec4<f32>,
+    bias: f32,
+    divisor: f32,
+    matrix_x: i32,
+    matrix_y: i32,
+    source_min: vec2<i32>,
+    source_max: vec2<i32>,
+    clamp: u32,
+    preserve_alpha: u32,
+    _padding1: u32,
+    _padding2: u32,
+}
+
+@group(0) @binding(0) var texture: texture_2d<f32>;
+@group(0) @binding(1) var texture_sampler: sampler;
+@group(0) @binding(2) var<uniform> filter_args: Filter;
+// Row major, four weights per element. Must match `MAX_MATRIX_SIZE` in `convolution.rs`.
+@group(0) @binding(3) var<uniform> convolution_matrix: array<vec4<f32>, 1024>;
+
+@vertex
+fn main_vertex(in: filter__VertexInput) -> filter__VertexOutput {
+    return filter__main_vertex(in);
+}
+
+/// Loads the straight alpha color of the source pixel at `coord`,
+/// applying the clamping or default color if it's outside of the source.
+fn load_source(coord: vec2<i32>) -> vec4<f32> {
+    var pos = coord;
+    if (any(pos < filter_args.source_min) || any(pos >= filter_args.source_max)) {
+        if (filter_args.clamp == 0u) {
+            return filter_args.default_color;
+        }
+        pos = clamp(pos, filter_args.source_min, filter_args.source_max - vec2<i32>(1, 1));
+    }
+    let color = textureLoad(texture, pos, 0);
+    if (color.a > 0.0) {
+        return vec4<f32>(color.rgb / color.a, color.a);
+    }
+    return vec4<f32>(0.0);
+}
+
+@fragment
+fn main_fragment(in: filter__VertexOutput) -> @location(0) vec4<f32> {
+    let size = vec2<f32>(textureDimensions(texture));
+    let center = vec2<i32>(floor(in.uv * size));
+    let half_size = vec2<i32>(filter_args.matrix_x / 2, filter_args.matrix_y / 2);
+
+    var total = vec4<f32>(0.0);
+    for (var y = 0; y < filter_args.matrix_y; y += 1) {
+        for (var x = 0; x < filter_args.matrix_x; x += 1) {
+            let index = y * filter_args.matrix_x + x;
+            let weight = convolution_matrix[index / 4][index % 4];
+            total += weight * load_source(center + vec2<i32>(x, y) - half_size);
+        }
+    }
+
+    var color = saturate(total / filter_args.divisor + vec4<f32>(filter_args.bias / 255.0));
+    if (filter_args.preserve_alpha != 0u) {
+        color.a = load_source(center).a;
+    }
+    return vec4<f32>(color.rgb * color.a, color.a);
+}
@@ -1,7 +1,7 @@
 struct Filter {
     color: vec4<f32>,
     strength: f32,
-    inner: u32,
+    glow_type: u32, // 0 outer, 1 inner
     knockout: u32,
     composite_source: u32,
 }
@@ -37,7 +37,7 @@ fn main_vertex(in: VertexInput) -> VertexOutput {
 
 @fragment
 fn main_fragment(in: VertexOutput) -> @location(0) vec4<f32> {
-    let inner = filter_args.inner > 0u;
+    let inner = filter_args.glow_type > 0u;
     let knockout = filter_args.knockout > 0u;
     let composite_source = filter_args.composite_source > 0u;
     var blur = textureSample(blurred, texture_sampler, in.blur_uv).a;
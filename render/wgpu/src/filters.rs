@@ -1,19 +1,18 @@
 mod bevel;
 mod blur;
 mod color_matrix;
+mod convolution;
 mod displacement_map;
 mod drop_shadow;
 mod glow;
 mod shader;
 
-use std::collections::HashSet;
-use std::sync::{Mutex, OnceLock};
-
 use crate::buffer_pool::TexturePool;
 use crate::descriptors::Descriptors;
 use crate::filters::bevel::BevelFilter;
 use crate::filters::blur::BlurFilter;
 use crate::filters::color_matrix::ColorMatrixFilter;
+use crate::filters::convolution::ConvolutionFilter;
 use crate::filters::displacement_map::DisplacementMapFilter;
 use crate::filters::drop_shadow::DropShadowFilter;
 use crate::filters::glow::GlowFilter;
@@ -188,6 +187,7 @@ impl<'a> FilterSource<'a> {
 pub struct Filters {
     pub blur: BlurFilter,
     pub color_matrix: ColorMatrixFilter,
+    pub convolution: ConvolutionFilter,
     pub shader: ShaderFilter,
     pub glow: GlowFilter,
     pub bevel: BevelFilter,
@@ -199,6 +199,7 @@ impl Filters {
         Self {
             blur: BlurFilter::new(device),
             color_matrix: ColorMatrixFilter::new(device),
+            convolution: ConvolutionFilter::new(device),
             shader: ShaderFilter::new(),
             glow: GlowFilter::new(device),
             bevel: BevelFilter::new(device),
@@ -276,31 +277,32 @@ impl Filters {
                 &source,
                 &filter,
             ),
-            filter => {
-                static WARNED_FILTERS: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
-                let name = match filter {
-                    Filter::GradientGlowFilter(_) => "GradientGlowFilter",
-                    Filter::GradientBevelFilter(_) => "GradientBevelFilter",
-                    Filter::ConvolutionFilter(_) => "ConvolutionFilter",
-                    Filter::ColorMatrixFilter(_)
-                    | Filter::BlurFilter(_)
-                    | Filter::GlowFilter(_)
-                    | Filter::DropShadowFilter(_)
-                    | Filter::BevelFilter(_)
-                    | Filter::DisplacementMapFilter(_)
-                    | Filter::ShaderFilter(_) => unreachable!(),
-                };
-                // Only warn once per filter type
-                if WARNED_FILTERS
-                    .get_or_init(Default::default)
-                    .lock()
-                    .unwrap()
-                    .insert(name)
-                {
-                    tracing::warn!("Unsupported filter {filter:?}");
-                }
-                None
-            }
+            Filter::GradientGlowFilter(filter) => Some(descriptors.filters.glow.apply_gradient(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                staging_belt,
+                &source,
+                &filter,
+                &self.blur,
+            )),
+            Filter::GradientBevelFilter(filter) => Some(descriptors.filters.bevel.apply_gradient(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                staging_belt,
+                &source,
+                &filter,
+                &self.blur,
+            )),
+            Filter::ConvolutionFilter(filter) => descriptors.filters.convolution.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                staging_belt,
+                &source,
+                &filter,
+            ),
         };
 
         let target = target.unwrap_or_else(|| {
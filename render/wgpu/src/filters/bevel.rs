@@ -5,11 +5,15 @@ use crate::filters::blur::BlurFilter;
 use crate::filters::{
     FilterSource, FilterVertexWithDoubleBlur, VERTEX_BUFFERS_DESCRIPTION_FILTERS_WITH_DOUBLE_BLUR,
 };
+use crate::mesh::create_gradient_texture_view;
 use crate::surface::target::CommandTarget;
 use crate::utils::SampleCountMap;
 use bytemuck::{Pod, Zeroable};
 use std::sync::OnceLock;
-use swf::BevelFilter as BevelFilterArgs;
+use swf::{
+    BevelFilter as BevelFilterArgs, GradientFilter as GradientBevelFilterArgs,
+    GradientInterpolation, GradientRecord,
+};
 use wgpu::util::StagingBelt;
 
 #[repr(C)]
@@ -23,76 +27,74 @@ struct BevelUniform {
     composite_source: u32, // undocumented flash feature, another bool
 }
 
-pub struct BevelFilter {
+/// How the difference between the two blurred edges of the source is turned
+/// into the color of the bevel.
+enum BevelLookup<'a> {
+    /// `BevelFilter` fades between a highlight and a shadow color.
+    Colors {
+        highlight: swf::Color,
+        shadow: swf::Color,
+    },
+    /// `GradientBevelFilter` uses the difference as a position in a gradient.
+    Gradient(&'a [GradientRecord]),
+}
+
+/// The bindings and pipelines for one kind of bevel lookup.
+struct BevelPipeline {
     bind_group_layout: wgpu::BindGroupLayout,
     pipeline_layout: wgpu::PipelineLayout,
+    pipeline: SampleCountMap<OnceLock<wgpu::RenderPipeline>>,
+}
+
+pub struct BevelFilter {
+    colors: BevelPipeline,
+    gradient: BevelPipeline,
     vertex_buffer: wgpu::Buffer,
     uniform_buffer: wgpu::Buffer,
     vertices_size: wgpu::BufferSize,
     uniform_size: wgpu::BufferSize,
-    pipeline: SampleCountMap<OnceLock<wgpu::RenderPipeline>>,
 }
 
-impl BevelFilter {
-    pub fn new(device: &wgpu::Device) -> Self {
-        let uniform_size = std::mem::size_of::<BevelUniform>() as u64;
-        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            entries: &[
-                wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Texture {
-                        multisampled: false,
-                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
-                        view_dimension: wgpu::TextureViewDimension::D2,
-                    },
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 1,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::NonFiltering),
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 2,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: wgpu::BufferSize::new(uniform_size),
-                    },
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 3,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Texture {
-                        multisampled: false,
-                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
-                        view_dimension: wgpu::TextureViewDimension::D2,
-                    },
-                    count: None,
+impl BevelPipeline {
+    fn new(device: &wgpu::Device, uniform_size: u64, gradient: bool) -> Self {
+        let texture_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                multisampled: false,
+                sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                view_dimension: wgpu::TextureViewDimension::D2,
+            },
+            count: None,
+        };
+        let mut entries = vec![
+            texture_entry(0),
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::NonFiltering),
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 2,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: wgpu::BufferSize::new(uniform_size),
                 },
-            ],
+                count: None,
+            },
+            texture_entry(3),
+        ];
+        if gradient {
+            entries.push(texture_entry(4));
+        }
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            entries: &entries,
             label: create_debug_label!("Bevel filter binds").as_deref(),
         });
 
-        let vertices_size = std::mem::size_of::<[FilterVertexWithDoubleBlur; 4]>() as u64;
-        let vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: None,
-            size: vertices_size,
-            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
-
-        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: None,
-            size: uniform_size,
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
-
         let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: None,
             bind_group_layouts: &[&bind_group_layout],
@@ -100,17 +102,18 @@ impl BevelFilter {
         });
 
         Self {
-            pipeline: Default::default(),
-            pipeline_layout,
-            vertex_buffer,
-            uniform_buffer,
             bind_group_layout,
-            uniform_size: wgpu::BufferSize::new(uniform_size).expect("Definitely not zero."),
-            vertices_size: wgpu::BufferSize::new(vertices_size).expect("Definitely not zero."),
+            pipeline_layout,
+            pipeline: Default::default(),
         }
     }
 
-    fn pipeline(&self, descriptors: &Descriptors, msaa_sample_count: u32) -> &wgpu::RenderPipeline {
+    fn pipeline(
+        &self,
+        descriptors: &Descriptors,
+        shader: &wgpu::ShaderModule,
+        msaa_sample_count: u32,
+    ) -> &wgpu::RenderPipeline {
         self.pipeline.get_or_init(msaa_sample_count, || {
             let label = create_debug_label!("Bevel Filter ({} msaa)", msaa_sample_count);
             descriptors
@@ -119,7 +122,7 @@ impl BevelFilter {
                     label: label.as_deref(),
                     layout: Some(&self.pipeline_layout),
                     vertex: wgpu::VertexState {
-                        module: &descriptors.shaders.bevel_filter,
+                        module: shader,
                         entry_point: "main_vertex",
                         buffers: &VERTEX_BUFFERS_DESCRIPTION_FILTERS_WITH_DOUBLE_BLUR,
                         compilation_options: Default::default(),
@@ -140,7 +143,7 @@ impl BevelFilter {
                         alpha_to_coverage_enabled: false,
                     },
                     fragment: Some(wgpu::FragmentState {
-                        module: &descriptors.shaders.bevel_filter,
+                        module: shader,
                         entry_point: "main_fragment",
                         targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
                         compilation_options: Default::default(),
@@ -150,6 +153,36 @@ impl BevelFilter {
                 })
         })
     }
+}
+
+impl BevelFilter {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let uniform_size = std::mem::size_of::<BevelUniform>() as u64;
+
+        let vertices_size = std::mem::size_of::<[FilterVertexWithDoubleBlur; 4]>() as u64;
+        let vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: vertices_size,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: uniform_size,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            colors: BevelPipeline::new(device, uniform_size, false),
+            gradient: BevelPipeline::new(device, uniform_size, true),
+            vertex_buffer,
+            uniform_buffer,
+            uniform_size: wgpu::BufferSize::new(uniform_size).expect("Definitely not zero."),
+            vertices_size: wgpu::BufferSize::new(vertices_size).expect("Definitely not zero."),
+        }
+    }
 
     #[allow(clippy::too_many_arguments)]
     pub fn apply(
@@ -161,17 +194,111 @@ impl BevelFilter {
         source: &FilterSource,
         filter: &BevelFilterArgs,
         blur_filter: &BlurFilter,
+    ) -> CommandTarget {
+        let distance = filter.distance.to_f32();
+        let angle = filter.angle.to_f32();
+        let blur_offset = (angle.cos() * distance, angle.sin() * distance);
+        self.draw(
+            descriptors,
+            texture_pool,
+            draw_encoder,
+            staging_belt,
+            source,
+            &filter.inner_blur_filter(),
+            blur_filter,
+            blur_offset,
+            BevelLookup::Colors {
+                highlight: filter.highlight_color,
+                shadow: filter.shadow_color,
+            },
+            BevelUniform {
+                highlight_color: [0.0; 4],
+                shadow_color: [0.0; 4],
+                strength: filter.strength.to_f32(),
+                bevel_type: if filter.is_on_top() {
+                    2
+                } else if filter.is_inner() {
+                    1
+                } else {
+                    0
+                },
+                knockout: if filter.is_knockout() { 1 } else { 0 },
+                composite_source: 1,
+            },
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_gradient(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        staging_belt: &mut StagingBelt,
+        source: &FilterSource,
+        filter: &GradientBevelFilterArgs,
+        blur_filter: &BlurFilter,
+    ) -> CommandTarget {
+        let distance = filter.distance.to_f32();
+        let angle = filter.angle.to_f32();
+        let blur_offset = (angle.cos() * distance, angle.sin() * distance);
+        self.draw(
+            descriptors,
+            texture_pool,
+            draw_encoder,
+            staging_belt,
+            source,
+            &filter.inner_blur_filter(),
+            blur_filter,
+            blur_offset,
+            BevelLookup::Gradient(&filter.colors),
+            BevelUniform {
+                highlight_color: [0.0; 4],
+                shadow_color: [0.0; 4],
+                strength: filter.strength.to_f32(),
+                bevel_type: if filter.is_on_top() {
+                    2
+                } else if filter.is_inner() {
+                    1
+                } else {
+                    0
+                },
+                knockout: if filter.is_knockout() { 1 } else { 0 },
+                composite_source: 0,
+            },
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn draw(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        staging_belt: &mut StagingBelt,
+        source: &FilterSource,
+        blur: &swf::BlurFilter,
+        blur_filter: &BlurFilter,
+        blur_offset: (f32, f32),
+        lookup: BevelLookup,
+        mut uniform: BevelUniform,
     ) -> CommandTarget {
         let sample_count = source.texture.sample_count();
         let format = source.texture.format();
-        let pipeline = self.pipeline(descriptors, sample_count);
+        let (bevel_pipeline, shader) = match lookup {
+            BevelLookup::Colors { .. } => (&self.colors, &descriptors.shaders.bevel_filter),
+            BevelLookup::Gradient(_) => {
+                (&self.gradient, &descriptors.shaders.gradient_bevel_filter)
+            }
+        };
+        let pipeline = bevel_pipeline.pipeline(descriptors, shader, sample_count);
         let blurred = blur_filter.apply(
             descriptors,
             texture_pool,
             draw_encoder,
             staging_belt,
             source,
-            &filter.inner_blur_filter(),
+            blur,
         );
         let blurred_texture = if let Some(blurred) = &blurred {
             blurred.ensure_cleared(draw_encoder);
@@ -181,9 +308,18 @@ impl BevelFilter {
         };
         let source_view = source.texture.create_view(&Default::default());
         let blurred_view = blurred_texture.create_view(&Default::default());
-        let distance = filter.distance.to_f32();
-        let angle = filter.angle.to_f32();
-        let blur_offset = (angle.cos() * distance, angle.sin() * distance);
+        let gradient_view = match lookup {
+            BevelLookup::Colors { highlight, shadow } => {
+                uniform.highlight_color = premultiplied(highlight);
+                uniform.shadow_color = premultiplied(shadow);
+                None
+            }
+            BevelLookup::Gradient(colors) => Some(create_gradient_texture_view(
+                descriptors,
+                colors,
+                GradientInterpolation::Rgb,
+            )),
+        };
 
         let target = CommandTarget::new(
             descriptors,
@@ -198,24 +334,6 @@ impl BevelFilter {
             RenderTargetMode::FreshWithColor(wgpu::Color::TRANSPARENT),
             draw_encoder,
         );
-        let mut highlight_color = [
-            f32::from(filter.highlight_color.r) / 255.0,
-            f32::from(filter.highlight_color.g) / 255.0,
-            f32::from(filter.highlight_color.b) / 255.0,
-            f32::from(filter.highlight_color.a) / 255.0,
-        ];
-        highlight_color[0] *= highlight_color[3];
-        highlight_color[1] *= highlight_color[3];
-        highlight_color[2] *= highlight_color[3];
-        let mut shadow_color = [
-            f32::from(filter.shadow_color.r) / 255.0,
-            f32::from(filter.shadow_color.g) / 255.0,
-            f32::from(filter.shadow_color.b) / 255.0,
-            f32::from(filter.shadow_color.a) / 255.0,
-        ];
-        shadow_color[0] *= shadow_color[3];
-        shadow_color[1] *= shadow_color[3];
-        shadow_color[2] *= shadow_color[3];
         staging_belt
             .write_buffer(
                 draw_encoder,
@@ -224,20 +342,7 @@ impl BevelFilter {
                 self.uniform_size,
                 &descriptors.device,
             )
-            .copy_from_slice(bytemuck::cast_slice(&[BevelUniform {
-                highlight_color,
-                shadow_color,
-                strength: filter.strength.to_f32(),
-                bevel_type: if filter.is_on_top() {
-                    2
-                } else if filter.is_inner() {
-                    1
-                } else {
-                    0
-                },
-                knockout: if filter.is_knockout() { 1 } else { 0 },
-                composite_source: 1,
-            }]));
+            .copy_from_slice(bytemuck::cast_slice(&[uniform]));
         staging_belt
             .write_buffer(
                 draw_encoder,
@@ -249,31 +354,38 @@ impl BevelFilter {
             .copy_from_slice(bytemuck::cast_slice(&[
                 source.vertices_with_highlight_and_shadow(blur_offset)
             ]));
+        let mut entries = vec![
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(&source_view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::Sampler(
+                    descriptors.bitmap_samplers.get_sampler(false, false),
+                ),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: self.uniform_buffer.as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 3,
+                resource: wgpu::BindingResource::TextureView(&blurred_view),
+            },
+        ];
+        if let Some(gradient_view) = &gradient_view {
+            entries.push(wgpu::BindGroupEntry {
+                binding: 4,
+                resource: wgpu::BindingResource::TextureView(gradient_view),
+            });
+        }
         let filter_group = descriptors
             .device
             .create_bind_group(&wgpu::BindGroupDescriptor {
                 label: create_debug_label!("Filter group").as_deref(),
-                layout: &self.bind_group_layout,
-                entries: &[
-                    wgpu::BindGroupEntry {
-                        binding: 0,
-                        resource: wgpu::BindingResource::TextureView(&source_view),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 1,
-                        resource: wgpu::BindingResource::Sampler(
-                            descriptors.bitmap_samplers.get_sampler(false, false),
-                        ),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 2,
-                        resource: self.uniform_buffer.as_entire_binding(),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 3,
-                        resource: wgpu::BindingResource::TextureView(&blurred_view),
-                    },
-                ],
+                layout: &bevel_pipeline.bind_group_layout,
+                entries: &entries,
             });
         let mut render_pass = draw_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: create_debug_label!("Bevel filter").as_deref(),
@@ -294,3 +406,13 @@ impl BevelFilter {
         target
     }
 }
+
+fn premultiplied(color: swf::Color) -> [f32; 4] {
+    let alpha = f32::from(color.a) / 255.0;
+    [
+        f32::from(color.r) / 255.0 * alpha,
+        f32::from(color.g) / 255.0 * alpha,
+        f32::from(color.b) / 255.0 * alpha,
+        alpha,
+    ]
+}
@@ -5,11 +5,15 @@ use crate::filters::blur::BlurFilter;
 use crate::filters::{
     FilterSource, FilterVertexWithBlur, VERTEX_BUFFERS_DESCRIPTION_FILTERS_WITH_BLUR,
 };
+use crate::mesh::create_gradient_texture_view;
 use crate::surface::target::CommandTarget;
 use crate::utils::SampleCountMap;
 use bytemuck::{Pod, Zeroable};
 use std::sync::OnceLock;
-use swf::GlowFilter as GlowFilterArgs;
+use swf::{
+    GlowFilter as GlowFilterArgs, GradientFilter as GradientGlowFilterArgs, GradientInterpolation,
+    GradientRecord,
+};
 use wgpu::util::StagingBelt;
 
 #[repr(C)]
@@ -17,79 +21,73 @@ use wgpu::util::StagingBelt;
 struct GlowUniform {
     color: [f32; 4],
     strength: f32,
-    inner: u32,            // a wasteful bool, but we need to be aligned anyway
+    glow_type: u32,        // 0 outer, 1 inner, 2 full
     knockout: u32,         // a wasteful bool, but we need to be aligned anyway
     composite_source: u32, // undocumented flash feature, another bool
 }
 
-pub struct GlowFilter {
+/// How the blurred alpha of the source is turned into the color of the glow.
+enum GlowLookup<'a> {
+    /// `GlowFilter` and `DropShadowFilter` fade a single color.
+    Color(swf::Color),
+    /// `GradientGlowFilter` uses the blurred alpha as a position in a gradient.
+    Gradient(&'a [GradientRecord]),
+}
+
+/// The bindings and pipelines for one kind of glow lookup.
+struct GlowPipeline {
     bind_group_layout: wgpu::BindGroupLayout,
     pipeline_layout: wgpu::PipelineLayout,
+    pipeline: SampleCountMap<OnceLock<wgpu::RenderPipeline>>,
+}
+
+pub struct GlowFilter {
+    color: GlowPipeline,
+    gradient: GlowPipeline,
     vertex_buffer: wgpu::Buffer,
     uniform_buffer: wgpu::Buffer,
     vertices_size: wgpu::BufferSize,
     uniform_size: wgpu::BufferSize,
-    pipeline: SampleCountMap<OnceLock<wgpu::RenderPipeline>>,
 }
 
-impl GlowFilter {
-    pub fn new(device: &wgpu::Device) -> Self {
-        let uniform_size = std::mem::size_of::<GlowUniform>() as u64;
-        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            entries: &[
-                wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Texture {
-                        multisampled: false,
-                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
-                        view_dimension: wgpu::TextureViewDimension::D2,
-                    },
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 1,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::NonFiltering),
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 2,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: wgpu::BufferSize::new(uniform_size),
-                    },
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 3,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Texture {
-                        multisampled: false,
-                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
-                        view_dimension: wgpu::TextureViewDimension::D2,
-                    },
-                    count: None,
+impl GlowPipeline {
+    fn new(device: &wgpu::Device, uniform_size: u64, gradient: bool) -> Self {
+        let texture_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                multisampled: false,
+                sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                view_dimension: wgpu::TextureViewDimension::D2,
+            },
+            count: None,
+        };
+        let mut entries = vec![
+            texture_entry(0),
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::NonFiltering),
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 2,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: wgpu::BufferSize::new(uniform_size),
                 },
-            ],
-            label: create_debug_label!("Blur filter binds").as_deref(),
-        });
-
-        let vertices_size = std::mem::size_of::<[FilterVertexWithBlur; 4]>() as u64;
-        let vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: None,
-            size: vertices_size,
-            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
-
-        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: None,
-            size: uniform_size,
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
+                count: None,
+            },
+            texture_entry(3),
+        ];
+        if gradient {
+            entries.push(texture_entry(4));
+        }
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            entries: &entries,
+            label: create_debug_label!("Glow filter binds").as_deref(),
         });
 
         let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
@@ -99,17 +97,18 @@ impl GlowFilter {
         });
 
         Self {
-            pipeline: Default::default(),
-            pipeline_layout,
-            vertex_buffer,
-            uniform_buffer,
             bind_group_layout,
-            uniform_size: wgpu::BufferSize::new(uniform_size).expect("Definitely not zero."),
-            vertices_size: wgpu::BufferSize::new(vertices_size).expect("Definitely not zero."),
+            pipeline_layout,
+            pipeline: Default::default(),
         }
     }
 
-    fn pipeline(&self, descriptors: &Descriptors, msaa_sample_count: u32) -> &wgpu::RenderPipeline {
+    fn pipeline(
+        &self,
+        descriptors: &Descriptors,
+        shader: &wgpu::ShaderModule,
+        msaa_sample_count: u32,
+    ) -> &wgpu::RenderPipeline {
         self.pipeline.get_or_init(msaa_sample_count, || {
             let label = create_debug_label!("Glow Filter ({} msaa)", msaa_sample_count);
             descriptors
@@ -118,7 +117,7 @@ impl GlowFilter {
                     label: label.as_deref(),
                     layout: Some(&self.pipeline_layout),
                     vertex: wgpu::VertexState {
-                        module: &descriptors.shaders.glow_filter,
+                        module: shader,
                         entry_point: "main_vertex",
                         buffers: &VERTEX_BUFFERS_DESCRIPTION_FILTERS_WITH_BLUR,
                         compilation_options: Default::default(),
@@ -139,7 +138,7 @@ impl GlowFilter {
                         alpha_to_coverage_enabled: false,
                     },
                     fragment: Some(wgpu::FragmentState {
-                        module: &descriptors.shaders.glow_filter,
+                        module: shader,
                         entry_point: "main_fragment",
                         targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
                         compilation_options: Default::default(),
@@ -149,6 +148,36 @@ impl GlowFilter {
                 })
         })
     }
+}
+
+impl GlowFilter {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let uniform_size = std::mem::size_of::<GlowUniform>() as u64;
+
+        let vertices_size = std::mem::size_of::<[FilterVertexWithBlur; 4]>() as u64;
+        let vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: vertices_size,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: uniform_size,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            color: GlowPipeline::new(device, uniform_size, false),
+            gradient: GlowPipeline::new(device, uniform_size, true),
+            vertex_buffer,
+            uniform_buffer,
+            uniform_size: wgpu::BufferSize::new(uniform_size).expect("Definitely not zero."),
+            vertices_size: wgpu::BufferSize::new(vertices_size).expect("Definitely not zero."),
+        }
+    }
 
     #[allow(clippy::too_many_arguments)]
     pub fn apply(
@@ -161,17 +190,96 @@ impl GlowFilter {
         filter: &GlowFilterArgs,
         blur_filter: &BlurFilter,
         blur_offset: (f32, f32),
+    ) -> CommandTarget {
+        self.draw(
+            descriptors,
+            texture_pool,
+            draw_encoder,
+            staging_belt,
+            source,
+            &filter.inner_blur_filter(),
+            blur_filter,
+            blur_offset,
+            GlowLookup::Color(filter.color),
+            GlowUniform {
+                color: [0.0; 4],
+                strength: filter.strength.to_f32(),
+                glow_type: if filter.is_inner() { 1 } else { 0 },
+                knockout: if filter.is_knockout() { 1 } else { 0 },
+                composite_source: if filter.composite_source() { 1 } else { 0 },
+            },
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_gradient(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        staging_belt: &mut StagingBelt,
+        source: &FilterSource,
+        filter: &GradientGlowFilterArgs,
+        blur_filter: &BlurFilter,
+    ) -> CommandTarget {
+        let distance = filter.distance.to_f32();
+        let angle = filter.angle.to_f32();
+        // Just like a drop shadow, the glow is offset away from the light source
+        let blur_offset = (-angle.cos() * distance, -angle.sin() * distance);
+        self.draw(
+            descriptors,
+            texture_pool,
+            draw_encoder,
+            staging_belt,
+            source,
+            &filter.inner_blur_filter(),
+            blur_filter,
+            blur_offset,
+            GlowLookup::Gradient(&filter.colors),
+            GlowUniform {
+                color: [0.0; 4],
+                strength: filter.strength.to_f32(),
+                glow_type: if filter.is_on_top() {
+                    2
+                } else if filter.is_inner() {
+                    1
+                } else {
+                    0
+                },
+                knockout: if filter.is_knockout() { 1 } else { 0 },
+                composite_source: 0,
+            },
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn draw(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        staging_belt: &mut StagingBelt,
+        source: &FilterSource,
+        blur: &swf::BlurFilter,
+        blur_filter: &BlurFilter,
+        blur_offset: (f32, f32),
+        lookup: GlowLookup,
+        mut uniform: GlowUniform,
     ) -> CommandTarget {
         let sample_count = source.texture.sample_count();
         let format = source.texture.format();
-        let pipeline = self.pipeline(descriptors, sample_count);
+        let (glow_pipeline, shader) = match lookup {
+            GlowLookup::Color(_) => (&self.color, &descriptors.shaders.glow_filter),
+            GlowLookup::Gradient(_) => (&self.gradient, &descriptors.shaders.gradient_glow_filter),
+        };
+        let pipeline = glow_pipeline.pipeline(descriptors, shader, sample_count);
         let blurred = blur_filter.apply(
             descriptors,
             texture_pool,
             draw_encoder,
             staging_belt,
             source,
-            &filter.inner_blur_filter(),
+            blur,
         );
         let blurred_texture = if let Some(blurred) = &blurred {
             blurred.ensure_cleared(draw_encoder);
@@ -181,6 +289,22 @@ impl GlowFilter {
         };
         let source_view = source.texture.create_view(&Default::default());
         let blurred_view = blurred_texture.create_view(&Default::default());
+        let gradient_view = match lookup {
+            GlowLookup::Color(color) => {
+                uniform.color = [
+                    f32::from(color.r) / 255.0,
+                    f32::from(color.g) / 255.0,
+                    f32::from(color.b) / 255.0,
+                    f32::from(color.a) / 255.0,
+                ];
+                None
+            }
+            GlowLookup::Gradient(colors) => Some(create_gradient_texture_view(
+                descriptors,
+                colors,
+                GradientInterpolation::Rgb,
+            )),
+        };
 
         let target = CommandTarget::new(
             descriptors,
@@ -203,18 +327,7 @@ impl GlowFilter {
                 self.uniform_size,
                 &descriptors.device,
             )
-            .copy_from_slice(bytemuck::cast_slice(&[GlowUniform {
-                color: [
-                    f32::from(filter.color.r) / 255.0,
-                    f32::from(filter.color.g) / 255.0,
-                    f32::from(filter.color.b) / 255.0,
-                    f32::from(filter.color.a) / 255.0,
-                ],
-                strength: filter.strength.to_f32(),
-                inner: if filter.is_inner() { 1 } else { 0 },
-                knockout: if filter.is_knockout() { 1 } else { 0 },
-                composite_source: if filter.composite_source() { 1 } else { 0 },
-            }]));
+            .copy_from_slice(bytemuck::cast_slice(&[uniform]));
         staging_belt
             .write_buffer(
                 draw_encoder,
@@ -226,31 +339,38 @@ impl GlowFilter {
             .copy_from_slice(bytemuck::cast_slice(&[
                 source.vertices_with_blur_offset(blur_offset)
             ]));
+        let mut entries = vec![
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(&source_view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::Sampler(
+                    descriptors.bitmap_samplers.get_sampler(false, false),
+                ),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: self.uniform_buffer.as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 3,
+                resource: wgpu::BindingResource::TextureView(&blurred_view),
+            },
+        ];
+        if let Some(gradient_view) = &gradient_view {
+            entries.push(wgpu::BindGroupEntry {
+                binding: 4,
+                resource: wgpu::BindingResource::TextureView(gradient_view),
+            });
+        }
         let filter_group = descriptors
             .device
             .create_bind_group(&wgpu::BindGroupDescriptor {
                 label: create_debug_label!("Filter group").as_deref(),
-                layout: &self.bind_group_layout,
-                entries: &[
-                    wgpu::BindGroupEntry {
-                        binding: 0,
-                        resource: wgpu::BindingResource::TextureView(&source_view),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 1,
-                        resource: wgpu::BindingResource::Sampler(
-                            descriptors.bitmap_samplers.get_sampler(false, false),
-                        ),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 2,
-                        resource: self.uniform_buffer.as_entire_binding(),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 3,
-                        resource: wgpu::BindingResource::TextureView(&blurred_view),
-                    },
-                ],
+                layout: &glow_pipeline.bind_group_layout,
+                entries: &entries,
             });
         let mut render_pass = draw_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: create_debug_label!("Glow filter").as_deref(),
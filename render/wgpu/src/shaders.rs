@@ -17,9 +17,12 @@ pub struct Shaders {
     pub copy_shader: wgpu::ShaderModule,
     pub blend_shaders: EnumMap<ComplexBlend, wgpu::ShaderModule>,
     pub color_matrix_filter: wgpu::ShaderModule,
+    pub convolution_filter: wgpu::ShaderModule,
     pub blur_filter: wgpu::ShaderModule,
     pub glow_filter: wgpu::ShaderModule,
     pub bevel_filter: wgpu::ShaderModule,
+    pub gradient_glow_filter: wgpu::ShaderModule,
+    pub gradient_bevel_filter: wgpu::ShaderModule,
     pub displacement_map_filter: wgpu::ShaderModule,
 }
 
@@ -42,6 +45,11 @@ impl Shaders {
             "filter/color_matrix.wgsl",
             include_str!("../shaders/filter/color_matrix.wgsl"),
         );
+        let convolution_filter = make_filter_shader(
+            device,
+            "filter/convolution.wgsl",
+            include_str!("../shaders/filter/convolution.wgsl"),
+        );
         let blur_filter = make_filter_shader(
             device,
             "filter/blur.wgsl",
@@ -57,6 +65,16 @@ impl Shaders {
             "filter/bevel.wgsl",
             include_str!("../shaders/filter/bevel.wgsl"),
         );
+        let gradient_glow_filter = make_filter_shader(
+            device,
+            "filter/gradient_glow.wgsl",
+            include_str!("../shaders/filter/gradient_glow.wgsl"),
+        );
+        let gradient_bevel_filter = make_filter_shader(
+            device,
+            "filter/gradient_bevel.wgsl",
+            include_str!("../shaders/filter/gradient_bevel.wgsl"),
+        );
         let displacement_map_filter = make_filter_shader(
             device,
             "filter/displacement_map.wgsl",
@@ -88,9 +106,12 @@ impl Shaders {
             copy_shader,
             blend_shaders,
             color_matrix_filter,
+            convolution_filter,
             blur_filter,
             glow_filter,
             bevel_filter,
+            gradient_glow_filter,
+            gradient_bevel_filter,
             displacement_map_filter,
         }
     }
@@ -0,0 +1,460 @@
+//! Textual disassembly and decompilation of AVM1 bytecode.
+//!
+//! [`disassemble`] lists every action, with constant pool indices resolved to
+//! their strings and jump offsets replaced by labels. [`decompile`] recovers
+//! ActionScript 2 source code from the output of the Flash compilers.
+
+mod decompile;
+
+pub use decompile::decompile;
+
+use crate::avm1::read::Reader;
+use crate::avm1::types::*;
+use crate::error::Error;
+use crate::string::SwfStr;
+use encoding_rs::Encoding;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+/// An action, along with its position in the bytecode.
+struct Instruction<'a> {
+    /// The offset of the action, from the start of the outermost action list.
+    offset: usize,
+
+    /// The offset right after the action, which jumps are relative to.
+    end: usize,
+
+    action: Action<'a>,
+}
+
+impl Instruction<'_> {
+    /// The offset that this action jumps to, if it's a jump.
+    fn jump_target(&self) -> Option<isize> {
+        match &self.action {
+            Action::If(If { offset }) | Action::Jump(Jump { offset }) => {
+                Some(self.end as isize + isize::from(*offset))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A list of actions, such as the body of a `DoAction` tag or of a function.
+struct ActionList<'a> {
+    instructions: Vec<Instruction<'a>>,
+
+    /// The offset right after the last action.
+    end: usize,
+
+    /// Why the actions couldn't all be read, if they couldn't.
+    error: Option<Error>,
+}
+
+impl<'a> ActionList<'a> {
+    /// Reads the actions of `data`, which starts at `offset` in the outermost action list.
+    fn read(data: &'a [u8], offset: usize, swf_version: u8) -> Self {
+        let mut reader = Reader::new(data, swf_version);
+        let mut instructions = Vec::new();
+        let mut error = None;
+        while !reader.get_ref().is_empty() {
+            let start = offset + data.len() - reader.get_ref().len();
+            match reader.read_action() {
+                Ok(action) => instructions.push(Instruction {
+                    offset: start,
+                    end: offset + data.len() - reader.get_ref().len(),
+                    action,
+                }),
+                Err(e) => {
+                    error = Some(e);
+                    break;
+                }
+            }
+        }
+        Self {
+            instructions,
+            end: offset + data.len(),
+            error,
+        }
+    }
+
+    /// The index of the action at the given offset.
+    ///
+    /// The end of the list is at the index right after the last action.
+    fn index_of(&self, offset: isize) -> Option<usize> {
+        let offset = usize::try_from(offset).ok()?;
+        if offset == self.end {
+            return Some(self.instructions.len());
+        }
+        self.instructions
+            .binary_search_by_key(&offset, |instruction| instruction.offset)
+            .ok()
+    }
+}
+
+/// The offset of a nested action list, such as a function body, inside of `base`.
+fn nested_offset(base: &[u8], nested: &[u8]) -> usize {
+    nested.as_ptr() as usize - base.as_ptr() as usize
+}
+
+/// The label of an offset, which may be before the start of the actions.
+fn label(offset: isize) -> String {
+    if offset < 0 {
+        format!("loc_-{:04x}", offset.unsigned_abs())
+    } else {
+        format!("loc_{offset:04x}")
+    }
+}
+
+fn write_quoted(out: &mut String, string: &str) {
+    out.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Lists the actions of `actions` as text, one per line.
+///
+/// Nested action lists, such as the bodies of functions, are indented inside
+/// braces after the action defining them. Jump targets get a label named after
+/// their offset from the start of `actions`.
+pub fn disassemble(actions: &[u8], swf_version: u8) -> String {
+    let mut disassembler = Disassembler {
+        base: actions,
+        swf_version,
+        encoding: SwfStr::encoding_for_version(swf_version),
+        constant_pool: Vec::new(),
+        out: String::new(),
+        indent: 0,
+    };
+    disassembler.action_list(actions);
+    disassembler.out
+}
+
+struct Disassembler<'a> {
+    base: &'a [u8],
+    swf_version: u8,
+    encoding: &'static Encoding,
+    constant_pool: Vec<String>,
+    out: String,
+    indent: usize,
+}
+
+impl<'a> Disassembler<'a> {
+    fn line(&mut self) {
+        for _ in 0..=self.indent {
+            self.out.push_str("    ");
+        }
+    }
+
+    /// Writes a label, less indented than the actions around it.
+    fn label(&mut self, offset: usize) {
+        for _ in 0..self.indent {
+            self.out.push_str("    ");
+        }
+        let _ = writeln!(self.out, "{}:", label(offset as isize));
+    }
+
+    fn string(&mut self, string: &SwfStr) {
+        write_quoted(&mut self.out, &string.to_str_lossy(self.encoding));
+    }
+
+    fn action_list(&mut self, data: &'a [u8]) {
+        let list = ActionList::read(data, nested_offset(self.base, data), self.swf_version);
+        let labels: BTreeSet<_> = list
+            .instructions
+            .iter()
+            .filter_map(Instruction::jump_target)
+            .collect();
+
+        for instruction in &list.instructions {
+            if labels.contains(&(instruction.offset as isize)) {
+                self.label(instruction.offset);
+            }
+            self.line();
+            self.action(instruction);
+            self.out.push('\n');
+        }
+        if labels.contains(&(list.end as isize)) {
+            self.label(list.end);
+        }
+        if let Some(error) = list.error {
+            self.line();
+            let _ = writeln!(self.out, "; Couldn't read action: {error}");
+        }
+    }
+
+    /// Writes a nested action list inside of braces.
+    fn nested(&mut self, data: &'a [u8]) {
+        self.out.push_str(" {\n");
+        self.indent += 1;
+        self.action_list(data);
+        self.indent -= 1;
+        self.line();
+        self.out.push('}');
+    }
+
+    fn action(&mut self, instruction: &Instruction<'a>) {
+        match &instruction.action {
+            Action::ConstantPool(pool) => {
+                self.out.push_str("ConstantPool");
+                self.constant_pool = pool
+                    .strings
+                    .iter()
+                    .map(|string| string.to_string_lossy(self.encoding))
+                    .collect();
+                for (i, string) in pool.strings.iter().enumerate() {
+                    self.out.push_str(if i == 0 { " " } else { ", " });
+                    self.string(string);
+                }
+            }
+            Action::DefineFunction(function) => {
+                self.out.push_str("DefineFunction ");
+                self.string(function.name);
+                self.out.push_str(" (");
+                for (i, param) in function.params.iter().enumerate() {
+                    if i > 0 {
+                        self.out.push_str(", ");
+                    }
+                    self.string(param);
+                }
+                self.out.push(')');
+                self.nested(function.actions);
+            }
+            Action::DefineFunction2(function) => {
+                self.out.push_str("DefineFunction2 ");
+                self.string(function.name);
+                self.out.push_str(" (");
+                for (i, param) in function.params.iter().enumerate() {
+                    if i > 0 {
+                        self.out.push_str(", ");
+                    }
+                    if let Some(register) = param.register_index {
+                        let _ = write!(self.out, "r:{register}=");
+                    }
+                    self.string(param.name);
+                }
+                let _ = write!(self.out, ") registers={}", function.register_count);
+                for (name, _) in function.flags.iter_names() {
+                    let _ = write!(self.out, " {}", name.to_ascii_lowercase());
+                }
+                self.nested(function.actions);
+            }
+            Action::GetUrl(get_url) => {
+                self.out.push_str("GetUrl ");
+                self.string(get_url.url);
+                self.out.push_str(", ");
+                self.string(get_url.target);
+            }
+            Action::GetUrl2(get_url) => {
+                self.out.push_str("GetUrl2");
+                match get_url.send_vars_method() {
+                    SendVarsMethod::None => {}
+                    SendVarsMethod::Get => self.out.push_str(" method=GET"),
+                    SendVarsMethod::Post => self.out.push_str(" method=POST"),
+                }
+                if get_url.is_target_sprite() {
+                    self.out.push_str(" load_target");
+                }
+                if get_url.is_load_vars() {
+                    self.out.push_str(" load_variables");
+                }
+            }
+            Action::GotoFrame(goto) => {
+                let _ = write!(self.out, "GotoFrame {}", goto.frame);
+            }
+            Action::GotoFrame2(goto) => {
+                self.out.push_str("GotoFrame2");
+                if goto.set_playing {
+                    self.out.push_str(" play");
+                }
+                if goto.scene_offset != 0 {
+                    let _ = write!(self.out, " scene_offset={}", goto.scene_offset);
+                }
+            }
+            Action::GotoLabel(goto) => {
+                self.out.push_str("GotoLabel ");
+                self.string(goto.label);
+            }
+            Action::If(_) | Action::Jump(_) => {
+                let name = match instruction.action {
+                    Action::If(_) => "If",
+                    _ => "Jump",
+                };
+                let target = instruction.jump_target().unwrap_or_default();
+                let _ = write!(self.out, "{name} {}", label(target));
+            }
+            Action::Push(push) => {
+                self.out.push_str("Push");
+                for (i, value) in push.values.iter().enumerate() {
+                    self.out.push_str(if i == 0 { " " } else { ", " });
+                    self.value(value);
+                }
+            }
+            Action::SetTarget(set_target) => {
+                self.out.push_str("SetTarget ");
+                self.string(set_target.target);
+            }
+            Action::StoreRegister(store) => {
+                let _ = write!(self.out, "StoreRegister {}", store.register);
+            }
+            Action::Try(try_block) => {
+                self.out.push_str("Try");
+                self.nested(try_block.try_body);
+                if let Some((var, body)) = &try_block.catch_body {
+                    self.out.push_str(" Catch ");
+                    match var {
+                        CatchVar::Var(name) => self.string(name),
+                        CatchVar::Register(register) => {
+                            let _ = write!(self.out, "r:{register}");
+                        }
+                    }
+                    self.nested(body);
+                }
+                if let Some(body) = try_block.finally_body {
+                    self.out.push_str(" Finally");
+                    self.nested(body);
+                }
+            }
+            Action::WaitForFrame(wait) => {
+                let _ = write!(
+                    self.out,
+                    "WaitForFrame {}, skip={}",
+                    wait.frame, wait.num_actions_to_skip
+                );
+            }
+            Action::WaitForFrame2(wait) => {
+                let _ = write!(self.out, "WaitForFrame2 skip={}", wait.num_actions_to_skip);
+            }
+            Action::With(with) => {
+                self.out.push_str("With");
+                self.nested(with.actions);
+            }
+            Action::Unknown(unknown) => {
+                let _ = write!(
+                    self.out,
+                    "Unknown 0x{:02x} ({} bytes)",
+                    unknown.opcode,
+                    unknown.data.len()
+                );
+            }
+            // The remaining actions have no operands, and are named after their variant.
+            action => {
+                let _ = write!(self.out, "{action:?}");
+            }
+        }
+    }
+
+    fn value(&mut self, value: &Value) {
+        match value {
+            Value::Undefined => self.out.push_str("undefined"),
+            Value::Null => self.out.push_str("null"),
+            Value::Bool(value) => {
+                let _ = write!(self.out, "{value}");
+            }
+            Value::Int(value) => {
+                let _ = write!(self.out, "{value}");
+            }
+            Value::Float(value) => {
+                let _ = write!(self.out, "{value:?}f");
+            }
+            Value::Double(value) => {
+                let _ = write!(self.out, "{value:?}");
+            }
+            Value::Str(string) => self.string(string),
+            Value::Register(register) => {
+                let _ = write!(self.out, "r:{register}");
+            }
+            Value::ConstantPool(index) => match self.constant_pool.get(usize::from(*index)) {
+                Some(string) => write_quoted(&mut self.out, string),
+                None => {
+                    let _ = write!(self.out, "c:{index}");
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::write::Writer;
+
+    /// Writes a list of actions as bytecode.
+    pub(super) fn assemble(actions: &[Action]) -> Vec<u8> {
+        let mut data = Vec::new();
+        let mut writer = Writer::new(&mut data, 10);
+        for action in actions {
+            writer.write_action(action).unwrap();
+        }
+        data
+    }
+
+    pub(super) fn str(string: &str) -> &SwfStr {
+        SwfStr::from_utf8_str(string)
+    }
+
+    #[test]
+    fn disassemble_actions() {
+        let data = assemble(&[
+            Action::ConstantPool(ConstantPool {
+                strings: vec![str("x")],
+            }),
+            Action::Push(Push {
+                values: vec![Value::ConstantPool(0), Value::Int(1), Value::Register(2)],
+            }),
+            Action::Trace,
+            Action::If(If { offset: -25 }),
+            Action::End,
+        ]);
+
+        assert_eq!(
+            disassemble(&data, 10),
+            concat!(
+                "loc_0000:\n",
+                "    ConstantPool \"x\"\n",
+                "    Push \"x\", 1, r:2\n",
+                "    Trace\n",
+                "    If loc_0000\n",
+                "    End\n",
+            )
+        );
+    }
+
+    #[test]
+    fn disassemble_nested_actions() {
+        let body = assemble(&[Action::Jump(Jump { offset: 0 }), Action::Return]);
+        let data = assemble(&[Action::DefineFunction2(DefineFunction2 {
+            name: str("f"),
+            register_count: 3,
+            params: vec![FunctionParam {
+                name: str("x"),
+                register_index: std::num::NonZeroU8::new(2),
+            }],
+            flags: FunctionFlags::PRELOAD_THIS,
+            actions: &body,
+        })]);
+
+        // Labels inside of the function are offsets from the start of `data`.
+        assert_eq!(
+            disassemble(&data, 10),
+            concat!(
+                "    DefineFunction2 \"f\" (r:2=\"x\") registers=3 preload_this {\n",
+                "        Jump loc_0014\n",
+                "    loc_0014:\n",
+                "        Return\n",
+                "    }\n",
+            )
+        );
+    }
+}
@@ -0,0 +1,1615 @@
+//! Recovers ActionScript 2 source code from AVM1 bytecode.
+//!
+//! Expressions are rebuilt by running the actions on a stack of expressions,
+//! and control flow by matching the jumps that the Flash compilers emit for
+//! each kind of statement. Jumps that don't match any statement are kept as
+//! `goto` comments.
+
+use super::{label, nested_offset, write_quoted, ActionList};
+use crate::avm1::types::*;
+use crate::string::SwfStr;
+use encoding_rs::Encoding;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::ops::Range;
+
+/// The properties of `getProperty` and `setProperty`, by index.
+const PROPERTIES: [&str; 22] = [
+    "_x",
+    "_y",
+    "_xscale",
+    "_yscale",
+    "_currentframe",
+    "_totalframes",
+    "_alpha",
+    "_visible",
+    "_width",
+    "_height",
+    "_rotation",
+    "_target",
+    "_framesloaded",
+    "_name",
+    "_droptarget",
+    "_url",
+    "_highquality",
+    "_focusrect",
+    "_soundbuftime",
+    "_quality",
+    "_xmouse",
+    "_ymouse",
+];
+
+#[derive(Clone, Debug, PartialEq)]
+enum Expr {
+    /// A keyword value, such as `true` or `undefined`.
+    Literal(&'static str),
+    Number(f64),
+    Str(String),
+
+    /// A variable, or a dotted path to one.
+    Name(String),
+    Member(Box<Expr>, Box<Expr>),
+    Call(Box<Expr>, Vec<Expr>),
+    New(Box<Expr>, Vec<Expr>),
+    Unary(&'static str, Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+    Conditional(Box<Expr>, Box<Expr>, Box<Expr>),
+    Assign(Box<Expr>, Box<Expr>),
+    Array(Vec<Expr>),
+    Object(Vec<(Expr, Expr)>),
+    Function(Box<Function>),
+
+    /// The property names of an object, pushed by `Enumerate` for a `for..in` loop.
+    Enumeration(Box<Expr>),
+}
+
+/// Popping an empty stack gives `undefined`.
+const UNDEFINED: Expr = Expr::Literal("undefined");
+
+impl Expr {
+    fn call(name: &str, args: Vec<Expr>) -> Self {
+        Self::Call(Box::new(Self::Name(name.to_string())), args)
+    }
+
+    fn binary(op: &'static str, left: Expr, right: Expr) -> Self {
+        Self::Binary(op, Box::new(left), Box::new(right))
+    }
+
+    /// A variable named by a value, as read by `GetVariable`.
+    fn variable(name: Expr) -> Self {
+        match name {
+            Self::Str(name) if is_path(&name) => Self::Name(name),
+            name => Self::call("eval", vec![name]),
+        }
+    }
+
+    /// The opposite condition, undoing the `Not` added by the compiler where possible.
+    fn negate(self) -> Self {
+        let opposite = |op| match op {
+            "==" => Some("!="),
+            "!=" => Some("=="),
+            "===" => Some("!=="),
+            "!==" => Some("==="),
+            "<" => Some(">="),
+            ">=" => Some("<"),
+            ">" => Some("<="),
+            "<=" => Some(">"),
+            _ => None,
+        };
+        match self {
+            Self::Unary("!", value) => *value,
+            Self::Binary(op, left, right) if opposite(op).is_some() => {
+                Self::Binary(opposite(op).unwrap_or(op), left, right)
+            }
+            Self::Literal("true") => Self::Literal("false"),
+            Self::Literal("false") => Self::Literal("true"),
+            value => Self::Unary("!", Box::new(value)),
+        }
+    }
+
+    /// Whether evaluating this can't have any side effect.
+    fn is_pure(&self) -> bool {
+        matches!(
+            self,
+            Self::Literal(_) | Self::Number(_) | Self::Str(_) | Self::Name(_)
+        )
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::Function(_) => 0,
+            Self::Assign(..) => 1,
+            Self::Conditional(..) => 2,
+            Self::Binary(op, ..) => binary_precedence(op),
+            Self::Unary(..) => 13,
+            Self::Number(value) if value.is_sign_negative() && *value != 0.0 => 13,
+            Self::Member(..) | Self::Call(..) | Self::New(..) => 14,
+            _ => 15,
+        }
+    }
+}
+
+fn binary_precedence(op: &str) -> u8 {
+    match op {
+        "||" => 3,
+        "&&" => 4,
+        "|" => 5,
+        "^" => 6,
+        "&" => 7,
+        "==" | "!=" | "===" | "!==" | "eq" | "ne" => 8,
+        "<" | ">" | "<=" | ">=" | "lt" | "gt" | "le" | "ge" | "instanceof" => 9,
+        "<<" | ">>" | ">>>" => 10,
+        "+" | "-" | "add" => 11,
+        _ => 12,
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+fn is_path(name: &str) -> bool {
+    name.split('.').all(is_identifier)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Function {
+    name: String,
+    params: Vec<String>,
+    body: Vec<Stmt>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Stmt {
+    Expr(Expr),
+    Var(String, Option<Expr>),
+    Return(Expr),
+    Throw(Expr),
+    If(Expr, Vec<Stmt>, Vec<Stmt>),
+    While(Expr, Vec<Stmt>),
+    DoWhile(Vec<Stmt>, Expr),
+    ForIn {
+        declare: bool,
+        variable: Expr,
+        object: Expr,
+        body: Vec<Stmt>,
+    },
+    With(Expr, Vec<Stmt>),
+    Try {
+        body: Vec<Stmt>,
+        catch: Option<(String, Vec<Stmt>)>,
+        finally: Option<Vec<Stmt>>,
+    },
+    Function(Function),
+    Break,
+    Continue,
+
+    /// Changes the target of the following actions, or resets it with `None`.
+    ///
+    /// These are grouped into `TellTarget` statements once a body is decompiled.
+    SetTarget(Option<Expr>),
+    TellTarget(Expr, Vec<Stmt>),
+
+    /// A jump that doesn't belong to any statement, taken if the condition is true.
+    Goto(Option<Expr>, isize),
+
+    /// Something with no ActionScript equivalent.
+    Comment(String),
+}
+
+impl Stmt {
+    fn call(name: &str, args: Vec<Expr>) -> Self {
+        Self::Expr(Expr::call(name, args))
+    }
+
+    /// An `if` statement, avoiding empty `then` branches.
+    fn if_else(condition: Expr, then: Vec<Stmt>, otherwise: Vec<Stmt>) -> Self {
+        if then.is_empty() && !otherwise.is_empty() {
+            Self::If(condition.negate(), otherwise, then)
+        } else {
+            Self::If(condition, then, otherwise)
+        }
+    }
+
+    /// A loop whose body ends with a jump back to its start.
+    ///
+    /// Loops with a condition start with a conditional `break`, which is turned
+    /// back into a `while` or `for..in` loop.
+    fn infinite_loop(mut body: Vec<Stmt>) -> Self {
+        let Some(Stmt::If(condition, then, otherwise)) = body.first() else {
+            return Self::While(Expr::Literal("true"), body);
+        };
+        if then.as_slice() != [Stmt::Break] || !otherwise.is_empty() {
+            return Self::While(Expr::Literal("true"), body);
+        }
+
+        // `for..in` loops store each property name in a register, before copying it.
+        if let Some((register, object)) = enumeration(condition) {
+            let (register, object) = (register.clone(), object.clone());
+            let (declare, variable, skip) = match body.get(1) {
+                Some(Stmt::Var(name, Some(value))) if *value == register => {
+                    (true, Expr::Name(name.clone()), 2)
+                }
+                Some(Stmt::Expr(Expr::Assign(variable, value))) if **value == register => {
+                    (false, (**variable).clone(), 2)
+                }
+                _ => (false, register, 1),
+            };
+            body.drain(..skip);
+            return Self::ForIn {
+                declare,
+                variable,
+                object,
+                body,
+            };
+        }
+
+        let condition = condition.clone().negate();
+        body.remove(0);
+        Self::While(condition, body)
+    }
+}
+
+/// Matches the condition ending a `for..in` loop, `(register = enumeration) == null`.
+fn enumeration(condition: &Expr) -> Option<(&Expr, &Expr)> {
+    let Expr::Binary("==", left, right) = condition else {
+        return None;
+    };
+    let Expr::Assign(register, value) = &**left else {
+        return None;
+    };
+    match (&**value, &**right) {
+        (Expr::Enumeration(object), Expr::Literal("null")) => Some((register, object)),
+        _ => None,
+    }
+}
+
+/// Groups the statements between `SetTarget` actions into `tellTarget` blocks.
+fn group_tell_targets(statements: Vec<Stmt>) -> Vec<Stmt> {
+    let mut out = Vec::with_capacity(statements.len());
+    let mut target: Option<(Expr, Vec<Stmt>)> = None;
+    for statement in statements {
+        match statement {
+            Stmt::SetTarget(new_target) => {
+                if let Some((target, body)) = target.take() {
+                    out.push(Stmt::TellTarget(target, body));
+                }
+                target = new_target.map(|new_target| (new_target, Vec::new()));
+            }
+            statement => match &mut target {
+                Some((_, body)) => body.push(statement),
+                None => out.push(statement),
+            },
+        }
+    }
+    if let Some((target, body)) = target {
+        out.push(Stmt::TellTarget(target, body));
+    }
+    out
+}
+
+/// The names of the registers of a function.
+#[derive(Default)]
+struct Registers(HashMap<u8, String>);
+
+impl Registers {
+    fn for_function(function: &DefineFunction2, encoding: &'static Encoding) -> Self {
+        let mut names = HashMap::new();
+        let mut register = 1;
+        for (flag, name) in [
+            (FunctionFlags::PRELOAD_THIS, "this"),
+            (FunctionFlags::PRELOAD_ARGUMENTS, "arguments"),
+            (FunctionFlags::PRELOAD_SUPER, "super"),
+            (FunctionFlags::PRELOAD_ROOT, "_root"),
+            (FunctionFlags::PRELOAD_PARENT, "_parent"),
+            (FunctionFlags::PRELOAD_GLOBAL, "_global"),
+        ] {
+            if function.flags.contains(flag) {
+                names.insert(register, name.to_string());
+                register += 1;
+            }
+        }
+        for param in &function.params {
+            if let Some(register) = param.register_index {
+                names.insert(register.get(), param.name.to_string_lossy(encoding));
+            }
+        }
+        Self(names)
+    }
+
+    fn get(&self, register: u8) -> Expr {
+        Expr::Name(
+            self.0
+                .get(&register)
+                .cloned()
+                .unwrap_or_else(|| format!("register{register}")),
+        )
+    }
+}
+
+/// The loop that `break` and `continue` statements jump out of.
+#[derive(Clone, Copy)]
+struct Loop {
+    /// The index of the first action of the loop.
+    head: usize,
+
+    /// The index of the first action after the loop.
+    exit: usize,
+}
+
+fn pop(stack: &mut Vec<Expr>) -> Expr {
+    stack.pop().unwrap_or(UNDEFINED)
+}
+
+/// Pops a count, followed by that many values.
+fn pop_list(stack: &mut Vec<Expr>) -> Vec<Expr> {
+    let count = match pop(stack) {
+        Expr::Number(count) if count > 0.0 => (count as usize).min(stack.len()),
+        _ => 0,
+    };
+    (0..count).map(|_| pop(stack)).collect()
+}
+
+/// Recovers ActionScript 2 source code from `actions`.
+///
+/// Code that doesn't match the output of the Flash compilers may not be fully
+/// recovered, in which case the jumps that couldn't be turned into statements
+/// are written as `goto` comments.
+pub fn decompile(actions: &[u8], swf_version: u8) -> String {
+    let mut decompiler = Decompiler {
+        base: actions,
+        swf_version,
+        encoding: SwfStr::encoding_for_version(swf_version),
+        constant_pool: Vec::new(),
+    };
+    let body = decompiler.body(actions, &Registers::default());
+    let mut writer = SourceWriter::default();
+    writer.statements(&body);
+    writer.out
+}
+
+struct Decompiler<'a> {
+    base: &'a [u8],
+    swf_version: u8,
+    encoding: &'static Encoding,
+    constant_pool: Vec<String>,
+}
+
+impl<'a> Decompiler<'a> {
+    fn string(&self, string: &SwfStr) -> String {
+        string.to_string_lossy(self.encoding)
+    }
+
+    fn body(&mut self, data: &'a [u8], registers: &Registers) -> Vec<Stmt> {
+        let list = ActionList::read(data, nested_offset(self.base, data), self.swf_version);
+        let mut out = Vec::new();
+        let mut stack = Vec::new();
+        let range = 0..list.instructions.len();
+        self.statements(&list, registers, range, &mut stack, None, &mut out);
+        if let Some(error) = list.error {
+            out.push(Stmt::Comment(format!("Couldn't read action: {error}")));
+        }
+        group_tell_targets(out)
+    }
+
+    fn statements(
+        &mut self,
+        list: &ActionList<'a>,
+        registers: &Registers,
+        range: Range<usize>,
+        stack: &mut Vec<Expr>,
+        current_loop: Option<Loop>,
+        out: &mut Vec<Stmt>,
+    ) {
+        let mut i = range.start;
+        while i < range.end {
+            // A jump back to this action makes it the start of a loop, which ends
+            // with the last such jump.
+            let loop_end = if current_loop.is_some_and(|l| l.head == i) {
+                None
+            } else {
+                (i + 1..range.end).rev().find(|&j| {
+                    let target = list.instructions[j].jump_target();
+                    target.and_then(|target| list.index_of(target)) == Some(i)
+                })
+            };
+            if let Some(j) = loop_end {
+                let inner = Some(Loop {
+                    head: i,
+                    exit: j + 1,
+                });
+                let mut loop_stack = stack.clone();
+                let mut body = Vec::new();
+                self.statements(list, registers, i..j, &mut loop_stack, inner, &mut body);
+                out.push(match list.instructions[j].action {
+                    Action::If(_) => Stmt::DoWhile(body, pop(&mut loop_stack)),
+                    _ => Stmt::infinite_loop(body),
+                });
+                // The loop ends once all the names to enumerate were popped.
+                if matches!(stack.last(), Some(Expr::Enumeration(_))) {
+                    stack.pop();
+                }
+                i = j + 1;
+                continue;
+            }
+
+            let instruction = &list.instructions[i];
+            let target_offset = instruction.jump_target().unwrap_or_default();
+            let target = list.index_of(target_offset);
+            match &instruction.action {
+                Action::If(_) => {
+                    i = self.condition(
+                        list,
+                        registers,
+                        i,
+                        range.clone(),
+                        (target, target_offset),
+                        stack,
+                        current_loop,
+                        out,
+                    );
+                }
+                Action::Jump(_) => {
+                    match target {
+                        Some(t) if current_loop.is_some_and(|l| t == l.exit) => {
+                            out.push(Stmt::Break)
+                        }
+                        Some(t) if current_loop.is_some_and(|l| t == l.head) => {
+                            out.push(Stmt::Continue)
+                        }
+                        Some(t) if t == i + 1 => {}
+                        // Leaving a `try` block jumps over the following blocks.
+                        None if target_offset >= list.end as isize
+                            && i + 1 == list.instructions.len() => {}
+                        _ => out.push(Stmt::Goto(None, target_offset)),
+                    }
+                    i += 1;
+                }
+                action => {
+                    self.action(action, registers, stack, out);
+                    i += 1;
+                }
+            }
+        }
+    }
+
+    /// Decompiles the conditional jump at `i`, returning the index of the next action.
+    #[allow(clippy::too_many_arguments)]
+    fn condition(
+        &mut self,
+        list: &ActionList<'a>,
+        registers: &Registers,
+        i: usize,
+        range: Range<usize>,
+        (target, target_offset): (Option<usize>, isize),
+        stack: &mut Vec<Expr>,
+        current_loop: Option<Loop>,
+        out: &mut Vec<Stmt>,
+    ) -> usize {
+        let action_at = |index: usize| &list.instructions[index].action;
+
+        // `a && b` and `a || b` keep `a` when jumping over the evaluation of `b`.
+        if let Some(t) = target.filter(|&t| t > i + 1 && t <= range.end) {
+            let and = i >= range.start + 2
+                && matches!(action_at(i - 1), Action::Not)
+                && matches!(action_at(i - 2), Action::PushDuplicate);
+            let or = i > range.start && matches!(action_at(i - 1), Action::PushDuplicate);
+            if (and || or) && matches!(action_at(i + 1), Action::Pop) {
+                stack.pop();
+                let left = pop(stack);
+                let mut right_stack = stack.clone();
+                self.statements(
+                    list,
+                    registers,
+                    i + 2..t,
+                    &mut right_stack,
+                    current_loop,
+                    out,
+                );
+                let right = pop(&mut right_stack);
+                stack.push(Expr::binary(if and { "&&" } else { "||" }, left, right));
+                return t;
+            }
+        }
+
+        let condition = pop(stack);
+        if let Some(l) = current_loop {
+            if target == Some(l.exit) {
+                out.push(Stmt::If(condition, vec![Stmt::Break], vec![]));
+                return i + 1;
+            }
+            if target == Some(l.head) {
+                out.push(Stmt::If(condition, vec![Stmt::Continue], vec![]));
+                return i + 1;
+            }
+        }
+
+        let Some(t) = target.filter(|&t| t > i && t <= range.end) else {
+            out.push(Stmt::Goto(Some(condition), target_offset));
+            return i + 1;
+        };
+
+        // The actions run when the condition is false come first. If there's an
+        // `else` branch, they end by jumping over it.
+        let else_end = match action_at(t - 1) {
+            Action::Jump(_) if t - 1 > i => list.instructions[t - 1]
+                .jump_target()
+                .and_then(|offset| list.index_of(offset))
+                .filter(|&e| e > t && e <= range.end)
+                .filter(|&e| current_loop.is_none_or(|l| e != l.exit && e != l.head)),
+            _ => None,
+        };
+        let Some(e) = else_end else {
+            let mut body_stack = stack.clone();
+            let mut body = Vec::new();
+            self.statements(
+                list,
+                registers,
+                i + 1..t,
+                &mut body_stack,
+                current_loop,
+                &mut body,
+            );
+            out.push(Stmt::If(condition.negate(), body, vec![]));
+            return t;
+        };
+
+        let mut false_stack = stack.clone();
+        let mut false_body = Vec::new();
+        let false_range = i + 1..t - 1;
+        self.statements(
+            list,
+            registers,
+            false_range,
+            &mut false_stack,
+            current_loop,
+            &mut false_body,
+        );
+        let mut true_stack = stack.clone();
+        let mut true_body = Vec::new();
+        self.statements(
+            list,
+            registers,
+            t..e,
+            &mut true_stack,
+            current_loop,
+            &mut true_body,
+        );
+
+        // Both branches leaving a value behind is a `condition ? a : b` expression.
+        if false_body.is_empty()
+            && true_body.is_empty()
+            && false_stack.len() == stack.len() + 1
+            && true_stack.len() == stack.len() + 1
+        {
+            stack.push(Expr::Conditional(
+                Box::new(condition),
+                Box::new(pop(&mut true_stack)),
+                Box::new(pop(&mut false_stack)),
+            ));
+        } else {
+            out.push(Stmt::if_else(condition.negate(), false_body, true_body));
+        }
+        e
+    }
+
+    fn function(
+        &mut self,
+        function: &DefineFunction2<'a>,
+        stack: &mut Vec<Expr>,
+        out: &mut Vec<Stmt>,
+    ) {
+        let registers = Registers::for_function(function, self.encoding);
+        let function = Function {
+            name: self.string(function.name),
+            params: function
+                .params
+                .iter()
+                .map(|param| self.string(param.name))
+                .collect(),
+            body: self.body(function.actions, &registers),
+        };
+        if function.name.is_empty() {
+            stack.push(Expr::Function(Box::new(function)));
+        } else {
+            out.push(Stmt::Function(function));
+        }
+    }
+
+    fn action(
+        &mut self,
+        action: &Action<'a>,
+        registers: &Registers,
+        stack: &mut Vec<Expr>,
+        out: &mut Vec<Stmt>,
+    ) {
+        let binary = match action {
+            Action::Add | Action::Add2 => Some("+"),
+            Action::Subtract => Some("-"),
+            Action::Multiply => Some("*"),
+            Action::Divide => Some("/"),
+            Action::Modulo => Some("%"),
+            Action::And => Some("&&"),
+            Action::Or => Some("||"),
+            Action::BitAnd => Some("&"),
+            Action::BitOr => Some("|"),
+            Action::BitXor => Some("^"),
+            Action::BitLShift => Some("<<"),
+            Action::BitRShift => Some(">>"),
+            Action::BitURShift => Some(">>>"),
+            Action::Equals | Action::Equals2 => Some("=="),
+            Action::StrictEquals => Some("==="),
+            Action::Less | Action::Less2 => Some("<"),
+            Action::Greater => Some(">"),
+            Action::StringEquals => Some("eq"),
+            Action::StringLess => Some("lt"),
+            Action::StringGreater => Some("gt"),
+            Action::StringAdd => Some("add"),
+            Action::InstanceOf => Some("instanceof"),
+            _ => None,
+        };
+        if let Some(op) = binary {
+            let right = pop(stack);
+            let left = pop(stack);
+            stack.push(Expr::binary(op, left, right));
+            return;
+        }
+
+        let function = match action {
+            Action::ToInteger => Some("int"),
+            Action::ToNumber => Some("Number"),
+            Action::ToString => Some("String"),
+            Action::CharToAscii => Some("ord"),
+            Action::AsciiToChar => Some("chr"),
+            Action::MBCharToAscii => Some("mbord"),
+            Action::MBAsciiToChar => Some("mbchr"),
+            Action::StringLength => Some("length"),
+            Action::MBStringLength => Some("mblength"),
+            Action::TargetPath => Some("targetPath"),
+            Action::RandomNumber => Some("random"),
+            _ => None,
+        };
+        if let Some(function) = function {
+            let value = pop(stack);
+            stack.push(Expr::call(function, vec![value]));
+            return;
+        }
+
+        let statement = match action {
+            Action::Play => Some("play"),
+            Action::Stop => Some("stop"),
+            Action::NextFrame => Some("nextFrame"),
+            Action::PreviousFrame => Some("prevFrame"),
+            Action::StopSounds => Some("stopAllSounds"),
+            Action::ToggleQuality => Some("toggleHighQuality"),
+            Action::EndDrag => Some("stopDrag"),
+            _ => None,
+        };
+        if let Some(statement) = statement {
+            out.push(Stmt::call(statement, vec![]));
+            return;
+        }
+
+        match action {
+            Action::ConstantPool(pool) => {
+                self.constant_pool = pool
+                    .strings
+                    .iter()
+                    .map(|string| self.string(string))
+                    .collect();
+            }
+            Action::Push(push) => {
+                for value in &push.values {
+                    stack.push(match value {
+                        Value::Undefined => UNDEFINED,
+                        Value::Null => Expr::Literal("null"),
+                        Value::Bool(true) => Expr::Literal("true"),
+                        Value::Bool(false) => Expr::Literal("false"),
+                        Value::Int(value) => Expr::Number((*value).into()),
+                        Value::Float(value) => Expr::Number((*value).into()),
+                        Value::Double(value) => Expr::Number(*value),
+                        Value::Str(string) => Expr::Str(self.string(string)),
+                        Value::Register(register) => registers.get(*register),
+                        Value::ConstantPool(index) => {
+                            match self.constant_pool.get(usize::from(*index)) {
+                                Some(string) => Expr::Str(string.clone()),
+                                None => UNDEFINED,
+                            }
+                        }
+                    });
+                }
+            }
+            Action::Pop => {
+                let value = pop(stack);
+                if !value.is_pure() && !matches!(value, Expr::Enumeration(_)) {
+                    out.push(Stmt::Expr(value));
+                }
+            }
+            Action::PushDuplicate => {
+                let value = stack.last().cloned().unwrap_or(UNDEFINED);
+                stack.push(value);
+            }
+            Action::StackSwap => {
+                let top = pop(stack);
+                let below = pop(stack);
+                stack.push(top);
+                stack.push(below);
+            }
+            Action::StoreRegister(store) => {
+                let value = pop(stack);
+                let register = registers.get(store.register);
+                stack.push(Expr::Assign(Box::new(register), Box::new(value)));
+            }
+            Action::Not => {
+                let value = pop(stack);
+                stack.push(value.negate());
+            }
+            Action::Increment | Action::Decrement => {
+                let value = pop(stack);
+                let op = if matches!(action, Action::Increment) {
+                    "+"
+                } else {
+                    "-"
+                };
+                stack.push(Expr::binary(op, value, Expr::Number(1.0)));
+            }
+            Action::TypeOf => {
+                let value = pop(stack);
+                stack.push(Expr::Unary("typeof ", Box::new(value)));
+            }
+            Action::StringExtract | Action::MBStringExtract => {
+                let count = pop(stack);
+                let index = pop(stack);
+                let string = pop(stack);
+                let function = if matches!(action, Action::StringExtract) {
+                    "substring"
+                } else {
+                    "mbsubstring"
+                };
+                stack.push(Expr::call(function, vec![string, index, count]));
+            }
+            Action::GetTime => stack.push(Expr::call("getTimer", vec![])),
+            Action::GetVariable => {
+                let name = pop(stack);
+                stack.push(Expr::variable(name));
+            }
+            Action::SetVariable => {
+                let value = pop(stack);
+                match pop(stack) {
+                    Expr::Str(name) if is_path(&name) => out.push(Stmt::Expr(Expr::Assign(
+                        Box::new(Expr::Name(name)),
+                        Box::new(value),
+                    ))),
+                    name => out.push(Stmt::call("set", vec![name, value])),
+                }
+            }
+            Action::DefineLocal => {
+                let value = pop(stack);
+                match pop(stack) {
+                    Expr::Str(name) => out.push(Stmt::Var(name, Some(value))),
+                    name => out.push(Stmt::call("set", vec![name, value])),
+                }
+            }
+            Action::DefineLocal2 => {
+                if let Expr::Str(name) = pop(stack) {
+                    out.push(Stmt::Var(name, None));
+                }
+            }
+            Action::GetMember => {
+                let name = pop(stack);
+                let object = pop(stack);
+                stack.push(Expr::Member(Box::new(object), Box::new(name)));
+            }
+            Action::SetMember => {
+                let value = pop(stack);
+                let name = pop(stack);
+                let object = pop(stack);
+                let member = Expr::Member(Box::new(object), Box::new(name));
+                out.push(Stmt::Expr(Expr::Assign(Box::new(member), Box::new(value))));
+            }
+            Action::Delete => {
+                let name = pop(stack);
+                let object = pop(stack);
+                let member = Expr::Member(Box::new(object), Box::new(name));
+                stack.push(Expr::Unary("delete ", Box::new(member)));
+            }
+            Action::Delete2 => {
+                let name = pop(stack);
+                stack.push(Expr::Unary("delete ", Box::new(Expr::variable(name))));
+            }
+            Action::CallFunction | Action::NewObject => {
+                let function = Box::new(Expr::variable(pop(stack)));
+                let args = pop_list(stack);
+                stack.push(match action {
+                    Action::CallFunction => Expr::Call(function, args),
+                    _ => Expr::New(function, args),
+                });
+            }
+            Action::CallMethod | Action::NewMethod => {
+                let name = pop(stack);
+                let object = pop(stack);
+                let args = pop_list(stack);
+                // Without a name, the object itself is called.
+                let function = match name {
+                    UNDEFINED => object,
+                    Expr::Str(name) if name.is_empty() => object,
+                    name => Expr::Member(Box::new(object), Box::new(name)),
+                };
+                stack.push(match action {
+                    Action::CallMethod => Expr::Call(Box::new(function), args),
+                    _ => Expr::New(Box::new(function), args),
+                });
+            }
+            Action::InitArray => {
+                let values = pop_list(stack);
+                stack.push(Expr::Array(values));
+            }
+            Action::InitObject => {
+                let count = match pop(stack) {
+                    Expr::Number(count) if count > 0.0 => (count as usize).min(stack.len() / 2),
+                    _ => 0,
+                };
+                let mut properties: Vec<_> = (0..count)
+                    .map(|_| {
+                        let value = pop(stack);
+                        (pop(stack), value)
+                    })
+                    .collect();
+                properties.reverse();
+                stack.push(Expr::Object(properties));
+            }
+            Action::CastOp => {
+                let object = pop(stack);
+                let class = pop(stack);
+                stack.push(Expr::Call(Box::new(class), vec![object]));
+            }
+            Action::ImplementsOp => {
+                let class = pop(stack);
+                let interfaces = pop_list(stack);
+                let interfaces: Vec<_> = interfaces.iter().map(render).collect();
+                out.push(Stmt::Comment(format!(
+                    "{} implements {}",
+                    render(&class),
+                    interfaces.join(", ")
+                )));
+            }
+            Action::Extends => {
+                let superclass = pop(stack);
+                let class = pop(stack);
+                out.push(Stmt::Comment(format!(
+                    "{} extends {}",
+                    render(&class),
+                    render(&superclass)
+                )));
+            }
+            Action::Enumerate => {
+                let name = pop(stack);
+                stack.push(Expr::Enumeration(Box::new(Expr::variable(name))));
+            }
+            Action::Enumerate2 => {
+                let object = pop(stack);
+                stack.push(Expr::Enumeration(Box::new(object)));
+            }
+            Action::Return => out.push(Stmt::Return(pop(stack))),
+            Action::Throw => out.push(Stmt::Throw(pop(stack))),
+            Action::Trace => {
+                let value = pop(stack);
+                out.push(Stmt::call("trace", vec![value]));
+            }
+            Action::Call => {
+                let frame = pop(stack);
+                out.push(Stmt::call("call", vec![frame]));
+            }
+            Action::GotoFrame(goto) => {
+                let frame = Expr::Number(f64::from(goto.frame) + 1.0);
+                out.push(Stmt::call("gotoAndStop", vec![frame]));
+            }
+            Action::GotoLabel(goto) => {
+                let label = Expr::Str(self.string(goto.label));
+                out.push(Stmt::call("gotoAndStop", vec![label]));
+            }
+            Action::GotoFrame2(goto) => {
+                let mut frame = pop(stack);
+                if goto.scene_offset != 0 {
+                    let offset = Expr::Number(goto.scene_offset.into());
+                    frame = Expr::binary("+", frame, offset);
+                }
+                let function = if goto.set_playing {
+                    "gotoAndPlay"
+                } else {
+                    "gotoAndStop"
+                };
+                out.push(Stmt::call(function, vec![frame]));
+            }
+            Action::GetUrl(get_url) => {
+                let url = self.string(get_url.url);
+                let target = Expr::Str(self.string(get_url.target));
+                match url.strip_prefix("FSCommand:") {
+                    Some(command) => {
+                        let command = Expr::Str(command.to_string());
+                        out.push(Stmt::call("fscommand", vec![command, target]));
+                    }
+                    None => out.push(Stmt::call("getURL", vec![Expr::Str(url), target])),
+                }
+            }
+            Action::GetUrl2(get_url) => {
+                let target = pop(stack);
+                let url = pop(stack);
+                let mut args = vec![url, target];
+                match get_url.send_vars_method() {
+                    SendVarsMethod::None => {}
+                    SendVarsMethod::Get => args.push(Expr::Str("GET".to_string())),
+                    SendVarsMethod::Post => args.push(Expr::Str("POST".to_string())),
+                }
+                let function = if get_url.is_load_vars() {
+                    "loadVariables"
+                } else if get_url.is_target_sprite() {
+                    "loadMovie"
+                } else {
+                    "getURL"
+                };
+                out.push(Stmt::call(function, args));
+            }
+            Action::GetProperty => {
+                let property = property(pop(stack));
+                let target = pop(stack);
+                stack.push(Expr::call("getProperty", vec![target, property]));
+            }
+            Action::SetProperty => {
+                let value = pop(stack);
+                let property = property(pop(stack));
+                let target = pop(stack);
+                out.push(Stmt::call("setProperty", vec![target, property, value]));
+            }
+            Action::CloneSprite => {
+                let depth = pop(stack);
+                let target = pop(stack);
+                let source = pop(stack);
+                out.push(Stmt::call(
+                    "duplicateMovieClip",
+                    vec![source, target, depth],
+                ));
+            }
+            Action::RemoveSprite => {
+                let target = pop(stack);
+                out.push(Stmt::call("removeMovieClip", vec![target]));
+            }
+            Action::StartDrag => {
+                let target = pop(stack);
+                let lock_center = pop(stack);
+                let constrain = match pop(stack) {
+                    Expr::Number(value) => value != 0.0,
+                    Expr::Literal(value) => value == "true",
+                    _ => false,
+                };
+                let mut args = vec![target, lock_center];
+                if constrain {
+                    let bottom = pop(stack);
+                    let right = pop(stack);
+                    let top = pop(stack);
+                    let left = pop(stack);
+                    args.extend([left, top, right, bottom]);
+                }
+                out.push(Stmt::call("startDrag", args));
+            }
+            Action::SetTarget(set_target) => {
+                let target = self.string(set_target.target);
+                out.push(Stmt::SetTarget(
+                    (!target.is_empty()).then_some(Expr::Str(target)),
+                ));
+            }
+            Action::SetTarget2 => {
+                let target = pop(stack);
+                let is_empty = matches!(&target, Expr::Str(target) if target.is_empty());
+                out.push(Stmt::SetTarget((!is_empty).then_some(target)));
+            }
+            Action::WaitForFrame(wait) => out.push(Stmt::Comment(format!(
+                "ifFrameLoaded({}), or skip {} actions",
+                u32::from(wait.frame) + 1,
+                wait.num_actions_to_skip
+            ))),
+            Action::WaitForFrame2(wait) => {
+                let frame = pop(stack);
+                out.push(Stmt::Comment(format!(
+                    "ifFrameLoaded({}), or skip {} actions",
+                    render(&frame),
+                    wait.num_actions_to_skip
+                )));
+            }
+            Action::DefineFunction(function) => self.function(&function.clone().into(), stack, out),
+            Action::DefineFunction2(function) => self.function(function, stack, out),
+            Action::With(with) => {
+                let object = pop(stack);
+                let body = self.body(with.actions, registers);
+                out.push(Stmt::With(object, body));
+            }
+            Action::Try(try_block) => {
+                let body = self.body(try_block.try_body, registers);
+                let catch = try_block.catch_body.as_ref().map(|(var, body)| {
+                    let name = match var {
+                        CatchVar::Var(name) => self.string(name),
+                        CatchVar::Register(register) => render(&registers.get(*register)),
+                    };
+                    (name, self.body(body, registers))
+                });
+                let finally = try_block
+                    .finally_body
+                    .map(|body| self.body(body, registers));
+                out.push(Stmt::Try {
+                    body,
+                    catch,
+                    finally,
+                });
+            }
+            Action::Unknown(unknown) => out.push(Stmt::Comment(format!(
+                "Unknown action 0x{:02x}",
+                unknown.opcode
+            ))),
+            // Jumps are handled with the statements they belong to.
+            _ => {}
+        }
+    }
+}
+
+/// The property named by an index of `getProperty` or `setProperty`.
+fn property(index: Expr) -> Expr {
+    match index {
+        Expr::Number(index) if index >= 0.0 => PROPERTIES
+            .get(index as usize)
+            .map_or(Expr::Number(index), |name| Expr::Name(name.to_string())),
+        index => index,
+    }
+}
+
+fn render(expr: &Expr) -> String {
+    let mut writer = SourceWriter::default();
+    writer.expr(expr, 0);
+    writer.out
+}
+
+#[derive(Default)]
+struct SourceWriter {
+    out: String,
+    indent: usize,
+}
+
+impl SourceWriter {
+    fn line(&mut self) {
+        for _ in 0..self.indent {
+            self.out.push_str("    ");
+        }
+    }
+
+    fn statements(&mut self, statements: &[Stmt]) {
+        for statement in statements {
+            self.line();
+            self.statement(statement);
+            self.out.push('\n');
+        }
+    }
+
+    fn block(&mut self, statements: &[Stmt]) {
+        self.out.push_str("{\n");
+        self.indent += 1;
+        self.statements(statements);
+        self.indent -= 1;
+        self.line();
+        self.out.push('}');
+    }
+
+    fn statement(&mut self, statement: &Stmt) {
+        match statement {
+            Stmt::Expr(expr) => {
+                self.expr(expr, 0);
+                self.out.push(';');
+            }
+            Stmt::Var(name, value) => {
+                let _ = write!(self.out, "var {name}");
+                if let Some(value) = value {
+                    self.out.push_str(" = ");
+                    self.expr(value, 1);
+                }
+                self.out.push(';');
+            }
+            Stmt::Return(UNDEFINED) => self.out.push_str("return;"),
+            Stmt::Return(value) => {
+                self.out.push_str("return ");
+                self.expr(value, 0);
+                self.out.push(';');
+            }
+            Stmt::Throw(value) => {
+                self.out.push_str("throw ");
+                self.expr(value, 0);
+                self.out.push(';');
+            }
+            Stmt::If(condition, then, otherwise) => {
+                self.out.push_str("if (");
+                self.expr(condition, 0);
+                self.out.push_str(") ");
+                self.block(then);
+                match otherwise.as_slice() {
+                    [] => {}
+                    [else_if @ Stmt::If(..)] => {
+                        self.out.push_str(" else ");
+                        self.statement(else_if);
+                    }
+                    otherwise => {
+                        self.out.push_str(" else ");
+                        self.block(otherwise);
+                    }
+                }
+            }
+            Stmt::While(condition, body) => {
+                self.out.push_str("while (");
+                self.expr(condition, 0);
+                self.out.push_str(") ");
+                self.block(body);
+            }
+            Stmt::DoWhile(body, condition) => {
+                self.out.push_str("do ");
+                self.block(body);
+                self.out.push_str(" while (");
+                self.expr(condition, 0);
+                self.out.push_str(");");
+            }
+            Stmt::ForIn {
+                declare,
+                variable,
+                object,
+                body,
+            } => {
+                self.out
+                    .push_str(if *declare { "for (var " } else { "for (" });
+                self.expr(variable, 14);
+                self.out.push_str(" in ");
+                self.expr(object, 0);
+                self.out.push_str(") ");
+                self.block(body);
+            }
+            Stmt::With(object, body) => {
+                self.out.push_str("with (");
+                self.expr(object, 0);
+                self.out.push_str(") ");
+                self.block(body);
+            }
+            Stmt::Try {
+                body,
+                catch,
+                finally,
+            } => {
+                self.out.push_str("try ");
+                self.block(body);
+                if let Some((name, body)) = catch {
+                    let _ = write!(self.out, " catch ({name}) ");
+                    self.block(body);
+                }
+                if let Some(body) = finally {
+                    self.out.push_str(" finally ");
+                    self.block(body);
+                }
+            }
+            Stmt::Function(function) => self.function(function),
+            Stmt::Break => self.out.push_str("break;"),
+            Stmt::Continue => self.out.push_str("continue;"),
+            Stmt::SetTarget(target) => {
+                self.out.push_str("// setTarget(");
+                self.expr(target.as_ref().unwrap_or(&Expr::Str(String::new())), 0);
+                self.out.push(')');
+            }
+            Stmt::TellTarget(target, body) => {
+                self.out.push_str("tellTarget (");
+                self.expr(target, 0);
+                self.out.push_str(") ");
+                self.block(body);
+            }
+            Stmt::Goto(condition, offset) => {
+                self.out.push_str("// ");
+                if let Some(condition) = condition {
+                    self.out.push_str("if (");
+                    self.expr(condition, 0);
+                    self.out.push_str(") ");
+                }
+                let _ = write!(self.out, "goto {}", label(*offset));
+            }
+            Stmt::Comment(text) => {
+                let _ = write!(self.out, "// {text}");
+            }
+        }
+    }
+
+    fn function(&mut self, function: &Function) {
+        let _ = write!(
+            self.out,
+            "function {}({}) ",
+            function.name,
+            function.params.join(", ")
+        );
+        self.block(&function.body);
+    }
+
+    fn list(&mut self, values: &[Expr]) {
+        for (i, value) in values.iter().enumerate() {
+            if i > 0 {
+                self.out.push_str(", ");
+            }
+            self.expr(value, 1);
+        }
+    }
+
+    /// Writes an expression, in parentheses if it binds less tightly than `precedence`.
+    fn expr(&mut self, expr: &Expr, precedence: u8) {
+        let parenthesize = expr.precedence() < precedence;
+        if parenthesize {
+            self.out.push('(');
+        }
+        match expr {
+            Expr::Literal(literal) => self.out.push_str(literal),
+            Expr::Number(value) => {
+                if value.is_nan() {
+                    self.out.push_str("NaN");
+                } else if value.is_infinite() {
+                    self.out.push_str(if *value > 0.0 {
+                        "Infinity"
+                    } else {
+                        "-Infinity"
+                    });
+                } else if value.fract() == 0.0 && value.abs() < 1e15 {
+                    let _ = write!(self.out, "{}", *value as i64);
+                } else {
+                    let _ = write!(self.out, "{value}");
+                }
+            }
+            Expr::Str(string) => write_quoted(&mut self.out, string),
+            Expr::Name(name) => self.out.push_str(name),
+            Expr::Member(object, name) => {
+                self.expr(object, 14);
+                match &**name {
+                    Expr::Str(name) if is_identifier(name) => {
+                        let _ = write!(self.out, ".{name}");
+                    }
+                    name => {
+                        self.out.push('[');
+                        self.expr(name, 0);
+                        self.out.push(']');
+                    }
+                }
+            }
+            Expr::Call(function, args) => {
+                self.expr(function, 14);
+                self.out.push('(');
+                self.list(args);
+                self.out.push(')');
+            }
+            Expr::New(class, args) => {
+                self.out.push_str("new ");
+                let precedence = if matches!(**class, Expr::Call(..)) {
+                    15
+                } else {
+                    14
+                };
+                self.expr(class, precedence);
+                self.out.push('(');
+                self.list(args);
+                self.out.push(')');
+            }
+            Expr::Unary(op, value) => {
+                self.out.push_str(op);
+                self.expr(value, 13);
+            }
+            Expr::Binary(op, left, right) => {
+                let precedence = binary_precedence(op);
+                self.expr(left, precedence);
+                let _ = write!(self.out, " {op} ");
+                self.expr(right, precedence + 1);
+            }
+            Expr::Conditional(condition, then, otherwise) => {
+                self.expr(condition, 3);
+                self.out.push_str(" ? ");
+                self.expr(then, 1);
+                self.out.push_str(" : ");
+                self.expr(otherwise, 1);
+            }
+            Expr::Assign(target, value) => {
+                self.expr(target, 14);
+                self.out.push_str(" = ");
+                self.expr(value, 1);
+            }
+            Expr::Array(values) => {
+                self.out.push('[');
+                self.list(values);
+                self.out.push(']');
+            }
+            Expr::Object(properties) => {
+                self.out.push('{');
+                for (i, (name, value)) in properties.iter().enumerate() {
+                    if i > 0 {
+                        self.out.push_str(", ");
+                    }
+                    match name {
+                        Expr::Str(name) if is_identifier(name) => self.out.push_str(name),
+                        name => self.expr(name, 15),
+                    }
+                    self.out.push_str(": ");
+                    self.expr(value, 1);
+                }
+                self.out.push('}');
+            }
+            Expr::Function(function) => self.function(function),
+            Expr::Enumeration(object) => {
+                self.out.push_str("enumerate(");
+                self.expr(object, 0);
+                self.out.push(')');
+            }
+        }
+        if parenthesize {
+            self.out.push(')');
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::disassemble;
+    use super::super::tests::{assemble, str};
+    use super::*;
+
+    fn push_str(string: &str) -> Action<'_> {
+        Action::Push(Push {
+            values: vec![Value::Str(str(string))],
+        })
+    }
+
+    #[test]
+    fn decompile_if_else() {
+        let data = assemble(&[
+            push_str("x"),
+            Action::GetVariable,
+            Action::Not,
+            Action::If(If { offset: 12 }),
+            push_str("a"),
+            Action::Trace,
+            Action::Jump(Jump { offset: 7 }),
+            push_str("b"),
+            Action::Trace,
+            Action::End,
+        ]);
+
+        assert_eq!(
+            decompile(&data, 10),
+            "if (x) {\n    trace(\"a\");\n} else {\n    trace(\"b\");\n}\n"
+        );
+    }
+
+    #[test]
+    fn decompile_function_with_loop() {
+        let body = assemble(&[
+            push_str("a"),
+            Action::GetVariable,
+            Action::PushDuplicate,
+            Action::Not,
+            Action::If(If { offset: 8 }),
+            Action::Pop,
+            push_str("b"),
+            Action::GetVariable,
+            Action::Not,
+            Action::If(If { offset: 28 }),
+            push_str("a"),
+            push_str("a"),
+            Action::GetVariable,
+            Action::Push(Push {
+                values: vec![Value::Int(1)],
+            }),
+            Action::Subtract,
+            Action::SetVariable,
+            Action::Jump(Jump { offset: -56 }),
+            push_str("a"),
+            Action::GetVariable,
+            Action::Return,
+        ]);
+        let data = assemble(&[
+            Action::DefineFunction(DefineFunction {
+                name: str("f"),
+                params: vec![str("a")],
+                actions: &body,
+            }),
+            Action::End,
+        ]);
+
+        assert_eq!(
+            decompile(&data, 10),
+            concat!(
+                "function f(a) {\n",
+                "    while (a && b) {\n",
+                "        a = a - 1;\n",
+                "    }\n",
+                "    return a;\n",
+                "}\n",
+            )
+        );
+    }
+
+    #[test]
+    fn decompile_for_in() {
+        let data = assemble(&[
+            push_str("o"),
+            Action::GetVariable,
+            Action::Enumerate2,
+            Action::StoreRegister(StoreRegister { register: 0 }),
+            Action::Push(Push {
+                values: vec![Value::Null],
+            }),
+            Action::Equals2,
+            Action::If(If { offset: 22 }),
+            Action::Push(Push {
+                values: vec![Value::Str(str("k")), Value::Register(0)],
+            }),
+            Action::DefineLocal,
+            push_str("k"),
+            Action::GetVariable,
+            Action::Trace,
+            Action::Jump(Jump { offset: -36 }),
+        ]);
+
+        assert_eq!(
+            decompile(&data, 10),
+            "for (var k in o) {\n    trace(k);\n}\n"
+        );
+    }
+
+    #[test]
+    fn decompile_while() {
+        let data = assemble(&[
+            push_str("i"),
+            Action::GetVariable,
+            Action::Push(Push {
+                values: vec![Value::Int(3)],
+            }),
+            Action::Less2,
+            Action::Not,
+            Action::If(If { offset: 12 }),
+            push_str("a"),
+            Action::Trace,
+            Action::Jump(Jump { offset: -34 }),
+        ]);
+
+        assert_eq!(
+            decompile(&data, 10),
+            "while (i < 3) {\n    trace(\"a\");\n}\n"
+        );
+    }
+
+    #[test]
+    fn decompile_do_while() {
+        let data = assemble(&[
+            push_str("a"),
+            Action::Trace,
+            push_str("x"),
+            Action::GetVariable,
+            Action::If(If { offset: -19 }),
+        ]);
+
+        assert_eq!(
+            decompile(&data, 10),
+            "do {\n    trace(\"a\");\n} while (x);\n"
+        );
+    }
+
+    #[test]
+    fn decompile_with() {
+        let body = assemble(&[push_str("a"), Action::Trace]);
+        let data = assemble(&[
+            push_str("o"),
+            Action::GetVariable,
+            Action::With(With { actions: &body }),
+        ]);
+
+        assert_eq!(decompile(&data, 10), "with (o) {\n    trace(\"a\");\n}\n");
+    }
+
+    #[test]
+    fn decompile_try_catch_finally() {
+        let catch_body = assemble(&[push_str("e"), Action::GetVariable, Action::Trace]);
+        // The end of the `try` block jumps over the `catch` block.
+        let try_body = assemble(&[
+            push_str("a"),
+            Action::Trace,
+            Action::Jump(Jump {
+                offset: catch_body.len() as i16,
+            }),
+        ]);
+        let finally_body = assemble(&[push_str("b"), Action::Trace]);
+        let data = assemble(&[Action::Try(Try {
+            try_body: &try_body,
+            catch_body: Some((CatchVar::Var(str("e")), &catch_body)),
+            finally_body: Some(&finally_body),
+        })]);
+
+        assert_eq!(
+            decompile(&data, 10),
+            concat!(
+                "try {\n",
+                "    trace(\"a\");\n",
+                "} catch (e) {\n",
+                "    trace(e);\n",
+                "} finally {\n",
+                "    trace(\"b\");\n",
+                "}\n",
+            )
+        );
+    }
+
+    #[test]
+    fn decompile_function2_registers() {
+        let body = assemble(&[
+            Action::Push(Push {
+                values: vec![Value::Register(2)],
+            }),
+            Action::Trace,
+            Action::Push(Push {
+                values: vec![Value::Register(1)],
+            }),
+            Action::Trace,
+            Action::Push(Push {
+                values: vec![Value::Register(3)],
+            }),
+            Action::Trace,
+        ]);
+        let data = assemble(&[Action::DefineFunction2(DefineFunction2 {
+            name: str("f"),
+            register_count: 4,
+            params: vec![FunctionParam {
+                name: str("x"),
+                register_index: std::num::NonZeroU8::new(2),
+            }],
+            flags: FunctionFlags::PRELOAD_THIS | FunctionFlags::SUPPRESS_ARGUMENTS,
+            actions: &body,
+        })]);
+
+        assert_eq!(
+            decompile(&data, 10),
+            concat!(
+                "function f(x) {\n",
+                "    trace(x);\n",
+                "    trace(this);\n",
+                "    trace(register3);\n",
+                "}\n",
+            )
+        );
+    }
+
+    #[test]
+    fn unstructured_jumps_fall_back_to_labels() {
+        let mut data = assemble(&[
+            push_str("a"),
+            Action::Trace,
+            // Into the middle of the next action.
+            Action::Jump(Jump { offset: 2 }),
+            push_str("b"),
+            Action::Trace,
+            // Before the start of the actions.
+            Action::If(If { offset: -100 }),
+            push_str("c"),
+        ]);
+        // Cut the last action short.
+        data.truncate(data.len() - 2);
+
+        assert_eq!(
+            decompile(&data, 10),
+            concat!(
+                "trace(\"a\");\n",
+                "// goto loc_000e\n",
+                "trace(\"b\");\n",
+                "// if (undefined) goto loc_-004c\n",
+                "// Couldn't read action: Error parsing AVM1 action Push: Not enough data for string\n",
+            )
+        );
+        // The labels are the offsets used by the disassembly.
+        let disassembly = disassemble(&data, 10);
+        assert!(disassembly.contains("    Jump loc_000e\n"), "{disassembly}");
+        assert!(disassembly.contains("    If loc_-004c\n"), "{disassembly}");
+    }
+}
@@ -0,0 +1,1063 @@
+//! A text format for ABC files, in the style of RABCDAsm.
+//!
+//! Constants are written inline where they're used, such as
+//! `QName(PackageNamespace("flash.display"), "Sprite")`. Index 0 of a constant
+//! pool is written as `null`. The `constants` section lists every entry of the
+//! constant pool in order, so that the assembler gives them back their
+//! indices, including the entries that nothing refers to. Constants that
+//! aren't listed there are added after the listed ones. Methods, metadata,
+//! classes and scripts keep their indices, and method bodies are written in
+//! the method they belong to:
+//!
+//! ```text
+//! version 46 16
+//!
+//! constants
+//!     string "void"
+//!     string ""
+//!     namespace PackageNamespace("")
+//!     multiname QName(PackageNamespace(""), "void")
+//! end
+//!
+//! method 0
+//!     name null
+//!     returns null
+//!     body
+//!         maxstack 1
+//!         localcount 1
+//!         initscopedepth 0
+//!         maxscopedepth 1
+//!         code
+//!             getlocal0
+//!             pushscope
+//!             returnvoid
+//!         end
+//!     end
+//! end
+//!
+//! script 0
+//!     sinit 0
+//! end
+//! ```
+//!
+//! Code is decoded byte by byte, so that assembling it gives back the same
+//! instructions. Branch targets become labels named after their offset, such
+//! as `L12`.
+//!
+//! Private namespaces are written with their index, as in
+//! `PrivateNamespace("Main", 4)`, since namespaces with the same name are still
+//! distinct. Entries that can't be told apart from an earlier entry by their
+//! value, and indices that are out of range of their constant pool, are
+//! written as `#index` and assembled as is. So are the multinames that a
+//! multiname of the `constants` section refers to, unless they're listed
+//! before it.
+
+mod assemble;
+
+pub use assemble::assemble;
+
+use crate::avm2::opcode::OpCode;
+use crate::avm2::types::*;
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::Write;
+use std::hash::Hash;
+
+/// The kind of an operand of an instruction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Operand {
+    U8,
+    U30,
+    /// A signed 24-bit branch offset.
+    Offset,
+    Int,
+    UInt,
+    Double,
+    String,
+    Namespace,
+    Multiname,
+    /// The case offsets of a `lookupswitch`, as a count and a list of offsets.
+    Cases,
+}
+
+struct OpInfo {
+    opcode: OpCode,
+    name: &'static str,
+    operands: &'static [Operand],
+}
+
+macro_rules! ops {
+    ($($opcode:ident $name:literal [$($operand:ident),*],)*) => {
+        &[$(OpInfo {
+            opcode: OpCode::$opcode,
+            name: $name,
+            operands: &[$(Operand::$operand),*],
+        },)*]
+    };
+}
+
+/// The names and operands of all instructions.
+const OPS: &[OpInfo] = ops![
+    Bkpt "bkpt" [],
+    Nop "nop" [],
+    Throw "throw" [],
+    GetSuper "getsuper" [Multiname],
+    SetSuper "setsuper" [Multiname],
+    Dxns "dxns" [String],
+    DxnsLate "dxnslate" [],
+    Kill "kill" [U30],
+    Label "label" [],
+    IfNlt "ifnlt" [Offset],
+    IfNle "ifnle" [Offset],
+    IfNgt "ifngt" [Offset],
+    IfNge "ifnge" [Offset],
+    Jump "jump" [Offset],
+    IfTrue "iftrue" [Offset],
+    IfFalse "iffalse" [Offset],
+    IfEq "ifeq" [Offset],
+    IfNe "ifne" [Offset],
+    IfLt "iflt" [Offset],
+    IfLe "ifle" [Offset],
+    IfGt "ifgt" [Offset],
+    IfGe "ifge" [Offset],
+    IfStrictEq "ifstricteq" [Offset],
+    IfStrictNe "ifstrictne" [Offset],
+    LookupSwitch "lookupswitch" [Offset, Cases],
+    PushWith "pushwith" [],
+    PopScope "popscope" [],
+    NextName "nextname" [],
+    HasNext "hasnext" [],
+    PushNull "pushnull" [],
+    PushUndefined "pushundefined" [],
+    NextValue "nextvalue" [],
+    PushByte "pushbyte" [U8],
+    PushShort "pushshort" [U30],
+    PushTrue "pushtrue" [],
+    PushFalse "pushfalse" [],
+    PushNaN "pushnan" [],
+    Pop "pop" [],
+    Dup "dup" [],
+    Swap "swap" [],
+    PushString "pushstring" [String],
+    PushInt "pushint" [Int],
+    PushUint "pushuint" [UInt],
+    PushDouble "pushdouble" [Double],
+    PushScope "pushscope" [],
+    PushNamespace "pushnamespace" [Namespace],
+    HasNext2 "hasnext2" [U30, U30],
+    Li8 "li8" [],
+    Li16 "li16" [],
+    Li32 "li32" [],
+    Lf32 "lf32" [],
+    Lf64 "lf64" [],
+    Si8 "si8" [],
+    Si16 "si16" [],
+    Si32 "si32" [],
+    Sf32 "sf32" [],
+    Sf64 "sf64" [],
+    NewFunction "newfunction" [U30],
+    Call "call" [U30],
+    Construct "construct" [U30],
+    CallMethod "callmethod" [U30, U30],
+    CallStatic "callstatic" [U30, U30],
+    CallSuper "callsuper" [Multiname, U30],
+    CallProperty "callproperty" [Multiname, U30],
+    ReturnVoid "returnvoid" [],
+    ReturnValue "returnvalue" [],
+    ConstructSuper "constructsuper" [U30],
+    ConstructProp "constructprop" [Multiname, U30],
+    CallPropLex "callproplex" [Multiname, U30],
+    CallSuperVoid "callsupervoid" [Multiname, U30],
+    CallPropVoid "callpropvoid" [Multiname, U30],
+    Sxi1 "sxi1" [],
+    Sxi8 "sxi8" [],
+    Sxi16 "sxi16" [],
+    ApplyType "applytype" [U30],
+    NewObject "newobject" [U30],
+    NewArray "newarray" [U30],
+    NewActivation "newactivation" [],
+    NewClass "newclass" [U30],
+    GetDescendants "getdescendants" [Multiname],
+    NewCatch "newcatch" [U30],
+    FindPropStrict "findpropstrict" [Multiname],
+    FindProperty "findproperty" [Multiname],
+    FindDef "finddef" [Multiname],
+    GetLex "getlex" [Multiname],
+    SetProperty "setproperty" [Multiname],
+    GetLocal "getlocal" [U30],
+    SetLocal "setlocal" [U30],
+    GetGlobalScope "getglobalscope" [],
+    GetScopeObject "getscopeobject" [U8],
+    GetProperty "getproperty" [Multiname],
+    GetOuterScope "getouterscope" [U30],
+    InitProperty "initproperty" [Multiname],
+    DeleteProperty "deleteproperty" [Multiname],
+    GetSlot "getslot" [U30],
+    SetSlot "setslot" [U30],
+    GetGlobalSlot "getglobalslot" [U30],
+    SetGlobalSlot "setglobalslot" [U30],
+    ConvertS "convert_s" [],
+    EscXElem "esc_xelem" [],
+    EscXAttr "esc_xattr" [],
+    ConvertI "convert_i" [],
+    ConvertU "convert_u" [],
+    ConvertD "convert_d" [],
+    ConvertB "convert_b" [],
+    ConvertO "convert_o" [],
+    CheckFilter "checkfilter" [],
+    Coerce "coerce" [Multiname],
+    CoerceB "coerce_b" [],
+    CoerceA "coerce_a" [],
+    CoerceI "coerce_i" [],
+    CoerceD "coerce_d" [],
+    CoerceS "coerce_s" [],
+    AsType "astype" [Multiname],
+    AsTypeLate "astypelate" [],
+    CoerceU "coerce_u" [],
+    CoerceO "coerce_o" [],
+    Negate "negate" [],
+    Increment "increment" [],
+    IncLocal "inclocal" [U30],
+    Decrement "decrement" [],
+    DecLocal "declocal" [U30],
+    TypeOf "typeof" [],
+    Not "not" [],
+    BitNot "bitnot" [],
+    Add "add" [],
+    Subtract "subtract" [],
+    Multiply "multiply" [],
+    Divide "divide" [],
+    Modulo "modulo" [],
+    LShift "lshift" [],
+    RShift "rshift" [],
+    URShift "urshift" [],
+    BitAnd "bitand" [],
+    BitOr "bitor" [],
+    BitXor "bitxor" [],
+    Equals "equals" [],
+    StrictEquals "strictequals" [],
+    LessThan "lessthan" [],
+    LessEquals "lessequals" [],
+    GreaterThan "greaterthan" [],
+    GreaterEquals "greaterequals" [],
+    InstanceOf "instanceof" [],
+    IsType "istype" [Multiname],
+    IsTypeLate "istypelate" [],
+    In "in" [],
+    IncrementI "increment_i" [],
+    DecrementI "decrement_i" [],
+    IncLocalI "inclocal_i" [U30],
+    DecLocalI "declocal_i" [U30],
+    NegateI "negate_i" [],
+    AddI "add_i" [],
+    SubtractI "subtract_i" [],
+    MultiplyI "multiply_i" [],
+    GetLocal0 "getlocal0" [],
+    GetLocal1 "getlocal1" [],
+    GetLocal2 "getlocal2" [],
+    GetLocal3 "getlocal3" [],
+    SetLocal0 "setlocal0" [],
+    SetLocal1 "setlocal1" [],
+    SetLocal2 "setlocal2" [],
+    SetLocal3 "setlocal3" [],
+    Debug "debug" [U8, String, U8, U30],
+    DebugLine "debugline" [U30],
+    DebugFile "debugfile" [String],
+    BkptLine "bkptline" [U30],
+    Timestamp "timestamp" [],
+];
+
+fn op_info(opcode: u8) -> Option<&'static OpInfo> {
+    OPS.iter().find(|info| info.opcode as u8 == opcode)
+}
+
+fn read_u8(data: &[u8], pos: &mut usize) -> Option<u8> {
+    let byte = *data.get(*pos)?;
+    *pos += 1;
+    Some(byte)
+}
+
+fn read_u30(data: &[u8], pos: &mut usize) -> Option<u32> {
+    let mut n = 0;
+    for i in 0..5 {
+        let byte = read_u8(data, pos)?;
+        n |= u32::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+    Some(n)
+}
+
+fn read_i24(data: &[u8], pos: &mut usize) -> Option<i32> {
+    let bytes = data.get(*pos..*pos + 3)?;
+    *pos += 3;
+    Some(i32::from(bytes[0]) | (i32::from(bytes[1]) << 8) | (i32::from(bytes[2] as i8) << 16))
+}
+
+/// Writes a string in quotes, escaping quotes, control characters and invalid UTF-8.
+fn write_quoted(out: &mut String, string: &[u8]) {
+    out.push('"');
+    for chunk in string.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            match c {
+                '"' => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if c.is_control() && c.is_ascii() => {
+                    let _ = write!(out, "\\x{:02x}", c as u8);
+                }
+                c => out.push(c),
+            }
+        }
+        for byte in chunk.invalid() {
+            let _ = write!(out, "\\x{byte:02x}");
+        }
+    }
+    out.push('"');
+}
+
+/// The name of a namespace kind, and its name and disambiguating index.
+fn namespace_parts(namespace: &Namespace) -> (&'static str, &Index<String>) {
+    match namespace {
+        Namespace::Namespace(name) => ("Namespace", name),
+        Namespace::Package(name) => ("PackageNamespace", name),
+        Namespace::PackageInternal(name) => ("PackageInternalNs", name),
+        Namespace::Protected(name) => ("ProtectedNamespace", name),
+        Namespace::Explicit(name) => ("ExplicitNamespace", name),
+        Namespace::StaticProtected(name) => ("StaticProtectedNs", name),
+        Namespace::Private(name) => ("PrivateNamespace", name),
+    }
+}
+
+/// The name of a multiname kind, and the indices that the multiname is made of.
+fn multiname_parts(multiname: &Multiname) -> (&'static str, Vec<u32>) {
+    match multiname {
+        Multiname::QName { namespace, name } => ("QName", vec![namespace.0, name.0]),
+        Multiname::QNameA { namespace, name } => ("QNameA", vec![namespace.0, name.0]),
+        Multiname::RTQName { name } => ("RTQName", vec![name.0]),
+        Multiname::RTQNameA { name } => ("RTQNameA", vec![name.0]),
+        Multiname::RTQNameL => ("RTQNameL", vec![]),
+        Multiname::RTQNameLA => ("RTQNameLA", vec![]),
+        Multiname::Multiname {
+            namespace_set,
+            name,
+        } => ("Multiname", vec![namespace_set.0, name.0]),
+        Multiname::MultinameA {
+            namespace_set,
+            name,
+        } => ("MultinameA", vec![namespace_set.0, name.0]),
+        Multiname::MultinameL { namespace_set } => ("MultinameL", vec![namespace_set.0]),
+        Multiname::MultinameLA { namespace_set } => ("MultinameLA", vec![namespace_set.0]),
+        Multiname::TypeName {
+            base_type,
+            parameters,
+        } => (
+            "TypeName",
+            std::iter::once(base_type)
+                .chain(parameters)
+                .map(|multiname| multiname.0)
+                .collect(),
+        ),
+    }
+}
+
+/// The indices of the entries of a constant pool that are equal to an earlier
+/// entry, and so can't be referred to by their value.
+fn duplicates<T, K: Eq + Hash>(pool: &[T], key: impl Fn(u32, &T) -> K) -> HashSet<u32> {
+    let mut keys = HashSet::new();
+    (1..)
+        .zip(pool)
+        .filter(|(index, value)| !keys.insert(key(*index, value)))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// The entries of each constant pool that are written as `#index`.
+struct Duplicates {
+    ints: HashSet<u32>,
+    uints: HashSet<u32>,
+    doubles: HashSet<u32>,
+    strings: HashSet<u32>,
+    namespaces: HashSet<u32>,
+    namespace_sets: HashSet<u32>,
+    multinames: HashSet<u32>,
+}
+
+impl Duplicates {
+    /// Finds the duplicates the way the assembler would: by value, with the
+    /// constants they refer to given by their indices.
+    fn new(pool: &ConstantPool) -> Self {
+        Self {
+            ints: duplicates(&pool.ints, |_, value| *value),
+            uints: duplicates(&pool.uints, |_, value| *value),
+            doubles: duplicates(&pool.doubles, |_, value| value.to_bits()),
+            strings: duplicates(&pool.strings, |_, value| value.clone()),
+            namespaces: duplicates(&pool.namespaces, |index, namespace| {
+                let (kind, name) = namespace_parts(namespace);
+                let discriminator = matches!(namespace, Namespace::Private(_)).then_some(index);
+                (kind, name.0, discriminator)
+            }),
+            namespace_sets: duplicates(&pool.namespace_sets, |_, set| {
+                set.iter().map(|namespace| namespace.0).collect::<Vec<_>>()
+            }),
+            multinames: duplicates(&pool.multinames, |_, multiname| multiname_parts(multiname)),
+        }
+    }
+}
+
+/// An instruction decoded from a method body.
+enum Instruction {
+    Op {
+        info: &'static OpInfo,
+        operands: Vec<u32>,
+        /// The absolute targets of the branch offsets.
+        targets: Vec<isize>,
+    },
+    /// A byte that couldn't be decoded as an instruction.
+    Byte(u8),
+}
+
+/// Decodes `code` into instructions, along with their offsets.
+fn decode(code: &[u8]) -> Vec<(usize, Instruction)> {
+    let mut instructions = Vec::new();
+    let mut pos = 0;
+    while pos < code.len() {
+        let start = pos;
+        match decode_op(code, &mut pos) {
+            Some(instruction) => instructions.push((start, instruction)),
+            None => {
+                // Keep whatever can't be decoded as raw bytes.
+                instructions.extend(
+                    code[start..]
+                        .iter()
+                        .enumerate()
+                        .map(|(i, byte)| (start + i, Instruction::Byte(*byte))),
+                );
+                break;
+            }
+        }
+    }
+    instructions
+}
+
+fn decode_op(code: &[u8], pos: &mut usize) -> Option<Instruction> {
+    let start = *pos;
+    let info = op_info(read_u8(code, pos)?)?;
+    let mut operands = Vec::new();
+    let mut offsets = Vec::new();
+    for operand in info.operands {
+        match operand {
+            Operand::U8 => operands.push(read_u8(code, pos)?.into()),
+            Operand::Offset => offsets.push(read_i24(code, pos)?),
+            Operand::Cases => {
+                let count = read_u30(code, pos)?;
+                operands.push(count);
+                for _ in 0..=count {
+                    offsets.push(read_i24(code, pos)?);
+                }
+            }
+            _ => operands.push(read_u30(code, pos)?),
+        }
+    }
+
+    // Branches are relative to the end of the instruction, except for
+    // `lookupswitch`, which is relative to its start.
+    let base = if info.opcode == OpCode::LookupSwitch {
+        start
+    } else {
+        *pos
+    };
+    let targets = offsets
+        .into_iter()
+        .map(|offset| base as isize + offset as isize)
+        .collect();
+    Some(Instruction::Op {
+        info,
+        operands,
+        targets,
+    })
+}
+
+/// Writes `abc_file` in the text format read by [`assemble`].
+pub fn disassemble(abc_file: &AbcFile) -> String {
+    let mut disassembler = Disassembler {
+        abc_file,
+        duplicates: Duplicates::new(&abc_file.constant_pool),
+        multiname_limit: u32::MAX,
+        out: String::new(),
+        indent: 0,
+    };
+    disassembler.abc_file();
+    disassembler.out
+}
+
+struct Disassembler<'a> {
+    abc_file: &'a AbcFile,
+    duplicates: Duplicates,
+    /// Multinames from this index on are written as `#index`.
+    multiname_limit: u32,
+    out: String,
+    indent: usize,
+}
+
+impl Disassembler<'_> {
+    /// Starts a new line at the current indentation.
+    fn line(&mut self) -> &mut String {
+        for _ in 0..self.indent {
+            self.out.push_str("    ");
+        }
+        &mut self.out
+    }
+
+    fn pool(&self) -> &ConstantPool {
+        &self.abc_file.constant_pool
+    }
+
+    /// Formats the entry of a constant pool at `index`, or `null` for index 0.
+    fn constant<T>(
+        pool: &[T],
+        duplicates: &HashSet<u32>,
+        index: u32,
+        format: impl FnOnce(&T) -> String,
+    ) -> String {
+        if index == 0 {
+            return "null".to_string();
+        }
+        match pool.get(index as usize - 1) {
+            Some(value) if !duplicates.contains(&index) => format(value),
+            _ => format!("#{index}"),
+        }
+    }
+
+    fn string(&self, index: &Index<String>) -> String {
+        let duplicates = &self.duplicates.strings;
+        Self::constant(&self.pool().strings, duplicates, index.0, |string| {
+            let mut out = String::new();
+            write_quoted(&mut out, string);
+            out
+        })
+    }
+
+    fn int(&self, index: u32) -> String {
+        let duplicates = &self.duplicates.ints;
+        Self::constant(&self.pool().ints, duplicates, index, i32::to_string)
+    }
+
+    fn uint(&self, index: u32) -> String {
+        let duplicates = &self.duplicates.uints;
+        Self::constant(&self.pool().uints, duplicates, index, u32::to_string)
+    }
+
+    fn double(&self, index: u32) -> String {
+        let duplicates = &self.duplicates.doubles;
+        Self::constant(&self.pool().doubles, duplicates, index, |value| {
+            format!("{value:?}")
+        })
+    }
+
+    fn namespace(&self, index: &Index<Namespace>) -> String {
+        let duplicates = &self.duplicates.namespaces;
+        Self::constant(&self.pool().namespaces, duplicates, index.0, |namespace| {
+            self.namespace_entry(index.0, namespace)
+        })
+    }
+
+    fn namespace_entry(&self, index: u32, namespace: &Namespace) -> String {
+        let (kind, name) = namespace_parts(namespace);
+        match namespace {
+            Namespace::Private(_) => format!("{kind}({}, {index})", self.string(name)),
+            _ => format!("{kind}({})", self.string(name)),
+        }
+    }
+
+    fn namespace_set(&self, index: &Index<NamespaceSet>) -> String {
+        let duplicates = &self.duplicates.namespace_sets;
+        Self::constant(&self.pool().namespace_sets, duplicates, index.0, |set| {
+            self.namespace_set_entry(set)
+        })
+    }
+
+    fn namespace_set_entry(&self, set: &NamespaceSet) -> String {
+        let namespaces: Vec<_> = set.iter().map(|ns| self.namespace(ns)).collect();
+        format!("[{}]", namespaces.join(", "))
+    }
+
+    fn multiname(&self, index: &Index<Multiname>) -> String {
+        self.multiname_at_depth(index, 0)
+    }
+
+    fn multiname_at_depth(&self, index: &Index<Multiname>, depth: usize) -> String {
+        // Malformed type names can refer to themselves.
+        if depth > 16 || index.0 >= self.multiname_limit {
+            return format!("#{}", index.0);
+        }
+        let duplicates = &self.duplicates.multinames;
+        Self::constant(&self.pool().multinames, duplicates, index.0, |multiname| {
+            self.multiname_entry(multiname, depth)
+        })
+    }
+
+    fn multiname_entry(&self, multiname: &Multiname, depth: usize) -> String {
+        match multiname {
+            Multiname::QName { namespace, name } => {
+                format!(
+                    "QName({}, {})",
+                    self.namespace(namespace),
+                    self.string(name)
+                )
+            }
+            Multiname::QNameA { namespace, name } => {
+                format!(
+                    "QNameA({}, {})",
+                    self.namespace(namespace),
+                    self.string(name)
+                )
+            }
+            Multiname::RTQName { name } => format!("RTQName({})", self.string(name)),
+            Multiname::RTQNameA { name } => format!("RTQNameA({})", self.string(name)),
+            Multiname::RTQNameL => "RTQNameL".to_string(),
+            Multiname::RTQNameLA => "RTQNameLA".to_string(),
+            Multiname::Multiname {
+                namespace_set,
+                name,
+            } => format!(
+                "Multiname({}, {})",
+                self.string(name),
+                self.namespace_set(namespace_set)
+            ),
+            Multiname::MultinameA {
+                namespace_set,
+                name,
+            } => format!(
+                "MultinameA({}, {})",
+                self.string(name),
+                self.namespace_set(namespace_set)
+            ),
+            Multiname::MultinameL { namespace_set } => {
+                format!("MultinameL({})", self.namespace_set(namespace_set))
+            }
+            Multiname::MultinameLA { namespace_set } => {
+                format!("MultinameLA({})", self.namespace_set(namespace_set))
+            }
+            Multiname::TypeName {
+                base_type,
+                parameters,
+            } => {
+                let parameters: Vec<_> = parameters
+                    .iter()
+                    .map(|parameter| self.multiname_at_depth(parameter, depth + 1))
+                    .collect();
+                format!(
+                    "TypeName({}<{}>)",
+                    self.multiname_at_depth(base_type, depth + 1),
+                    parameters.join(", ")
+                )
+            }
+        }
+    }
+
+    fn default_value(&self, value: &DefaultValue) -> String {
+        match value {
+            DefaultValue::Int(index) => format!("Integer({})", self.int(index.0)),
+            DefaultValue::Uint(index) => format!("UInteger({})", self.uint(index.0)),
+            DefaultValue::Double(index) => format!("Double({})", self.double(index.0)),
+            DefaultValue::String(index) => format!("Utf8({})", self.string(index)),
+            DefaultValue::True => "True".to_string(),
+            DefaultValue::False => "False".to_string(),
+            DefaultValue::Null => "Null".to_string(),
+            DefaultValue::Undefined => "Undefined".to_string(),
+            // The kind of namespace value is given by the namespace itself.
+            DefaultValue::Namespace(namespace)
+            | DefaultValue::Package(namespace)
+            | DefaultValue::PackageInternal(namespace)
+            | DefaultValue::Protected(namespace)
+            | DefaultValue::Explicit(namespace)
+            | DefaultValue::StaticProtected(namespace)
+            | DefaultValue::Private(namespace) => self.namespace(namespace),
+        }
+    }
+
+    fn abc_file(&mut self) {
+        let abc_file = self.abc_file;
+        let _ = writeln!(
+            self.out,
+            "version {} {}",
+            abc_file.major_version, abc_file.minor_version
+        );
+        self.constants();
+
+        for (i, metadata) in abc_file.metadata.iter().enumerate() {
+            let name = self.string(&metadata.name);
+            let _ = writeln!(self.out, "\nmetadata {i} {name}");
+            self.indent += 1;
+            for item in &metadata.items {
+                let (key, value) = (self.string(&item.key), self.string(&item.value));
+                let _ = writeln!(self.line(), "item {key} {value}");
+            }
+            self.indent -= 1;
+            self.out.push_str("end\n");
+        }
+
+        for (i, method) in abc_file.methods.iter().enumerate() {
+            let _ = writeln!(self.out, "\nmethod {i}");
+            self.indent += 1;
+            self.method(i, method);
+            self.indent -= 1;
+            self.out.push_str("end\n");
+        }
+
+        for (i, (instance, class)) in abc_file.instances.iter().zip(&abc_file.classes).enumerate() {
+            let _ = writeln!(self.out, "\nclass {i}");
+            self.indent += 1;
+            self.instance(instance);
+            let _ = writeln!(self.line(), "cinit {}", class.init_method.0);
+            self.traits(&class.traits);
+            self.indent -= 1;
+            self.out.push_str("end\n");
+        }
+
+        for (i, script) in abc_file.scripts.iter().enumerate() {
+            let _ = writeln!(self.out, "\nscript {i}");
+            self.indent += 1;
+            let _ = writeln!(self.line(), "sinit {}", script.init_method.0);
+            self.traits(&script.traits);
+            self.indent -= 1;
+            self.out.push_str("end\n");
+        }
+    }
+
+    fn constants(&mut self) {
+        let abc_file = self.abc_file;
+        let pool = &abc_file.constant_pool;
+        self.out.push_str("\nconstants\n");
+        self.indent += 1;
+        for value in &pool.ints {
+            let _ = writeln!(self.line(), "int {value}");
+        }
+        for value in &pool.uints {
+            let _ = writeln!(self.line(), "uint {value}");
+        }
+        for value in &pool.doubles {
+            let _ = writeln!(self.line(), "double {value:?}");
+        }
+        for value in &pool.strings {
+            self.line().push_str("string ");
+            write_quoted(&mut self.out, value);
+            self.out.push('\n');
+        }
+        for (index, namespace) in (1..).zip(&pool.namespaces) {
+            let namespace = self.namespace_entry(index, namespace);
+            let _ = writeln!(self.line(), "namespace {namespace}");
+        }
+        for set in &pool.namespace_sets {
+            let set = self.namespace_set_entry(set);
+            let _ = writeln!(self.line(), "namespaceset {set}");
+        }
+        for (index, multiname) in (1..).zip(&pool.multinames) {
+            // The multinames listed after this one aren't known yet when it's assembled.
+            self.multiname_limit = index;
+            let multiname = self.multiname_entry(multiname, 0);
+            let _ = writeln!(self.line(), "multiname {multiname}");
+        }
+        self.multiname_limit = u32::MAX;
+        self.indent -= 1;
+        self.out.push_str("end\n");
+    }
+
+    fn method(&mut self, index: usize, method: &Method) {
+        let name = self.string(&method.name);
+        let _ = writeln!(self.line(), "name {name}");
+        for param in &method.params {
+            let mut line = format!("param {}", self.multiname(&param.kind));
+            if let Some(name) = &param.name {
+                let _ = write!(line, " name {}", self.string(name));
+            }
+            if let Some(value) = &param.default_value {
+                let _ = write!(line, " default {}", self.default_value(value));
+            }
+            let _ = writeln!(self.line(), "{line}");
+        }
+        let return_type = self.multiname(&method.return_type);
+        let _ = writeln!(self.line(), "returns {return_type}");
+        if !method.flags.is_empty() {
+            let flags: Vec<_> = method.flags.iter_names().map(|(name, _)| name).collect();
+            let _ = writeln!(self.line(), "flags {}", flags.join(" "));
+        }
+
+        let bodies = &self.abc_file.method_bodies;
+        let body = match &method.body {
+            Some(body) => bodies.get(body.0 as usize),
+            None => bodies.iter().find(|body| body.method.0 as usize == index),
+        };
+        if let Some(body) = body {
+            self.line().push_str("body\n");
+            self.indent += 1;
+            self.body(body);
+            self.indent -= 1;
+            self.line().push_str("end\n");
+        }
+    }
+
+    fn body(&mut self, body: &MethodBody) {
+        let _ = writeln!(self.line(), "maxstack {}", body.max_stack);
+        let _ = writeln!(self.line(), "localcount {}", body.num_locals);
+        let _ = writeln!(self.line(), "initscopedepth {}", body.init_scope_depth);
+        let _ = writeln!(self.line(), "maxscopedepth {}", body.max_scope_depth);
+
+        let instructions = decode(&body.code);
+        let mut boundaries: BTreeSet<isize> = instructions
+            .iter()
+            .map(|(offset, _)| *offset as isize)
+            .collect();
+        boundaries.insert(body.code.len() as isize);
+
+        // Only offsets at the start of an instruction can have a label.
+        let mut labels = BTreeSet::new();
+        for (_, instruction) in &instructions {
+            if let Instruction::Op { targets, .. } = instruction {
+                labels.extend(targets.iter().filter(|t| boundaries.contains(t)));
+            }
+        }
+        for exception in &body.exceptions {
+            labels.extend(
+                [
+                    exception.from_offset,
+                    exception.to_offset,
+                    exception.target_offset,
+                ]
+                .into_iter()
+                .map(|offset| offset as isize)
+                .filter(|offset| boundaries.contains(offset)),
+            );
+        }
+        let label = |target: isize| labels.contains(&target).then(|| format!("L{target}"));
+
+        self.line().push_str("code\n");
+        for (offset, instruction) in &instructions {
+            if labels.contains(&(*offset as isize)) {
+                let _ = writeln!(self.line(), "L{offset}:");
+            }
+            self.indent += 1;
+            let text = match instruction {
+                Instruction::Op {
+                    info,
+                    operands,
+                    targets,
+                } => self.instruction(*offset, info, operands, targets, &label),
+                Instruction::Byte(byte) => format!(".byte 0x{byte:02x}"),
+            };
+            let _ = writeln!(self.line(), "{text}");
+            self.indent -= 1;
+        }
+        if labels.contains(&(body.code.len() as isize)) {
+            let _ = writeln!(self.line(), "L{}:", body.code.len());
+        }
+        self.line().push_str("end\n");
+
+        // Offsets without a label are written as they are.
+        let position = |offset: u32| label(offset as isize).unwrap_or_else(|| offset.to_string());
+        for exception in &body.exceptions {
+            let line = format!(
+                "try from {} to {} target {} type {} name {}",
+                position(exception.from_offset),
+                position(exception.to_offset),
+                position(exception.target_offset),
+                self.multiname(&exception.type_name),
+                self.multiname(&exception.variable_name),
+            );
+            let _ = writeln!(self.line(), "{line}");
+        }
+        self.traits(&body.traits);
+    }
+
+    fn instruction(
+        &self,
+        offset: usize,
+        info: &OpInfo,
+        operands: &[u32],
+        targets: &[isize],
+        label: &impl Fn(isize) -> Option<String>,
+    ) -> String {
+        // Branches without a label are written as relative offsets.
+        let end = offset as isize
+            + match info.opcode {
+                OpCode::LookupSwitch => 0,
+                _ => 4,
+            };
+        let target = |target: isize| label(target).unwrap_or_else(|| (target - end).to_string());
+
+        let mut operands = operands.iter();
+        let mut targets = targets.iter();
+        let mut text: Vec<String> = Vec::new();
+        for operand in info.operands {
+            match operand {
+                Operand::Offset => text.extend(targets.next().map(|t| target(*t))),
+                Operand::Cases => {
+                    operands.next();
+                    let cases: Vec<_> = targets.by_ref().map(|t| target(*t)).collect();
+                    text.push(format!("[{}]", cases.join(", ")));
+                }
+                operand => {
+                    let value = *operands.next().unwrap_or(&0);
+                    text.push(match operand {
+                        Operand::Int => self.int(value),
+                        Operand::UInt => self.uint(value),
+                        Operand::Double => self.double(value),
+                        Operand::String => self.string(&Index::new(value)),
+                        Operand::Namespace => self.namespace(&Index::new(value)),
+                        Operand::Multiname => self.multiname(&Index::new(value)),
+                        _ => value.to_string(),
+                    });
+                }
+            }
+        }
+
+        if text.is_empty() {
+            info.name.to_string()
+        } else {
+            format!("{} {}", info.name, text.join(", "))
+        }
+    }
+
+    fn traits(&mut self, traits: &[Trait]) {
+        for t in traits {
+            let name = self.multiname(&t.name);
+            let mut line = match &t.kind {
+                TraitKind::Slot {
+                    slot_id,
+                    type_name,
+                    value,
+                }
+                | TraitKind::Const {
+                    slot_id,
+                    type_name,
+                    value,
+                } => {
+                    let kind = match t.kind {
+                        TraitKind::Slot { .. } => "slot",
+                        _ => "const",
+                    };
+                    let mut line = format!(
+                        "trait {kind} {name} slotid {slot_id} type {}",
+                        self.multiname(type_name)
+                    );
+                    if let Some(value) = value {
+                        let _ = write!(line, " value {}", self.default_value(value));
+                    }
+                    line
+                }
+                TraitKind::Method { disp_id, method } => {
+                    format!("trait method {name} dispid {disp_id} method {}", method.0)
+                }
+                TraitKind::Getter { disp_id, method } => {
+                    format!("trait getter {name} dispid {disp_id} method {}", method.0)
+                }
+                TraitKind::Setter { disp_id, method } => {
+                    format!("trait setter {name} dispid {disp_id} method {}", method.0)
+                }
+                TraitKind::Class { slot_id, class } => {
+                    format!("trait class {name} slotid {slot_id} class {}", class.0)
+                }
+                TraitKind::Function { slot_id, function } => {
+                    format!(
+                        "trait function {name} slotid {slot_id} method {}",
+                        function.0
+                    )
+                }
+            };
+            if t.is_final {
+                line.push_str(" final");
+            }
+            if t.is_override {
+                line.push_str(" override");
+            }
+            for metadata in &t.metadata {
+                let _ = write!(line, " metadata {}", metadata.0);
+            }
+            let _ = writeln!(self.line(), "{line}");
+        }
+    }
+
+    fn instance(&mut self, instance: &Instance) {
+        let name = self.multiname(&instance.name);
+        let _ = writeln!(self.line(), "instance {name}");
+        self.indent += 1;
+        let super_name = self.multiname(&instance.super_name);
+        let _ = writeln!(self.line(), "extends {super_name}");
+        let flags: Vec<_> = [
+            (instance.is_sealed, "SEALED"),
+            (instance.is_final, "FINAL"),
+            (instance.is_interface, "INTERFACE"),
+        ]
+        .into_iter()
+        .filter_map(|(set, name)| set.then_some(name))
+        .collect();
+        if !flags.is_empty() {
+            let _ = writeln!(self.line(), "flags {}", flags.join(" "));
+        }
+        if let Some(namespace) = &instance.protected_namespace {
+            let namespace = self.namespace(namespace);
+            let _ = writeln!(self.line(), "protectedns {namespace}");
+        }
+        for interface in &instance.interfaces {
+            let interface = self.multiname(interface);
+            let _ = writeln!(self.line(), "implements {interface}");
+        }
+        let _ = writeln!(self.line(), "iinit {}", instance.init_method.0);
+        self.traits(&instance.traits);
+        self.indent -= 1;
+        self.line().push_str("end\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::read::tests::read_abc_from_file;
+    use crate::avm2::read::Reader;
+
+    #[test]
+    fn disassemble_code() {
+        let code = vec![
+            0xd0, // getlocal0
+            0x30, // pushscope
+            0x24, 0x05, // pushbyte 5
+            0x11, 0x04, 0x00, 0x00, // iftrue +4
+            0x10, 0xf6, 0xff, 0xff, // jump -10
+            0x10, 0xfb, 0xff, 0xff, // jump -5
+            0x47, // returnvoid
+            0xff, // unknown
+        ];
+        let instructions: Vec<_> = decode(&code)
+            .into_iter()
+            .map(|(offset, _)| offset)
+            .collect();
+        assert_eq!(instructions, vec![0, 1, 2, 4, 8, 12, 16, 17]);
+
+        let mut abc_file = Reader::new(&read_abc_from_file("tests/swfs/Avm2Dummy.swf"))
+            .read()
+            .unwrap();
+        abc_file.method_bodies[0].code = code;
+        abc_file.method_bodies[0].exceptions.clear();
+        let text = disassemble(&abc_file);
+        assert!(
+            text.contains(concat!(
+                "        code\n",
+                "            getlocal0\n",
+                "            pushscope\n",
+                "        L2:\n",
+                "            pushbyte 5\n",
+                "            iftrue L12\n",
+                "            jump L2\n",
+                "        L12:\n",
+                "            jump -5\n",
+                "            returnvoid\n",
+                "            .byte 0xff\n",
+                "        end\n",
+            )),
+            "{text}"
+        );
+    }
+}
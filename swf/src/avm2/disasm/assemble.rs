@@ -0,0 +1,1321 @@
+//! Parses the text format written by [`disassemble`](super::disassemble).
+
+use super::{multiname_parts, namespace_parts, Operand, OPS};
+use crate::avm2::opcode::OpCode;
+use crate::avm2::types::*;
+use crate::error::{Error, Result};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+
+fn error(line: usize, message: impl Display) -> Error {
+    Error::invalid_data(format!("Line {line}: {message}"))
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Number(String),
+    Str(Vec<u8>),
+    Punct(char),
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Word(word) | Token::Number(word) => write!(f, "`{word}`"),
+            Token::Str(_) => write!(f, "a string"),
+            Token::Punct(c) => write!(f, "`{c}`"),
+        }
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$' || c == '.'
+}
+
+/// Splits `text` into tokens, along with their line numbers.
+fn tokenize(text: &str) -> Result<Vec<(Token, usize)>> {
+    let mut tokens = Vec::new();
+    for (line, text) in text.lines().enumerate() {
+        let line = line + 1;
+        let mut chars = text.chars().peekable();
+        while let Some(&c) = chars.peek() {
+            let token = match c {
+                // Comments run until the end of the line.
+                ';' => break,
+                c if c.is_whitespace() => {
+                    chars.next();
+                    continue;
+                }
+                '"' => {
+                    chars.next();
+                    Token::Str(
+                        read_string(&mut chars).ok_or_else(|| error(line, "Invalid string"))?,
+                    )
+                }
+                '(' | ')' | '[' | ']' | '<' | '>' | ',' | ':' | '#' => {
+                    chars.next();
+                    Token::Punct(c)
+                }
+                c if c.is_ascii_digit() || c == '-' || c == '+' => {
+                    let mut number = String::new();
+                    while let Some(&c) = chars.peek() {
+                        let exponent_sign = (c == '-' || c == '+')
+                            && (number.is_empty() || number.ends_with(['e', 'E']));
+                        if !(c.is_alphanumeric() || c == '.' || exponent_sign) {
+                            break;
+                        }
+                        number.push(c);
+                        chars.next();
+                    }
+                    Token::Number(number)
+                }
+                c if is_word_char(c) => {
+                    let mut word = String::new();
+                    while let Some(&c) = chars.peek().filter(|c| is_word_char(**c)) {
+                        word.push(c);
+                        chars.next();
+                    }
+                    Token::Word(word)
+                }
+                c => return Err(error(line, format!("Unexpected character `{c}`"))),
+            };
+            tokens.push((token, line));
+        }
+    }
+    Ok(tokens)
+}
+
+/// Reads a string after its opening quote, returning `None` if it's invalid.
+fn read_string(chars: &mut impl Iterator<Item = char>) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(bytes),
+            '\\' => match chars.next()? {
+                '"' => bytes.push(b'"'),
+                '\\' => bytes.push(b'\\'),
+                'n' => bytes.push(b'\n'),
+                'r' => bytes.push(b'\r'),
+                't' => bytes.push(b'\t'),
+                'x' => {
+                    let hex: String = [chars.next()?, chars.next()?].into_iter().collect();
+                    bytes.push(u8::from_str_radix(&hex, 16).ok()?);
+                }
+                _ => return None,
+            },
+            c => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+        }
+    }
+}
+
+fn write_u30(code: &mut Vec<u8>, mut n: u32) {
+    loop {
+        let byte = (n as u8) & 0x7f;
+        n >>= 7;
+        if n == 0 {
+            code.push(byte);
+            break;
+        }
+        code.push(0x80 | byte);
+    }
+}
+
+/// Builds a constant pool, reusing the entries that were already added.
+struct PoolBuilder {
+    pool: ConstantPool,
+    strings: HashMap<Vec<u8>, u32>,
+    ints: HashMap<i32, u32>,
+    uints: HashMap<u32, u32>,
+    doubles: HashMap<u64, u32>,
+    namespaces: HashMap<(&'static str, u32, Option<u32>), u32>,
+    namespace_sets: HashMap<Vec<u32>, u32>,
+    multinames: HashMap<(&'static str, Vec<u32>), u32>,
+}
+
+/// How an entry is added to a constant pool.
+#[derive(Clone, Copy)]
+enum Entry {
+    /// An equal entry that was already added is reused.
+    Interned,
+    /// The entry is added even if there's an equal one, as listed in the
+    /// `constants` section.
+    Defined,
+}
+
+/// Returns the index of the entry with `key`, adding it to `pool` if needed.
+fn add<K: Eq + Hash, T>(
+    indices: &mut HashMap<K, u32>,
+    pool: &mut Vec<T>,
+    key: K,
+    value: T,
+    entry: Entry,
+) -> u32 {
+    if let (Entry::Interned, Some(index)) = (entry, indices.get(&key)) {
+        return *index;
+    }
+    pool.push(value);
+    let index = pool.len() as u32;
+    // References by value keep referring to the first of equal entries.
+    indices.entry(key).or_insert(index);
+    index
+}
+
+impl PoolBuilder {
+    fn new() -> Self {
+        Self {
+            pool: ConstantPool {
+                ints: Vec::new(),
+                uints: Vec::new(),
+                doubles: Vec::new(),
+                strings: Vec::new(),
+                namespaces: Vec::new(),
+                namespace_sets: Vec::new(),
+                multinames: Vec::new(),
+            },
+            strings: HashMap::new(),
+            ints: HashMap::new(),
+            uints: HashMap::new(),
+            doubles: HashMap::new(),
+            namespaces: HashMap::new(),
+            namespace_sets: HashMap::new(),
+            multinames: HashMap::new(),
+        }
+    }
+
+    fn string(&mut self, string: Vec<u8>, entry: Entry) -> u32 {
+        let pool = &mut self.pool.strings;
+        add(&mut self.strings, pool, string.clone(), string, entry)
+    }
+
+    fn int(&mut self, value: i32, entry: Entry) -> u32 {
+        add(&mut self.ints, &mut self.pool.ints, value, value, entry)
+    }
+
+    fn uint(&mut self, value: u32, entry: Entry) -> u32 {
+        add(&mut self.uints, &mut self.pool.uints, value, value, entry)
+    }
+
+    fn double(&mut self, value: f64, entry: Entry) -> u32 {
+        let pool = &mut self.pool.doubles;
+        add(&mut self.doubles, pool, value.to_bits(), value, entry)
+    }
+
+    fn namespace(&mut self, namespace: Namespace, discriminator: Option<u32>, entry: Entry) -> u32 {
+        let (kind, name) = namespace_parts(&namespace);
+        let key = (kind, name.0, discriminator);
+        let pool = &mut self.pool.namespaces;
+        add(&mut self.namespaces, pool, key, namespace, entry)
+    }
+
+    fn namespace_set(&mut self, set: NamespaceSet, entry: Entry) -> u32 {
+        let key = set.iter().map(|namespace| namespace.0).collect();
+        let pool = &mut self.pool.namespace_sets;
+        add(&mut self.namespace_sets, pool, key, set, entry)
+    }
+
+    fn multiname(&mut self, multiname: Multiname, entry: Entry) -> u32 {
+        let key = multiname_parts(&multiname);
+        let pool = &mut self.pool.multinames;
+        add(&mut self.multinames, pool, key, multiname, entry)
+    }
+}
+
+/// A code offset, given by a label or as a number.
+enum Position {
+    Label { name: String, line: usize },
+    Offset(i64),
+}
+
+/// A branch offset to fill in once its label is known.
+struct Fixup {
+    /// Where the offset is written in the code.
+    pos: usize,
+    /// The position that the offset is relative to.
+    base: usize,
+    label: String,
+    line: usize,
+}
+
+/// Parses an ABC file from the text format written by [`disassemble`](super::disassemble).
+pub fn assemble(text: &str) -> Result<AbcFile> {
+    let parser = Parser {
+        tokens: tokenize(text)?,
+        pos: 0,
+        pool: PoolBuilder::new(),
+    };
+    parser.abc_file()
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    pool: PoolBuilder,
+}
+
+impl Parser {
+    /// The line of the last token read.
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.pos.saturating_sub(1))
+            .map_or(1, |(_, line)| *line)
+    }
+
+    fn error(&self, message: impl Display) -> Error {
+        error(self.line(), message)
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    /// Whether the next token is on `line`, for the optional parts of a line.
+    fn on_line(&self, line: usize) -> bool {
+        self.tokens
+            .get(self.pos)
+            .is_some_and(|(_, token_line)| *token_line == line)
+    }
+
+    fn next(&mut self) -> Result<Token> {
+        let token = self.peek().cloned();
+        match token {
+            Some(token) => {
+                self.pos += 1;
+                Ok(token)
+            }
+            None => Err(self.error("Unexpected end of input")),
+        }
+    }
+
+    fn word(&mut self) -> Result<String> {
+        match self.next()? {
+            Token::Word(word) => Ok(word),
+            token => Err(self.error(format!("Expected a keyword, found {token}"))),
+        }
+    }
+
+    fn eat_word(&mut self, word: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Word(w)) if w == word);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect_word(&mut self, word: &str) -> Result<()> {
+        match self.next()? {
+            Token::Word(w) if w == word => Ok(()),
+            token => Err(self.error(format!("Expected `{word}`, found {token}"))),
+        }
+    }
+
+    fn eat_punct(&mut self, c: char) -> bool {
+        let found = self.peek() == Some(&Token::Punct(c));
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect_punct(&mut self, c: char) -> Result<()> {
+        match self.next()? {
+            Token::Punct(p) if p == c => Ok(()),
+            token => Err(self.error(format!("Expected `{c}`, found {token}"))),
+        }
+    }
+
+    /// Parses a list of items separated by commas, after its opening bracket.
+    fn list<T>(
+        &mut self,
+        close: char,
+        mut item: impl FnMut(&mut Self) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+        if self.eat_punct(close) {
+            return Ok(items);
+        }
+        loop {
+            items.push(item(self)?);
+            if self.eat_punct(close) {
+                return Ok(items);
+            }
+            self.expect_punct(',')?;
+        }
+    }
+
+    fn integer(&mut self) -> Result<i64> {
+        let Token::Number(number) = self.next()? else {
+            return Err(self.error("Expected a number"));
+        };
+        let (negative, digits) = match number.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, number.strip_prefix('+').unwrap_or(&number)),
+        };
+        let value = match digits.strip_prefix("0x") {
+            Some(hex) => i64::from_str_radix(hex, 16),
+            None => digits.parse(),
+        }
+        .map_err(|_| self.error(format!("Invalid integer `{number}`")))?;
+        Ok(if negative { -value } else { value })
+    }
+
+    fn number<T: TryFrom<i64>>(&mut self) -> Result<T> {
+        let value = self.integer()?;
+        T::try_from(value).map_err(|_| self.error(format!("{value} is out of range")))
+    }
+
+    fn index<T>(&mut self) -> Result<Index<T>> {
+        Ok(Index::new(self.number()?))
+    }
+
+    /// Parses `null` or `#index`, which are used as they are instead of a constant.
+    fn raw_index(&mut self) -> Result<Option<u32>> {
+        if self.eat_word("null") {
+            return Ok(Some(0));
+        }
+        if self.eat_punct('#') {
+            return Ok(Some(self.number()?));
+        }
+        Ok(None)
+    }
+
+    fn string(&mut self) -> Result<Index<String>> {
+        if let Some(index) = self.raw_index()? {
+            return Ok(Index::new(index));
+        }
+        let string = self.string_value()?;
+        Ok(Index::new(self.pool.string(string, Entry::Interned)))
+    }
+
+    fn string_value(&mut self) -> Result<Vec<u8>> {
+        match self.next()? {
+            Token::Str(string) => Ok(string),
+            token => Err(self.error(format!("Expected a string, found {token}"))),
+        }
+    }
+
+    fn int(&mut self) -> Result<u32> {
+        match self.raw_index()? {
+            Some(index) => Ok(index),
+            None => {
+                let value = self.number()?;
+                Ok(self.pool.int(value, Entry::Interned))
+            }
+        }
+    }
+
+    fn uint(&mut self) -> Result<u32> {
+        match self.raw_index()? {
+            Some(index) => Ok(index),
+            None => {
+                let value = self.number()?;
+                Ok(self.pool.uint(value, Entry::Interned))
+            }
+        }
+    }
+
+    fn double(&mut self) -> Result<u32> {
+        if let Some(index) = self.raw_index()? {
+            return Ok(index);
+        }
+        let value = self.double_value()?;
+        Ok(self.pool.double(value, Entry::Interned))
+    }
+
+    fn double_value(&mut self) -> Result<f64> {
+        let (Token::Number(number) | Token::Word(number)) = self.next()? else {
+            return Err(self.error("Expected a number"));
+        };
+        number
+            .parse()
+            .map_err(|_| self.error(format!("Invalid number `{number}`")))
+    }
+
+    fn namespace(&mut self) -> Result<Index<Namespace>> {
+        if let Some(index) = self.raw_index()? {
+            return Ok(Index::new(index));
+        }
+        let (namespace, discriminator) = self.namespace_value()?;
+        Ok(Index::new(self.pool.namespace(
+            namespace,
+            discriminator,
+            Entry::Interned,
+        )))
+    }
+
+    /// Parses a namespace, along with the index that tells private namespaces apart.
+    fn namespace_value(&mut self) -> Result<(Namespace, Option<u32>)> {
+        let kind = self.word()?;
+        let namespace: fn(Index<String>) -> Namespace = match kind.as_str() {
+            "Namespace" => Namespace::Namespace,
+            "PackageNamespace" => Namespace::Package,
+            "PackageInternalNs" => Namespace::PackageInternal,
+            "ProtectedNamespace" => Namespace::Protected,
+            "ExplicitNamespace" => Namespace::Explicit,
+            "StaticProtectedNs" => Namespace::StaticProtected,
+            "PrivateNamespace" => Namespace::Private,
+            _ => return Err(self.error(format!("Unknown namespace kind `{kind}`"))),
+        };
+        self.expect_punct('(')?;
+        let name = self.string()?;
+        let discriminator = if self.eat_punct(',') {
+            Some(self.number()?)
+        } else {
+            None
+        };
+        self.expect_punct(')')?;
+        Ok((namespace(name), discriminator))
+    }
+
+    fn namespace_set(&mut self) -> Result<Index<NamespaceSet>> {
+        if let Some(index) = self.raw_index()? {
+            return Ok(Index::new(index));
+        }
+        let set = self.namespace_set_value()?;
+        Ok(Index::new(self.pool.namespace_set(set, Entry::Interned)))
+    }
+
+    fn namespace_set_value(&mut self) -> Result<NamespaceSet> {
+        self.expect_punct('[')?;
+        self.list(']', Self::namespace)
+    }
+
+    fn multiname(&mut self) -> Result<Index<Multiname>> {
+        if let Some(index) = self.raw_index()? {
+            return Ok(Index::new(index));
+        }
+        let multiname = self.multiname_value()?;
+        Ok(Index::new(self.pool.multiname(multiname, Entry::Interned)))
+    }
+
+    fn multiname_value(&mut self) -> Result<Multiname> {
+        let kind = self.word()?;
+        let multiname = match kind.as_str() {
+            "QName" | "QNameA" => {
+                self.expect_punct('(')?;
+                let namespace = self.namespace()?;
+                self.expect_punct(',')?;
+                let name = self.string()?;
+                self.expect_punct(')')?;
+                match kind.as_str() {
+                    "QName" => Multiname::QName { namespace, name },
+                    _ => Multiname::QNameA { namespace, name },
+                }
+            }
+            "RTQName" | "RTQNameA" => {
+                self.expect_punct('(')?;
+                let name = self.string()?;
+                self.expect_punct(')')?;
+                match kind.as_str() {
+                    "RTQName" => Multiname::RTQName { name },
+                    _ => Multiname::RTQNameA { name },
+                }
+            }
+            "RTQNameL" => Multiname::RTQNameL,
+            "RTQNameLA" => Multiname::RTQNameLA,
+            "Multiname" | "MultinameA" => {
+                self.expect_punct('(')?;
+                let name = self.string()?;
+                self.expect_punct(',')?;
+                let namespace_set = self.namespace_set()?;
+                self.expect_punct(')')?;
+                match kind.as_str() {
+                    "Multiname" => Multiname::Multiname {
+                        namespace_set,
+                        name,
+                    },
+                    _ => Multiname::MultinameA {
+                        namespace_set,
+                        name,
+                    },
+                }
+            }
+            "MultinameL" | "MultinameLA" => {
+                self.expect_punct('(')?;
+                let namespace_set = self.namespace_set()?;
+                self.expect_punct(')')?;
+                match kind.as_str() {
+                    "MultinameL" => Multiname::MultinameL { namespace_set },
+                    _ => Multiname::MultinameLA { namespace_set },
+                }
+            }
+            "TypeName" => {
+                self.expect_punct('(')?;
+                let base_type = self.multiname()?;
+                self.expect_punct('<')?;
+                let parameters = self.list('>', Self::multiname)?;
+                self.expect_punct(')')?;
+                Multiname::TypeName {
+                    base_type,
+                    parameters,
+                }
+            }
+            _ => return Err(self.error(format!("Unknown multiname kind `{kind}`"))),
+        };
+        Ok(multiname)
+    }
+
+    fn default_value(&mut self) -> Result<DefaultValue> {
+        let value = match self.word()?.as_str() {
+            "True" => DefaultValue::True,
+            "False" => DefaultValue::False,
+            "Null" => DefaultValue::Null,
+            "Undefined" => DefaultValue::Undefined,
+            kind @ ("Integer" | "UInteger" | "Double" | "Utf8") => {
+                self.expect_punct('(')?;
+                let value = match kind {
+                    "Integer" => DefaultValue::Int(Index::new(self.int()?)),
+                    "UInteger" => DefaultValue::Uint(Index::new(self.uint()?)),
+                    "Double" => DefaultValue::Double(Index::new(self.double()?)),
+                    _ => DefaultValue::String(self.string()?),
+                };
+                self.expect_punct(')')?;
+                value
+            }
+            _ => {
+                // Anything else is a namespace, which gives the kind of the value.
+                self.pos -= 1;
+                let index = self.namespace()?;
+                let namespace = (index.0 as usize)
+                    .checked_sub(1)
+                    .and_then(|i| self.pool.pool.namespaces.get(i));
+                match namespace {
+                    Some(Namespace::Package(_)) => DefaultValue::Package(index),
+                    Some(Namespace::PackageInternal(_)) => DefaultValue::PackageInternal(index),
+                    Some(Namespace::Protected(_)) => DefaultValue::Protected(index),
+                    Some(Namespace::Explicit(_)) => DefaultValue::Explicit(index),
+                    Some(Namespace::StaticProtected(_)) => DefaultValue::StaticProtected(index),
+                    Some(Namespace::Private(_)) => DefaultValue::Private(index),
+                    Some(Namespace::Namespace(_)) | None => DefaultValue::Namespace(index),
+                }
+            }
+        };
+        Ok(value)
+    }
+
+    fn position(&mut self) -> Result<Position> {
+        match self.peek() {
+            Some(Token::Word(_)) => Ok(Position::Label {
+                name: self.word()?,
+                line: self.line(),
+            }),
+            _ => Ok(Position::Offset(self.integer()?)),
+        }
+    }
+
+    fn abc_file(mut self) -> Result<AbcFile> {
+        self.expect_word("version")?;
+        let major_version = self.number()?;
+        let minor_version = self.number()?;
+        if self.eat_word("constants") {
+            self.constants()?;
+        }
+
+        let mut methods = Vec::new();
+        let mut metadata = Vec::new();
+        let mut instances = Vec::new();
+        let mut classes = Vec::new();
+        let mut scripts = Vec::new();
+        let mut method_bodies = Vec::new();
+        while self.peek().is_some() {
+            let section = self.word()?;
+            let index: usize = self.number()?;
+            let expected = match section.as_str() {
+                "metadata" => metadata.len(),
+                "method" => methods.len(),
+                "class" => classes.len(),
+                "script" => scripts.len(),
+                _ => return Err(self.error(format!("Unknown section `{section}`"))),
+            };
+            if index != expected {
+                return Err(self.error(format!("Expected {section} {expected}, found {index}")));
+            }
+
+            match section.as_str() {
+                "metadata" => metadata.push(self.metadata()?),
+                "method" => {
+                    let (mut method, body) = self.method(index as u32)?;
+                    if let Some(body) = body {
+                        method.body = Some(Index::new(method_bodies.len() as u32));
+                        method_bodies.push(body);
+                    }
+                    methods.push(method);
+                }
+                "class" => {
+                    let (instance, class) = self.class()?;
+                    instances.push(instance);
+                    classes.push(class);
+                }
+                _ => scripts.push(self.script()?),
+            }
+        }
+
+        Ok(AbcFile {
+            major_version,
+            minor_version,
+            constant_pool: self.pool.pool,
+            methods,
+            metadata,
+            instances,
+            classes,
+            scripts,
+            method_bodies,
+        })
+    }
+
+    /// Adds the entries of the `constants` section to the constant pool, in order.
+    fn constants(&mut self) -> Result<()> {
+        loop {
+            match self.word()?.as_str() {
+                "end" => return Ok(()),
+                "int" => {
+                    let value = self.number()?;
+                    self.pool.int(value, Entry::Defined);
+                }
+                "uint" => {
+                    let value = self.number()?;
+                    self.pool.uint(value, Entry::Defined);
+                }
+                "double" => {
+                    let value = self.double_value()?;
+                    self.pool.double(value, Entry::Defined);
+                }
+                "string" => {
+                    let string = self.string_value()?;
+                    self.pool.string(string, Entry::Defined);
+                }
+                "namespace" => {
+                    let (namespace, discriminator) = self.namespace_value()?;
+                    self.pool
+                        .namespace(namespace, discriminator, Entry::Defined);
+                }
+                "namespaceset" => {
+                    let set = self.namespace_set_value()?;
+                    self.pool.namespace_set(set, Entry::Defined);
+                }
+                "multiname" => {
+                    let multiname = self.multiname_value()?;
+                    self.pool.multiname(multiname, Entry::Defined);
+                }
+                word => return Err(self.error(format!("Unexpected `{word}` in constants"))),
+            }
+        }
+    }
+
+    fn metadata(&mut self) -> Result<Metadata> {
+        let name = self.string()?;
+        let mut items = Vec::new();
+        while !self.eat_word("end") {
+            self.expect_word("item")?;
+            let key = self.string()?;
+            let value = self.string()?;
+            items.push(MetadataItem { key, value });
+        }
+        Ok(Metadata { name, items })
+    }
+
+    fn method(&mut self, index: u32) -> Result<(Method, Option<MethodBody>)> {
+        let mut method = Method {
+            name: Index::new(0),
+            params: Vec::new(),
+            return_type: Index::new(0),
+            flags: MethodFlags::empty(),
+            body: None,
+        };
+        let mut body = None;
+        loop {
+            let line = self.line();
+            match self.word()?.as_str() {
+                "end" => break,
+                "name" => method.name = self.string()?,
+                "param" => {
+                    let line = self.line();
+                    let mut param = MethodParam {
+                        name: None,
+                        kind: self.multiname()?,
+                        default_value: None,
+                    };
+                    while self.on_line(line) {
+                        match self.word()?.as_str() {
+                            "name" => param.name = Some(self.string()?),
+                            "default" => param.default_value = Some(self.default_value()?),
+                            word => return Err(self.error(format!("Unexpected `{word}`"))),
+                        }
+                    }
+                    method.params.push(param);
+                }
+                "returns" => method.return_type = self.multiname()?,
+                "flags" => {
+                    while self.on_line(self.line()) {
+                        let name = self.word()?;
+                        method.flags |= MethodFlags::from_name(&name)
+                            .ok_or_else(|| self.error(format!("Unknown method flag `{name}`")))?;
+                    }
+                }
+                "body" => body = Some(self.body(index)?),
+                word => return Err(error(line, format!("Unexpected `{word}` in method"))),
+            }
+        }
+        Ok((method, body))
+    }
+
+    fn body(&mut self, method: u32) -> Result<MethodBody> {
+        let mut body = MethodBody {
+            method: Index::new(method),
+            max_stack: 0,
+            num_locals: 0,
+            init_scope_depth: 0,
+            max_scope_depth: 0,
+            code: Vec::new(),
+            exceptions: Vec::new(),
+            traits: Vec::new(),
+        };
+        let mut labels = HashMap::new();
+        let mut exceptions = Vec::new();
+        loop {
+            match self.word()?.as_str() {
+                "end" => break,
+                "maxstack" => body.max_stack = self.number()?,
+                "localcount" => body.num_locals = self.number()?,
+                "initscopedepth" => body.init_scope_depth = self.number()?,
+                "maxscopedepth" => body.max_scope_depth = self.number()?,
+                "code" => labels = self.code(&mut body.code)?,
+                "try" => {
+                    self.expect_word("from")?;
+                    let from = self.position()?;
+                    self.expect_word("to")?;
+                    let to = self.position()?;
+                    self.expect_word("target")?;
+                    let target = self.position()?;
+                    self.expect_word("type")?;
+                    let type_name = self.multiname()?;
+                    self.expect_word("name")?;
+                    let variable_name = self.multiname()?;
+                    exceptions.push(([from, to, target], type_name, variable_name));
+                }
+                "trait" => body.traits.push(self.trait_()?),
+                word => return Err(self.error(format!("Unexpected `{word}` in method body"))),
+            }
+        }
+
+        // Exceptions can only be resolved once the labels of the code are known.
+        for (positions, type_name, variable_name) in exceptions {
+            let mut offsets = [0; 3];
+            for (offset, position) in offsets.iter_mut().zip(positions) {
+                *offset = match position {
+                    Position::Label { name, line } => *labels
+                        .get(&name)
+                        .ok_or_else(|| error(line, format!("Unknown label `{name}`")))?
+                        as u32,
+                    Position::Offset(value) => u32::try_from(value)
+                        .map_err(|_| self.error(format!("Invalid offset {value}")))?,
+                };
+            }
+            let [from_offset, to_offset, target_offset] = offsets;
+            body.exceptions.push(Exception {
+                from_offset,
+                to_offset,
+                target_offset,
+                variable_name,
+                type_name,
+            });
+        }
+        Ok(body)
+    }
+
+    /// Assembles instructions until `end`, returning the offsets of the labels.
+    fn code(&mut self, code: &mut Vec<u8>) -> Result<HashMap<String, usize>> {
+        let mut labels = HashMap::new();
+        let mut fixups = Vec::new();
+        loop {
+            let name = self.word()?;
+            if name == "end" {
+                break;
+            }
+            if self.eat_punct(':') {
+                if labels.insert(name.clone(), code.len()).is_some() {
+                    return Err(self.error(format!("Duplicate label `{name}`")));
+                }
+                continue;
+            }
+            if name == ".byte" {
+                code.push(self.number()?);
+                continue;
+            }
+
+            let info = OPS
+                .iter()
+                .find(|info| info.name == name)
+                .ok_or_else(|| self.error(format!("Unknown instruction `{name}`")))?;
+            let start = code.len();
+            code.push(info.opcode as u8);
+            for (i, operand) in info.operands.iter().enumerate() {
+                if i > 0 {
+                    self.expect_punct(',')?;
+                }
+                match operand {
+                    Operand::U8 => code.push(self.number()?),
+                    Operand::U30 => write_u30(code, self.number()?),
+                    Operand::Offset => {
+                        // Branches are relative to the end of the instruction, except
+                        // for `lookupswitch`, which is relative to its start.
+                        let base = match info.opcode {
+                            OpCode::LookupSwitch => start,
+                            _ => code.len() + 3,
+                        };
+                        let position = self.position()?;
+                        self.offset(code, base, position, &mut fixups)?;
+                    }
+                    Operand::Cases => {
+                        self.expect_punct('[')?;
+                        let cases = self.list(']', Self::position)?;
+                        if cases.is_empty() {
+                            return Err(self.error("`lookupswitch` needs at least one case"));
+                        }
+                        write_u30(code, cases.len() as u32 - 1);
+                        for position in cases {
+                            self.offset(code, start, position, &mut fixups)?;
+                        }
+                    }
+                    Operand::Int => write_u30(code, self.int()?),
+                    Operand::UInt => write_u30(code, self.uint()?),
+                    Operand::Double => write_u30(code, self.double()?),
+                    Operand::String => write_u30(code, self.string()?.0),
+                    Operand::Namespace => write_u30(code, self.namespace()?.0),
+                    Operand::Multiname => write_u30(code, self.multiname()?.0),
+                }
+            }
+        }
+
+        for fixup in fixups {
+            let target = *labels
+                .get(&fixup.label)
+                .ok_or_else(|| error(fixup.line, format!("Unknown label `{}`", fixup.label)))?;
+            let offset = target as i64 - fixup.base as i64;
+            let bytes = i24_bytes(offset)
+                .ok_or_else(|| error(fixup.line, format!("Label `{}` is too far", fixup.label)))?;
+            code[fixup.pos..fixup.pos + 3].copy_from_slice(&bytes);
+        }
+        Ok(labels)
+    }
+
+    /// Writes a branch offset, or a placeholder to fix up if it's given by a label.
+    fn offset(
+        &self,
+        code: &mut Vec<u8>,
+        base: usize,
+        position: Position,
+        fixups: &mut Vec<Fixup>,
+    ) -> Result<()> {
+        match position {
+            Position::Label { name, line } => {
+                fixups.push(Fixup {
+                    pos: code.len(),
+                    base,
+                    label: name,
+                    line,
+                });
+                code.extend_from_slice(&[0; 3]);
+            }
+            Position::Offset(offset) => {
+                let bytes = i24_bytes(offset)
+                    .ok_or_else(|| self.error(format!("Offset {offset} is out of range")))?;
+                code.extend_from_slice(&bytes);
+            }
+        }
+        Ok(())
+    }
+
+    fn trait_(&mut self) -> Result<Trait> {
+        let line = self.line();
+        let kind = self.word()?;
+        let name = self.multiname()?;
+        let kind = match kind.as_str() {
+            "slot" | "const" => {
+                self.expect_word("slotid")?;
+                let slot_id = self.number()?;
+                self.expect_word("type")?;
+                let type_name = self.multiname()?;
+                let value = if self.on_line(line) && self.eat_word("value") {
+                    Some(self.default_value()?)
+                } else {
+                    None
+                };
+                match kind.as_str() {
+                    "slot" => TraitKind::Slot {
+                        slot_id,
+                        type_name,
+                        value,
+                    },
+                    _ => TraitKind::Const {
+                        slot_id,
+                        type_name,
+                        value,
+                    },
+                }
+            }
+            "method" | "getter" | "setter" => {
+                self.expect_word("dispid")?;
+                let disp_id = self.number()?;
+                self.expect_word("method")?;
+                let method = self.index()?;
+                match kind.as_str() {
+                    "method" => TraitKind::Method { disp_id, method },
+                    "getter" => TraitKind::Getter { disp_id, method },
+                    _ => TraitKind::Setter { disp_id, method },
+                }
+            }
+            "class" => {
+                self.expect_word("slotid")?;
+                let slot_id = self.number()?;
+                self.expect_word("class")?;
+                let class = self.index()?;
+                TraitKind::Class { slot_id, class }
+            }
+            "function" => {
+                self.expect_word("slotid")?;
+                let slot_id = self.number()?;
+                self.expect_word("method")?;
+                let function = self.index()?;
+                TraitKind::Function { slot_id, function }
+            }
+            _ => return Err(self.error(format!("Unknown trait kind `{kind}`"))),
+        };
+
+        let mut result = Trait {
+            name,
+            kind,
+            metadata: Vec::new(),
+            is_final: false,
+            is_override: false,
+        };
+        while self.on_line(line) {
+            match self.word()?.as_str() {
+                "final" => result.is_final = true,
+                "override" => result.is_override = true,
+                "metadata" => result.metadata.push(self.index()?),
+                word => return Err(self.error(format!("Unexpected `{word}` in trait"))),
+            }
+        }
+        Ok(result)
+    }
+
+    fn class(&mut self) -> Result<(Instance, Class)> {
+        self.expect_word("instance")?;
+        let mut instance = Instance {
+            name: self.multiname()?,
+            super_name: Index::new(0),
+            is_sealed: false,
+            is_final: false,
+            is_interface: false,
+            protected_namespace: None,
+            interfaces: Vec::new(),
+            init_method: Index::new(0),
+            traits: Vec::new(),
+        };
+        loop {
+            match self.word()?.as_str() {
+                "end" => break,
+                "extends" => instance.super_name = self.multiname()?,
+                "flags" => {
+                    while self.on_line(self.line()) {
+                        match self.word()?.as_str() {
+                            "SEALED" => instance.is_sealed = true,
+                            "FINAL" => instance.is_final = true,
+                            "INTERFACE" => instance.is_interface = true,
+                            flag => return Err(self.error(format!("Unknown class flag `{flag}`"))),
+                        }
+                    }
+                }
+                "protectedns" => instance.protected_namespace = Some(self.namespace()?),
+                "implements" => instance.interfaces.push(self.multiname()?),
+                "iinit" => instance.init_method = self.index()?,
+                "trait" => instance.traits.push(self.trait_()?),
+                word => return Err(self.error(format!("Unexpected `{word}` in instance"))),
+            }
+        }
+
+        let mut class = Class {
+            init_method: Index::new(0),
+            traits: Vec::new(),
+        };
+        loop {
+            match self.word()?.as_str() {
+                "end" => break,
+                "cinit" => class.init_method = self.index()?,
+                "trait" => class.traits.push(self.trait_()?),
+                word => return Err(self.error(format!("Unexpected `{word}` in class"))),
+            }
+        }
+        Ok((instance, class))
+    }
+
+    fn script(&mut self) -> Result<Script> {
+        let mut script = Script {
+            init_method: Index::new(0),
+            traits: Vec::new(),
+        };
+        loop {
+            match self.word()?.as_str() {
+                "end" => break,
+                "sinit" => script.init_method = self.index()?,
+                "trait" => script.traits.push(self.trait_()?),
+                word => return Err(self.error(format!("Unexpected `{word}` in script"))),
+            }
+        }
+        Ok(script)
+    }
+}
+
+/// Encodes a signed 24-bit offset, or returns `None` if it's out of range.
+fn i24_bytes(offset: i64) -> Option<[u8; 3]> {
+    if !(-(1 << 23)..1 << 23).contains(&offset) {
+        return None;
+    }
+    let bytes = (offset as i32).to_le_bytes();
+    Some([bytes[0], bytes[1], bytes[2]])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::disassemble;
+    use super::*;
+    use crate::avm2::read::tests::read_abc_from_file;
+    use crate::avm2::read::Reader;
+    use crate::avm2::write::Writer;
+
+    /// Checks that `abc_file` is disassembled the same once assembled again.
+    ///
+    /// As the constant pool is listed in full, this also checks that it's
+    /// assembled in the same order.
+    fn round_trip(abc_file: &AbcFile, name: &str) -> AbcFile {
+        let text = disassemble(abc_file);
+        let assembled =
+            assemble(&text).unwrap_or_else(|error| panic!("Assembling {name}: {error}"));
+        assert_eq!(disassemble(&assembled), text, "Round trip of {name}");
+        assembled
+    }
+
+    /// An ABC file with a single method, and nothing else besides `constant_pool`.
+    fn abc_file(constant_pool: ConstantPool, code: Vec<u8>, exceptions: Vec<Exception>) -> AbcFile {
+        AbcFile {
+            major_version: 46,
+            minor_version: 16,
+            constant_pool,
+            methods: vec![Method {
+                name: Index::new(0),
+                params: vec![],
+                return_type: Index::new(0),
+                flags: MethodFlags::empty(),
+                body: Some(Index::new(0)),
+            }],
+            metadata: vec![],
+            instances: vec![],
+            classes: vec![],
+            scripts: vec![],
+            method_bodies: vec![MethodBody {
+                method: Index::new(0),
+                max_stack: 1,
+                num_locals: 1,
+                init_scope_depth: 0,
+                max_scope_depth: 1,
+                code,
+                exceptions,
+                traits: vec![],
+            }],
+        }
+    }
+
+    fn empty_constant_pool() -> ConstantPool {
+        ConstantPool {
+            ints: vec![],
+            uints: vec![],
+            doubles: vec![],
+            strings: vec![],
+            namespaces: vec![],
+            namespace_sets: vec![],
+            multinames: vec![],
+        }
+    }
+
+    #[test]
+    fn round_trip_test_swfs() {
+        for path in [
+            "tests/swfs/Avm2Dummy.swf",
+            "tests/swfs/Avm2DefaultValue.swf",
+        ] {
+            let abc_file = Reader::new(&read_abc_from_file(path)).read().unwrap();
+            let assembled = round_trip(&abc_file, path);
+            assert_eq!(assembled.constant_pool, abc_file.constant_pool);
+
+            // The assembled file must also be written and read back the same.
+            let mut data = Vec::new();
+            Writer::new(&mut data).write(assembled.clone()).unwrap();
+            assert_eq!(Reader::new(&data).read().unwrap(), assembled);
+        }
+    }
+
+    /// The example of the module documentation.
+    #[test]
+    fn round_trip_module_example() {
+        let text = concat!(
+            "version 46 16\n",
+            "\n",
+            "constants\n",
+            "    string \"void\"\n",
+            "    string \"\"\n",
+            "    namespace PackageNamespace(\"\")\n",
+            "    multiname QName(PackageNamespace(\"\"), \"void\")\n",
+            "end\n",
+            "\n",
+            "method 0\n",
+            "    name null\n",
+            "    returns null\n",
+            "    body\n",
+            "        maxstack 1\n",
+            "        localcount 1\n",
+            "        initscopedepth 0\n",
+            "        maxscopedepth 1\n",
+            "        code\n",
+            "            getlocal0\n",
+            "            pushscope\n",
+            "            returnvoid\n",
+            "        end\n",
+            "    end\n",
+            "end\n",
+            "\n",
+            "script 0\n",
+            "    sinit 0\n",
+            "end\n",
+        );
+        assert_eq!(disassemble(&assemble(text).unwrap()), text);
+    }
+
+    #[test]
+    fn round_trip_constants() {
+        let constant_pool = ConstantPool {
+            ints: vec![1, -5, 1],
+            uints: vec![u32::MAX],
+            doubles: vec![0.5, -0.0, f64::INFINITY],
+            strings: vec![
+                b"".to_vec(),
+                b"Main".to_vec(),
+                b"quote \" and\nnewline".to_vec(),
+                vec![0xff, 0x00],
+                b"Main".to_vec(),
+            ],
+            namespaces: vec![
+                Namespace::Package(Index::new(1)),
+                Namespace::Private(Index::new(2)),
+                Namespace::Private(Index::new(2)),
+                Namespace::Namespace(Index::new(99)),
+            ],
+            namespace_sets: vec![vec![Index::new(1), Index::new(3)]],
+            multinames: vec![
+                Multiname::QName {
+                    namespace: Index::new(2),
+                    name: Index::new(3),
+                },
+                Multiname::TypeName {
+                    base_type: Index::new(1),
+                    parameters: vec![Index::new(4)],
+                },
+                Multiname::RTQNameL,
+                Multiname::MultinameA {
+                    namespace_set: Index::new(1),
+                    name: Index::new(4),
+                },
+            ],
+        };
+        let abc_file = abc_file(constant_pool, vec![0x47], vec![]);
+        let assembled = round_trip(&abc_file, "constants");
+        assert_eq!(assembled.constant_pool, abc_file.constant_pool);
+    }
+
+    #[test]
+    fn round_trip_code() {
+        let code = vec![
+            0xd0, // getlocal0
+            0x30, // pushscope
+            0x24, 0x05, // pushbyte 5
+            0x11, 0x04, 0x00, 0x00, // iftrue +4
+            0x10, 0xf6, 0xff, 0xff, // jump -10
+            0x10, 0xfb, 0xff, 0xff, // jump -5, into the middle of an instruction
+            0x47, // returnvoid
+            0xff, // unknown
+        ];
+        let exceptions = vec![Exception {
+            from_offset: 2,
+            to_offset: 12,
+            target_offset: 16,
+            variable_name: Index::new(0),
+            type_name: Index::new(0),
+        }];
+        let abc_file = abc_file(empty_constant_pool(), code, exceptions);
+        let assembled = round_trip(&abc_file, "code");
+        assert_eq!(assembled.method_bodies, abc_file.method_bodies);
+    }
+
+    #[test]
+    fn assemble_code() {
+        let abc_file = assemble(concat!(
+            "version 46 16\n",
+            "method 0\n",
+            "    name \"f\"\n",
+            "    returns QName(PackageNamespace(\"\"), \"void\")\n",
+            "    body\n",
+            "        maxstack 2\n",
+            "        localcount 1\n",
+            "        initscopedepth 0\n",
+            "        maxscopedepth 1\n",
+            "        code\n",
+            "        L0:\n",
+            "            pushbyte 1 ; a comment\n",
+            "            lookupswitch L0, [L0, L14]\n",
+            "            returnvoid\n",
+            "        L14:\n",
+            "            iftrue L0\n",
+            "        end\n",
+            "        try from L0 to L14 target L14 type null name null\n",
+            "    end\n",
+            "end\n",
+        ))
+        .unwrap();
+
+        let body = &abc_file.method_bodies[0];
+        assert_eq!(
+            body.code,
+            [
+                0x24, 0x01, // pushbyte 1
+                0x1b, 0xfe, 0xff, 0xff, // lookupswitch L0,
+                0x01, 0xfe, 0xff, 0xff, 0x0c, 0x00, 0x00, // [L0, L14]
+                0x47, // returnvoid
+                0x11, 0xee, 0xff, 0xff, // iftrue -18
+            ]
+        );
+        assert_eq!(body.exceptions[0].to_offset, 14);
+        assert_eq!(abc_file.methods[0].body, Some(Index::new(0)));
+        assert_eq!(
+            abc_file.constant_pool.strings,
+            [b"f".to_vec(), b"".to_vec(), b"void".to_vec()]
+        );
+    }
+
+    #[test]
+    fn assemble_errors() {
+        let error = |text: &str| assemble(text).unwrap_err().to_string();
+        assert!(error("version 46 16\nmethod 1\nend\n").contains("Line 2"));
+        assert!(error(concat!(
+            "version 46 16\n",
+            "method 0\n",
+            "    body\n",
+            "        code\n",
+            "            jump L4\n",
+            "        end\n",
+            "    end\n",
+            "end\n",
+        ))
+        .contains("Line 5: Unknown label `L4`"));
+    }
+}
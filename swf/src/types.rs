@@ -1026,6 +1026,8 @@ pub enum AudioCompression {
     Nellymoser16Khz = 4,
     Nellymoser8Khz = 5,
     Nellymoser = 6,
+    /// Only valid in FLV and F4V containers; SWF files can't embed AAC sounds.
+    Aac = 10,
     Speex = 11,
 }
 
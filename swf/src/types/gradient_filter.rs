@@ -1,4 +1,5 @@
-use crate::{BlurFilter, BlurFilterFlags, Fixed16, Fixed8, GradientRecord};
+use crate::types::bevel_filter::bevel_dest_rect;
+use crate::{BlurFilter, BlurFilterFlags, Fixed16, Fixed8, GradientRecord, Rectangle, Twips};
 use bitflags::bitflags;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -46,6 +47,15 @@ impl GradientFilter {
             flags: BlurFilterFlags::from_passes(self.num_passes()),
         }
     }
+
+    pub fn calculate_dest_rect(&self, source_rect: Rectangle<Twips>) -> Rectangle<Twips> {
+        bevel_dest_rect(
+            &self.inner_blur_filter(),
+            self.angle,
+            self.distance,
+            source_rect,
+        )
+    }
 }
 
 bitflags! {
@@ -1,9 +1,13 @@
 mod audio;
+mod filesystem;
 mod log;
 mod navigator;
+mod print;
 mod ui;
 
 pub use audio::TestAudioBackend;
+pub use filesystem::TestFilesystemBackend;
 pub use log::TestLogBackend;
 pub use navigator::TestNavigatorBackend;
+pub use print::TestPrintBackend;
 pub use ui::TestUiBackend;
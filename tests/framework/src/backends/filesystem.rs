@@ -0,0 +1,128 @@
+use ruffle_core::backend::filesystem::{
+    FileMetadata, FilePath, FileRoot, FilesystemBackend, FilesystemError, MemoryFilesystemBackend,
+};
+use std::io::Read;
+use vfs::{VfsFileType, VfsPath};
+
+/// A filesystem where `app:/` is the (read-only) test directory, and
+/// `app-storage:/` starts out empty and only exists in memory.
+pub struct TestFilesystemBackend {
+    application_directory: VfsPath,
+    storage: MemoryFilesystemBackend,
+}
+
+impl TestFilesystemBackend {
+    pub fn new(application_directory: VfsPath) -> Self {
+        Self {
+            application_directory,
+            storage: MemoryFilesystemBackend::new(),
+        }
+    }
+
+    /// Where a path is in the test directory, or `None` if it's in storage.
+    fn application_path(&self, path: &FilePath) -> Option<Result<VfsPath, FilesystemError>> {
+        if path.file_root() != FileRoot::Application {
+            return None;
+        }
+
+        let mut vfs_path = self.application_directory.clone();
+        for component in path.components() {
+            if component.contains(['/', '\\']) || component == ".." {
+                return Some(Err(FilesystemError::AccessDenied));
+            }
+            vfs_path = match vfs_path.join(component) {
+                Ok(vfs_path) => vfs_path,
+                Err(e) => return Some(Err(vfs_error(e))),
+            };
+        }
+        Some(Ok(vfs_path))
+    }
+}
+
+fn vfs_error(error: vfs::VfsError) -> FilesystemError {
+    FilesystemError::Other(error.to_string())
+}
+
+/// Fails with `NotFound` if nothing exists at the path.
+fn existing(path: VfsPath) -> Result<VfsPath, FilesystemError> {
+    if path.exists().map_err(vfs_error)? {
+        Ok(path)
+    } else {
+        Err(FilesystemError::NotFound)
+    }
+}
+
+impl FilesystemBackend for TestFilesystemBackend {
+    fn native_path(&self, path: &FilePath) -> String {
+        path.to_url()
+    }
+
+    fn path_from_native(&self, native_path: &str) -> Option<FilePath> {
+        FilePath::from_url(native_path)
+    }
+
+    fn metadata(&self, path: &FilePath) -> Option<FileMetadata> {
+        let Some(vfs_path) = self.application_path(path) else {
+            return self.storage.metadata(path);
+        };
+        let metadata = vfs_path.ok()?.metadata().ok()?;
+        let is_directory = metadata.file_type == VfsFileType::Directory;
+        // Times are left out, so that they don't end up in the test output.
+        Some(FileMetadata {
+            is_directory,
+            size: if is_directory { 0 } else { metadata.len },
+            creation_time: None,
+            modification_time: None,
+        })
+    }
+
+    fn read(&self, path: &FilePath) -> Result<Vec<u8>, FilesystemError> {
+        let Some(vfs_path) = self.application_path(path) else {
+            return self.storage.read(path);
+        };
+        let vfs_path = existing(vfs_path?)?;
+        if vfs_path.is_dir().map_err(vfs_error)? {
+            return Err(FilesystemError::AccessDenied);
+        }
+        let mut data = Vec::new();
+        vfs_path
+            .open_file()
+            .map_err(vfs_error)?
+            .read_to_end(&mut data)?;
+        Ok(data)
+    }
+
+    fn write(&mut self, path: &FilePath, data: &[u8]) -> Result<(), FilesystemError> {
+        if path.file_root() == FileRoot::Application {
+            return Err(FilesystemError::AccessDenied);
+        }
+        self.storage.write(path, data)
+    }
+
+    fn list_directory(&self, path: &FilePath) -> Result<Vec<String>, FilesystemError> {
+        let Some(vfs_path) = self.application_path(path) else {
+            return self.storage.list_directory(path);
+        };
+        let mut names: Vec<_> = existing(vfs_path?)?
+            .read_dir()
+            .map_err(vfs_error)?
+            .map(|entry| entry.filename())
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    fn create_directory(&mut self, path: &FilePath) -> Result<(), FilesystemError> {
+        if path.file_root() == FileRoot::Application {
+            return Err(FilesystemError::AccessDenied);
+        }
+        self.storage.create_directory(path)
+    }
+
+    fn delete(&mut self, path: &FilePath) -> Result<(), FilesystemError> {
+        if path.file_root() == FileRoot::Application {
+            return Err(FilesystemError::AccessDenied);
+        }
+        self.storage.delete(path)
+    }
+}
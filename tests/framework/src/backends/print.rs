@@ -0,0 +1,65 @@
+use crate::backends::TestLogBackend;
+use ruffle_core::backend::log::LogBackend;
+use ruffle_core::backend::print::{PageSettings, PrintBackend, PrintPage};
+
+/// A print backend that accepts every print job, and describes the printed
+/// pages in the trace output.
+pub struct TestPrintBackend {
+    log: TestLogBackend,
+}
+
+impl TestPrintBackend {
+    pub fn new(log: TestLogBackend) -> Self {
+        Self { log }
+    }
+}
+
+impl PrintBackend for TestPrintBackend {
+    fn start_job(&mut self) -> Option<PageSettings> {
+        Some(PageSettings::default())
+    }
+
+    fn send_job(&mut self, _settings: &PageSettings, pages: Vec<PrintPage>) {
+        self.log
+            .avm_trace(&format!("[print] Sent a job with {} page(s)", pages.len()));
+        for (i, page) in pages.iter().enumerate() {
+            self.log.avm_trace(&format!(
+                "[print] Page {}: {}x{}, {}",
+                i + 1,
+                page.width,
+                page.height,
+                describe_content(page)
+            ));
+        }
+    }
+}
+
+/// Describes the bounds of everything that isn't white on a page, along with
+/// the color in the middle of those bounds.
+fn describe_content(page: &PrintPage) -> String {
+    let mut bounds: Option<(u32, u32, u32, u32)> = None;
+    for (i, pixel) in page.rgba.chunks_exact(4).enumerate() {
+        if pixel[..3] == [0xFF, 0xFF, 0xFF] {
+            continue;
+        }
+        let x = i as u32 % page.width;
+        let y = i as u32 / page.width;
+        bounds = Some(match bounds {
+            Some((x_min, y_min, x_max, y_max)) => {
+                (x_min.min(x), y_min.min(y), x_max.max(x), y_max.max(y))
+            }
+            None => (x, y, x, y),
+        });
+    }
+
+    let Some((x_min, y_min, x_max, y_max)) = bounds else {
+        return "blank".to_string();
+    };
+    let center = (((y_min + y_max) / 2 * page.width + (x_min + x_max) / 2) * 4) as usize;
+    let [r, g, b] = [
+        page.rgba[center],
+        page.rgba[center + 1],
+        page.rgba[center + 2],
+    ];
+    format!("content from ({x_min}, {y_min}) to ({x_max}, {y_max}), #{r:02X}{g:02X}{b:02X} in the middle")
+}
@@ -76,6 +76,7 @@ impl FileDialogResult for TestFileDialogResult {
 /// * Attempting to display a file save dialog with a file name hint of "debug-success.txt" will simulate successfully selecting a destination
 ///   otherwise a user cancellation will be simulated
 /// * Simulated in-memory clipboard
+/// * Gamepad input is available, as tests can simulate gamepads
 pub struct TestUiBackend {
     fonts: Vec<Font>,
     clipboard: String,
@@ -107,6 +108,14 @@ impl UiBackend for TestUiBackend {
         self.clipboard = content;
     }
 
+    fn gamepad_input_available(&self) -> bool {
+        true
+    }
+
+    fn touch_input_available(&self) -> bool {
+        true
+    }
+
     fn set_fullscreen(&mut self, _is_full: bool) -> Result<(), FullscreenError> {
         Ok(())
     }
@@ -44,3 +44,40 @@ pub trait RenderInterface {
     /// The provided backend is guaranteed to be the same one paired with this interface.
     fn capture(&self, renderer: &mut Box<dyn RenderBackend>) -> image::RgbaImage;
 }
+
+/// A [RenderInterface] for the CPU-only [ruffle_render_software::SoftwareRenderBackend].
+///
+/// This is used by tests that opt into it with `software = true` in their render options,
+/// instead of the renderer of the [Environment].
+#[cfg(feature = "ruffle_render_software")]
+pub struct SoftwareRenderInterface;
+
+#[cfg(feature = "ruffle_render_software")]
+impl SoftwareRenderInterface {
+    pub fn create_pair(
+        width: u32,
+        height: u32,
+    ) -> (Box<dyn RenderInterface>, Box<dyn RenderBackend>) {
+        (
+            Box::new(Self),
+            Box::new(ruffle_render_software::SoftwareRenderBackend::new(
+                width, height,
+            )),
+        )
+    }
+}
+
+#[cfg(feature = "ruffle_render_software")]
+impl RenderInterface for SoftwareRenderInterface {
+    fn name(&self) -> String {
+        "software".to_string()
+    }
+
+    fn capture(&self, renderer: &mut Box<dyn RenderBackend>) -> image::RgbaImage {
+        renderer
+            .downcast_mut::<ruffle_render_software::SoftwareRenderBackend>()
+            .expect("Renderer must be a software renderer")
+            .capture_frame()
+            .expect("Failed to capture image")
+    }
+}
@@ -133,11 +133,14 @@ impl Approximations {
 pub struct RequiredFeatures {
     lzma: bool,
     jpegxr: bool,
+    sqlite: bool,
 }
 
 impl RequiredFeatures {
     pub fn can_run(&self) -> bool {
-        (!self.lzma || cfg!(feature = "lzma")) && (!self.jpegxr || cfg!(feature = "jpegxr"))
+        (!self.lzma || cfg!(feature = "lzma"))
+            && (!self.jpegxr || cfg!(feature = "jpegxr"))
+            && (!self.sqlite || cfg!(feature = "sqlite"))
     }
 }
 
@@ -207,7 +210,12 @@ impl PlayerOptions {
         if let Some(render) = &self.with_renderer {
             // If we don't actually want to check the renderer (ie we're just listing potential tests),
             // don't spend the cost to create it
-            if check_renderer && !render.optional && !environment.is_render_supported(render) {
+            let supported = if render.software {
+                cfg!(feature = "ruffle_render_software")
+            } else {
+                !check_renderer || environment.is_render_supported(render)
+            };
+            if !render.optional && !supported {
                 return false;
             }
         }
@@ -228,11 +236,17 @@ impl PlayerOptions {
         environment: &impl Environment,
         dimensions: ViewportDimensions,
     ) -> Option<(Box<dyn RenderInterface>, Box<dyn RenderBackend>)> {
-        if self.with_renderer.is_some() {
-            environment.create_renderer(dimensions.width, dimensions.height)
-        } else {
-            None
+        let render = self.with_renderer.as_ref()?;
+        if render.software {
+            #[cfg(feature = "ruffle_render_software")]
+            return Some(crate::environment::SoftwareRenderInterface::create_pair(
+                dimensions.width,
+                dimensions.height,
+            ));
+            #[cfg(not(feature = "ruffle_render_software"))]
+            return None;
         }
+        environment.create_renderer(dimensions.width, dimensions.height)
     }
 }
 
@@ -393,6 +407,11 @@ impl ImageComparison {
 pub struct RenderOptions {
     optional: bool,
     pub sample_count: u32,
+
+    /// Render with the CPU-only software backend instead of the environment's renderer.
+    ///
+    /// The expected images of such tests are rendered by the software backend too.
+    pub software: bool,
 }
 
 impl Default for RenderOptions {
@@ -400,6 +419,7 @@ impl Default for RenderOptions {
         Self {
             optional: false,
             sample_count: 1,
+            software: false,
         }
     }
 }
@@ -1,4 +1,6 @@
-use crate::backends::{TestLogBackend, TestNavigatorBackend, TestUiBackend};
+use crate::backends::{
+    TestFilesystemBackend, TestLogBackend, TestNavigatorBackend, TestPrintBackend, TestUiBackend,
+};
 use crate::environment::RenderInterface;
 use crate::fs_commands::{FsCommand, TestFsCommandProvider};
 use crate::image_trigger::ImageTrigger;
@@ -9,15 +11,11 @@ use anyhow::{anyhow, Error, Result};
 use image::ImageFormat;
 use pretty_assertions::Comparison;
 use ruffle_core::backend::navigator::NullExecutor;
-use ruffle_core::events::{KeyCode, TextControlCode as RuffleTextControlCode};
-use ruffle_core::events::{MouseButton as RuffleMouseButton, MouseWheelDelta};
+use ruffle_core::events::{KeyCode, MouseWheelDelta};
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::{Player, PlayerBuilder, PlayerEvent};
-use ruffle_input_format::{
-    AutomatedEvent, InputInjector, MouseButton as InputMouseButton,
-    TextControlCode as InputTextControlCode,
-};
+use ruffle_input_format::{AutomatedEvent, InputInjector};
 use ruffle_render::backend::{RenderBackend, ViewportDimensions};
 use ruffle_socket_format::SocketEvent;
 use std::collections::HashMap;
@@ -87,6 +85,8 @@ impl TestRunner {
             .with_navigator(navigator)
             .with_max_execution_duration(Duration::from_secs(300))
             .with_fs_commands(Box::new(fs_command_provider))
+            .with_filesystem(TestFilesystemBackend::new(test.root_path.clone()))
+            .with_print(TestPrintBackend::new(log.clone()))
             .with_ui(TestUiBackend::new(test.fonts()?))
             .with_viewport_dimensions(
                 viewport_dimensions.width,
@@ -203,17 +203,22 @@ impl TestRunner {
                 return;
             }
 
+            // Frontends tell the player whether the mouse is over it separately from its events.
+            match evt {
+                AutomatedEvent::MouseLeave => player.set_mouse_in_stage(false),
+                AutomatedEvent::MouseMove { .. }
+                | AutomatedEvent::MouseDown { .. }
+                | AutomatedEvent::MouseUp { .. } => player.set_mouse_in_stage(true),
+                _ => {}
+            }
+
             let handled = player.handle_event(match evt {
                 AutomatedEvent::MouseDown {
                     pos, btn, index, ..
                 } => PlayerEvent::MouseDown {
                     x: pos.0,
                     y: pos.1,
-                    button: match btn {
-                        InputMouseButton::Left => RuffleMouseButton::Left,
-                        InputMouseButton::Middle => RuffleMouseButton::Middle,
-                        InputMouseButton::Right => RuffleMouseButton::Right,
-                    },
+                    button: (*btn).into(),
                     // None here means that the core will compute index automatically,
                     // however we do not want that in tests.
                     index: Some(index.unwrap_or_default()),
@@ -222,12 +227,9 @@ impl TestRunner {
                 AutomatedEvent::MouseUp { pos, btn } => PlayerEvent::MouseUp {
                     x: pos.0,
                     y: pos.1,
-                    button: match btn {
-                        InputMouseButton::Left => RuffleMouseButton::Left,
-                        InputMouseButton::Middle => RuffleMouseButton::Middle,
-                        InputMouseButton::Right => RuffleMouseButton::Right,
-                    },
+                    button: (*btn).into(),
                 },
+                AutomatedEvent::MouseLeave => PlayerEvent::MouseLeave,
                 AutomatedEvent::MouseWheel { lines, pixels } => PlayerEvent::MouseWheel {
                     delta: match (lines, pixels) {
                         (Some(lines), None) => MouseWheelDelta::Lines(*lines),
@@ -247,50 +249,71 @@ impl TestRunner {
                     codepoint: *codepoint,
                 },
                 AutomatedEvent::TextControl { code } => PlayerEvent::TextControl {
-                    code: match code {
-                        InputTextControlCode::MoveLeft => RuffleTextControlCode::MoveLeft,
-                        InputTextControlCode::MoveLeftWord => RuffleTextControlCode::MoveLeftWord,
-                        InputTextControlCode::MoveLeftLine => RuffleTextControlCode::MoveLeftLine,
-                        InputTextControlCode::MoveLeftDocument => {
-                            RuffleTextControlCode::MoveLeftDocument
-                        }
-                        InputTextControlCode::MoveRight => RuffleTextControlCode::MoveRight,
-                        InputTextControlCode::MoveRightWord => RuffleTextControlCode::MoveRightWord,
-                        InputTextControlCode::MoveRightLine => RuffleTextControlCode::MoveRightLine,
-                        InputTextControlCode::MoveRightDocument => {
-                            RuffleTextControlCode::MoveRightDocument
-                        }
-                        InputTextControlCode::SelectLeft => RuffleTextControlCode::SelectLeft,
-                        InputTextControlCode::SelectLeftWord => {
-                            RuffleTextControlCode::SelectLeftWord
-                        }
-                        InputTextControlCode::SelectLeftLine => {
-                            RuffleTextControlCode::SelectLeftLine
-                        }
-                        InputTextControlCode::SelectLeftDocument => {
-                            RuffleTextControlCode::SelectLeftDocument
-                        }
-                        InputTextControlCode::SelectRight => RuffleTextControlCode::SelectRight,
-                        InputTextControlCode::SelectRightWord => {
-                            RuffleTextControlCode::SelectRightWord
-                        }
-                        InputTextControlCode::SelectRightLine => {
-                            RuffleTextControlCode::SelectRightLine
-                        }
-                        InputTextControlCode::SelectRightDocument => {
-                            RuffleTextControlCode::SelectRightDocument
-                        }
-                        InputTextControlCode::SelectAll => RuffleTextControlCode::SelectAll,
-                        InputTextControlCode::Copy => RuffleTextControlCode::Copy,
-                        InputTextControlCode::Paste => RuffleTextControlCode::Paste,
-                        InputTextControlCode::Cut => RuffleTextControlCode::Cut,
-                        InputTextControlCode::Backspace => RuffleTextControlCode::Backspace,
-                        InputTextControlCode::Enter => RuffleTextControlCode::Enter,
-                        InputTextControlCode::Delete => RuffleTextControlCode::Delete,
-                    },
+                    code: (*code).into(),
                 },
                 AutomatedEvent::FocusGained => PlayerEvent::FocusGained,
                 AutomatedEvent::FocusLost => PlayerEvent::FocusLost,
+                AutomatedEvent::TouchBegin { id, pos } => PlayerEvent::TouchBegin {
+                    id: *id,
+                    x: pos.0,
+                    y: pos.1,
+                },
+                AutomatedEvent::TouchMove { id, pos } => PlayerEvent::TouchMove {
+                    id: *id,
+                    x: pos.0,
+                    y: pos.1,
+                },
+                AutomatedEvent::TouchEnd { id, pos } => PlayerEvent::TouchEnd {
+                    id: *id,
+                    x: pos.0,
+                    y: pos.1,
+                },
+                AutomatedEvent::Gesture {
+                    gesture,
+                    phase,
+                    pos,
+                } => PlayerEvent::Gesture {
+                    gesture: (*gesture).into(),
+                    phase: (*phase).into(),
+                    x: pos.0,
+                    y: pos.1,
+                },
+                AutomatedEvent::TwoFingerTap { pos } => {
+                    PlayerEvent::TwoFingerTap { x: pos.0, y: pos.1 }
+                }
+                AutomatedEvent::PressAndTap { pos, tap_pos } => PlayerEvent::PressAndTap {
+                    x: pos.0,
+                    y: pos.1,
+                    tap_x: tap_pos.0,
+                    tap_y: tap_pos.1,
+                },
+                AutomatedEvent::GamepadConnected { gamepad } => {
+                    PlayerEvent::GamepadConnected { gamepad: *gamepad }
+                }
+                AutomatedEvent::GamepadDisconnected { gamepad } => {
+                    PlayerEvent::GamepadDisconnected { gamepad: *gamepad }
+                }
+                AutomatedEvent::GamepadButtonDown { gamepad, button } => {
+                    PlayerEvent::GamepadButtonDown {
+                        gamepad: *gamepad,
+                        button: (*button).into(),
+                    }
+                }
+                AutomatedEvent::GamepadButtonUp { gamepad, button } => {
+                    PlayerEvent::GamepadButtonUp {
+                        gamepad: *gamepad,
+                        button: (*button).into(),
+                    }
+                }
+                AutomatedEvent::GamepadAxisChanged {
+                    gamepad,
+                    axis,
+                    value,
+                } => PlayerEvent::GamepadAxisChanged {
+                    gamepad: *gamepad,
+                    axis: (*axis).into(),
+                    value: *value,
+                },
                 AutomatedEvent::Wait | AutomatedEvent::SetClipboardText { .. } => unreachable!(),
             });
 
@@ -8,11 +8,11 @@ use serde::{Deserialize, Serialize};
 /// pixels) or at 2x the size will see mouse clicks at its bottom right corner
 /// on (1280x960), relative to the window. That coordinate needs to be scaled
 /// down to match the desired stage.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
 pub struct MousePosition(pub f64, pub f64);
 
 /// Which mouse button is being pressed or released.
-#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
 pub enum MouseButton {
     Left,
     Middle,
@@ -43,15 +43,65 @@ pub enum TextControlCode {
     Paste,
     Cut,
     Backspace,
+    BackspaceWord,
     Enter,
     Delete,
+    DeleteWord,
+}
+
+/// A transform gesture performed with touch input.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum Gesture {
+    Pan { offset_x: f64, offset_y: f64 },
+    Rotate { rotation: f64 },
+    Swipe { offset_x: f64, offset_y: f64 },
+    Zoom { scale_x: f64, scale_y: f64 },
+}
+
+/// The progress of a gesture.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GesturePhase {
+    Begin,
+    Update,
+    End,
+    All,
+}
+
+/// A button of a gamepad.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GamepadButton {
+    South,
+    East,
+    North,
+    West,
+    LeftTrigger,
+    LeftTrigger2,
+    RightTrigger,
+    RightTrigger2,
+    Select,
+    Start,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+/// An analog stick axis or trigger of a gamepad.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
 }
 
 /// All automated event types supported by FlashTAS.
 ///
 /// A FlashTAS input file consists of a string of `AutomatedEvent`s which are
 /// played back by FlashTAS.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(tag = "type")]
 pub enum AutomatedEvent {
     /// End the current frame's input and wait for the next frame before
@@ -75,6 +125,9 @@ pub enum AutomatedEvent {
         btn: MouseButton,
     },
 
+    /// Move the mouse out of the player.
+    MouseLeave,
+
     /// Mouse scroll.
     MouseWheel {
         lines: Option<f64>,
@@ -101,9 +154,59 @@ pub enum AutomatedEvent {
 
     /// Inform the player that the focus has been lost (i.e. the user focused another window).
     FocusLost,
+
+    /// Start touching the screen with a touch point.
+    TouchBegin { id: i32, pos: MousePosition },
+
+    /// Move a touch point that is touching the screen.
+    TouchMove { id: i32, pos: MousePosition },
+
+    /// Stop touching the screen with a touch point.
+    TouchEnd { id: i32, pos: MousePosition },
+
+    /// Perform a gesture centered on a position.
+    Gesture {
+        gesture: Gesture,
+        phase: GesturePhase,
+        pos: MousePosition,
+    },
+
+    /// Tap with two touch points at once, centered on a position.
+    TwoFingerTap { pos: MousePosition },
+
+    /// Tap with a touch point while holding another one down at a position.
+    PressAndTap {
+        pos: MousePosition,
+        tap_pos: MousePosition,
+    },
+
+    /// Connect a gamepad, identified by an arbitrary ID.
+    GamepadConnected { gamepad: usize },
+
+    /// Disconnect a gamepad.
+    GamepadDisconnected { gamepad: usize },
+
+    /// Press a button of a gamepad.
+    GamepadButtonDown {
+        gamepad: usize,
+        button: GamepadButton,
+    },
+
+    /// Release a button of a gamepad.
+    GamepadButtonUp {
+        gamepad: usize,
+        button: GamepadButton,
+    },
+
+    /// Move an analog stick or trigger of a gamepad.
+    GamepadAxisChanged {
+        gamepad: usize,
+        axis: GamepadAxis,
+        value: f64,
+    },
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct EventHandledAssertion {
     pub value: bool,
     pub message: String,
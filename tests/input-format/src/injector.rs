@@ -82,6 +82,11 @@ impl InputInjector {
         }
     }
 
+    /// Whether every event has been injected.
+    pub fn is_finished(&self) -> bool {
+        self.pos >= self.items.len()
+    }
+
     /// Run the next frame's worth of events.
     pub fn next<Sink>(&mut self, mut event_sink: Sink)
     where
@@ -95,6 +100,7 @@ impl InputInjector {
                 match event {
                     AutomatedEvent::Wait => break,
                     AutomatedEvent::MouseMove { .. }
+                    | AutomatedEvent::MouseLeave
                     | AutomatedEvent::KeyDown { .. }
                     | AutomatedEvent::KeyUp { .. }
                     | AutomatedEvent::TextInput { .. }
@@ -102,7 +108,18 @@ impl InputInjector {
                     | AutomatedEvent::SetClipboardText { .. }
                     | AutomatedEvent::MouseWheel { .. }
                     | AutomatedEvent::FocusGained
-                    | AutomatedEvent::FocusLost => {}
+                    | AutomatedEvent::FocusLost
+                    | AutomatedEvent::TouchBegin { .. }
+                    | AutomatedEvent::TouchMove { .. }
+                    | AutomatedEvent::TouchEnd { .. }
+                    | AutomatedEvent::Gesture { .. }
+                    | AutomatedEvent::TwoFingerTap { .. }
+                    | AutomatedEvent::PressAndTap { .. }
+                    | AutomatedEvent::GamepadConnected { .. }
+                    | AutomatedEvent::GamepadDisconnected { .. }
+                    | AutomatedEvent::GamepadButtonDown { .. }
+                    | AutomatedEvent::GamepadButtonUp { .. }
+                    | AutomatedEvent::GamepadAxisChanged { .. } => {}
                     AutomatedEvent::MouseDown { btn, .. } => {
                         self.buttons |= (*btn).into();
                     }
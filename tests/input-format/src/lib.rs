@@ -1,5 +1,8 @@
 mod format;
 mod injector;
 
-pub use format::{AutomatedEvent, MouseButton, TextControlCode};
+pub use format::{
+    AutomatedEvent, EventHandledAssertion, GamepadAxis, GamepadButton, Gesture, GesturePhase,
+    MouseButton, MousePosition, TextControlCode,
+};
 pub use injector::{InputInjector, MouseButtons};
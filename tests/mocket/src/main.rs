@@ -19,6 +19,10 @@ struct Opt {
     /// Path to a `socket.json` file.
     #[clap(name = "FILE")]
     file_path: PathBuf,
+
+    /// Skip the socket policy file exchange, as RTMP connections don't perform one.
+    #[clap(long)]
+    no_policy: bool,
 }
 
 fn main() -> Result<(), Error> {
@@ -42,15 +46,17 @@ fn main() -> Result<(), Error> {
     let (mut stream, addr) = listener.accept()?;
     tracing::info!("Incoming connection from {}", addr);
 
-    // Handle socket policy stuff. (Required as Flash Player wont want to connect otherwise.)
-    let mut buffer = [0; 4096];
-    let _ = stream.read(&mut buffer);
-    stream.write_all(POLICY)?;
-    tracing::info!("Policy sent successfully!");
+    if !opt.no_policy {
+        // Handle socket policy stuff. (Required as Flash Player wont want to connect otherwise.)
+        let mut buffer = [0; 4096];
+        let _ = stream.read(&mut buffer);
+        stream.write_all(POLICY)?;
+        tracing::info!("Policy sent successfully!");
 
-    // Now we listen again as flash reopens socket connection.
-    let (mut stream, addr) = listener.accept()?;
-    tracing::info!("Incoming connection from {}", addr);
+        // Now we listen again as flash reopens socket connection.
+        (stream, _) = listener.accept()?;
+        tracing::info!("Incoming connection from {}", stream.peer_addr()?);
+    }
 
     for (index, event) in events.into_iter().enumerate() {
         tracing::info!("Running step {}/{}", index + 1, event_count);
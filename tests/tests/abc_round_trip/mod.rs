@@ -0,0 +1,52 @@
+use swf::avm2::disasm::{assemble, disassemble};
+use swf::avm2::read::Reader;
+use swf::Tag;
+
+/// Disassemble the ABC files of every test SWF, then check that assembling
+/// the text again gives the same disassembly.
+pub fn abc_round_trip() -> Result<(), libtest_mimic::Failed> {
+    let mut count = 0;
+    for entry in walkdir::WalkDir::new("tests/swfs").sort_by_file_name() {
+        let entry = entry?;
+        let path = entry.path();
+        if !entry.file_type().is_file() || path.extension().is_none_or(|ext| ext != "swf") {
+            continue;
+        }
+
+        let data = std::fs::read(path)?;
+        // Some SWFs are deliberately malformed.
+        let Ok(swf_buf) = swf::decompress_swf(&data[..]) else {
+            continue;
+        };
+        let Ok(swf) = swf::parse_swf(&swf_buf) else {
+            continue;
+        };
+        for tag in swf.tags {
+            let abc = match tag {
+                Tag::DoAbc(data) => data,
+                Tag::DoAbc2(do_abc) => do_abc.data,
+                _ => continue,
+            };
+            let Ok(abc_file) = Reader::new(abc).read() else {
+                continue;
+            };
+
+            let text = disassemble(&abc_file);
+            let assembled = assemble(&text)
+                .map_err(|error| format!("Assembling {}: {error}", path.display()))?;
+            if disassemble(&assembled) != text {
+                return Err(format!(
+                    "{} is disassembled differently once assembled",
+                    path.display()
+                )
+                .into());
+            }
+            count += 1;
+        }
+    }
+
+    if count == 0 {
+        return Err("No ABC files found in the test SWFs".into());
+    }
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use ruffle_test_framework::environment::Environment;
+use ruffle_test_framework::options::TestOptions;
+use ruffle_test_framework::runner::{TestRunner, TestStatus};
+use ruffle_test_framework::test::Test;
+use ruffle_test_framework::vfs::{MemoryFS, PhysicalFS, VfsPath};
+use std::thread::sleep;
+
+/// Record the input of a test as it runs, then run the test again
+/// with the recorded input in place of its `input.json`.
+pub fn input_recording(environment: &impl Environment) -> Result<(), libtest_mimic::Failed> {
+    let test_dir = VfsPath::new(PhysicalFS::new("tests/swfs/avm2/mouse_leave/"));
+    let options = TestOptions::read(&test_dir.join("test.toml")?)?;
+
+    let test = Test::from_options(options.clone(), test_dir.clone(), "input_recording".into())?;
+    let mut runner = test.create_test_runner(environment)?;
+    runner.player().lock().unwrap().start_recording_input();
+    run(&mut runner)?;
+    let recording = runner
+        .player()
+        .lock()
+        .unwrap()
+        .stop_recording_input()
+        .expect("Input should have been recorded");
+    assert_eq!(Some(recording.ticks() as u32), options.num_ticks);
+
+    let replay_dir = VfsPath::new(MemoryFS::new());
+    test_dir.copy_dir(&replay_dir)?;
+    recording.to_writer(replay_dir.join("input.json")?.create_file()?)?;
+
+    let test = Test::from_options(options, replay_dir, "input_recording_replay".into())?;
+    let mut runner = test.create_test_runner(environment)?;
+    run(&mut runner)?;
+
+    Ok(())
+}
+
+fn run(runner: &mut TestRunner) -> Result<(), libtest_mimic::Failed> {
+    loop {
+        runner.tick();
+        match runner.test()? {
+            TestStatus::Continue => {}
+            TestStatus::Sleep(duration) => sleep(duration),
+            TestStatus::Finished => return Ok(()),
+        }
+    }
+}
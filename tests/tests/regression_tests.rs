@@ -2,8 +2,10 @@
 //!
 //! Trace output can be compared with correct output from the official Flash Player.
 
+use crate::abc_round_trip::abc_round_trip;
 use crate::environment::NativeEnvironment;
 use crate::external_interface::tests::{external_interface_avm1, external_interface_avm2};
+use crate::input_recording::input_recording;
 use crate::shared_object::{shared_object_avm1, shared_object_avm2, shared_object_self_ref_avm1};
 use anyhow::Context;
 use anyhow::Result;
@@ -17,8 +19,10 @@ use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
 use std::path::Path;
 use std::thread::sleep;
 
+mod abc_round_trip;
 mod environment;
 mod external_interface;
+mod input_recording;
 mod shared_object;
 
 const TEST_TOML_NAME: &str = "test.toml";
@@ -125,6 +129,10 @@ fn main() {
     tests.push(Trial::test("external_interface_avm2", || {
         external_interface_avm2(&NativeEnvironment)
     }));
+    tests.push(Trial::test("input_recording", || {
+        input_recording(&NativeEnvironment)
+    }));
+    tests.push(Trial::test("abc_round_trip", abc_round_trip));
 
     tests.sort_unstable_by(|a, b| a.name().cmp(b.name()));
 
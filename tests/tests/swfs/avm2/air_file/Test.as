@@ -0,0 +1,210 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.events.FileListEvent;
+	import flash.events.IOErrorEvent;
+	import flash.filesystem.File;
+	import flash.filesystem.FileMode;
+	import flash.filesystem.FileStream;
+
+	public class Test extends Sprite {
+		public function Test() {
+			testResolvePath();
+			testApplicationDirectory();
+			testCreateAndList();
+			testCopyAndMove();
+			testDelete();
+			testAsync();
+		}
+
+		private static function attempt(name:String, operation:Function):void {
+			try {
+				operation();
+				trace(name + ": ok");
+			} catch (error:Error) {
+				trace(name + ": " + error.errorID);
+			}
+		}
+
+		private static function names(files:Array):String {
+			var result:Array = [];
+			for each (var file:File in files) {
+				result.push(file.name + (file.isDirectory ? "/" : ""));
+			}
+			return result.join(", ");
+		}
+
+		private static function writeText(file:File, text:String):void {
+			var stream:FileStream = new FileStream();
+			stream.open(file, FileMode.WRITE);
+			stream.writeUTFBytes(text);
+			stream.close();
+		}
+
+		private static function readText(file:File):String {
+			var stream:FileStream = new FileStream();
+			stream.open(file, FileMode.READ);
+			var text:String = stream.readUTFBytes(stream.bytesAvailable);
+			stream.close();
+			return text;
+		}
+
+		private static function testResolvePath():void {
+			trace("/// resolvePath");
+			var storage:File = File.applicationStorageDirectory;
+			trace(storage.url);
+			trace(storage.resolvePath("saves/slot1.sav").url);
+			trace(storage.resolvePath("saves/../other/./data.sav").url);
+			trace(storage.resolvePath("saves\\backslash.sav").url);
+			trace(storage.resolvePath("with space.txt").url);
+			trace(storage.resolvePath("saves/slot1.sav").parent.url);
+			trace(storage.resolvePath("saves/slot1.sav").name);
+			trace(storage.resolvePath("a/b").resolvePath("/c").url);
+			trace(new File("app-storage:/saves/").resolvePath("slot2.sav").url);
+		}
+
+		private static function testApplicationDirectory():void {
+			trace("/// applicationDirectory");
+			var files:File = File.applicationDirectory.resolvePath("files");
+			trace("exists: " + files.exists);
+			trace("isDirectory: " + files.isDirectory);
+			trace("listing: " + names(files.getDirectoryListing()));
+			trace("hello.txt: " + readText(files.resolvePath("hello.txt")));
+			trace("missing exists: " + files.resolvePath("missing.txt").exists);
+			attempt("write", function():void {
+				writeText(files.resolvePath("new.txt"), "nope");
+			});
+			attempt("createDirectory", function():void {
+				files.resolvePath("dir").createDirectory();
+			});
+			attempt("deleteFile", function():void {
+				files.resolvePath("hello.txt").deleteFile();
+			});
+			trace("hello.txt exists: " + files.resolvePath("hello.txt").exists);
+		}
+
+		private static function testCreateAndList():void {
+			trace("/// createDirectory and getDirectoryListing");
+			var dir:File = File.applicationStorageDirectory.resolvePath("list/nested");
+			trace("exists: " + dir.exists);
+			dir.createDirectory();
+			trace("exists: " + dir.exists);
+			trace("isDirectory: " + dir.isDirectory);
+			writeText(dir.resolvePath("b.txt"), "b");
+			writeText(dir.resolvePath("a.txt"), "a");
+			dir.resolvePath("c").createDirectory();
+			trace("listing: " + names(dir.getDirectoryListing()));
+			trace("parent listing: " + names(dir.parent.getDirectoryListing()));
+			trace("a.txt isDirectory: " + dir.resolvePath("a.txt").isDirectory);
+			attempt("list a file", function():void {
+				dir.resolvePath("a.txt").getDirectoryListing();
+			});
+			attempt("list a missing directory", function():void {
+				dir.resolvePath("missing").getDirectoryListing();
+			});
+		}
+
+		private static function testCopyAndMove():void {
+			trace("/// copyTo and moveTo");
+			var dir:File = File.applicationStorageDirectory.resolvePath("copy");
+			dir.createDirectory();
+			var original:File = dir.resolvePath("original.txt");
+			writeText(original, "original");
+
+			var copy:File = dir.resolvePath("copy.txt");
+			original.copyTo(copy);
+			trace("copy: " + readText(copy));
+			attempt("copy over existing", function():void {
+				original.copyTo(copy);
+			});
+			writeText(original, "changed");
+			original.copyTo(copy, true);
+			trace("copy after overwrite: " + readText(copy));
+
+			var moved:File = dir.resolvePath("moved.txt");
+			copy.moveTo(moved);
+			trace("copy exists: " + copy.exists);
+			trace("moved: " + readText(moved));
+			attempt("move over existing", function():void {
+				original.moveTo(moved);
+			});
+			attempt("move missing", function():void {
+				copy.moveTo(dir.resolvePath("nowhere.txt"));
+			});
+
+			var app:File = File.applicationDirectory.resolvePath("files/hello.txt");
+			app.copyTo(dir.resolvePath("hello.txt"));
+			trace("copied from app: " + readText(dir.resolvePath("hello.txt")));
+			attempt("copy into app", function():void {
+				original.copyTo(File.applicationDirectory.resolvePath("files/original.txt"));
+			});
+
+			var subdir:File = dir.resolvePath("sub");
+			subdir.createDirectory();
+			writeText(subdir.resolvePath("inner.txt"), "inner");
+			subdir.copyTo(dir.resolvePath("sub copy"));
+			trace("copied directory: " + names(dir.resolvePath("sub copy").getDirectoryListing()));
+			attempt("copy directory into itself", function():void {
+				subdir.copyTo(subdir.resolvePath("self"));
+			});
+			subdir.moveTo(dir.resolvePath("sub moved"));
+			trace("listing: " + names(dir.getDirectoryListing()));
+		}
+
+		private static function testDelete():void {
+			trace("/// deleteFile and deleteDirectory");
+			var dir:File = File.applicationStorageDirectory.resolvePath("delete");
+			dir.createDirectory();
+			var file:File = dir.resolvePath("file.txt");
+			writeText(file, "data");
+			file.deleteFile();
+			trace("file exists: " + file.exists);
+			attempt("delete missing file", function():void {
+				file.deleteFile();
+			});
+
+			writeText(file, "data");
+			attempt("delete non-empty directory", function():void {
+				dir.deleteDirectory();
+			});
+			dir.deleteDirectory(true);
+			trace("directory exists: " + dir.exists);
+			attempt("delete storage root", function():void {
+				File.applicationStorageDirectory.deleteDirectory(true);
+			});
+		}
+
+		private static function testAsync():void {
+			var dir:File = File.applicationStorageDirectory.resolvePath("async");
+			dir.createDirectory();
+			var original:File = dir.resolvePath("original.txt");
+			writeText(original, "async");
+			var copy:File = dir.resolvePath("copy.txt");
+
+			original.addEventListener(Event.COMPLETE, function(event:Event):void {
+				original.removeEventListener(Event.COMPLETE, arguments.callee);
+				trace("/// copyToAsync");
+				trace("complete: " + readText(copy));
+
+				dir.addEventListener(FileListEvent.DIRECTORY_LISTING, function(event:FileListEvent):void {
+					trace("/// getDirectoryListingAsync");
+					trace("listing: " + names(event.files));
+
+					copy.addEventListener(Event.COMPLETE, function(event:Event):void {
+						trace("/// deleteFileAsync");
+						trace("copy exists: " + copy.exists);
+
+						copy.addEventListener(IOErrorEvent.IO_ERROR, function(event:IOErrorEvent):void {
+							trace("/// deleteFileAsync missing");
+							trace("ioError: " + event.errorID);
+						});
+						copy.deleteFileAsync();
+					});
+					copy.deleteFileAsync();
+				});
+				dir.getDirectoryListingAsync();
+			});
+			original.copyToAsync(copy);
+		}
+	}
+}
@@ -0,0 +1,218 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.events.IOErrorEvent;
+	import flash.events.ProgressEvent;
+	import flash.filesystem.File;
+	import flash.filesystem.FileMode;
+	import flash.filesystem.FileStream;
+	import flash.utils.ByteArray;
+
+	public class Test extends Sprite {
+		private static var dir:File = File.applicationStorageDirectory.resolvePath("streams");
+
+		// Kept open for the whole test, to check that nothing waits for close().
+		private static var unclosed:FileStream;
+
+		public function Test() {
+			dir.createDirectory();
+			testWrite();
+			testUnclosed();
+			testAppend();
+			testUpdate();
+			testReadOnly();
+			testErrors();
+			testAsyncRead();
+		}
+
+		private static function attempt(name:String, operation:Function):void {
+			try {
+				operation();
+				trace(name + ": ok");
+			} catch (error:Error) {
+				trace(name + ": " + error.errorID);
+			}
+		}
+
+		private static function contents(file:File):String {
+			var stream:FileStream = new FileStream();
+			stream.open(file, FileMode.READ);
+			var bytes:Array = [];
+			while (stream.bytesAvailable > 0) {
+				var byte:uint = stream.readUnsignedByte();
+				bytes.push(byte >= 0x20 && byte < 0x7F ? String.fromCharCode(byte) : "\\x" + byte.toString(16));
+			}
+			stream.close();
+			return bytes.join("") + " (" + file.size + " bytes)";
+		}
+
+		private static function testWrite():void {
+			trace("/// open WRITE");
+			var file:File = dir.resolvePath("write.bin");
+			var stream:FileStream = new FileStream();
+			stream.open(file, FileMode.WRITE);
+			trace("after open: " + contents(file));
+			stream.writeUTFBytes("abc");
+			stream.writeByte(0x21);
+			stream.writeShort(0x4142);
+			stream.writeInt(0x43444546);
+			stream.writeBoolean(true);
+			stream.writeUTF("hi");
+			trace("position: " + stream.position);
+			trace("before close: " + contents(file));
+			stream.close();
+			trace("after close: " + contents(file));
+			attempt("write after close", function():void {
+				stream.writeByte(1);
+			});
+
+			stream.open(file, FileMode.WRITE);
+			trace("reopened: " + contents(file));
+			stream.endian = "littleEndian";
+			stream.writeUnsignedInt(0x64636261);
+			stream.close();
+			trace("little endian: " + contents(file));
+		}
+
+		private static function testUnclosed():void {
+			trace("/// unclosed stream");
+			var file:File = dir.resolvePath("unclosed.txt");
+			unclosed = new FileStream();
+			unclosed.open(file, FileMode.WRITE);
+			var bytes:ByteArray = new ByteArray();
+			bytes.writeUTFBytes("0123456789");
+			unclosed.writeBytes(bytes, 2, 5);
+			trace("written: " + contents(file));
+		}
+
+		private static function testAppend():void {
+			trace("/// open APPEND");
+			var file:File = dir.resolvePath("append.txt");
+			var stream:FileStream = new FileStream();
+			stream.open(file, FileMode.APPEND);
+			trace("created: " + file.exists + ", " + contents(file));
+			stream.writeUTFBytes("one");
+			stream.close();
+
+			stream.open(file, FileMode.APPEND);
+			trace("position: " + stream.position);
+			stream.position = 0;
+			stream.writeUTFBytes(",two");
+			trace("appended: " + contents(file));
+			attempt("read", function():void {
+				stream.readByte();
+			});
+			stream.close();
+		}
+
+		private static function testUpdate():void {
+			trace("/// open UPDATE");
+			var file:File = dir.resolvePath("update.txt");
+			var stream:FileStream = new FileStream();
+			stream.open(file, FileMode.WRITE);
+			stream.writeUTFBytes("hello world");
+			stream.close();
+
+			stream.open(file, FileMode.UPDATE);
+			trace("position: " + stream.position);
+			trace("bytesAvailable: " + stream.bytesAvailable);
+			trace("read: " + stream.readUTFBytes(5));
+			stream.writeUTFBytes("_");
+			trace("overwritten: " + contents(file));
+			stream.position = 13;
+			stream.writeUTFBytes("!");
+			trace("past the end: " + contents(file));
+			stream.position = 5;
+			stream.truncate();
+			trace("truncated: " + contents(file));
+			trace("bytesAvailable: " + stream.bytesAvailable);
+			stream.close();
+		}
+
+		private static function testReadOnly():void {
+			trace("/// open READ");
+			var stream:FileStream = new FileStream();
+			stream.open(File.applicationDirectory.resolvePath("data.txt"), FileMode.READ);
+			trace("read: " + stream.readUTFBytes(stream.bytesAvailable));
+			attempt("write", function():void {
+				stream.writeUTFBytes("nope");
+			});
+			attempt("truncate", function():void {
+				stream.truncate();
+			});
+			stream.close();
+		}
+
+		private static function testErrors():void {
+			trace("/// errors");
+			var stream:FileStream = new FileStream();
+			attempt("read missing file", function():void {
+				stream.open(dir.resolvePath("missing.txt"), FileMode.READ);
+			});
+			attempt("write into app", function():void {
+				stream.open(File.applicationDirectory.resolvePath("new.txt"), FileMode.WRITE);
+			});
+			attempt("write into missing directory", function():void {
+				stream.open(dir.resolvePath("missing/new.txt"), FileMode.WRITE);
+			});
+			attempt("unknown mode", function():void {
+				stream.open(dir.resolvePath("new.txt"), "sideways");
+			});
+			attempt("read without open", function():void {
+				stream.readByte();
+			});
+		}
+
+		private static function testAsyncRead():void {
+			var file:File = dir.resolvePath("async.txt");
+			var stream:FileStream = new FileStream();
+			stream.addEventListener(ProgressEvent.PROGRESS, function(event:ProgressEvent):void {
+				trace("progress: " + event.bytesLoaded + "/" + event.bytesTotal);
+			});
+			stream.addEventListener(Event.COMPLETE, function(event:Event):void {
+				trace("/// openAsync READ");
+				trace("read: " + stream.readUTFBytes(stream.bytesAvailable));
+				stream.close();
+			});
+			stream.addEventListener(Event.CLOSE, function(event:Event):void {
+				trace("close");
+				testAsyncWrite();
+			});
+
+			var writer:FileStream = new FileStream();
+			writer.open(file, FileMode.WRITE);
+			writer.writeUTFBytes("async data");
+			writer.close();
+			stream.openAsync(file, FileMode.READ);
+		}
+
+		private static function testAsyncWrite():void {
+			trace("/// openAsync WRITE and APPEND");
+			var file:File = dir.resolvePath("async-write.txt");
+			var stream:FileStream = new FileStream();
+			stream.openAsync(file, FileMode.WRITE);
+			stream.writeUTFBytes("first");
+			trace("written: " + contents(file));
+			stream.close();
+
+			var appender:FileStream = new FileStream();
+			appender.addEventListener(Event.CLOSE, function(event:Event):void {
+				trace("appended: " + contents(file));
+				testAsyncError();
+			});
+			appender.openAsync(file, FileMode.APPEND);
+			appender.writeUTFBytes(", second");
+			appender.close();
+		}
+
+		private static function testAsyncError():void {
+			trace("/// openAsync missing file");
+			var stream:FileStream = new FileStream();
+			stream.addEventListener(IOErrorEvent.IO_ERROR, function(event:IOErrorEvent):void {
+				trace("ioError: " + event.errorID);
+				trace("unclosed: " + contents(dir.resolvePath("unclosed.txt")));
+			});
+			stream.openAsync(dir.resolvePath("missing.txt"), FileMode.READ);
+		}
+	}
+}
@@ -0,0 +1,208 @@
+package {
+	import flash.data.SQLConnection;
+	import flash.data.SQLMode;
+	import flash.data.SQLResult;
+	import flash.data.SQLStatement;
+	import flash.display.Sprite;
+	import flash.errors.SQLError;
+	import flash.events.SQLErrorEvent;
+	import flash.events.SQLEvent;
+	import flash.filesystem.File;
+	import flash.net.Responder;
+	import flash.utils.ByteArray;
+
+	public class Test extends Sprite {
+		public function Test() {
+			trace("SQLConnection.isSupported: " + SQLConnection.isSupported);
+			testParameters();
+			testTransactions();
+			testFile();
+			testAsync();
+		}
+
+		private static function execute(connection:SQLConnection, text:String, parameters:Object = null, itemClass:Class = null):SQLResult {
+			var statement:SQLStatement = new SQLStatement();
+			statement.sqlConnection = connection;
+			statement.text = text;
+			statement.itemClass = itemClass;
+			for (var name:String in parameters) {
+				statement.parameters[name] = parameters[name];
+			}
+			statement.execute();
+			return statement.getResult();
+		}
+
+		private static function traceRows(result:SQLResult):void {
+			if (result.data == null) {
+				trace("  (no rows)");
+				return;
+			}
+			for each (var row:Object in result.data) {
+				trace("  " + row.name + ", " + row.count);
+			}
+		}
+
+		private static function countRows(connection:SQLConnection):Number {
+			return execute(connection, "SELECT COUNT(*) AS n FROM items").data[0].n;
+		}
+
+		private function testParameters():void {
+			trace("/// Parameters");
+			var connection:SQLConnection = new SQLConnection();
+			connection.open();
+			execute(connection, "CREATE TABLE items (name TEXT, count REAL)");
+
+			var result:SQLResult = execute(connection, "INSERT INTO items VALUES (:name, @count)", {":name": "apple", "@count": 3});
+			trace("rowsAffected: " + result.rowsAffected + ", lastInsertRowID: " + result.lastInsertRowID);
+			execute(connection, "INSERT INTO items VALUES ($name, $count)", {"$name": "pear", "$count": 1.5});
+			execute(connection, "INSERT INTO items VALUES (?, ?)", {0: "plum", 1: null});
+			execute(connection, "INSERT INTO items VALUES (?, ?)", {0: "fig", 1: true});
+			trace("connection.lastInsertRowID: " + connection.lastInsertRowID);
+
+			trace("Rows:");
+			traceRows(execute(connection, "SELECT * FROM items ORDER BY rowid"));
+
+			trace("Rows matching :min:");
+			traceRows(execute(connection, "SELECT * FROM items WHERE count >= :min ORDER BY count", {":min": 1.5}));
+
+			trace("Rows as Item:");
+			result = execute(connection, "SELECT * FROM items WHERE name = ?", {0: "apple"}, Item);
+			trace("  " + (result.data[0] is Item) + " " + result.data[0].name + " " + result.data[0].count);
+
+			var bytes:ByteArray = new ByteArray();
+			bytes.writeUTFBytes("blob");
+			execute(connection, "CREATE TABLE blobs (data BLOB)");
+			execute(connection, "INSERT INTO blobs VALUES (:data)", {":data": bytes});
+			var data:ByteArray = execute(connection, "SELECT data FROM blobs").data[0].data;
+			trace("Blob: " + data.length + " bytes, " + data.toString());
+
+			try {
+				execute(connection, "INSERT INTO items VALUES (:name, :count)", {":nope": "kiwi"});
+			} catch (e:SQLError) {
+				trace("Unknown parameter: " + e.errorID + " " + e.operation + " " + e.details);
+			}
+			connection.close();
+		}
+
+		private function testTransactions():void {
+			trace("/// Transactions");
+			var connection:SQLConnection = new SQLConnection();
+			connection.open();
+			execute(connection, "CREATE TABLE items (name TEXT, count REAL)");
+			trace("inTransaction: " + connection.inTransaction + ", totalChanges: " + connection.totalChanges);
+
+			connection.begin();
+			execute(connection, "INSERT INTO items VALUES ('apple', 1)");
+			execute(connection, "INSERT INTO items VALUES ('pear', 2)");
+			trace("After begin: inTransaction: " + connection.inTransaction + ", rows: " + countRows(connection));
+			connection.rollback();
+			trace("After rollback: inTransaction: " + connection.inTransaction + ", rows: " + countRows(connection) + ", totalChanges: " + connection.totalChanges);
+
+			connection.begin("immediate");
+			execute(connection, "INSERT INTO items VALUES ('plum', 3)");
+			connection.commit();
+			trace("After commit: inTransaction: " + connection.inTransaction + ", rows: " + countRows(connection) + ", totalChanges: " + connection.totalChanges);
+
+			execute(connection, "UPDATE items SET count = count + 1");
+			trace("After update: totalChanges: " + connection.totalChanges);
+
+			try {
+				connection.begin("nope");
+			} catch (e:ArgumentError) {
+				trace("Invalid begin option: " + e.errorID);
+			}
+			try {
+				connection.commit();
+			} catch (e:SQLError) {
+				trace("Commit without a transaction: " + e.errorID + " " + e.operation);
+			}
+
+			connection.begin();
+			execute(connection, "INSERT INTO items VALUES ('fig', 4)");
+			connection.close();
+			trace("After close: connected: " + connection.connected + ", inTransaction: " + connection.inTransaction + ", totalChanges: " + connection.totalChanges);
+			try {
+				connection.commit();
+			} catch (e:Error) {
+				trace("Commit after close: " + e.errorID);
+			}
+		}
+
+		private function testFile():void {
+			trace("/// File");
+			var file:File = File.applicationStorageDirectory.resolvePath("test.db");
+			var connection:SQLConnection = new SQLConnection();
+			connection.open(file);
+			execute(connection, "CREATE TABLE items (name TEXT, count REAL)");
+			connection.begin();
+			execute(connection, "INSERT INTO items VALUES ('apple', 1)");
+			connection.commit();
+			connection.begin();
+			execute(connection, "INSERT INTO items VALUES ('pear', 2)");
+			connection.close();
+
+			connection.open(file, SQLMode.READ);
+			trace("Rows after reopening:");
+			traceRows(execute(connection, "SELECT * FROM items"));
+			try {
+				execute(connection, "INSERT INTO items VALUES ('plum', 3)");
+			} catch (e:SQLError) {
+				trace("Insert into read-only database: " + e.errorID);
+			}
+			connection.close();
+		}
+
+		private function testAsync():void {
+			trace("/// Async");
+			var connection:SQLConnection = new SQLConnection();
+			var statement:SQLStatement = new SQLStatement();
+
+			connection.addEventListener(SQLEvent.OPEN, function(event:SQLEvent):void {
+				trace("Event: " + event.type + ", connected: " + connection.connected);
+
+				statement.sqlConnection = connection;
+				statement.text = "CREATE TABLE items (name TEXT, count REAL)";
+				statement.execute();
+				trace("executing: " + statement.executing);
+			});
+			statement.addEventListener(SQLEvent.RESULT, function(event:SQLEvent):void {
+				trace("Event: " + event.type + ", executing: " + statement.executing + ", rowsAffected: " + statement.getResult().rowsAffected);
+				statement.removeEventListener(SQLEvent.RESULT, arguments.callee);
+
+				connection.begin(null, new Responder(function(event:SQLEvent):void {
+					trace("Responder result: " + event.type + ", inTransaction: " + connection.inTransaction);
+
+					statement.text = "INSERT INTO items VALUES (:name, :count)";
+					statement.parameters[":name"] = "apple";
+					statement.parameters[":count"] = 2;
+					statement.execute(-1, new Responder(function(result:SQLResult):void {
+						trace("Responder result: rowsAffected: " + result.rowsAffected + ", getResult: " + statement.getResult());
+
+						statement.clearParameters();
+						statement.text = "SELECT * FROM nope";
+						statement.execute();
+					}));
+				}));
+			});
+			statement.addEventListener(SQLErrorEvent.ERROR, function(event:SQLErrorEvent):void {
+				trace("Event: " + event.type + ", " + event.error.errorID + " " + event.error.operation + " " + event.error.details);
+				connection.commit();
+			});
+			connection.addEventListener(SQLEvent.COMMIT, function(event:SQLEvent):void {
+				trace("Event: " + event.type + ", inTransaction: " + connection.inTransaction);
+				connection.close();
+			});
+			connection.addEventListener(SQLEvent.CLOSE, function(event:SQLEvent):void {
+				trace("Event: " + event.type + ", connected: " + connection.connected);
+			});
+
+			connection.openAsync();
+			trace("After openAsync: connected: " + connection.connected);
+		}
+	}
+}
+
+class Item {
+	public var name:String;
+	public var count:Number;
+}
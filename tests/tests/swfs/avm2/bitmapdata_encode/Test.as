@@ -0,0 +1,166 @@
+package {
+	import flash.display.Bitmap;
+	import flash.display.BitmapData;
+	import flash.display.JPEGEncoderOptions;
+	import flash.display.JPEGXREncoderOptions;
+	import flash.display.Loader;
+	import flash.display.PNGEncoderOptions;
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.geom.Rectangle;
+	import flash.utils.ByteArray;
+
+	public class Test extends Sprite {
+		static var COLORS: Array = [0xFFFF0000, 0xFF00FF00, 0xFF0000FF, 0x80FFFFFF, 0x00000000, 0xFF000000];
+
+		var transparent: BitmapData;
+		var opaque: BitmapData;
+		var pending: Array = [];
+
+		public function Test() {
+			transparent = new BitmapData(8, 6, true, 0);
+			for (var y = 0; y < 6; y++) {
+				for (var x = 0; x < 8; x++) {
+					transparent.setPixel32(x, y, COLORS[(x + y) % COLORS.length]);
+				}
+			}
+			opaque = new BitmapData(16, 16, false, 0);
+			for (var y = 0; y < 16; y++) {
+				for (var x = 0; x < 16; x++) {
+					opaque.setPixel(x, y, (x * 16) << 16 | (y * 16) << 8 | 0x80);
+				}
+			}
+
+			trace("/// PNG");
+			var png: ByteArray = transparent.encode(transparent.rect, new PNGEncoderOptions());
+			trace("// signature");
+			trace(bytes(png, 0, 8));
+			trace("// position == length");
+			trace(png.position == png.length);
+			var fast: ByteArray = transparent.encode(transparent.rect, new PNGEncoderOptions(true));
+			trace("// fastCompression signature");
+			trace(bytes(fast, 0, 8));
+
+			trace("/// PNG into an existing ByteArray");
+			var existing: ByteArray = new ByteArray();
+			existing.writeUTFBytes("abc");
+			existing.position = 1;
+			var returned: ByteArray = transparent.encode(new Rectangle(2, 2, 3, 2), new PNGEncoderOptions(), existing);
+			trace("// returned == existing");
+			trace(returned == existing);
+			trace("// first bytes");
+			trace(bytes(existing, 0, 5));
+			trace("// position == length");
+			trace(existing.position == existing.length);
+			var region: ByteArray = new ByteArray();
+			existing.position = 1;
+			existing.readBytes(region);
+			pending.push(["PNG of a region", region, new Rectangle(2, 2, 3, 2), transparent, 0]);
+
+			trace("/// JPEG");
+			var jpeg: ByteArray = opaque.encode(opaque.rect, new JPEGEncoderOptions(100));
+			trace("// start and end markers");
+			trace(bytes(jpeg, 0, 2), bytes(jpeg, jpeg.length - 2, 2));
+			var low: ByteArray = opaque.encode(opaque.rect, new JPEGEncoderOptions(1));
+			trace("// quality 1 is smaller than quality 100");
+			trace(low.length < jpeg.length);
+			var transparentJpeg: ByteArray = transparent.encode(transparent.rect, new JPEGEncoderOptions());
+			trace("// transparent bitmaps are encoded too");
+			trace(bytes(transparentJpeg, 0, 2));
+
+			trace("/// JPEG XR");
+			var jxr: ByteArray = opaque.encode(opaque.rect, new JPEGXREncoderOptions());
+			trace("// container header");
+			trace(bytes(jxr, 0, 8));
+			trace("// pixel format is 24bpp BGR");
+			trace(bytes(jxr, 8, 16));
+			trace("// position == length");
+			trace(jxr.position == jxr.length);
+			var codestream: ByteArray = new ByteArray();
+			jxr.position = 0x86;
+			jxr.readBytes(codestream, 0, 7);
+			trace("// codestream signature");
+			trace(codestream.toString());
+			var lossless: ByteArray = opaque.encode(opaque.rect, new JPEGXREncoderOptions(0));
+			var lossy: ByteArray = opaque.encode(opaque.rect, new JPEGXREncoderOptions(100));
+			trace("// quantization 100 is smaller than quantization 0");
+			trace(lossy.length < lossless.length);
+			var noisy: BitmapData = new BitmapData(32, 32, false, 0);
+			for (var y = 0; y < 32; y++) {
+				for (var x = 0; x < 32; x++) {
+					var v: uint = (x * 97 + y * 61 + x * y * 13) & 0xFF;
+					noisy.setPixel(x, y, v << 16 | (v ^ 0x5A) << 8 | ((v * 3) & 0xFF));
+				}
+			}
+			var untrimmed: ByteArray = noisy.encode(noisy.rect, new JPEGXREncoderOptions(0));
+			var trimmed: ByteArray = noisy.encode(noisy.rect, new JPEGXREncoderOptions(0, "auto", 4));
+			trace("// trimFlexBits 4 is smaller than trimFlexBits 0");
+			trace(trimmed.length < untrimmed.length);
+			var transparentJxr: ByteArray = transparent.encode(transparent.rect, new JPEGXREncoderOptions());
+			trace("// transparent pixel format is 32bpp BGRA");
+			trace(bytes(transparentJxr, 8, 16));
+			trace("// transparent size");
+			trace(bytes(transparentJxr, 0x42, 4), bytes(transparentJxr, 0x4e, 4));
+
+			trace("/// Errors");
+			try {
+				transparent.encode(transparent.rect, {});
+			} catch (e: Error) {
+				trace(e);
+			}
+			try {
+				transparent.encode(transparent.rect, null);
+			} catch (e: Error) {
+				trace(e);
+			}
+
+			pending.push(["PNG", png, transparent.rect, transparent, 0]);
+			pending.push(["fastCompression PNG", fast, transparent.rect, transparent, 0]);
+			pending.push(["JPEG", jpeg, opaque.rect, opaque, 8]);
+			pending.push(["lossless JPEG XR", lossless, opaque.rect, opaque, 0]);
+			pending.push(["JPEG XR", jxr, opaque.rect, opaque, 32]);
+			pending.push(["transparent lossless JPEG XR", transparent.encode(transparent.rect, new JPEGXREncoderOptions(0)), transparent.rect, transparent, 0]);
+			loadNext();
+		}
+
+		static function bytes(data: ByteArray, start: uint, count: uint): String {
+			var result: Array = [];
+			for (var i = start; i < start + count; i++) {
+				result.push(data[i]);
+			}
+			return result.join(",");
+		}
+
+		function loadNext(): void {
+			if (pending.length == 0) {
+				trace("/// Done");
+				return;
+			}
+			var test: Array = pending.shift();
+			var loader: Loader = new Loader();
+			loader.contentLoaderInfo.addEventListener(Event.COMPLETE, function(event: Event): void {
+				var decoded: BitmapData = Bitmap(loader.content).bitmapData;
+				var rect: Rectangle = test[2];
+				var source: BitmapData = test[3];
+				trace("/// Decoded " + test[0]);
+				trace("// size");
+				trace(decoded.width, decoded.height);
+				var maxDifference: uint = 0;
+				for (var y = 0; y < rect.height; y++) {
+					for (var x = 0; x < rect.width; x++) {
+						var expected: uint = source.getPixel32(rect.x + x, rect.y + y);
+						var actual: uint = decoded.getPixel32(x, y);
+						for (var shift = 0; shift < 32; shift += 8) {
+							var difference: int = ((expected >>> shift) & 0xFF) - ((actual >>> shift) & 0xFF);
+							maxDifference = Math.max(maxDifference, Math.abs(difference));
+						}
+					}
+				}
+				trace("// pixels differ by at most " + test[4]);
+				trace(maxDifference <= test[4]);
+				loadNext();
+			});
+			loader.loadBytes(test[1]);
+		}
+	}
+}
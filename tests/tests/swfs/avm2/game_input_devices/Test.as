@@ -0,0 +1,61 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.events.GameInputEvent;
+	import flash.ui.GameInput;
+	import flash.ui.GameInputControl;
+	import flash.ui.GameInputDevice;
+
+	public class Test extends Sprite {
+		private var frame:int = 0;
+		private var gameInput:GameInput;
+
+		public function Test() {
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onEnterFrame(e:Event):void {
+			frame++;
+			if (frame == 2) {
+				// Gamepad 1 was connected before GameInput was first used.
+				trace("GameInput.isSupported: " + GameInput.isSupported);
+				trace("GameInput.numDevices: " + GameInput.numDevices);
+				traceDevice(GameInput.getDeviceAt(0));
+
+				gameInput = new GameInput();
+				gameInput.addEventListener(GameInputEvent.DEVICE_ADDED, onDeviceAdded);
+				gameInput.addEventListener(GameInputEvent.DEVICE_REMOVED, onDeviceRemoved);
+			}
+		}
+
+		private function traceDevice(device:GameInputDevice):void {
+			trace("device.id: " + device.id);
+			trace("device.name: " + device.name);
+			trace("device.enabled: " + device.enabled);
+			trace("device.numControls: " + device.numControls);
+			for (var i:int = 0; i < device.numControls; i++) {
+				var control:GameInputControl = device.getControlAt(i);
+				trace("  " + control.id + " [" + control.minValue + ", " + control.maxValue + "] = " + control.value);
+			}
+		}
+
+		private function onDeviceAdded(e:GameInputEvent):void {
+			trace("deviceAdded: " + e.device.id);
+			trace("GameInput.numDevices: " + GameInput.numDevices);
+			e.device.enabled = true;
+			for (var i:int = 0; i < e.device.numControls; i++) {
+				e.device.getControlAt(i).addEventListener(Event.CHANGE, onControlChange);
+			}
+		}
+
+		private function onDeviceRemoved(e:GameInputEvent):void {
+			trace("deviceRemoved: " + e.device.id);
+			trace("GameInput.numDevices: " + GameInput.numDevices);
+		}
+
+		private function onControlChange(e:Event):void {
+			var control:GameInputControl = e.target as GameInputControl;
+			trace("change: " + control.device.id + " " + control.id + " = " + control.value);
+		}
+	}
+}
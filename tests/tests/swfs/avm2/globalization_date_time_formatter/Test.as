@@ -0,0 +1,40 @@
+package {
+	import flash.display.Sprite;
+	import flash.globalization.DateTimeFormatter;
+	import flash.globalization.DateTimeNameContext;
+	import flash.globalization.DateTimeNameStyle;
+	import flash.globalization.DateTimeStyle;
+
+	public class Test extends Sprite {
+		public function Test() {
+			var date:Date = new Date(Date.UTC(2024, 0, 5, 15, 4, 9));
+			var styles:Array = [DateTimeStyle.LONG, DateTimeStyle.MEDIUM, DateTimeStyle.SHORT, DateTimeStyle.NONE];
+
+			for each (var name:String in ["en-US", "en-GB", "fr-FR", "de-DE", "ru-RU", "ja-JP", "ko-KR", "zh-TW", "xx-YY"]) {
+				var formatter:DateTimeFormatter = new DateTimeFormatter(name);
+				trace("// " + name);
+				trace("lastOperationStatus: " + formatter.lastOperationStatus);
+				trace("actualLocaleIDName: " + formatter.actualLocaleIDName);
+				trace("firstWeekday: " + formatter.getFirstWeekday());
+				for each (var dateStyle:String in styles) {
+					for each (var timeStyle:String in styles) {
+						if (dateStyle == DateTimeStyle.NONE && timeStyle == DateTimeStyle.NONE) {
+							continue;
+						}
+						formatter.setDateTimeStyles(dateStyle, timeStyle);
+						trace(dateStyle + "/" + timeStyle + ": " + escape(formatter.getDateTimePattern()) + " -> " + escape(formatter.formatUTC(date)));
+					}
+				}
+				trace("months: " + formatter.getMonthNames().join(","));
+				trace("months (short, format): " + formatter.getMonthNames(DateTimeNameStyle.SHORT_ABBREVIATION, DateTimeNameContext.FORMAT).join(","));
+				trace("weekdays: " + formatter.getWeekdayNames().join(","));
+				trace("weekdays (short): " + formatter.getWeekdayNames(DateTimeNameStyle.SHORT_ABBREVIATION).join(","));
+				trace("");
+			}
+
+			var custom:DateTimeFormatter = new DateTimeFormatter("en-US");
+			custom.setDateTimePattern("yyyy-MM-dd'T'HH:mm:ss EEE 'o''clock' a W");
+			trace("custom: " + custom.formatUTC(date));
+		}
+	}
+}
@@ -0,0 +1,54 @@
+package {
+	import flash.display.Sprite;
+	import flash.globalization.CurrencyFormatter;
+	import flash.globalization.LocaleID;
+	import flash.globalization.NumberFormatter;
+
+	public class Test extends Sprite {
+		public function Test() {
+			for each (var name:String in ["en-US", "fr-FR", "de-DE", "hi-IN", "ja-JP", "nl-NL", "en-ZA", "xx-YY"]) {
+				var nf:NumberFormatter = new NumberFormatter(name);
+				trace("// " + name);
+				trace("lastOperationStatus: " + nf.lastOperationStatus);
+				trace("actualLocaleIDName: " + nf.actualLocaleIDName);
+				trace("decimalSeparator: " + escape(nf.decimalSeparator));
+				trace("groupingSeparator: " + escape(nf.groupingSeparator));
+				trace("groupingPattern: " + nf.groupingPattern);
+				trace("negativeNumberFormat: " + nf.negativeNumberFormat);
+				trace("formatNumber(-1234567.891): " + escape(nf.formatNumber(-1234567.891)));
+				trace("formatInt(-42): " + escape(nf.formatInt(-42)));
+				trace("formatUint(1000): " + escape(nf.formatUint(1000)));
+				trace("parseNumber(formatNumber(9876.5)): " + nf.parseNumber(nf.formatNumber(9876.5)));
+
+				var cf:CurrencyFormatter = new CurrencyFormatter(name);
+				trace("currencyISOCode: " + cf.currencyISOCode);
+				trace("currencySymbol: " + escape(cf.currencySymbol));
+				trace("fractionalDigits: " + cf.fractionalDigits);
+				trace("positiveCurrencyFormat: " + cf.positiveCurrencyFormat);
+				trace("negativeCurrencyFormat: " + cf.negativeCurrencyFormat);
+				trace("format(1234.5, true): " + escape(cf.format(1234.5, true)));
+				trace("format(-1234.5): " + escape(cf.format(-1234.5)));
+				trace("");
+			}
+
+			var formatter:NumberFormatter = new NumberFormatter(LocaleID.DEFAULT);
+			trace("// " + LocaleID.DEFAULT);
+			trace("actualLocaleIDName: " + formatter.actualLocaleIDName);
+			formatter.negativeNumberFormat = 0;
+			formatter.fractionalDigits = 1;
+			formatter.groupingPattern = "3;2;*";
+			trace("formatNumber(-1234567.891): " + formatter.formatNumber(-1234567.891));
+			formatter.trailingZeros = false;
+			formatter.leadingZero = false;
+			trace("formatNumber(0.5): " + formatter.formatNumber(0.5));
+			trace("formatNumber(2): " + formatter.formatNumber(2));
+
+			formatter = new NumberFormatter("en-US");
+			var result:* = formatter.parse("  -1,234.5 units");
+			trace("parse: " + result.value + " " + result.startIndex + " " + result.endIndex);
+			trace("lastOperationStatus: " + formatter.lastOperationStatus);
+			trace("parseNumber(\"abc\"): " + formatter.parseNumber("abc"));
+			trace("lastOperationStatus: " + formatter.lastOperationStatus);
+		}
+	}
+}
@@ -0,0 +1,94 @@
+package {
+
+import flash.display.GraphicsPathCommand;
+import flash.display.GraphicsPathWinding;
+import flash.display.MovieClip;
+import flash.display.Shape;
+
+[SWF(width="160", height="120", backgroundColor="#000000")]
+public class Test extends MovieClip {
+    public function Test() {
+        for each (var winding:String in [GraphicsPathWinding.EVEN_ODD, GraphicsPathWinding.NON_ZERO]) {
+            var y:int = winding == GraphicsPathWinding.EVEN_ODD ? 0 : 60;
+
+            // Two overlapping squares drawn the same way round.
+            var shape:Shape = testShape(0, y, 0xFF0000);
+            shape.graphics.drawPath(squares(), Vector.<Number>([
+                5, 5, 35, 5, 35, 35, 5, 35, 5, 5,
+                15, 15, 45, 15, 45, 45, 15, 45, 15, 15
+            ]), winding);
+            shape.graphics.endFill();
+            testHit(winding + " squares", shape, 25, 25);
+
+            // The same squares drawn opposite ways round.
+            shape = testShape(50, y, 0x00FF00);
+            shape.graphics.drawPath(squares(), Vector.<Number>([
+                5, 5, 35, 5, 35, 35, 5, 35, 5, 5,
+                15, 15, 15, 45, 45, 45, 45, 15, 15, 15
+            ]), winding);
+            shape.graphics.endFill();
+            testHit(winding + " reversed squares", shape, 25, 25);
+
+            // Separate paths don't affect each other's winding, even with the
+            // same fill.
+            shape = testShape(100, y, 0x0000FF);
+            shape.graphics.drawPath(square(), Vector.<Number>([
+                5, 5, 35, 5, 35, 35, 5, 35, 5, 5
+            ]), winding);
+            shape.graphics.drawPath(square(), Vector.<Number>([
+                15, 15, 45, 15, 45, 45, 15, 45, 15, 15
+            ]), winding);
+            shape.graphics.endFill();
+            testHit(winding + " separate paths", shape, 25, 25);
+        }
+
+        // A path drawn with lineTo after drawPath uses the fill's own rule again.
+        shape = new Shape();
+        shape.graphics.beginFill(0xFFFFFF);
+        shape.graphics.drawPath(square(), Vector.<Number>([
+            0, 0, 10, 0, 10, 10, 0, 10, 0, 0
+        ]), GraphicsPathWinding.NON_ZERO);
+        shape.graphics.moveTo(20, 20);
+        shape.graphics.lineTo(40, 20);
+        shape.graphics.lineTo(40, 40);
+        shape.graphics.lineTo(20, 40);
+        shape.graphics.moveTo(25, 25);
+        shape.graphics.lineTo(35, 25);
+        shape.graphics.lineTo(35, 35);
+        shape.graphics.lineTo(25, 35);
+        shape.graphics.endFill();
+        testHit("lineTo after drawPath", shape, 30, 30);
+    }
+
+    private function square():Vector.<int> {
+        return Vector.<int>([
+            GraphicsPathCommand.MOVE_TO,
+            GraphicsPathCommand.LINE_TO,
+            GraphicsPathCommand.LINE_TO,
+            GraphicsPathCommand.LINE_TO,
+            GraphicsPathCommand.LINE_TO
+        ]);
+    }
+
+    private function squares():Vector.<int> {
+        var commands:Vector.<int> = square();
+        for each (var command:int in square()) {
+            commands.push(command);
+        }
+        return commands;
+    }
+
+    private function testShape(x:int, y:int, color:uint):Shape {
+        var shape:Shape = new Shape();
+        shape.x = x;
+        shape.y = y;
+        shape.graphics.beginFill(color);
+        addChild(shape);
+        return shape;
+    }
+
+    private function testHit(name:String, shape:Shape, x:Number, y:Number):void {
+        trace(name + ": " + shape.hitTestPoint(shape.x + x, shape.y + y, true));
+    }
+}
+}
@@ -0,0 +1,85 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.Shape;
+	import flash.display.Sprite;
+	import flash.geom.Rectangle;
+
+	public class Test extends Sprite {
+		private var texture:BitmapData;
+
+		public function Test() {
+			// A texture with a red, green, blue and yellow quarter.
+			texture = new BitmapData(64, 64, false, 0);
+			texture.fillRect(new Rectangle(0, 0, 32, 32), 0xFF0000);
+			texture.fillRect(new Rectangle(32, 0, 32, 32), 0x00FF00);
+			texture.fillRect(new Rectangle(0, 32, 32, 32), 0x0000FF);
+			texture.fillRect(new Rectangle(32, 32, 32, 32), 0xFFFF00);
+
+			trace("// A square mapped onto the whole texture");
+			var square:Vector.<Number> = Vector.<Number>([0, 0, 100, 0, 0, 100, 100, 100]);
+			var indices:Vector.<int> = Vector.<int>([0, 1, 2, 1, 3, 2]);
+			var result:BitmapData = draw(square, indices, Vector.<Number>([0, 0, 1, 0, 0, 1, 1, 1]));
+			sample(result, 25, 25);
+			sample(result, 75, 25);
+			sample(result, 25, 75);
+			sample(result, 75, 75);
+
+			trace("// A square mapped onto the top left quarter of the texture");
+			result = draw(square, indices, Vector.<Number>([0, 0, 0.5, 0, 0, 0.5, 0.5, 0.5]));
+			sample(result, 10, 10);
+			sample(result, 90, 90);
+
+			trace("// A square mapped onto the texture upside down");
+			result = draw(square, indices, Vector.<Number>([0, 1, 1, 1, 0, 0, 1, 0]));
+			sample(result, 25, 25);
+			sample(result, 75, 75);
+
+			// A trapezoid whose top edge is twice as far away as its bottom edge.
+			// With perspective, the middle of the texture is a third of the way down.
+			var trapezoid:Vector.<Number> = Vector.<Number>([30, 0, 70, 0, 0, 100, 100, 100]);
+
+			trace("// A trapezoid without T coordinates");
+			result = draw(trapezoid, indices, Vector.<Number>([0, 0, 1, 0, 0, 1, 1, 1]));
+			sample(result, 40, 30);
+			sample(result, 40, 42);
+			sample(result, 35, 60);
+			sample(result, 65, 60);
+
+			trace("// A trapezoid with T coordinates");
+			result = draw(trapezoid, indices, Vector.<Number>([0, 0, 0.5, 1, 0, 0.5, 0, 1, 1, 1, 1, 1]));
+			sample(result, 40, 30);
+			sample(result, 40, 42);
+			sample(result, 35, 60);
+			sample(result, 65, 60);
+
+			trace("// A square with a solid fill ignores uvtData");
+			var shape:Shape = new Shape();
+			shape.graphics.beginFill(0xFF00FF);
+			shape.graphics.drawTriangles(square, indices, Vector.<Number>([0, 0, 1, 0, 0, 1, 1, 1]));
+			shape.graphics.endFill();
+			result = new BitmapData(100, 100, false, 0xFFFFFF);
+			result.draw(shape);
+			sample(result, 25, 25);
+			sample(result, 75, 75);
+		}
+
+		private function draw(vertices:Vector.<Number>, indices:Vector.<int>, uvtData:Vector.<Number>):BitmapData {
+			var shape:Shape = new Shape();
+			shape.graphics.beginBitmapFill(texture, null, false, false);
+			shape.graphics.drawTriangles(vertices, indices, uvtData);
+			shape.graphics.endFill();
+
+			var result:BitmapData = new BitmapData(100, 100, false, 0xFFFFFF);
+			result.draw(shape);
+			return result;
+		}
+
+		private static function sample(result:BitmapData, x:int, y:int):void {
+			var color:String = result.getPixel(x, y).toString(16);
+			while (color.length < 6) {
+				color = "0" + color;
+			}
+			trace("(" + x + ", " + y + "): " + color);
+		}
+	}
+}
@@ -0,0 +1,116 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.GradientType;
+	import flash.display.GraphicsBitmapFill;
+	import flash.display.GraphicsEndFill;
+	import flash.display.GraphicsGradientFill;
+	import flash.display.GraphicsPath;
+	import flash.display.GraphicsSolidFill;
+	import flash.display.GraphicsStroke;
+	import flash.display.IGraphicsData;
+	import flash.display.Shape;
+	import flash.display.Sprite;
+	import flash.geom.Matrix;
+	import flash.utils.getQualifiedClassName;
+
+	public class Test extends Sprite {
+		public function Test() {
+			trace("// Solid fill");
+			var shape:Shape = new Shape();
+			shape.graphics.beginFill(0xFF0000, 0.5);
+			shape.graphics.drawRect(10, 20, 30, 40);
+			shape.graphics.endFill();
+			dump(shape.graphics.readGraphicsData());
+
+			trace("// Stroke with a curve");
+			shape = new Shape();
+			shape.graphics.lineStyle(2, 0x00FF00, 1, true, "normal", "none", "miter", 5);
+			shape.graphics.moveTo(0, 0);
+			shape.graphics.curveTo(10, 0, 10, 10);
+			shape.graphics.lineTo(0, 10);
+			dump(shape.graphics.readGraphicsData());
+
+			trace("// Gradient fill");
+			shape = new Shape();
+			var matrix:Matrix = new Matrix();
+			matrix.createGradientBox(100, 100);
+			shape.graphics.beginGradientFill(GradientType.LINEAR, [0xFF0000, 0x0000FF], [1, 0.5], [0, 255], matrix);
+			shape.graphics.drawRect(0, 0, 100, 100);
+			shape.graphics.endFill();
+			dump(shape.graphics.readGraphicsData());
+
+			trace("// Bitmap fill");
+			var bitmapData:BitmapData = new BitmapData(10, 10, false, 0x123456);
+			shape = new Shape();
+			shape.graphics.beginBitmapFill(bitmapData, new Matrix(2, 0, 0, 2, 5, 5), false, true);
+			shape.graphics.drawRect(0, 0, 20, 20);
+			shape.graphics.endFill();
+			var data:Vector.<IGraphicsData> = shape.graphics.readGraphicsData();
+			dump(data);
+			trace("same BitmapData: " + (GraphicsBitmapFill(data[0]).bitmapData === bitmapData));
+
+			trace("// Bitmap fill after copyFrom");
+			var copy:Shape = new Shape();
+			copy.graphics.copyFrom(shape.graphics);
+			data = copy.graphics.readGraphicsData();
+			trace("same BitmapData: " + (GraphicsBitmapFill(data[0]).bitmapData === bitmapData));
+
+			trace("// After clear");
+			shape.graphics.clear();
+			dump(shape.graphics.readGraphicsData());
+
+			trace("// Round trip through drawGraphicsData");
+			shape = new Shape();
+			shape.graphics.beginFill(0x0000FF);
+			shape.graphics.drawRect(0, 0, 5, 5);
+			shape.graphics.endFill();
+			copy = new Shape();
+			copy.graphics.drawGraphicsData(shape.graphics.readGraphicsData());
+			dump(copy.graphics.readGraphicsData());
+
+			trace("// Children");
+			var sprite:Sprite = new Sprite();
+			sprite.graphics.beginFill(0xFFFFFF);
+			sprite.graphics.drawRect(0, 0, 1, 1);
+			sprite.graphics.endFill();
+			var child:Shape = new Shape();
+			child.x = 100;
+			child.y = 50;
+			child.graphics.beginFill(0x000000);
+			child.graphics.drawRect(0, 0, 2, 2);
+			child.graphics.endFill();
+			sprite.addChild(child);
+			trace("recurse = true");
+			dump(sprite.graphics.readGraphicsData(true));
+			trace("recurse = false");
+			dump(sprite.graphics.readGraphicsData(false));
+		}
+
+		private static function dump(data:Vector.<IGraphicsData>):void {
+			for each (var item:IGraphicsData in data) {
+				var name:String = getQualifiedClassName(item);
+				if (item is GraphicsSolidFill) {
+					var solid:GraphicsSolidFill = GraphicsSolidFill(item);
+					trace(name + " color=" + solid.color.toString(16) + " alpha=" + solid.alpha);
+				} else if (item is GraphicsGradientFill) {
+					var gradient:GraphicsGradientFill = GraphicsGradientFill(item);
+					trace(name + " type=" + gradient.type + " colors=" + gradient.colors + " alphas=" + gradient.alphas + " ratios=" + gradient.ratios + " matrix=" + gradient.matrix + " spread=" + gradient.spreadMethod + " interpolation=" + gradient.interpolationMethod + " focal=" + gradient.focalPointRatio);
+				} else if (item is GraphicsBitmapFill) {
+					var bitmap:GraphicsBitmapFill = GraphicsBitmapFill(item);
+					trace(name + " size=" + bitmap.bitmapData.width + "x" + bitmap.bitmapData.height + " matrix=" + bitmap.matrix + " repeat=" + bitmap.repeat + " smooth=" + bitmap.smooth);
+				} else if (item is GraphicsStroke) {
+					var stroke:GraphicsStroke = GraphicsStroke(item);
+					trace(name + " thickness=" + stroke.thickness + " pixelHinting=" + stroke.pixelHinting + " scaleMode=" + stroke.scaleMode + " caps=" + stroke.caps + " joints=" + stroke.joints + " miterLimit=" + stroke.miterLimit);
+					if (stroke.fill is GraphicsSolidFill) {
+						trace("  fill color=" + GraphicsSolidFill(stroke.fill).color.toString(16));
+					}
+				} else if (item is GraphicsPath) {
+					var path:GraphicsPath = GraphicsPath(item);
+					trace(name + " commands=" + path.commands + " data=" + path.data + " winding=" + path.winding);
+				} else {
+					trace(name);
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,26 @@
+package {
+import flash.display.Sprite;
+import flash.events.KeyboardEvent;
+import flash.events.MouseEvent;
+
+[SWF(width="100", height="100")]
+public class Test extends Sprite {
+    public function Test() {
+        var sprite:Sprite = new Sprite();
+        sprite.graphics.beginFill(0xFF0000);
+        sprite.graphics.drawRect(20, 20, 60, 60);
+        addChild(sprite);
+
+        for each (var eventName in [MouseEvent.ROLL_OVER, MouseEvent.ROLL_OUT, MouseEvent.MOUSE_DOWN, MouseEvent.MOUSE_UP]) {
+            sprite.addEventListener(eventName, function(event:MouseEvent):void {
+                trace(event.type);
+            });
+        }
+        for each (eventName in [KeyboardEvent.KEY_DOWN, KeyboardEvent.KEY_UP]) {
+            stage.addEventListener(eventName, function(event:KeyboardEvent):void {
+                trace(event.type + " " + event.keyCode);
+            });
+        }
+    }
+}
+}
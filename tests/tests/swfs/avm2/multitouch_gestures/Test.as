@@ -0,0 +1,87 @@
+package {
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.events.GestureEvent;
+    import flash.events.PressAndTapGestureEvent;
+    import flash.events.TouchEvent;
+    import flash.events.TransformGestureEvent;
+    import flash.ui.Multitouch;
+    import flash.ui.MultitouchInputMode;
+
+    public class Test extends Sprite {
+        private var frame:int = 0;
+
+        public function Test() {
+            trace("supportsTouchEvents: " + Multitouch.supportsTouchEvents);
+            trace("supportsGestureEvents: " + Multitouch.supportsGestureEvents);
+            trace("supportedGestures: " + Multitouch.supportedGestures);
+            trace("inputMode: " + Multitouch.inputMode);
+
+            var box:Sprite = new Sprite();
+            box.name = "box";
+            box.graphics.beginFill(0xFF0000);
+            box.graphics.drawRect(0, 0, 100, 100);
+            box.graphics.endFill();
+            box.x = 50;
+            box.y = 50;
+            addChild(box);
+
+            for each (var type:String in [
+                TouchEvent.TOUCH_BEGIN,
+                TouchEvent.TOUCH_MOVE,
+                TouchEvent.TOUCH_END,
+                TouchEvent.TOUCH_TAP
+            ]) {
+                box.addEventListener(type, onTouch);
+            }
+            for each (type in [
+                TransformGestureEvent.GESTURE_ZOOM,
+                TransformGestureEvent.GESTURE_SWIPE
+            ]) {
+                box.addEventListener(type, onTransformGesture);
+            }
+            box.addEventListener(GestureEvent.GESTURE_TWO_FINGER_TAP, onGesture);
+            box.addEventListener(PressAndTapGestureEvent.GESTURE_PRESS_AND_TAP, onPressAndTap);
+
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+
+        private function onEnterFrame(event:Event):void {
+            frame++;
+            if (frame == 3) {
+                Multitouch.inputMode = MultitouchInputMode.TOUCH_POINT;
+                trace("inputMode: " + Multitouch.inputMode);
+            }
+        }
+
+        private function onTouch(event:TouchEvent):void {
+            trace(event.type + ": id=" + event.touchPointID +
+                  " primary=" + event.isPrimaryTouchPoint +
+                  " local=" + event.localX + "," + event.localY +
+                  " stage=" + event.stageX + "," + event.stageY +
+                  " target=" + event.target.name);
+        }
+
+        private function onTransformGesture(event:TransformGestureEvent):void {
+            trace(event.type + ": phase=" + event.phase +
+                  " local=" + event.localX + "," + event.localY +
+                  " scale=" + event.scaleX + "," + event.scaleY +
+                  " offset=" + event.offsetX + "," + event.offsetY);
+        }
+
+        private function onGesture(event:GestureEvent):void {
+            trace(event.type + ": phase=" + event.phase +
+                  " local=" + event.localX + "," + event.localY +
+                  " stage=" + event.stageX + "," + event.stageY +
+                  " target=" + event.target.name);
+        }
+
+        private function onPressAndTap(event:PressAndTapGestureEvent):void {
+            trace(event.type + ": phase=" + event.phase +
+                  " local=" + event.localX + "," + event.localY +
+                  " tapLocal=" + event.tapLocalX + "," + event.tapLocalY +
+                  " tapStage=" + event.tapStageX + "," + event.tapStageY +
+                  " target=" + event.target.name);
+        }
+    }
+}
@@ -0,0 +1,48 @@
+package
+{
+    import flash.display.Sprite;
+
+    public class Test extends Sprite
+    {
+    }
+}
+
+import flash.events.NetStatusEvent;
+import flash.net.NetConnection;
+import flash.net.Responder;
+
+// To run against a real player, serve socket.json with `mocket --no-policy`.
+var connection:NetConnection = new NetConnection();
+
+connection.client = {
+    serverHello: function(message:String):void
+    {
+        trace("client.serverHello: " + message);
+    }
+};
+
+connection.addEventListener(NetStatusEvent.NET_STATUS, function(event:NetStatusEvent):void
+{
+    trace("netStatus: " + event.info.code + " (" + event.info.level + ")");
+    trace("connection.connected = " + connection.connected);
+
+    if (event.info.code == "NetConnection.Connect.Success") {
+        connection.call("add", new Responder(onResult, onStatus), 2, 3);
+        connection.call("fail", new Responder(onResult, onStatus));
+    }
+});
+
+function onResult(result:*):void
+{
+    trace("Responder result: " + result);
+}
+
+function onStatus(info:Object):void
+{
+    trace("Responder status: " + info.code + " (" + info.level + ")");
+    connection.close();
+}
+
+trace("Before connect: connection.connected = " + connection.connected);
+connection.connect("rtmp://localhost:8001/app", "user");
+trace("After connect: connection.connected = " + connection.connected);
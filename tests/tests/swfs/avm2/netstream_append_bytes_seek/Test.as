@@ -0,0 +1,77 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.events.NetStatusEvent;
+	import flash.media.Video;
+	import flash.net.NetConnection;
+	import flash.net.NetStream;
+	import flash.net.NetStreamAppendBytesAction;
+	import flash.net.URLLoader;
+	import flash.net.URLLoaderDataFormat;
+	import flash.net.URLRequest;
+	import flash.utils.ByteArray;
+
+	public class Test extends MovieClip {
+		// Offsets of tags in test_video.flv.
+		// The keyframe at 4000ms, without the preceding back pointer.
+		private static const SEEK_TAG:uint = 47969;
+		// The end of the keyframe at 667ms.
+		private static const FIRST_PART_END:uint = 16824;
+
+		private var data:ByteArray;
+		private var ns:NetStream;
+		private var frames:int = 0;
+
+		public function Test() {
+			var loader:URLLoader = new URLLoader();
+			loader.dataFormat = URLLoaderDataFormat.BINARY;
+			loader.addEventListener(Event.COMPLETE, onLoaded);
+			loader.load(new URLRequest("test_video.flv"));
+		}
+
+		private function onLoaded(event:Event):void {
+			data = event.target.data;
+
+			var nc:NetConnection = new NetConnection();
+			nc.connect(null);
+			ns = new NetStream(nc);
+			ns.client = {};
+			ns.addEventListener(NetStatusEvent.NET_STATUS, onStatus);
+
+			var video:Video = new Video();
+			video.attachNetStream(ns);
+			addChild(video);
+
+			ns.play(null);
+			ns.appendBytesAction(NetStreamAppendBytesAction.RESET_BEGIN);
+			appendRange(0, FIRST_PART_END);
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function appendRange(start:uint, end:uint):void {
+			var bytes:ByteArray = new ByteArray();
+			bytes.writeBytes(data, start, end - start);
+			ns.appendBytes(bytes);
+		}
+
+		private function onStatus(event:NetStatusEvent):void {
+			trace("netStatus: " + event.info.code);
+			if (event.info.code == "NetStream.Seek.Notify") {
+				ns.appendBytesAction(NetStreamAppendBytesAction.RESET_SEEK);
+				appendRange(SEEK_TAG, data.length);
+			}
+		}
+
+		private function onEnterFrame(event:Event):void {
+			frames++;
+			if (frames == 10) {
+				trace("before seek: " + (ns.time > 0 && ns.time < 1));
+				trace("// ns.seek(4)");
+				ns.seek(4);
+			} else if (frames == 20) {
+				trace("after seek: " + (ns.time > 4.2 && ns.time < 5));
+				removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+			}
+		}
+	}
+}
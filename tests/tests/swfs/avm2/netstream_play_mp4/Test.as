@@ -0,0 +1,82 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.events.NetStatusEvent;
+	import flash.media.Video;
+	import flash.net.NetConnection;
+	import flash.net.NetStream;
+
+	public class Test extends MovieClip {
+		// Both videos are one second long, with keyframes every half second.
+		// The first one is a regular MP4 with the `moov` box at the end, and
+		// the second one is a fragmented MP4 with a fragment for each keyframe.
+		private static const VIDEOS:Array = ["test.mp4", "test_fragmented.mp4"];
+
+		private var ns:NetStream;
+		private var video:Video;
+		private var current:int = -1;
+		private var frames:int = 0;
+		private var stopped:Boolean = false;
+
+		public function Test() {
+			video = new Video();
+			addChild(video);
+			playNext();
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function playNext():void {
+			current++;
+			frames = 0;
+			stopped = false;
+			if (current >= VIDEOS.length) {
+				removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+				return;
+			}
+
+			trace("// play " + VIDEOS[current]);
+			var nc:NetConnection = new NetConnection();
+			nc.connect(null);
+			ns = new NetStream(nc);
+			ns.client = {onMetaData: onMetaData};
+			ns.addEventListener(NetStatusEvent.NET_STATUS, onStatus);
+			video.attachNetStream(ns);
+			ns.play(VIDEOS[current]);
+		}
+
+		private function onMetaData(info:Object):void {
+			trace("onMetaData");
+			trace("duration: " + info.duration);
+			trace("width: " + info.width);
+			trace("height: " + info.height);
+			trace("videocodecid: " + info.videocodecid);
+			trace("videoframerate: " + info.videoframerate);
+			trace("audiocodecid: " + info.audiocodecid);
+			trace("audiosamplerate: " + info.audiosamplerate);
+			trace("audiochannels: " + info.audiochannels);
+			trace("trackinfo.length: " + info.trackinfo.length);
+		}
+
+		private function onStatus(event:NetStatusEvent):void {
+			trace("netStatus: " + event.info.code);
+			if (event.info.code == "NetStream.Seek.Complete") {
+				// The seek snaps back to the keyframe at 0.5 seconds.
+				trace("time after seek: " + ns.time);
+			} else if (event.info.code == "NetStream.Play.Stop") {
+				stopped = true;
+			}
+		}
+
+		private function onEnterFrame(event:Event):void {
+			frames++;
+			if (stopped) {
+				ns.close();
+				playNext();
+			} else if (frames == 5) {
+				trace("playing: " + (ns.time > 0 && ns.time < 0.5));
+				trace("// ns.seek(0.7)");
+				ns.seek(0.7);
+			}
+		}
+	}
+}
@@ -0,0 +1,140 @@
+"""Generate the MP4 files for this test.
+
+Usage: python3 generate_videos.py ../netstream_play_flv/noise.mp3 .
+"""
+import struct, sys
+
+def box(kind, *parts):
+    data = b"".join(parts)
+    return struct.pack(">I", len(data) + 8) + kind + data
+
+def full_box(kind, *parts, version=0, flags=0):
+    return box(kind, struct.pack(">I", (version << 24) | flags), *parts)
+
+def u32s(*values):
+    return b"".join(struct.pack(">I", v) for v in values)
+
+# MP3 frames from the FLV test's noise.
+mp3 = open(sys.argv[1], "rb").read()
+pos = 10 + ((mp3[6] << 21) | (mp3[7] << 14) | (mp3[8] << 7) | mp3[9])
+frames = []
+bitrates = [0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320]
+rates = [44100, 48000, 32000]
+while pos + 4 <= len(mp3):
+    h = mp3[pos:pos + 4]
+    if h[0] != 0xFF or (h[1] & 0xE0) != 0xE0:
+        break
+    bitrate = bitrates[h[2] >> 4] * 1000
+    rate = rates[(h[2] >> 2) & 3]
+    padding = (h[2] >> 1) & 1
+    length = 144 * bitrate // rate + padding
+    frames.append(mp3[pos:pos + length])
+    pos += length
+channels = 1 if (frames[0][3] >> 6) == 3 else 2
+sample_rate = rates[(frames[0][2] >> 2) & 3]
+
+VIDEO_FPS = 10
+DURATION = 1
+video = []
+for i in range(VIDEO_FPS * DURATION):
+    # A fake length-prefixed NAL unit; keyframes every half second.
+    sync = i % 5 == 0
+    nal = bytes([0x65 if sync else 0x41]) + bytes([i]) * (40 if sync else 10)
+    video.append((struct.pack(">I", len(nal)) + nal, sync))
+num_audio = (sample_rate * DURATION + 1151) // 1152
+audio = [frames[i % len(frames)] for i in range(num_audio)]
+
+avcc = box(b"avcC", bytes([1, 0x42, 0, 0x1E, 0xFF, 0xE1, 0, 4, 0x67, 0x42, 0, 0x1E, 1, 0, 2, 0x68, 0xCE]))
+avc1 = box(b"avc1", bytes(6), struct.pack(">H", 1), bytes(16), struct.pack(">HH", 160, 120),
+           u32s(0x480000, 0x480000, 0), struct.pack(">H", 1), bytes(32), struct.pack(">Hh", 24, -1), avcc)
+mp3_entry = box(b".mp3", bytes(6), struct.pack(">H", 1), bytes(8),
+                struct.pack(">HHHH", channels, 16, 0, 0), u32s(sample_rate << 16))
+
+def trak(track_id, handler, entry, timescale, duration, tables, width=0, height=0):
+    tkhd = full_box(b"tkhd", u32s(0, 0, track_id, 0, duration * 1000 // timescale), bytes(8),
+                    bytes(8), u32s(0x10000, 0, 0, 0, 0x10000, 0, 0, 0, 0x40000000),
+                    u32s(width << 16, height << 16), flags=3)
+    mdhd = full_box(b"mdhd", u32s(0, 0, timescale, duration), struct.pack(">HH", 0x55C4, 0))
+    hdlr = full_box(b"hdlr", u32s(0), handler, bytes(12), b"\0")
+    stsd = full_box(b"stsd", u32s(1), entry)
+    stbl = box(b"stbl", stsd, *tables)
+    minf = box(b"minf", stbl)
+    return box(b"trak", tkhd, box(b"mdia", mdhd, hdlr, minf))
+
+VIDEO_TIMESCALE = 1000
+VIDEO_DELTA = VIDEO_TIMESCALE // VIDEO_FPS
+AUDIO_DELTA = 1152
+ftyp = box(b"ftyp", b"isom", u32s(0x200), b"isomiso2avc1mp41")
+mvhd = full_box(b"mvhd", u32s(0, 0, 1000, DURATION * 1000), u32s(0x10000), struct.pack(">H", 0x100),
+                bytes(10), u32s(0x10000, 0, 0, 0, 0x10000, 0, 0, 0, 0x40000000), bytes(24), u32s(3))
+
+def plain():
+    # Interleave the samples in half second chunks, with the `moov` at the end.
+    chunks = []
+    for start in range(0, DURATION * 2):
+        v = video[start * 5:(start + 1) * 5]
+        a = audio[start * num_audio // (DURATION * 2):(start + 1) * num_audio // (DURATION * 2)]
+        chunks.append((v, a))
+    mdat_start = len(ftyp)
+    payload = b""
+    video_offsets, audio_offsets = [], []
+    for v, a in chunks:
+        video_offsets.append(mdat_start + 8 + len(payload))
+        payload += b"".join(s for s, _ in v)
+        audio_offsets.append(mdat_start + 8 + len(payload))
+        payload += b"".join(a)
+    mdat = box(b"mdat", payload)
+    vtables = [
+        full_box(b"stts", u32s(1, len(video), VIDEO_DELTA)),
+        full_box(b"stss", u32s(sum(1 for _, s in video if s), *[i + 1 for i, (_, s) in enumerate(video) if s])),
+        full_box(b"stsz", u32s(0, len(video), *[len(s) for s, _ in video])),
+        full_box(b"stsc", u32s(1, 1, 5, 1)),
+        full_box(b"stco", u32s(len(video_offsets), *video_offsets)),
+    ]
+    atables = [
+        full_box(b"stts", u32s(1, len(audio), AUDIO_DELTA)),
+        full_box(b"stsz", u32s(0, len(audio), *[len(f) for f in audio])),
+        full_box(b"stsc", u32s(len(chunks), *sum([[i + 1, len(a), 1] for i, (_, a) in enumerate(chunks)], []))),
+        full_box(b"stco", u32s(len(audio_offsets), *audio_offsets)),
+    ]
+    moov = box(b"moov", mvhd,
+               trak(1, b"vide", avc1, VIDEO_TIMESCALE, len(video) * VIDEO_DELTA, vtables, 160, 120),
+               trak(2, b"soun", mp3_entry, sample_rate, len(audio) * AUDIO_DELTA, atables))
+    return ftyp + mdat + moov
+
+def fragmented():
+    empty = [full_box(b"stts", u32s(0)), full_box(b"stsz", u32s(0, 0)),
+             full_box(b"stsc", u32s(0)), full_box(b"stco", u32s(0))]
+    mvex = box(b"mvex", full_box(b"mehd", u32s(DURATION * 1000)),
+               full_box(b"trex", u32s(1, 1, VIDEO_DELTA, 0, 0x10000)),
+               full_box(b"trex", u32s(2, 1, AUDIO_DELTA, 0, 0)))
+    mvhd0 = mvhd[:24] + u32s(0) + mvhd[28:]
+    moov = box(b"moov", mvhd0,
+               trak(1, b"vide", avc1, VIDEO_TIMESCALE, 0, empty, 160, 120),
+               trak(2, b"soun", mp3_entry, sample_rate, 0, empty), mvex)
+    out = ftyp + moov
+    for seq in range(DURATION * 2):
+        v = video[seq * 5:(seq + 1) * 5]
+        a = audio[seq * num_audio // (DURATION * 2):(seq + 1) * num_audio // (DURATION * 2)]
+        a_start = seq * num_audio // (DURATION * 2)
+
+        def make_moof(video_offset, audio_offset):
+            vtraf = box(b"traf", full_box(b"tfhd", u32s(1), flags=0x20000),
+                        full_box(b"tfdt", u32s(seq * 5 * VIDEO_DELTA)),
+                        full_box(b"trun", u32s(len(v), video_offset, 0), u32s(*[len(s) for s, _ in v]),
+                                 flags=0x205))
+            atraf = box(b"traf", full_box(b"tfhd", u32s(2), flags=0x20000),
+                        full_box(b"tfdt", u32s(a_start * AUDIO_DELTA)),
+                        full_box(b"trun", u32s(len(a), audio_offset), u32s(*[len(f) for f in a]),
+                                 flags=0x201))
+            return box(b"moof", full_box(b"mfhd", u32s(seq + 1)), vtraf, atraf)
+
+        moof_len = len(make_moof(0, 0))
+        video_data = b"".join(s for s, _ in v)
+        moof = make_moof(moof_len + 8, moof_len + 8 + len(video_data))
+        out += moof + box(b"mdat", video_data, b"".join(a))
+    return out
+
+open(sys.argv[2] + "/test.mp4", "wb").write(plain())
+open(sys.argv[2] + "/test_fragmented.mp4", "wb").write(fragmented())
+print(len(frames), channels, sample_rate, num_audio)
@@ -0,0 +1,69 @@
+package {
+	import flash.display.Loader;
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.geom.Rectangle;
+	import flash.net.URLRequest;
+	import flash.printing.PrintJob;
+	import flash.printing.PrintJobOptions;
+
+	public class Test extends Sprite {
+		private var loader:Loader = new Loader();
+
+		public function Test() {
+			// child.swf has a red square on its first frame, and a blue one on its second.
+			loader.contentLoaderInfo.addEventListener(Event.COMPLETE, onComplete);
+			loader.load(new URLRequest("child.swf"));
+		}
+
+		private function onComplete(event:Event):void {
+			var clip:MovieClip = loader.content as MovieClip;
+			clip.gotoAndStop(1);
+			clip.addFrameScript(1, function():void {
+				trace("frame 2 script");
+			});
+
+			trace("PrintJob.isSupported: " + PrintJob.isSupported);
+
+			trace("// job.start()");
+			var job:PrintJob = new PrintJob();
+			trace(job.start());
+			trace("// job.start() again");
+			trace(job.start());
+			trace("// job.addPage(clip, null, null, 2)");
+			job.addPage(clip, null, null, 2);
+			trace("// job.addPage(clip, new Rectangle(25, 25, 50, 50), new PrintJobOptions(true))");
+			job.addPage(clip, new Rectangle(25, 25, 50, 50), new PrintJobOptions(true));
+			trace("// job.send()");
+			job.send();
+			trace("clip.currentFrame: " + clip.currentFrame);
+
+			trace("// abandoned.start()");
+			var abandoned:PrintJob = new PrintJob();
+			trace(abandoned.start());
+			trace("// next.start()");
+			var next:PrintJob = new PrintJob();
+			trace(next.start());
+			trace("// abandoned.addPage(clip)");
+			try {
+				abandoned.addPage(clip);
+			} catch (e:Error) {
+				trace(e);
+			}
+			trace("// next.addPage(clip, null, null, 2)");
+			next.addPage(clip, null, null, 2);
+			trace("// abandoned.send()");
+			abandoned.send();
+			trace("// next.send()");
+			next.send();
+
+			trace("// job.addPage(clip) after send()");
+			try {
+				job.addPage(clip);
+			} catch (e:Error) {
+				trace(e);
+			}
+		}
+	}
+}
@@ -0,0 +1,94 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.sampler.*;
+	import flash.utils.ByteArray;
+
+	public class Test extends Sprite {
+		private var foo:Foo;
+		private var fooId:Number = -1;
+		private var fooSample:NewObjectSample;
+		private var frames:int = 0;
+
+		public function Test() {
+			trace("// getSize");
+			var small:ByteArray = new ByteArray();
+			var big:ByteArray = new ByteArray();
+			big.length = 10000;
+			trace("empty ByteArray > 0: " + (getSize(small) > 0));
+			trace("10000 byte ByteArray > 10000: " + (getSize(big) > 10000));
+			trace("10000 byte ByteArray > empty ByteArray: " + (getSize(big) > getSize(small)));
+
+			startSampling();
+			foo = makeFoo();
+			var other:Foo = makeFoo();
+			pauseSampling();
+
+			trace("// getSamples after allocating two Foos");
+			var fooSamples:Array = [];
+			for each (var sample:Sample in getSamples()) {
+				var newSample:NewObjectSample = sample as NewObjectSample;
+				if (newSample && newSample.type == Foo) {
+					fooSamples.push(newSample);
+				}
+			}
+			trace("Foo samples: " + fooSamples.length);
+			for each (newSample in fooSamples) {
+				trace("stack: " + newSample.stack.join(", "));
+				trace("size > 0: " + (newSample.size > 0));
+				trace("size == getSize(object): " + (newSample.size == getSize(newSample.object)));
+			}
+			fooSample = fooSamples[0];
+			fooId = fooSample.id;
+			trace("first sample is foo: " + (fooSample.object === foo));
+			trace("second sample is other: " + (fooSamples[1].object === other));
+			trace("ids differ: " + (fooSamples[0].id != fooSamples[1].id));
+
+			startSampling();
+			foo = null;
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function makeFoo():Foo {
+			return new Foo();
+		}
+
+		private function onEnterFrame(event:Event):void {
+			frames++;
+
+			// Make enough garbage for the collector to run, without sampling it.
+			pauseSampling();
+			for (var i:int = 0; i < 20000; i++) {
+				var garbage:Object = {a: i, b: [i, i, i]};
+			}
+
+			var deletes:int = 0;
+			var deleteSample:DeleteObjectSample;
+			for each (var sample:Sample in getSamples()) {
+				if (sample is DeleteObjectSample && DeleteObjectSample(sample).id == fooId) {
+					deletes++;
+					deleteSample = DeleteObjectSample(sample);
+				}
+			}
+			startSampling();
+
+			if (deletes > 0) {
+				removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+				trace("// foo was collected");
+				trace("delete samples for foo: " + deletes);
+				trace("delete size == new size: " + (deleteSample.size == fooSample.size));
+				trace("fooSample.object: " + fooSample.object);
+				trace("fooSample.size > 0: " + (fooSample.size > 0));
+				stopSampling();
+				trace("samples after stopSampling: " + getSampleCount());
+			} else if (frames == 50) {
+				trace("foo was never collected");
+			}
+		}
+	}
+}
+
+class Foo {
+	public var a:int = 1;
+	public var b:String = "foo";
+}
@@ -0,0 +1,74 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.media.Sound;
+	import flash.net.URLRequest;
+	import flash.utils.ByteArray;
+
+	public class Test extends Sprite {
+		private var sound:Sound = new Sound();
+
+		public function Test() {
+			sound.addEventListener(Event.COMPLETE, onComplete);
+			sound.load(new URLRequest("noise.mp3"));
+		}
+
+		private function onComplete(e:Event):void {
+			var frames:int = Math.floor(sound.length * 44.1);
+
+			trace("// extract(first, 4096, 0)");
+			var first:ByteArray = new ByteArray();
+			trace(sound.extract(first, 4096, 0));
+			trace("first.length: " + first.length);
+
+			trace("// extract(next, 4096)");
+			var next:ByteArray = new ByteArray();
+			trace(sound.extract(next, 4096));
+
+			trace("// extract(middle, 4096, 2048)");
+			var middle:ByteArray = new ByteArray();
+			trace(sound.extract(middle, 4096, 2048));
+			trace("matches first and next: " + (sameSamples(middle, 0, first, 2048 * 8, 2048) && sameSamples(middle, 2048 * 8, next, 0, 2048)));
+			trace("not silent: " + !isSilent(middle));
+
+			trace("// extract(rest, 8192) until the end");
+			var total:int = 0;
+			var rest:ByteArray = new ByteArray();
+			var extracted:Number = sound.extract(rest, 8192, 0);
+			while (extracted > 0) {
+				total += extracted;
+				extracted = sound.extract(rest, 8192);
+			}
+			trace("total == length: " + (Math.abs(total - frames) <= 1));
+			trace("rest.length == total * 8: " + (rest.length == total * 8));
+			trace("starts with first: " + sameSamples(rest, 0, first, 0, 4096));
+
+			trace("// extract(end, 4096, total - 10)");
+			var end:ByteArray = new ByteArray();
+			trace(sound.extract(end, 4096, total - 10));
+			trace("// extract(past, 4096, total + 10)");
+			trace(sound.extract(new ByteArray(), 4096, total + 10));
+		}
+
+		private static function sameSamples(a:ByteArray, aOffset:int, b:ByteArray, bOffset:int, frames:int):Boolean {
+			for (var i:int = 0; i < frames * 2; i++) {
+				a.position = aOffset + i * 4;
+				b.position = bOffset + i * 4;
+				if (a.readFloat() != b.readFloat()) {
+					return false;
+				}
+			}
+			return true;
+		}
+
+		private static function isSilent(a:ByteArray):Boolean {
+			a.position = 0;
+			while (a.bytesAvailable > 0) {
+				if (a.readFloat() != 0) {
+					return false;
+				}
+			}
+			return true;
+		}
+	}
+}
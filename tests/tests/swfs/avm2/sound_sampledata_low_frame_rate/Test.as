@@ -0,0 +1,40 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.events.SampleDataEvent;
+	import flash.media.Sound;
+	import flash.media.SoundChannel;
+
+	// Runs at 2 FPS, so each frame plays 22050 sample frames of the sound.
+	public class Test extends Sprite {
+		private var frame:int = 0;
+		private var events:int = 0;
+
+		public function Test() {
+			var sound:Sound = new Sound();
+			sound.addEventListener(SampleDataEvent.SAMPLE_DATA, onSampleData);
+			var channel:SoundChannel = sound.play();
+			channel.addEventListener(Event.SOUND_COMPLETE, function(e:Event):void {
+				trace("soundComplete");
+			});
+
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onEnterFrame(e:Event):void {
+			frame++;
+			trace("// frame " + frame);
+		}
+
+		// Provides 8192 sample frames per event, and ends the sound on the eleventh event.
+		private function onSampleData(event:SampleDataEvent):void {
+			events++;
+			var count:int = events < 11 ? 8192 : 0;
+			trace("sampleData at " + event.position + ", writing " + count);
+			for (var i:int = 0; i < count; i++) {
+				event.data.writeFloat(0.25);
+				event.data.writeFloat(-0.25);
+			}
+		}
+	}
+}
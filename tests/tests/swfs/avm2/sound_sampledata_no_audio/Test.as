@@ -0,0 +1,60 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.events.SampleDataEvent;
+	import flash.media.Sound;
+	import flash.media.SoundChannel;
+
+	public class Test extends Sprite {
+		private var frame:int = 0;
+		private var eventsA:int = 0;
+		private var eventsB:int = 0;
+
+		public function Test() {
+			var a:Sound = new Sound();
+			a.addEventListener(SampleDataEvent.SAMPLE_DATA, onSampleDataA);
+			var b:Sound = new Sound();
+			b.addEventListener(SampleDataEvent.SAMPLE_DATA, onSampleDataB);
+
+			var channelA:SoundChannel = a.play();
+			var channelB:SoundChannel = b.play();
+			trace("channels differ: " + (channelA != channelB));
+			channelA.addEventListener(Event.SOUND_COMPLETE, function(e:Event):void {
+				trace("a: soundComplete");
+			});
+			channelB.addEventListener(Event.SOUND_COMPLETE, function(e:Event):void {
+				trace("b: soundComplete");
+			});
+
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onEnterFrame(e:Event):void {
+			frame++;
+			trace("// frame " + frame);
+		}
+
+		private static function writeSamples(event:SampleDataEvent, count:int):void {
+			for (var i:int = 0; i < count; i++) {
+				event.data.writeFloat(0.25);
+				event.data.writeFloat(-0.25);
+			}
+		}
+
+		// Provides 2048 sample frames per event, and ends the sound on the sixth event.
+		private function onSampleDataA(event:SampleDataEvent):void {
+			eventsA++;
+			var count:int = eventsA < 6 ? 2048 : 100;
+			trace("a: sampleData at " + event.position + ", writing " + count);
+			writeSamples(event, count);
+		}
+
+		// Provides 3072 sample frames per event, and ends the sound on the fourth event.
+		private function onSampleDataB(event:SampleDataEvent):void {
+			eventsB++;
+			var count:int = eventsB < 4 ? 3072 : 0;
+			trace("b: sampleData at " + event.position + ", writing " + count);
+			writeSamples(event, count);
+		}
+	}
+}
@@ -0,0 +1,61 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.Shape;
+	import flash.display.Sprite;
+	import flash.text.engine.ContentElement;
+	import flash.text.engine.ElementFormat;
+	import flash.text.engine.FontDescription;
+	import flash.text.engine.GraphicElement;
+	import flash.text.engine.GroupElement;
+	import flash.text.engine.TextBlock;
+	import flash.text.engine.TextElement;
+	import flash.text.engine.TextLine;
+
+	[SWF(width="200", height="100")]
+	public class Test extends Sprite {
+		public function Test() {
+			var format:ElementFormat = new ElementFormat(new FontDescription("Tinos"), 20);
+
+			var graphic:Shape = new Shape();
+			graphic.graphics.beginFill(0xFF0000);
+			graphic.graphics.drawRect(0, 0, 30, 40);
+			graphic.graphics.endFill();
+
+			var elements:Vector.<ContentElement> = new Vector.<ContentElement>();
+			elements.push(new TextElement("ab", format));
+			elements.push(new GraphicElement(graphic, 30, 40, format));
+			elements.push(new TextElement("cd", format));
+			var block:TextBlock = new TextBlock(new GroupElement(elements));
+			var line:TextLine = block.createTextLine(null, 200);
+			line.x = 10;
+			line.y = 60;
+			addChild(line);
+
+			var graphicX:Number = line.x + line.getAtomBounds(2).x;
+			trace("// With a graphic");
+			sample(graphicX + 15, line.y - 20);
+			sample(graphicX + 15, line.y - 45);
+			sample(graphicX + 40, line.y + 10);
+
+			trace("// Moving the line moves the graphic");
+			line.x += 50;
+			sample(graphicX + 15, line.y - 20);
+			sample(graphicX + 65, line.y - 20);
+
+			trace("// Recreated without a graphic");
+			block.content = new TextElement("abcd", format);
+			block.recreateTextLine(line, null, 200);
+			sample(graphicX + 65, line.y - 20);
+		}
+
+		private function sample(x:Number, y:Number):void {
+			var bitmapData:BitmapData = new BitmapData(200, 100, false, 0xFFFFFF);
+			bitmapData.draw(this);
+			var color:String = bitmapData.getPixel(x, y).toString(16);
+			while (color.length < 6) {
+				color = "0" + color;
+			}
+			trace("(" + Math.round(x) + ", " + Math.round(y) + "): " + color);
+		}
+	}
+}
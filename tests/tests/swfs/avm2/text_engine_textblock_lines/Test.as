@@ -0,0 +1,138 @@
+package {
+	import flash.display.Shape;
+	import flash.display.Sprite;
+	import flash.geom.Rectangle;
+	import flash.text.engine.BreakOpportunity;
+	import flash.text.engine.ContentElement;
+	import flash.text.engine.ElementFormat;
+	import flash.text.engine.FontDescription;
+	import flash.text.engine.GraphicElement;
+	import flash.text.engine.GroupElement;
+	import flash.text.engine.TextBlock;
+	import flash.text.engine.TextElement;
+	import flash.text.engine.TextLine;
+
+	public class Test extends Sprite {
+		public function Test() {
+			trace("// Breaking at word boundaries");
+			var block:TextBlock = new TextBlock(new TextElement("The quick brown fox jumps over the lazy dog", format()));
+			createLines(block, 150);
+
+			trace("// A wider line");
+			createLines(block, 300);
+
+			trace("// A mandatory break");
+			block = new TextBlock(new TextElement("First\u2028Second", format()));
+			createLines(block, 300);
+
+			trace("// Breaking anywhere");
+			block = new TextBlock(new TextElement("abcdefghijklmnopqrstuvwxyz", format(BreakOpportunity.ANY)));
+			createLines(block, 100);
+
+			trace("// Breaking everywhere");
+			block = new TextBlock(new TextElement("abc", format(BreakOpportunity.ALL)));
+			createLines(block, 300);
+
+			trace("// A word that is too long");
+			block = new TextBlock(new TextElement("Supercalifragilistic word", format()));
+			createLines(block, 60);
+
+			trace("// Not even a character fits");
+			block = new TextBlock(new TextElement("Supercalifragilistic", format()));
+			var line:TextLine = block.createTextLine(null, 5, 0, false);
+			trace("without fitSomething: " + line + ", result: " + block.textLineCreationResult);
+			line = block.createTextLine(null, 5, 0, true);
+			trace("with fitSomething: " + describe(block, line) + ", result: " + block.textLineCreationResult);
+
+			trace("// Widths");
+			block = new TextBlock(new TextElement("Hello", format()));
+			line = block.createTextLine(null, 300);
+			var shortWidth:Number = line.textWidth;
+			block = new TextBlock(new TextElement("Hello Hello", format()));
+			line = block.createTextLine(null, 300);
+			trace("twice as long is wider: " + (line.textWidth > shortWidth * 2));
+			trace("textWidth == unjustifiedTextWidth: " + (line.textWidth == line.unjustifiedTextWidth));
+			trace("specifiedWidth: " + line.specifiedWidth);
+			var previousRight:Number = -1;
+			var increasing:Boolean = true;
+			for (var i:int = 0; i < line.atomCount; i++) {
+				var bounds:Rectangle = line.getAtomBounds(i);
+				if (bounds.x < previousRight - 0.01) {
+					increasing = false;
+				}
+				previousRight = bounds.right;
+			}
+			trace("atoms are in order: " + increasing);
+			trace("last atom ends at the text width: " + (Math.abs(previousRight - line.textWidth) < 1));
+
+			trace("// recreateTextLine");
+			block = new TextBlock(new TextElement("The quick brown fox jumps over the lazy dog", format()));
+			var first:TextLine = block.createTextLine(null, 150);
+			var second:TextLine = block.createTextLine(first, 150);
+			var third:TextLine = block.createTextLine(second, 150);
+			var recreated:TextLine = block.recreateTextLine(second, first, 300);
+			trace("same object: " + (recreated === second));
+			trace("recreated: " + describe(block, recreated));
+			trace("validity of the line after it: " + third.validity);
+			trace("textBlock of the line after it: " + third.textBlock);
+			trace("lastLine is the recreated line: " + (block.lastLine === recreated));
+			trace("nextLine of the first line: " + (first.nextLine === recreated));
+			var next:TextLine = block.createTextLine(recreated, 300);
+			trace("next: " + describe(block, next));
+			trace("result: " + block.textLineCreationResult);
+			var reused:TextLine = block.recreateTextLine(third, null, 100);
+			trace("reusing an invalid line: " + (reused === third) + " " + describe(block, reused));
+			trace("validity of the lines after it: " + first.validity + " " + recreated.validity);
+
+			trace("// releaseLines");
+			block.releaseLines(block.firstLine, block.lastLine);
+			trace("firstLine: " + block.firstLine + ", lastLine: " + block.lastLine);
+			trace("validity: " + first.validity);
+
+			trace("// GraphicElement");
+			var graphic:Shape = new Shape();
+			var elements:Vector.<ContentElement> = new Vector.<ContentElement>();
+			elements.push(new TextElement("ab", format()));
+			elements.push(new GraphicElement(graphic, 30, 40, format()));
+			elements.push(new TextElement("cd", format()));
+			block = new TextBlock(new GroupElement(elements));
+			line = block.createTextLine(null, 300);
+			trace("rawTextLength: " + line.rawTextLength);
+			trace("hasGraphicElement: " + line.hasGraphicElement);
+			trace("getAtomGraphic(2) is the graphic: " + (line.getAtomGraphic(2) === graphic));
+			trace("getAtomGraphic(1): " + line.getAtomGraphic(1));
+			bounds = line.getAtomBounds(2);
+			trace("graphic atom width: " + bounds.width);
+			trace("ascent fits the graphic: " + (line.ascent >= 40));
+			trace("graphic x is at its atom: " + (Math.abs(graphic.x - bounds.x) < 0.1));
+			trace("graphic y: " + graphic.y);
+		}
+
+		private static function format(breakOpportunity:String = "auto"):ElementFormat {
+			var format:ElementFormat = new ElementFormat(new FontDescription("Tinos"), 20);
+			format.breakOpportunity = breakOpportunity;
+			return format;
+		}
+
+		private static function describe(block:TextBlock, line:TextLine):String {
+			if (line == null) {
+				return "null";
+			}
+			var text:String = block.content.rawText.substr(line.textBlockBeginIndex, line.rawTextLength);
+			text = text.split("\u2028").join("\\u2028");
+			return "[" + line.textBlockBeginIndex + ", " + line.rawTextLength + "] \"" + text + "\" " + line.validity;
+		}
+
+		private static function createLines(block:TextBlock, width:Number):void {
+			var line:TextLine = null;
+			while (true) {
+				line = block.createTextLine(line, width);
+				if (line == null) {
+					break;
+				}
+				trace(describe(block, line) + " (" + block.textLineCreationResult + ")");
+			}
+			trace("result: " + block.textLineCreationResult);
+		}
+	}
+}
@@ -0,0 +1,138 @@
+package {
+    import flash.concurrent.Condition;
+    import flash.concurrent.Mutex;
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.system.MessageChannel;
+    import flash.system.Worker;
+    import flash.system.WorkerDomain;
+    import flash.utils.ByteArray;
+
+    public class Test extends Sprite {
+        private var worker:Worker;
+        private var toWorker:MessageChannel;
+        private var fromWorker:MessageChannel;
+        private var bytes:ByteArray;
+        private var mutex:Mutex;
+        private var condition:Condition;
+        private var frame:int = 0;
+
+        public function Test() {
+            if (Worker.current.isPrimordial) {
+                startPrimordial();
+            } else {
+                startBackground();
+            }
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+
+        private function startPrimordial():void {
+            worker = WorkerDomain.current.createWorker(loaderInfo.bytes);
+            toWorker = Worker.current.createMessageChannel(worker);
+            fromWorker = worker.createMessageChannel(Worker.current);
+
+            bytes = new ByteArray();
+            bytes.shareable = true;
+            bytes.length = 4;
+
+            mutex = new Mutex();
+            condition = new Condition(mutex);
+
+            worker.setSharedProperty("toWorker", toWorker);
+            worker.setSharedProperty("fromWorker", fromWorker);
+            worker.setSharedProperty("bytes", bytes);
+            worker.setSharedProperty("mutex", mutex);
+            worker.setSharedProperty("condition", condition);
+            worker.start();
+            trace("primordial: started worker");
+        }
+
+        private function startBackground():void {
+            var current:Worker = Worker.current;
+            toWorker = current.getSharedProperty("toWorker");
+            fromWorker = current.getSharedProperty("fromWorker");
+            bytes = current.getSharedProperty("bytes");
+            mutex = current.getSharedProperty("mutex");
+            condition = current.getSharedProperty("condition");
+
+            trace("worker: running, same mutex: " + (condition.mutex == mutex));
+            trace("worker: tryLock " + mutex.tryLock());
+            bytes[0] = 42;
+            fromWorker.send("hello from worker");
+            toWorker.addEventListener(Event.CHANNEL_MESSAGE, onWorkerMessage);
+        }
+
+        private function onWorkerMessage(event:Event):void {
+            var message:* = toWorker.receive();
+            trace("worker: received " + message);
+            if (message == "wait") {
+                mutex.lock();
+                bytes[2] = 99;
+                condition.notify();
+                mutex.unlock();
+                trace("worker: notified");
+            } else if (message == "reply") {
+                fromWorker.send("reply from worker");
+            }
+        }
+
+        private function onEnterFrame(event:Event):void {
+            frame++;
+            if (Worker.current.isPrimordial) {
+                primordialFrame();
+            } else {
+                backgroundFrame();
+            }
+        }
+
+        private function backgroundFrame():void {
+            if (frame == 1) {
+                bytes[1] = 7;
+                mutex.unlock();
+                trace("worker: unlocked");
+            }
+        }
+
+        // A frame that has to wait for the worker stops there, and continues
+        // with the next frame once the wait is over.
+        private function primordialFrame():void {
+            switch (frame) {
+                case 1:
+                    trace("primordial: bytes[0] = " + bytes[0]);
+                    trace("primordial: tryLock " + mutex.tryLock());
+                    mutex.lock();
+                    break;
+                case 2:
+                    trace("primordial: locked, bytes[1] = " + bytes[1]);
+                    mutex.unlock();
+                    trace("primordial: received " + fromWorker.receive());
+                    mutex.lock();
+                    toWorker.send("wait");
+                    condition.wait();
+                    break;
+                case 3:
+                    trace("primordial: notified, bytes[2] = " + bytes[2]);
+                    mutex.unlock();
+                    toWorker.send("reply");
+                    fromWorker.receive(true);
+                    break;
+                case 4:
+                    // The message that was waited for is still queued.
+                    trace("primordial: received " + fromWorker.receive());
+                    mutex.lock();
+                    condition.wait(100);
+                    break;
+                case 5:
+                    // Throws if the mutex isn't owned again.
+                    mutex.unlock();
+                    trace("primordial: unlocked after timeout");
+                    break;
+                case 6:
+                    trace("primordial: terminate " + worker.terminate());
+                    trace("primordial: worker state " + worker.state);
+                    trace("primordial: channel state " + toWorker.state);
+                    break;
+            }
+        }
+    }
+}
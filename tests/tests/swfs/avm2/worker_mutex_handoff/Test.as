@@ -0,0 +1,120 @@
+package {
+    import flash.concurrent.Condition;
+    import flash.concurrent.Mutex;
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.system.MessageChannel;
+    import flash.system.Worker;
+    import flash.system.WorkerDomain;
+    import flash.utils.ByteArray;
+
+    public class Test extends Sprite {
+        private var worker:Worker;
+        private var toWorker:MessageChannel;
+        private var turns:ByteArray;
+        private var mutex:Mutex;
+        private var condition:Condition;
+        private var frame:int = 0;
+        private var locking:Boolean = false;
+
+        public function Test() {
+            if (Worker.current.isPrimordial) {
+                startPrimordial();
+            } else {
+                startBackground();
+            }
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+
+        private function startPrimordial():void {
+            worker = WorkerDomain.current.createWorker(loaderInfo.bytes);
+            toWorker = Worker.current.createMessageChannel(worker);
+
+            turns = new ByteArray();
+            turns.shareable = true;
+            turns.length = 1;
+
+            mutex = new Mutex();
+            condition = new Condition(mutex);
+
+            worker.setSharedProperty("toWorker", toWorker);
+            worker.setSharedProperty("turns", turns);
+            worker.setSharedProperty("condition", condition);
+            worker.start();
+            trace("primordial: started worker");
+        }
+
+        private function startBackground():void {
+            var current:Worker = Worker.current;
+            toWorker = current.getSharedProperty("toWorker");
+            turns = current.getSharedProperty("turns");
+            condition = current.getSharedProperty("condition");
+            mutex = condition.mutex;
+
+            trace("worker: running");
+            toWorker.addEventListener(Event.CHANNEL_MESSAGE, onWorkerMessage);
+        }
+
+        private function onWorkerMessage(event:Event):void {
+            trace("worker: received " + toWorker.receive());
+            // The mutex is owned by the primordial worker, so this waits
+            // until the next frame after it's unlocked.
+            locking = true;
+            mutex.lock();
+        }
+
+        private function onEnterFrame(event:Event):void {
+            frame++;
+            if (Worker.current.isPrimordial) {
+                primordialFrame();
+            } else {
+                backgroundFrame();
+            }
+        }
+
+        private function backgroundFrame():void {
+            if (locking) {
+                locking = false;
+                trace("worker: locked");
+                mutex.unlock();
+                return;
+            }
+
+            // Take a turn whenever the primordial worker is waiting for one.
+            if (mutex.tryLock()) {
+                if (turns[0] % 2 == 1) {
+                    turns[0]++;
+                    trace("worker: turn " + turns[0]);
+                    condition.notify();
+                }
+                mutex.unlock();
+            }
+        }
+
+        // Waiting is always the last thing a frame does, and the mutex is
+        // owned again by the next frame.
+        private function primordialFrame():void {
+            switch (frame) {
+                case 1:
+                    mutex.lock();
+                case 2:
+                case 3:
+                    turns[0]++;
+                    trace("primordial: turn " + turns[0]);
+                    condition.wait();
+                    break;
+                case 4:
+                    trace("primordial: done after " + turns[0] + " turns");
+                    toWorker.send("lock");
+                    break;
+                case 5:
+                    mutex.unlock();
+                    trace("primordial: unlocked");
+                    break;
+                case 6:
+                    trace("primordial: terminate " + worker.terminate());
+                    break;
+            }
+        }
+    }
+}
@@ -0,0 +1,51 @@
+package {
+	import flash.display.Bitmap;
+	import flash.display.BitmapData;
+	import flash.display.Sprite;
+	import flash.filters.ConvolutionFilter;
+	import flash.geom.Point;
+	import flash.geom.Rectangle;
+
+	public class Test extends Sprite {
+		public function Test() {
+			var blur = [1, 1, 1, 1, 1, 1, 1, 1, 1];
+			var blur5 = [];
+			for (var i = 0; i < 25; i++) {
+				blur5.push(1);
+			}
+			var sharpen = [0, -1, 0, -1, 5, -1, 0, -1, 0];
+			var edges = [-1, -1, -1, -1, 8, -1, -1, -1, -1];
+
+			// Divisor and bias.
+			addFiltered(0, 0, new ConvolutionFilter(3, 3, sharpen, 1, 0, true, true));
+			addFiltered(1, 0, new ConvolutionFilter(3, 3, blur, 9, 0, true, true));
+			addFiltered(2, 0, new ConvolutionFilter(3, 3, blur, 4, 0, true, true));
+			addFiltered(3, 0, new ConvolutionFilter(3, 3, edges, 1, 128, true, true));
+
+			// Pixels outside of the source are either clamped, or the default color.
+			addFiltered(0, 1, new ConvolutionFilter(5, 5, blur5, 25, 0, false, false, 0x0000FF, 1));
+			addFiltered(1, 1, new ConvolutionFilter(5, 5, blur5, 25, 0, false, false, 0x0000FF, 0));
+			addFiltered(2, 1, new ConvolutionFilter(5, 5, blur5, 25, 0, false, true));
+
+			// An asymmetric matrix moves the image to the right.
+			addFiltered(3, 1, new ConvolutionFilter(3, 1, [1, 0, 0], 1, 0, false, true));
+		}
+
+		private function addFiltered(col:int, row:int, filter:ConvolutionFilter):void {
+			var data = new BitmapData(60, 60, true, 0);
+			data.fillRect(new Rectangle(0, 0, 30, 30), 0xFFFF0000);
+			data.fillRect(new Rectangle(30, 0, 30, 30), 0x8000FF00);
+			data.fillRect(new Rectangle(0, 30, 30, 30), 0xFFFFFFFF);
+			data.fillRect(new Rectangle(10, 40, 10, 10), 0xFF000000);
+			data.fillRect(new Rectangle(40, 40, 10, 10), 0xC0FFFF00);
+			data.applyFilter(data, data.rect, new Point(0, 0), filter);
+
+			var bitmap = new Bitmap(data);
+			bitmap.x = 10 + col * 135;
+			bitmap.y = 10 + row * 135;
+			bitmap.scaleX = 2;
+			bitmap.scaleY = 2;
+			addChild(bitmap);
+		}
+	}
+}
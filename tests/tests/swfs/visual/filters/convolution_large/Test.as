@@ -0,0 +1,41 @@
+package {
+	import flash.display.Bitmap;
+	import flash.display.BitmapData;
+	import flash.display.Sprite;
+	import flash.filters.ConvolutionFilter;
+	import flash.geom.Point;
+	import flash.geom.Rectangle;
+
+	public class Test extends Sprite {
+		public function Test() {
+			// Matrices with a single weight move the image, which shows that every
+			// entry up to the last one is read.
+			addFiltered(0, new ConvolutionFilter(40, 40, single(40, 40, 1599), 1, 0, true, false, 0x0000FF, 1));
+			addFiltered(1, new ConvolutionFilter(40, 40, single(40, 40, 0), 1, 0, true, false, 0x0000FF, 1));
+			addFiltered(2, new ConvolutionFilter(64, 1, single(64, 1, 63), 1, 0, true, true));
+			addFiltered(3, new ConvolutionFilter(1, 64, single(1, 64, 0), 1, 0, true, true));
+		}
+
+		private function single(matrixX:int, matrixY:int, index:int):Array {
+			var matrix = [];
+			for (var i = 0; i < matrixX * matrixY; i++) {
+				matrix.push(i == index ? 1 : 0);
+			}
+			return matrix;
+		}
+
+		private function addFiltered(col:int, filter:ConvolutionFilter):void {
+			var data = new BitmapData(60, 60, false, 0xFFFFFF);
+			data.fillRect(new Rectangle(0, 0, 30, 30), 0xFF0000);
+			data.fillRect(new Rectangle(30, 0, 30, 30), 0x00FF00);
+			data.fillRect(new Rectangle(10, 40, 10, 10), 0x000000);
+			data.fillRect(new Rectangle(40, 40, 10, 10), 0xFFFF00);
+			data.applyFilter(data, data.rect, new Point(0, 0), filter);
+
+			var bitmap = new Bitmap(data);
+			bitmap.x = 10 + col * 70;
+			bitmap.y = 10;
+			addChild(bitmap);
+		}
+	}
+}
@@ -0,0 +1,36 @@
+package {
+	import flash.display.Shape;
+	import flash.display.Sprite;
+	import flash.filters.BitmapFilterType;
+	import flash.filters.GradientGlowFilter;
+
+	public class Test extends Sprite {
+		public function Test() {
+			var types = [BitmapFilterType.OUTER, BitmapFilterType.INNER, BitmapFilterType.FULL];
+			for (var col = 0; col < types.length; col++) {
+				// Plain glows, then knockouts.
+				addShape(col, 0, new GradientGlowFilter(0, 45,
+					[0xFF0000, 0xFFFF00, 0x00FF00], [0, 0.5, 1], [0, 128, 255],
+					16, 16, 1, 1, types[col], false));
+				addShape(col, 1, new GradientGlowFilter(0, 45,
+					[0x0000FF, 0x00FFFF], [0, 1], [0, 255],
+					16, 16, 2, 1, types[col], true));
+				// Offset from the light source, with a higher quality blur.
+				addShape(col, 2, new GradientGlowFilter(8, 45,
+					[0xFF00FF, 0xFF00FF, 0x000000], [0, 1, 1], [0, 64, 255],
+					12, 12, 1, 3, types[col], false));
+			}
+		}
+
+		private function addShape(col:int, row:int, filter:GradientGlowFilter):void {
+			var shape = new Shape();
+			shape.graphics.beginFill(0x808080);
+			shape.graphics.drawRect(0, 0, 80, 60);
+			shape.graphics.endFill();
+			shape.x = 40 + col * 170;
+			shape.y = 30 + row * 120;
+			shape.filters = [filter];
+			addChild(shape);
+		}
+	}
+}